@@ -1,6 +1,6 @@
 //! Types for balance queries.
 use casper_hashing::Digest;
-use casper_types::{Key, StoredValue, URef, U512};
+use casper_types::{account::AccountHash, Key, StoredValue, URef, U512};
 
 use crate::storage::trie::merkle_proof::TrieMerkleProof;
 
@@ -62,3 +62,51 @@ impl BalanceRequest {
         self.purse_uref
     }
 }
+
+/// Identifies a purse whose balance is requested as part of a [`BalancesRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceIdentifier {
+    /// The purse identified by this URef.
+    Purse(URef),
+    /// The main purse of the account identified by this account hash.
+    Account(AccountHash),
+}
+
+/// Represents a request for the balances of several purses under a single state hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalancesRequest {
+    state_hash: Digest,
+    identifiers: Vec<BalanceIdentifier>,
+}
+
+impl BalancesRequest {
+    /// Creates a new [`BalancesRequest`].
+    pub fn new(state_hash: Digest, identifiers: Vec<BalanceIdentifier>) -> Self {
+        BalancesRequest {
+            state_hash,
+            identifiers,
+        }
+    }
+
+    /// Returns a state hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the identifiers of the purses to query.
+    pub fn identifiers(&self) -> &[BalanceIdentifier] {
+        &self.identifiers
+    }
+}
+
+/// Result enum that represents all possible outcomes of a balances request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BalancesResult {
+    /// Returned if a passed state root hash is not found.
+    RootNotFound,
+    /// The balances were queried.
+    ///
+    /// Holds one entry per requested identifier, in request order.  The balance is `None` if the
+    /// account or purse doesn't exist under the given state hash.
+    Success(Vec<(BalanceIdentifier, Option<U512>)>),
+}
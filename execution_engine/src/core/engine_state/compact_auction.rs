@@ -0,0 +1,158 @@
+//! Support for compacting the auction's records in global state.
+use std::collections::{BTreeMap, BTreeSet};
+
+use casper_hashing::Digest;
+use casper_types::{
+    system::auction::{Bid, UnbondingPurse},
+    Key, PublicKey,
+};
+
+/// The result of compacting the auction's records in global state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuctionCompactionResult {
+    /// Root not found.
+    RootNotFound,
+    /// Key does not exists.
+    DoesNotExist,
+    /// New state root hash generated after the stale records were deleted.
+    Success {
+        /// State root hash.
+        post_state_hash: Digest,
+        /// The keys which were deleted from global state.
+        reclaimed_keys: Vec<Key>,
+    },
+}
+
+/// Returns the keys of the auction records which no longer carry any information.
+///
+/// These are the emptied unbonding queues left behind once all their purses have been paid out, and
+/// the bids of validators which withdrew fully and have neither delegators nor pending unbonding
+/// purses referring to them, whether as the validator unbonded from or as the target of a
+/// redelegation.
+pub(crate) fn keys_to_compact(
+    bids: &BTreeMap<Key, Bid>,
+    unbonds: &BTreeMap<Key, Vec<UnbondingPurse>>,
+) -> Vec<Key> {
+    let referenced_validators: BTreeSet<&PublicKey> = unbonds
+        .values()
+        .flatten()
+        .flat_map(|unbonding_purse| {
+            Some(unbonding_purse.validator_public_key())
+                .into_iter()
+                .chain(unbonding_purse.new_validator().as_ref())
+        })
+        .collect();
+
+    let stale_unbonds = unbonds
+        .iter()
+        .filter(|(_, unbonding_purses)| unbonding_purses.is_empty())
+        .map(|(key, _)| *key);
+
+    let stale_bids = bids
+        .iter()
+        .filter(|(_, bid)| {
+            bid.inactive()
+                && bid.staked_amount().is_zero()
+                && bid.delegators().is_empty()
+                && !referenced_validators.contains(bid.validator_public_key())
+        })
+        .map(|(key, _)| *key);
+
+    stale_unbonds.chain(stale_bids).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{
+        account::AccountHash, system::auction::Delegator, AccessRights, EraId, SecretKey, URef,
+        U512,
+    };
+
+    use super::*;
+
+    fn public_key(seed: u8) -> PublicKey {
+        let secret_key = SecretKey::ed25519_from_bytes([seed; SecretKey::ED25519_LENGTH]).unwrap();
+        PublicKey::from(&secret_key)
+    }
+
+    fn withdrawn_bid(validator: &PublicKey) -> (Key, Bid) {
+        let bid = Bid::empty(validator.clone(), URef::default());
+        (Key::Bid(AccountHash::from(validator)), bid)
+    }
+
+    fn unbonding_purse(
+        validator: &PublicKey,
+        unbonder: &PublicKey,
+        new_validator: Option<PublicKey>,
+    ) -> UnbondingPurse {
+        UnbondingPurse::new(
+            URef::new([1; 32], AccessRights::READ_ADD_WRITE),
+            validator.clone(),
+            unbonder.clone(),
+            EraId::new(1),
+            U512::one(),
+            new_validator,
+        )
+    }
+
+    #[test]
+    fn should_compact_empty_unbonds_and_withdrawn_bids() {
+        let withdrawn = public_key(1);
+        let active = public_key(2);
+        let unbonder = public_key(3);
+
+        let mut bids = BTreeMap::new();
+        let (withdrawn_key, withdrawn_bid) = withdrawn_bid(&withdrawn);
+        bids.insert(withdrawn_key, withdrawn_bid);
+        bids.insert(
+            Key::Bid(AccountHash::from(&active)),
+            Bid::unlocked(active, URef::default(), U512::one(), 10),
+        );
+
+        let mut unbonds = BTreeMap::new();
+        let empty_unbond_key = Key::Unbond(AccountHash::from(&unbonder));
+        unbonds.insert(empty_unbond_key, vec![]);
+
+        assert_eq!(
+            keys_to_compact(&bids, &unbonds),
+            vec![empty_unbond_key, withdrawn_key]
+        );
+    }
+
+    #[test]
+    fn should_keep_withdrawn_bids_still_referenced() {
+        let withdrawn = public_key(1);
+        let redelegation_target = public_key(2);
+        let with_delegator = public_key(3);
+        let unbonder = public_key(4);
+
+        let mut bids = BTreeMap::new();
+        for validator in [&withdrawn, &redelegation_target] {
+            let (key, bid) = withdrawn_bid(validator);
+            bids.insert(key, bid);
+        }
+        let (key, mut bid) = withdrawn_bid(&with_delegator);
+        bid.delegators_mut().insert(
+            unbonder.clone(),
+            Delegator::unlocked(
+                unbonder.clone(),
+                U512::one(),
+                URef::default(),
+                with_delegator,
+            ),
+        );
+        bids.insert(key, bid);
+
+        let mut unbonds = BTreeMap::new();
+        unbonds.insert(
+            Key::Unbond(AccountHash::from(&withdrawn)),
+            vec![unbonding_purse(
+                &withdrawn,
+                &unbonder,
+                Some(redelegation_target),
+            )],
+        );
+
+        assert!(keys_to_compact(&bids, &unbonds).is_empty());
+    }
+}
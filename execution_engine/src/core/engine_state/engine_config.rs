@@ -1,7 +1,10 @@
 //! Support for runtime configuration of the execution engine - as an integral property of the
 //! `EngineState` instance.
+mod account_creation_policy;
+mod delegation_rate_change_limit;
 mod fee_handling;
 mod refund_handling;
+mod storage_rent;
 
 use std::collections::BTreeSet;
 
@@ -10,9 +13,17 @@ use num_traits::One;
 
 use casper_types::{account::AccountHash, PublicKey};
 
-use crate::shared::{system_config::SystemConfig, wasm_config::WasmConfig};
+use crate::shared::{
+    module_cache::{ModuleCache, DEFAULT_MODULE_CACHE_CAPACITY},
+    system_config::SystemConfig,
+    wasm_config::WasmConfig,
+};
 
-pub use self::{fee_handling::FeeHandling, refund_handling::RefundHandling};
+pub use self::{
+    account_creation_policy::AccountCreationPolicy,
+    delegation_rate_change_limit::DelegationRateChangeLimit, fee_handling::FeeHandling,
+    refund_handling::RefundHandling, storage_rent::StorageRent,
+};
 
 /// Default value for a maximum query depth configuration option.
 pub const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
@@ -26,8 +37,16 @@ pub const DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT: u32 = 12;
     note = "not used in `casper-execution-engine` config anymore"
 )]
 pub const DEFAULT_MAX_STORED_VALUE_SIZE: u32 = 8 * 1024 * 1024;
+/// Default value for maximum size in bytes of a single `CLValue` written to global state.
+pub const DEFAULT_MAX_CL_VALUE_SIZE: u32 = 8 * 1024 * 1024;
+/// Default value for maximum number of named keys of a single account or contract.
+pub const DEFAULT_MAX_NAMED_KEYS: u32 = 10_000;
 /// Default value for minimum delegation amount in motes.
 pub const DEFAULT_MINIMUM_DELEGATION_AMOUNT: u64 = 500 * 1_000_000_000;
+/// Default value for minimum bid amount in motes.
+pub const DEFAULT_MINIMUM_BID_AMOUNT: u64 = 0;
+/// Default value for recording auction snapshots.
+pub const DEFAULT_RECORD_AUCTION_SNAPSHOTS: bool = false;
 /// Default value for strict argument checking.
 pub const DEFAULT_STRICT_ARGUMENT_CHECKING: bool = false;
 /// 91 days / 7 days in a week = 13 weeks
@@ -47,6 +66,13 @@ pub const DEFAULT_REFUND_HANDLING: RefundHandling = RefundHandling::Refund {
 };
 /// Default fee handling.
 pub const DEFAULT_FEE_HANDLING: FeeHandling = FeeHandling::PayToProposer;
+/// Default storage rent.
+pub const DEFAULT_STORAGE_RENT: StorageRent = StorageRent::Disabled;
+/// Default limit of delegation rate changes.
+pub const DEFAULT_DELEGATION_RATE_CHANGE_LIMIT: DelegationRateChangeLimit =
+    DelegationRateChangeLimit::Unlimited;
+/// Default policy of account creation by transfers.
+pub const DEFAULT_ACCOUNT_CREATION_POLICY: AccountCreationPolicy = AccountCreationPolicy::Allowed;
 
 /// The runtime configuration of the execution engine
 #[derive(Debug, Clone)]
@@ -58,7 +84,16 @@ pub struct EngineConfig {
     /// [`Weight`](casper_types::account::Weight)s) for a single account.
     max_associated_keys: u32,
     max_runtime_call_stack_height: u32,
+    /// Maximum size in bytes of the serialized payload of a single `CLValue` written to global
+    /// state.
+    max_cl_value_size: u32,
+    /// Maximum number of named keys a single account or contract can hold.
+    max_named_keys: u32,
     minimum_delegation_amount: u64,
+    /// Minimum stake in motes a validator's bid must hold unless it is fully withdrawn.
+    minimum_bid_amount: u64,
+    /// Whether the auction records the validator set of each era under `Key::AuctionSnapshot`.
+    record_auction_snapshots: bool,
     /// This flag indicates if arguments passed to contracts are checked against the defined types.
     strict_argument_checking: bool,
     /// Vesting schedule period in milliseconds.
@@ -81,6 +116,14 @@ pub struct EngineConfig {
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
     pub(crate) fee_handling: FeeHandling,
+    /// Storage rent.
+    pub(crate) storage_rent: StorageRent,
+    /// Limit of how often and by how much validators may raise their delegation rate.
+    pub(crate) delegation_rate_change_limit: DelegationRateChangeLimit,
+    /// Whether transfers to accounts which do not exist yet create them.
+    pub(crate) account_creation_policy: AccountCreationPolicy,
+    /// The cache of Wasm modules, shared by all clones of the config.
+    module_cache: ModuleCache,
 }
 
 impl Default for EngineConfig {
@@ -89,7 +132,11 @@ impl Default for EngineConfig {
             max_query_depth: DEFAULT_MAX_QUERY_DEPTH,
             max_associated_keys: DEFAULT_MAX_ASSOCIATED_KEYS,
             max_runtime_call_stack_height: DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
+            max_cl_value_size: DEFAULT_MAX_CL_VALUE_SIZE,
+            max_named_keys: DEFAULT_MAX_NAMED_KEYS,
             minimum_delegation_amount: DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            minimum_bid_amount: DEFAULT_MINIMUM_BID_AMOUNT,
+            record_auction_snapshots: DEFAULT_RECORD_AUCTION_SNAPSHOTS,
             strict_argument_checking: DEFAULT_STRICT_ARGUMENT_CHECKING,
             vesting_schedule_period_millis: DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS,
             max_delegators_per_validator: None,
@@ -100,6 +147,10 @@ impl Default for EngineConfig {
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            storage_rent: DEFAULT_STORAGE_RENT,
+            delegation_rate_change_limit: DEFAULT_DELEGATION_RATE_CHANGE_LIMIT,
+            account_creation_policy: DEFAULT_ACCOUNT_CREATION_POLICY,
+            module_cache: ModuleCache::default(),
         }
     }
 }
@@ -129,7 +180,11 @@ impl EngineConfig {
             max_query_depth,
             max_associated_keys,
             max_runtime_call_stack_height,
+            max_cl_value_size: DEFAULT_MAX_CL_VALUE_SIZE,
+            max_named_keys: DEFAULT_MAX_NAMED_KEYS,
             minimum_delegation_amount,
+            minimum_bid_amount: DEFAULT_MINIMUM_BID_AMOUNT,
+            record_auction_snapshots: DEFAULT_RECORD_AUCTION_SNAPSHOTS,
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
@@ -140,6 +195,10 @@ impl EngineConfig {
             allow_unrestricted_transfers: DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            storage_rent: DEFAULT_STORAGE_RENT,
+            delegation_rate_change_limit: DEFAULT_DELEGATION_RATE_CHANGE_LIMIT,
+            account_creation_policy: DEFAULT_ACCOUNT_CREATION_POLICY,
+            module_cache: ModuleCache::default(),
         }
     }
 
@@ -153,6 +212,16 @@ impl EngineConfig {
         self.max_runtime_call_stack_height
     }
 
+    /// Returns the current max `CLValue` size config.
+    pub fn max_cl_value_size(&self) -> u32 {
+        self.max_cl_value_size
+    }
+
+    /// Returns the current max named keys config.
+    pub fn max_named_keys(&self) -> u32 {
+        self.max_named_keys
+    }
+
     /// Returns the current wasm config.
     pub fn wasm_config(&self) -> &WasmConfig {
         &self.wasm_config
@@ -168,6 +237,16 @@ impl EngineConfig {
         self.minimum_delegation_amount
     }
 
+    /// Returns the minimum bid amount in motes.
+    pub fn minimum_bid_amount(&self) -> u64 {
+        self.minimum_bid_amount
+    }
+
+    /// Returns whether the auction records per-era auction snapshots.
+    pub fn record_auction_snapshots(&self) -> bool {
+        self.record_auction_snapshots
+    }
+
     /// Get the engine config's strict argument checking flag.
     pub fn strict_argument_checking(&self) -> bool {
         self.strict_argument_checking
@@ -212,6 +291,26 @@ impl EngineConfig {
     pub fn fee_handling(&self) -> FeeHandling {
         self.fee_handling
     }
+
+    /// Returns the engine config's storage rent.
+    pub fn storage_rent(&self) -> StorageRent {
+        self.storage_rent
+    }
+
+    /// Returns the engine config's limit of delegation rate changes.
+    pub fn delegation_rate_change_limit(&self) -> DelegationRateChangeLimit {
+        self.delegation_rate_change_limit
+    }
+
+    /// Returns the engine config's policy of account creation by transfers.
+    pub fn account_creation_policy(&self) -> AccountCreationPolicy {
+        self.account_creation_policy
+    }
+
+    /// Returns the engine config's cache of Wasm modules.
+    pub fn module_cache(&self) -> &ModuleCache {
+        &self.module_cache
+    }
 }
 
 /// A builder for an [`EngineConfig`].
@@ -223,7 +322,11 @@ pub struct EngineConfigBuilder {
     max_query_depth: Option<u64>,
     max_associated_keys: Option<u32>,
     max_runtime_call_stack_height: Option<u32>,
+    max_cl_value_size: Option<u32>,
+    max_named_keys: Option<u32>,
     minimum_delegation_amount: Option<u64>,
+    minimum_bid_amount: Option<u64>,
+    record_auction_snapshots: Option<bool>,
     strict_argument_checking: Option<bool>,
     vesting_schedule_period_millis: Option<u64>,
     max_delegators_per_validator: Option<u32>,
@@ -234,6 +337,10 @@ pub struct EngineConfigBuilder {
     allow_unrestricted_transfers: Option<bool>,
     refund_handling: Option<RefundHandling>,
     fee_handling: Option<FeeHandling>,
+    storage_rent: Option<StorageRent>,
+    delegation_rate_change_limit: Option<DelegationRateChangeLimit>,
+    account_creation_policy: Option<AccountCreationPolicy>,
+    module_cache_capacity: Option<usize>,
 }
 
 impl EngineConfigBuilder {
@@ -263,6 +370,18 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the max `CLValue` size config option.
+    pub fn with_max_cl_value_size(mut self, max_cl_value_size: u32) -> Self {
+        self.max_cl_value_size = Some(max_cl_value_size);
+        self
+    }
+
+    /// Sets the max named keys config option.
+    pub fn with_max_named_keys(mut self, max_named_keys: u32) -> Self {
+        self.max_named_keys = Some(max_named_keys);
+        self
+    }
+
     /// Sets the strict argument checking config option.
     pub fn with_strict_argument_checking(mut self, value: bool) -> Self {
         self.strict_argument_checking = Some(value);
@@ -306,6 +425,18 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets the minimum bid amount config option.
+    pub fn with_minimum_bid_amount(mut self, minimum_bid_amount: u64) -> Self {
+        self.minimum_bid_amount = Some(minimum_bid_amount);
+        self
+    }
+
+    /// Sets the record auction snapshots config option.
+    pub fn with_record_auction_snapshots(mut self, record_auction_snapshots: bool) -> Self {
+        self.record_auction_snapshots = Some(record_auction_snapshots);
+        self
+    }
+
     /// Sets the administrative accounts.
     pub fn with_administrative_accounts(
         mut self,
@@ -348,6 +479,36 @@ impl EngineConfigBuilder {
         self
     }
 
+    /// Sets storage rent config option.
+    pub fn with_storage_rent(mut self, storage_rent: StorageRent) -> Self {
+        self.storage_rent = Some(storage_rent);
+        self
+    }
+
+    /// Sets the delegation rate change limit config option.
+    pub fn with_delegation_rate_change_limit(
+        mut self,
+        delegation_rate_change_limit: DelegationRateChangeLimit,
+    ) -> Self {
+        self.delegation_rate_change_limit = Some(delegation_rate_change_limit);
+        self
+    }
+
+    /// Sets the account creation policy config option.
+    pub fn with_account_creation_policy(
+        mut self,
+        account_creation_policy: AccountCreationPolicy,
+    ) -> Self {
+        self.account_creation_policy = Some(account_creation_policy);
+        self
+    }
+
+    /// Sets the capacity in bytes of the cache of Wasm modules.  Zero disables caching.
+    pub fn with_module_cache_capacity(mut self, module_cache_capacity: usize) -> Self {
+        self.module_cache_capacity = Some(module_cache_capacity);
+        self
+    }
+
     /// Builds a new [`EngineConfig`] object.
     pub fn build(self) -> EngineConfig {
         let max_query_depth = self.max_query_depth.unwrap_or(DEFAULT_MAX_QUERY_DEPTH);
@@ -357,9 +518,17 @@ impl EngineConfigBuilder {
         let max_runtime_call_stack_height = self
             .max_runtime_call_stack_height
             .unwrap_or(DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT);
+        let max_cl_value_size = self.max_cl_value_size.unwrap_or(DEFAULT_MAX_CL_VALUE_SIZE);
+        let max_named_keys = self.max_named_keys.unwrap_or(DEFAULT_MAX_NAMED_KEYS);
         let minimum_delegation_amount = self
             .minimum_delegation_amount
             .unwrap_or(DEFAULT_MINIMUM_DELEGATION_AMOUNT);
+        let minimum_bid_amount = self
+            .minimum_bid_amount
+            .unwrap_or(DEFAULT_MINIMUM_BID_AMOUNT);
+        let record_auction_snapshots = self
+            .record_auction_snapshots
+            .unwrap_or(DEFAULT_RECORD_AUCTION_SNAPSHOTS);
         let wasm_config = self.wasm_config.unwrap_or_default();
         let system_config = self.system_config.unwrap_or_default();
         let administrative_accounts = {
@@ -377,6 +546,13 @@ impl EngineConfigBuilder {
             .unwrap_or(DEFAULT_ALLOW_UNRESTRICTED_TRANSFERS);
         let refund_handling = self.refund_handling.unwrap_or(DEFAULT_REFUND_HANDLING);
         let fee_handling = self.fee_handling.unwrap_or(DEFAULT_FEE_HANDLING);
+        let storage_rent = self.storage_rent.unwrap_or(DEFAULT_STORAGE_RENT);
+        let delegation_rate_change_limit = self
+            .delegation_rate_change_limit
+            .unwrap_or(DEFAULT_DELEGATION_RATE_CHANGE_LIMIT);
+        let account_creation_policy = self
+            .account_creation_policy
+            .unwrap_or(DEFAULT_ACCOUNT_CREATION_POLICY);
 
         let strict_argument_checking = self
             .strict_argument_checking
@@ -385,12 +561,20 @@ impl EngineConfigBuilder {
             .vesting_schedule_period_millis
             .unwrap_or(DEFAULT_VESTING_SCHEDULE_LENGTH_MILLIS);
         let max_delegators_per_validator = self.max_delegators_per_validator;
+        let module_cache = ModuleCache::new(
+            self.module_cache_capacity
+                .unwrap_or(DEFAULT_MODULE_CACHE_CAPACITY),
+        );
 
         EngineConfig {
             max_query_depth,
             max_associated_keys,
             max_runtime_call_stack_height,
+            max_cl_value_size,
+            max_named_keys,
             minimum_delegation_amount,
+            minimum_bid_amount,
+            record_auction_snapshots,
             wasm_config,
             system_config,
             administrative_accounts,
@@ -398,9 +582,13 @@ impl EngineConfigBuilder {
             allow_unrestricted_transfers,
             refund_handling,
             fee_handling,
+            storage_rent,
+            delegation_rate_change_limit,
+            account_creation_policy,
             strict_argument_checking,
             vesting_schedule_period_millis,
             max_delegators_per_validator,
+            module_cache,
         }
     }
 }
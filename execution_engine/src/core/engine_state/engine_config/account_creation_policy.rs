@@ -0,0 +1,143 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    U512,
+};
+
+use crate::core::execution::Error;
+
+const ACCOUNT_CREATION_ALLOWED_TAG: u8 = 0;
+const ACCOUNT_CREATION_DISALLOWED_TAG: u8 = 1;
+const ACCOUNT_CREATION_ALLOWED_ABOVE_MINIMUM_TAG: u8 = 2;
+
+/// Defines whether a transfer to an account which does not exist yet creates it.
+///
+/// Transfers made by administrators are never restricted.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DataSize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccountCreationPolicy {
+    /// Transfers create their target account if it does not exist.
+    ///
+    /// This is the default option.
+    Allowed,
+    /// Transfers to an account which does not exist fail.
+    Disallowed,
+    /// Transfers create their target account only if they transfer at least `minimum_amount`
+    /// motes.
+    AllowedAboveMinimum {
+        /// Minimum amount in motes of a transfer creating its target account.
+        minimum_amount: u64,
+    },
+}
+
+impl AccountCreationPolicy {
+    /// Checks whether a transfer of `amount` motes may create its target account.
+    pub(crate) fn check_account_creation(&self, amount: U512) -> Result<(), Error> {
+        match self {
+            AccountCreationPolicy::Allowed => Ok(()),
+            AccountCreationPolicy::Disallowed => Err(Error::DisabledAccountCreation),
+            AccountCreationPolicy::AllowedAboveMinimum { minimum_amount } => {
+                if amount < U512::from(*minimum_amount) {
+                    Err(Error::AccountCreationAmountTooLow {
+                        amount,
+                        minimum_amount: *minimum_amount,
+                    })
+                } else {
+                    Ok(())
+                }
+            }
+        }
+    }
+}
+
+impl ToBytes for AccountCreationPolicy {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+
+        match self {
+            AccountCreationPolicy::Allowed => {
+                buffer.push(ACCOUNT_CREATION_ALLOWED_TAG);
+            }
+            AccountCreationPolicy::Disallowed => {
+                buffer.push(ACCOUNT_CREATION_DISALLOWED_TAG);
+            }
+            AccountCreationPolicy::AllowedAboveMinimum { minimum_amount } => {
+                buffer.push(ACCOUNT_CREATION_ALLOWED_ABOVE_MINIMUM_TAG);
+                buffer.extend(minimum_amount.to_bytes()?);
+            }
+        }
+
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            AccountCreationPolicy::Allowed | AccountCreationPolicy::Disallowed => 0,
+            AccountCreationPolicy::AllowedAboveMinimum { minimum_amount } => {
+                minimum_amount.serialized_length()
+            }
+        }
+    }
+}
+
+impl FromBytes for AccountCreationPolicy {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            ACCOUNT_CREATION_ALLOWED_TAG => Ok((AccountCreationPolicy::Allowed, rem)),
+            ACCOUNT_CREATION_DISALLOWED_TAG => Ok((AccountCreationPolicy::Disallowed, rem)),
+            ACCOUNT_CREATION_ALLOWED_ABOVE_MINIMUM_TAG => {
+                let (minimum_amount, rem) = u64::from_bytes(rem)?;
+                Ok((
+                    AccountCreationPolicy::AllowedAboveMinimum { minimum_amount },
+                    rem,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip_for_allowed() {
+        let policy = AccountCreationPolicy::Allowed;
+        bytesrepr::test_serialization_roundtrip(&policy);
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip_for_disallowed() {
+        let policy = AccountCreationPolicy::Disallowed;
+        bytesrepr::test_serialization_roundtrip(&policy);
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip_for_allowed_above_minimum() {
+        let policy = AccountCreationPolicy::AllowedAboveMinimum {
+            minimum_amount: 2_500_000_000,
+        };
+        bytesrepr::test_serialization_roundtrip(&policy);
+    }
+
+    #[test]
+    fn should_check_amount_against_minimum() {
+        let policy = AccountCreationPolicy::AllowedAboveMinimum { minimum_amount: 10 };
+        assert!(policy.check_account_creation(U512::from(10)).is_ok());
+        assert!(matches!(
+            policy.check_account_creation(U512::from(9)),
+            Err(Error::AccountCreationAmountTooLow {
+                minimum_amount: 10,
+                ..
+            })
+        ));
+        assert!(matches!(
+            AccountCreationPolicy::Disallowed.check_account_creation(U512::MAX),
+            Err(Error::DisabledAccountCreation)
+        ));
+    }
+}
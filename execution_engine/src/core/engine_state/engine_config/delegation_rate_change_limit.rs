@@ -0,0 +1,104 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::DelegationRate,
+};
+
+const DELEGATION_RATE_CHANGE_UNLIMITED_TAG: u8 = 0;
+const DELEGATION_RATE_CHANGE_LIMITED_TAG: u8 = 1;
+
+/// Defines how often and by how much validators may raise their delegation rate.
+///
+/// Lowering the delegation rate is never limited.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DataSize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DelegationRateChangeLimit {
+    /// Validators may raise their delegation rate at any time, by any amount.
+    ///
+    /// This is the default option.
+    Unlimited,
+    /// Validators may raise their delegation rate by at most `max_increase` percentage points at
+    /// a time, and only once `min_eras_between_increases` eras have passed since they last raised
+    /// it.
+    Limited {
+        /// Number of eras which have to pass between two increases of the delegation rate.
+        min_eras_between_increases: u64,
+        /// Maximum increase of the delegation rate in a single change, in percentage points.
+        max_increase: DelegationRate,
+    },
+}
+
+impl ToBytes for DelegationRateChangeLimit {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+
+        match self {
+            DelegationRateChangeLimit::Unlimited => {
+                buffer.push(DELEGATION_RATE_CHANGE_UNLIMITED_TAG);
+            }
+            DelegationRateChangeLimit::Limited {
+                min_eras_between_increases,
+                max_increase,
+            } => {
+                buffer.push(DELEGATION_RATE_CHANGE_LIMITED_TAG);
+                buffer.extend(min_eras_between_increases.to_bytes()?);
+                buffer.extend(max_increase.to_bytes()?);
+            }
+        }
+
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            DelegationRateChangeLimit::Unlimited => 0,
+            DelegationRateChangeLimit::Limited {
+                min_eras_between_increases,
+                max_increase,
+            } => min_eras_between_increases.serialized_length() + max_increase.serialized_length(),
+        }
+    }
+}
+
+impl FromBytes for DelegationRateChangeLimit {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            DELEGATION_RATE_CHANGE_UNLIMITED_TAG => Ok((DelegationRateChangeLimit::Unlimited, rem)),
+            DELEGATION_RATE_CHANGE_LIMITED_TAG => {
+                let (min_eras_between_increases, rem) = u64::from_bytes(rem)?;
+                let (max_increase, rem) = DelegationRate::from_bytes(rem)?;
+                Ok((
+                    DelegationRateChangeLimit::Limited {
+                        min_eras_between_increases,
+                        max_increase,
+                    },
+                    rem,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip_for_unlimited() {
+        let limit = DelegationRateChangeLimit::Unlimited;
+        bytesrepr::test_serialization_roundtrip(&limit);
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip_for_limited() {
+        let limit = DelegationRateChangeLimit::Limited {
+            min_eras_between_increases: 12,
+            max_increase: 5,
+        };
+        bytesrepr::test_serialization_roundtrip(&limit);
+    }
+}
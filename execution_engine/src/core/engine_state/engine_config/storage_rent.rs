@@ -0,0 +1,106 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::bytesrepr::{self, FromBytes, ToBytes};
+
+const STORAGE_RENT_DISABLED_TAG: u8 = 0;
+const STORAGE_RENT_LEASE_TAG: u8 = 1;
+
+/// Defines whether values written to global state are leased for a limited number of eras.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize, DataSize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StorageRent {
+    /// Values are stored indefinitely.
+    ///
+    /// This is the default option.
+    Disabled,
+    /// Each write of a value under a [`Key::URef`](casper_types::Key::URef) or a
+    /// [`Key::Dictionary`](casper_types::Key::Dictionary) by user code pays for a lease of the
+    /// value until `lease_eras` eras after the current one, recorded under a
+    /// [`Key::StorageLease`](casper_types::Key::StorageLease).
+    ///
+    /// Writing the value again renews the lease.  Once a lease and the `grace_eras` following it
+    /// have passed, the lease is marked as expired at the next switch block and the value can no
+    /// longer be read until it is written again.
+    ///
+    /// This setting is a prototype intended for private chains.
+    Lease {
+        /// Number of eras a write pays for.
+        lease_eras: u64,
+        /// Number of eras after a lease ends during which the value can still be read and renewed.
+        grace_eras: u64,
+    },
+}
+
+impl ToBytes for StorageRent {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+
+        match self {
+            StorageRent::Disabled => {
+                buffer.push(STORAGE_RENT_DISABLED_TAG);
+            }
+            StorageRent::Lease {
+                lease_eras,
+                grace_eras,
+            } => {
+                buffer.push(STORAGE_RENT_LEASE_TAG);
+                buffer.extend(lease_eras.to_bytes()?);
+                buffer.extend(grace_eras.to_bytes()?);
+            }
+        }
+
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        1 + match self {
+            StorageRent::Disabled => 0,
+            StorageRent::Lease {
+                lease_eras,
+                grace_eras,
+            } => lease_eras.serialized_length() + grace_eras.serialized_length(),
+        }
+    }
+}
+
+impl FromBytes for StorageRent {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (tag, rem) = u8::from_bytes(bytes)?;
+        match tag {
+            STORAGE_RENT_DISABLED_TAG => Ok((StorageRent::Disabled, rem)),
+            STORAGE_RENT_LEASE_TAG => {
+                let (lease_eras, rem) = u64::from_bytes(rem)?;
+                let (grace_eras, rem) = u64::from_bytes(rem)?;
+                Ok((
+                    StorageRent::Lease {
+                        lease_eras,
+                        grace_eras,
+                    },
+                    rem,
+                ))
+            }
+            _ => Err(bytesrepr::Error::Formatting),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip_for_disabled() {
+        let storage_rent = StorageRent::Disabled;
+        bytesrepr::test_serialization_roundtrip(&storage_rent);
+    }
+
+    #[test]
+    fn bytesrepr_roundtrip_for_lease() {
+        let storage_rent = StorageRent::Lease {
+            lease_eras: 100,
+            grace_eras: 10,
+        };
+        bytesrepr::test_serialization_roundtrip(&storage_rent);
+    }
+}
@@ -7,7 +7,9 @@ use casper_types::{bytesrepr, system::mint, ApiError, ProtocolVersion};
 
 use crate::{
     core::{
-        engine_state::{genesis::GenesisError, upgrade::ProtocolUpgradeError},
+        engine_state::{
+            genesis::GenesisError, snapshot::SnapshotError, upgrade::ProtocolUpgradeError,
+        },
         execution,
         runtime::stack,
     },
@@ -106,6 +108,9 @@ pub enum Error {
     /// Failed to retrieve accumulation purse from handle payment system contract.
     #[error("Failed to retrieve accumulation purse from the handle payment contract")]
     FailedToRetrieveAccumulationPurse,
+    /// Exporting or importing a global state snapshot failed.
+    #[error("Snapshot error: {0}")]
+    Snapshot(#[from] SnapshotError),
 }
 
 impl Error {
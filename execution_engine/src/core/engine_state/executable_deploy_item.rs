@@ -47,7 +47,7 @@ const TRANSFER_TAG: u8 = 5;
 
 /// Possible ways to identify the `ExecutableDeployItem`.
 #[derive(
-    Clone, DataSize, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
+    Clone, DataSize, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, JsonSchema,
 )]
 pub enum ExecutableDeployItemIdentifier {
     /// The deploy item is of the type [`ExecutableDeployItem::ModuleBytes`]
@@ -3,7 +3,8 @@
 use std::collections::VecDeque;
 
 use casper_types::{
-    bytesrepr::FromBytes, CLTyped, CLValue, Gas, Key, Motes, StoredValue, TransferAddr,
+    bytesrepr::FromBytes, CLTyped, CLValue, ExecutionEffect as JsonExecutionEffect,
+    ExecutionResultV2, Gas, Key, Motes, StoredValue, TransferAddr,
 };
 
 use super::error;
@@ -49,6 +50,8 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// How the execution was paid for, if it went through the finalization of the payment.
+        payment_summary: Option<PaymentSummary>,
     },
     /// Execution was finished successfully
     Success {
@@ -58,15 +61,52 @@ pub enum ExecutionResult {
         cost: Gas,
         /// Journal of execution.
         execution_journal: ExecutionJournal,
+        /// How the execution was paid for, if it went through the finalization of the payment.
+        payment_summary: Option<PaymentSummary>,
     },
 }
 
+/// How the execution of a deploy was paid for, as settled when finalizing the payment.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PaymentSummary {
+    /// The maximum amount of gas the deploy could consume, as bought by the paid amount.
+    pub limit: Gas,
+    /// The price of one unit of gas in motes.
+    pub gas_price: u64,
+    /// The amount deposited into the payment purse.
+    pub paid_amount: Motes,
+    /// The part of the paid amount refunded to the payer.  It is zero if refunds are burned.
+    pub refund: Motes,
+}
+
+impl PaymentSummary {
+    fn into_execution_result_v2(
+        self,
+        effect: JsonExecutionEffect,
+        transfers: Vec<TransferAddr>,
+        consumed: Gas,
+        error_message: Option<String>,
+    ) -> casper_types::ExecutionResult {
+        casper_types::ExecutionResult::V2(ExecutionResultV2 {
+            effect,
+            transfers,
+            limit: self.limit.value(),
+            consumed: consumed.value(),
+            gas_price: self.gas_price,
+            paid_amount: self.paid_amount.value(),
+            refund: self.refund.value(),
+            error_message,
+        })
+    }
+}
+
 impl Default for ExecutionResult {
     fn default() -> Self {
         ExecutionResult::Success {
             execution_journal: Default::default(),
             transfers: Default::default(),
             cost: Default::default(),
+            payment_summary: None,
         }
     }
 }
@@ -94,6 +134,7 @@ impl ExecutionResult {
             transfers: Vec::default(),
             cost: Gas::default(),
             execution_journal: Default::default(),
+            payment_summary: None,
         }
     }
 
@@ -166,21 +207,25 @@ impl ExecutionResult {
                 error,
                 transfers,
                 execution_journal,
+                payment_summary,
                 ..
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary,
             },
             ExecutionResult::Success {
                 transfers,
                 execution_journal,
+                payment_summary,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary,
             },
         }
     }
@@ -195,21 +240,25 @@ impl ExecutionResult {
                 error,
                 cost,
                 execution_journal,
+                payment_summary,
                 ..
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary,
             },
             ExecutionResult::Success {
                 cost,
                 execution_journal,
+                payment_summary,
                 ..
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary,
             },
         }
     }
@@ -225,20 +274,24 @@ impl ExecutionResult {
                 transfers,
                 cost,
                 execution_journal: _,
+                payment_summary,
             } => ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary,
             },
             ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal: _,
+                payment_summary,
             } => ExecutionResult::Success {
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary,
             },
         }
     }
@@ -328,6 +381,7 @@ impl ExecutionResult {
             execution_journal,
             transfers,
             cost: gas_cost,
+            payment_summary: None,
         })
     }
 
@@ -340,6 +394,35 @@ impl ExecutionResult {
     pub(crate) fn take_without_ret<T: FromBytes + CLTyped>(self) -> (Option<T>, Self) {
         (None, self)
     }
+
+    /// Converts the result into a V1 [`casper_types::ExecutionResult`], disregarding the payment
+    /// summary, e.g. while V2 execution results are not activated yet.
+    pub fn to_execution_result_v1(&self) -> casper_types::ExecutionResult {
+        match self {
+            ExecutionResult::Success {
+                transfers,
+                cost,
+                execution_journal,
+                payment_summary: _,
+            } => casper_types::ExecutionResult::Success {
+                effect: execution_journal.into(),
+                transfers: transfers.clone(),
+                cost: cost.value(),
+            },
+            ExecutionResult::Failure {
+                error,
+                transfers,
+                cost,
+                execution_journal,
+                payment_summary: _,
+            } => casper_types::ExecutionResult::Failure {
+                effect: execution_journal.into(),
+                transfers: transfers.clone(),
+                cost: cost.value(),
+                error_message: error.to_string(),
+            },
+        }
+    }
 }
 
 impl From<&ExecutionResult> for casper_types::ExecutionResult {
@@ -349,22 +432,47 @@ impl From<&ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary: None,
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
                 cost: cost.value(),
             },
+            ExecutionResult::Success {
+                transfers,
+                cost,
+                execution_journal,
+                payment_summary: Some(payment_summary),
+            } => payment_summary.into_execution_result_v2(
+                execution_journal.into(),
+                transfers.clone(),
+                *cost,
+                None,
+            ),
             ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary: None,
             } => casper_types::ExecutionResult::Failure {
                 effect: execution_journal.into(),
                 transfers: transfers.clone(),
                 cost: cost.value(),
                 error_message: error.to_string(),
             },
+            ExecutionResult::Failure {
+                error,
+                transfers,
+                cost,
+                execution_journal,
+                payment_summary: Some(payment_summary),
+            } => payment_summary.into_execution_result_v2(
+                execution_journal.into(),
+                transfers.clone(),
+                *cost,
+                Some(error.to_string()),
+            ),
         }
     }
 }
@@ -376,22 +484,47 @@ impl From<ExecutionResult> for casper_types::ExecutionResult {
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary: None,
             } => casper_types::ExecutionResult::Success {
                 effect: execution_journal.into(),
                 transfers,
                 cost: cost.value(),
             },
+            ExecutionResult::Success {
+                transfers,
+                cost,
+                execution_journal,
+                payment_summary: Some(payment_summary),
+            } => payment_summary.into_execution_result_v2(
+                execution_journal.into(),
+                transfers,
+                cost,
+                None,
+            ),
             ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal,
+                payment_summary: None,
             } => casper_types::ExecutionResult::Failure {
                 effect: execution_journal.into(),
                 transfers,
                 cost: cost.value(),
                 error_message: error.to_string(),
             },
+            ExecutionResult::Failure {
+                error,
+                transfers,
+                cost,
+                execution_journal,
+                payment_summary: Some(payment_summary),
+            } => payment_summary.into_execution_result_v2(
+                execution_journal.into(),
+                transfers,
+                cost,
+                Some(error.to_string()),
+            ),
         }
     }
 }
@@ -414,6 +547,7 @@ pub struct ExecutionResultBuilder {
     payment_execution_result: Option<ExecutionResult>,
     session_execution_result: Option<ExecutionResult>,
     finalize_execution_result: Option<ExecutionResult>,
+    payment_summary: Option<PaymentSummary>,
 }
 
 impl ExecutionResultBuilder {
@@ -446,6 +580,15 @@ impl ExecutionResultBuilder {
         self
     }
 
+    /// Sets how the execution was paid for.
+    pub fn set_payment_summary(
+        &mut self,
+        payment_summary: PaymentSummary,
+    ) -> &mut ExecutionResultBuilder {
+        self.payment_summary = Some(payment_summary);
+        self
+    }
+
     /// Calculates the total gas cost of the execution result.
     ///
     /// Takes a payment execution result, and a session execution result and returns a sum. If
@@ -502,6 +645,7 @@ impl ExecutionResultBuilder {
                 transfers: session_transfers,
                 execution_journal: _,
                 cost: _,
+                payment_summary: _,
             }) => {
                 error = Some(session_error);
                 transfers = session_transfers;
@@ -530,12 +674,14 @@ impl ExecutionResultBuilder {
                 transfers,
                 cost,
                 execution_journal: journal,
+                payment_summary: self.payment_summary,
             }),
             Some(error) => Ok(ExecutionResult::Failure {
                 error,
                 transfers,
                 cost,
                 execution_journal: journal,
+                payment_summary: self.payment_summary,
             }),
         }
     }
@@ -25,7 +25,10 @@ use casper_types::{
             SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
         },
         handle_payment::{self, ACCUMULATION_PURSE_KEY},
-        mint::{self, ARG_ROUND_SEIGNIORAGE_RATE, ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY},
+        mint::{
+            self, ARG_ROUND_SEIGNIORAGE_RATE, HISTORICAL_TOTAL_SUPPLY_KEY, MINT_PAUSED_KEY,
+            ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY,
+        },
         standard_payment, AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
     AccessRights, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash,
@@ -71,6 +74,8 @@ pub const DEFAULT_UNBONDING_DELAY: u64 = 7;
 pub const DEFAULT_ROUND_SEIGNIORAGE_RATE: Ratio<u64> = Ratio::new_raw(7, 175070816);
 /// Default genesis timestamp in milliseconds.
 pub const DEFAULT_GENESIS_TIMESTAMP_MILLIS: u64 = 0;
+/// By default, the mint is not paused at genesis.
+pub const DEFAULT_MINT_PAUSED: bool = false;
 
 /// Represents an outcome of a successful genesis run.
 #[derive(Debug)]
@@ -660,6 +665,7 @@ pub struct ExecConfig {
     genesis_timestamp_millis: u64,
     refund_handling: RefundHandling,
     fee_handling: FeeHandling,
+    mint_paused: bool,
 }
 
 impl ExecConfig {
@@ -695,6 +701,7 @@ impl ExecConfig {
             genesis_timestamp_millis,
             refund_handling: DEFAULT_REFUND_HANDLING,
             fee_handling: DEFAULT_FEE_HANDLING,
+            mint_paused: DEFAULT_MINT_PAUSED,
         }
     }
 
@@ -774,6 +781,11 @@ impl ExecConfig {
     pub fn genesis_timestamp_millis(&self) -> u64 {
         self.genesis_timestamp_millis
     }
+
+    /// Returns whether the mint is paused at genesis.
+    pub fn mint_paused(&self) -> bool {
+        self.mint_paused
+    }
 }
 
 impl Distribution<ExecConfig> for Standard {
@@ -811,6 +823,8 @@ impl Distribution<ExecConfig> for Standard {
             FeeHandling::PayToProposer
         };
 
+        let mint_paused = rng.gen();
+
         ExecConfig {
             accounts,
             wasm_config,
@@ -823,6 +837,7 @@ impl Distribution<ExecConfig> for Standard {
             genesis_timestamp_millis,
             refund_handling,
             fee_handling,
+            mint_paused,
         }
     }
 }
@@ -844,6 +859,7 @@ pub struct ExecConfigBuilder {
     genesis_timestamp_millis: Option<u64>,
     refund_handling: Option<RefundHandling>,
     fee_handling: Option<FeeHandling>,
+    mint_paused: Option<bool>,
 }
 
 impl ExecConfigBuilder {
@@ -918,6 +934,12 @@ impl ExecConfigBuilder {
         self
     }
 
+    /// Sets the mint paused config option.
+    pub fn with_mint_paused(mut self, mint_paused: bool) -> Self {
+        self.mint_paused = Some(mint_paused);
+        self
+    }
+
     /// Builds a new [`ExecConfig`] object.
     pub fn build(self) -> ExecConfig {
         ExecConfig {
@@ -938,6 +960,7 @@ impl ExecConfigBuilder {
                 .unwrap_or(DEFAULT_GENESIS_TIMESTAMP_MILLIS),
             refund_handling: self.refund_handling.unwrap_or(DEFAULT_REFUND_HANDLING),
             fee_handling: self.fee_handling.unwrap_or(DEFAULT_FEE_HANDLING),
+            mint_paused: self.mint_paused.unwrap_or(DEFAULT_MINT_PAUSED),
         }
     }
 }
@@ -1107,6 +1130,35 @@ where
             total_supply_uref
         };
 
+        let mint_paused_uref = {
+            let mint_paused_uref = self
+                .address_generator
+                .borrow_mut()
+                .new_uref(AccessRights::READ_ADD_WRITE);
+
+            self.tracking_copy.borrow_mut().write(
+                mint_paused_uref.into(),
+                StoredValue::CLValue(
+                    CLValue::from_t(self.exec_config.mint_paused())
+                        .map_err(|_| GenesisError::CLValue(MINT_PAUSED_KEY.to_string()))?,
+                ),
+            );
+            mint_paused_uref
+        };
+
+        let historical_total_supply_uref = {
+            let historical_total_supply_uref = self
+                .address_generator
+                .borrow_mut()
+                .new_uref(AccessRights::READ_ADD_WRITE);
+
+            self.tracking_copy.borrow_mut().write(
+                historical_total_supply_uref.into(),
+                StoredValue::CLValue(CLValue::unit()),
+            );
+            historical_total_supply_uref
+        };
+
         let named_keys = {
             let mut named_keys = NamedKeys::new();
             named_keys.insert(
@@ -1114,6 +1166,11 @@ where
                 round_seigniorage_rate_uref.into(),
             );
             named_keys.insert(TOTAL_SUPPLY_KEY.to_string(), total_supply_uref.into());
+            named_keys.insert(MINT_PAUSED_KEY.to_string(), mint_paused_uref.into());
+            named_keys.insert(
+                HISTORICAL_TOTAL_SUPPLY_KEY.to_string(),
+                historical_total_supply_uref.into(),
+            );
 
             named_keys
         };
@@ -1,23 +1,92 @@
 //! Support for obtaining current bids from the auction system.
+use std::collections::BTreeSet;
+
 use casper_hashing::Digest;
-use casper_types::system::auction::Bids;
+use casper_types::{account::AccountHash, system::auction::Bids, PublicKey};
+
+/// The status of the bids returned by a [`GetBidsRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BidStatus {
+    /// Bids of validators which have not been evicted.
+    Active,
+    /// Bids of validators which have been evicted, or which have withdrawn their whole stake.
+    Inactive,
+}
 
 /// Represents a request to obtain current bids in the auction system.
+///
+/// By default all bids are returned.  The bids can be narrowed down to given validators and to a
+/// given status, and split into pages, in which case bids are returned in the order of the account
+/// hashes of their validators.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct GetBidsRequest {
     state_hash: Digest,
+    validators: Option<BTreeSet<PublicKey>>,
+    status: Option<BidStatus>,
+    page_size: Option<usize>,
+    continuation_token: Option<AccountHash>,
 }
 
 impl GetBidsRequest {
     /// Creates new request.
     pub fn new(state_hash: Digest) -> Self {
-        GetBidsRequest { state_hash }
+        GetBidsRequest {
+            state_hash,
+            validators: None,
+            status: None,
+            page_size: None,
+            continuation_token: None,
+        }
+    }
+
+    /// Only returns the bids of the given validators.
+    pub fn with_validators(mut self, validators: impl IntoIterator<Item = PublicKey>) -> Self {
+        self.validators = Some(validators.into_iter().collect());
+        self
+    }
+
+    /// Only returns the bids with the given status.
+    pub fn with_status(mut self, status: BidStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+
+    /// Returns at most `page_size` bids.  A page size of zero is treated as one.
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+
+    /// Continues from the page which returned the given continuation token.
+    pub fn with_continuation_token(mut self, continuation_token: AccountHash) -> Self {
+        self.continuation_token = Some(continuation_token);
+        self
     }
 
     /// Returns state root hash.
     pub fn state_hash(&self) -> Digest {
         self.state_hash
     }
+
+    /// Returns the validators whose bids are requested, or `None` if all bids are requested.
+    pub fn validators(&self) -> Option<&BTreeSet<PublicKey>> {
+        self.validators.as_ref()
+    }
+
+    /// Returns the status of the requested bids, or `None` if bids of any status are requested.
+    pub fn status(&self) -> Option<BidStatus> {
+        self.status
+    }
+
+    /// Returns the maximum number of bids to return, or `None` if the bids are not paginated.
+    pub fn page_size(&self) -> Option<usize> {
+        self.page_size
+    }
+
+    /// Returns the continuation token of the previous page, if any.
+    pub fn continuation_token(&self) -> Option<AccountHash> {
+        self.continuation_token
+    }
 }
 
 /// Represents a result of a `get_bids` request.
@@ -29,13 +98,17 @@ pub enum GetBidsResult {
     Success {
         /// Current bids.
         bids: Bids,
+        /// The token to request the next page with, or `None` if this is the last page.
+        ///
+        /// The next page may turn out to be empty if none of the remaining bids match the filter.
+        continuation_token: Option<AccountHash>,
     },
 }
 
 impl GetBidsResult {
     /// Returns wrapped [`Bids`] if this represents a successful query result.
     pub fn into_success(self) -> Option<Bids> {
-        if let Self::Success { bids } = self {
+        if let Self::Success { bids, .. } = self {
             Some(bids)
         } else {
             None
@@ -0,0 +1,97 @@
+//! Support for obtaining an account or a contract together with its named keys and entry points.
+use casper_hashing::Digest;
+use casper_types::{
+    account::{Account, AccountHash},
+    Contract, ContractHash, Key, PublicKey,
+};
+
+/// Identifier of an account or a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntityIdentifier {
+    /// The account identified by the account hash of this public key.
+    PublicKey(PublicKey),
+    /// The account identified by this account hash.
+    AccountHash(AccountHash),
+    /// The contract identified by this contract hash.
+    ContractHash(ContractHash),
+}
+
+impl EntityIdentifier {
+    /// Returns the key under which the entity is stored in global state.
+    pub fn key(&self) -> Key {
+        match self {
+            EntityIdentifier::PublicKey(public_key) => Key::Account(public_key.to_account_hash()),
+            EntityIdentifier::AccountHash(account_hash) => Key::Account(*account_hash),
+            EntityIdentifier::ContractHash(contract_hash) => Key::from(*contract_hash),
+        }
+    }
+}
+
+/// Represents a request to obtain an account or a contract.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetEntityRequest {
+    state_hash: Digest,
+    entity_identifier: EntityIdentifier,
+}
+
+impl GetEntityRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, entity_identifier: EntityIdentifier) -> Self {
+        GetEntityRequest {
+            state_hash,
+            entity_identifier,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the identifier of the requested entity.
+    pub fn entity_identifier(&self) -> &EntityIdentifier {
+        &self.entity_identifier
+    }
+}
+
+/// An account or a contract read from global state.
+///
+/// Both carry their named keys.  An account also carries its associated keys and action
+/// thresholds, while a contract carries its entry points with the types of their parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entity {
+    /// An account.
+    Account(Account),
+    /// A contract.
+    Contract {
+        /// The hash of the contract.
+        contract_hash: ContractHash,
+        /// The contract.
+        contract: Contract,
+    },
+}
+
+/// Represents a result of a `get_entity` request.
+#[derive(Debug)]
+pub enum GetEntityResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// No account or contract is stored under the given identifier.
+    EntityNotFound,
+    /// Contains the entity returned from the global state.
+    Success {
+        /// The requested entity.
+        entity: Entity,
+    },
+}
+
+impl GetEntityResult {
+    /// Returns the wrapped [`Entity`] if this represents a successful query result.
+    pub fn into_success(self) -> Option<Entity> {
+        if let Self::Success { entity } = self {
+            Some(entity)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Support for obtaining the unbonding purses of a public key from the auction system.
+use casper_hashing::Digest;
+use casper_types::{system::auction::UnbondingPurse, EraId, PublicKey};
+
+/// Represents a request to obtain the unbonding purses of a public key, both as a validator and
+/// as a delegator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GetUnbondingPursesRequest {
+    state_hash: Digest,
+    public_key: PublicKey,
+}
+
+impl GetUnbondingPursesRequest {
+    /// Creates new request.
+    pub fn new(state_hash: Digest, public_key: PublicKey) -> Self {
+        GetUnbondingPursesRequest {
+            state_hash,
+            public_key,
+        }
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the public key whose unbonding purses are requested.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+}
+
+/// The key space an unbonding purse was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnbondingPurseKind {
+    /// The purse is stored under a `Key::Unbond`.
+    Unbond,
+    /// The purse is stored under a legacy `Key::Withdraw`.
+    Withdraw,
+}
+
+/// An unbonding purse together with the era in which its funds are released.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnbondingPurseEntry {
+    kind: UnbondingPurseKind,
+    unbonding_purse: UnbondingPurse,
+    release_era: EraId,
+}
+
+impl UnbondingPurseEntry {
+    pub(crate) fn new(
+        kind: UnbondingPurseKind,
+        unbonding_purse: UnbondingPurse,
+        unbonding_delay: u64,
+    ) -> Self {
+        let release_era = unbonding_purse
+            .era_of_creation()
+            .saturating_add(unbonding_delay);
+        UnbondingPurseEntry {
+            kind,
+            unbonding_purse,
+            release_era,
+        }
+    }
+
+    /// Returns the key space the purse was found in.
+    pub fn kind(&self) -> UnbondingPurseKind {
+        self.kind
+    }
+
+    /// Returns the unbonding purse.
+    ///
+    /// Legacy withdraw purses are converted into unbonding purses without a new validator.
+    pub fn unbonding_purse(&self) -> &UnbondingPurse {
+        &self.unbonding_purse
+    }
+
+    /// Returns the era at the start of which the unbonded funds are paid out.
+    pub fn release_era(&self) -> EraId {
+        self.release_era
+    }
+}
+
+/// Represents a result of a `get_unbonding_purses` request.
+#[derive(Debug)]
+pub enum GetUnbondingPursesResult {
+    /// Invalid state root hash.
+    RootNotFound,
+    /// Contains the unbonding purses returned from the global state.
+    Success {
+        /// The unbonding purses of the requested public key.
+        unbonding_purses: Vec<UnbondingPurseEntry>,
+    },
+}
+
+impl GetUnbondingPursesResult {
+    /// Returns the wrapped unbonding purses if this represents a successful query result.
+    pub fn into_success(self) -> Option<Vec<UnbondingPurseEntry>> {
+        if let Self::Success { unbonding_purses } = self {
+            Some(unbonding_purses)
+        } else {
+            None
+        }
+    }
+}
@@ -2,6 +2,7 @@
 pub mod balance;
 pub mod chainspec_registry;
 pub mod checksum_registry;
+mod compact_auction;
 pub mod deploy_item;
 pub mod engine_config;
 pub mod era_validators;
@@ -12,10 +13,13 @@ pub mod execution_effect;
 pub mod execution_result;
 pub mod genesis;
 pub mod get_bids;
+pub mod get_entity;
+pub mod get_unbonding_purses;
 pub mod op;
 mod prune;
 pub mod query;
 pub mod run_genesis_request;
+mod snapshot;
 pub mod step;
 pub mod system_contract_registry;
 mod transfer;
@@ -25,6 +29,8 @@ use std::{
     cell::RefCell,
     collections::{BTreeMap, BTreeSet},
     convert::TryFrom,
+    io::{Read, Write},
+    ops::Bound,
     rc::Rc,
 };
 
@@ -40,7 +46,7 @@ use casper_types::{
     contracts::NamedKeys,
     system::{
         auction::{
-            EraValidators, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
+            EraValidators, UnbondingPurse, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_EVICTED_VALIDATORS,
             ARG_REWARD_FACTORS, ARG_VALIDATOR_PUBLIC_KEYS, AUCTION_DELAY_KEY,
             LOCKED_FUNDS_PERIOD_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY,
             VALIDATOR_SLOTS_KEY,
@@ -49,14 +55,16 @@ use casper_types::{
         mint::{self, ROUND_SEIGNIORAGE_RATE_KEY},
         AUCTION, HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
-    AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo, Gas, Key,
-    KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue, URef, U512,
+    AccessRights, ApiError, BlockTime, CLValue, ContractHash, DeployHash, DeployInfo, EraId, Gas,
+    Key, KeyTag, Motes, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StorageLease, StoredValue,
+    URef, U512,
 };
 
 pub use self::{
-    balance::{BalanceRequest, BalanceResult},
+    balance::{BalanceIdentifier, BalanceRequest, BalanceResult, BalancesRequest, BalancesResult},
     chainspec_registry::ChainspecRegistry,
     checksum_registry::ChecksumRegistry,
+    compact_auction::AuctionCompactionResult,
     deploy_item::DeployItem,
     engine_config::{
         EngineConfig, EngineConfigBuilder, DEFAULT_MAX_QUERY_DEPTH,
@@ -67,40 +75,53 @@ pub use self::{
     executable_deploy_item::{ExecutableDeployItem, ExecutableDeployItemIdentifier},
     execute_request::ExecuteRequest,
     execution::Error as ExecError,
-    execution_result::{ExecutionResult, ForcedTransferResult},
+    execution_result::{ExecutionResult, ForcedTransferResult, PaymentSummary},
     genesis::{ExecConfig, GenesisAccount, GenesisConfig, GenesisSuccess},
-    get_bids::{GetBidsRequest, GetBidsResult},
+    get_bids::{BidStatus, GetBidsRequest, GetBidsResult},
+    get_entity::{Entity, EntityIdentifier, GetEntityRequest, GetEntityResult},
+    get_unbonding_purses::{
+        GetUnbondingPursesRequest, GetUnbondingPursesResult, UnbondingPurseEntry,
+        UnbondingPurseKind,
+    },
     prune::{PruneConfig, PruneResult},
-    query::{QueryRequest, QueryResult},
+    query::{
+        QueryChunk, QueryRequest, QueryResult, QueryStream, QueryStreamRequest, QueryStreamTarget,
+    },
     run_genesis_request::RunGenesisRequest,
+    snapshot::{SnapshotError, SnapshotSummary, SNAPSHOT_FORMAT_VERSION, SNAPSHOT_MAGIC},
     step::{RewardItem, SlashItem, StepError, StepRequest, StepSuccess},
     system_contract_registry::SystemContractRegistry,
     transfer::{TransferArgs, TransferRuntimeArgsBuilder, TransferTargetMode},
     upgrade::{UpgradeConfig, UpgradeSuccess},
 };
-use self::{engine_config::FeeHandling, transfer::NewTransferTargetMode};
+use self::{
+    engine_config::{FeeHandling, RefundHandling, StorageRent},
+    transfer::NewTransferTargetMode,
+};
 use crate::{
     core::{
         engine_state::{
             executable_deploy_item::ExecutionKind,
             execution_result::{ExecutionResultBuilder, ExecutionResults},
             genesis::GenesisInstaller,
+            snapshot::{SnapshotReader, SnapshotWriter},
             upgrade::{ProtocolUpgradeError, SystemUpgrader},
         },
         execution::{self, DirectSystemContractCall, Executor},
-        runtime::RuntimeStack,
+        runtime::{self, RuntimeStack},
         tracking_copy::{TrackingCopy, TrackingCopyExt},
     },
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         global_state::{
-            lmdb::LmdbGlobalState, scratch::ScratchGlobalState, CommitProvider, StateProvider,
-            StateReader,
+            lmdb::{LmdbGlobalState, TriePruningSummary},
+            scratch::ScratchGlobalState,
+            CommitProvider, StateProvider, StateReader,
         },
-        trie::{merkle_proof::TrieMerkleProof, TrieRaw},
+        trie::{self, merkle_proof::TrieMerkleProof, LazyTrieLeaf, TrieRaw},
         trie_store::operations::DeleteResult,
     },
-    system::auction,
+    system::{auction, handle_payment::calculate_refund_and_fee},
 };
 
 /// The maximum amount of motes that payment code execution can cost.
@@ -167,6 +188,35 @@ impl EngineState<LmdbGlobalState> {
             .put_stored_values(CorrelationId::new(), state_root_hash, stored_values)
             .map_err(Into::into)
     }
+
+    /// Starts retaining the trie nodes written from now on for the next pruning.  Returns `false`
+    /// if tries are already being pruned.
+    ///
+    /// See [`LmdbGlobalState::start_trie_pruning`].
+    pub fn start_trie_pruning(&self) -> Result<bool, Error> {
+        Ok(self.state.start_trie_pruning()?)
+    }
+
+    /// Ends the pruning started by [`start_trie_pruning`](Self::start_trie_pruning) without
+    /// pruning anything.
+    pub fn cancel_trie_pruning(&self) -> Result<(), Error> {
+        Ok(self.state.cancel_trie_pruning()?)
+    }
+
+    /// Deletes the trie nodes which are not reachable from any of `retained_state_root_hashes`,
+    /// nor written since the pruning started, in batches of `batch_size` trie nodes.
+    ///
+    /// Commits only wait for the current batch.  See
+    /// [`LmdbGlobalState::prune_unreachable_tries`].
+    pub fn prune_unreachable_tries(
+        &self,
+        retained_state_root_hashes: &[Digest],
+        batch_size: usize,
+    ) -> Result<TriePruningSummary, Error> {
+        Ok(self
+            .state
+            .prune_unreachable_tries(retained_state_root_hashes, batch_size)?)
+    }
 }
 
 impl<S> EngineState<S>
@@ -357,6 +407,17 @@ where
             )
             .map_err(Error::ProtocolUpgrade)?;
 
+        if let Some(new_mint_paused) = upgrade_config.new_mint_paused() {
+            debug!(%new_mint_paused, "Mint paused flag set as part of the upgrade");
+            system_upgrader
+                .set_mint_paused(correlation_id, mint_hash, new_mint_paused)
+                .map_err(Error::ProtocolUpgrade)?;
+        }
+
+        system_upgrader
+            .create_historical_total_supply_if_required(correlation_id, mint_hash)
+            .map_err(Error::ProtocolUpgrade)?;
+
         system_upgrader
             .refresh_system_contracts(
                 correlation_id,
@@ -504,6 +565,75 @@ where
         }
     }
 
+    /// Commits the deletion of the auction's records which no longer carry any information.
+    ///
+    /// Emptied unbonding queues and the bids of fully withdrawn validators which nothing refers to
+    /// anymore accumulate over the life of a network and slow down reading the auction's state.
+    /// The deleted keys are returned alongside the new state root hash.
+    pub fn commit_auction_compaction(
+        &self,
+        correlation_id: CorrelationId,
+        pre_state_hash: Digest,
+    ) -> Result<AuctionCompactionResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(pre_state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(AuctionCompactionResult::RootNotFound),
+        };
+
+        let mut bids = BTreeMap::new();
+        let bid_keys = tracking_copy
+            .get_keys(correlation_id, &KeyTag::Bid)
+            .map_err(|err| Error::Exec(err.into()))?;
+        for key in bid_keys {
+            if let Some(StoredValue::Bid(bid)) = tracking_copy
+                .get(correlation_id, &key)
+                .map_err(Into::into)?
+            {
+                bids.insert(key, *bid);
+            }
+        }
+
+        let mut unbonds = BTreeMap::new();
+        let unbond_keys = tracking_copy
+            .get_keys(correlation_id, &KeyTag::Unbond)
+            .map_err(|err| Error::Exec(err.into()))?;
+        for key in unbond_keys {
+            if let Some(StoredValue::Unbonding(unbonding_purses)) = tracking_copy
+                .get(correlation_id, &key)
+                .map_err(Into::into)?
+            {
+                unbonds.insert(key, unbonding_purses);
+            }
+        }
+
+        let reclaimed_keys = compact_auction::keys_to_compact(&bids, &unbonds);
+        if reclaimed_keys.is_empty() {
+            return Ok(AuctionCompactionResult::Success {
+                post_state_hash: pre_state_hash,
+                reclaimed_keys,
+            });
+        }
+
+        match self
+            .state
+            .delete_keys(correlation_id, pre_state_hash, &reclaimed_keys)
+        {
+            Ok(DeleteResult::Deleted(post_state_hash)) => {
+                debug!(
+                    reclaimed_keys = reclaimed_keys.len(),
+                    "compacted auction records"
+                );
+                Ok(AuctionCompactionResult::Success {
+                    post_state_hash,
+                    reclaimed_keys,
+                })
+            }
+            Ok(DeleteResult::DoesNotExist) => Ok(AuctionCompactionResult::DoesNotExist),
+            Ok(DeleteResult::RootNotFound) => Ok(AuctionCompactionResult::RootNotFound),
+            Err(error) => Err(Error::Exec(error.into())),
+        }
+    }
+
     /// Creates a new tracking copy instance.
     pub fn tracking_copy(&self, hash: Digest) -> Result<Option<TrackingCopy<S::Reader>>, Error> {
         match self.state.checkout(hash).map_err(Into::into)? {
@@ -540,6 +670,26 @@ where
             .into())
     }
 
+    /// Executes a query streaming the values of a collection, such as all bids or the items of a
+    /// dictionary, in chunks with their Merkle proofs.
+    ///
+    /// Returns `None` if the state root hash is not found.
+    pub fn run_query_stream(
+        &self,
+        correlation_id: CorrelationId,
+        query_stream_request: QueryStreamRequest,
+    ) -> Result<Option<QueryStream<S::Reader>>, Error> {
+        let reader = match self
+            .state
+            .checkout(query_stream_request.state_hash())
+            .map_err(Into::into)?
+        {
+            Some(reader) => reader,
+            None => return Ok(None),
+        };
+        QueryStream::new(correlation_id, reader, &query_stream_request).map(Some)
+    }
+
     /// Runs a deploy execution request.
     ///
     /// For each deploy stored in the request it will execute it.
@@ -646,6 +796,48 @@ where
         Ok(BalanceResult::Success { motes, proof })
     }
 
+    /// Obtains the balances of several purses under a single state hash.
+    ///
+    /// Unlike [`EngineState::get_purse_balance`], no Merkle proofs are produced, and a missing
+    /// account or purse yields no balance for that entry rather than failing the whole request.
+    pub fn get_balances(
+        &self,
+        correlation_id: CorrelationId,
+        balances_request: BalancesRequest,
+    ) -> Result<BalancesResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(balances_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(BalancesResult::RootNotFound),
+        };
+
+        let mut balances = Vec::with_capacity(balances_request.identifiers().len());
+        for identifier in balances_request.identifiers() {
+            let purse_uref = match *identifier {
+                BalanceIdentifier::Purse(purse_uref) => purse_uref,
+                BalanceIdentifier::Account(account_hash) => {
+                    match tracking_copy.read_account(correlation_id, account_hash) {
+                        Ok(account) => account.main_purse(),
+                        Err(execution::Error::KeyNotFound(_)) => {
+                            balances.push((*identifier, None));
+                            continue;
+                        }
+                        Err(error) => return Err(error.into()),
+                    }
+                }
+            };
+            let balance_key =
+                tracking_copy.get_purse_balance_key(correlation_id, purse_uref.into())?;
+            let motes = match tracking_copy.get_purse_balance(correlation_id, balance_key) {
+                Ok(balance) => Some(balance.value()),
+                Err(execution::Error::KeyNotFound(_)) => None,
+                Err(error) => return Err(error.into()),
+            };
+            balances.push((*identifier, motes));
+        }
+
+        Ok(BalancesResult::Success(balances))
+    }
+
     /// Executes a native transfer.
     ///
     /// Native transfers do not involve WASM at all, and also skip executing payment code.
@@ -841,7 +1033,20 @@ where
             | NewTransferTargetMode::PurseExists(_) => {
                 // Noop
             }
-            NewTransferTargetMode::CreateAccount(account_hash) => {
+            NewTransferTargetMode::CreateAccount(target_account_hash) => {
+                if !self.config.is_administrator(&account_hash) {
+                    let amount = match runtime_args_builder.resolve_amount() {
+                        Ok(amount) => amount,
+                        Err(error) => return Ok(make_charged_execution_failure(error)),
+                    };
+                    if let Err(error) = self
+                        .config
+                        .account_creation_policy()
+                        .check_account_creation(amount)
+                    {
+                        return Ok(make_charged_execution_failure(error.into()));
+                    }
+                }
                 let create_purse_stack = self.get_new_system_call_stack();
                 let (maybe_uref, execution_result): (Option<URef>, ExecutionResult) = executor
                     .call_system_contract(
@@ -864,11 +1069,11 @@ where
                     Some(main_purse) => {
                         let new_account = {
                             let named_keys = NamedKeys::default();
-                            Account::create(account_hash, named_keys, main_purse)
+                            Account::create(target_account_hash, named_keys, main_purse)
                         };
                         // write new account
                         tracking_copy.borrow_mut().write(
-                            Key::Account(account_hash),
+                            Key::Account(target_account_hash),
                             StoredValue::Account(new_account),
                         );
                     }
@@ -968,6 +1173,7 @@ where
                 payment_uref,
                 wasmless_transfer_motes.value(),
                 transfer_args.arg_id(),
+                None,
             );
 
             let runtime_args = match RuntimeArgs::try_from(new_transfer_args) {
@@ -1165,6 +1371,13 @@ where
         execution_result_builder.set_payment_execution_result(payment_result);
         execution_result_builder.set_session_execution_result(session_result);
         execution_result_builder.set_finalize_execution_result(finalize_result);
+        // Wasmless transfers are charged exactly their fixed cost, leaving nothing to refund.
+        execution_result_builder.set_payment_summary(PaymentSummary {
+            limit: wasmless_transfer_gas_cost,
+            gas_price: WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+            paid_amount: wasmless_transfer_motes,
+            refund: Motes::new(U512::zero()),
+        });
 
         let execution_result = execution_result_builder
             .build()
@@ -1624,25 +1837,24 @@ where
         // results) is enforced in execution_result_builder.build()
         execution_result_builder.set_session_execution_result(session_result);
 
+        // ((gas spent during payment code execution) + (gas spent during session code execution))
+        // * gas_price
+        let finalize_cost_motes =
+            match Motes::from_gas(execution_result_builder.total_cost(), deploy_item.gas_price) {
+                Some(motes) => motes,
+                None => {
+                    return Ok(ExecutionResult::precondition_failure(
+                        Error::GasConversionOverflow,
+                    ))
+                }
+            };
+
         // payment_code_spec_5: run finalize process
         let finalize_result: ExecutionResult = {
             let post_session_tc = post_session_rc.borrow();
             let finalization_tc = Rc::new(RefCell::new(post_session_tc.fork()));
 
             let handle_payment_args = {
-                //((gas spent during payment code execution) + (gas spent during session code execution)) * gas_price
-                let finalize_cost_motes = match Motes::from_gas(
-                    execution_result_builder.total_cost(),
-                    deploy_item.gas_price,
-                ) {
-                    Some(motes) => motes,
-                    None => {
-                        return Ok(ExecutionResult::precondition_failure(
-                            Error::GasConversionOverflow,
-                        ))
-                    }
-                };
-
                 let maybe_runtime_args = RuntimeArgs::try_new(|args| {
                     args.insert(handle_payment::ARG_AMOUNT, finalize_cost_motes.value())?;
                     args.insert(handle_payment::ARG_ACCOUNT, account.account_hash())?;
@@ -1709,6 +1921,26 @@ where
 
         execution_result_builder.set_finalize_execution_result(finalize_result);
 
+        // The refund as settled by the finalization of the payment above.  If it failed, the
+        // builder discards the payment summary along with the rest of the result.
+        let refund = match self.config.refund_handling() {
+            refund_handling @ RefundHandling::Refund { .. } => calculate_refund_and_fee(
+                finalize_cost_motes.value(),
+                payment_purse_balance.value(),
+                refund_handling,
+            )
+            .map(|(refund, _fee)| refund)
+            .unwrap_or_default(),
+            RefundHandling::Burn { .. } => U512::zero(),
+        };
+        execution_result_builder.set_payment_summary(PaymentSummary {
+            limit: Gas::from_motes(payment_purse_balance, deploy_item.gas_price)
+                .unwrap_or_default(),
+            gas_price: deploy_item.gas_price,
+            paid_amount: payment_purse_balance,
+            refund: Motes::new(refund),
+        });
+
         // We panic here to indicate that the builder was not used properly.
         let ret = execution_result_builder
             .build()
@@ -1820,6 +2052,93 @@ where
         }
     }
 
+    /// Exports the global state under `state_root_hash` into a snapshot written to `writer`.
+    ///
+    /// Every trie node is written after all of its descendants, so that the snapshot can be
+    /// imported into an empty global state by `import_snapshot`.
+    pub fn export_snapshot<W: Write>(
+        &self,
+        correlation_id: CorrelationId,
+        state_root_hash: Digest,
+        writer: W,
+    ) -> Result<SnapshotSummary, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let read_trie = |trie_hash: Digest| -> Result<(TrieRaw, Vec<Digest>), Error> {
+            let trie_raw = self
+                .state
+                .get_trie_full(correlation_id, &trie_hash)?
+                .ok_or(SnapshotError::MissingTrie(trie_hash))?;
+            let lazy_trie: LazyTrieLeaf<Key, StoredValue> =
+                trie::lazy_trie_deserialize(trie_raw.inner().clone())?;
+            let children = trie::lazy_trie_iter_children(&lazy_trie).collect();
+            Ok((trie_raw, children))
+        };
+
+        if self
+            .state
+            .get_trie_full(correlation_id, &state_root_hash)?
+            .is_none()
+        {
+            return Err(Error::RootNotFound(state_root_hash));
+        }
+        let mut snapshot = SnapshotWriter::new(writer, state_root_hash)?;
+
+        // The trie nodes being visited, each with its children which are yet to be written.
+        let mut stack = vec![read_trie(state_root_hash)?];
+        while let Some((_, unwritten_children)) = stack.last_mut() {
+            match unwritten_children.pop() {
+                Some(child) => stack.push(read_trie(child)?),
+                None => {
+                    let (trie_raw, _) = stack.pop().expect("stack should not be empty");
+                    snapshot.push(trie_raw)?;
+                }
+            }
+        }
+
+        Ok(snapshot.finish()?)
+    }
+
+    /// Imports the global state held by a snapshot read from `reader`.
+    ///
+    /// Each trie node is only put into global state if all of its children are present, and the
+    /// snapshot must end with the trie node of its state root hash.
+    pub fn import_snapshot<R: Read>(
+        &self,
+        correlation_id: CorrelationId,
+        reader: R,
+    ) -> Result<SnapshotSummary, Error>
+    where
+        Error: From<S::Error>,
+    {
+        let mut snapshot = SnapshotReader::new(reader)?;
+        let mut trie_count = 0;
+        let mut last_trie_hash = None;
+        while let Some(tries) = snapshot.next_chunk()? {
+            for trie_raw in tries {
+                let trie_hash =
+                    self.put_trie_if_all_children_present(correlation_id, trie_raw.inner())?;
+                last_trie_hash = Some(trie_hash);
+                trie_count += 1;
+            }
+        }
+
+        let state_root_hash = snapshot.state_root_hash();
+        if last_trie_hash != Some(state_root_hash) {
+            return Err(SnapshotError::RootMismatch {
+                expected: state_root_hash,
+                actual: last_trie_hash,
+            }
+            .into());
+        }
+        Ok(SnapshotSummary {
+            state_root_hash,
+            trie_count,
+            chunk_count: snapshot.chunk_count(),
+        })
+    }
+
     /// Obtains validator weights for given era.
     ///
     /// This skips execution of auction's `get_era_validator` entry point logic to avoid creating an
@@ -1889,7 +2208,7 @@ where
         Ok(era_validators_result)
     }
 
-    /// Gets current bids from the auction system.
+    /// Gets current bids from the auction system, filtered and paginated as requested.
     pub fn get_bids(
         &self,
         correlation_id: CorrelationId,
@@ -1902,21 +2221,195 @@ where
 
         let mut tracking_copy = tracking_copy.borrow_mut();
 
-        let bid_keys = tracking_copy
-            .get_keys(correlation_id, &KeyTag::Bid)
-            .map_err(|err| Error::Exec(err.into()))?;
+        // Requesting given validators avoids scanning the whole bid key space.
+        let bid_keys = match get_bids_request.validators() {
+            Some(validators) => validators
+                .iter()
+                .map(|public_key| Key::Bid(public_key.to_account_hash()))
+                .collect(),
+            None => tracking_copy
+                .get_keys(correlation_id, &KeyTag::Bid)
+                .map_err(|err| Error::Exec(err.into()))?,
+        };
+        let lower_bound = match get_bids_request.continuation_token() {
+            Some(account_hash) => Bound::Excluded(Key::Bid(account_hash)),
+            None => Bound::Unbounded,
+        };
+        let page_size = get_bids_request.page_size().unwrap_or(usize::MAX).max(1);
 
         let mut bids = BTreeMap::new();
+        let mut remaining_keys = bid_keys.range((lower_bound, Bound::Unbounded)).peekable();
+        let mut continuation_token = None;
+
+        while let Some(key) = remaining_keys.next() {
+            let bid = match tracking_copy.get(correlation_id, key).map_err(Into::into)? {
+                Some(StoredValue::Bid(bid)) => bid,
+                _ => continue,
+            };
+            let status_matches = match get_bids_request.status() {
+                Some(BidStatus::Active) => !bid.inactive(),
+                Some(BidStatus::Inactive) => bid.inactive(),
+                None => true,
+            };
+            if !status_matches {
+                continue;
+            }
+            let account_hash = bid.validator_public_key().to_account_hash();
+            bids.insert(bid.validator_public_key().clone(), *bid);
+            if bids.len() >= page_size {
+                if remaining_keys.peek().is_some() {
+                    continuation_token = Some(account_hash);
+                }
+                break;
+            }
+        }
+
+        Ok(GetBidsResult::Success {
+            bids,
+            continuation_token,
+        })
+    }
+
+    /// Gets all unbonding purses of a public key from the auction system, both as a validator and
+    /// as a delegator.
+    ///
+    /// Only purses unbonded by the given public key are returned, i.e. those of its delegators are
+    /// not included when querying for a validator.  Purses stored under the legacy `Key::Withdraw`
+    /// key space are included alongside those stored under `Key::Unbond`.  Each purse is
+    /// returned with the era in which it is released, based on the unbonding delay in effect
+    /// under the given state hash.
+    pub fn get_unbonding_purses(
+        &self,
+        correlation_id: CorrelationId,
+        get_unbonding_purses_request: GetUnbondingPursesRequest,
+    ) -> Result<GetUnbondingPursesResult, Error> {
+        let state_hash = get_unbonding_purses_request.state_hash();
+        let mut tracking_copy = match self.tracking_copy(state_hash)? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(GetUnbondingPursesResult::RootNotFound),
+        };
+
+        let auction_hash = *self
+            .get_system_contract_registry(correlation_id, state_hash)?
+            .get(AUCTION)
+            .ok_or_else(|| Error::MissingSystemContractHash(AUCTION.to_string()))?;
+        let auction_contract = tracking_copy.get_contract(correlation_id, auction_hash)?;
+        let unbonding_delay_key = auction_contract
+            .named_keys()
+            .get(UNBONDING_DELAY_KEY)
+            .copied()
+            .ok_or_else(|| {
+                Error::Exec(execution::Error::NamedKeyNotFound(
+                    UNBONDING_DELAY_KEY.to_string(),
+                ))
+            })?;
+        let unbonding_delay: u64 = match tracking_copy
+            .read(correlation_id, &unbonding_delay_key)
+            .map_err(Into::into)?
+        {
+            Some(StoredValue::CLValue(cl_value)) => cl_value
+                .into_t()
+                .map_err(|_| Error::Bytesrepr(UNBONDING_DELAY_KEY.to_string()))?,
+            _ => return Err(Error::Bytesrepr(UNBONDING_DELAY_KEY.to_string())),
+        };
 
-        for key in bid_keys.iter() {
-            if let Some(StoredValue::Bid(bid)) =
+        let public_key = get_unbonding_purses_request.public_key();
+        let is_relevant =
+            |unbonding_purse: &UnbondingPurse| unbonding_purse.unbonder_public_key() == public_key;
+        let mut unbonding_purses = Vec::new();
+
+        let unbond_keys = tracking_copy
+            .get_keys(correlation_id, &KeyTag::Unbond)
+            .map_err(|err| Error::Exec(err.into()))?;
+        for key in unbond_keys.iter() {
+            if let Some(StoredValue::Unbonding(purses)) =
                 tracking_copy.get(correlation_id, key).map_err(Into::into)?
             {
-                bids.insert(bid.validator_public_key().clone(), *bid);
-            };
+                unbonding_purses.extend(purses.into_iter().filter(is_relevant).map(
+                    |unbonding_purse| {
+                        UnbondingPurseEntry::new(
+                            UnbondingPurseKind::Unbond,
+                            unbonding_purse,
+                            unbonding_delay,
+                        )
+                    },
+                ));
+            }
+        }
+
+        let withdraw_keys = tracking_copy
+            .get_keys(correlation_id, &KeyTag::Withdraw)
+            .map_err(|err| Error::Exec(err.into()))?;
+        for key in withdraw_keys.iter() {
+            if let Some(StoredValue::Withdraw(purses)) =
+                tracking_copy.get(correlation_id, key).map_err(Into::into)?
+            {
+                unbonding_purses.extend(
+                    purses
+                        .into_iter()
+                        .map(UnbondingPurse::from)
+                        .filter(is_relevant)
+                        .map(|unbonding_purse| {
+                            UnbondingPurseEntry::new(
+                                UnbondingPurseKind::Withdraw,
+                                unbonding_purse,
+                                unbonding_delay,
+                            )
+                        }),
+                );
+            }
         }
 
-        Ok(GetBidsResult::Success { bids })
+        Ok(GetUnbondingPursesResult::Success { unbonding_purses })
+    }
+
+    /// Gets an account or a contract from global state.
+    ///
+    /// The returned entity carries its named keys, and either the associated keys of the account
+    /// or the entry points of the contract, so that callers don't need to look them up
+    /// separately.
+    pub fn get_entity(
+        &self,
+        correlation_id: CorrelationId,
+        get_entity_request: GetEntityRequest,
+    ) -> Result<GetEntityResult, Error> {
+        let mut tracking_copy = match self.tracking_copy(get_entity_request.state_hash())? {
+            Some(tracking_copy) => tracking_copy,
+            None => return Ok(GetEntityResult::RootNotFound),
+        };
+
+        let entity_identifier = get_entity_request.entity_identifier();
+        let entity = match (
+            entity_identifier,
+            tracking_copy
+                .read(correlation_id, &entity_identifier.key())
+                .map_err(Into::into)?,
+        ) {
+            (
+                EntityIdentifier::ContractHash(contract_hash),
+                Some(StoredValue::Contract(contract)),
+            ) => Entity::Contract {
+                contract_hash: *contract_hash,
+                contract,
+            },
+            (
+                EntityIdentifier::PublicKey(_) | EntityIdentifier::AccountHash(_),
+                Some(StoredValue::Account(account)),
+            ) => Entity::Account(account),
+            _ => return Ok(GetEntityResult::EntityNotFound),
+        };
+
+        Ok(GetEntityResult::Success { entity })
+    }
+
+    /// Executes a built-in Wasm module through the same preprocessing and instantiation steps as
+    /// deploys, with the host functions of the given protocol version.
+    ///
+    /// No global state is involved.  This is intended to check the Wasm stack of the host, e.g. on
+    /// node startup.
+    pub fn run_wasm_self_test(&self, protocol_version: ProtocolVersion) -> Result<(), Error> {
+        runtime::self_test::execute_self_test_module(&self.config, protocol_version)
+            .map_err(Into::into)
     }
 
     /// Executes a step request.
@@ -2072,7 +2565,7 @@ where
             DirectSystemContractCall::RunAuction,
             run_auction_args,
             &virtual_system_account,
-            authorization_keys,
+            authorization_keys.clone(),
             BlockTime::default(),
             deploy_hash,
             gas_limit,
@@ -2089,6 +2582,45 @@ where
             return Err(StepError::AuctionError(exec_error));
         }
 
+        // Now that this era's rewards have been minted, snapshot the total supply the next era
+        // starts with.
+        let record_total_supply_args = RuntimeArgs::try_new(|args| {
+            args.insert(mint::ARG_ERA_ID, step_request.next_era_id)?;
+            Ok(())
+        })?;
+
+        let record_total_supply_stack = self.get_new_system_call_stack();
+        let (_, execution_result): (Option<()>, ExecutionResult) = executor.call_system_contract(
+            DirectSystemContractCall::RecordTotalSupply,
+            record_total_supply_args,
+            &virtual_system_account,
+            authorization_keys,
+            BlockTime::default(),
+            deploy_hash,
+            gas_limit,
+            step_request.protocol_version,
+            correlation_id,
+            Rc::clone(&tracking_copy),
+            Phase::Session,
+            record_total_supply_stack,
+            // Recording the total supply does not transfer tokens.
+            U512::zero(),
+        );
+
+        if let Some(exec_error) = execution_result.take_error() {
+            return Err(StepError::RecordTotalSupplyError(exec_error));
+        }
+
+        if let StorageRent::Lease { grace_eras, .. } = self.config.storage_rent() {
+            self.expire_storage_leases(
+                correlation_id,
+                &mut tracking_copy.borrow_mut(),
+                step_request.next_era_id,
+                grace_eras,
+            )
+            .map_err(StepError::ExpireStorageLeasesError)?;
+        }
+
         let execution_effect = tracking_copy.borrow().effect();
         let execution_journal = tracking_copy.borrow().execution_journal();
 
@@ -2108,6 +2640,51 @@ where
         })
     }
 
+    /// Marks the storage leases which are due in `era_id` as expired.
+    ///
+    /// Only the leases indexed under `era_id` when they were last renewed are visited.  Leases
+    /// renewed since then are due in a later era and are left untouched.
+    fn expire_storage_leases(
+        &self,
+        correlation_id: CorrelationId,
+        tracking_copy: &mut TrackingCopy<S::Reader>,
+        era_id: EraId,
+        grace_eras: u64,
+    ) -> Result<(), Error> {
+        let expiry_keys = tracking_copy
+            .reader()
+            .keys_with_prefix(correlation_id, &Key::storage_lease_expiry_prefix(era_id))
+            .map_err(|err| Error::Exec(err.into()))?;
+
+        for expiry_key in expiry_keys {
+            let lease_key: Key = match tracking_copy
+                .get(correlation_id, &expiry_key)
+                .map_err(Into::into)?
+            {
+                Some(StoredValue::CLValue(cl_value)) => {
+                    cl_value.into_t().map_err(|err| Error::Exec(err.into()))?
+                }
+                _ => continue,
+            };
+            let mut lease: StorageLease = match tracking_copy
+                .get(correlation_id, &lease_key)
+                .map_err(Into::into)?
+            {
+                Some(StoredValue::CLValue(cl_value)) => {
+                    cl_value.into_t().map_err(|err| Error::Exec(err.into()))?
+                }
+                _ => continue,
+            };
+            if lease.is_due(era_id, grace_eras) {
+                lease.expire();
+                let cl_value = CLValue::from_t(lease).map_err(|err| Error::Exec(err.into()))?;
+                tracking_copy.write(lease_key, StoredValue::CLValue(cl_value));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Gets the balance of a given public key.
     pub fn get_balance(
         &self,
@@ -2282,6 +2859,7 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
             transfers,
             cost,
             execution_journal,
+            ..
         } => {
             debug!(
                 %cost,
@@ -2296,6 +2874,7 @@ fn log_execution_result(preamble: &'static str, result: &ExecutionResult) {
             transfers,
             cost,
             execution_journal,
+            ..
         } => {
             debug!(
                 %error,
@@ -2316,6 +2895,7 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             transfers: _,
             cost: _,
             execution_journal: _,
+            payment_summary: _,
         } => match error {
             Error::Exec(err) => match err {
                 ExecError::WasmPreprocessing(_) | ExecError::UnsupportedWasmStart => true,
@@ -2365,7 +2945,12 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
                 | ExecError::ValueTooLarge
                 | ExecError::MissingRuntimeStack
                 | ExecError::DisabledContract(_)
-                | ExecError::DisabledUnrestrictedTransfers => false,
+                | ExecError::DisabledUnrestrictedTransfers
+                | ExecError::CLValueTooLarge { .. }
+                | ExecError::TooManyNamedKeys { .. }
+                | ExecError::StorageLeaseExpired(_)
+                | ExecError::DisabledAccountCreation
+                | ExecError::AccountCreationAmountTooLow { .. } => false,
             },
             Error::WasmPreprocessing(_) => true,
             Error::WasmSerialization(_) => true,
@@ -2394,7 +2979,8 @@ fn should_charge_for_errors_in_wasm(execution_result: &ExecutionResult) -> bool
             | Error::FailedToRetrieveUnbondingDelay
             | Error::FailedToRetrieveEraId
             | Error::MissingTrieNodeChildren(_)
-            | Error::FailedToRetrieveAccumulationPurse => false,
+            | Error::FailedToRetrieveAccumulationPurse
+            | Error::Snapshot(_) => false,
         },
         ExecutionResult::Success { .. } => false,
     }
@@ -1,9 +1,14 @@
 //! Support for global state queries.
 use casper_hashing::Digest;
-use casper_types::{Key, StoredValue};
+use casper_types::{Key, KeyTag, StoredValue, URef};
 
 use crate::{
-    core::tracking_copy::TrackingCopyQueryResult, storage::trie::merkle_proof::TrieMerkleProof,
+    core::{
+        engine_state::Error, execution, runtime_context::dictionary::DictionaryValue,
+        tracking_copy::TrackingCopyQueryResult,
+    },
+    shared::newtypes::CorrelationId,
+    storage::{global_state::StateReader, trie::merkle_proof::TrieMerkleProof},
 };
 
 /// Result of a global state query request.
@@ -63,6 +68,202 @@ impl QueryRequest {
     }
 }
 
+/// A collection of values in global state which can be streamed by a [`QueryStreamRequest`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryStreamTarget {
+    /// All values stored under keys with the given tag, e.g. all bids.
+    KeyTag(KeyTag),
+    /// All items of the dictionary with the given seed [`URef`].
+    Dictionary(URef),
+}
+
+/// Request for a global state query streaming the values of a collection in chunks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryStreamRequest {
+    state_hash: Digest,
+    target: QueryStreamTarget,
+    chunk_size: usize,
+    start_after: Option<Key>,
+}
+
+impl QueryStreamRequest {
+    /// Creates new request object, streaming `chunk_size` values at a time.
+    pub fn new(state_hash: Digest, target: QueryStreamTarget, chunk_size: usize) -> Self {
+        QueryStreamRequest {
+            state_hash,
+            target,
+            chunk_size,
+            start_after: None,
+        }
+    }
+
+    /// Resumes a previous stream: only values stored under keys whose serialized form sorts after
+    /// that of `key` are streamed.
+    ///
+    /// Passing the [`QueryChunk::last_key`] of the last chunk received allows paginating through a
+    /// collection over several requests.
+    pub fn with_start_after(mut self, key: Key) -> Self {
+        self.start_after = Some(key);
+        self
+    }
+
+    /// Returns state root hash.
+    pub fn state_hash(&self) -> Digest {
+        self.state_hash
+    }
+
+    /// Returns the streamed collection.
+    pub fn target(&self) -> QueryStreamTarget {
+        self.target
+    }
+
+    /// Returns the maximum number of values per chunk.
+    pub fn chunk_size(&self) -> usize {
+        self.chunk_size
+    }
+
+    /// Returns the key after which the stream starts, if any.
+    pub fn start_after(&self) -> Option<Key> {
+        self.start_after
+    }
+}
+
+/// A chunk of the values streamed by a [`QueryStream`].
+#[derive(Debug)]
+pub struct QueryChunk {
+    /// Stored values with their keys, in the order of the keys' serialized forms.
+    ///
+    /// Dictionary items are unwrapped, as with [`QueryResult`].
+    pub values: Vec<(Key, StoredValue)>,
+    /// Merkle proofs of the values, in the same order.
+    pub proofs: Vec<TrieMerkleProof<Key, StoredValue>>,
+}
+
+impl QueryChunk {
+    /// Returns the key of the last value of the chunk.
+    pub fn last_key(&self) -> Option<Key> {
+        self.values.last().map(|(key, _)| *key)
+    }
+}
+
+/// An iterator over the values of a collection in global state, yielding them in chunks.
+///
+/// Keys, values and their proofs are read one chunk at a time as the stream is advanced, so that a
+/// large collection is never held in memory as a whole.
+pub struct QueryStream<R> {
+    correlation_id: CorrelationId,
+    reader: R,
+    target: QueryStreamTarget,
+    prefix: Vec<u8>,
+    /// The last key read, after which the next chunk of keys starts.
+    start_after: Option<Key>,
+    /// Whether all keys of the collection have been read.
+    exhausted: bool,
+    chunk_size: usize,
+}
+
+impl<R> QueryStream<R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    pub(crate) fn new(
+        correlation_id: CorrelationId,
+        reader: R,
+        request: &QueryStreamRequest,
+    ) -> Result<Self, Error> {
+        let key_tag = match request.target() {
+            QueryStreamTarget::KeyTag(key_tag) => key_tag,
+            // Dictionary items are stored under hashes of their seed URef and item key, so the
+            // items of a dictionary can only be found among all dictionary keys.
+            QueryStreamTarget::Dictionary(_) => KeyTag::Dictionary,
+        };
+        Ok(QueryStream {
+            correlation_id,
+            reader,
+            target: request.target(),
+            prefix: vec![key_tag as u8],
+            start_after: request.start_after(),
+            exhausted: false,
+            chunk_size: request.chunk_size().max(1),
+        })
+    }
+
+    /// Reads the next page of at most `limit` keys of the collection.
+    fn next_keys(&mut self, limit: usize) -> Result<Vec<Key>, Error> {
+        let keys = self
+            .reader
+            .keys_with_prefix_after(
+                self.correlation_id,
+                &self.prefix,
+                self.start_after.as_ref(),
+                limit,
+            )
+            .map_err(|err| Error::Exec(err.into()))?;
+        self.exhausted = keys.len() < limit;
+        if let Some(last_key) = keys.last() {
+            self.start_after = Some(*last_key);
+        }
+        Ok(keys)
+    }
+
+    /// Returns the value to stream from what is stored under `key`, if it is part of the target.
+    fn streamed_value(&self, key: Key, value: StoredValue) -> Option<StoredValue> {
+        let seed_uref = match self.target {
+            QueryStreamTarget::KeyTag(_) => return Some(value),
+            QueryStreamTarget::Dictionary(seed_uref) => seed_uref,
+        };
+        let cl_value = match (key, value) {
+            (Key::Dictionary(_), StoredValue::CLValue(cl_value)) => cl_value,
+            _ => return None,
+        };
+        let dictionary_value: DictionaryValue = cl_value.into_t().ok()?;
+        if dictionary_value.seed_uref_addr() != seed_uref.addr() {
+            return None;
+        }
+        Some(StoredValue::CLValue(dictionary_value.into_cl_value()))
+    }
+}
+
+impl<R> Iterator for QueryStream<R>
+where
+    R: StateReader<Key, StoredValue>,
+    R::Error: Into<execution::Error>,
+{
+    type Item = Result<QueryChunk, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = QueryChunk {
+            values: Vec::with_capacity(self.chunk_size),
+            proofs: Vec::with_capacity(self.chunk_size),
+        };
+        // Items of other dictionaries are skipped, so filling a chunk may take several pages of
+        // keys.
+        while chunk.values.len() < self.chunk_size && !self.exhausted {
+            let keys = match self.next_keys(self.chunk_size - chunk.values.len()) {
+                Ok(keys) => keys,
+                Err(error) => return Some(Err(error)),
+            };
+            for key in keys {
+                let proof = match self.reader.read_with_proof(self.correlation_id, &key) {
+                    Ok(Some(proof)) => proof,
+                    Ok(None) => continue,
+                    Err(err) => return Some(Err(Error::Exec(err.into()))),
+                };
+                if let Some(value) = self.streamed_value(key, proof.value().clone()) {
+                    chunk.values.push((key, value));
+                    chunk.proofs.push(proof);
+                }
+            }
+        }
+        if chunk.values.is_empty() {
+            None
+        } else {
+            Some(Ok(chunk))
+        }
+    }
+}
+
 impl From<TrackingCopyQueryResult> for QueryResult {
     fn from(tracking_copy_query_result: TrackingCopyQueryResult) -> Self {
         match tracking_copy_query_result {
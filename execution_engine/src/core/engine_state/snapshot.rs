@@ -0,0 +1,341 @@
+//! Support for exporting global state into portable snapshots, and importing them.
+//!
+//! A snapshot holds every trie node reachable from a state root hash, each node following all of
+//! its descendants.  It can thus be imported node by node into an empty global state, with every
+//! node being checked to have all its children present before being put.  A snapshot is laid out
+//! as:
+//!
+//! * a header made of [`SNAPSHOT_MAGIC`], the format version as a little-endian `u32` and the state
+//!   root hash,
+//! * chunks, each made of its length as a little-endian `u32`, the serialized raw trie nodes it
+//!   holds and the hash of those bytes as a checksum,
+//! * a zero length marking the end of the snapshot.
+use std::{
+    convert::TryFrom,
+    io::{self, Read, Write},
+    mem,
+};
+
+use thiserror::Error;
+
+use casper_hashing::Digest;
+use casper_types::bytesrepr;
+
+use crate::storage::trie::TrieRaw;
+
+/// The bytes every snapshot starts with.
+pub const SNAPSHOT_MAGIC: [u8; 8] = *b"CSPRSNAP";
+
+/// The version of the snapshot format.
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 1;
+
+/// The size in bytes of the trie nodes beyond which a chunk is written out.
+const TARGET_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The maximum size in bytes of a chunk.
+const MAX_CHUNK_SIZE: u32 = 256 * 1024 * 1024;
+
+/// An error exporting or importing a snapshot.
+#[derive(Clone, Error, Debug, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// Reading or writing the snapshot failed.
+    #[error("I/O error: {0}")]
+    Io(String),
+    /// The data doesn't start with the snapshot magic bytes.
+    #[error("not a global state snapshot")]
+    NotASnapshot,
+    /// The snapshot format version is not supported.
+    #[error("unsupported snapshot format version {0}")]
+    UnsupportedVersion(u32),
+    /// A chunk exceeds the maximum chunk size.
+    #[error("chunk {index} of {size} bytes exceeds the maximum chunk size")]
+    ChunkTooLarge {
+        /// The index of the chunk.
+        index: u64,
+        /// The size of the chunk in bytes.
+        size: u64,
+    },
+    /// A chunk doesn't match its checksum.
+    #[error("checksum mismatch in chunk {index}")]
+    ChecksumMismatch {
+        /// The index of the chunk.
+        index: u64,
+    },
+    /// A chunk could not be serialized or deserialized.
+    #[error("malformed chunk {index}: {error}")]
+    MalformedChunk {
+        /// The index of the chunk.
+        index: u64,
+        /// The serialization error.
+        error: bytesrepr::Error,
+    },
+    /// A trie node reachable from the exported state root hash is missing from global state.
+    #[error("missing trie node {0}")]
+    MissingTrie(Digest),
+    /// The last trie node of the snapshot is not its state root.
+    #[error("snapshot of state root hash {expected} ends with trie node {actual:?}")]
+    RootMismatch {
+        /// The state root hash in the snapshot's header.
+        expected: Digest,
+        /// The hash of the last trie node of the snapshot, if any.
+        actual: Option<Digest>,
+    },
+}
+
+impl From<io::Error> for SnapshotError {
+    fn from(error: io::Error) -> Self {
+        SnapshotError::Io(error.to_string())
+    }
+}
+
+/// A summary of an exported or imported snapshot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SnapshotSummary {
+    /// The state root hash of the snapshot.
+    pub state_root_hash: Digest,
+    /// The number of trie nodes in the snapshot.
+    pub trie_count: u64,
+    /// The number of chunks in the snapshot.
+    pub chunk_count: u64,
+}
+
+/// Writes a snapshot, chunk by chunk.
+pub(crate) struct SnapshotWriter<W> {
+    writer: W,
+    chunk: Vec<TrieRaw>,
+    chunk_size: usize,
+    summary: SnapshotSummary,
+}
+
+impl<W: Write> SnapshotWriter<W> {
+    /// Writes the header of the snapshot of `state_root_hash`.
+    pub(crate) fn new(mut writer: W, state_root_hash: Digest) -> Result<Self, SnapshotError> {
+        writer.write_all(&SNAPSHOT_MAGIC)?;
+        writer.write_all(&SNAPSHOT_FORMAT_VERSION.to_le_bytes())?;
+        writer.write_all(state_root_hash.as_ref())?;
+        Ok(SnapshotWriter {
+            writer,
+            chunk: Vec::new(),
+            chunk_size: 0,
+            summary: SnapshotSummary {
+                state_root_hash,
+                trie_count: 0,
+                chunk_count: 0,
+            },
+        })
+    }
+
+    /// Adds a trie node to the snapshot, after all of its descendants.
+    pub(crate) fn push(&mut self, trie: TrieRaw) -> Result<(), SnapshotError> {
+        self.chunk_size += trie.inner().len();
+        self.chunk.push(trie);
+        self.summary.trie_count += 1;
+        if self.chunk_size >= TARGET_CHUNK_SIZE {
+            self.write_chunk()?;
+        }
+        Ok(())
+    }
+
+    /// Writes the remaining trie nodes and the end of the snapshot.
+    pub(crate) fn finish(mut self) -> Result<SnapshotSummary, SnapshotError> {
+        if !self.chunk.is_empty() {
+            self.write_chunk()?;
+        }
+        self.writer.write_all(&0u32.to_le_bytes())?;
+        self.writer.flush()?;
+        Ok(self.summary)
+    }
+
+    fn write_chunk(&mut self) -> Result<(), SnapshotError> {
+        let index = self.summary.chunk_count;
+        let bytes = bytesrepr::serialize(mem::take(&mut self.chunk))
+            .map_err(|error| SnapshotError::MalformedChunk { index, error })?;
+        let size = u32::try_from(bytes.len())
+            .ok()
+            .filter(|size| *size <= MAX_CHUNK_SIZE)
+            .ok_or(SnapshotError::ChunkTooLarge {
+                index,
+                size: bytes.len() as u64,
+            })?;
+        self.writer.write_all(&size.to_le_bytes())?;
+        self.writer.write_all(&bytes)?;
+        self.writer.write_all(Digest::hash(&bytes).as_ref())?;
+        self.chunk_size = 0;
+        self.summary.chunk_count += 1;
+        Ok(())
+    }
+}
+
+/// Reads a snapshot, chunk by chunk, verifying the checksum of each chunk.
+pub(crate) struct SnapshotReader<R> {
+    reader: R,
+    state_root_hash: Digest,
+    chunk_count: u64,
+}
+
+impl<R: Read> SnapshotReader<R> {
+    /// Reads and checks the header of a snapshot.
+    pub(crate) fn new(mut reader: R) -> Result<Self, SnapshotError> {
+        let mut magic = [0; SNAPSHOT_MAGIC.len()];
+        reader.read_exact(&mut magic)?;
+        if magic != SNAPSHOT_MAGIC {
+            return Err(SnapshotError::NotASnapshot);
+        }
+        let version = read_u32(&mut reader)?;
+        if version != SNAPSHOT_FORMAT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let state_root_hash = read_digest(&mut reader)?;
+        Ok(SnapshotReader {
+            reader,
+            state_root_hash,
+            chunk_count: 0,
+        })
+    }
+
+    /// Returns the state root hash of the snapshot.
+    pub(crate) fn state_root_hash(&self) -> Digest {
+        self.state_root_hash
+    }
+
+    /// Returns the number of chunks read so far.
+    pub(crate) fn chunk_count(&self) -> u64 {
+        self.chunk_count
+    }
+
+    /// Reads the trie nodes of the next chunk, or returns `None` at the end of the snapshot.
+    pub(crate) fn next_chunk(&mut self) -> Result<Option<Vec<TrieRaw>>, SnapshotError> {
+        let index = self.chunk_count;
+        let size = read_u32(&mut self.reader)?;
+        if size == 0 {
+            return Ok(None);
+        }
+        if size > MAX_CHUNK_SIZE {
+            return Err(SnapshotError::ChunkTooLarge {
+                index,
+                size: size.into(),
+            });
+        }
+        let mut bytes = vec![0; size as usize];
+        self.reader.read_exact(&mut bytes)?;
+        if read_digest(&mut self.reader)? != Digest::hash(&bytes) {
+            return Err(SnapshotError::ChecksumMismatch { index });
+        }
+        let tries = bytesrepr::deserialize(bytes)
+            .map_err(|error| SnapshotError::MalformedChunk { index, error })?;
+        self.chunk_count += 1;
+        Ok(Some(tries))
+    }
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> Result<u32, SnapshotError> {
+    let mut bytes = [0; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_digest<R: Read>(reader: &mut R) -> Result<Digest, SnapshotError> {
+    let mut bytes = [0; Digest::LENGTH];
+    reader.read_exact(&mut bytes)?;
+    Ok(Digest::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{CLValue, Key, StoredValue};
+
+    use super::*;
+    use crate::{
+        core::engine_state::{EngineConfig, EngineState, Error},
+        shared::newtypes::CorrelationId,
+        storage::global_state::{in_memory::InMemoryGlobalState, StateProvider, StateReader},
+    };
+
+    fn source_state(correlation_id: CorrelationId) -> (Vec<(Key, StoredValue)>, Digest, Vec<u8>) {
+        let pairs: Vec<(Key, StoredValue)> = (0..100u8)
+            .map(|i| {
+                let value = CLValue::from_t(u64::from(i)).unwrap();
+                (Key::Hash([i; 32]), StoredValue::CLValue(value))
+            })
+            .collect();
+        let (state, state_root_hash) =
+            InMemoryGlobalState::from_pairs(correlation_id, &pairs).unwrap();
+        let engine_state = EngineState::new(state, EngineConfig::default());
+
+        let mut snapshot = Vec::new();
+        let summary = engine_state
+            .export_snapshot(correlation_id, state_root_hash, &mut snapshot)
+            .unwrap();
+        assert_eq!(summary.state_root_hash, state_root_hash);
+        assert_eq!(summary.chunk_count, 1);
+        (pairs, state_root_hash, snapshot)
+    }
+
+    #[test]
+    fn should_import_exported_snapshot() {
+        let correlation_id = CorrelationId::new();
+        let (pairs, state_root_hash, snapshot) = source_state(correlation_id);
+
+        let engine_state = EngineState::new(
+            InMemoryGlobalState::empty().unwrap(),
+            EngineConfig::default(),
+        );
+        let summary = engine_state
+            .import_snapshot(correlation_id, snapshot.as_slice())
+            .unwrap();
+        assert_eq!(summary.state_root_hash, state_root_hash);
+        assert_eq!(summary.chunk_count, 1);
+
+        let reader = engine_state
+            .state
+            .checkout(state_root_hash)
+            .unwrap()
+            .expect("imported state root should exist");
+        for (key, value) in &pairs {
+            assert_eq!(
+                reader.read(correlation_id, key).unwrap().as_ref(),
+                Some(value)
+            );
+        }
+    }
+
+    #[test]
+    fn should_reject_corrupted_snapshot() {
+        let correlation_id = CorrelationId::new();
+        let (_, _, mut snapshot) = source_state(correlation_id);
+        let engine_state = EngineState::new(
+            InMemoryGlobalState::empty().unwrap(),
+            EngineConfig::default(),
+        );
+
+        // Flip a byte of the first chunk, right after the header and the chunk's length.
+        let header_len = SNAPSHOT_MAGIC.len() + 4 + Digest::LENGTH;
+        snapshot[header_len + 4] ^= 1;
+        assert!(matches!(
+            engine_state.import_snapshot(correlation_id, snapshot.as_slice()),
+            Err(Error::Snapshot(SnapshotError::ChecksumMismatch {
+                index: 0
+            }))
+        ));
+
+        snapshot[0] ^= 1;
+        assert!(matches!(
+            engine_state.import_snapshot(correlation_id, snapshot.as_slice()),
+            Err(Error::Snapshot(SnapshotError::NotASnapshot))
+        ));
+    }
+
+    #[test]
+    fn should_not_export_unknown_state_root() {
+        let correlation_id = CorrelationId::new();
+        let engine_state = EngineState::new(
+            InMemoryGlobalState::empty().unwrap(),
+            EngineConfig::default(),
+        );
+        let state_root_hash = Digest::hash([1u8]);
+        assert!(matches!(
+            engine_state.export_snapshot(correlation_id, state_root_hash, Vec::new()),
+            Err(Error::RootNotFound(root)) if root == state_root_hash
+        ));
+    }
+}
@@ -154,6 +154,12 @@ pub enum StepError {
     /// Error executing a distribute accumulated fees operation.
     #[error("Distribute accumulated fees error: {0}")]
     DistributeAccumulatedFeesError(Error),
+    /// Error recording the total supply.
+    #[error("Record total supply error: {0}")]
+    RecordTotalSupplyError(Error),
+    /// Error expiring storage leases.
+    #[error("Expire storage leases error: {0}")]
+    ExpireStorageLeasesError(Error),
     /// Invalid protocol version.
     #[error("Invalid protocol version: {0}")]
     InvalidProtocolVersion(ProtocolVersion),
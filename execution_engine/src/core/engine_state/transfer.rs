@@ -3,8 +3,8 @@ use std::{cell::RefCell, convert::TryFrom, rc::Rc};
 use casper_types::{
     account::{Account, AccountHash},
     system::mint,
-    AccessRights, ApiError, CLType, CLValueError, Key, PublicKey, RuntimeArgs, StoredValue, URef,
-    U512,
+    AccessRights, ApiError, CLType, CLValueError, Key, PublicKey, RuntimeArgs, StoredValue,
+    TransferMemo, URef, U512,
 };
 
 use crate::{
@@ -56,6 +56,7 @@ pub struct TransferArgs {
     target: URef,
     amount: U512,
     arg_id: Option<u64>,
+    memo: Option<TransferMemo>,
 }
 
 impl TransferArgs {
@@ -66,6 +67,7 @@ impl TransferArgs {
         target: URef,
         amount: U512,
         arg_id: Option<u64>,
+        memo: Option<TransferMemo>,
     ) -> Self {
         Self {
             to,
@@ -73,6 +75,7 @@ impl TransferArgs {
             target,
             amount,
             arg_id,
+            memo,
         }
     }
 
@@ -86,6 +89,11 @@ impl TransferArgs {
         self.source
     }
 
+    /// Returns `target` field.
+    pub fn target(&self) -> URef {
+        self.target
+    }
+
     /// Returns `arg_id` field.
     pub fn arg_id(&self) -> Option<u64> {
         self.arg_id
@@ -95,6 +103,11 @@ impl TransferArgs {
     pub fn amount(&self) -> U512 {
         self.amount
     }
+
+    /// Returns `memo` field.
+    pub fn memo(&self) -> Option<TransferMemo> {
+        self.memo
+    }
 }
 
 impl TryFrom<TransferArgs> for RuntimeArgs {
@@ -108,6 +121,11 @@ impl TryFrom<TransferArgs> for RuntimeArgs {
         runtime_args.insert(mint::ARG_TARGET, transfer_args.target)?;
         runtime_args.insert(mint::ARG_AMOUNT, transfer_args.amount)?;
         runtime_args.insert(mint::ARG_ID, transfer_args.arg_id)?;
+        // The memo is only passed when set, so that the arguments of transfers without one are
+        // unchanged.
+        if transfer_args.memo.is_some() {
+            runtime_args.insert(mint::ARG_MEMO, transfer_args.memo)?;
+        }
 
         Ok(runtime_args)
     }
@@ -295,7 +313,7 @@ impl TransferRuntimeArgsBuilder {
     /// Resolves amount.
     ///
     /// User has to specify "amount" argument that could be either a [`U512`] or a u64.
-    fn resolve_amount(&self) -> Result<U512, Error> {
+    pub(crate) fn resolve_amount(&self) -> Result<U512, Error> {
         let imputed_runtime_args = &self.inner;
 
         let amount = match imputed_runtime_args.get(mint::ARG_AMOUNT) {
@@ -330,6 +348,16 @@ impl TransferRuntimeArgsBuilder {
         Ok(id)
     }
 
+    /// Resolves the optional memo.
+    ///
+    /// Unlike the id, the memo argument may be omitted entirely.
+    fn resolve_memo(&self) -> Result<Option<TransferMemo>, Error> {
+        match self.inner.get(mint::ARG_MEMO) {
+            Some(memo_value) => memo_value.clone().into_t().map_err(Error::reverter),
+            None => Ok(None),
+        }
+    }
+
     /// Creates new [`TransferArgs`] instance.
     pub fn build<R>(
         mut self,
@@ -368,12 +396,15 @@ impl TransferRuntimeArgsBuilder {
 
         let id = self.resolve_id()?;
 
+        let memo = self.resolve_memo()?;
+
         Ok(TransferArgs {
             to,
             source: source_uref,
             target: target_uref,
             amount,
             arg_id: id,
+            memo,
         })
     }
 }
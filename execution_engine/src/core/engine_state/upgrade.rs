@@ -8,7 +8,11 @@ use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::{self, ToBytes},
     contracts::NamedKeys,
-    system::{handle_payment::ACCUMULATION_PURSE_KEY, SystemContractType},
+    system::{
+        handle_payment::ACCUMULATION_PURSE_KEY,
+        mint::{HISTORICAL_TOTAL_SUPPLY_KEY, MINT_PAUSED_KEY},
+        SystemContractType,
+    },
     AccessRights, CLValue, CLValueError, Contract, ContractHash, EraId, Key, Phase,
     ProtocolVersion, StoredValue, U512,
 };
@@ -56,6 +60,7 @@ pub struct UpgradeConfig {
     new_locked_funds_period_millis: Option<u64>,
     new_round_seigniorage_rate: Option<Ratio<u64>>,
     new_unbonding_delay: Option<u64>,
+    new_mint_paused: Option<bool>,
     global_state_update: BTreeMap<Key, StoredValue>,
     chainspec_registry: ChainspecRegistry,
 }
@@ -73,6 +78,7 @@ impl UpgradeConfig {
         new_locked_funds_period_millis: Option<u64>,
         new_round_seigniorage_rate: Option<Ratio<u64>>,
         new_unbonding_delay: Option<u64>,
+        new_mint_paused: Option<bool>,
         global_state_update: BTreeMap<Key, StoredValue>,
         chainspec_registry: ChainspecRegistry,
     ) -> Self {
@@ -86,6 +92,7 @@ impl UpgradeConfig {
             new_locked_funds_period_millis,
             new_round_seigniorage_rate,
             new_unbonding_delay,
+            new_mint_paused,
             global_state_update,
             chainspec_registry,
         }
@@ -136,6 +143,11 @@ impl UpgradeConfig {
         self.new_unbonding_delay
     }
 
+    /// Returns whether the mint should be paused after this upgrade.
+    pub fn new_mint_paused(&self) -> Option<bool> {
+        self.new_mint_paused
+    }
+
     /// Returns new map of emergency global state updates.
     pub fn global_state_update(&self) -> &BTreeMap<Key, StoredValue> {
         &self.global_state_update
@@ -411,4 +423,137 @@ where
 
         Ok(())
     }
+
+    /// Sets the flag pausing the mint, creating it in the mint system contract if its not present.
+    ///
+    /// The flag is missing on networks which did not support pausing the mint at genesis.
+    pub(crate) fn set_mint_paused(
+        &self,
+        correlation_id: CorrelationId,
+        mint_hash: &ContractHash,
+        mint_paused: bool,
+    ) -> Result<(), ProtocolUpgradeError> {
+        let system_contract = SystemContractType::Mint;
+        let contract_name = system_contract.contract_name();
+        let mut contract = if let StoredValue::Contract(contract) = self
+            .tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &Key::Hash(mint_hash.value()))
+            .map_err(|_| {
+                ProtocolUpgradeError::UnableToRetrieveSystemContract(contract_name.to_string())
+            })?
+            .ok_or_else(|| {
+                ProtocolUpgradeError::UnableToRetrieveSystemContract(contract_name.to_string())
+            })? {
+            contract
+        } else {
+            return Err(ProtocolUpgradeError::UnableToRetrieveSystemContract(
+                contract_name,
+            ));
+        };
+
+        let value = StoredValue::CLValue(CLValue::from_t(mint_paused)?);
+        if let Some(mint_paused_key) = contract.named_keys().get(MINT_PAUSED_KEY) {
+            self.tracking_copy
+                .borrow_mut()
+                .write(*mint_paused_key, value);
+            return Ok(());
+        }
+
+        // Seeded differently from the accumulation purse's generator, so that both can be created
+        // in the same upgrade.
+        let mut address_generator = {
+            let seed_bytes = (
+                self.old_protocol_version,
+                self.new_protocol_version,
+                MINT_PAUSED_KEY.to_string(),
+            )
+                .to_bytes()?;
+
+            AddressGenerator::new(&seed_bytes, Phase::System)
+        };
+
+        let mint_paused_uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy
+            .borrow_mut()
+            .write(Key::URef(mint_paused_uref), value);
+
+        let mut new_named_keys = NamedKeys::new();
+        new_named_keys.insert(MINT_PAUSED_KEY.into(), Key::from(mint_paused_uref));
+        contract.named_keys_append(&mut new_named_keys);
+
+        self.tracking_copy
+            .borrow_mut()
+            .write((*mint_hash).into(), StoredValue::Contract(contract));
+
+        Ok(())
+    }
+
+    /// Creates the dictionary of the total supply at the start of each era in the mint system
+    /// contract if its not present.
+    ///
+    /// The dictionary is missing on networks which did not record the total supply at genesis.
+    pub(crate) fn create_historical_total_supply_if_required(
+        &self,
+        correlation_id: CorrelationId,
+        mint_hash: &ContractHash,
+    ) -> Result<(), ProtocolUpgradeError> {
+        let system_contract = SystemContractType::Mint;
+        let contract_name = system_contract.contract_name();
+        let mut contract = if let StoredValue::Contract(contract) = self
+            .tracking_copy
+            .borrow_mut()
+            .read(correlation_id, &Key::Hash(mint_hash.value()))
+            .map_err(|_| {
+                ProtocolUpgradeError::UnableToRetrieveSystemContract(contract_name.to_string())
+            })?
+            .ok_or_else(|| {
+                ProtocolUpgradeError::UnableToRetrieveSystemContract(contract_name.to_string())
+            })? {
+            contract
+        } else {
+            return Err(ProtocolUpgradeError::UnableToRetrieveSystemContract(
+                contract_name,
+            ));
+        };
+
+        if contract
+            .named_keys()
+            .contains_key(HISTORICAL_TOTAL_SUPPLY_KEY)
+        {
+            return Ok(());
+        }
+
+        // Seeded differently from the other generators, so that all can be used in the same
+        // upgrade.
+        let mut address_generator = {
+            let seed_bytes = (
+                self.old_protocol_version,
+                self.new_protocol_version,
+                HISTORICAL_TOTAL_SUPPLY_KEY.to_string(),
+            )
+                .to_bytes()?;
+
+            AddressGenerator::new(&seed_bytes, Phase::System)
+        };
+
+        let historical_total_supply_uref = address_generator.new_uref(AccessRights::READ_ADD_WRITE);
+        self.tracking_copy.borrow_mut().write(
+            Key::URef(historical_total_supply_uref),
+            StoredValue::CLValue(CLValue::unit()),
+        );
+
+        let mut new_named_keys = NamedKeys::new();
+        new_named_keys.insert(
+            HISTORICAL_TOTAL_SUPPLY_KEY.into(),
+            Key::from(historical_total_supply_uref),
+        );
+        contract.named_keys_append(&mut new_named_keys);
+
+        self.tracking_copy
+            .borrow_mut()
+            .write((*mint_hash).into(), StoredValue::Contract(contract));
+
+        Ok(())
+    }
 }
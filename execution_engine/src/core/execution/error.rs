@@ -6,6 +6,7 @@ use casper_types::{
     account::{AddKeyFailure, RemoveKeyFailure, SetThresholdFailure, UpdateKeyFailure},
     bytesrepr, system, AccessRights, ApiError, CLType, CLValueError, ContractHash,
     ContractPackageHash, ContractVersionKey, ContractWasmHash, Key, StoredValueTypeMismatch, URef,
+    U512,
 };
 
 use crate::{
@@ -176,6 +177,35 @@ pub enum Error {
     /// Failed to transfer tokens on a private chain.
     #[error("Failed to transfer with unrestricted transfers disabled")]
     DisabledUnrestrictedTransfers,
+    /// An attempt to write a `CLValue` to global state whose serialized payload is too large.
+    #[error("CLValue of {size} bytes exceeds the maximum size of {max} bytes")]
+    CLValueTooLarge {
+        /// Size of the serialized payload in bytes.
+        size: usize,
+        /// Maximum allowed size in bytes.
+        max: u32,
+    },
+    /// An attempt to add a named key to an account or contract which already holds the maximum
+    /// number of named keys.
+    #[error("Named keys limit of {max} reached")]
+    TooManyNamedKeys {
+        /// Maximum allowed number of named keys.
+        max: u32,
+    },
+    /// An attempt to read a value whose storage lease has expired.
+    #[error("Storage lease of value under {0} has expired")]
+    StorageLeaseExpired(Key),
+    /// An attempt to create an account by transferring to it while account creation is disabled.
+    #[error("Failed to create account with account creation disabled")]
+    DisabledAccountCreation,
+    /// An attempt to create an account by transferring less than the minimum amount to it.
+    #[error("Transfer of {amount} motes is too low to create an account, minimum is {minimum_amount}")]
+    AccountCreationAmountTooLow {
+        /// Amount of the transfer in motes.
+        amount: U512,
+        /// Minimum amount of a transfer creating an account in motes.
+        minimum_amount: u64,
+    },
 }
 
 impl From<wasm_prep::PreprocessingError> for Error {
@@ -122,12 +122,14 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_summary: None,
             },
             Err(error) => ExecutionResult::Failure {
                 error: error.into(),
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_summary: None,
             },
         }
     }
@@ -197,12 +199,14 @@ impl Executor {
                 execution_journal: runtime.context().execution_journal(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_summary: None,
             },
             Err(error) => ExecutionResult::Failure {
                 execution_journal,
                 error: error.into(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_summary: None,
             },
         }
     }
@@ -259,7 +263,9 @@ impl Executor {
                     .expect("should have auction hash");
                 *auction_hash
             }
-            DirectSystemContractCall::CreatePurse | DirectSystemContractCall::Transfer => {
+            DirectSystemContractCall::CreatePurse
+            | DirectSystemContractCall::Transfer
+            | DirectSystemContractCall::RecordTotalSupply => {
                 let mint_hash = system_contract_registry
                     .get(MINT)
                     .expect("should have mint hash");
@@ -322,6 +328,7 @@ impl Executor {
                     execution_journal: runtime.context().execution_journal(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    payment_summary: None,
                 }
                 .take_with_ret(ret),
                 Err(error) => ExecutionResult::Failure {
@@ -329,6 +336,7 @@ impl Executor {
                     error: Error::CLValue(error).into(),
                     transfers: runtime.context().transfers().to_owned(),
                     cost: runtime.context().gas_counter(),
+                    payment_summary: None,
                 }
                 .take_without_ret(),
             },
@@ -337,6 +345,7 @@ impl Executor {
                 error: error.into(),
                 transfers: runtime.context().transfers().to_owned(),
                 cost: runtime.context().gas_counter(),
+                payment_summary: None,
             }
             .take_without_ret(),
         }
@@ -412,6 +421,8 @@ pub(crate) enum DirectSystemContractCall {
     GetPaymentPurse,
     /// Calls handle payment's `distribute_accumulated_fees` entry point.
     DistributeAccumulatedFees,
+    /// Calls mint's `record_total_supply` entry point.
+    RecordTotalSupply,
 }
 
 impl DirectSystemContractCall {
@@ -427,6 +438,7 @@ impl DirectSystemContractCall {
             DirectSystemContractCall::DistributeAccumulatedFees => {
                 handle_payment::METHOD_DISTRIBUTE_ACCUMULATED_FEES
             }
+            DirectSystemContractCall::RecordTotalSupply => mint::METHOD_RECORD_TOTAL_SUPPLY,
         }
     }
 }
@@ -28,9 +28,23 @@ pub(crate) fn create_module_resolver(
     Err(ResolverError::UnknownProtocolVersion(protocol_version))
 }
 
+/// Returns the names of the host functions which Wasm modules may import for given protocol
+/// version.
+///
+/// * `protocol_version` Version of the protocol. Can't be lower than 1.
+pub fn host_function_names(
+    protocol_version: ProtocolVersion,
+) -> Result<&'static [&'static str], ResolverError> {
+    if protocol_version >= ProtocolVersion::V1_0_0 {
+        return Ok(v1_resolver::HOST_FUNCTION_NAMES);
+    }
+    Err(ResolverError::UnknownProtocolVersion(protocol_version))
+}
+
 #[cfg(test)]
 mod tests {
     use casper_types::ProtocolVersion;
+    use casper_wasmi::Signature;
 
     use super::*;
 
@@ -45,4 +59,19 @@ mod tests {
     fn protocol_version_1_always_resolves() {
         assert!(create_module_resolver(ProtocolVersion::V1_0_0, &EngineConfig::default()).is_ok());
     }
+
+    #[test]
+    fn should_resolve_all_host_function_names() {
+        let resolver =
+            create_module_resolver(ProtocolVersion::V1_0_0, &EngineConfig::default()).unwrap();
+        for name in host_function_names(ProtocolVersion::V1_0_0).unwrap() {
+            let signature = Signature::new(&[][..], None);
+            assert!(
+                resolver.resolve_func(name, &signature).is_ok(),
+                "{} should be resolved",
+                name
+            );
+        }
+        assert!(host_function_names(ProtocolVersion::default()).is_err());
+    }
 }
@@ -2,6 +2,11 @@
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 
+use crate::shared::{
+    gas_profile,
+    host_function_costs::{Cost, HostFunctionCosts},
+};
+
 /// Enum representing unique IDs of host functions supported in major version 1.
 #[derive(Debug, PartialEq, FromPrimitive, ToPrimitive, Clone, Copy)]
 #[repr(usize)]
@@ -60,6 +65,146 @@ pub(crate) enum FunctionIndex {
     EnableContractVersion,
 }
 
+impl FunctionIndex {
+    /// Returns the name under which gas charged by this host function is reported in a
+    /// [`GasProfile`](crate::shared::gas_profile::GasProfile).
+    pub(crate) fn gas_profile_group(self) -> String {
+        if self == FunctionIndex::GasFuncIndex {
+            return gas_profile::WASM_OPCODES_GROUP.to_string();
+        }
+        let name = format!("{:?}", self);
+        ["FuncIndex", "FnIndex", "Index"]
+            .iter()
+            .find_map(|suffix| name.strip_suffix(suffix))
+            .unwrap_or(&name)
+            .to_string()
+    }
+
+    /// Returns every host function the runtime can dispatch.
+    pub(crate) fn all() -> impl Iterator<Item = FunctionIndex> {
+        (0..).map_while(|index| FunctionIndex::try_from(index).ok())
+    }
+
+    /// Returns the name and base cost of the entry of the host function cost table charged when
+    /// calling this host function, or `None` if the charge isn't taken from the table.
+    pub(crate) fn cost_table_entry(
+        self,
+        costs: &HostFunctionCosts,
+    ) -> Option<(&'static str, Cost)> {
+        let entry = match self {
+            FunctionIndex::WriteFuncIndex => ("write", costs.write.cost()),
+            FunctionIndex::ReadFuncIndex => ("read_value", costs.read_value.cost()),
+            FunctionIndex::AddFuncIndex => ("add", costs.add.cost()),
+            FunctionIndex::NewFuncIndex => ("new_uref", costs.new_uref.cost()),
+            FunctionIndex::RetFuncIndex => ("ret", costs.ret.cost()),
+            FunctionIndex::CallContractFuncIndex => ("call_contract", costs.call_contract.cost()),
+            FunctionIndex::GetKeyFuncIndex => ("get_key", costs.get_key.cost()),
+            FunctionIndex::HasKeyFuncIndex => ("has_key", costs.has_key.cost()),
+            FunctionIndex::PutKeyFuncIndex => ("put_key", costs.put_key.cost()),
+            FunctionIndex::IsValidURefFnIndex => ("is_valid_uref", costs.is_valid_uref.cost()),
+            FunctionIndex::RevertFuncIndex => ("revert", costs.revert.cost()),
+            FunctionIndex::AddAssociatedKeyFuncIndex => {
+                ("add_associated_key", costs.add_associated_key.cost())
+            }
+            FunctionIndex::RemoveAssociatedKeyFuncIndex => {
+                ("remove_associated_key", costs.remove_associated_key.cost())
+            }
+            FunctionIndex::UpdateAssociatedKeyFuncIndex => {
+                ("update_associated_key", costs.update_associated_key.cost())
+            }
+            FunctionIndex::SetActionThresholdFuncIndex => {
+                ("set_action_threshold", costs.set_action_threshold.cost())
+            }
+            FunctionIndex::LoadNamedKeysFuncIndex => {
+                ("load_named_keys", costs.load_named_keys.cost())
+            }
+            FunctionIndex::RemoveKeyFuncIndex => ("remove_key", costs.remove_key.cost()),
+            FunctionIndex::GetCallerIndex => ("get_caller", costs.get_caller.cost()),
+            FunctionIndex::GetBlocktimeIndex => ("get_blocktime", costs.get_blocktime.cost()),
+            FunctionIndex::CreatePurseIndex => ("create_purse", costs.create_purse.cost()),
+            FunctionIndex::TransferToAccountIndex => {
+                ("transfer_to_account", costs.transfer_to_account.cost())
+            }
+            FunctionIndex::TransferFromPurseToAccountIndex => (
+                "transfer_from_purse_to_account",
+                costs.transfer_from_purse_to_account.cost(),
+            ),
+            FunctionIndex::TransferFromPurseToPurseIndex => (
+                "transfer_from_purse_to_purse",
+                costs.transfer_from_purse_to_purse.cost(),
+            ),
+            FunctionIndex::GetBalanceIndex => ("get_balance", costs.get_balance.cost()),
+            FunctionIndex::GetPhaseIndex => ("get_phase", costs.get_phase.cost()),
+            FunctionIndex::GetSystemContractIndex => {
+                ("get_system_contract", costs.get_system_contract.cost())
+            }
+            FunctionIndex::GetMainPurseIndex => ("get_main_purse", costs.get_main_purse.cost()),
+            FunctionIndex::ReadHostBufferIndex => {
+                ("read_host_buffer", costs.read_host_buffer.cost())
+            }
+            FunctionIndex::CreateContractPackageAtHash => (
+                "create_contract_package_at_hash",
+                costs.create_contract_package_at_hash.cost(),
+            ),
+            FunctionIndex::AddContractVersion => {
+                ("add_contract_version", costs.add_contract_version.cost())
+            }
+            FunctionIndex::DisableContractVersion => (
+                "disable_contract_version",
+                costs.disable_contract_version.cost(),
+            ),
+            FunctionIndex::CallVersionedContract => (
+                "call_versioned_contract",
+                costs.call_versioned_contract.cost(),
+            ),
+            FunctionIndex::CreateContractUserGroup => (
+                "create_contract_user_group",
+                costs.create_contract_user_group.cost(),
+            ),
+            #[cfg(feature = "test-support")]
+            FunctionIndex::PrintIndex => ("print", costs.print.cost()),
+            FunctionIndex::GetRuntimeArgsizeIndex => {
+                ("get_named_arg_size", costs.get_named_arg_size.cost())
+            }
+            FunctionIndex::GetRuntimeArgIndex => ("get_named_arg", costs.get_named_arg.cost()),
+            FunctionIndex::RemoveContractUserGroupIndex => (
+                "remove_contract_user_group",
+                costs.remove_contract_user_group.cost(),
+            ),
+            FunctionIndex::ExtendContractUserGroupURefsIndex => (
+                "provision_contract_user_group_uref",
+                costs.provision_contract_user_group_uref.cost(),
+            ),
+            FunctionIndex::RemoveContractUserGroupURefsIndex => (
+                "remove_contract_user_group_urefs",
+                costs.remove_contract_user_group_urefs.cost(),
+            ),
+            FunctionIndex::Blake2b => ("blake2b", costs.blake2b.cost()),
+            FunctionIndex::DictionaryGetFuncIndex => {
+                ("dictionary_get", costs.dictionary_get.cost())
+            }
+            FunctionIndex::DictionaryPutFuncIndex => {
+                ("dictionary_put", costs.dictionary_put.cost())
+            }
+            FunctionIndex::RandomBytes => ("random_bytes", costs.random_bytes.cost()),
+            FunctionIndex::DictionaryReadFuncIndex => ("read_value", costs.read_value.cost()),
+            FunctionIndex::EnableContractVersion => (
+                "enable_contract_version",
+                costs.enable_contract_version.cost(),
+            ),
+            // Metering and recording of system contract data aren't charged as host functions.
+            FunctionIndex::GasFuncIndex
+            | FunctionIndex::RecordTransfer
+            | FunctionIndex::RecordEraInfo => return None,
+            // These are charged a fixed cost until they get an entry in the cost table.
+            FunctionIndex::NewDictionaryFuncIndex
+            | FunctionIndex::LoadCallStack
+            | FunctionIndex::LoadAuthorizationKeys => return None,
+        };
+        Some(entry)
+    }
+}
+
 impl From<FunctionIndex> for usize {
     fn from(index: FunctionIndex) -> usize {
         // NOTE: This can't fail as `FunctionIndex` is represented by usize,
@@ -94,4 +239,14 @@ mod tests {
     fn invalid_index() {
         assert!(FunctionIndex::try_from(123_456_789usize).is_err());
     }
+
+    #[test]
+    fn all_should_list_every_function_once() {
+        let all: Vec<_> = FunctionIndex::all().collect();
+        assert_eq!(all.first(), Some(&FunctionIndex::WriteFuncIndex));
+        assert_eq!(all.last(), Some(&FunctionIndex::EnableContractVersion));
+        for (index, function) in all.into_iter().enumerate() {
+            assert_eq!(usize::from(function), index);
+        }
+    }
 }
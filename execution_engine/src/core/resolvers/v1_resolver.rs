@@ -9,6 +9,62 @@ use super::{
     error::ResolverError, memory_resolver::MemoryResolver, v1_function_index::FunctionIndex,
 };
 
+/// The names of the host functions resolved by [`RuntimeModuleImportResolver`].
+///
+/// This excludes the internal gas function, which Wasm modules may not import, and `casper_print`,
+/// which is only provided by test builds.
+pub(super) const HOST_FUNCTION_NAMES: &[&str] = &[
+    "casper_read_value",
+    "casper_load_named_keys",
+    "casper_write",
+    "casper_add",
+    "casper_new_uref",
+    "casper_ret",
+    "casper_get_key",
+    "casper_has_key",
+    "casper_put_key",
+    "casper_is_valid_uref",
+    "casper_revert",
+    "casper_add_associated_key",
+    "casper_remove_associated_key",
+    "casper_update_associated_key",
+    "casper_set_action_threshold",
+    "casper_remove_key",
+    "casper_get_caller",
+    "casper_get_blocktime",
+    "casper_create_purse",
+    "casper_transfer_to_account",
+    "casper_transfer_from_purse_to_account",
+    "casper_transfer_from_purse_to_purse",
+    "casper_get_balance",
+    "casper_get_phase",
+    "casper_get_system_contract",
+    "casper_get_main_purse",
+    "casper_read_host_buffer",
+    "casper_create_contract_package_at_hash",
+    "casper_create_contract_user_group",
+    "casper_add_contract_version",
+    "casper_disable_contract_version",
+    "casper_call_contract",
+    "casper_call_versioned_contract",
+    "casper_get_named_arg_size",
+    "casper_get_named_arg",
+    "casper_remove_contract_user_group",
+    "casper_provision_contract_user_group_uref",
+    "casper_remove_contract_user_group_urefs",
+    "casper_blake2b",
+    "casper_record_transfer",
+    "casper_record_era_info",
+    "casper_load_call_stack",
+    "casper_dictionary_get",
+    "casper_dictionary_read",
+    "casper_dictionary_put",
+    "casper_new_dictionary",
+    "casper_load_authorization_keys",
+    "casper_random_bytes",
+    "casper_enable_contract_version",
+];
+
 pub(crate) struct RuntimeModuleImportResolver {
     memory: RefCell<Option<MemoryRef>>,
     max_memory: u32,
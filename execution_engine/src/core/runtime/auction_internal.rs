@@ -5,7 +5,7 @@ use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
     crypto,
     system::{
-        auction::{Bid, EraInfo, Error, UnbondingPurse},
+        auction::{AuctionSnapshot, Bid, EraInfo, Error, UnbondingPurse},
         mint,
     },
     CLTyped, CLValue, EraId, Key, KeyTag, PublicKey, RuntimeArgs, StoredValue, URef,
@@ -14,7 +14,7 @@ use casper_types::{
 
 use super::Runtime;
 use crate::{
-    core::execution,
+    core::{engine_state::engine_config::DelegationRateChangeLimit, execution},
     storage::global_state::StateReader,
     system::auction::{
         providers::{AccountProvider, MintProvider, RuntimeProvider, StorageProvider},
@@ -39,6 +39,13 @@ where
     R: StateReader<Key, StoredValue>,
     R::Error: Into<execution::Error>,
 {
+    fn new_uref<T: ToBytes + CLTyped>(&mut self, init: T) -> Result<URef, Error> {
+        let cl_value = CLValue::from_t(init).map_err(|_| Error::CLValue)?;
+        self.context
+            .new_uref(StoredValue::CLValue(cl_value))
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
     fn read<T: FromBytes + CLTyped>(&mut self, uref: URef) -> Result<Option<T>, Error> {
         match self.context.read_gs(&uref.into()) {
             Ok(Some(StoredValue::CLValue(cl_value))) => {
@@ -110,6 +117,19 @@ where
         Runtime::record_era_summary(self, era_summary)
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::RecordEraInfo))
     }
+
+    fn record_auction_snapshot(
+        &mut self,
+        era_id: EraId,
+        auction_snapshot: AuctionSnapshot,
+    ) -> Result<(), Error> {
+        self.context
+            .metered_write_gs_unsafe(
+                Key::AuctionSnapshot(era_id),
+                StoredValue::AuctionSnapshot(auction_snapshot),
+            )
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
 }
 
 impl<'a, R> RuntimeProvider for Runtime<'a, R>
@@ -129,6 +149,12 @@ where
         self.context.named_keys_get(name).cloned()
     }
 
+    fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error> {
+        self.context
+            .put_key(name.to_string(), key)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
+
     fn get_keys(&mut self, key_tag: &KeyTag) -> Result<BTreeSet<Key>, Error> {
         self.context.get_keys(key_tag).map_err(|_| Error::Storage)
     }
@@ -144,6 +170,10 @@ where
     fn allow_auction_bids(&self) -> bool {
         self.config.allow_auction_bids()
     }
+
+    fn delegation_rate_change_limit(&self) -> DelegationRateChangeLimit {
+        self.config.delegation_rate_change_limit()
+    }
 }
 
 impl<'a, R> MintProvider for Runtime<'a, R>
@@ -16,7 +16,10 @@ use casper_types::{
 use super::{args::Args, Error, Runtime};
 use crate::{
     core::resolvers::v1_function_index::FunctionIndex,
-    shared::host_function_costs::{Cost, HostFunction, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY},
+    shared::{
+        gas_profile,
+        host_function_costs::{Cost, HostFunction, DEFAULT_HOST_FUNCTION_NEW_DICTIONARY},
+    },
     storage::global_state::StateReader,
 };
 
@@ -31,6 +34,7 @@ where
         args: RuntimeArgs,
     ) -> Result<Option<RuntimeValue>, Trap> {
         let func = FunctionIndex::try_from(index).expect("unknown function index");
+        let _gas_profile_group = gas_profile::enter_group(|| func.gas_profile_group());
 
         let host_function_costs = self.config.wasm_config().take_host_function_costs();
 
@@ -934,7 +938,7 @@ where
                 let target: URef = self.t_from_mem(target_ptr, target_size)?;
                 let amount: U512 = self.t_from_mem(amount_ptr, amount_size)?;
                 let id: Option<u64> = self.t_from_mem(id_ptr, id_size)?;
-                self.record_transfer(maybe_to, source, target, amount, id)?;
+                self.record_transfer(maybe_to, source, target, amount, id, None)?;
                 Ok(Some(RuntimeValue::I32(0)))
             }
 
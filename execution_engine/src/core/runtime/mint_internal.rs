@@ -2,7 +2,7 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::{mint::Error, CallStackElement},
-    CLTyped, CLValue, Key, Phase, StoredValue, URef, U512,
+    CLTyped, CLValue, Key, Phase, StoredValue, TransferMemo, URef, U512,
 };
 
 use super::Runtime;
@@ -134,6 +134,34 @@ where
             .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
     }
 
+    fn read_dictionary_item<T: CLTyped + FromBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Result<Option<T>, Error> {
+        let maybe_value = self
+            .context
+            .dictionary_get(seed_uref, dictionary_item_key)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))?;
+        maybe_value
+            .map(|value| CLValue::into_t(value).map_err(|_| Error::CLValue))
+            .transpose()
+    }
+
+    fn write_dictionary_item<T: CLTyped + ToBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    ) -> Result<(), Error> {
+        let cl_value = CLValue::from_t(value).map_err(|_| Error::CLValue)?;
+        self.context
+            .dictionary_put(seed_uref, dictionary_item_key, cl_value)
+            .map_err(|exec_error| {
+                <Option<Error>>::from(exec_error).unwrap_or(Error::WriteDictionary)
+            })
+    }
+
     fn read_balance(&mut self, uref: URef) -> Result<Option<U512>, Error> {
         let maybe_value = self
             .context
@@ -176,12 +204,18 @@ where
         target: URef,
         amount: U512,
         id: Option<u64>,
+        memo: Option<TransferMemo>,
     ) -> Result<(), Error> {
-        let result = Runtime::record_transfer(self, maybe_to, source, target, amount, id);
+        let result = Runtime::record_transfer(self, maybe_to, source, target, amount, id, memo);
         result.map_err(|exec_error| {
             <Option<Error>>::from(exec_error).unwrap_or(Error::RecordTransferFailure)
         })
     }
+
+    fn record_burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        Runtime::record_burn(self, purse, amount)
+            .map_err(|exec_error| <Option<Error>>::from(exec_error).unwrap_or(Error::Storage))
+    }
 }
 
 impl<'a, R> Mint for Runtime<'a, R>
@@ -5,6 +5,7 @@ mod externals;
 mod handle_payment_internal;
 mod host_function_flag;
 mod mint_internal;
+pub(crate) mod self_test;
 pub mod stack;
 mod standard_payment_internal;
 mod utils;
@@ -23,6 +24,7 @@ use tracing::error;
 #[cfg(feature = "test-support")]
 use casper_wasmi::RuntimeValue;
 
+use casper_hashing::Digest;
 use casper_types::{
     account::{
         Account, AccountHash, ActionType, AddKeyFailure, RemoveKeyFailure, SetThresholdFailure,
@@ -40,15 +42,16 @@ use casper_types::{
         handle_payment, mint, standard_payment, CallStackElement, SystemContractType, AUCTION,
         HANDLE_PAYMENT, MINT, STANDARD_PAYMENT,
     },
-    AccessRights, ApiError, CLTyped, CLValue, ContextAccessRights, ContractHash,
-    ContractPackageHash, ContractVersionKey, ContractWasm, DeployHash, EntryPointType, Gas,
+    AccessRights, ApiError, Burn, CLTyped, CLValue, ContextAccessRights, ContractHash,
+    ContractPackageHash, ContractVersionKey, ContractWasm, DeployHash, EntryPointType, EraId, Gas,
     GrantedAccess, Key, NamedArg, Parameter, Phase, PublicKey, RuntimeArgs, StoredValue, Transfer,
-    TransferResult, TransferredTo, URef, DICTIONARY_ITEM_KEY_MAX_LENGTH, U512,
+    TransferMemo, TransferResult, TransferredTo, URef, URefAddr, DICTIONARY_ITEM_KEY_MAX_LENGTH,
+    U512,
 };
 
 use crate::{
     core::{
-        engine_state::EngineConfig,
+        engine_state::{EngineConfig, TransferArgs},
         execution::{self, Error},
         runtime::host_function_flag::HostFunctionFlag,
         runtime_context::{self, RuntimeContext},
@@ -56,6 +59,7 @@ use crate::{
     },
     shared::{
         host_function_costs::{Cost, HostFunction},
+        module_cache::{ModuleCacheKey, ModuleSource},
         wasm_prep::{self, PreprocessingError},
     },
     storage::global_state::StateReader,
@@ -576,6 +580,21 @@ where
             .map_err(|_| Error::Revert(ApiError::InvalidArgument))
     }
 
+    /// Returns the named argument, or `None` if it was not provided.
+    fn try_get_named_argument<T: FromBytes + CLTyped>(
+        args: &RuntimeArgs,
+        name: &str,
+    ) -> Result<Option<T>, Error> {
+        match args.get(name) {
+            Some(arg) => arg
+                .clone()
+                .into_t()
+                .map(Some)
+                .map_err(|_| Error::Revert(ApiError::InvalidArgument)),
+            None => Ok(None),
+        }
+    }
+
     fn reverter<T: Into<ApiError>>(error: T) -> Error {
         let api_error: ApiError = error.into();
         // NOTE: This is special casing needed to keep the native system contracts propagate
@@ -667,7 +686,8 @@ where
                 CLValue::from_t(maybe_balance).map_err(Self::reverter)
             })(),
             // Type: `fn transfer(maybe_to: Option<AccountHash>, source: URef, target: URef, amount:
-            // U512, id: Option<u64>) -> Result<(), Error>`
+            // U512, id: Option<u64>, memo: Option<TransferMemo>) -> Result<(), Error>`, where
+            // `memo` may be omitted
             mint::METHOD_TRANSFER => (|| {
                 mint_runtime.charge_system_contract_call(mint_costs.transfer)?;
 
@@ -677,8 +697,68 @@ where
                 let target: URef = Self::get_named_argument(runtime_args, mint::ARG_TARGET)?;
                 let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
                 let id: Option<u64> = Self::get_named_argument(runtime_args, mint::ARG_ID)?;
+                let memo: Option<TransferMemo> =
+                    Self::try_get_named_argument(runtime_args, mint::ARG_MEMO)?.flatten();
                 let result: Result<(), mint::Error> =
-                    mint_runtime.transfer(maybe_to, source, target, amount, id);
+                    mint_runtime.transfer(maybe_to, source, target, amount, id, memo);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn transfer_batch(source: URef, transfers: Vec<(Option<AccountHash>, URef,
+            // U512)>, id: Option<u64>) -> Result<Vec<Result<(), Error>>, Error>`
+            mint::METHOD_TRANSFER_BATCH => (|| {
+                let source: URef = Self::get_named_argument(runtime_args, mint::ARG_SOURCE)?;
+                let transfers: Vec<(Option<AccountHash>, URef, U512)> =
+                    Self::get_named_argument(runtime_args, mint::ARG_TRANSFERS)?;
+                let id: Option<u64> = Self::get_named_argument(runtime_args, mint::ARG_ID)?;
+
+                // Each transfer of the batch is charged as a separate call to `transfer`.
+                for _ in &transfers {
+                    mint_runtime.charge_system_contract_call(mint_costs.transfer)?;
+                }
+
+                let transfers = transfers
+                    .into_iter()
+                    .map(|(maybe_to, target, amount)| {
+                        TransferArgs::new(maybe_to, source, target, amount, id, None)
+                    })
+                    .collect();
+                let result: Result<Vec<mint::TransferResult>, mint::Error> =
+                    mint_runtime.transfer_batch(transfers);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn approve(purse: URef, spender: Key, amount: U512) -> Result<(), Error>`
+            mint::METHOD_APPROVE => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.approve)?;
+
+                let purse: URef = Self::get_named_argument(runtime_args, mint::ARG_PURSE)?;
+                let spender: Key = Self::get_named_argument(runtime_args, mint::ARG_SPENDER)?;
+                let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+
+                let result: Result<(), mint::Error> = mint_runtime.approve(purse, spender, amount);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn transfer_from(source: URefAddr, target: URef, amount: U512, id:
+            // Option<u64>) -> Result<(), Error>`
+            mint::METHOD_TRANSFER_FROM => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.transfer)?;
+
+                let source: URefAddr = Self::get_named_argument(runtime_args, mint::ARG_SOURCE)?;
+                let target: URef = Self::get_named_argument(runtime_args, mint::ARG_TARGET)?;
+                let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+                let id: Option<u64> = Self::get_named_argument(runtime_args, mint::ARG_ID)?;
+
+                let result: Result<(), mint::Error> =
+                    mint_runtime.transfer_from(source, target, amount, id);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn allowance(purse: URefAddr, spender: Key) -> Result<U512, Error>`
+            mint::METHOD_ALLOWANCE => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.balance)?;
+
+                let purse: URefAddr = Self::get_named_argument(runtime_args, mint::ARG_PURSE)?;
+                let spender: Key = Self::get_named_argument(runtime_args, mint::ARG_SPENDER)?;
+
+                let result: Result<U512, mint::Error> = mint_runtime.allowance(purse, spender);
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
             // Type: `fn read_base_round_reward() -> Result<U512, Error>`
@@ -700,6 +780,35 @@ where
                     mint_runtime.mint_into_existing_purse(existing_purse, amount);
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
+            mint::METHOD_BURN => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.burn)?;
+
+                let purse: URef = Self::get_named_argument(runtime_args, mint::ARG_PURSE)?;
+                let amount: U512 = Self::get_named_argument(runtime_args, mint::ARG_AMOUNT)?;
+
+                let result: Result<(), mint::Error> = mint_runtime.burn(purse, amount);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            mint::METHOD_SET_MINT_PAUSED => (|| {
+                mint_runtime.charge_system_contract_call(mint_costs.set_mint_paused)?;
+
+                let paused: bool = Self::get_named_argument(runtime_args, mint::ARG_PAUSED)?;
+
+                let result: Result<(), mint::Error> = mint_runtime.set_mint_paused(paused);
+                CLValue::from_t(result).map_err(Self::reverter)
+            })(),
+            // Type: `fn record_total_supply(era_id: EraId)`
+            mint::METHOD_RECORD_TOTAL_SUPPLY => (|| {
+                // Only called by the system at switch blocks, like `reduce_total_supply`.
+                mint_runtime.charge_system_contract_call(mint_costs.reduce_total_supply)?;
+
+                let era_id: EraId = Self::get_named_argument(runtime_args, mint::ARG_ERA_ID)?;
+
+                mint_runtime
+                    .record_total_supply(era_id)
+                    .map_err(Self::reverter)?;
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
 
             _ => CLValue::from_t(()).map_err(Self::reverter),
         };
@@ -884,8 +993,10 @@ where
                     Self::get_named_argument(runtime_args, auction::ARG_DELEGATION_RATE)?;
                 let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
 
+                let minimum_bid_amount = self.config.minimum_bid_amount();
+
                 let result = runtime
-                    .add_bid(account_hash, delegation_rate, amount)
+                    .add_bid(account_hash, delegation_rate, amount, minimum_bid_amount)
                     .map_err(Self::reverter)?;
 
                 CLValue::from_t(result).map_err(Self::reverter)
@@ -897,8 +1008,10 @@ where
                 let account_hash = Self::get_named_argument(runtime_args, auction::ARG_PUBLIC_KEY)?;
                 let amount = Self::get_named_argument(runtime_args, auction::ARG_AMOUNT)?;
 
+                let minimum_bid_amount = self.config.minimum_bid_amount();
+
                 let result = runtime
-                    .withdraw_bid(account_hash, amount)
+                    .withdraw_bid(account_hash, amount, minimum_bid_amount)
                     .map_err(Self::reverter)?;
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
@@ -964,6 +1077,54 @@ where
                 CLValue::from_t(result).map_err(Self::reverter)
             })(),
 
+            auction::METHOD_BULK_DELEGATE => (|| {
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let delegations: BTreeMap<PublicKey, U512> =
+                    Self::get_named_argument(runtime_args, auction::ARG_DELEGATIONS)?;
+                let redelegations: Vec<(PublicKey, PublicKey, U512)> =
+                    Self::get_named_argument(runtime_args, auction::ARG_REDELEGATIONS)?;
+
+                // Charged as if each delegation and redelegation was made by a separate call.
+                let cost = u64::from(auction_costs.delegate)
+                    .saturating_mul(delegations.len() as u64)
+                    .saturating_add(
+                        u64::from(auction_costs.redelegate)
+                            .saturating_mul(redelegations.len() as u64),
+                    );
+                runtime.charge_system_contract_call(Gas::from(cost))?;
+
+                let max_delegators_per_validator = self.config.max_delegators_per_validator();
+                let minimum_delegation_amount = self.config.minimum_delegation_amount();
+
+                runtime
+                    .bulk_delegate(
+                        delegator,
+                        delegations,
+                        redelegations,
+                        max_delegators_per_validator,
+                        minimum_delegation_amount,
+                    )
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
+            auction::METHOD_SET_DELEGATOR_REWARD_PURSE => (|| {
+                // Charged like a delegation, which it amends.
+                runtime.charge_system_contract_call(auction_costs.delegate)?;
+
+                let delegator = Self::get_named_argument(runtime_args, auction::ARG_DELEGATOR)?;
+                let validator = Self::get_named_argument(runtime_args, auction::ARG_VALIDATOR)?;
+                let reward_purse: Option<URef> =
+                    Self::get_named_argument(runtime_args, auction::ARG_REWARD_PURSE)?;
+
+                runtime
+                    .set_delegator_reward_purse(delegator, validator, reward_purse)
+                    .map_err(Self::reverter)?;
+
+                CLValue::from_t(()).map_err(Self::reverter)
+            })(),
+
             auction::METHOD_RUN_AUCTION => (|| {
                 runtime.charge_system_contract_call(auction_costs.run_auction)?;
 
@@ -973,12 +1134,14 @@ where
                     Self::get_named_argument(runtime_args, auction::ARG_EVICTED_VALIDATORS)?;
 
                 let max_delegators_per_validator = self.config.max_delegators_per_validator();
+                let record_auction_snapshots = self.config.record_auction_snapshots();
 
                 runtime
                     .run_auction(
                         era_end_timestamp_millis,
                         evicted_validators,
                         max_delegators_per_validator,
+                        record_auction_snapshots,
                     )
                     .map_err(Self::reverter)?;
 
@@ -1069,7 +1232,16 @@ where
     ) -> Result<CLValue, Error> {
         let protocol_version = self.context.protocol_version();
         let engine_config = self.config.clone();
-        let module = wasm_prep::preprocess(*engine_config.wasm_config(), module_bytes)?;
+        let module_cache_key = ModuleCacheKey::new(
+            ModuleSource::ModuleBytes(Digest::hash(module_bytes)),
+            protocol_version,
+            engine_config.wasm_config(),
+        );
+        let module = engine_config.module_cache().get_or_try_insert_with(
+            module_cache_key,
+            module_bytes.len(),
+            || wasm_prep::preprocess(*engine_config.wasm_config(), module_bytes),
+        )?;
         let (instance, memory) =
             utils::instance_and_memory(module.clone(), protocol_version, &engine_config)?;
         self.memory = Some(memory);
@@ -1392,7 +1564,16 @@ where
                 None => return Err(Error::KeyNotFound(context_key)),
             };
 
-            casper_wasm::deserialize_buffer(contract_wasm.bytes())?
+            let module_cache_key = ModuleCacheKey::new(
+                ModuleSource::Contract(contract_hash),
+                protocol_version,
+                self.config.wasm_config(),
+            );
+            self.config.module_cache().get_or_try_insert_with(
+                module_cache_key,
+                contract_wasm.bytes().len(),
+                || casper_wasm::deserialize_buffer(contract_wasm.bytes()),
+            )?
         };
 
         let context = self.context.new_from_self(
@@ -1707,6 +1888,7 @@ where
     ) -> Result<Result<(), ApiError>, Error> {
         self.context
             .validate_key(&Key::from(contract_package_hash))?;
+        self.context.validate_named_keys_count(&named_keys)?;
 
         let mut contract_package: ContractPackage = self
             .context
@@ -1880,6 +2062,9 @@ where
     }
 
     /// Records a transfer.
+    ///
+    /// Transfers tagged with a `memo` are recorded as a [`TransferV2`](casper_types::TransferV2).
+    #[allow(clippy::too_many_arguments)]
     fn record_transfer(
         &mut self,
         maybe_to: Option<AccountHash>,
@@ -1887,6 +2072,7 @@ where
         target: URef,
         amount: U512,
         id: Option<u64>,
+        memo: Option<TransferMemo>,
     ) -> Result<(), Error> {
         if self.context.base_key() != Key::from(self.context.get_system_contract(MINT)?) {
             return Err(Error::InvalidContext);
@@ -1908,7 +2094,25 @@ where
             transfers.push(transfer_addr);
         }
         self.context
-            .write_transfer(Key::Transfer(transfer_addr), transfer);
+            .write_transfer(Key::Transfer(transfer_addr), transfer, memo);
+        Ok(())
+    }
+
+    /// Records a burn of `amount` tokens from `purse` by the current deploy under a new
+    /// [`Key::Burn`].
+    fn record_burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if self.context.base_key() != Key::from(self.context.get_system_contract(MINT)?) {
+            return Err(Error::InvalidContext);
+        }
+
+        let burn_addr = self.context.new_burn_addr()?;
+        let burn = Burn::new(
+            self.context.get_deploy_hash(),
+            self.context.account().account_hash(),
+            purse,
+            amount,
+        );
+        self.context.write_burn(Key::Burn(burn_addr), burn);
         Ok(())
     }
 
@@ -2271,6 +2475,12 @@ where
             return Err(Error::DisabledUnrestrictedTransfers);
         }
 
+        if !self.config.is_administrator(&self.context.get_caller()) {
+            self.config
+                .account_creation_policy()
+                .check_account_creation(amount)?;
+        }
+
         // A precondition check that verifies that the transfer can be done
         // as the source purse has enough funds to cover the transfer.
         if amount > self.get_balance(source)?.unwrap_or_default() {
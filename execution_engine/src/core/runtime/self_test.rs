@@ -0,0 +1,77 @@
+//! Execution of a built-in Wasm module, used to check the Wasm stack of the host.
+
+use casper_wasmi::{Externals, RuntimeArgs, RuntimeValue, Trap};
+
+use casper_types::{contracts::DEFAULT_ENTRY_POINT_NAME, ProtocolVersion};
+
+use super::utils;
+use crate::{
+    core::{
+        engine_state::EngineConfig, execution::Error, resolvers::v1_function_index::FunctionIndex,
+    },
+    shared::wasm_prep,
+};
+
+/// A minimal module exporting a `call` entry point which pushes and drops a constant, so that the
+/// gas counter injected during preprocessing is invoked.
+const SELF_TEST_MODULE: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+    0x01, 0x04, 0x01, 0x60, 0x00, 0x00, // type section: `fn()`
+    0x03, 0x02, 0x01, 0x00, // function section: one function of type 0
+    0x05, 0x03, 0x01, 0x00, 0x01, // memory section: one page
+    0x07, 0x08, 0x01, 0x04, b'c', b'a', b'l', b'l', 0x00, 0x00, // export section: `call`
+    0x0a, 0x07, 0x01, 0x05, 0x00, 0x41, 0x01, 0x1a, 0x0b, // code: `i32.const 1; drop`
+];
+
+/// Host externals which only support the gas counter, recording how many times it was called.
+#[derive(Default)]
+struct SelfTestExternals {
+    gas_calls: usize,
+}
+
+impl Externals for SelfTestExternals {
+    fn invoke_index(
+        &mut self,
+        index: usize,
+        _args: RuntimeArgs,
+    ) -> Result<Option<RuntimeValue>, Trap> {
+        if index == usize::from(FunctionIndex::GasFuncIndex) {
+            self.gas_calls += 1;
+            return Ok(None);
+        }
+        Err(Error::Interpreter(format!(
+            "self-test module called unexpected host function {}",
+            index
+        ))
+        .into())
+    }
+}
+
+/// Preprocesses the built-in self-test module, instantiates it against the host function resolver
+/// for `protocol_version` and invokes its entry point.
+pub(crate) fn execute_self_test_module(
+    engine_config: &EngineConfig,
+    protocol_version: ProtocolVersion,
+) -> Result<(), Error> {
+    let module = wasm_prep::preprocess(*engine_config.wasm_config(), SELF_TEST_MODULE)?;
+    let (instance, _memory) = utils::instance_and_memory(module, protocol_version, engine_config)?;
+    let mut externals = SelfTestExternals::default();
+    instance.invoke_export(DEFAULT_ENTRY_POINT_NAME, &[], &mut externals)?;
+    if externals.gas_calls == 0 {
+        return Err(Error::Interpreter(
+            "self-test module was not charged for its execution".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_execute_self_test_module() {
+        execute_self_test_module(&EngineConfig::default(), ProtocolVersion::V1_0_0)
+            .expect("should execute self-test module");
+    }
+}
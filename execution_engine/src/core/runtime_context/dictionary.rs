@@ -35,6 +35,11 @@ impl DictionaryValue {
     pub fn into_cl_value(self) -> CLValue {
         self.cl_value
     }
+
+    /// Returns the address of the [`casper_types::URef`] seeding the dictionary holding the value.
+    pub fn seed_uref_addr(&self) -> &[u8] {
+        &self.seed_uref_addr
+    }
 }
 
 impl CLTyped for DictionaryValue {
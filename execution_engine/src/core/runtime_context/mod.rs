@@ -1,6 +1,6 @@
 //! The context of execution of WASM code.
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     collections::BTreeSet,
     convert::{TryFrom, TryInto},
     fmt::Debug,
@@ -16,21 +16,28 @@ use casper_types::{
     },
     bytesrepr::ToBytes,
     contracts::NamedKeys,
-    system::auction::EraInfo,
-    AccessRights, BlockTime, CLType, CLValue, ContextAccessRights, Contract, ContractHash,
+    system::{
+        auction::{EraInfo, ERA_ID_KEY},
+        AUCTION,
+    },
+    AccessRights, BlockTime, Burn, CLType, CLValue, ContextAccessRights, Contract, ContractHash,
     ContractPackage, ContractPackageHash, DeployHash, DeployInfo, EntryPointAccess, EntryPointType,
-    Gas, GrantedAccess, Key, KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs, StoredValue,
-    Transfer, TransferAddr, URef, URefAddr, DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH, U512,
+    EraId, Gas, GrantedAccess, Key, KeyTag, Phase, ProtocolVersion, PublicKey, RuntimeArgs,
+    StorageLease, StoredValue, Transfer, TransferAddr, TransferMemo, TransferV2, URef, URefAddr,
+    DICTIONARY_ITEM_KEY_MAX_LENGTH, KEY_HASH_LENGTH, U512,
 };
 
 use crate::{
     core::{
-        engine_state::{execution_effect::ExecutionEffect, EngineConfig, SystemContractRegistry},
+        engine_state::{
+            engine_config::StorageRent, execution_effect::ExecutionEffect, EngineConfig,
+            SystemContractRegistry,
+        },
         execution::{AddressGenerator, Error},
         runtime_context::dictionary::DictionaryValue,
         tracking_copy::{AddResult, TrackingCopy, TrackingCopyExt},
     },
-    shared::{execution_journal::ExecutionJournal, newtypes::CorrelationId},
+    shared::{execution_journal::ExecutionJournal, gas_profile, newtypes::CorrelationId},
     storage::global_state::StateReader,
 };
 
@@ -41,6 +48,10 @@ mod tests;
 /// Number of bytes returned from the `random_bytes` function.
 pub const RANDOM_BYTES_COUNT: usize = 32;
 
+/// Extends the deploy hash when seeding the generator of burn addresses, so that it yields other
+/// addresses than the generator shared by everything else created by the deploy.
+const BURN_ADDRESS_GENERATOR_SEED: &[u8] = b"burn";
+
 /// Validates an entry point access with a special validator callback.
 ///
 /// If the passed `access` object is a `Groups` variant, then this function will return a
@@ -97,6 +108,9 @@ pub struct RuntimeContext<'a, R> {
     gas_limit: Gas,
     gas_counter: Gas,
     address_generator: Rc<RefCell<AddressGenerator>>,
+    // Kept apart from `address_generator` so that recording burns doesn't change the addresses of
+    // anything created afterwards
+    burn_address_generator: Rc<RefCell<AddressGenerator>>,
     protocol_version: ProtocolVersion,
     correlation_id: CorrelationId,
     phase: Phase,
@@ -104,6 +118,8 @@ pub struct RuntimeContext<'a, R> {
     entry_point_type: EntryPointType,
     transfers: Vec<TransferAddr>,
     remaining_spending_limit: U512,
+    // The current era, read from the auction at most once per execution
+    current_era_id: Rc<Cell<Option<EraId>>>,
 }
 
 impl<'a, R> RuntimeContext<'a, R>
@@ -136,6 +152,11 @@ where
         transfers: Vec<TransferAddr>,
         remaining_spending_limit: U512,
     ) -> Self {
+        let burn_address_generator = {
+            let seed = [deploy_hash.as_bytes(), BURN_ADDRESS_GENERATOR_SEED].concat();
+            Rc::new(RefCell::new(AddressGenerator::new(&seed, phase)))
+        };
+
         RuntimeContext {
             tracking_copy,
             entry_point_type,
@@ -150,12 +171,14 @@ where
             gas_limit,
             gas_counter,
             address_generator,
+            burn_address_generator,
             protocol_version,
             correlation_id,
             phase,
             engine_config,
             transfers,
             remaining_spending_limit,
+            current_era_id: Rc::new(Cell::new(None)),
         }
     }
 
@@ -178,12 +201,14 @@ where
         let gas_limit = self.gas_limit;
         let gas_counter = self.gas_counter;
         let address_generator = self.address_generator.clone();
+        let burn_address_generator = Rc::clone(&self.burn_address_generator);
         let protocol_version = self.protocol_version;
         let correlation_id = self.correlation_id;
         let phase = self.phase;
         let engine_config = self.engine_config.clone();
         let transfers = self.transfers.clone();
         let remaining_spending_limit = self.remaining_spending_limit();
+        let current_era_id = Rc::clone(&self.current_era_id);
 
         RuntimeContext {
             tracking_copy,
@@ -199,12 +224,14 @@ where
             gas_limit,
             gas_counter,
             address_generator,
+            burn_address_generator,
             protocol_version,
             correlation_id,
             phase,
             engine_config,
             transfers,
             remaining_spending_limit,
+            current_era_id,
         }
     }
 
@@ -338,6 +365,22 @@ where
                 error!("should not remove the checksum registry key");
                 Err(Error::RemoveKeyFailure(RemoveKeyFailure::PermissionDenied))
             }
+            Key::AuctionSnapshot(_) => {
+                self.named_keys.remove(name);
+                Ok(())
+            }
+            Key::StorageLease(_) => {
+                self.named_keys.remove(name);
+                Ok(())
+            }
+            Key::Burn(_) => {
+                self.named_keys.remove(name);
+                Ok(())
+            }
+            Key::StorageLeaseExpiry(..) => {
+                self.named_keys.remove(name);
+                Ok(())
+            }
         }
     }
 
@@ -460,10 +503,26 @@ where
         Ok(TransferAddr::new(transfer_addr))
     }
 
+    /// Creates a new burn address using the burn address generator.
+    pub fn new_burn_addr(&mut self) -> Result<[u8; KEY_HASH_LENGTH], Error> {
+        Ok(self.burn_address_generator.borrow_mut().new_hash_address())
+    }
+
     /// Puts `key` to the map of named keys of current context.
     pub fn put_key(&mut self, name: String, key: Key) -> Result<(), Error> {
         // No need to perform actual validation on the base key because an account or contract (i.e.
         // the element stored under `base_key`) is allowed to add new named keys to itself.
+        if !self.named_keys.contains_key(&name) {
+            let max_named_keys = self.engine_config.max_named_keys();
+            if self.named_keys.len() >= max_named_keys as usize
+                && !self.is_executing_system_contract()?
+            {
+                return Err(Error::TooManyNamedKeys {
+                    max: max_named_keys,
+                });
+            }
+        }
+
         let named_key_value = StoredValue::CLValue(CLValue::from_t((name.clone(), key))?);
         self.validate_value(&named_key_value)?;
         self.metered_add_gs_unsafe(self.base_key(), named_key_value)?;
@@ -500,6 +559,7 @@ where
     pub fn read_gs(&mut self, key: &Key) -> Result<Option<StoredValue>, Error> {
         self.validate_readable(key)?;
         self.validate_key(key)?;
+        self.validate_storage_lease(key)?;
 
         let maybe_stored_value = self
             .tracking_copy
@@ -584,14 +644,31 @@ where
     }
 
     /// Write a transfer instance to the global state.
-    pub fn write_transfer(&mut self, key: Key, value: Transfer) {
+    ///
+    /// If the transfer is tagged with a `memo`, it is written as a [`TransferV2`], otherwise as a
+    /// [`Transfer`].
+    pub fn write_transfer(&mut self, key: Key, value: Transfer, memo: Option<TransferMemo>) {
         if let Key::Transfer(_) = key {
+            let stored_value = match memo {
+                Some(memo) => StoredValue::TransferV2(TransferV2::new(value, memo)),
+                None => StoredValue::Transfer(value),
+            };
             // Writing a `Transfer` will not exceed write size limit.
+            self.tracking_copy.borrow_mut().write(key, stored_value);
+        } else {
+            panic!("Do not use this function for writing non-transfer keys")
+        }
+    }
+
+    /// Writes the record of a burn to the global state.
+    pub fn write_burn(&mut self, key: Key, value: Burn) {
+        if let Key::Burn(_) = key {
+            // Writing a `Burn` will not exceed write size limit.
             self.tracking_copy
                 .borrow_mut()
-                .write(key, StoredValue::Transfer(value));
+                .write(key, StoredValue::Burn(value));
         } else {
-            panic!("Do not use this function for writing non-transfer keys")
+            panic!("Do not use this function for writing non-burn keys")
         }
     }
 
@@ -718,6 +795,9 @@ where
             StoredValue::Bid(_) => Ok(()),
             StoredValue::Withdraw(_) => Ok(()),
             StoredValue::Unbonding(_) => Ok(()),
+            StoredValue::AuctionSnapshot(_) => Ok(()),
+            StoredValue::TransferV2(_) => Ok(()),
+            StoredValue::Burn(_) => Ok(()),
         }
     }
 
@@ -796,6 +876,10 @@ where
             Key::Unbond(_) => true,
             Key::ChainspecRegistry => true,
             Key::ChecksumRegistry => true,
+            Key::AuctionSnapshot(_) => true,
+            Key::StorageLease(_) => true,
+            Key::Burn(_) => true,
+            Key::StorageLeaseExpiry(..) => true,
         }
     }
 
@@ -816,6 +900,10 @@ where
             Key::Unbond(_) => false,
             Key::ChainspecRegistry => false,
             Key::ChecksumRegistry => false,
+            Key::AuctionSnapshot(_) => false,
+            Key::StorageLease(_) => false,
+            Key::Burn(_) => false,
+            Key::StorageLeaseExpiry(..) => false,
         }
     }
 
@@ -836,6 +924,10 @@ where
             Key::Unbond(_) => false,
             Key::ChainspecRegistry => false,
             Key::ChecksumRegistry => false,
+            Key::AuctionSnapshot(_) => false,
+            Key::StorageLease(_) => false,
+            Key::Burn(_) => false,
+            Key::StorageLeaseExpiry(..) => false,
         }
     }
 
@@ -848,7 +940,7 @@ where
         let prev = self.gas_counter();
         let gas_limit = self.gas_limit();
         // gas charge overflow protection
-        match prev.checked_add(amount) {
+        let result = match prev.checked_add(amount) {
             None => {
                 self.set_gas_counter(gas_limit);
                 Err(Error::GasLimit)
@@ -861,7 +953,11 @@ where
                 self.set_gas_counter(val);
                 Ok(())
             }
+        };
+        if let Some(charged) = self.gas_counter().checked_sub(prev) {
+            gas_profile::record(charged);
         }
+        result
     }
 
     /// Checks if we are calling a system contract.
@@ -871,14 +967,140 @@ where
             .has_contract_hash(contract_hash))
     }
 
+    /// Checks if the code being executed in this context is a system contract.
+    fn is_executing_system_contract(&self) -> Result<bool, Error> {
+        match self.base_key().into_hash() {
+            Some(base_key) => self.is_system_contract(&ContractHash::new(base_key)),
+            None => Ok(false),
+        }
+    }
+
+    /// Checks that a `CLValue` about to be written to global state doesn't exceed the maximum
+    /// configured size.
+    ///
+    /// System contracts are exempt, as the size of the values they write is not under the control
+    /// of the user.
+    fn validate_stored_value_size(&self, stored_value: &StoredValue) -> Result<(), Error> {
+        if let StoredValue::CLValue(cl_value) = stored_value {
+            let size = cl_value.inner_bytes().len();
+            let max = self.engine_config.max_cl_value_size();
+            if size > max as usize && !self.is_executing_system_contract()? {
+                return Err(Error::CLValueTooLarge { size, max });
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that a set of named keys given to a new contract doesn't exceed the maximum
+    /// configured number of named keys.
+    pub(crate) fn validate_named_keys_count(&self, named_keys: &NamedKeys) -> Result<(), Error> {
+        let max = self.engine_config.max_named_keys();
+        if named_keys.len() > max as usize && !self.is_executing_system_contract()? {
+            return Err(Error::TooManyNamedKeys { max });
+        }
+        Ok(())
+    }
+
+    /// Returns the current era, as tracked by the auction.
+    ///
+    /// The era is read from the auction once and then shared by all the contexts of the execution.
+    fn current_era_id(&self) -> Result<EraId, Error> {
+        if let Some(era_id) = self.current_era_id.get() {
+            return Ok(era_id);
+        }
+        let era_id = self.read_current_era_id()?;
+        self.current_era_id.set(Some(era_id));
+        Ok(era_id)
+    }
+
+    /// Reads the current era from the auction.
+    fn read_current_era_id(&self) -> Result<EraId, Error> {
+        let auction_hash = self.get_system_contract(AUCTION)?;
+        let auction = self
+            .tracking_copy
+            .borrow_mut()
+            .get_contract(self.correlation_id, auction_hash)?;
+        let era_id_key = match auction.named_keys().get(ERA_ID_KEY) {
+            Some(era_id_key) => *era_id_key,
+            None => return Err(Error::NamedKeyNotFound(ERA_ID_KEY.to_string())),
+        };
+        match self
+            .tracking_copy
+            .borrow_mut()
+            .read(self.correlation_id, &era_id_key)
+            .map_err(Into::into)?
+        {
+            Some(StoredValue::CLValue(cl_value)) => Ok(cl_value.into_t()?),
+            Some(_) => Err(Error::UnexpectedStoredValueVariant),
+            None => Err(Error::KeyNotFound(era_id_key)),
+        }
+    }
+
+    /// Renews the storage lease of the value just written under `key`, if storage rent is enabled
+    /// and the value is subject to it.
+    ///
+    /// Only values under [`Key::URef`]s and [`Key::Dictionary`]s written by user code are leased.
+    /// The lease is also indexed under the era it becomes due in, so that the step of that era
+    /// only visits the leases which may expire.
+    fn renew_storage_lease(&mut self, key: Key) -> Result<(), Error> {
+        let (lease_eras, grace_eras) = match self.engine_config.storage_rent() {
+            StorageRent::Disabled => return Ok(()),
+            StorageRent::Lease {
+                lease_eras,
+                grace_eras,
+            } => (lease_eras, grace_eras),
+        };
+        if !matches!(key, Key::URef(_) | Key::Dictionary(_))
+            || self.is_executing_system_contract()?
+        {
+            return Ok(());
+        }
+
+        let leased_key = key.normalize();
+        let paid_until = self.current_era_id()?.saturating_add(lease_eras);
+        let lease_key = Key::storage_lease(&leased_key);
+        let lease =
+            StoredValue::CLValue(CLValue::from_t(StorageLease::new(leased_key, paid_until))?);
+        self.charge_gas_storage(lease.serialized_length())?;
+        self.tracking_copy.borrow_mut().write(lease_key, lease);
+
+        // The lease is due once the paid eras and the grace eras following them have passed.
+        let due_era_id = paid_until.saturating_add(grace_eras).saturating_add(1);
+        let expiry_key = Key::storage_lease_expiry(due_era_id, &leased_key);
+        let expiry = StoredValue::CLValue(CLValue::from_t(lease_key)?);
+        self.charge_gas_storage(expiry.serialized_length())?;
+        self.tracking_copy.borrow_mut().write(expiry_key, expiry);
+        Ok(())
+    }
+
+    /// Checks that the storage lease of the value under `key`, if any, has not expired.
+    fn validate_storage_lease(&self, key: &Key) -> Result<(), Error> {
+        if self.engine_config.storage_rent() == StorageRent::Disabled
+            || !matches!(key, Key::URef(_) | Key::Dictionary(_))
+        {
+            return Ok(());
+        }
+
+        let lease_key = Key::storage_lease(&key.normalize());
+        let maybe_lease = self
+            .tracking_copy
+            .borrow_mut()
+            .read(self.correlation_id, &lease_key)
+            .map_err(Into::into)?;
+        if let Some(StoredValue::CLValue(cl_value)) = maybe_lease {
+            let lease: StorageLease = cl_value.into_t()?;
+            if lease.is_expired() {
+                return Err(Error::StorageLeaseExpired(*key));
+            }
+        }
+        Ok(())
+    }
+
     /// Charges gas for specified amount of bytes used.
     fn charge_gas_storage(&mut self, bytes_count: usize) -> Result<(), Error> {
-        if let Some(base_key) = self.base_key().into_hash() {
-            let contract_hash = ContractHash::new(base_key);
-            if self.is_system_contract(&contract_hash)? {
-                // Don't charge storage used while executing a system contract.
-                return Ok(());
-            }
+        if self.is_executing_system_contract()? {
+            // Don't charge storage used while executing a system contract.
+            return Ok(());
         }
 
         let storage_costs = self.engine_config.wasm_config().storage_costs();
@@ -913,15 +1135,15 @@ where
         V: Into<StoredValue>,
     {
         let stored_value = value.into();
+        self.validate_stored_value_size(&stored_value)?;
 
         // Charge for amount as measured by serialized length
         let bytes_count = stored_value.serialized_length();
         self.charge_gas_storage(bytes_count)?;
 
-        self.tracking_copy
-            .borrow_mut()
-            .write(key.into(), stored_value);
-        Ok(())
+        let key = key.into();
+        self.tracking_copy.borrow_mut().write(key, stored_value);
+        self.renew_storage_lease(key)
     }
 
     /// Writes data to a global state and charges for bytes stored.
@@ -946,6 +1168,8 @@ where
         key: Key,
         value: StoredValue,
     ) -> Result<(), Error> {
+        self.validate_stored_value_size(&value)?;
+
         let value_bytes_count = value.serialized_length();
         self.charge_gas_storage(value_bytes_count)?;
 
@@ -955,7 +1179,7 @@ where
             .add(self.correlation_id, key, value)
         {
             Err(storage_error) => Err(storage_error.into()),
-            Ok(AddResult::Success) => Ok(()),
+            Ok(AddResult::Success) => self.renew_storage_lease(key),
             Ok(AddResult::KeyNotFound(key)) => Err(Error::KeyNotFound(key)),
             Ok(AddResult::TypeMismatch(type_mismatch)) => Err(Error::TypeMismatch(type_mismatch)),
             Ok(AddResult::Serialization(error)) => Err(Error::BytesRepr(error)),
@@ -1200,6 +1424,8 @@ where
         &mut self,
         dictionary_key: Key,
     ) -> Result<Option<CLValue>, Error> {
+        self.validate_storage_lease(&dictionary_key)?;
+
         let maybe_stored_value = self
             .tracking_copy
             .borrow_mut()
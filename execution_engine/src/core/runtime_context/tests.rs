@@ -1034,3 +1034,60 @@ fn associated_keys_add_full() {
         Error::AddKeyFailure(AddKeyFailure::MaxKeysLimit)
     ));
 }
+
+#[test]
+fn named_keys_put_full() {
+    let final_put_result = build_runtime_context_and_execute(Default::default(), |mut rc| {
+        for count in 0..rc.engine_config.max_named_keys() {
+            rc.put_key(format!("key-{}", count), Key::Hash([1; 32]))
+                .unwrap_or_else(|e| panic!("should put key {}: {:?}", count, e));
+        }
+
+        // Overwriting an existing named key doesn't increase the number of named keys.
+        rc.put_key("key-0".to_string(), Key::Hash([2; 32]))
+            .expect("should overwrite existing key");
+
+        rc.put_key("one-too-many".to_string(), Key::Hash([1; 32]))
+    });
+
+    assert!(matches!(
+        final_put_result.expect_err("should error out"),
+        Error::TooManyNamedKeys { max } if max == test_engine_config().max_named_keys()
+    ));
+}
+
+#[test]
+fn should_not_write_cl_value_exceeding_max_size() {
+    let mut rng = AddressGenerator::new(&DEPLOY_HASH, PHASE);
+    let uref_as_key = create_uref_as_key(&mut rng, AccessRights::WRITE);
+
+    let mut named_keys = NamedKeys::new();
+    named_keys.insert(String::new(), uref_as_key);
+
+    let max_size = test_engine_config().max_cl_value_size() as usize;
+    let write_result = build_runtime_context_and_execute(named_keys, |mut rc| {
+        let value = CLValue::from_t(vec![0u8; max_size]).unwrap();
+        rc.metered_write_gs(uref_as_key, StoredValue::CLValue(value))
+    });
+
+    // The payload is prefixed by its length, so it exceeds the maximum by 4 bytes.
+    assert!(matches!(
+        write_result.expect_err("should error out"),
+        Error::CLValueTooLarge { size, max } if size == max_size + 4 && max as usize == max_size
+    ));
+}
+
+#[test]
+fn should_not_change_other_addresses_when_creating_burn_addresses() {
+    let (burn_addr, hash_addr) =
+        build_runtime_context_and_execute(NamedKeys::new(), |mut runtime_context| {
+            let burn_addr = runtime_context.new_burn_addr()?;
+            let hash_addr = runtime_context.new_hash_address()?;
+            Ok((burn_addr, hash_addr))
+        })
+        .expect("should create addresses");
+
+    let expected_hash_addr = AddressGenerator::new(&DEPLOY_HASH, PHASE).new_hash_address();
+    assert_eq!(hash_addr, expected_hash_addr);
+    assert_ne!(burn_addr, expected_hash_addr);
+}
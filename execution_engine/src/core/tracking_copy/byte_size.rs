@@ -44,6 +44,11 @@ impl ByteSize for StoredValue {
                 StoredValue::Bid(bid) => bid.serialized_length(),
                 StoredValue::Withdraw(withdraw_purses) => withdraw_purses.serialized_length(),
                 StoredValue::Unbonding(unbonding_purses) => unbonding_purses.serialized_length(),
+                StoredValue::AuctionSnapshot(auction_snapshot) => {
+                    auction_snapshot.serialized_length()
+                }
+                StoredValue::TransferV2(transfer_v2) => transfer_v2.serialized_length(),
+                StoredValue::Burn(burn) => burn.serialized_length(),
             }
     }
 }
@@ -556,6 +556,15 @@ impl<R: StateReader<Key, StoredValue>> TrackingCopy<R> {
                 StoredValue::Unbonding(_) => {
                     return Ok(query.into_not_found_result("UnbondingPurses value found."));
                 }
+                StoredValue::AuctionSnapshot(_) => {
+                    return Ok(query.into_not_found_result("AuctionSnapshot value found."));
+                }
+                StoredValue::TransferV2(_) => {
+                    return Ok(query.into_not_found_result("TransferV2 value found."));
+                }
+                StoredValue::Burn(_) => {
+                    return Ok(query.into_not_found_result("Burn value found."));
+                }
             }
         }
     }
@@ -599,6 +608,17 @@ impl<R: StateReader<Key, StoredValue>> StateReader<Key, StoredValue> for &Tracki
     ) -> Result<Vec<Key>, Self::Error> {
         self.reader.keys_with_prefix(correlation_id, prefix)
     }
+
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        self.reader
+            .keys_with_prefix_after(correlation_id, prefix, start_after, limit)
+    }
 }
 
 /// Error conditions of a proof validation.
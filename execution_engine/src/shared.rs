@@ -1,8 +1,10 @@
 //! The shared logic of the execution engine.
 pub mod additive_map;
 pub mod execution_journal;
+pub mod gas_profile;
 pub mod host_function_costs;
 pub mod logging;
+pub mod module_cache;
 pub mod newtypes;
 pub mod opcode_costs;
 pub mod storage_costs;
@@ -0,0 +1,155 @@
+//! Opt-in aggregation of gas consumption per host function.
+//!
+//! Profiling is scoped to the current thread: while a closure passed to [`collect`] runs, every
+//! gas charge made by the runtime is attributed to the host function currently being executed.
+//! Gas charged by the instrumentation injected into Wasm code is attributed to the
+//! [`WASM_OPCODES_GROUP`] group, as the injected metering calls don't carry per-opcode metadata.
+//! Charges made outside of any host function are attributed to the [`OTHER_GROUP`] group.
+
+use std::{cell::RefCell, collections::BTreeMap};
+
+use casper_types::Gas;
+
+/// Name of the group gathering gas charged for executing Wasm instructions.
+pub const WASM_OPCODES_GROUP: &str = "wasm_opcodes";
+/// Name of the group gathering gas charged outside of any host function.
+pub const OTHER_GROUP: &str = "other";
+
+thread_local! {
+    static ACTIVE_PROFILE: RefCell<Option<ActiveProfile>> = RefCell::new(None);
+}
+
+#[derive(Default)]
+struct ActiveProfile {
+    current_group: Option<String>,
+    profile: GasProfile,
+}
+
+/// Gas consumption aggregated per host function or opcode group.
+#[derive(Clone, Default, Debug, PartialEq, Eq)]
+pub struct GasProfile(BTreeMap<String, Gas>);
+
+impl GasProfile {
+    /// Returns the gas consumed by the given group, if any was recorded.
+    pub fn get(&self, group: &str) -> Option<Gas> {
+        self.0.get(group).copied()
+    }
+
+    /// Returns an iterator over the groups and the gas consumed by each, ordered by group name.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &Gas)> {
+        self.0.iter()
+    }
+
+    /// Returns the total gas recorded across all groups.
+    pub fn total(&self) -> Gas {
+        self.0
+            .values()
+            .fold(Gas::default(), |acc, gas| saturating_add(acc, *gas))
+    }
+
+    fn record(&mut self, group: &str, amount: Gas) {
+        let entry = self.0.entry(group.to_string()).or_default();
+        *entry = saturating_add(*entry, amount);
+    }
+}
+
+fn saturating_add(lhs: Gas, rhs: Gas) -> Gas {
+    Gas::new(lhs.value().saturating_add(rhs.value()))
+}
+
+impl IntoIterator for GasProfile {
+    type Item = (String, Gas);
+    type IntoIter = std::collections::btree_map::IntoIter<String, Gas>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Runs `f` with gas profiling enabled on the current thread and returns its output together with
+/// the gas consumption recorded while it ran.
+pub fn collect<T>(f: impl FnOnce() -> T) -> (T, GasProfile) {
+    let previous = ACTIVE_PROFILE.with(|active| active.replace(Some(ActiveProfile::default())));
+    let output = f();
+    let profile = ACTIVE_PROFILE
+        .with(|active| active.replace(previous))
+        .map(|active| active.profile)
+        .unwrap_or_default();
+    (output, profile)
+}
+
+/// Attributes subsequent gas charges to the group returned by `group` until the returned guard is
+/// dropped, at which point the previous group is restored.
+///
+/// `group` is only evaluated if profiling is enabled on the current thread.
+pub(crate) fn enter_group(group: impl FnOnce() -> String) -> GroupGuard {
+    let previous = ACTIVE_PROFILE.with(|active| {
+        active
+            .borrow_mut()
+            .as_mut()
+            .map(|active| active.current_group.replace(group()))
+    });
+    GroupGuard { previous }
+}
+
+/// Records `amount` against the current group if profiling is enabled on the current thread.
+pub(crate) fn record(amount: Gas) {
+    ACTIVE_PROFILE.with(|active| {
+        if let Some(active) = active.borrow_mut().as_mut() {
+            let ActiveProfile {
+                current_group,
+                profile,
+            } = active;
+            profile.record(current_group.as_deref().unwrap_or(OTHER_GROUP), amount);
+        }
+    })
+}
+
+/// Restores the previously active group when dropped.
+#[must_use]
+pub(crate) struct GroupGuard {
+    previous: Option<Option<String>>,
+}
+
+impl Drop for GroupGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.previous.take() {
+            ACTIVE_PROFILE.with(|active| {
+                if let Some(active) = active.borrow_mut().as_mut() {
+                    active.current_group = previous;
+                }
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_record_when_disabled() {
+        let _guard = enter_group(|| unreachable!("group should not be evaluated"));
+        record(Gas::new(10.into()));
+        let ((), profile) = collect(|| ());
+        assert_eq!(profile, GasProfile::default());
+    }
+
+    #[test]
+    fn should_attribute_charges_to_nested_groups() {
+        let ((), profile) = collect(|| {
+            record(Gas::new(1.into()));
+            let _outer = enter_group(|| "CallContract".to_string());
+            record(Gas::new(2.into()));
+            {
+                let _inner = enter_group(|| WASM_OPCODES_GROUP.to_string());
+                record(Gas::new(3.into()));
+            }
+            record(Gas::new(4.into()));
+        });
+        assert_eq!(profile.get(OTHER_GROUP), Some(Gas::new(1.into())));
+        assert_eq!(profile.get("CallContract"), Some(Gas::new(6.into())));
+        assert_eq!(profile.get(WASM_OPCODES_GROUP), Some(Gas::new(3.into())));
+        assert_eq!(profile.total(), Gas::new(10.into()));
+    }
+}
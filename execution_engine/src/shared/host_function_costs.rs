@@ -8,6 +8,8 @@ use casper_types::{
     Gas,
 };
 
+use crate::core::resolvers::v1_function_index::FunctionIndex;
+
 /// Representation of argument's cost.
 pub type Cost = u32;
 
@@ -196,6 +198,14 @@ where
     }
 }
 
+/// Error returned when a host function cost table lets a host function be called for free.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, thiserror::Error)]
+#[error("host function cost table entry `{host_function}` has a cost of zero")]
+pub struct ZeroCostHostFunction {
+    /// The name of the cost table entry.
+    pub host_function: &'static str,
+}
+
 /// Definition of a host function cost table.
 #[derive(Copy, Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
 #[serde(deny_unknown_fields)]
@@ -292,6 +302,20 @@ pub struct HostFunctionCosts {
     pub enable_contract_version: HostFunction<[Cost; 4]>,
 }
 
+impl HostFunctionCosts {
+    /// Checks that every host function the runtime can dispatch which is charged from this table
+    /// has a non-zero base cost.
+    pub fn validate(&self) -> Result<(), ZeroCostHostFunction> {
+        match FunctionIndex::all()
+            .filter_map(|function| function.cost_table_entry(self))
+            .find(|(_, cost)| *cost == 0)
+        {
+            Some((host_function, _)) => Err(ZeroCostHostFunction { host_function }),
+            None => Ok(()),
+        }
+    }
+}
+
 impl Default for HostFunctionCosts {
     fn default() -> Self {
         Self {
@@ -821,6 +845,22 @@ mod tests {
 
         assert_eq!(lhs, Gas::new(rhs));
     }
+
+    #[test]
+    fn should_reject_zero_cost_host_functions() {
+        assert_eq!(HostFunctionCosts::default().validate(), Ok(()));
+
+        let costs = HostFunctionCosts {
+            dictionary_get: HostFunction::fixed(0),
+            ..Default::default()
+        };
+        assert_eq!(
+            costs.validate(),
+            Err(ZeroCostHostFunction {
+                host_function: "dictionary_get"
+            })
+        );
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,311 @@
+//! A size-bounded cache of the Wasm modules ready to be instantiated by the runtime.
+//!
+//! Session code passed as module bytes is cached once preprocessed, sparing repeated executions of
+//! the same code the validation and the injection of the gas counter and stack height limiter.
+//! Stored contracts are preprocessed when installed, so the modules of called contracts are cached
+//! once deserialized.  Modules are keyed by the code they were built from, the protocol version
+//! and the hash of the Wasm config, and the least recently used modules are evicted once the total
+//! size of the cached modules exceeds the capacity of the cache.
+//!
+//! The cache only saves work: its entries are built exactly as they would be without it, and
+//! failures to build a module are not cached.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use casper_wasm::elements::Module;
+
+use casper_hashing::Digest;
+use casper_types::{bytesrepr::ToBytes, ContractHash, ProtocolVersion};
+
+use super::wasm_config::WasmConfig;
+
+/// Default capacity of the module cache in bytes.
+pub const DEFAULT_MODULE_CACHE_CAPACITY: usize = 128 * 1024 * 1024;
+
+/// The code a cached module was built from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) enum ModuleSource {
+    /// The Wasm of a stored contract, which never changes for a given contract hash.
+    Contract(ContractHash),
+    /// Module bytes, by their hash.
+    ModuleBytes(Digest),
+}
+
+/// The key of a cached module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(crate) struct ModuleCacheKey {
+    source: ModuleSource,
+    protocol_version: ProtocolVersion,
+    wasm_config_hash: Digest,
+}
+
+impl ModuleCacheKey {
+    /// Creates the key of the module built from `source` under the given protocol version and Wasm
+    /// config.
+    pub(crate) fn new(
+        source: ModuleSource,
+        protocol_version: ProtocolVersion,
+        wasm_config: &WasmConfig,
+    ) -> Self {
+        let wasm_config_hash = wasm_config.to_bytes().map(Digest::hash).unwrap_or_default();
+        ModuleCacheKey {
+            source,
+            protocol_version,
+            wasm_config_hash,
+        }
+    }
+}
+
+/// Statistics of a module cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ModuleCacheStats {
+    /// The number of lookups which found the module in the cache.
+    pub hits: u64,
+    /// The number of lookups which had to build the module.
+    pub misses: u64,
+    /// The number of modules evicted to stay within the capacity of the cache.
+    pub evictions: u64,
+    /// The number of cached modules.
+    pub entry_count: usize,
+    /// The total size in bytes of the cached modules.
+    pub size: usize,
+}
+
+impl ModuleCacheStats {
+    /// Returns the ratio of lookups which found the module in the cache, or zero if there were no
+    /// lookups.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits.saturating_add(self.misses);
+        if lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / lookups as f64
+    }
+}
+
+struct CachedModule {
+    module: Module,
+    size: usize,
+    last_used: u64,
+}
+
+struct Inner {
+    capacity: usize,
+    entries: HashMap<ModuleCacheKey, CachedModule>,
+    /// The keys of the cached modules, by the tick at which they were last used.
+    recency: BTreeMap<u64, ModuleCacheKey>,
+    tick: u64,
+    stats: ModuleCacheStats,
+}
+
+impl Inner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, key: &ModuleCacheKey) -> Option<Module> {
+        let tick = self.next_tick();
+        let entry = self.entries.get_mut(key)?;
+        self.recency.remove(&entry.last_used);
+        self.recency.insert(tick, *key);
+        entry.last_used = tick;
+        Some(entry.module.clone())
+    }
+
+    fn insert(&mut self, key: ModuleCacheKey, module: Module, size: usize) {
+        if size > self.capacity || self.entries.contains_key(&key) {
+            return;
+        }
+        while self.stats.size + size > self.capacity {
+            let (_, evicted_key) = match self.recency.pop_first() {
+                Some(oldest) => oldest,
+                None => break,
+            };
+            if let Some(evicted) = self.entries.remove(&evicted_key) {
+                self.stats.size -= evicted.size;
+                self.stats.evictions += 1;
+            }
+        }
+        let tick = self.next_tick();
+        self.recency.insert(tick, key);
+        self.entries.insert(
+            key,
+            CachedModule {
+                module,
+                size,
+                last_used: tick,
+            },
+        );
+        self.stats.size += size;
+    }
+}
+
+/// A handle to a cache of Wasm modules, shared by all its clones.
+#[derive(Clone)]
+pub struct ModuleCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+impl ModuleCache {
+    /// Creates an empty cache holding modules of up to `capacity` bytes in total.  A capacity of
+    /// zero disables caching.
+    pub fn new(capacity: usize) -> Self {
+        ModuleCache {
+            inner: Arc::new(Mutex::new(Inner {
+                capacity,
+                entries: HashMap::new(),
+                recency: BTreeMap::new(),
+                tick: 0,
+                stats: ModuleCacheStats::default(),
+            })),
+        }
+    }
+
+    /// Returns the capacity of the cache in bytes.
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    /// Returns the statistics of the cache.
+    pub fn stats(&self) -> ModuleCacheStats {
+        let inner = self.lock();
+        ModuleCacheStats {
+            entry_count: inner.entries.len(),
+            ..inner.stats
+        }
+    }
+
+    /// Returns the module cached under `key`, or builds it with `build` and caches it with the
+    /// given size in bytes.
+    ///
+    /// The cache is not locked while building the module, so concurrent lookups of the same
+    /// missing module may each build it.
+    pub(crate) fn get_or_try_insert_with<F, E>(
+        &self,
+        key: ModuleCacheKey,
+        size: usize,
+        build: F,
+    ) -> Result<Module, E>
+    where
+        F: FnOnce() -> Result<Module, E>,
+    {
+        {
+            let mut inner = self.lock();
+            if let Some(module) = inner.get(&key) {
+                inner.stats.hits += 1;
+                return Ok(module);
+            }
+            inner.stats.misses += 1;
+        }
+        let module = build()?;
+        self.lock().insert(key, module.clone(), size);
+        Ok(module)
+    }
+
+    fn lock(&self) -> MutexGuard<Inner> {
+        // A panic while holding the lock cannot leave the cache inconsistent, as entries are
+        // inserted and evicted before the lock is released.
+        self.inner
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        ModuleCache::new(DEFAULT_MODULE_CACHE_CAPACITY)
+    }
+}
+
+impl Debug for ModuleCache {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("ModuleCache")
+            .field("capacity", &self.capacity())
+            .field("stats", &self.stats())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use super::*;
+
+    fn key(byte: u8) -> ModuleCacheKey {
+        ModuleCacheKey::new(
+            ModuleSource::Contract(ContractHash::new([byte; 32])),
+            ProtocolVersion::V1_0_0,
+            &WasmConfig::default(),
+        )
+    }
+
+    fn get(cache: &ModuleCache, byte: u8, size: usize) -> bool {
+        let mut built = false;
+        cache
+            .get_or_try_insert_with(key(byte), size, || {
+                built = true;
+                Ok::<_, Infallible>(Module::default())
+            })
+            .unwrap();
+        !built
+    }
+
+    #[test]
+    fn should_evict_least_recently_used_modules() {
+        let cache = ModuleCache::new(100);
+        assert!(!get(&cache, 1, 40));
+        assert!(!get(&cache, 2, 40));
+        assert!(get(&cache, 1, 40));
+
+        // Caching a third module evicts the least recently used one.
+        assert!(!get(&cache, 3, 40));
+        assert!(get(&cache, 1, 40));
+        assert!(get(&cache, 3, 40));
+        assert!(!get(&cache, 2, 40));
+
+        // Modules larger than the capacity are never cached.
+        assert!(!get(&cache, 4, 101));
+        assert!(!get(&cache, 4, 101));
+
+        assert_eq!(
+            cache.stats(),
+            ModuleCacheStats {
+                hits: 3,
+                misses: 6,
+                evictions: 2,
+                entry_count: 2,
+                size: 80,
+            }
+        );
+    }
+
+    #[test]
+    fn should_key_modules_by_wasm_config() {
+        let cache = ModuleCache::new(100);
+        assert!(!get(&cache, 1, 10));
+
+        let mut wasm_config = WasmConfig::default();
+        wasm_config.max_memory += 1;
+        let other_key = ModuleCacheKey::new(
+            ModuleSource::Contract(ContractHash::new([1; 32])),
+            ProtocolVersion::V1_0_0,
+            &wasm_config,
+        );
+        assert_ne!(key(1), other_key);
+    }
+
+    #[test]
+    fn should_not_cache_when_disabled() {
+        let cache = ModuleCache::new(0);
+        assert!(!get(&cache, 1, 10));
+        assert!(!get(&cache, 1, 10));
+        assert_eq!(cache.stats().entry_count, 0);
+    }
+}
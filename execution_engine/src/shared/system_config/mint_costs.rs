@@ -18,6 +18,12 @@ pub const DEFAULT_TRANSFER_COST: u32 = 10_000;
 pub const DEFAULT_READ_BASE_ROUND_REWARD_COST: u32 = 10_000;
 /// Default cost of the `mint_into_existing_purse` mint entry point.
 pub const DEFAULT_MINT_INTO_EXISTING_PURSE_COST: u32 = 2_500_000_000;
+/// Default cost of the `set_mint_paused` mint entry point.
+pub const DEFAULT_SET_MINT_PAUSED_COST: u32 = 10_000;
+/// Default cost of the `burn` mint entry point.
+pub const DEFAULT_BURN_COST: u32 = 10_000;
+/// Default cost of the `approve` mint entry point.
+pub const DEFAULT_APPROVE_COST: u32 = 10_000;
 
 /// Description of the costs of calling mint entry points.
 #[derive(Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize)]
@@ -37,6 +43,12 @@ pub struct MintCosts {
     pub read_base_round_reward: u32,
     /// Cost of calling the `mint_into_existing_purse` entry point.
     pub mint_into_existing_purse: u32,
+    /// Cost of calling the `set_mint_paused` entry point.
+    pub set_mint_paused: u32,
+    /// Cost of calling the `burn` entry point.
+    pub burn: u32,
+    /// Cost of calling the `approve` entry point.
+    pub approve: u32,
 }
 
 impl Default for MintCosts {
@@ -49,6 +61,9 @@ impl Default for MintCosts {
             transfer: DEFAULT_TRANSFER_COST,
             read_base_round_reward: DEFAULT_READ_BASE_ROUND_REWARD_COST,
             mint_into_existing_purse: DEFAULT_MINT_INTO_EXISTING_PURSE_COST,
+            set_mint_paused: DEFAULT_SET_MINT_PAUSED_COST,
+            burn: DEFAULT_BURN_COST,
+            approve: DEFAULT_APPROVE_COST,
         }
     }
 }
@@ -65,6 +80,9 @@ impl ToBytes for MintCosts {
             transfer,
             read_base_round_reward,
             mint_into_existing_purse,
+            set_mint_paused,
+            burn,
+            approve,
         } = self;
 
         ret.append(&mut mint.to_bytes()?);
@@ -74,6 +92,9 @@ impl ToBytes for MintCosts {
         ret.append(&mut transfer.to_bytes()?);
         ret.append(&mut read_base_round_reward.to_bytes()?);
         ret.append(&mut mint_into_existing_purse.to_bytes()?);
+        ret.append(&mut set_mint_paused.to_bytes()?);
+        ret.append(&mut burn.to_bytes()?);
+        ret.append(&mut approve.to_bytes()?);
 
         Ok(ret)
     }
@@ -87,6 +108,9 @@ impl ToBytes for MintCosts {
             transfer,
             read_base_round_reward,
             mint_into_existing_purse,
+            set_mint_paused,
+            burn,
+            approve,
         } = self;
 
         mint.serialized_length()
@@ -96,6 +120,9 @@ impl ToBytes for MintCosts {
             + transfer.serialized_length()
             + read_base_round_reward.serialized_length()
             + mint_into_existing_purse.serialized_length()
+            + set_mint_paused.serialized_length()
+            + burn.serialized_length()
+            + approve.serialized_length()
     }
 }
 
@@ -108,6 +135,9 @@ impl FromBytes for MintCosts {
         let (transfer, rem) = FromBytes::from_bytes(rem)?;
         let (read_base_round_reward, rem) = FromBytes::from_bytes(rem)?;
         let (mint_into_existing_purse, rem) = FromBytes::from_bytes(rem)?;
+        let (set_mint_paused, rem) = FromBytes::from_bytes(rem)?;
+        let (burn, rem) = FromBytes::from_bytes(rem)?;
+        let (approve, rem) = FromBytes::from_bytes(rem)?;
 
         Ok((
             Self {
@@ -118,6 +148,9 @@ impl FromBytes for MintCosts {
                 transfer,
                 read_base_round_reward,
                 mint_into_existing_purse,
+                set_mint_paused,
+                burn,
+                approve,
             },
             rem,
         ))
@@ -134,6 +167,9 @@ impl Distribution<MintCosts> for Standard {
             transfer: rng.gen(),
             read_base_round_reward: rng.gen(),
             mint_into_existing_purse: rng.gen(),
+            set_mint_paused: rng.gen(),
+            burn: rng.gen(),
+            approve: rng.gen(),
         }
     }
 }
@@ -154,6 +190,9 @@ pub mod gens {
             transfer in num::u32::ANY,
             read_base_round_reward in num::u32::ANY,
             mint_into_existing_purse in num::u32::ANY,
+            set_mint_paused in num::u32::ANY,
+            burn in num::u32::ANY,
+            approve in num::u32::ANY,
         ) -> MintCosts {
             MintCosts {
                 mint,
@@ -163,6 +202,9 @@ pub mod gens {
                 transfer,
                 read_base_round_reward,
                 mint_into_existing_purse,
+                set_mint_paused,
+                burn,
+                approve,
             }
         }
     }
@@ -232,6 +232,21 @@ impl Transform {
                     let found = "Unbonding".to_string();
                     Err(StoredValueTypeMismatch::new(expected, found).into())
                 }
+                StoredValue::AuctionSnapshot(_) => {
+                    let expected = "Contract or Account".to_string();
+                    let found = "AuctionSnapshot".to_string();
+                    Err(StoredValueTypeMismatch::new(expected, found).into())
+                }
+                StoredValue::TransferV2(_) => {
+                    let expected = "Contract or Account".to_string();
+                    let found = "TransferV2".to_string();
+                    Err(StoredValueTypeMismatch::new(expected, found).into())
+                }
+                StoredValue::Burn(_) => {
+                    let expected = "Contract or Account".to_string();
+                    let found = "Burn".to_string();
+                    Err(StoredValueTypeMismatch::new(expected, found).into())
+                }
             },
             Transform::Failure(error) => Err(error),
         }
@@ -369,6 +384,13 @@ impl From<&Transform> for casper_types::Transform {
             Transform::Write(StoredValue::Unbonding(unbonding_purses)) => {
                 casper_types::Transform::WriteUnbonding(unbonding_purses.clone())
             }
+            Transform::Write(StoredValue::AuctionSnapshot(auction_snapshot)) => {
+                casper_types::Transform::WriteAuctionSnapshot(auction_snapshot.clone())
+            }
+            Transform::Write(StoredValue::TransferV2(transfer_v2)) => {
+                casper_types::Transform::WriteTransferV2(*transfer_v2)
+            }
+            Transform::Write(StoredValue::Burn(burn)) => casper_types::Transform::WriteBurn(*burn),
             Transform::AddInt32(value) => casper_types::Transform::AddInt32(*value),
             Transform::AddUInt64(value) => casper_types::Transform::AddUInt64(*value),
             Transform::AddUInt128(value) => casper_types::Transform::AddUInt128(*value),
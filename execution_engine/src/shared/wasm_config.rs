@@ -13,6 +13,12 @@ use super::{
 pub const DEFAULT_WASM_MAX_MEMORY: u32 = 64;
 /// Default maximum stack height.
 pub const DEFAULT_MAX_STACK_HEIGHT: u32 = 500;
+/// We only allow maximum of 4k function pointers in a table section.
+pub const DEFAULT_MAX_TABLE_SIZE: u32 = 4096;
+/// Default maximum number of function references in the element segments of a module.
+pub const DEFAULT_MAX_TABLE_ELEMENTS: u32 = 4096;
+/// Default maximum number of distinct signatures used by the indirect calls of a module.
+pub const DEFAULT_MAX_INDIRECT_CALL_SIGNATURES: u32 = 256;
 
 /// Configuration of the Wasm execution environment.
 ///
@@ -25,6 +31,12 @@ pub struct WasmConfig {
     pub max_memory: u32,
     /// Max stack height (native WebAssembly stack limiter).
     pub max_stack_height: u32,
+    /// Maximum initial and maximum size of the table of a module.
+    pub max_table_size: u32,
+    /// Maximum total number of function references in the element segments of a module.
+    pub max_table_elements: u32,
+    /// Maximum number of distinct function signatures used by the indirect calls of a module.
+    pub max_indirect_call_signatures: u32,
     /// Wasm opcode costs table.
     opcode_costs: OpcodeCosts,
     /// Storage costs.
@@ -38,6 +50,9 @@ impl WasmConfig {
     pub const fn new(
         max_memory: u32,
         max_stack_height: u32,
+        max_table_size: u32,
+        max_table_elements: u32,
+        max_indirect_call_signatures: u32,
         opcode_costs: OpcodeCosts,
         storage_costs: StorageCosts,
         host_function_costs: HostFunctionCosts,
@@ -45,6 +60,9 @@ impl WasmConfig {
         Self {
             max_memory,
             max_stack_height,
+            max_table_size,
+            max_table_elements,
+            max_indirect_call_signatures,
             opcode_costs,
             storage_costs,
             host_function_costs,
@@ -61,6 +79,11 @@ impl WasmConfig {
         self.storage_costs
     }
 
+    /// Returns host function costs.
+    pub fn host_function_costs(&self) -> &HostFunctionCosts {
+        &self.host_function_costs
+    }
+
     /// Returns host function costs and consumes this object.
     pub fn take_host_function_costs(self) -> HostFunctionCosts {
         self.host_function_costs
@@ -72,6 +95,9 @@ impl Default for WasmConfig {
         Self {
             max_memory: DEFAULT_WASM_MAX_MEMORY,
             max_stack_height: DEFAULT_MAX_STACK_HEIGHT,
+            max_table_size: DEFAULT_MAX_TABLE_SIZE,
+            max_table_elements: DEFAULT_MAX_TABLE_ELEMENTS,
+            max_indirect_call_signatures: DEFAULT_MAX_INDIRECT_CALL_SIGNATURES,
             opcode_costs: OpcodeCosts::default(),
             storage_costs: StorageCosts::default(),
             host_function_costs: HostFunctionCosts::default(),
@@ -85,6 +111,9 @@ impl ToBytes for WasmConfig {
 
         ret.append(&mut self.max_memory.to_bytes()?);
         ret.append(&mut self.max_stack_height.to_bytes()?);
+        ret.append(&mut self.max_table_size.to_bytes()?);
+        ret.append(&mut self.max_table_elements.to_bytes()?);
+        ret.append(&mut self.max_indirect_call_signatures.to_bytes()?);
         ret.append(&mut self.opcode_costs.to_bytes()?);
         ret.append(&mut self.storage_costs.to_bytes()?);
         ret.append(&mut self.host_function_costs.to_bytes()?);
@@ -95,6 +124,9 @@ impl ToBytes for WasmConfig {
     fn serialized_length(&self) -> usize {
         self.max_memory.serialized_length()
             + self.max_stack_height.serialized_length()
+            + self.max_table_size.serialized_length()
+            + self.max_table_elements.serialized_length()
+            + self.max_indirect_call_signatures.serialized_length()
             + self.opcode_costs.serialized_length()
             + self.storage_costs.serialized_length()
             + self.host_function_costs.serialized_length()
@@ -105,6 +137,9 @@ impl FromBytes for WasmConfig {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (max_memory, rem) = FromBytes::from_bytes(bytes)?;
         let (max_stack_height, rem) = FromBytes::from_bytes(rem)?;
+        let (max_table_size, rem) = FromBytes::from_bytes(rem)?;
+        let (max_table_elements, rem) = FromBytes::from_bytes(rem)?;
+        let (max_indirect_call_signatures, rem) = FromBytes::from_bytes(rem)?;
         let (opcode_costs, rem) = FromBytes::from_bytes(rem)?;
         let (storage_costs, rem) = FromBytes::from_bytes(rem)?;
         let (host_function_costs, rem) = FromBytes::from_bytes(rem)?;
@@ -113,6 +148,9 @@ impl FromBytes for WasmConfig {
             WasmConfig {
                 max_memory,
                 max_stack_height,
+                max_table_size,
+                max_table_elements,
+                max_indirect_call_signatures,
                 opcode_costs,
                 storage_costs,
                 host_function_costs,
@@ -127,6 +165,9 @@ impl Distribution<WasmConfig> for Standard {
         WasmConfig {
             max_memory: rng.gen(),
             max_stack_height: rng.gen(),
+            max_table_size: rng.gen(),
+            max_table_elements: rng.gen(),
+            max_indirect_call_signatures: rng.gen(),
             opcode_costs: rng.gen(),
             storage_costs: rng.gen(),
             host_function_costs: rng.gen(),
@@ -149,6 +190,9 @@ pub mod gens {
         pub fn wasm_config_arb() (
             max_memory in num::u32::ANY,
             max_stack_height in num::u32::ANY,
+            max_table_size in num::u32::ANY,
+            max_table_elements in num::u32::ANY,
+            max_indirect_call_signatures in num::u32::ANY,
             opcode_costs in opcode_costs_arb(),
             storage_costs in storage_costs_arb(),
             host_function_costs in host_function_costs_arb(),
@@ -156,6 +200,9 @@ pub mod gens {
             WasmConfig {
                 max_memory,
                 max_stack_height,
+                max_table_size,
+                max_table_elements,
+                max_indirect_call_signatures,
                 opcode_costs,
                 storage_costs,
                 host_function_costs,
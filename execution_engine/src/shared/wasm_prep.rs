@@ -1,19 +1,24 @@
 //! Preprocessing of Wasm modules.
+use std::collections::BTreeSet;
+
 use casper_wasm::elements::{
     self, External, Instruction, Internal, MemorySection, Module, Section, TableType, Type,
 };
 use casper_wasm_utils::{self, stack_height};
+use datasize::DataSize;
+use serde::Serialize;
 use thiserror::Error;
 
 use super::wasm_config::WasmConfig;
 use crate::core::execution;
 
 const DEFAULT_GAS_MODULE_NAME: &str = "env";
+/// Name of the module through which the host provides its functions to Wasm modules.
+const HOST_MODULE_NAME: &str = "env";
 /// Name of the internal gas function injected by [`casper_wasm_utils::inject_gas_counter`].
 const INTERNAL_GAS_FUNCTION_NAME: &str = "gas";
 
-/// We only allow maximum of 4k function pointers in a table section.
-pub const DEFAULT_MAX_TABLE_SIZE: u32 = 4096;
+pub use super::wasm_config::DEFAULT_MAX_TABLE_SIZE;
 /// Maximum number of elements that can appear as immediate value to the br_table instruction.
 pub const DEFAULT_BR_TABLE_MAX_SIZE: u32 = 256;
 /// Maximum number of global a module is allowed to declare.
@@ -44,6 +49,22 @@ pub enum WasmValidationError {
     /// Number of the tables in a Wasm must be at most one.
     #[error("the number of tables must be at most one")]
     MoreThanOneTable,
+    /// Element segments declare more function references than allowed.
+    #[error("number of table elements ({actual}) exceeds allowed limit of {max}")]
+    TooManyTableElements {
+        /// Maximum allowed function references.
+        max: u32,
+        /// Actual number of function references declared in the element segments of the Wasm.
+        actual: usize,
+    },
+    /// Indirect calls use more distinct function signatures than allowed.
+    #[error("number of indirect call signatures ({actual}) exceeds allowed limit of {max}")]
+    TooManyIndirectCallSignatures {
+        /// Maximum allowed distinct signatures.
+        max: u32,
+        /// Actual number of distinct signatures used by indirect calls in the Wasm.
+        actual: usize,
+    },
     /// Length of a br_table exceeded the maximum allowed size.
     #[error("maximum br_table size of {actual} exceeds allowed limit of {max}")]
     BrTableSizeExceeded {
@@ -91,6 +112,27 @@ pub enum WasmValidationError {
     },
 }
 
+/// An import of a Wasm module which the host would not be able to resolve.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, DataSize, Error)]
+#[non_exhaustive]
+pub enum ImportViolation {
+    /// Module imports from a module other than the one through which the host provides its
+    /// functions.
+    #[error("import of '{field}' from unknown module '{module}'")]
+    UnknownModule {
+        /// Name of the imported module.
+        module: String,
+        /// Name of the imported item.
+        field: String,
+    },
+    /// Module imports a function which is not among the allowed host functions.
+    #[error("import of unknown host function '{name}'")]
+    UnknownHostFunction {
+        /// Name of the imported function.
+        name: String,
+    },
+}
+
 /// An error emitted by the Wasm preprocessor.
 #[derive(Debug, Clone, Error)]
 #[non_exhaustive]
@@ -275,6 +317,52 @@ fn ensure_table_size_limit(mut module: Module, limit: u32) -> Result<Module, Was
     Ok(module)
 }
 
+/// Ensures the element segments of the module declare at most `limit` function references in
+/// total.
+fn ensure_table_elements_limit(module: &Module, limit: u32) -> Result<(), WasmValidationError> {
+    if let Some(element_section) = module.elements_section() {
+        let actual = element_section
+            .entries()
+            .iter()
+            .map(|element_segment| element_segment.members().len())
+            .sum::<usize>();
+        if actual > limit as usize {
+            return Err(WasmValidationError::TooManyTableElements { max: limit, actual });
+        }
+    }
+    Ok(())
+}
+
+/// Ensures the `call_indirect` instructions of the module use at most `limit` distinct function
+/// signatures.
+fn ensure_indirect_call_signatures_limit(
+    module: &Module,
+    limit: u32,
+) -> Result<(), WasmValidationError> {
+    let code_section = if let Some(code_section) = module.code_section() {
+        code_section
+    } else {
+        return Ok(());
+    };
+
+    let signatures: BTreeSet<u32> = code_section
+        .bodies()
+        .iter()
+        .flat_map(|body| body.code().elements())
+        .filter_map(|instr| match instr {
+            Instruction::CallIndirect(type_index, _) => Some(*type_index),
+            _ => None,
+        })
+        .collect();
+    if signatures.len() > limit as usize {
+        return Err(WasmValidationError::TooManyIndirectCallSignatures {
+            max: limit,
+            actual: signatures.len(),
+        });
+    }
+    Ok(())
+}
+
 /// Ensure that any `br_table` instruction adheres to its immediate value limit.
 fn ensure_br_table_size_limit(module: &Module, limit: u32) -> Result<(), WasmValidationError> {
     let code_section = if let Some(type_section) = module.code_section() {
@@ -360,10 +448,46 @@ fn ensure_valid_imports(module: &Module) -> Result<(), WasmValidationError> {
     Ok(())
 }
 
+/// Validates the imports of a module against the names of the host functions allowed for it.
+///
+/// The allowed host functions depend on the protocol version under which the module is to be
+/// executed, and are supplied by the caller.
+///
+/// Returns every import which the host would not be able to resolve, in the order they are
+/// declared, or an empty list if all imports are valid.
+pub fn validate_imports(module: &Module, allowed_host_functions: &[&str]) -> Vec<ImportViolation> {
+    let import_entries = module
+        .import_section()
+        .map(|is| is.entries())
+        .unwrap_or(&[]);
+
+    import_entries
+        .iter()
+        .filter_map(|import| {
+            if import.module() != HOST_MODULE_NAME {
+                return Some(ImportViolation::UnknownModule {
+                    module: import.module().to_string(),
+                    field: import.field().to_string(),
+                });
+            }
+            match import.external() {
+                External::Function(_) if !allowed_host_functions.contains(&import.field()) => {
+                    Some(ImportViolation::UnknownHostFunction {
+                        name: import.field().to_string(),
+                    })
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
 /// Preprocesses Wasm bytes and returns a module.
 ///
 /// This process consists of a few steps:
 /// - Validate that the given bytes contain a memory section, and check the memory page limit.
+/// - Check the table size, the number of table elements and the number of distinct signatures of
+///   indirect calls against their limits.
 /// - Inject gas counters into the code, which makes it possible for the executed Wasm to be charged
 ///   for opcodes; this also validates opcodes and ensures that there are no forbidden opcodes in
 ///   use, such as floating point opcodes.
@@ -385,7 +509,9 @@ pub fn preprocess(
         return Err(PreprocessingError::MissingMemorySection);
     }
 
-    let module = ensure_table_size_limit(module, DEFAULT_MAX_TABLE_SIZE)?;
+    let module = ensure_table_size_limit(module, wasm_config.max_table_size)?;
+    ensure_table_elements_limit(&module, wasm_config.max_table_elements)?;
+    ensure_indirect_call_signatures_limit(&module, wasm_config.max_indirect_call_signatures)?;
     ensure_br_table_size_limit(&module, DEFAULT_BR_TABLE_MAX_SIZE)?;
     ensure_global_variable_limit(&module, DEFAULT_MAX_GLOBALS)?;
     ensure_parameter_limit(&module, DEFAULT_MAX_PARAMETER_COUNT)?;
@@ -441,12 +567,70 @@ mod tests {
     use casper_types::contracts::DEFAULT_ENTRY_POINT_NAME;
     use casper_wasm::{
         builder,
-        elements::{CodeSection, Instructions},
+        elements::{CodeSection, Instructions, ValueType},
     };
     use walrus::{FunctionBuilder, ModuleConfig, ValType};
 
     use super::*;
 
+    #[test]
+    fn should_report_imports_not_provided_by_host() {
+        let module = builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .build()
+            .build()
+            .import()
+            .module(HOST_MODULE_NAME)
+            .field("casper_revert")
+            .external()
+            .func(0)
+            .build()
+            .import()
+            .module(HOST_MODULE_NAME)
+            .field("casper_future_function")
+            .external()
+            .func(0)
+            .build()
+            .import()
+            .module("wasi_snapshot_preview1")
+            .field("fd_write")
+            .external()
+            .func(0)
+            .build()
+            .import()
+            .module(HOST_MODULE_NAME)
+            .field("memory")
+            .external()
+            .memory(1, None)
+            .build()
+            .build();
+
+        assert_eq!(
+            validate_imports(&module, &["casper_revert", "casper_ret"]),
+            vec![
+                ImportViolation::UnknownHostFunction {
+                    name: "casper_future_function".to_string()
+                },
+                ImportViolation::UnknownModule {
+                    module: "wasi_snapshot_preview1".to_string(),
+                    field: "fd_write".to_string()
+                },
+            ]
+        );
+        // Functions can only be provided through the host module.
+        let violations = validate_imports(
+            &module,
+            &["casper_revert", "casper_future_function", "fd_write"],
+        );
+        assert!(matches!(
+            violations.as_slice(),
+            [ImportViolation::UnknownModule { .. }]
+        ));
+    }
+
     #[test]
     fn should_not_panic_on_empty_memory() {
         // These bytes were generated during fuzz testing and are compiled from Wasm which
@@ -617,6 +801,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_enforce_table_elements_limit() {
+        let module = builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![Instruction::Nop, Instruction::End]))
+            .build()
+            .build()
+            .table()
+            .with_min(3)
+            .with_element(0, vec![0, 0, 0])
+            .build()
+            // Memory section is mandatory
+            .memory()
+            .build()
+            .build();
+        let module_bytes = casper_wasm::serialize(module).expect("should serialize");
+        let mut wasm_config = WasmConfig::default();
+        wasm_config.max_table_elements = 2;
+        let error = preprocess(wasm_config, &module_bytes).expect_err("should fail with an error");
+        assert!(
+            matches!(
+                &error,
+                PreprocessingError::WasmValidation(WasmValidationError::TooManyTableElements {
+                    max: 2,
+                    actual: 3
+                })
+            ),
+            "{:?}",
+            error,
+        );
+    }
+
+    #[test]
+    fn should_enforce_indirect_call_signatures_limit() {
+        let module = builder::module()
+            .function()
+            .signature()
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![
+                Instruction::I32Const(0),
+                Instruction::CallIndirect(0, 0),
+                Instruction::I32Const(0),
+                Instruction::I32Const(0),
+                Instruction::CallIndirect(1, 0),
+                Instruction::End,
+            ]))
+            .build()
+            .build()
+            .function()
+            .signature()
+            .with_param(ValueType::I32)
+            .build()
+            .body()
+            .with_instructions(Instructions::new(vec![Instruction::End]))
+            .build()
+            .build()
+            .table()
+            .with_min(2)
+            .with_element(0, vec![0, 1])
+            .build()
+            // Memory section is mandatory
+            .memory()
+            .build()
+            .build();
+        let module_bytes = casper_wasm::serialize(module).expect("should serialize");
+        let mut wasm_config = WasmConfig::default();
+        wasm_config.max_indirect_call_signatures = 1;
+        let error = preprocess(wasm_config, &module_bytes).expect_err("should fail with an error");
+        assert!(
+            matches!(
+                &error,
+                PreprocessingError::WasmValidation(
+                    WasmValidationError::TooManyIndirectCallSignatures { max: 1, actual: 2 }
+                )
+            ),
+            "{:?}",
+            error,
+        );
+    }
+
     #[test]
     fn should_not_accept_multi_value_proposal_wasm() {
         let module_bytes = {
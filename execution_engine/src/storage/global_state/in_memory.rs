@@ -7,7 +7,7 @@ use crate::{
     shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
     storage::{
         error::{self, in_memory},
-        global_state::{commit, CommitProvider, StateProvider, StateReader},
+        global_state::{collect_keys_after, commit, CommitProvider, StateProvider, StateReader},
         store::Store,
         transaction_source::{
             in_memory::{
@@ -197,6 +197,26 @@ impl StateReader<Key, StoredValue> for InMemoryGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = keys_with_prefix::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let ret = collect_keys_after(keys_iter, start_after, limit)?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl CommitProvider for InMemoryGlobalState {
@@ -1,4 +1,12 @@
-use std::{collections::HashMap, ops::Deref, sync::Arc};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    mem,
+    ops::Deref,
+    sync::{Arc, Mutex},
+};
+
+use lmdb::{Cursor, Database, DatabaseFlags, RwTransaction, WriteFlags};
 
 use casper_hashing::Digest;
 use casper_types::{Key, StoredValue};
@@ -8,13 +16,14 @@ use crate::{
     storage::{
         error,
         global_state::{
-            commit, put_stored_values, scratch::ScratchGlobalState, CommitProvider, StateProvider,
-            StateReader,
+            collect_keys_after, commit, put_stored_values, scratch::ScratchGlobalState,
+            CommitProvider, StateProvider, StateReader,
         },
         store::Store,
         transaction_source::{lmdb::LmdbEnvironment, Transaction, TransactionSource},
         trie::{
-            merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie, Trie, TrieRaw,
+            self, merkle_proof::TrieMerkleProof, operations::create_hashed_empty_trie,
+            LazyTrieLeaf, Trie, TrieRaw,
         },
         trie_store::{
             lmdb::{LmdbTrieStore, ScratchTrieStore},
@@ -35,6 +44,21 @@ pub struct LmdbGlobalState {
     // TODO: make this a lazy-static
     /// Empty root hash used for a new trie.
     pub(crate) empty_root_hash: Digest,
+    /// The hashes of the trie nodes written while tries are being pruned, if they are.
+    written_while_pruning: Mutex<Option<Vec<Digest>>>,
+}
+
+/// Name of the database in which the trie nodes reachable from the retained state roots are marked
+/// while pruning.
+const TRIE_PRUNING_MARKS_DB_NAME: &str = "TRIE_PRUNING_MARKS";
+
+/// The outcome of pruning the trie nodes unreachable from the retained state roots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TriePruningSummary {
+    /// The number of trie nodes reachable from the retained state roots, which were kept.
+    pub retained_count: u64,
+    /// The number of unreachable trie nodes, which were deleted.
+    pub pruned_count: u64,
 }
 
 /// Represents a "view" of global state at a particular root hash.
@@ -75,6 +99,7 @@ impl LmdbGlobalState {
             environment,
             trie_store,
             empty_root_hash,
+            written_while_pruning: Mutex::new(None),
         }
     }
 
@@ -94,16 +119,18 @@ impl LmdbGlobalState {
         prestate_hash: Digest,
         stored_values: HashMap<Key, StoredValue>,
     ) -> Result<Digest, error::Error> {
-        let scratch_trie = self.get_scratch_store();
-        let new_state_root = put_stored_values::<_, _, error::Error>(
-            &scratch_trie,
-            &scratch_trie,
-            correlation_id,
-            prestate_hash,
-            stored_values,
-        )?;
-        scratch_trie.write_root_to_db(new_state_root)?;
-        Ok(new_state_root)
+        self.record_written(|| {
+            let scratch_trie = self.get_scratch_store();
+            let new_state_root = put_stored_values::<_, _, error::Error>(
+                &scratch_trie,
+                &scratch_trie,
+                correlation_id,
+                prestate_hash,
+                stored_values,
+            )?;
+            scratch_trie.write_root_to_db(new_state_root)?;
+            Ok(new_state_root)
+        })
     }
 
     /// Gets a scratch trie store.
@@ -127,6 +154,215 @@ impl LmdbGlobalState {
     pub fn empty_state_root_hash(&self) -> Digest {
         self.empty_root_hash
     }
+
+    /// Starts recording the trie nodes written from now on, so that the next call to
+    /// [`prune_unreachable_tries`](Self::prune_unreachable_tries) retains them.
+    ///
+    /// Returns `false` if tries are already being pruned.
+    pub fn start_trie_pruning(&self) -> Result<bool, error::Error> {
+        let mut written_while_pruning = self.written_while_pruning.lock()?;
+        if written_while_pruning.is_some() {
+            return Ok(false);
+        }
+        *written_while_pruning = Some(Vec::new());
+        Ok(true)
+    }
+
+    /// Stops recording the trie nodes written since
+    /// [`start_trie_pruning`](Self::start_trie_pruning) without pruning anything.
+    pub fn cancel_trie_pruning(&self) -> Result<(), error::Error> {
+        *self.written_while_pruning.lock()? = None;
+        Ok(())
+    }
+
+    /// Deletes every trie node which is reachable neither from any of
+    /// `retained_state_root_hashes` nor from the empty root, nor from the trie nodes written since
+    /// tries started being pruned.
+    ///
+    /// The reachable trie nodes are marked in a dedicated database, then the unmarked ones swept,
+    /// in transactions each marking or sweeping at most `batch_size` trie nodes, so that commits
+    /// only wait for the current batch.  Before each batch is swept, the trie nodes written in the
+    /// meantime are marked too: the pre-states of commits made while pruning must hence be among
+    /// the retained state roots.  Retained state roots which are missing or only partially
+    /// present, e.g. while being synced, are marked as far as they are present.
+    ///
+    /// Starts recording the written trie nodes if [`start_trie_pruning`](Self::start_trie_pruning)
+    /// wasn't called, and stops once done.  Must not be called concurrently.
+    pub fn prune_unreachable_tries(
+        &self,
+        retained_state_root_hashes: &[Digest],
+        batch_size: usize,
+    ) -> Result<TriePruningSummary, error::Error> {
+        self.written_while_pruning
+            .lock()?
+            .get_or_insert_with(Vec::new);
+        let result = self.mark_and_sweep(retained_state_root_hashes, batch_size.max(1));
+        self.cancel_trie_pruning()?;
+        result
+    }
+
+    fn mark_and_sweep(
+        &self,
+        retained_state_root_hashes: &[Digest],
+        batch_size: usize,
+    ) -> Result<TriePruningSummary, error::Error> {
+        let marks = self
+            .environment
+            .env()
+            .create_db(Some(TRIE_PRUNING_MARKS_DB_NAME), DatabaseFlags::empty())?;
+        // Marks left by an interrupted pruning could retain unreachable trie nodes.
+        let mut txn = self.environment.create_read_write_txn()?;
+        txn.clear_db(marks)?;
+        txn.commit()?;
+
+        let mut summary = TriePruningSummary::default();
+
+        let mut unvisited = retained_state_root_hashes.to_vec();
+        unvisited.push(self.empty_root_hash);
+        while !unvisited.is_empty() {
+            let mut txn = self.environment.create_read_write_txn()?;
+            summary.retained_count += mark_reachable(
+                &mut txn,
+                &self.trie_store,
+                marks,
+                &mut unvisited,
+                batch_size,
+            )?;
+            txn.commit()?;
+        }
+
+        let mut start_after = None;
+        loop {
+            // Writes wait for the batch to be swept, so that none of the trie nodes they write can
+            // be.
+            let mut written_while_pruning = self.written_while_pruning.lock()?;
+            let mut txn = self.environment.create_read_write_txn()?;
+            let mut written = written_while_pruning
+                .as_mut()
+                .map(mem::take)
+                .unwrap_or_default();
+            summary.retained_count +=
+                mark_reachable(&mut txn, &self.trie_store, marks, &mut written, usize::MAX)?;
+            let (pruned_count, last_visited) = sweep_unmarked(
+                &mut txn,
+                self.trie_store.get_db(),
+                marks,
+                start_after.as_ref(),
+                batch_size,
+            )?;
+            txn.commit()?;
+            drop(written_while_pruning);
+
+            summary.pruned_count += pruned_count;
+            match last_visited {
+                Some(trie_hash) => start_after = Some(trie_hash),
+                None => break,
+            }
+        }
+
+        let mut txn = self.environment.create_read_write_txn()?;
+        txn.clear_db(marks)?;
+        txn.commit()?;
+
+        Ok(summary)
+    }
+
+    /// Runs `write`, which writes the trie nodes under the returned hash, recording it if tries
+    /// are being pruned.
+    fn record_written<F>(&self, write: F) -> Result<Digest, error::Error>
+    where
+        F: FnOnce() -> Result<Digest, error::Error>,
+    {
+        let mut written_while_pruning = self.written_while_pruning.lock()?;
+        let trie_hash = write()?;
+        if let Some(written) = written_while_pruning.as_mut() {
+            written.push(trie_hash);
+        }
+        Ok(trie_hash)
+    }
+}
+
+/// Marks the trie nodes reachable from `unvisited` which are present and not yet marked, until
+/// `limit` of them are marked or none is left.  Returns the number of newly marked trie nodes.
+///
+/// `unvisited` is used as a depth-first stack, so that it never holds more than the children of
+/// the trie nodes along a single path besides the initial entries.
+fn mark_reachable(
+    txn: &mut RwTransaction,
+    trie_store: &LmdbTrieStore,
+    marks: Database,
+    unvisited: &mut Vec<Digest>,
+    limit: usize,
+) -> Result<u64, error::Error> {
+    let mut marked_count = 0;
+    while marked_count < limit {
+        let trie_hash = match unvisited.pop() {
+            Some(trie_hash) => trie_hash,
+            None => break,
+        };
+        match lmdb::Transaction::get(&*txn, marks, &trie_hash) {
+            Ok(_) => continue,
+            Err(lmdb::Error::NotFound) => (),
+            Err(error) => return Err(error.into()),
+        }
+        let trie_bytes = match Store::<Digest, Trie<Key, StoredValue>>::get_raw(
+            trie_store, &*txn, &trie_hash,
+        )? {
+            Some(trie_bytes) => trie_bytes,
+            None => continue,
+        };
+        txn.put(marks, &trie_hash, b"", WriteFlags::empty())?;
+        marked_count += 1;
+        let lazy_trie: LazyTrieLeaf<Key, StoredValue> = trie::lazy_trie_deserialize(trie_bytes)?;
+        unvisited.extend(trie::lazy_trie_iter_children(&lazy_trie));
+    }
+    Ok(marked_count as u64)
+}
+
+/// Deletes the unmarked trie nodes among the `batch_size` ones following `start_after`, or the
+/// first ones if `None`.  Returns the number of deleted trie nodes and the hash of the last visited
+/// one, which is `None` once all have been visited.
+fn sweep_unmarked(
+    txn: &mut RwTransaction,
+    db: Database,
+    marks: Database,
+    start_after: Option<&Digest>,
+    batch_size: usize,
+) -> Result<(u64, Option<Digest>), error::Error> {
+    let mut unmarked = Vec::new();
+    let mut last_visited = None;
+    {
+        let mut cursor = lmdb::Transaction::open_ro_cursor(&*txn, db)?;
+        let rows = match start_after {
+            Some(trie_hash) => cursor.iter_from(trie_hash),
+            None => cursor.iter_start(),
+        };
+        let mut visited_count = 0;
+        for row in rows {
+            let (key, _) = row?;
+            let trie_hash = match Digest::try_from(key) {
+                Ok(trie_hash) => trie_hash,
+                Err(_) => continue,
+            };
+            if Some(&trie_hash) == start_after {
+                continue;
+            }
+            match lmdb::Transaction::get(&*txn, marks, &trie_hash) {
+                Ok(_) => (),
+                Err(lmdb::Error::NotFound) => unmarked.push(trie_hash),
+                Err(error) => return Err(error.into()),
+            }
+            last_visited = Some(trie_hash);
+            visited_count += 1;
+            if visited_count == batch_size {
+                break;
+            }
+        }
+    }
+    for trie_hash in &unmarked {
+        txn.del(db, trie_hash, None)?;
+    }
+    Ok((unmarked.len() as u64, last_visited))
 }
 
 fn compute_empty_root_hash() -> Result<(Digest, Trie<Key, StoredValue>), error::Error> {
@@ -208,6 +444,26 @@ impl StateReader<Key, StoredValue> for LmdbGlobalStateView {
         txn.commit()?;
         Ok(ret)
     }
+
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&Key>,
+        limit: usize,
+    ) -> Result<Vec<Key>, Self::Error> {
+        let txn = self.environment.create_read_txn()?;
+        let keys_iter = keys_with_prefix::<Key, StoredValue, _, _>(
+            correlation_id,
+            &txn,
+            self.store.deref(),
+            &self.root_hash,
+            prefix,
+        );
+        let ret = collect_keys_after(keys_iter, start_after, limit)?;
+        txn.commit()?;
+        Ok(ret)
+    }
 }
 
 impl CommitProvider for LmdbGlobalState {
@@ -217,14 +473,15 @@ impl CommitProvider for LmdbGlobalState {
         prestate_hash: Digest,
         effects: AdditiveMap<Key, Transform>,
     ) -> Result<Digest, Self::Error> {
-        commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
-            &self.environment,
-            &self.trie_store,
-            correlation_id,
-            prestate_hash,
-            effects,
-        )
-        .map_err(Into::into)
+        self.record_written(|| {
+            commit::<LmdbEnvironment, LmdbTrieStore, _, Self::Error>(
+                &self.environment,
+                &self.trie_store,
+                correlation_id,
+                prestate_hash,
+                effects,
+            )
+        })
     }
 }
 
@@ -263,16 +520,17 @@ impl StateProvider for LmdbGlobalState {
     }
 
     fn put_trie(&self, correlation_id: CorrelationId, trie: &[u8]) -> Result<Digest, Self::Error> {
-        let mut txn = self.environment.create_read_write_txn()?;
-        let trie_hash = put_trie::<
-            Key,
-            StoredValue,
-            lmdb::RwTransaction,
-            LmdbTrieStore,
-            Self::Error,
-        >(correlation_id, &mut txn, &self.trie_store, trie)?;
-        txn.commit()?;
-        Ok(trie_hash)
+        self.record_written(|| {
+            let mut txn = self.environment.create_read_write_txn()?;
+            let trie_hash = put_trie::<Key, StoredValue, RwTransaction, LmdbTrieStore, Self::Error>(
+                correlation_id,
+                &mut txn,
+                &self.trie_store,
+                trie,
+            )?;
+            txn.commit()?;
+            Ok(trie_hash)
+        })
     }
 
     /// Finds all of the keys of missing directly descendant `Trie<K,V>` values.
@@ -300,6 +558,7 @@ impl StateProvider for LmdbGlobalState {
         mut state_root_hash: Digest,
         keys: &[Key],
     ) -> Result<DeleteResult, Self::Error> {
+        let mut written_while_pruning = self.written_while_pruning.lock()?;
         let scratch_trie_store = self.get_scratch_store();
 
         let mut txn = scratch_trie_store.create_read_write_txn()?;
@@ -323,6 +582,9 @@ impl StateProvider for LmdbGlobalState {
         txn.commit()?;
 
         scratch_trie_store.write_root_to_db(state_root_hash)?;
+        if let Some(written) = written_while_pruning.as_mut() {
+            written.push(state_root_hash);
+        }
         Ok(DeleteResult::Deleted(state_root_hash))
     }
 }
@@ -505,4 +767,71 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn pruning_deletes_tries_unreachable_from_retained_roots() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state(create_test_pairs);
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated
+            .iter()
+            .map(|TestPair { key, value }| (*key, Transform::Write(value.to_owned())))
+            .collect();
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let summary = state
+            .prune_unreachable_tries(&[root_hash, updated_hash], 1)
+            .unwrap();
+        assert_eq!(summary.pruned_count, 0);
+
+        let summary = state.prune_unreachable_tries(&[updated_hash], 1).unwrap();
+        assert!(summary.pruned_count > 0);
+        assert!(state.checkout(root_hash).unwrap().is_none());
+
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+        assert!(state.checkout(state.empty_root_hash).unwrap().is_some());
+
+        let summary = state.prune_unreachable_tries(&[updated_hash], 100).unwrap();
+        assert_eq!(summary.pruned_count, 0);
+    }
+
+    #[test]
+    fn pruning_retains_tries_written_after_it_started() {
+        let correlation_id = CorrelationId::new();
+        let test_pairs_updated = create_test_pairs_updated();
+
+        let (state, root_hash) = create_test_state(create_test_pairs);
+        assert!(state.start_trie_pruning().unwrap());
+        assert!(!state.start_trie_pruning().unwrap());
+
+        let effects: AdditiveMap<Key, Transform> = test_pairs_updated
+            .iter()
+            .map(|TestPair { key, value }| (*key, Transform::Write(value.to_owned())))
+            .collect();
+        let updated_hash = state.commit(correlation_id, root_hash, effects).unwrap();
+
+        let summary = state.prune_unreachable_tries(&[root_hash], 1).unwrap();
+        assert_eq!(summary.pruned_count, 0);
+        let updated_checkout = state.checkout(updated_hash).unwrap().unwrap();
+        for TestPair { key, value } in test_pairs_updated.iter().cloned() {
+            assert_eq!(
+                Some(value),
+                updated_checkout.read(correlation_id, &key).unwrap()
+            );
+        }
+
+        // Pruning is over: the next one doesn't retain the tries written before it.
+        assert!(state.start_trie_pruning().unwrap());
+        let summary = state.prune_unreachable_tries(&[root_hash], 1).unwrap();
+        assert!(summary.pruned_count > 0);
+        assert!(state.checkout(updated_hash).unwrap().is_none());
+    }
 }
@@ -14,7 +14,10 @@ use std::{collections::HashMap, hash::BuildHasher};
 use tracing::error;
 
 use casper_hashing::Digest;
-use casper_types::{bytesrepr, Key, StoredValue};
+use casper_types::{
+    bytesrepr::{self, ToBytes},
+    Key, StoredValue,
+};
 
 use crate::{
     shared::{
@@ -55,6 +58,51 @@ pub trait StateReader<K, V> {
         correlation_id: CorrelationId,
         prefix: &[u8],
     ) -> Result<Vec<K>, Self::Error>;
+
+    /// Returns at most `limit` keys in the trie matching `prefix`, in the order of their serialized
+    /// forms, starting after `start_after` if given.
+    ///
+    /// Unlike [`StateReader::keys_with_prefix`], this never holds more than `limit` keys, so large
+    /// sets of keys can be read page by page.
+    fn keys_with_prefix_after(
+        &self,
+        correlation_id: CorrelationId,
+        prefix: &[u8],
+        start_after: Option<&K>,
+        limit: usize,
+    ) -> Result<Vec<K>, Self::Error>;
+}
+
+/// Collects at most `limit` of the `keys` whose serialized forms sort after `start_after`.
+///
+/// `keys` must be yielded in the order of their serialized forms, as they are by a trie.
+pub(crate) fn collect_keys_after<K, E, I>(
+    keys: I,
+    start_after: Option<&K>,
+    limit: usize,
+) -> Result<Vec<K>, E>
+where
+    K: ToBytes,
+    E: From<bytesrepr::Error>,
+    I: Iterator<Item = Result<K, E>>,
+{
+    let mut maybe_start_after = start_after.map(ToBytes::to_bytes).transpose()?;
+    let mut ret = Vec::new();
+    for result in keys {
+        if ret.len() >= limit {
+            break;
+        }
+        let key = result?;
+        if let Some(start_after) = maybe_start_after.as_ref() {
+            if key.to_bytes()? <= *start_after {
+                continue;
+            }
+            // All following keys sort after this one.
+            maybe_start_after = None;
+        }
+        ret.push(key);
+    }
+    Ok(ret)
 }
 
 /// An error emitted by the execution engine on commit
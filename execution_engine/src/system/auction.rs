@@ -4,15 +4,15 @@ pub(crate) mod providers;
 use std::collections::BTreeMap;
 
 use num_rational::Ratio;
-use num_traits::{CheckedMul, CheckedSub};
 
 use casper_types::{
     account::AccountHash,
+    fallible_num::MaybeNum,
     system::auction::{
-        Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
+        AuctionSnapshot, Bid, DelegationRate, EraInfo, EraValidators, Error, SeigniorageAllocation,
         SeigniorageRecipients, ValidatorWeights, BLOCK_REWARD, DELEGATION_RATE_DENOMINATOR,
     },
-    ApiError, EraId, PublicKey, U512,
+    ApiError, EraId, PublicKey, URef, U512,
 };
 
 use self::providers::{AccountProvider, MintProvider, RuntimeProvider, StorageProvider};
@@ -55,14 +55,18 @@ pub trait Auction:
     /// its stake.
     ///
     /// Validators cannot create a bid with 0 amount, and the delegation rate can't exceed
-    /// [`DELEGATION_RATE_DENOMINATOR`].
+    /// [`DELEGATION_RATE_DENOMINATOR`].  Raising the delegation rate of an existing bid is subject
+    /// to the configured delegation rate change limit.
     ///
     /// Returns a [`U512`] value indicating total amount of tokens staked for given `public_key`.
+    ///
+    /// Fails if the resulting stake is below `minimum_bid_amount`.
     fn add_bid(
         &mut self,
         public_key: PublicKey,
         delegation_rate: DelegationRate,
         amount: U512,
+        minimum_bid_amount: u64,
     ) -> Result<U512, ApiError> {
         if !self.allow_auction_bids() {
             // Validation set rotation might be disabled on some private chains and we should not
@@ -91,9 +95,22 @@ pub trait Auction:
         // Update bids or stakes
         let updated_amount = match self.read_bid(&account_hash)? {
             Some(mut bid) => {
+                let staked_amount = bid
+                    .staked_amount()
+                    .checked_add(amount)
+                    .ok_or(Error::InvalidAmount)?;
+                if staked_amount < U512::from(minimum_bid_amount) {
+                    return Err(Error::BidAmountTooSmall.into());
+                }
                 if bid.inactive() {
                     bid.activate();
                 }
+                detail::check_delegation_rate_change(
+                    self,
+                    &public_key,
+                    *bid.delegation_rate(),
+                    delegation_rate,
+                )?;
                 self.mint_transfer_direct(
                     Some(PublicKey::System.to_account_hash()),
                     source,
@@ -115,6 +132,9 @@ pub trait Auction:
                 updated_amount
             }
             None => {
+                if amount < U512::from(minimum_bid_amount) {
+                    return Err(Error::BidAmountTooSmall.into());
+                }
                 let bonding_purse = self.create_purse()?;
                 self.mint_transfer_direct(
                     Some(PublicKey::System.to_account_hash()),
@@ -149,11 +169,17 @@ pub trait Auction:
     /// be activated again by staking tokens.
     ///
     /// You can't withdraw higher amount than its currently staked. Withdrawing zero is allowed,
-    /// although it does not change the state of the auction.
+    /// although it does not change the state of the auction. A partial withdrawal can't leave less
+    /// than `minimum_bid_amount` staked.
     ///
     /// The function returns the new amount of motes remaining in the bid. If the target bid does
     /// not exist, the function call returns an error.
-    fn withdraw_bid(&mut self, public_key: PublicKey, amount: U512) -> Result<U512, Error> {
+    fn withdraw_bid(
+        &mut self,
+        public_key: PublicKey,
+        amount: U512,
+        minimum_bid_amount: u64,
+    ) -> Result<U512, Error> {
         let provided_account_hash = AccountHash::from_public_key(&public_key, |x| self.blake2b(x));
 
         if !self.is_allowed_session_caller(&provided_account_hash) {
@@ -169,6 +195,9 @@ pub trait Auction:
         // Fails if requested amount is greater than either the total stake or the amount of vested
         // stake.
         let updated_stake = bid.decrease_stake(amount, era_end_timestamp_millis)?;
+        if !updated_stake.is_zero() && updated_stake < U512::from(minimum_bid_amount) {
+            return Err(Error::BidAmountTooSmall);
+        }
 
         detail::create_unbonding_purse(
             self,
@@ -295,7 +324,7 @@ pub trait Auction:
             Some(delegator) => {
                 detail::create_unbonding_purse(
                     self,
-                    validator_public_key,
+                    validator_public_key.clone(),
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
@@ -306,6 +335,12 @@ pub trait Auction:
                 let updated_stake = delegator.decrease_stake(amount, era_end_timestamp_millis)?;
                 if updated_stake == U512::zero() {
                     delegators.remove(&delegator_public_key);
+                    detail::set_delegator_reward_purse(
+                        self,
+                        validator_public_key,
+                        delegator_public_key,
+                        None,
+                    )?;
                 };
                 updated_stake
             }
@@ -358,7 +393,7 @@ pub trait Auction:
             Some(delegator) => {
                 detail::create_unbonding_purse(
                     self,
-                    validator_public_key,
+                    validator_public_key.clone(),
                     delegator_public_key.clone(),
                     *delegator.bonding_purse(),
                     amount,
@@ -369,6 +404,12 @@ pub trait Auction:
                 let updated_stake = delegator.decrease_stake(amount, era_end_timestamp_millis)?;
                 if updated_stake == U512::zero() {
                     delegators.remove(&delegator_public_key);
+                    detail::set_delegator_reward_purse(
+                        self,
+                        validator_public_key,
+                        delegator_public_key,
+                        None,
+                    )?;
                 };
                 updated_stake
             }
@@ -380,6 +421,92 @@ pub trait Auction:
         Ok(new_amount)
     }
 
+    /// Delegates to and redelegates between several validators in a single call.
+    ///
+    /// `delegations` maps each validator to the amount delegated to it from the delegator's main
+    /// purse, and `redelegations` lists the validator to redelegate from, the validator to
+    /// redelegate to and the amount.  Delegations are made before redelegations are requested.
+    ///
+    /// The first failing delegation or redelegation is returned as the error of the whole call,
+    /// in which case the execution is reverted and none of them takes effect.
+    fn bulk_delegate(
+        &mut self,
+        delegator_public_key: PublicKey,
+        delegations: BTreeMap<PublicKey, U512>,
+        redelegations: Vec<(PublicKey, PublicKey, U512)>,
+        max_delegators_per_validator: Option<u32>,
+        minimum_delegation_amount: u64,
+    ) -> Result<(), ApiError> {
+        for (validator_public_key, amount) in delegations {
+            self.delegate(
+                delegator_public_key.clone(),
+                validator_public_key,
+                amount,
+                max_delegators_per_validator,
+                minimum_delegation_amount,
+            )?;
+        }
+
+        for (validator_public_key, new_validator, amount) in redelegations {
+            self.redelegate(
+                delegator_public_key.clone(),
+                validator_public_key,
+                amount,
+                new_validator,
+                minimum_delegation_amount,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the purse receiving the rewards of the delegator's stake with the validator.
+    ///
+    /// While a reward purse is set, the delegator's rewards are paid into it instead of increasing
+    /// the delegated stake.  Passing `None` as `reward_purse` restores reinvesting the rewards.
+    fn set_delegator_reward_purse(
+        &mut self,
+        delegator_public_key: PublicKey,
+        validator_public_key: PublicKey,
+        reward_purse: Option<URef>,
+    ) -> Result<(), Error> {
+        let provided_account_hash =
+            AccountHash::from_public_key(&delegator_public_key, |x| self.blake2b(x));
+
+        if !self.is_allowed_session_caller(&provided_account_hash) {
+            return Err(Error::InvalidContext);
+        }
+
+        let validator_account_hash = AccountHash::from(&validator_public_key);
+
+        let bid = match self.read_bid(&validator_account_hash)? {
+            Some(bid) => bid,
+            None => return Err(Error::ValidatorNotFound),
+        };
+
+        if !bid.delegators().contains_key(&delegator_public_key) {
+            return Err(Error::DelegatorNotFound);
+        }
+
+        let reward_purse = match reward_purse {
+            Some(reward_purse) => {
+                if !reward_purse.is_addable() || self.get_balance(reward_purse)?.is_none() {
+                    return Err(Error::InvalidRewardPurse);
+                }
+                // Paying rewards only requires adding to the purse.
+                Some(reward_purse.into_add())
+            }
+            None => None,
+        };
+
+        detail::set_delegator_reward_purse(
+            self,
+            validator_public_key,
+            delegator_public_key,
+            reward_purse,
+        )
+    }
+
     /// Slashes each validator.
     ///
     /// This can be only invoked through a system call.
@@ -425,12 +552,16 @@ pub trait Auction:
     /// added to their delegators') ordered by size from largest to smallest, then takes the top N
     /// (number of auction slots) bidders and replaces era_validators with these.
     ///
+    /// The new validator set is also recorded under `Key::AuctionSnapshot` if
+    /// `record_auction_snapshots` is set.
+    ///
     /// Accessed by: node
     fn run_auction(
         &mut self,
         era_end_timestamp_millis: u64,
         evicted_validators: Vec<PublicKey>,
         max_delegators_per_validator: Option<u32>,
+        record_auction_snapshots: bool,
     ) -> Result<(), ApiError> {
         if self.get_caller() != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidCaller.into());
@@ -533,6 +664,14 @@ pub trait Auction:
                 recipients.insert(era_validator.clone(), seigniorage_recipient);
             }
 
+            // Keep a compact, era-indexed record of the new validator set so that historical
+            // weights can be looked up without access to archival global state.
+            if record_auction_snapshots {
+                let auction_snapshot = AuctionSnapshot::from_seigniorage_recipients(&recipients)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                self.record_auction_snapshot(delayed_era, auction_snapshot)?;
+            }
+
             let previous_recipients = snapshot.insert(delayed_era, recipients);
             assert!(previous_recipients.is_none());
 
@@ -559,6 +698,7 @@ pub trait Auction:
 
         let seigniorage_recipients = self.read_seigniorage_recipients()?;
         let base_round_reward = self.read_base_round_reward()?;
+        let delegator_reward_purses = detail::get_delegator_reward_purses(self)?;
 
         let mut era_info = EraInfo::new();
         let seigniorage_allocations = era_info.seigniorage_allocations_mut();
@@ -579,9 +719,9 @@ pub trait Auction:
             }
 
             let total_reward: Ratio<U512> = {
-                let reward_rate = Ratio::new(U512::from(reward_factor), U512::from(BLOCK_REWARD));
-                reward_rate
-                    .checked_mul(&Ratio::from(base_round_reward))
+                let reward_rate =
+                    MaybeNum::ratio(U512::from(reward_factor), U512::from(BLOCK_REWARD));
+                (reward_rate * MaybeNum::new(base_round_reward).into_ratio())
                     .ok_or(Error::ArithmeticOverflow)?
             };
 
@@ -590,45 +730,56 @@ pub trait Auction:
                 .ok_or(Error::ArithmeticOverflow)?;
 
             let delegators_part: Ratio<U512> = {
-                let commission_rate = Ratio::new(
+                let commission_rate = MaybeNum::ratio(
                     U512::from(*recipient.delegation_rate()),
                     U512::from(DELEGATION_RATE_DENOMINATOR),
                 );
-                let reward_multiplier: Ratio<U512> =
-                    Ratio::new(delegator_total_stake, current_stake);
-                let delegator_reward: Ratio<U512> = total_reward
-                    .checked_mul(&reward_multiplier)
-                    .ok_or(Error::ArithmeticOverflow)?;
-                let commission: Ratio<U512> = delegator_reward
-                    .checked_mul(&commission_rate)
-                    .ok_or(Error::ArithmeticOverflow)?;
-                delegator_reward
-                    .checked_sub(&commission)
-                    .ok_or(Error::ArithmeticOverflow)?
+                let reward_multiplier = MaybeNum::ratio(delegator_total_stake, current_stake);
+                let delegator_reward = MaybeNum::new(total_reward) * reward_multiplier;
+                let commission = delegator_reward * commission_rate;
+                (delegator_reward - commission).ok_or(Error::ArithmeticOverflow)?
             };
 
-            let delegator_rewards =
-                recipient
-                    .delegator_stake()
-                    .iter()
-                    .map(|(delegator_key, delegator_stake)| {
-                        let reward_multiplier = Ratio::new(*delegator_stake, delegator_total_stake);
-                        let reward = delegators_part * reward_multiplier;
-                        (delegator_key.clone(), reward)
-                    });
+            let delegator_rewards = recipient
+                .delegator_stake()
+                .iter()
+                .map(|(delegator_key, delegator_stake)| {
+                    let reward_multiplier =
+                        MaybeNum::ratio(*delegator_stake, delegator_total_stake);
+                    (MaybeNum::new(delegators_part) * reward_multiplier)
+                        .ok_or(Error::ArithmeticOverflow)
+                        .map(|reward| (delegator_key.clone(), reward))
+                })
+                .collect::<Result<Vec<_>, Error>>()?;
+
+            // Purses which no longer exist are skipped, so the rewards are reinvested rather than
+            // failing the distribution.
+            let mut reward_purses = BTreeMap::new();
+            for ((validator_public_key, delegator_public_key), reward_purse) in
+                &delegator_reward_purses
+            {
+                if *validator_public_key == public_key && self.get_balance(*reward_purse)?.is_some()
+                {
+                    reward_purses.insert(delegator_public_key.clone(), *reward_purse);
+                }
+            }
+
             let delegator_payouts = detail::reinvest_delegator_rewards(
                 self,
                 seigniorage_allocations,
                 public_key.clone(),
-                delegator_rewards,
+                delegator_rewards.into_iter(),
+                &reward_purses,
             )?;
-            let total_delegator_payout: U512 = delegator_payouts
-                .iter()
-                .map(|(_delegator_hash, amount, _bonding_purse)| *amount)
-                .sum();
-
-            let validators_part: Ratio<U512> = total_reward - Ratio::from(total_delegator_payout);
-            let validator_reward = validators_part.to_integer();
+            let total_delegator_payout = delegator_payouts.iter().fold(
+                MaybeNum::new(U512::zero()),
+                |total, (_delegator_hash, amount, _bonding_purse)| total + *amount,
+            );
+
+            let validator_reward = (MaybeNum::new(total_reward)
+                - total_delegator_payout.into_ratio())
+            .to_integer()
+            .ok_or(Error::ArithmeticOverflow)?;
             let validator_bonding_purse = detail::reinvest_validator_reward(
                 self,
                 seigniorage_allocations,
@@ -6,8 +6,9 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::auction::{
-        Bids, Delegator, Error, SeigniorageAllocation, SeigniorageRecipientsSnapshot,
-        UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY, ERA_END_TIMESTAMP_MILLIS_KEY,
+        Bids, DelegationRate, Delegator, Error, SeigniorageAllocation,
+        SeigniorageRecipientsSnapshot, UnbondingPurse, UnbondingPurses, AUCTION_DELAY_KEY,
+        DELEGATION_RATE_INCREASES_KEY, DELEGATOR_REWARD_PURSES_KEY, ERA_END_TIMESTAMP_MILLIS_KEY,
         ERA_ID_KEY, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY, UNBONDING_DELAY_KEY, VALIDATOR_SLOTS_KEY,
     },
     ApiError, CLTyped, EraId, Key, KeyTag, PublicKey, URef, U512,
@@ -17,6 +18,7 @@ use tracing::error;
 use super::{
     Auction, Bid, EraValidators, MintProvider, RuntimeProvider, StorageProvider, ValidatorWeights,
 };
+use crate::core::engine_state::engine_config::DelegationRateChangeLimit;
 
 fn read_from<P, T>(provider: &mut P, name: &str) -> Result<T, Error>
 where
@@ -120,6 +122,107 @@ where
     write_to(provider, ERA_ID_KEY, era_id)
 }
 
+/// Checks that the delegation rate change limit allows the validator to change its delegation rate
+/// from `current_rate` to `new_rate`, and records the change if it is an increase.
+pub fn check_delegation_rate_change<P>(
+    provider: &mut P,
+    validator_public_key: &PublicKey,
+    current_rate: DelegationRate,
+    new_rate: DelegationRate,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let (min_eras_between_increases, max_increase) = match provider.delegation_rate_change_limit() {
+        DelegationRateChangeLimit::Unlimited => return Ok(()),
+        DelegationRateChangeLimit::Limited {
+            min_eras_between_increases,
+            max_increase,
+        } => (min_eras_between_increases, max_increase),
+    };
+    if new_rate <= current_rate {
+        return Ok(());
+    }
+    if new_rate - current_rate > max_increase {
+        return Err(Error::DelegationRateChangeLimited);
+    }
+
+    let era_id = get_era_id(provider)?;
+    let mut increases: BTreeMap<PublicKey, EraId> =
+        match provider.named_keys_get(DELEGATION_RATE_INCREASES_KEY) {
+            Some(_) => read_from(provider, DELEGATION_RATE_INCREASES_KEY)?,
+            None => BTreeMap::new(),
+        };
+    // Forget the increases which no longer restrict further ones.
+    increases.retain(|_, last_increase| {
+        era_id < last_increase.saturating_add(min_eras_between_increases)
+    });
+    if increases.contains_key(validator_public_key) {
+        return Err(Error::DelegationRateChangeLimited);
+    }
+    increases.insert(validator_public_key.clone(), era_id);
+
+    if provider
+        .named_keys_get(DELEGATION_RATE_INCREASES_KEY)
+        .is_some()
+    {
+        return write_to(provider, DELEGATION_RATE_INCREASES_KEY, increases);
+    }
+    // The record is created on first use, so that the auction of an existing network does not need
+    // to be migrated.
+    let uref = provider.new_uref(increases)?;
+    provider.put_key(DELEGATION_RATE_INCREASES_KEY, Key::URef(uref))
+}
+
+/// Returns the purses receiving the rewards of delegators, by validator and delegator.
+pub fn get_delegator_reward_purses<P>(
+    provider: &mut P,
+) -> Result<BTreeMap<(PublicKey, PublicKey), URef>, Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    match provider.named_keys_get(DELEGATOR_REWARD_PURSES_KEY) {
+        Some(_) => read_from(provider, DELEGATOR_REWARD_PURSES_KEY),
+        None => Ok(BTreeMap::new()),
+    }
+}
+
+/// Sets the purse receiving the rewards of the delegator's stake with the validator, or clears it
+/// if `reward_purse` is `None`.
+pub fn set_delegator_reward_purse<P>(
+    provider: &mut P,
+    validator_public_key: PublicKey,
+    delegator_public_key: PublicKey,
+    reward_purse: Option<URef>,
+) -> Result<(), Error>
+where
+    P: StorageProvider + RuntimeProvider + ?Sized,
+{
+    let mut reward_purses = get_delegator_reward_purses(provider)?;
+    let key = (validator_public_key, delegator_public_key);
+    match reward_purse {
+        Some(reward_purse) => {
+            reward_purses.insert(key, reward_purse);
+        }
+        None => {
+            if reward_purses.remove(&key).is_none() {
+                return Ok(());
+            }
+        }
+    }
+
+    if provider
+        .named_keys_get(DELEGATOR_REWARD_PURSES_KEY)
+        .is_some()
+    {
+        return write_to(provider, DELEGATOR_REWARD_PURSES_KEY, reward_purses);
+    }
+    // The record is created on first use, so that the auction of an existing network does not need
+    // to be migrated.
+    let uref = provider.new_uref(reward_purses)?;
+    provider.put_key(DELEGATOR_REWARD_PURSES_KEY, Key::URef(uref))
+}
+
 pub fn get_era_end_timestamp_millis<P>(provider: &mut P) -> Result<u64, Error>
 where
     P: StorageProvider + RuntimeProvider + ?Sized,
@@ -309,11 +412,15 @@ pub(crate) fn create_unbonding_purse<P: Auction + ?Sized>(
 }
 
 /// Reinvests delegator reward by increasing its stake.
+///
+/// The rewards of delegators listed in `reward_purses` are paid into the given purse instead, and
+/// leave their stake unchanged.
 pub fn reinvest_delegator_rewards<P>(
     provider: &mut P,
     seigniorage_allocations: &mut Vec<SeigniorageAllocation>,
     validator_public_key: PublicKey,
     rewards: impl Iterator<Item = (PublicKey, Ratio<U512>)>,
+    reward_purses: &BTreeMap<PublicKey, URef>,
 ) -> Result<Vec<(AccountHash, U512, URef)>, Error>
 where
     P: StorageProvider,
@@ -337,12 +444,18 @@ where
 
         let delegator_reward_trunc = delegator_reward.to_integer();
 
-        delegator.increase_stake(delegator_reward_trunc)?;
+        let payout_purse = match reward_purses.get(&delegator_key) {
+            Some(reward_purse) => *reward_purse,
+            None => {
+                delegator.increase_stake(delegator_reward_trunc)?;
+                *delegator.bonding_purse()
+            }
+        };
 
         delegator_payouts.push((
             delegator_key.to_account_hash(),
             delegator_reward_trunc,
-            *delegator.bonding_purse(),
+            payout_purse,
         ));
 
         let allocation = SeigniorageAllocation::delegator(
@@ -4,12 +4,14 @@ use casper_types::{
     account::AccountHash,
     bytesrepr::{FromBytes, ToBytes},
     system::{
-        auction::{Bid, EraInfo, Error, UnbondingPurse},
+        auction::{AuctionSnapshot, Bid, EraInfo, Error, UnbondingPurse},
         mint,
     },
     CLTyped, EraId, Key, KeyTag, URef, BLAKE2B_DIGEST_LENGTH, U512,
 };
 
+use crate::core::engine_state::engine_config::DelegationRateChangeLimit;
+
 /// Provider of runtime host functionality.
 pub trait RuntimeProvider {
     /// This method should return the caller of the current context.
@@ -21,6 +23,9 @@ pub trait RuntimeProvider {
     /// Gets named key under a `name`.
     fn named_keys_get(&self, name: &str) -> Option<Key>;
 
+    /// Puts a named key under a `name`.
+    fn put_key(&mut self, name: &str, key: Key) -> Result<(), Error>;
+
     /// Gets keys in a given keyspace
     fn get_keys(&mut self, key_tag: &KeyTag) -> Result<BTreeSet<Key>, Error>;
 
@@ -32,10 +37,16 @@ pub trait RuntimeProvider {
 
     /// Check if auction bids are allowed.
     fn allow_auction_bids(&self) -> bool;
+
+    /// Returns the limit of how often and by how much validators may raise their delegation rate.
+    fn delegation_rate_change_limit(&self) -> DelegationRateChangeLimit;
 }
 
 /// Provides functionality of a contract storage.
 pub trait StorageProvider {
+    /// Creates a new [`URef`] holding `init`.
+    fn new_uref<T: ToBytes + CLTyped>(&mut self, init: T) -> Result<URef, Error>;
+
     /// Reads data from [`URef`].
     fn read<T: FromBytes + CLTyped>(&mut self, uref: URef) -> Result<Option<T>, Error>;
 
@@ -60,6 +71,13 @@ pub trait StorageProvider {
 
     /// Records era summary.
     fn record_era_info(&mut self, _era_id: EraId, era_summary: EraInfo) -> Result<(), Error>;
+
+    /// Records the auction snapshot of the given era.
+    fn record_auction_snapshot(
+        &mut self,
+        era_id: EraId,
+        auction_snapshot: AuctionSnapshot,
+    ) -> Result<(), Error>;
 }
 
 /// Provides an access to mint.
@@ -10,6 +10,8 @@ use crate::system::handle_payment::{
     storage_provider::StorageProvider,
 };
 
+pub(crate) use internal::calculate_refund_and_fee;
+
 /// Handle payment functionality implementation.
 pub trait HandlePayment: MintProvider + RuntimeProvider + StorageProvider + Sized {
     /// Get payment purse.
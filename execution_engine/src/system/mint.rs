@@ -2,27 +2,44 @@ pub(crate) mod runtime_provider;
 pub(crate) mod storage_provider;
 pub(crate) mod system_provider;
 
+use std::collections::{BTreeMap, BTreeSet};
+
 use num_rational::Ratio;
-use num_traits::CheckedMul;
 use tracing::warn;
 
 use casper_types::{
     account::AccountHash,
+    bytesrepr::ToBytes,
+    crypto,
+    fallible_num::MaybeNum,
     system::{
-        mint::{Error, ROUND_SEIGNIORAGE_RATE_KEY, TOTAL_SUPPLY_KEY},
-        CallStackElement,
+        mint::{
+            historical_total_supply_item_key, Error, TransferResult, ALLOWANCES_KEY,
+            HISTORICAL_TOTAL_SUPPLY_KEY, MINT_PAUSED_KEY, ROUND_SEIGNIORAGE_RATE_KEY,
+            TOTAL_SUPPLY_KEY,
+        },
+        CallStackElement, HANDLE_PAYMENT, STANDARD_PAYMENT,
     },
-    Key, Phase, PublicKey, StoredValue, URef, U512,
+    AccessRights, EraId, Key, Phase, PublicKey, StoredValue, TransferMemo, URef, URefAddr, U512,
 };
 
 use crate::{
-    core::engine_state::SystemContractRegistry,
+    core::engine_state::{SystemContractRegistry, TransferArgs},
     system::mint::{
         runtime_provider::RuntimeProvider, storage_provider::StorageProvider,
         system_provider::SystemProvider,
     },
 };
 
+/// Returns the name of the item of the allowances dictionary holding the allowance of `spender` on
+/// the purse at `purse_addr`.
+fn allowance_item_key(purse_addr: URefAddr, spender: &Key) -> Result<String, Error> {
+    let bytes = (purse_addr, *spender)
+        .to_bytes()
+        .map_err(|_| Error::Serialize)?;
+    Ok(base16::encode_lower(&crypto::blake2b(bytes)))
+}
+
 /// Mint trait.
 pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
     /// Mint new token with given `initial_balance` balance. Returns new purse on success, otherwise
@@ -33,6 +50,9 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if !is_empty_purse && caller != PublicKey::System.to_account_hash() {
             return Err(Error::InvalidNonEmptyPurseCreation);
         }
+        if !is_empty_purse && self.is_mint_paused()? {
+            return Err(Error::MintPaused);
+        }
 
         let purse_uref: URef = self.new_uref(())?;
         self.write_balance(purse_uref, initial_balance)?;
@@ -67,6 +87,10 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
             return Ok(()); // no change to supply
         }
 
+        if self.is_mint_paused()? {
+            return Err(Error::MintPaused);
+        }
+
         // get total supply or error
         let total_supply_uref = match self.get_key(TOTAL_SUPPLY_KEY) {
             Some(Key::URef(uref)) => uref,
@@ -88,6 +112,53 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         Ok(())
     }
 
+    /// Burns `amount` tokens from `purse`, reducing the total supply accordingly.  The caller
+    /// must have write access to the purse.
+    fn burn(&mut self, purse: URef, amount: U512) -> Result<(), Error> {
+        if !purse.is_readable() || !purse.is_writeable() {
+            return Err(Error::InvalidAccessRights);
+        }
+
+        if self.is_mint_paused()? {
+            return Err(Error::MintPaused);
+        }
+
+        let purse_balance: U512 = match self.read_balance(purse)? {
+            Some(purse_balance) => purse_balance,
+            None => return Err(Error::PurseNotFound),
+        };
+        if amount > purse_balance {
+            return Err(Error::InsufficientBalanceForBurn);
+        }
+        if amount.is_zero() {
+            return Ok(());
+        }
+
+        if self.get_caller() != PublicKey::System.to_account_hash()
+            && self.get_main_purse().addr() == purse.addr()
+        {
+            if amount > self.get_approved_spending_limit() {
+                return Err(Error::UnapprovedSpendingAmount);
+            }
+            self.sub_approved_spending_limit(amount);
+        }
+
+        let total_supply_uref = match self.get_key(TOTAL_SUPPLY_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) | None => return Err(Error::MissingKey),
+        };
+        let total_supply: U512 = self
+            .read(total_supply_uref)?
+            .ok_or(Error::TotalSupplyNotFound)?;
+        let reduced_total_supply = total_supply
+            .checked_sub(amount)
+            .ok_or(Error::ArithmeticOverflow)?;
+
+        self.write_balance(purse, purse_balance - amount)?;
+        self.write(total_supply_uref, reduced_total_supply)?;
+        self.record_burn(purse, amount)
+    }
+
     /// Read balance of given `purse`.
     fn balance(&mut self, purse: URef) -> Result<Option<U512>, Error> {
         match self.read_balance(purse)? {
@@ -97,6 +168,8 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
     }
 
     /// Transfers `amount` of tokens from `source` purse to a `target` purse.
+    ///
+    /// The transfer is recorded with the optional `id` and `memo` tags.
     fn transfer(
         &mut self,
         maybe_to: Option<AccountHash>,
@@ -104,6 +177,7 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         target: URef,
         amount: U512,
         id: Option<u64>,
+        memo: Option<TransferMemo>,
     ) -> Result<(), Error> {
         if let (Phase::Session, Some(CallStackElement::StoredSession { .. })) =
             (self.get_phase(), self.get_immediate_caller())
@@ -112,127 +186,368 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
             return Err(Error::InvalidContext);
         }
 
-        if !self.allow_unrestricted_transfers() {
-            let registry = match self.get_system_contract_registry() {
-                Ok(registry) => registry,
-                Err(error) => {
-                    warn!(%error, "unable to obtain system contract registry during transfer");
-                    SystemContractRegistry::new()
+        if self.is_transfer_paused()? {
+            return Err(Error::MintPaused);
+        }
+
+        self.check_transfer_restrictions(maybe_to, target)?;
+
+        if !source.is_readable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        if !source.is_writeable() || !target.is_addable() {
+            return Err(Error::InvalidAccessRights);
+        }
+        let source_balance: U512 = match self.read_balance(source)? {
+            Some(source_balance) => source_balance,
+            None => return Err(Error::SourceNotFound),
+        };
+        if amount > source_balance {
+            return Err(Error::InsufficientFunds);
+        }
+        if self.read_balance(target)?.is_none() {
+            return Err(Error::DestNotFound);
+        }
+        if self.get_caller() != PublicKey::System.to_account_hash()
+            && self.get_main_purse().addr() == source.addr()
+        {
+            if amount > self.get_approved_spending_limit() {
+                return Err(Error::UnapprovedSpendingAmount);
+            }
+            self.sub_approved_spending_limit(amount);
+        }
+        self.write_balance(source, source_balance - amount)?;
+        self.add_balance(target, amount)?;
+        self.record_transfer(maybe_to, source, target, amount, id, memo)?;
+        Ok(())
+    }
+
+    /// Transfers tokens to multiple targets in a single call.
+    ///
+    /// Every transfer is validated against the balances resulting from the valid transfers
+    /// preceding it, and its outcome is returned at its position in the batch. Balances are only
+    /// written if all of the transfers are valid, so either all of them are made or none of them.
+    fn transfer_batch(
+        &mut self,
+        transfers: Vec<TransferArgs>,
+    ) -> Result<Vec<TransferResult>, Error> {
+        if let (Phase::Session, Some(CallStackElement::StoredSession { .. })) =
+            (self.get_phase(), self.get_immediate_caller())
+        {
+            // stored session code is not allowed to call this method in the session phase
+            return Err(Error::InvalidContext);
+        }
+
+        if self.is_transfer_paused()? {
+            return Err(Error::MintPaused);
+        }
+
+        let is_system_caller = self.get_caller() == PublicKey::System.to_account_hash();
+
+        let main_purse_addr = self.get_main_purse().addr();
+        let approved_spending_limit = self.get_approved_spending_limit();
+        // Initial and running balances of the purses touched by the batch.
+        let mut balances: BTreeMap<URefAddr, (URef, U512, U512)> = BTreeMap::new();
+        let mut debited_purses = BTreeSet::new();
+        let mut spent_from_main_purse = U512::zero();
+
+        let results: Vec<TransferResult> = transfers
+            .iter()
+            .map(|transfer| {
+                let (source, target, amount) =
+                    (transfer.source(), transfer.target(), transfer.amount());
+
+                self.check_transfer_restrictions(transfer.to(), target)?;
+
+                if !source.is_readable() {
+                    return Err(Error::InvalidAccessRights);
                 }
-            };
-            let immediate_caller = self.get_immediate_caller().cloned();
-            match immediate_caller {
-                Some(CallStackElement::StoredSession { contract_hash, .. })
-                | Some(CallStackElement::StoredContract { contract_hash, .. })
-                    if registry.has_contract_hash(&contract_hash) =>
-                {
-                    // System contract calling a mint is fine (i.e. standard payment calling mint's
-                    // transfer)
+                if !source.is_writeable() || !target.is_addable() {
+                    return Err(Error::InvalidAccessRights);
                 }
 
-                Some(CallStackElement::StoredSession {
-                    account_hash,
-                    contract_package_hash: _,
-                    contract_hash: _,
-                }) => {
-                    if account_hash != PublicKey::System.to_account_hash()
-                        && !self.is_administrator(&account_hash)
-                    {
-                        return Err(Error::DisabledUnrestrictedTransfers);
+                let (source_initial, source_balance) = match balances.get(&source.addr()) {
+                    Some((_, initial, balance)) => (*initial, *balance),
+                    None => {
+                        let balance = self.read_balance(source)?.ok_or(Error::SourceNotFound)?;
+                        (balance, balance)
                     }
+                };
+                if amount > source_balance {
+                    return Err(Error::InsufficientFunds);
                 }
+                let source_balance = source_balance - amount;
 
-                Some(CallStackElement::Session { account_hash: _ })
-                    if self.is_called_from_standard_payment() =>
-                {
-                    // Standard payment acts as a session without separate stack frame and calls
-                    // into mint's transfer.
-                }
+                let (target_initial, target_balance) = if target.addr() == source.addr() {
+                    (source_initial, source_balance)
+                } else {
+                    match balances.get(&target.addr()) {
+                        Some((_, initial, balance)) => (*initial, *balance),
+                        None => {
+                            let balance = self.read_balance(target)?.ok_or(Error::DestNotFound)?;
+                            (balance, balance)
+                        }
+                    }
+                };
+                let target_balance = target_balance
+                    .checked_add(amount)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                let spent = if !is_system_caller && source.addr() == main_purse_addr {
+                    let spent = spent_from_main_purse
+                        .checked_add(amount)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    if spent > approved_spending_limit {
+                        return Err(Error::UnapprovedSpendingAmount);
+                    }
+                    spent
+                } else {
+                    spent_from_main_purse
+                };
+
+                // The transfer is valid, so the running balances are only updated now.
+                balances.insert(source.addr(), (source, source_initial, source_balance));
+                balances.insert(target.addr(), (target, target_initial, target_balance));
+                debited_purses.insert(source.addr());
+                spent_from_main_purse = spent;
+                Ok(())
+            })
+            .collect();
+
+        if results.iter().any(Result::is_err) {
+            return Ok(results);
+        }
+
+        if !spent_from_main_purse.is_zero() {
+            self.sub_approved_spending_limit(spent_from_main_purse);
+        }
+
+        // As with single transfers, only the balances of debited purses are overwritten; purses
+        // which are only credited have the difference added to them.
+        for (purse_addr, (purse, initial, balance)) in balances {
+            if debited_purses.contains(&purse_addr) {
+                self.write_balance(purse, balance)?;
+            } else {
+                self.add_balance(purse, balance - initial)?;
+            }
+        }
+        for transfer in transfers {
+            self.record_transfer(
+                transfer.to(),
+                transfer.source(),
+                transfer.target(),
+                transfer.amount(),
+                transfer.arg_id(),
+                transfer.memo(),
+            )?;
+        }
+        Ok(results)
+    }
 
-                Some(CallStackElement::Session { account_hash })
-                    if account_hash == PublicKey::System.to_account_hash() =>
+    /// Checks that a transfer of tokens to `target` is allowed when unrestricted transfers are
+    /// disabled.
+    fn check_transfer_restrictions(
+        &mut self,
+        maybe_to: Option<AccountHash>,
+        target: URef,
+    ) -> Result<(), Error> {
+        if self.allow_unrestricted_transfers() {
+            return Ok(());
+        }
+        let registry = match self.get_system_contract_registry() {
+            Ok(registry) => registry,
+            Err(error) => {
+                warn!(%error, "unable to obtain system contract registry during transfer");
+                SystemContractRegistry::new()
+            }
+        };
+        let immediate_caller = self.get_immediate_caller().cloned();
+        match immediate_caller {
+            Some(CallStackElement::StoredSession { contract_hash, .. })
+            | Some(CallStackElement::StoredContract { contract_hash, .. })
+                if registry.has_contract_hash(&contract_hash) =>
+            {
+                // System contract calling a mint is fine (i.e. standard payment calling mint's
+                // transfer)
+            }
+
+            Some(CallStackElement::StoredSession {
+                account_hash,
+                contract_package_hash: _,
+                contract_hash: _,
+            }) => {
+                if account_hash != PublicKey::System.to_account_hash()
+                    && !self.is_administrator(&account_hash)
                 {
-                    // System calls a session code.
+                    return Err(Error::DisabledUnrestrictedTransfers);
                 }
+            }
 
-                Some(CallStackElement::Session { account_hash }) => {
-                    // For example: a session using transfer host functions, or calling the mint's
-                    // entrypoint directly
-
-                    let is_source_admin = self.is_administrator(&account_hash);
-                    match maybe_to {
-                        Some(to) => {
-                            let maybe_account = self.read_account(&to);
-
-                            match maybe_account {
-                                Ok(Some(StoredValue::Account(account))) => {
-                                    // This can happen when user tries to transfer funds by
-                                    // calling mint
-                                    // directly but tries to specify wrong account hash.
-                                    if account.main_purse().addr() != target.addr() {
-                                        return Err(Error::DisabledUnrestrictedTransfers);
-                                    }
-                                    let is_target_system_account = account.account_hash()
-                                        == PublicKey::System.to_account_hash();
-                                    let is_target_administrator =
-                                        self.is_administrator(&account.account_hash());
-                                    if !(is_source_admin
-                                        || is_target_system_account
-                                        || is_target_administrator)
-                                    {
-                                        return Err(Error::DisabledUnrestrictedTransfers);
-                                    }
-                                }
-                                Ok(Some(_stored_value)) => {
+            Some(CallStackElement::Session { account_hash: _ })
+                if self.is_called_from_standard_payment() =>
+            {
+                // Standard payment acts as a session without separate stack frame and calls
+                // into mint's transfer.
+            }
+
+            Some(CallStackElement::Session { account_hash })
+                if account_hash == PublicKey::System.to_account_hash() =>
+            {
+                // System calls a session code.
+            }
+
+            Some(CallStackElement::Session { account_hash }) => {
+                // For example: a session using transfer host functions, or calling the mint's
+                // entrypoint directly
+
+                let is_source_admin = self.is_administrator(&account_hash);
+                match maybe_to {
+                    Some(to) => {
+                        let maybe_account = self.read_account(&to);
+
+                        match maybe_account {
+                            Ok(Some(StoredValue::Account(account))) => {
+                                // This can happen when user tries to transfer funds by
+                                // calling mint
+                                // directly but tries to specify wrong account hash.
+                                if account.main_purse().addr() != target.addr() {
                                     return Err(Error::DisabledUnrestrictedTransfers);
                                 }
-                                Ok(None) => {
-                                    // `to` is specified, but no new account is persisted
-                                    // yet. Only
-                                    // administrators can do that and it is also validated
-                                    // at the host function level.
-                                    if !is_source_admin {
-                                        return Err(Error::DisabledUnrestrictedTransfers);
-                                    }
-                                }
-                                Err(error) => {
-                                    warn!(%error, "error while reading account");
-                                    return Err(Error::Storage);
+                                let is_target_system_account =
+                                    account.account_hash() == PublicKey::System.to_account_hash();
+                                let is_target_administrator =
+                                    self.is_administrator(&account.account_hash());
+                                if !(is_source_admin
+                                    || is_target_system_account
+                                    || is_target_administrator)
+                                {
+                                    return Err(Error::DisabledUnrestrictedTransfers);
                                 }
                             }
-                        }
-                        None => {
-                            if !is_source_admin {
+                            Ok(Some(_stored_value)) => {
                                 return Err(Error::DisabledUnrestrictedTransfers);
                             }
+                            Ok(None) => {
+                                // `to` is specified, but no new account is persisted
+                                // yet. Only
+                                // administrators can do that and it is also validated
+                                // at the host function level.
+                                if !is_source_admin {
+                                    return Err(Error::DisabledUnrestrictedTransfers);
+                                }
+                            }
+                            Err(error) => {
+                                warn!(%error, "error while reading account");
+                                return Err(Error::Storage);
+                            }
                         }
                     }
-                }
-
-                Some(CallStackElement::StoredContract {
-                    contract_package_hash: _,
-                    contract_hash: _,
-                }) => {
-                    if self.get_caller() != PublicKey::System.to_account_hash()
-                        && !self.is_administrator(&self.get_caller())
-                    {
-                        return Err(Error::DisabledUnrestrictedTransfers);
+                    None => {
+                        if !is_source_admin {
+                            return Err(Error::DisabledUnrestrictedTransfers);
+                        }
                     }
                 }
+            }
 
-                None => {
-                    // There's always an immediate caller, but we should return something.
+            Some(CallStackElement::StoredContract {
+                contract_package_hash: _,
+                contract_hash: _,
+            }) => {
+                if self.get_caller() != PublicKey::System.to_account_hash()
+                    && !self.is_administrator(&self.get_caller())
+                {
                     return Err(Error::DisabledUnrestrictedTransfers);
                 }
             }
+
+            None => {
+                // There's always an immediate caller, but we should return something.
+                return Err(Error::DisabledUnrestrictedTransfers);
+            }
         }
+        Ok(())
+    }
 
-        if !source.is_readable() {
+    /// Returns the amount `spender` is allowed to transfer out of the purse at `purse_addr`.
+    fn allowance(&mut self, purse_addr: URefAddr, spender: Key) -> Result<U512, Error> {
+        let allowances_uref = match self.get_key(ALLOWANCES_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => return Ok(U512::zero()),
+        };
+        let item_key = allowance_item_key(purse_addr, &spender)?;
+        Ok(self
+            .read_dictionary_item(allowances_uref, &item_key)?
+            .unwrap_or_default())
+    }
+
+    /// Allows `spender` to transfer up to `amount` of tokens out of `purse`, replacing any
+    /// allowance previously granted to it.  Requires write access to the purse.
+    fn approve(&mut self, purse: URef, spender: Key, amount: U512) -> Result<(), Error> {
+        if !purse.is_writeable() {
             return Err(Error::InvalidAccessRights);
         }
-        if !source.is_writeable() || !target.is_addable() {
+        if self.read_balance(purse)?.is_none() {
+            return Err(Error::PurseNotFound);
+        }
+        let allowances_uref = match self.get_key(ALLOWANCES_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) => return Err(Error::MissingKey),
+            None => {
+                // The dictionary is created on first use, so that the mint of an existing network
+                // does not need to be migrated.
+                let allowances_uref = self.new_uref(())?;
+                self.put_key(ALLOWANCES_KEY, Key::URef(allowances_uref))?;
+                allowances_uref
+            }
+        };
+        let item_key = allowance_item_key(purse.addr(), &spender)?;
+        self.write_dictionary_item(allowances_uref, &item_key, amount)
+    }
+
+    /// Transfers `amount` of tokens out of the purse at `source_addr` to the `target` purse on
+    /// behalf of its owner, deducting it from the allowance granted to the immediate caller.
+    ///
+    /// The immediate caller is identified by its account hash when calling from session code, or
+    /// by its contract hash when calling from a stored contract.
+    fn transfer_from(
+        &mut self,
+        source_addr: URefAddr,
+        target: URef,
+        amount: U512,
+        id: Option<u64>,
+    ) -> Result<(), Error> {
+        if let (Phase::Session, Some(CallStackElement::StoredSession { .. })) =
+            (self.get_phase(), self.get_immediate_caller())
+        {
+            // stored session code is not allowed to call this method in the session phase
+            return Err(Error::InvalidContext);
+        }
+
+        if self.get_caller() != PublicKey::System.to_account_hash() && self.is_mint_paused()? {
+            return Err(Error::MintPaused);
+        }
+
+        let spender = match self.get_immediate_caller() {
+            Some(CallStackElement::Session { account_hash }) => Key::Account(*account_hash),
+            Some(CallStackElement::StoredSession { contract_hash, .. })
+            | Some(CallStackElement::StoredContract { contract_hash, .. }) => {
+                Key::Hash(contract_hash.value())
+            }
+            None => return Err(Error::InvalidContext),
+        };
+
+        self.check_transfer_restrictions(None, target)?;
+
+        if !target.is_addable() {
             return Err(Error::InvalidAccessRights);
         }
+        let allowance = self.allowance(source_addr, spender)?;
+        if amount > allowance {
+            return Err(Error::InsufficientAllowance);
+        }
+        let source = URef::new(source_addr, AccessRights::READ_ADD_WRITE);
         let source_balance: U512 = match self.read_balance(source)? {
             Some(source_balance) => source_balance,
             None => return Err(Error::SourceNotFound),
@@ -243,17 +558,18 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         if self.read_balance(target)?.is_none() {
             return Err(Error::DestNotFound);
         }
-        if self.get_caller() != PublicKey::System.to_account_hash()
-            && self.get_main_purse().addr() == source.addr()
-        {
-            if amount > self.get_approved_spending_limit() {
-                return Err(Error::UnapprovedSpendingAmount);
-            }
-            self.sub_approved_spending_limit(amount);
+
+        if !amount.is_zero() {
+            let allowances_uref = self
+                .get_key(ALLOWANCES_KEY)
+                .and_then(Key::into_uref)
+                .ok_or(Error::MissingKey)?;
+            let item_key = allowance_item_key(source_addr, &spender)?;
+            self.write_dictionary_item(allowances_uref, &item_key, allowance - amount)?;
         }
         self.write_balance(source, source_balance - amount)?;
         self.add_balance(target, amount)?;
-        self.record_transfer(maybe_to, source, target, amount, id)?;
+        self.record_transfer(None, source, target, amount, id, None)?;
         Ok(())
     }
 
@@ -277,9 +593,8 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
             .read(round_seigniorage_rate_uref)?
             .ok_or(Error::TotalSupplyNotFound)?;
 
-        round_seigniorage_rate
-            .checked_mul(&Ratio::from(total_supply))
-            .map(|ratio| ratio.to_integer())
+        (MaybeNum::new(round_seigniorage_rate) * MaybeNum::new(total_supply).into_ratio())
+            .to_integer()
             .ok_or(Error::ArithmeticOverflow)
     }
 
@@ -317,4 +632,91 @@ pub trait Mint: RuntimeProvider + StorageProvider + SystemProvider {
         self.add(total_supply_uref, amount)?;
         Ok(())
     }
+
+    /// Returns whether mint, transfer, burn and total supply reduction are paused.
+    ///
+    /// The mint is treated as not paused if the flag has not been installed.
+    fn is_mint_paused(&mut self) -> Result<bool, Error> {
+        match self.get_key(MINT_PAUSED_KEY) {
+            Some(Key::URef(uref)) => Ok(self.read(uref)?.unwrap_or_default()),
+            Some(_) => Err(Error::MissingKey),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns whether a transfer by the current caller is refused as the mint is paused.
+    ///
+    /// Transfers by the system account or an administrator are not affected, nor are the
+    /// transfers into and out of the payment purse made by standard payment and the handle payment
+    /// contract, so that deploys can still be paid for, e.g. to resume the mint.
+    fn is_transfer_paused(&mut self) -> Result<bool, Error> {
+        let caller = self.get_caller();
+        if caller == PublicKey::System.to_account_hash()
+            || self.is_administrator(&caller)
+            || self.is_called_from_standard_payment()
+        {
+            return Ok(false);
+        }
+        let immediate_caller_hash = match self.get_immediate_caller() {
+            Some(CallStackElement::StoredSession { contract_hash, .. })
+            | Some(CallStackElement::StoredContract { contract_hash, .. }) => Some(*contract_hash),
+            Some(CallStackElement::Session { .. }) | None => None,
+        };
+        if let Some(contract_hash) = immediate_caller_hash {
+            let registry = match self.get_system_contract_registry() {
+                Ok(registry) => registry,
+                Err(error) => {
+                    warn!(%error, "unable to obtain system contract registry during transfer");
+                    SystemContractRegistry::new()
+                }
+            };
+            let is_payment_contract = [STANDARD_PAYMENT, HANDLE_PAYMENT]
+                .iter()
+                .any(|name| registry.get(name) == Some(&contract_hash));
+            if is_payment_contract {
+                return Ok(false);
+            }
+        }
+        self.is_mint_paused()
+    }
+
+    /// Pauses or resumes mint, transfer and total supply reduction.  Only the system account or
+    /// an administrator may do so, which they can also while the mint is paused.
+    fn set_mint_paused(&mut self, paused: bool) -> Result<(), Error> {
+        let caller = self.get_caller();
+        if caller != PublicKey::System.to_account_hash() && !self.is_administrator(&caller) {
+            return Err(Error::InvalidContext);
+        }
+        let mint_paused_uref = match self.get_key(MINT_PAUSED_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) | None => return Err(Error::MissingKey),
+        };
+        self.write(mint_paused_uref, paused)
+    }
+
+    /// Records the current total supply as the total supply at the start of `era_id`.  Only the
+    /// system account may do so, which happens at every switch block.
+    fn record_total_supply(&mut self, era_id: EraId) -> Result<(), Error> {
+        if self.get_caller() != PublicKey::System.to_account_hash() {
+            return Err(Error::InvalidContext);
+        }
+        let total_supply_uref = match self.get_key(TOTAL_SUPPLY_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) | None => return Err(Error::MissingKey),
+        };
+        let total_supply: U512 = self
+            .read(total_supply_uref)?
+            .ok_or(Error::TotalSupplyNotFound)?;
+
+        // The dictionary is created at genesis or by the protocol upgrade introducing it.
+        let historical_total_supply_uref = match self.get_key(HISTORICAL_TOTAL_SUPPLY_KEY) {
+            Some(Key::URef(uref)) => uref,
+            Some(_) | None => return Err(Error::MissingKey),
+        };
+        self.write_dictionary_item(
+            historical_total_supply_uref,
+            &historical_total_supply_item_key(era_id),
+            total_supply,
+        )
+    }
 }
@@ -18,6 +18,21 @@ pub trait StorageProvider {
     /// Add data to a [`URef`].
     fn add<T: CLTyped + ToBytes>(&mut self, uref: URef, value: T) -> Result<(), Error>;
 
+    /// Read data from a dictionary item.
+    fn read_dictionary_item<T: CLTyped + FromBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+    ) -> Result<Option<T>, Error>;
+
+    /// Write data to a dictionary item.
+    fn write_dictionary_item<T: CLTyped + ToBytes>(
+        &mut self,
+        seed_uref: URef,
+        dictionary_item_key: &str,
+        value: T,
+    ) -> Result<(), Error>;
+
     /// Read balance.
     fn read_balance(&mut self, uref: URef) -> Result<Option<U512>, Error>;
 
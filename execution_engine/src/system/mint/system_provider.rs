@@ -1,4 +1,4 @@
-use casper_types::{account::AccountHash, system::mint::Error, URef, U512};
+use casper_types::{account::AccountHash, system::mint::Error, TransferMemo, URef, U512};
 
 /// Provides functionality of a system module.
 pub trait SystemProvider {
@@ -10,5 +10,9 @@ pub trait SystemProvider {
         target: URef,
         amount: U512,
         id: Option<u64>,
+        memo: Option<TransferMemo>,
     ) -> Result<(), Error>;
+
+    /// Records a burn of `amount` tokens from `purse`.
+    fn record_burn(&mut self, purse: URef, amount: U512) -> Result<(), Error>;
 }
@@ -11,7 +11,10 @@ use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::{
     core::engine_state::{
-        engine_config::{FeeHandling, RefundHandling},
+        engine_config::{
+            AccountCreationPolicy, DelegationRateChangeLimit, FeeHandling, RefundHandling,
+            StorageRent,
+        },
         genesis::ExecConfigBuilder,
         run_genesis_request::RunGenesisRequest,
         ExecConfig, GenesisAccount,
@@ -68,8 +71,18 @@ pub struct CoreConfig {
     pub(crate) max_associated_keys: u32,
     /// Maximum height of contract runtime call stack.
     pub(crate) max_runtime_call_stack_height: u32,
+    /// Maximum size in bytes of a single `CLValue` written to global state.
+    pub(crate) max_cl_value_size: u32,
+    /// Maximum number of named keys of a single account or contract.
+    pub(crate) max_named_keys: u32,
     /// The minimum bound of motes that can be delegated to a validator.
     pub(crate) minimum_delegation_amount: u64,
+    /// The minimum bound of motes a validator's bid must hold unless it is fully withdrawn.
+    #[serde(default)]
+    pub(crate) minimum_bid_amount: u64,
+    /// Whether the auction records the validator set of each era.
+    #[serde(default)]
+    pub(crate) record_auction_snapshots: bool,
     /// Enables strict arguments checking when calling a contract.
     pub(crate) strict_argument_checking: bool,
     /// The maximum amount of delegators per validator.
@@ -78,6 +91,12 @@ pub struct CoreConfig {
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
     pub(crate) fee_handling: FeeHandling,
+    /// Storage rent.
+    pub(crate) storage_rent: StorageRent,
+    /// Delegation rate change limit.
+    pub(crate) delegation_rate_change_limit: DelegationRateChangeLimit,
+    /// Account creation policy.
+    pub(crate) account_creation_policy: AccountCreationPolicy,
 }
 
 /// This struct can be parsed from a TOML-encoded chainspec file.  It means that as the
@@ -155,11 +174,18 @@ impl ChainspecConfig {
             round_seigniorage_rate,
             max_associated_keys: _,
             max_runtime_call_stack_height: _,
+            max_cl_value_size: _,
+            max_named_keys: _,
             minimum_delegation_amount: _,
+            minimum_bid_amount: _,
+            record_auction_snapshots: _,
             strict_argument_checking: _,
             max_delegators_per_validator: _,
             refund_handling: _,
             fee_handling: _,
+            storage_rent: _,
+            delegation_rate_change_limit: _,
+            account_creation_policy: _,
         } = core_config;
 
         let exec_config = ExecConfigBuilder::new()
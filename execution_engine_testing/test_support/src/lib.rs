@@ -26,8 +26,10 @@ use once_cell::sync::Lazy;
 #[doc(inline)]
 #[allow(deprecated)]
 pub use casper_execution_engine::core::engine_state::engine_config::{
-    DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT,
-    DEFAULT_MAX_STORED_VALUE_SIZE, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+    DEFAULT_MAX_ASSOCIATED_KEYS, DEFAULT_MAX_CL_VALUE_SIZE, DEFAULT_MAX_NAMED_KEYS,
+    DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT, DEFAULT_MAX_STORED_VALUE_SIZE,
+    DEFAULT_MINIMUM_BID_AMOUNT, DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+    DEFAULT_RECORD_AUCTION_SNAPSHOTS,
 };
 use casper_execution_engine::{
     core::engine_state::{
@@ -228,10 +230,26 @@ mod tests {
             production.core_config.max_runtime_call_stack_height,
             DEFAULT_MAX_RUNTIME_CALL_STACK_HEIGHT
         );
+        assert_eq!(
+            production.core_config.max_cl_value_size,
+            DEFAULT_MAX_CL_VALUE_SIZE
+        );
+        assert_eq!(
+            production.core_config.max_named_keys,
+            DEFAULT_MAX_NAMED_KEYS
+        );
         assert_eq!(
             production.core_config.minimum_delegation_amount,
             DEFAULT_MINIMUM_DELEGATION_AMOUNT
         );
+        assert_eq!(
+            production.core_config.minimum_bid_amount,
+            DEFAULT_MINIMUM_BID_AMOUNT
+        );
+        assert_eq!(
+            production.core_config.record_auction_snapshots,
+            DEFAULT_RECORD_AUCTION_SNAPSHOTS
+        );
 
         assert_eq!(production.wasm_config, WasmConfig::default());
         assert_eq!(production.system_costs_config, SystemConfig::default());
@@ -17,6 +17,7 @@ pub struct UpgradeRequestBuilder {
     new_locked_funds_period_millis: Option<u64>,
     new_round_seigniorage_rate: Option<Ratio<u64>>,
     new_unbonding_delay: Option<u64>,
+    new_mint_paused: Option<bool>,
     global_state_update: BTreeMap<Key, StoredValue>,
     chainspec_registry: ChainspecRegistry,
 }
@@ -78,6 +79,12 @@ impl UpgradeRequestBuilder {
         self
     }
 
+    /// Sets `new_mint_paused`.
+    pub fn with_new_mint_paused(mut self, mint_paused: bool) -> Self {
+        self.new_mint_paused = Some(mint_paused);
+        self
+    }
+
     /// Sets `global_state_update`.
     pub fn with_global_state_update(
         mut self,
@@ -111,6 +118,7 @@ impl UpgradeRequestBuilder {
             self.new_locked_funds_period_millis,
             self.new_round_seigniorage_rate,
             self.new_unbonding_delay,
+            self.new_mint_paused,
             self.global_state_update,
             self.chainspec_registry,
         )
@@ -129,6 +137,7 @@ impl Default for UpgradeRequestBuilder {
             new_locked_funds_period_millis: None,
             new_round_seigniorage_rate: None,
             new_unbonding_delay: None,
+            new_mint_paused: None,
             global_state_update: Default::default(),
             chainspec_registry: ChainspecRegistry::new_with_optional_global_state(&[], None),
         }
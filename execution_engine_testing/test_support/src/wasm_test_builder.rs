@@ -25,9 +25,12 @@ use casper_execution_engine::{
             execution_result::ExecutionResult,
             run_genesis_request::RunGenesisRequest,
             step::{EvictItem, StepRequest, StepSuccess},
-            BalanceResult, EngineConfig, EngineConfigBuilder, EngineState, Error, GenesisSuccess,
-            GetBidsRequest, PruneConfig, PruneResult, QueryRequest, QueryResult, RewardItem,
-            StepError, SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+            AuctionCompactionResult, BalanceIdentifier, BalanceResult, BalancesRequest,
+            BalancesResult, EngineConfig, EngineConfigBuilder, EngineState, EntityIdentifier,
+            Error, GenesisSuccess, GetBidsRequest, GetBidsResult, GetEntityRequest,
+            GetEntityResult, GetUnbondingPursesRequest, PruneConfig, PruneResult, QueryChunk,
+            QueryRequest, QueryResult, QueryStreamRequest, RewardItem, StepError,
+            SystemContractRegistry, UnbondingPurseEntry, UpgradeConfig, UpgradeSuccess,
             DEFAULT_MAX_QUERY_DEPTH,
         },
         execution,
@@ -70,7 +73,7 @@ use casper_types::{
     },
     CLTyped, CLValue, Contract, ContractHash, ContractPackage, ContractPackageHash, ContractWasm,
     DeployHash, DeployInfo, EraId, Gas, Key, KeyTag, Motes, ProtocolVersion, PublicKey,
-    RuntimeArgs, StoredValue, Transfer, TransferAddr, URef, U512,
+    RuntimeArgs, StoredValue, Transfer, TransferAddr, TransferV2, URef, U512,
 };
 
 use crate::{
@@ -229,18 +232,29 @@ impl InMemoryWasmTestBuilder {
             round_seigniorage_rate: _,
             max_associated_keys,
             max_runtime_call_stack_height,
+            max_cl_value_size,
+            max_named_keys,
             minimum_delegation_amount,
+            minimum_bid_amount,
+            record_auction_snapshots,
             strict_argument_checking,
             max_delegators_per_validator,
             refund_handling,
             fee_handling,
+            storage_rent,
+            delegation_rate_change_limit,
+            account_creation_policy,
         } = core_config;
 
         let engine_config = EngineConfigBuilder::new()
             .with_max_query_depth(DEFAULT_MAX_QUERY_DEPTH)
             .with_max_associated_keys(max_associated_keys)
             .with_max_runtime_call_stack_height(max_runtime_call_stack_height)
+            .with_max_cl_value_size(max_cl_value_size)
+            .with_max_named_keys(max_named_keys)
             .with_minimum_delegation_amount(minimum_delegation_amount)
+            .with_minimum_bid_amount(minimum_bid_amount)
+            .with_record_auction_snapshots(record_auction_snapshots)
             .with_strict_argument_checking(strict_argument_checking)
             .with_vesting_schedule_period_millis(vesting_schedule_period.millis())
             .with_max_delegators_per_validator(max_delegators_per_validator)
@@ -248,6 +262,9 @@ impl InMemoryWasmTestBuilder {
             .with_system_config(system_costs_config)
             .with_refund_handling(refund_handling)
             .with_fee_handling(fee_handling)
+            .with_storage_rent(storage_rent)
+            .with_delegation_rate_change_limit(delegation_rate_change_limit)
+            .with_account_creation_policy(account_creation_policy)
             .build();
 
         let global_state = InMemoryGlobalState::empty().expect("should create global state");
@@ -359,7 +376,11 @@ impl LmdbWasmTestBuilder {
             .with_max_runtime_call_stack_height(
                 chainspec_config.core_config.max_runtime_call_stack_height,
             )
+            .with_max_cl_value_size(chainspec_config.core_config.max_cl_value_size)
+            .with_max_named_keys(chainspec_config.core_config.max_named_keys)
             .with_minimum_delegation_amount(chainspec_config.core_config.minimum_delegation_amount)
+            .with_minimum_bid_amount(chainspec_config.core_config.minimum_bid_amount)
+            .with_record_auction_snapshots(chainspec_config.core_config.record_auction_snapshots)
             .with_strict_argument_checking(chainspec_config.core_config.strict_argument_checking)
             .with_vesting_schedule_period_millis(
                 chainspec_config
@@ -664,6 +685,16 @@ where
         panic! {"{:?}", query_result};
     }
 
+    /// Streams the values of a collection in global state and returns all chunks.
+    pub fn query_stream(&self, query_stream_request: QueryStreamRequest) -> Vec<QueryChunk> {
+        self.engine_state
+            .run_query_stream(CorrelationId::new(), query_stream_request)
+            .expect("should start query stream")
+            .expect("should find state root hash")
+            .collect::<Result<_, _>>()
+            .expect("should stream values")
+    }
+
     /// Queries for the total supply of token.
     /// # Panics
     /// Panics if the total supply can't be found.
@@ -1075,6 +1106,18 @@ where
             .expect("should get purse balance using public key")
     }
 
+    /// Returns a `BalancesResult` for the given purses, panics if the balances can't be queried.
+    pub fn get_balances_result(&self, identifiers: Vec<BalanceIdentifier>) -> BalancesResult {
+        let correlation_id = CorrelationId::new();
+        let state_root_hash: Digest = self.post_state_hash.expect("should have post_state_hash");
+        self.engine_state
+            .get_balances(
+                correlation_id,
+                BalancesRequest::new(state_root_hash, identifiers),
+            )
+            .expect("should get balances")
+    }
+
     /// Gets the purse balance of a proposer.
     pub fn get_proposer_purse_balance(&self) -> U512 {
         let proposer_account = self
@@ -1147,8 +1190,21 @@ where
             .query(None, Key::Transfer(transfer), &[])
             .expect("should have transfer value");
 
-        if let StoredValue::Transfer(transfer) = transfer_value {
-            Some(transfer)
+        match transfer_value {
+            StoredValue::Transfer(transfer) => Some(transfer),
+            StoredValue::TransferV2(transfer_v2) => Some(transfer_v2.transfer),
+            _ => None,
+        }
+    }
+
+    /// Queries for a transfer tagged with a memo by `TransferAddr`.
+    pub fn get_transfer_v2(&self, transfer: TransferAddr) -> Option<TransferV2> {
+        let transfer_value: StoredValue = self
+            .query(None, Key::Transfer(transfer), &[])
+            .expect("should have transfer value");
+
+        if let StoredValue::TransferV2(transfer_v2) = transfer_value {
+            Some(transfer_v2)
         } else {
             None
         }
@@ -1242,6 +1298,36 @@ where
         get_bids_result.into_success().unwrap()
     }
 
+    /// Gets the bids matching the given request, which may be filtered and paginated.
+    pub fn get_bids_with_request(&mut self, get_bids_request: GetBidsRequest) -> GetBidsResult {
+        self.engine_state
+            .get_bids(CorrelationId::new(), get_bids_request)
+            .unwrap()
+    }
+
+    /// Gets the unbonding purses of the given public key, both as a validator and as a delegator.
+    pub fn get_unbonding_purses(&mut self, public_key: PublicKey) -> Vec<UnbondingPurseEntry> {
+        let get_unbonding_purses_request =
+            GetUnbondingPursesRequest::new(self.get_post_state_hash(), public_key);
+
+        let get_unbonding_purses_result = self
+            .engine_state
+            .get_unbonding_purses(CorrelationId::new(), get_unbonding_purses_request)
+            .unwrap();
+
+        get_unbonding_purses_result.into_success().unwrap()
+    }
+
+    /// Gets the account or contract identified by `entity_identifier`.
+    pub fn get_entity(&self, entity_identifier: EntityIdentifier) -> GetEntityResult {
+        let get_entity_request =
+            GetEntityRequest::new(self.get_post_state_hash(), entity_identifier);
+
+        self.engine_state
+            .get_entity(CorrelationId::new(), get_entity_request)
+            .unwrap()
+    }
+
     /// Gets [`UnbondingPurses`].
     pub fn get_unbonds(&mut self) -> UnbondingPurses {
         let correlation_id = CorrelationId::new();
@@ -1495,6 +1581,23 @@ where
         self
     }
 
+    /// Commits the deletion of the auction's stale records and returns the result.
+    pub fn commit_auction_compaction(&mut self) -> AuctionCompactionResult {
+        let result = self
+            .engine_state
+            .commit_auction_compaction(CorrelationId::new(), self.get_post_state_hash())
+            .expect("should compact auction state");
+
+        if let AuctionCompactionResult::Success {
+            post_state_hash, ..
+        } = &result
+        {
+            self.post_state_hash = Some(*post_state_hash);
+        }
+
+        result
+    }
+
     /// Returns a `Result` containing a [`PruneResult`].
     pub fn get_prune_result(
         &self,
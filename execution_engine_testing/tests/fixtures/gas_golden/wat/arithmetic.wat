@@ -0,0 +1,10 @@
+(module
+  (func $call
+    (local $acc i64)
+    (local.set $acc (i64.const 1))
+    (local.set $acc (i64.mul (local.get $acc) (i64.const 3)))
+    (local.set $acc (i64.add (local.get $acc) (i64.const 7)))
+    (local.set $acc (i64.div_u (local.get $acc) (i64.const 2)))
+    (drop (i64.rem_u (local.get $acc) (i64.const 5))))
+  (memory $memory 1)
+  (export "call" (func $call)))
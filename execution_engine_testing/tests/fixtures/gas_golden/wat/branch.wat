@@ -0,0 +1,10 @@
+(module
+  (func $call
+    (local $i i32)
+    (block $exit
+      (loop $loop
+        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+        (br_if $exit (i32.ge_u (local.get $i) (i32.const 100)))
+        (br $loop))))
+  (memory $memory 1)
+  (export "call" (func $call)))
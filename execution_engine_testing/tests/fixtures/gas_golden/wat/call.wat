@@ -0,0 +1,7 @@
+(module
+  (func $square (param $x i32) (result i32)
+    (i32.mul (local.get $x) (local.get $x)))
+  (func $call
+    (drop (call $square (call $square (i32.const 3)))))
+  (memory $memory 1)
+  (export "call" (func $call)))
@@ -0,0 +1,14 @@
+(module
+  (func $select (param $x i32) (result i32)
+    (if (result i32) (i32.eqz (local.get $x))
+      (then (i32.const 10))
+      (else
+        (if (result i32) (i32.gt_u (local.get $x) (i32.const 5))
+          (then (i32.const 20))
+          (else (i32.const 30))))))
+  (func $call
+    (drop (call $select (i32.const 0)))
+    (drop (call $select (i32.const 7)))
+    (drop (call $select (i32.const 3))))
+  (memory $memory 1)
+  (export "call" (func $call)))
@@ -0,0 +1,7 @@
+(module
+  (func $call
+    (drop (memory.grow (i32.const 1)))
+    (i32.store (i32.const 0) (i32.const 42))
+    (i64.store (i32.const 65536) (i64.load (i32.const 0))))
+  (memory $memory 1)
+  (export "call" (func $call)))
@@ -5,12 +5,15 @@ use casper_engine_test_support::{
     DEFAULT_PAYMENT, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
 };
 use casper_execution_engine::core::{
-    engine_state::{run_genesis_request::RunGenesisRequest, Error as EngineError, GenesisAccount},
+    engine_state::{
+        run_genesis_request::RunGenesisRequest, Error as EngineError, GenesisAccount, QueryChunk,
+        QueryStreamRequest, QueryStreamTarget,
+    },
     execution::Error,
 };
 use casper_types::{
     account::AccountHash, runtime_args, system::mint, AccessRights, ApiError, CLType, CLValue,
-    ContractHash, Key, Motes, RuntimeArgs, StoredValue, U512,
+    ContractHash, Key, KeyTag, Motes, RuntimeArgs, StoredValue, U512,
 };
 use std::{convert::TryFrom, path::PathBuf};
 
@@ -652,6 +655,60 @@ fn should_query_dictionary_items_with_test_builder() {
     }
 }
 
+#[ignore]
+#[test]
+fn should_stream_dictionary_items() {
+    let (builder, _contract_hash) = setup();
+
+    let dictionary_uref = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have account")
+        .named_keys()
+        .get(dictionary::DICTIONARY_REF)
+        .and_then(Key::as_uref)
+        .copied()
+        .expect("should have dictionary uref");
+    let state_hash = builder.get_post_state_hash();
+
+    // Only the items of the requested dictionary are streamed, unwrapped as by regular queries.
+    let chunks = builder.query_stream(QueryStreamRequest::new(
+        state_hash,
+        QueryStreamTarget::Dictionary(dictionary_uref),
+        10,
+    ));
+    assert_eq!(chunks.len(), 1);
+    let expected_key = Key::dictionary(
+        dictionary_uref,
+        dictionary::DEFAULT_DICTIONARY_NAME.as_bytes(),
+    );
+    assert_eq!(chunks[0].values.len(), 1);
+    assert_eq!(chunks[0].proofs.len(), 1);
+    let (key, value) = chunks[0].values[0].clone();
+    assert_eq!(key, expected_key);
+    assert_eq!(*chunks[0].proofs[0].key(), expected_key);
+    let value = CLValue::try_from(value).expect("should have cl value");
+    let value: String = value.into_t().expect("should be string");
+    assert_eq!(value, dictionary::DEFAULT_DICTIONARY_VALUE);
+
+    // A stream can be resumed after the last key of a chunk.
+    let all_chunks = builder.query_stream(QueryStreamRequest::new(
+        state_hash,
+        QueryStreamTarget::KeyTag(KeyTag::Dictionary),
+        1,
+    ));
+    assert!(all_chunks.iter().all(|chunk| chunk.values.len() == 1));
+    let first_key = all_chunks[0].last_key().expect("should have key");
+    let resumed_chunks = builder.query_stream(
+        QueryStreamRequest::new(state_hash, QueryStreamTarget::KeyTag(KeyTag::Dictionary), 1)
+            .with_start_after(first_key),
+    );
+    assert_eq!(resumed_chunks.len(), all_chunks.len() - 1);
+    assert_eq!(
+        resumed_chunks.first().and_then(QueryChunk::last_key),
+        all_chunks.get(1).and_then(QueryChunk::last_key)
+    );
+}
+
 #[ignore]
 #[test]
 fn should_be_able_to_perform_dictionary_read() {
@@ -9,7 +9,7 @@ use casper_engine_test_support::{
 use casper_execution_engine::shared::system_config::DEFAULT_WASMLESS_TRANSFER_COST;
 use casper_types::{
     account::AccountHash, runtime_args, system::mint, AccessRights, Contract, ContractHash,
-    DeployHash, PublicKey, RuntimeArgs, SecretKey, Transfer, TransferAddr, U512,
+    DeployHash, PublicKey, RuntimeArgs, SecretKey, Transfer, TransferAddr, TransferMemo, U512,
 };
 
 const CONTRACT_TRANSFER_PURSE_TO_ACCOUNT: &str = "transfer_purse_to_account.wasm";
@@ -114,6 +114,51 @@ fn should_record_wasmless_transfer() {
     assert_eq!(transfer.amount, *TRANSFER_AMOUNT_1);
     assert_eq!(transfer.gas, U512::zero());
     assert_eq!(transfer.id, id);
+    assert!(builder.get_transfer_v2(transfers[0]).is_none());
+}
+
+#[ignore]
+#[test]
+fn should_record_wasmless_transfer_with_memo() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let id = Some(0);
+    let memo = TransferMemo::new([42; 32]);
+
+    let transfer_request = ExecuteRequestBuilder::transfer(
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            TRANSFER_ARG_TARGET => *ALICE_ADDR,
+            TRANSFER_ARG_AMOUNT => *TRANSFER_AMOUNT_1,
+            TRANSFER_ARG_ID => id,
+            mint::ARG_MEMO => Some(memo)
+        },
+    )
+    .build();
+
+    let deploy_hash = transfer_request.deploys()[0].deploy_hash;
+
+    builder.exec(transfer_request).commit().expect_success();
+
+    let transfers = builder
+        .get_deploy_info(deploy_hash)
+        .expect("should have deploy info")
+        .transfers;
+    assert_eq!(transfers.len(), 1);
+
+    let transfer_v2 = builder
+        .get_transfer_v2(transfers[0])
+        .expect("should have transfer with memo");
+
+    assert_eq!(transfer_v2.memo, memo);
+    assert_eq!(transfer_v2.transfer.to, Some(*ALICE_ADDR));
+    assert_eq!(transfer_v2.transfer.amount, *TRANSFER_AMOUNT_1);
+    assert_eq!(transfer_v2.transfer.id, id);
+    assert_eq!(
+        builder.get_transfer(transfers[0]),
+        Some(transfer_v2.transfer)
+    );
 }
 
 #[ignore]
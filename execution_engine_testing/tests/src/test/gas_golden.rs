@@ -0,0 +1,157 @@
+//! Compares the gas consumed by a fixed corpus of Wasm fixtures against golden values checked in
+//! per protocol version, so that changes to costs don't go unnoticed.
+//!
+//! After an intended change of costs, regenerate the golden file by running this test with the
+//! `UPDATE_GAS_GOLDEN_FILES` environment variable set (`make update-gas-golden-files`) and commit
+//! the result.
+
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_PROTOCOL_VERSION,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::engine_state::ExecuteRequest;
+use casper_types::{account::AccountHash, runtime_args, Gas, ProtocolVersion, RuntimeArgs};
+
+const UPDATE_GAS_GOLDEN_FILES_ENV: &str = "UPDATE_GAS_GOLDEN_FILES";
+const GAS_GOLDEN_DIRECTORY: &str = "fixtures/gas_golden";
+const WAT_FIXTURES_DIRECTORY: &str = "wat";
+
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([1u8; 32]);
+
+fn is_golden_update_enabled() -> bool {
+    env::var_os(UPDATE_GAS_GOLDEN_FILES_ENV).is_some()
+}
+
+fn path_to_gas_golden() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join(GAS_GOLDEN_DIRECTORY)
+}
+
+fn path_to_golden_file(protocol_version: ProtocolVersion) -> PathBuf {
+    let version = protocol_version.value();
+    path_to_gas_golden().join(format!(
+        "{}_{}_{}.json",
+        version.major, version.minor, version.patch
+    ))
+}
+
+/// Returns the requests of the corpus, keyed by the fixture name.
+///
+/// Each `.wat` file of the fixtures directory is executed as module bytes, alongside a selection of
+/// EE test contracts.
+fn gas_corpus() -> BTreeMap<String, ExecuteRequest> {
+    let mut corpus = BTreeMap::new();
+
+    let wat_fixtures = fs::read_dir(path_to_gas_golden().join(WAT_FIXTURES_DIRECTORY))
+        .expect("should read wat fixtures directory");
+    for entry in wat_fixtures {
+        let path = entry.expect("should read directory entry").path();
+        let name = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .expect("should have file name")
+            .to_string();
+        let module_bytes = wat::parse_file(&path).expect("should parse wat fixture");
+        let request = ExecuteRequestBuilder::module_bytes(
+            *DEFAULT_ACCOUNT_ADDR,
+            module_bytes,
+            RuntimeArgs::default(),
+        )
+        .build();
+        corpus.insert(name, request);
+    }
+
+    let contracts = [
+        ("do_nothing.wasm", RuntimeArgs::default()),
+        (
+            "new_named_uref.wasm",
+            runtime_args! { "uref_name" => "golden_uref" },
+        ),
+        (
+            "create_purse_01.wasm",
+            runtime_args! { "purse_name" => "golden_purse" },
+        ),
+        (
+            "add_update_associated_key.wasm",
+            runtime_args! { "account" => ACCOUNT_1_ADDR },
+        ),
+    ];
+    for (contract, args) in contracts {
+        let request =
+            ExecuteRequestBuilder::standard(*DEFAULT_ACCOUNT_ADDR, contract, args).build();
+        corpus.insert(contract.to_string(), request);
+    }
+
+    corpus
+}
+
+/// Executes each request of the corpus on a fresh global state and returns the gas it consumed.
+fn measure_gas(corpus: BTreeMap<String, ExecuteRequest>) -> BTreeMap<String, Gas> {
+    corpus
+        .into_iter()
+        .map(|(name, request)| {
+            let mut builder = InMemoryWasmTestBuilder::default();
+            builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+            builder.exec(request).expect_success().commit();
+            (name, builder.last_exec_gas_cost())
+        })
+        .collect()
+}
+
+#[ignore]
+#[test]
+fn should_match_gas_golden_values() {
+    let actual = measure_gas(gas_corpus());
+    let path_to_golden_file = path_to_golden_file(*DEFAULT_PROTOCOL_VERSION);
+
+    if is_golden_update_enabled() {
+        let mut serialized = serde_json::to_string_pretty(&actual).expect("should serialize");
+        serialized.push('\n');
+        fs::write(&path_to_golden_file, serialized).expect("should write golden file");
+        return;
+    }
+
+    let golden: BTreeMap<String, Gas> = match fs::read(&path_to_golden_file) {
+        Ok(bytes) => serde_json::from_slice(&bytes).expect("should parse golden file"),
+        Err(error) => panic!(
+            "failed to read gas golden file {}: {}; run with {} set to create it",
+            path_to_golden_file.display(),
+            error,
+            UPDATE_GAS_GOLDEN_FILES_ENV
+        ),
+    };
+
+    let mut mismatches = Vec::new();
+    for (name, actual_gas) in &actual {
+        match golden.get(name) {
+            Some(golden_gas) if golden_gas == actual_gas => {}
+            Some(golden_gas) => mismatches.push(format!(
+                "{}: expected {}, consumed {}",
+                name,
+                golden_gas.value(),
+                actual_gas.value()
+            )),
+            None => mismatches.push(format!("{}: missing golden value", name)),
+        }
+    }
+    for name in golden.keys().filter(|name| !actual.contains_key(*name)) {
+        mismatches.push(format!(
+            "{}: golden value for a fixture not in the corpus",
+            name
+        ));
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "gas consumption differs from {}:\n{}\nif the change is intended, rerun with {} set to \
+        update the golden file",
+        path_to_golden_file.display(),
+        mismatches.join("\n"),
+        UPDATE_GAS_GOLDEN_FILES_ENV
+    );
+}
@@ -4,7 +4,13 @@ use casper_engine_test_support::{
     ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
     PRODUCTION_RUN_GENESIS_REQUEST,
 };
-use casper_execution_engine::{core, core::ValidationError};
+use casper_execution_engine::{
+    core,
+    core::{
+        engine_state::{BalanceIdentifier, BalancesResult},
+        ValidationError,
+    },
+};
 use casper_hashing::Digest;
 use casper_types::{
     account::AccountHash, runtime_args, AccessRights, Key, PublicKey, RuntimeArgs, SecretKey, URef,
@@ -192,3 +198,58 @@ fn get_balance_using_public_key_should_work() {
         Err(ValidationError::UnexpectedValue)
     );
 }
+
+#[ignore]
+#[test]
+fn get_balances_should_work() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_request = ExecuteRequestBuilder::transfer(
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            TRANSFER_ARG_TARGET => *ALICE_ADDR,
+            TRANSFER_ARG_AMOUNT => *TRANSFER_AMOUNT_1,
+            TRANSFER_ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build();
+
+    builder.exec(transfer_request).commit().expect_success();
+
+    let alice_main_purse = builder
+        .get_account(*ALICE_ADDR)
+        .expect("should have Alice's account")
+        .main_purse();
+    let default_account_main_purse = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account")
+        .main_purse();
+    let missing_account = AccountHash::new([42; 32]);
+    let missing_purse = URef::new([42; 32], AccessRights::READ_ADD_WRITE);
+
+    let identifiers = vec![
+        BalanceIdentifier::Account(*ALICE_ADDR),
+        BalanceIdentifier::Purse(default_account_main_purse),
+        BalanceIdentifier::Account(missing_account),
+        BalanceIdentifier::Purse(missing_purse),
+        BalanceIdentifier::Purse(alice_main_purse),
+    ];
+
+    let balances = match builder.get_balances_result(identifiers.clone()) {
+        BalancesResult::Success(balances) => balances,
+        BalancesResult::RootNotFound => panic!("should find post state hash"),
+    };
+
+    let expected = vec![
+        (identifiers[0], Some(*TRANSFER_AMOUNT_1)),
+        (
+            identifiers[1],
+            Some(builder.get_purse_balance(default_account_main_purse)),
+        ),
+        (identifiers[2], None),
+        (identifiers[3], None),
+        (identifiers[4], Some(*TRANSFER_AMOUNT_1)),
+    ];
+    assert_eq!(balances, expected);
+}
@@ -0,0 +1,86 @@
+use casper_engine_test_support::{
+    InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR, DEFAULT_ACCOUNT_PUBLIC_KEY,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::engine_state::{Entity, EntityIdentifier, GetEntityResult};
+use casper_types::{
+    account::AccountHash,
+    system::mint::{ARG_AMOUNT, METHOD_TRANSFER},
+    CLType,
+};
+
+#[ignore]
+#[test]
+fn should_get_account_by_public_key_and_account_hash() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let default_account = builder
+        .get_account(*DEFAULT_ACCOUNT_ADDR)
+        .expect("should have default account");
+
+    for entity_identifier in [
+        EntityIdentifier::PublicKey(DEFAULT_ACCOUNT_PUBLIC_KEY.clone()),
+        EntityIdentifier::AccountHash(*DEFAULT_ACCOUNT_ADDR),
+    ] {
+        let entity = builder
+            .get_entity(entity_identifier)
+            .into_success()
+            .expect("should get entity");
+        assert_eq!(entity, Entity::Account(default_account.clone()));
+    }
+}
+
+#[ignore]
+#[test]
+fn should_get_contract_with_entry_point_parameter_types() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let mint_hash = builder.get_mint_contract_hash();
+
+    let (contract_hash, contract) = match builder
+        .get_entity(EntityIdentifier::ContractHash(mint_hash))
+        .into_success()
+    {
+        Some(Entity::Contract {
+            contract_hash,
+            contract,
+        }) => (contract_hash, contract),
+        other => panic!("expected the mint contract, got {:?}", other),
+    };
+    assert_eq!(contract_hash, mint_hash);
+    assert!(!contract.named_keys().is_empty());
+
+    let transfer_entry_point = contract
+        .entry_point(METHOD_TRANSFER)
+        .expect("should have transfer entry point");
+    let amount_parameter = transfer_entry_point
+        .args()
+        .iter()
+        .find(|parameter| parameter.name() == ARG_AMOUNT)
+        .expect("should have amount parameter");
+    assert_eq!(amount_parameter.cl_type(), &CLType::U512);
+}
+
+#[ignore]
+#[test]
+fn should_not_get_missing_entity() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let missing_account = AccountHash::new([255; 32]);
+    assert!(matches!(
+        builder.get_entity(EntityIdentifier::AccountHash(missing_account)),
+        GetEntityResult::EntityNotFound
+    ));
+
+    // A contract hash is not accepted as an account hash.
+    let mint_hash = builder.get_mint_contract_hash();
+    assert!(matches!(
+        builder.get_entity(EntityIdentifier::AccountHash(AccountHash::new(
+            mint_hash.value()
+        ))),
+        GetEntityResult::EntityNotFound
+    ));
+}
@@ -6,7 +6,11 @@ mod contract_context;
 mod deploy;
 mod explorer;
 mod gas_counter;
+// Golden values are recorded for the Rust test contracts.
+#[cfg(not(feature = "use-as-wasm"))]
+mod gas_golden;
 mod get_balance;
+mod get_entity;
 mod groups;
 mod host_function_costs;
 mod manage_groups;
@@ -15,6 +19,7 @@ mod regression;
 mod stack_overflow;
 mod step;
 mod storage_costs;
+mod storage_rent;
 mod system_contracts;
 mod system_costs;
 mod tutorial;
@@ -23,7 +23,10 @@ use casper_execution_engine::{
         host_function_costs::{HostFunction, HostFunctionCosts},
         opcode_costs::OpcodeCosts,
         storage_costs::StorageCosts,
-        wasm_config::{WasmConfig, DEFAULT_MAX_STACK_HEIGHT, DEFAULT_WASM_MAX_MEMORY},
+        wasm_config::{
+            WasmConfig, DEFAULT_MAX_INDIRECT_CALL_SIGNATURES, DEFAULT_MAX_STACK_HEIGHT,
+            DEFAULT_MAX_TABLE_ELEMENTS, DEFAULT_MAX_TABLE_SIZE, DEFAULT_WASM_MAX_MEMORY,
+        },
     },
 };
 use num_rational::Ratio;
@@ -207,6 +210,9 @@ fn make_wasm_config() -> WasmConfig {
     WasmConfig::new(
         DEFAULT_WASM_MAX_MEMORY,
         DEFAULT_MAX_STACK_HEIGHT,
+        DEFAULT_MAX_TABLE_SIZE,
+        DEFAULT_MAX_TABLE_ELEMENTS,
+        DEFAULT_MAX_INDIRECT_CALL_SIGNATURES,
         OpcodeCosts::default(),
         StorageCosts::default(),
         host_functions,
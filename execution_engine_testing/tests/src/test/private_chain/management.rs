@@ -925,6 +925,70 @@ fn should_not_allow_delegate_on_private_chain() {
     // therefore there is nothing to test.
 }
 
+#[ignore]
+#[test]
+fn administrator_account_should_pause_and_resume_mint() {
+    let mut builder = super::private_chain_setup();
+
+    let mint_paused = |builder: &InMemoryWasmTestBuilder| -> bool {
+        let mint_paused_key = builder
+            .get_contract(builder.get_mint_contract_hash())
+            .expect("mint should exist")
+            .named_keys()[mint::MINT_PAUSED_KEY];
+        builder
+            .query(None, mint_paused_key, &[])
+            .expect("should have mint paused flag")
+            .as_cl_value()
+            .expect("should be a CLValue")
+            .clone()
+            .into_t()
+            .expect("should be bool")
+    };
+    let set_mint_paused_request = |builder: &InMemoryWasmTestBuilder, paused: bool| {
+        ExecuteRequestBuilder::contract_call_by_hash(
+            *DEFAULT_ADMIN_ACCOUNT_ADDR,
+            builder.get_mint_contract_hash(),
+            mint::METHOD_SET_MINT_PAUSED,
+            runtime_args! {
+                mint::ARG_PAUSED => paused,
+            },
+        )
+        .build()
+    };
+    let transfer_request = || {
+        let transfer_args = runtime_args! {
+            mint::ARG_TARGET => *DEFAULT_ADMIN_ACCOUNT_ADDR,
+            mint::ARG_AMOUNT => U512::one(),
+            mint::ARG_ID => <Option<u64>>::None,
+        };
+        ExecuteRequestBuilder::transfer(*ACCOUNT_1_ADDR, transfer_args).build()
+    };
+
+    // The administrator pays for the deploy pausing the mint as usual.
+    let pause_request = set_mint_paused_request(&builder, true);
+    builder.exec(pause_request).expect_success().commit();
+    assert!(mint_paused(&builder));
+
+    builder.exec(transfer_request()).expect_failure().commit();
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(api_error))
+            if api_error == mint::Error::MintPaused.into()
+        ),
+        "{:?}",
+        error
+    );
+
+    // While the mint is paused, the administrator can still pay for the deploy resuming it.
+    let resume_request = set_mint_paused_request(&builder, false);
+    builder.exec(resume_request).expect_success().commit();
+    assert!(!mint_paused(&builder));
+
+    builder.exec(transfer_request()).expect_success().commit();
+}
+
 fn make_call_contract_session_request(
     account_hash: AccountHash,
     contract_hash: ContractHash,
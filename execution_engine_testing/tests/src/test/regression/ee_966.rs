@@ -16,7 +16,10 @@ use casper_execution_engine::{
         host_function_costs::HostFunctionCosts,
         opcode_costs::OpcodeCosts,
         storage_costs::StorageCosts,
-        wasm_config::{WasmConfig, DEFAULT_MAX_STACK_HEIGHT, DEFAULT_WASM_MAX_MEMORY},
+        wasm_config::{
+            WasmConfig, DEFAULT_MAX_INDIRECT_CALL_SIGNATURES, DEFAULT_MAX_STACK_HEIGHT,
+            DEFAULT_MAX_TABLE_ELEMENTS, DEFAULT_MAX_TABLE_SIZE, DEFAULT_WASM_MAX_MEMORY,
+        },
     },
 };
 use casper_types::{
@@ -32,6 +35,9 @@ static DOUBLED_WASM_MEMORY_LIMIT: Lazy<WasmConfig> = Lazy::new(|| {
     WasmConfig::new(
         DEFAULT_WASM_MAX_MEMORY * 2,
         DEFAULT_MAX_STACK_HEIGHT,
+        DEFAULT_MAX_TABLE_SIZE,
+        DEFAULT_MAX_TABLE_ELEMENTS,
+        DEFAULT_MAX_INDIRECT_CALL_SIGNATURES,
         OpcodeCosts::default(),
         StorageCosts::default(),
         HostFunctionCosts::default(),
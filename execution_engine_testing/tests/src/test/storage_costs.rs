@@ -15,7 +15,10 @@ use casper_execution_engine::{
         host_function_costs::{HostFunction, HostFunctionCosts},
         opcode_costs::{BrTableCost, ControlFlowCosts, OpcodeCosts},
         storage_costs::StorageCosts,
-        wasm_config::{WasmConfig, DEFAULT_MAX_STACK_HEIGHT, DEFAULT_WASM_MAX_MEMORY},
+        wasm_config::{
+            WasmConfig, DEFAULT_MAX_INDIRECT_CALL_SIGNATURES, DEFAULT_MAX_STACK_HEIGHT,
+            DEFAULT_MAX_TABLE_ELEMENTS, DEFAULT_MAX_TABLE_SIZE, DEFAULT_WASM_MAX_MEMORY,
+        },
     },
 };
 use casper_types::{
@@ -144,6 +147,9 @@ static STORAGE_COSTS_ONLY: Lazy<WasmConfig> = Lazy::new(|| {
     WasmConfig::new(
         DEFAULT_WASM_MAX_MEMORY,
         DEFAULT_MAX_STACK_HEIGHT,
+        DEFAULT_MAX_TABLE_SIZE,
+        DEFAULT_MAX_TABLE_ELEMENTS,
+        DEFAULT_MAX_INDIRECT_CALL_SIGNATURES,
         NEW_OPCODE_COSTS,
         StorageCosts::default(),
         *NEW_HOST_FUNCTION_COSTS,
@@ -0,0 +1,100 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
+    PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::core::engine_state::{
+    engine_config::StorageRent, EngineConfigBuilder,
+};
+use casper_types::{runtime_args, EraId, Key, RuntimeArgs, StorageLease};
+
+const NEW_NAMED_UREF_WASM: &str = "new_named_uref.wasm";
+const ARG_UREF_NAME: &str = "uref_name";
+const UREF_NAME: &str = "leased_uref";
+const LEASE_ERAS: u64 = 1;
+const GRACE_ERAS: u64 = 1;
+
+fn query_lease(builder: &InMemoryWasmTestBuilder, leased_key: Key) -> StorageLease {
+    builder
+        .query(None, Key::storage_lease(&leased_key.normalize()), &[])
+        .expect("should have storage lease")
+        .as_cl_value()
+        .expect("should be a CLValue")
+        .clone()
+        .into_t()
+        .expect("should be a storage lease")
+}
+
+#[ignore]
+#[test]
+fn should_lease_written_values_and_expire_them_after_grace_period() {
+    let engine_config = EngineConfigBuilder::new()
+        .with_storage_rent(StorageRent::Lease {
+            lease_eras: LEASE_ERAS,
+            grace_eras: GRACE_ERAS,
+        })
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        NEW_NAMED_UREF_WASM,
+        runtime_args! { ARG_UREF_NAME => UREF_NAME },
+    )
+    .build();
+    builder.exec(request).expect_success().commit();
+
+    let uref_key = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()[UREF_NAME];
+    let written_in: EraId = builder.get_era();
+
+    let lease = query_lease(&builder, uref_key);
+    assert_eq!(lease.paid_until(), written_in.saturating_add(LEASE_ERAS));
+    assert!(!lease.is_expired());
+
+    // The lease is indexed under the era it becomes due in.
+    let due_era_id = lease.paid_until().saturating_add(GRACE_ERAS + 1);
+    let indexed_lease_key: Key = builder
+        .query(
+            None,
+            Key::storage_lease_expiry(due_era_id, &uref_key.normalize()),
+            &[],
+        )
+        .expect("should have storage lease expiry")
+        .as_cl_value()
+        .expect("should be a CLValue")
+        .clone()
+        .into_t()
+        .expect("should be a key");
+    assert_eq!(indexed_lease_key, Key::storage_lease(&uref_key.normalize()));
+
+    // The lease is still valid during the grace period.
+    builder.advance_eras_by(LEASE_ERAS + GRACE_ERAS, Vec::new(), Vec::new());
+    assert!(!query_lease(&builder, uref_key).is_expired());
+
+    builder.advance_era(Vec::new(), Vec::new());
+    assert!(query_lease(&builder, uref_key).is_expired());
+}
+
+#[ignore]
+#[test]
+fn should_not_lease_values_with_storage_rent_disabled() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        NEW_NAMED_UREF_WASM,
+        runtime_args! { ARG_UREF_NAME => UREF_NAME },
+    )
+    .build();
+    builder.exec(request).expect_success().commit();
+
+    let uref_key = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()[UREF_NAME];
+    assert!(builder
+        .query(None, Key::storage_lease(&uref_key.normalize()), &[])
+        .is_err());
+}
@@ -1,4 +1,7 @@
-use std::{collections::BTreeSet, iter::FromIterator};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    iter::FromIterator,
+};
 
 use assert_matches::assert_matches;
 use num_traits::{One, Zero};
@@ -16,11 +19,12 @@ use casper_execution_engine::{
     core::{
         engine_state::{
             self,
-            engine_config::DEFAULT_MINIMUM_DELEGATION_AMOUNT,
+            engine_config::{DelegationRateChangeLimit, DEFAULT_MINIMUM_DELEGATION_AMOUNT},
             genesis::{ExecConfigBuilder, GenesisAccount, GenesisValidator},
             run_genesis_request::RunGenesisRequest,
             step::EvictItem,
-            EngineConfigBuilder, Error, RewardItem,
+            AuctionCompactionResult, BidStatus, EngineConfigBuilder, Error, GetBidsRequest,
+            GetBidsResult, RewardItem, UnbondingPurseKind,
         },
         execution,
     },
@@ -35,11 +39,12 @@ use casper_types::{
         self,
         auction::{
             self, Bids, DelegationRate, EraValidators, Error as AuctionError, UnbondingPurses,
-            ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR, ARG_NEW_VALIDATOR,
-            ARG_PUBLIC_KEY, ARG_VALIDATOR, ERA_ID_KEY, INITIAL_ERA_ID,
+            ValidatorWeights, ARG_AMOUNT, ARG_DELEGATIONS, ARG_DELEGATION_RATE, ARG_DELEGATOR,
+            ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY, ARG_REDELEGATIONS, ARG_VALIDATOR, ERA_ID_KEY,
+            INITIAL_ERA_ID,
         },
     },
-    EraId, Motes, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U256, U512,
+    EraId, Key, Motes, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U256, U512,
 };
 
 const ARG_TARGET: &str = "target";
@@ -527,7 +532,11 @@ fn should_calculate_era_validators() {
 
     let run_genesis_request = utils::create_run_genesis_request(accounts);
 
-    let mut builder = InMemoryWasmTestBuilder::default();
+    let engine_config = EngineConfigBuilder::default()
+        .with_record_auction_snapshots(true)
+        .build();
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, None);
 
     builder.run_genesis(&run_genesis_request);
 
@@ -655,6 +664,61 @@ fn should_calculate_era_validators() {
 
     // Make sure looked up era validators are different than initial era validators
     assert_ne!(era_validators_result, first_validator_weights);
+
+    // The new era's validator set is also recorded in an era-indexed auction snapshot
+    let auction_snapshot = builder
+        .query(None, Key::AuctionSnapshot(lookup_era_id), &[])
+        .expect("should have auction snapshot")
+        .as_auction_snapshot()
+        .cloned()
+        .expect("should be auction snapshot");
+    let snapshot_weights: ValidatorWeights = auction_snapshot
+        .validators()
+        .iter()
+        .map(|validator| {
+            (
+                validator.validator_public_key().clone(),
+                *validator.weight(),
+            )
+        })
+        .collect();
+    assert_eq!(snapshot_weights, *validator_weights);
+    let bid_account_snapshot = auction_snapshot
+        .validator(&BID_ACCOUNT_1_PK)
+        .expect("should have bid account in snapshot");
+    assert_eq!(bid_account_snapshot.delegated_amount(), &U512::zero());
+    assert_eq!(bid_account_snapshot.delegator_count(), 0);
+    assert_eq!(
+        bid_account_snapshot.delegation_rate(),
+        ADD_BID_DELEGATION_RATE_1
+    );
+
+    // Snapshots are only recorded when running the auction, not at genesis
+    assert!(builder
+        .query(None, Key::AuctionSnapshot(INITIAL_ERA_ID), &[])
+        .is_err());
+}
+
+#[ignore]
+#[test]
+fn should_not_record_auction_snapshots_unless_enabled() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    builder.run_auction(
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS,
+        Vec::new(),
+    );
+
+    let era_validators: EraValidators = builder.get_era_validators();
+    let (last_era, _) = era_validators
+        .iter()
+        .max()
+        .expect("should have era validators");
+    assert!(builder
+        .query(None, Key::AuctionSnapshot(*last_era), &[])
+        .is_err());
 }
 
 #[ignore]
@@ -3613,6 +3677,93 @@ fn should_enforce_minimum_delegation_amount() {
         if auction_error == AuctionError::DelegationAmountTooSmall as u8));
 }
 
+#[ignore]
+#[test]
+fn should_enforce_minimum_bid_amount() {
+    const MINIMUM_BID_AMOUNT: u64 = ADD_BID_AMOUNT_1;
+
+    let engine_config = EngineConfigBuilder::default()
+        .with_minimum_bid_amount(MINIMUM_BID_AMOUNT)
+        .build();
+    let global_state = InMemoryGlobalState::empty().expect("should create global state");
+    let mut builder = InMemoryWasmTestBuilder::new(global_state, engine_config, None);
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+    builder
+        .exec(transfer_to_validator_1)
+        .expect_success()
+        .commit();
+
+    let add_bid_request = |amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            CONTRACT_ADD_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_AMOUNT => U512::from(amount),
+                ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+            },
+        )
+        .build()
+    };
+    let withdraw_bid_request = |amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            CONTRACT_WITHDRAW_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_AMOUNT => U512::from(amount),
+            },
+        )
+        .build()
+    };
+
+    // A new bid below the minimum bid amount fails.
+    builder
+        .exec(add_bid_request(MINIMUM_BID_AMOUNT - 1))
+        .expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::BidAmountTooSmall as u8));
+
+    builder
+        .exec(add_bid_request(MINIMUM_BID_AMOUNT))
+        .expect_success()
+        .commit();
+
+    // A partial withdrawal leaving less than the minimum bid amount staked fails.
+    builder.exec(withdraw_bid_request(1)).expect_failure();
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::BidAmountTooSmall as u8));
+
+    // Withdrawing the whole bid is allowed.
+    builder
+        .exec(withdraw_bid_request(MINIMUM_BID_AMOUNT))
+        .expect_success()
+        .commit();
+    let bids: Bids = builder.get_bids();
+    let bid = bids
+        .get(&NON_FOUNDER_VALIDATOR_1_PK)
+        .expect("should have bid");
+    assert!(bid.staked_amount().is_zero());
+    assert!(bid.inactive());
+}
+
 #[ignore]
 #[test]
 fn should_allow_delegations_with_minimal_floor_amount() {
@@ -4368,3 +4519,555 @@ fn should_increase_existing_delegation_when_limit_exceeded() {
         .expect_success()
         .commit();
 }
+
+#[ignore]
+#[test]
+fn should_get_unbonding_purses_as_validator_and_delegator() {
+    const DELEGATOR_1_BID: u64 = 500_000;
+    const DELEGATOR_1_WITHDRAW_AMOUNT: u64 = 100_000;
+    const DELEGATOR_1_UNDELEGATE_AMOUNT: u64 = 200_000;
+
+    let fund_requests = vec![*SYSTEM_ADDR, *VALIDATOR_1_ADDR, *DELEGATOR_1_ADDR]
+        .into_iter()
+        .map(|target| {
+            ExecuteRequestBuilder::standard(
+                *DEFAULT_ACCOUNT_ADDR,
+                CONTRACT_TRANSFER_TO_ACCOUNT,
+                runtime_args! {
+                    ARG_TARGET => target,
+                    ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+                },
+            )
+            .build()
+        });
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+
+    // Delegator 1 is a validator in its own right as well as a delegator of validator 1.
+    let delegator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *DELEGATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATOR_1_BID),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => DELEGATOR_1.clone(),
+        },
+    )
+    .build();
+
+    let delegator_1_delegate_request = ExecuteRequestBuilder::standard(
+        *DELEGATOR_1_ADDR,
+        CONTRACT_DELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATOR_1_STAKE),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+        },
+    )
+    .build();
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let post_genesis_requests = fund_requests.chain(vec![
+        validator_1_add_bid_request,
+        delegator_1_add_bid_request,
+        delegator_1_delegate_request,
+    ]);
+    for request in post_genesis_requests {
+        builder.exec(request).commit().expect_success();
+    }
+
+    let mut timestamp_millis =
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
+    for _ in 0..5 {
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+
+    let delegator_1_withdraw_bid_request = ExecuteRequestBuilder::standard(
+        *DELEGATOR_1_ADDR,
+        CONTRACT_WITHDRAW_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => DELEGATOR_1.clone(),
+            ARG_AMOUNT => U512::from(DELEGATOR_1_WITHDRAW_AMOUNT),
+        },
+    )
+    .build();
+
+    let delegator_1_undelegate_request = ExecuteRequestBuilder::standard(
+        *DELEGATOR_1_ADDR,
+        CONTRACT_UNDELEGATE,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(DELEGATOR_1_UNDELEGATE_AMOUNT),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+        },
+    )
+    .build();
+
+    builder
+        .exec(delegator_1_withdraw_bid_request)
+        .commit()
+        .expect_success();
+    builder
+        .exec(delegator_1_undelegate_request)
+        .commit()
+        .expect_success();
+
+    // Validator 1 hasn't unbonded anything itself; its delegator's purse isn't reported.
+    assert!(builder.get_unbonding_purses(VALIDATOR_1.clone()).is_empty());
+
+    let unbonding_purses = builder.get_unbonding_purses(DELEGATOR_1.clone());
+    assert_eq!(unbonding_purses.len(), 2);
+    for entry in &unbonding_purses {
+        assert_eq!(entry.kind(), UnbondingPurseKind::Unbond);
+        assert_eq!(entry.unbonding_purse().unbonder_public_key(), &*DELEGATOR_1);
+        assert_eq!(
+            entry.release_era(),
+            entry
+                .unbonding_purse()
+                .era_of_creation()
+                .saturating_add(DEFAULT_UNBONDING_DELAY)
+        );
+    }
+
+    let as_validator = unbonding_purses
+        .iter()
+        .find(|entry| entry.unbonding_purse().is_validator())
+        .expect("should have unbonding purse as validator");
+    assert_eq!(
+        as_validator.unbonding_purse().amount(),
+        &U512::from(DELEGATOR_1_WITHDRAW_AMOUNT)
+    );
+
+    let as_delegator = unbonding_purses
+        .iter()
+        .find(|entry| !entry.unbonding_purse().is_validator())
+        .expect("should have unbonding purse as delegator");
+    assert_eq!(
+        as_delegator.unbonding_purse().validator_public_key(),
+        &*VALIDATOR_1
+    );
+    assert_eq!(
+        as_delegator.unbonding_purse().amount(),
+        &U512::from(DELEGATOR_1_UNDELEGATE_AMOUNT)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_compact_withdrawn_bid_once_unbonding_purses_are_paid_out() {
+    let validator_1_fund_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+
+    let validator_1_withdraw_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_WITHDRAW_BID,
+        runtime_args! {
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+        },
+    )
+    .build();
+
+    let mut timestamp_millis =
+        DEFAULT_GENESIS_TIMESTAMP_MILLIS + DEFAULT_LOCKED_FUNDS_PERIOD_MILLIS;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for request in [
+        validator_1_fund_request,
+        validator_1_add_bid_request,
+        validator_1_withdraw_bid_request,
+    ] {
+        builder.exec(request).commit().expect_success();
+    }
+
+    // The withdrawn bid is still referred to by the pending unbonding purse.
+    assert_matches!(
+        builder.commit_auction_compaction(),
+        AuctionCompactionResult::Success { reclaimed_keys, .. } if reclaimed_keys.is_empty()
+    );
+
+    for _ in 0..=DEFAULT_UNBONDING_DELAY {
+        builder.run_auction(timestamp_millis, Vec::new());
+        timestamp_millis += TIMESTAMP_MILLIS_INCREMENT;
+    }
+
+    let unbonding_purses: UnbondingPurses = builder.get_unbonds();
+    assert!(unbonding_purses
+        .get(&VALIDATOR_1_ADDR)
+        .expect("should have emptied unbonding purses")
+        .is_empty());
+
+    assert_matches!(
+        builder.commit_auction_compaction(),
+        AuctionCompactionResult::Success { reclaimed_keys, .. }
+            if reclaimed_keys == vec![Key::Unbond(*VALIDATOR_1_ADDR), Key::Bid(*VALIDATOR_1_ADDR)]
+    );
+
+    let unbonding_purses: UnbondingPurses = builder.get_unbonds();
+    assert!(!unbonding_purses.contains_key(&VALIDATOR_1_ADDR));
+    let bids: Bids = builder.get_bids();
+    assert!(!bids.contains_key(&VALIDATOR_1));
+}
+
+#[ignore]
+#[test]
+fn should_bulk_delegate_and_redelegate() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let fund_requests = [
+        *SYSTEM_ADDR,
+        *BID_ACCOUNT_1_ADDR,
+        *NON_FOUNDER_VALIDATOR_1_ADDR,
+        *NON_FOUNDER_VALIDATOR_2_ADDR,
+    ]
+    .iter()
+    .map(|target| {
+        ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => *target,
+                ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+            },
+        )
+        .build()
+    });
+    for request in fund_requests {
+        builder.exec(request).commit().expect_success();
+    }
+
+    for (account_hash, public_key, amount) in [
+        (
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            NON_FOUNDER_VALIDATOR_1_PK.clone(),
+            ADD_BID_AMOUNT_1,
+        ),
+        (
+            *NON_FOUNDER_VALIDATOR_2_ADDR,
+            NON_FOUNDER_VALIDATOR_2_PK.clone(),
+            ADD_BID_AMOUNT_2,
+        ),
+    ] {
+        let add_bid_request = ExecuteRequestBuilder::standard(
+            account_hash,
+            CONTRACT_ADD_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => public_key,
+                ARG_AMOUNT => U512::from(amount),
+                ARG_DELEGATION_RATE => ADD_BID_DELEGATION_RATE_1,
+            },
+        )
+        .build();
+        builder.exec(add_bid_request).commit().expect_success();
+    }
+
+    let auction_hash = builder.get_auction_contract_hash();
+    let bulk_delegate_request =
+        |delegations: BTreeMap<PublicKey, U512>,
+         redelegations: Vec<(PublicKey, PublicKey, U512)>| {
+            ExecuteRequestBuilder::contract_call_by_hash(
+                *BID_ACCOUNT_1_ADDR,
+                auction_hash,
+                auction::METHOD_BULK_DELEGATE,
+                runtime_args! {
+                    ARG_DELEGATOR => BID_ACCOUNT_1_PK.clone(),
+                    ARG_DELEGATIONS => delegations,
+                    ARG_REDELEGATIONS => redelegations,
+                },
+            )
+            .build()
+        };
+
+    // Delegate to both validators in a single deploy.
+    let mut delegations = BTreeMap::new();
+    delegations.insert(
+        NON_FOUNDER_VALIDATOR_1_PK.clone(),
+        U512::from(DELEGATE_AMOUNT_1),
+    );
+    delegations.insert(
+        NON_FOUNDER_VALIDATOR_2_PK.clone(),
+        U512::from(DELEGATE_AMOUNT_2),
+    );
+    builder
+        .exec(bulk_delegate_request(delegations, vec![]))
+        .commit()
+        .expect_success();
+
+    let bids_before: Bids = builder.get_bids();
+    assert_eq!(
+        *bids_before[&NON_FOUNDER_VALIDATOR_1_PK].delegators()[&BID_ACCOUNT_1_PK].staked_amount(),
+        U512::from(DELEGATE_AMOUNT_1)
+    );
+    assert_eq!(
+        *bids_before[&NON_FOUNDER_VALIDATOR_2_PK].delegators()[&BID_ACCOUNT_1_PK].staked_amount(),
+        U512::from(DELEGATE_AMOUNT_2)
+    );
+
+    // A single failing delegation reverts the delegations made before it.
+    let mut delegations = BTreeMap::new();
+    delegations.insert(
+        NON_FOUNDER_VALIDATOR_1_PK.clone(),
+        U512::from(DELEGATE_AMOUNT_1),
+    );
+    delegations.insert(DELEGATOR_1.clone(), U512::from(DELEGATE_AMOUNT_1));
+    builder
+        .exec(bulk_delegate_request(delegations, vec![]))
+        .commit()
+        .expect_failure();
+
+    let error = builder.get_error().expect("must get error");
+    assert!(matches!(
+        error,
+        Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+        if auction_error == AuctionError::ValidatorNotFound as u8));
+    assert_eq!(builder.get_bids(), bids_before);
+
+    builder.advance_eras_by_default_auction_delay(vec![], vec![]);
+
+    // Rebalance part of the delegation to validator 1 onto validator 2.
+    let redelegate_amount = U512::from(UNDELEGATE_AMOUNT_1 + DEFAULT_MINIMUM_DELEGATION_AMOUNT);
+    let redelegations = vec![(
+        NON_FOUNDER_VALIDATOR_1_PK.clone(),
+        NON_FOUNDER_VALIDATOR_2_PK.clone(),
+        redelegate_amount,
+    )];
+    builder
+        .exec(bulk_delegate_request(BTreeMap::new(), redelegations))
+        .commit()
+        .expect_success();
+
+    let unbonding_purses = builder
+        .get_unbonds()
+        .get(&NON_FOUNDER_VALIDATOR_1_ADDR)
+        .cloned()
+        .expect("must have purses");
+    assert_eq!(unbonding_purses.len(), 1);
+    assert_eq!(
+        unbonding_purses[0].new_validator(),
+        &Some(NON_FOUNDER_VALIDATOR_2_PK.clone())
+    );
+    assert_eq!(*unbonding_purses[0].amount(), redelegate_amount);
+
+    let bids: Bids = builder.get_bids();
+    assert_eq!(
+        *bids[&NON_FOUNDER_VALIDATOR_1_PK].delegators()[&BID_ACCOUNT_1_PK].staked_amount(),
+        U512::from(DELEGATE_AMOUNT_1) - redelegate_amount
+    );
+}
+
+#[ignore]
+#[test]
+fn should_limit_delegation_rate_increases() {
+    const MIN_ERAS_BETWEEN_INCREASES: u64 = 3;
+    const MAX_INCREASE: DelegationRate = 5;
+
+    let engine_config = EngineConfigBuilder::default()
+        .with_delegation_rate_change_limit(DelegationRateChangeLimit::Limited {
+            min_eras_between_increases: MIN_ERAS_BETWEEN_INCREASES,
+            max_increase: MAX_INCREASE,
+        })
+        .build();
+
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let transfer_to_validator_1 = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_ACCOUNT,
+        runtime_args! {
+            ARG_TARGET => *NON_FOUNDER_VALIDATOR_1_ADDR,
+            ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+        },
+    )
+    .build();
+    builder
+        .exec(transfer_to_validator_1)
+        .expect_success()
+        .commit();
+
+    let add_bid_request = |delegation_rate: DelegationRate| {
+        ExecuteRequestBuilder::standard(
+            *NON_FOUNDER_VALIDATOR_1_ADDR,
+            CONTRACT_ADD_BID,
+            runtime_args! {
+                ARG_PUBLIC_KEY => NON_FOUNDER_VALIDATOR_1_PK.clone(),
+                ARG_AMOUNT => U512::one(),
+                ARG_DELEGATION_RATE => delegation_rate,
+            },
+        )
+        .build()
+    };
+    let assert_limited = |builder: &mut InMemoryWasmTestBuilder| {
+        let error = builder.get_error().expect("must get error");
+        assert!(matches!(
+            error,
+            Error::Exec(execution::Error::Revert(ApiError::AuctionError(auction_error)))
+            if auction_error == AuctionError::DelegationRateChangeLimited as u8));
+    };
+    let delegation_rate = |builder: &mut InMemoryWasmTestBuilder| {
+        let bids: Bids = builder.get_bids();
+        *bids[&NON_FOUNDER_VALIDATOR_1_PK].delegation_rate()
+    };
+
+    // Creating a bid is not limited.
+    builder
+        .exec(add_bid_request(ADD_BID_DELEGATION_RATE_1))
+        .expect_success()
+        .commit();
+
+    // Raising the rate by more than the maximum increase fails.
+    builder
+        .exec(add_bid_request(
+            ADD_BID_DELEGATION_RATE_1 + MAX_INCREASE + 1,
+        ))
+        .expect_failure()
+        .commit();
+    assert_limited(&mut builder);
+
+    let raised_rate = ADD_BID_DELEGATION_RATE_1 + MAX_INCREASE;
+    builder
+        .exec(add_bid_request(raised_rate))
+        .expect_success()
+        .commit();
+    assert_eq!(delegation_rate(&mut builder), raised_rate);
+
+    // Raising the rate again is only possible once enough eras have passed.
+    builder.advance_eras_by(MIN_ERAS_BETWEEN_INCREASES - 1, vec![], vec![]);
+    builder
+        .exec(add_bid_request(raised_rate + 1))
+        .expect_failure()
+        .commit();
+    assert_limited(&mut builder);
+
+    // Lowering the rate is never limited.
+    builder
+        .exec(add_bid_request(ADD_BID_DELEGATION_RATE_1))
+        .expect_success()
+        .commit();
+    assert_eq!(delegation_rate(&mut builder), ADD_BID_DELEGATION_RATE_1);
+
+    builder.advance_era(vec![], vec![]);
+    builder
+        .exec(add_bid_request(raised_rate))
+        .expect_success()
+        .commit();
+    assert_eq!(delegation_rate(&mut builder), raised_rate);
+}
+
+#[ignore]
+#[test]
+fn should_filter_and_paginate_bids() {
+    let accounts = {
+        let mut tmp: Vec<GenesisAccount> = DEFAULT_ACCOUNTS.clone();
+        for (public_key, balance, bond) in [
+            (ACCOUNT_1_PK.clone(), ACCOUNT_1_BALANCE, ACCOUNT_1_BOND),
+            (ACCOUNT_2_PK.clone(), ACCOUNT_2_BALANCE, ACCOUNT_2_BOND),
+        ] {
+            tmp.push(GenesisAccount::account(
+                public_key,
+                Motes::new(balance.into()),
+                Some(GenesisValidator::new(
+                    Motes::new(bond.into()),
+                    DelegationRate::zero(),
+                )),
+            ));
+        }
+        tmp
+    };
+
+    let run_genesis_request = utils::create_run_genesis_request(accounts);
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+
+    builder.run_genesis(&run_genesis_request);
+
+    builder.advance_era(vec![], vec![EvictItem::new(ACCOUNT_2_PK.clone())]);
+
+    let all_bids = builder.get_bids();
+    assert_eq!(all_bids.len(), 2);
+
+    let state_hash = builder.get_post_state_hash();
+    let get_bids = |builder: &mut InMemoryWasmTestBuilder, request: GetBidsRequest| match builder
+        .get_bids_with_request(request)
+    {
+        GetBidsResult::Success {
+            bids,
+            continuation_token,
+        } => (bids, continuation_token),
+        GetBidsResult::RootNotFound => panic!("root should exist"),
+    };
+
+    let (bids, _) = get_bids(
+        &mut builder,
+        GetBidsRequest::new(state_hash).with_validators(vec![ACCOUNT_1_PK.clone()]),
+    );
+    assert_eq!(bids.keys().collect::<Vec<_>>(), vec![&*ACCOUNT_1_PK]);
+
+    let (bids, _) = get_bids(
+        &mut builder,
+        GetBidsRequest::new(state_hash).with_status(BidStatus::Active),
+    );
+    assert_eq!(bids.keys().collect::<Vec<_>>(), vec![&*ACCOUNT_1_PK]);
+
+    let (bids, _) = get_bids(
+        &mut builder,
+        GetBidsRequest::new(state_hash).with_status(BidStatus::Inactive),
+    );
+    assert_eq!(bids.keys().collect::<Vec<_>>(), vec![&*ACCOUNT_2_PK]);
+
+    // Paging through the bids one at a time returns each bid exactly once.
+    let (first_page, continuation_token) = get_bids(
+        &mut builder,
+        GetBidsRequest::new(state_hash).with_page_size(1),
+    );
+    assert_eq!(first_page.len(), 1);
+    let continuation_token = continuation_token.expect("should have another page");
+    let (second_page, continuation_token) = get_bids(
+        &mut builder,
+        GetBidsRequest::new(state_hash)
+            .with_page_size(1)
+            .with_continuation_token(continuation_token),
+    );
+    assert_eq!(second_page.len(), 1);
+    assert!(continuation_token.is_none());
+
+    let mut paged_bids = first_page;
+    paged_bids.extend(second_page);
+    assert_eq!(paged_bids, all_bids);
+}
@@ -21,7 +21,7 @@ use casper_types::{
     system::auction::{
         self, Bid, Bids, DelegationRate, Delegator, SeigniorageAllocation,
         SeigniorageRecipientsSnapshot, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
-        ARG_PUBLIC_KEY, ARG_REWARD_FACTORS, ARG_VALIDATOR, BLOCK_REWARD,
+        ARG_PUBLIC_KEY, ARG_REWARD_FACTORS, ARG_REWARD_PURSE, ARG_VALIDATOR, BLOCK_REWARD,
         DELEGATION_RATE_DENOMINATOR, METHOD_DISTRIBUTE, SEIGNIORAGE_RECIPIENTS_SNAPSHOT_KEY,
     },
     EraId, Key, ProtocolVersion, PublicKey, RuntimeArgs, SecretKey, U512,
@@ -4648,3 +4648,139 @@ fn delegator_full_unbond_during_first_reward_era() {
     let delegator = get_delegator_bid(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone());
     assert!(delegator.is_none());
 }
+
+#[ignore]
+#[test]
+fn should_pay_delegator_rewards_into_reward_purse() {
+    const VALIDATOR_1_STAKE: u64 = 1_000_000_000_000;
+    const DELEGATOR_1_STAKE: u64 = 1_000_000_000_000;
+    const DELEGATOR_2_STAKE: u64 = 1_000_000_000_000;
+
+    const VALIDATOR_1_DELEGATION_RATE: DelegationRate = DELEGATION_RATE_DENOMINATOR / 2;
+
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    for target in [
+        *SYSTEM_ADDR,
+        *VALIDATOR_1_ADDR,
+        *DELEGATOR_1_ADDR,
+        *DELEGATOR_2_ADDR,
+    ] {
+        let fund_request = ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_TO_ACCOUNT,
+            runtime_args! {
+                ARG_TARGET => target,
+                ARG_AMOUNT => U512::from(TRANSFER_AMOUNT)
+            },
+        )
+        .build();
+        builder.exec(fund_request).commit().expect_success();
+    }
+
+    let validator_1_add_bid_request = ExecuteRequestBuilder::standard(
+        *VALIDATOR_1_ADDR,
+        CONTRACT_ADD_BID,
+        runtime_args! {
+            ARG_AMOUNT => U512::from(VALIDATOR_1_STAKE),
+            ARG_DELEGATION_RATE => VALIDATOR_1_DELEGATION_RATE,
+            ARG_PUBLIC_KEY => VALIDATOR_1.clone(),
+        },
+    )
+    .build();
+    builder
+        .exec(validator_1_add_bid_request)
+        .commit()
+        .expect_success();
+
+    for (account_hash, public_key, amount) in [
+        (*DELEGATOR_1_ADDR, DELEGATOR_1.clone(), DELEGATOR_1_STAKE),
+        (*DELEGATOR_2_ADDR, DELEGATOR_2.clone(), DELEGATOR_2_STAKE),
+    ] {
+        let delegate_request = ExecuteRequestBuilder::standard(
+            account_hash,
+            CONTRACT_DELEGATE,
+            runtime_args! {
+                ARG_AMOUNT => U512::from(amount),
+                ARG_VALIDATOR => VALIDATOR_1.clone(),
+                ARG_DELEGATOR => public_key,
+            },
+        )
+        .build();
+        builder.exec(delegate_request).commit().expect_success();
+    }
+
+    // Delegator 1 has its rewards paid into its main purse rather than reinvested.
+    let delegator_1_main_purse = builder.get_expected_account(*DELEGATOR_1_ADDR).main_purse();
+    let set_reward_purse_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DELEGATOR_1_ADDR,
+        builder.get_auction_contract_hash(),
+        auction::METHOD_SET_DELEGATOR_REWARD_PURSE,
+        runtime_args! {
+            ARG_DELEGATOR => DELEGATOR_1.clone(),
+            ARG_VALIDATOR => VALIDATOR_1.clone(),
+            ARG_REWARD_PURSE => Some(delegator_1_main_purse),
+        },
+    )
+    .build();
+    builder
+        .exec(set_reward_purse_request)
+        .commit()
+        .expect_success();
+
+    for _ in 0..=builder.get_auction_delay() {
+        let step_request = StepRequestBuilder::new()
+            .with_parent_state_hash(builder.get_post_state_hash())
+            .with_protocol_version(ProtocolVersion::V1_0_0)
+            .with_next_era_id(builder.get_era().successor())
+            .with_run_auction(true)
+            .build();
+
+        builder
+            .step(step_request)
+            .expect("must execute step successfully");
+    }
+
+    let delegator_1_balance_before = builder.get_purse_balance(delegator_1_main_purse);
+
+    let step_request = StepRequestBuilder::new()
+        .with_parent_state_hash(builder.get_post_state_hash())
+        .with_protocol_version(ProtocolVersion::V1_0_0)
+        .with_reward_item(RewardItem::new(VALIDATOR_1.clone(), BLOCK_REWARD))
+        .with_next_era_id(builder.get_era())
+        .with_run_auction(true)
+        .build();
+
+    builder
+        .step(step_request)
+        .expect("must execute step successfully");
+
+    let era_info = builder
+        .query(None, Key::EraSummary, &[])
+        .expect("should have value")
+        .as_era_info()
+        .cloned()
+        .expect("should be era info");
+    let delegator_payout = |delegator: &PublicKey| match era_info.select(delegator.clone()).next() {
+        Some(SeigniorageAllocation::Delegator { amount, .. }) => *amount,
+        _ => panic!("should have delegator allocation"),
+    };
+    let delegator_1_payout = delegator_payout(&DELEGATOR_1);
+    let delegator_2_payout = delegator_payout(&DELEGATOR_2);
+    assert!(!delegator_1_payout.is_zero());
+    assert_eq!(delegator_1_payout, delegator_2_payout);
+
+    assert_eq!(
+        get_delegator_staked_amount(&mut builder, VALIDATOR_1.clone(), DELEGATOR_1.clone()),
+        U512::from(DELEGATOR_1_STAKE)
+    );
+    assert_eq!(
+        builder.get_purse_balance(delegator_1_main_purse),
+        delegator_1_balance_before + delegator_1_payout
+    );
+    assert_eq!(
+        get_delegator_staked_amount(&mut builder, VALIDATOR_1.clone(), DELEGATOR_2.clone()),
+        U512::from(DELEGATOR_2_STAKE) + delegator_2_payout
+    );
+}
@@ -1,12 +1,14 @@
 use casper_engine_test_support::{
     DeployItemBuilder, ExecuteRequestBuilder, InMemoryWasmTestBuilder, DEFAULT_ACCOUNT_ADDR,
-    DEFAULT_PAYMENT, MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
+    DEFAULT_GAS_PRICE, DEFAULT_PAYMENT, MINIMUM_ACCOUNT_CREATION_BALANCE,
+    PRODUCTION_RUN_GENESIS_REQUEST, SYSTEM_ADDR,
 };
+use casper_execution_engine::core::engine_state::ExecutionResult;
 use casper_types::{
     account::{Account, AccountHash},
     runtime_args,
     system::handle_payment,
-    Key, RuntimeArgs, URef, U512,
+    Gas, Key, Motes, RuntimeArgs, URef, U512,
 };
 
 const CONTRACT_FINALIZE_PAYMENT: &str = "finalize_payment.wasm";
@@ -177,6 +179,45 @@ fn finalize_payment_should_refund_to_specified_purse() {
     );
 }
 
+#[ignore]
+#[test]
+fn finalize_payment_should_record_payment_summary() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let exec_request = ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CREATE_PURSE_01,
+        runtime_args! { ARG_PURSE_NAME => LOCAL_REFUND_PURSE },
+    )
+    .build();
+    builder.exec(exec_request).expect_success().commit();
+
+    let exec_results = builder
+        .get_last_exec_results()
+        .expect("should have exec results");
+    let (cost, payment_summary) = match &*exec_results[0] {
+        ExecutionResult::Success {
+            cost,
+            payment_summary,
+            ..
+        } => (*cost, payment_summary.expect("should have payment summary")),
+        ExecutionResult::Failure { error, .. } => panic!("unexpected failure: {}", error),
+    };
+
+    assert_eq!(
+        payment_summary.limit,
+        Gas::from_motes(Motes::new(*DEFAULT_PAYMENT), DEFAULT_GAS_PRICE).unwrap()
+    );
+    assert_eq!(payment_summary.gas_price, DEFAULT_GAS_PRICE);
+    assert_eq!(payment_summary.paid_amount, Motes::new(*DEFAULT_PAYMENT));
+
+    // The default refund handling refunds 99% of the unspent payment.
+    let spent = Motes::from_gas(cost, DEFAULT_GAS_PRICE).unwrap().value();
+    let expected_refund = (*DEFAULT_PAYMENT - spent) * 99 / 100;
+    assert_eq!(payment_summary.refund, Motes::new(expected_refund));
+}
+
 // ------------- utility functions -------------------- //
 
 fn get_handle_payment_payment_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
@@ -0,0 +1,394 @@
+use casper_engine_test_support::{
+    ExecuteRequestBuilder, InMemoryWasmTestBuilder, UpgradeRequestBuilder, DEFAULT_ACCOUNT_ADDR,
+    MINIMUM_ACCOUNT_CREATION_BALANCE, PRODUCTION_RUN_GENESIS_REQUEST,
+};
+use casper_execution_engine::{
+    core::{
+        engine_state::{Error, ExecuteRequest},
+        execution,
+    },
+    shared::transform::Transform,
+};
+use casper_types::{
+    account::AccountHash, bytesrepr::ToBytes, crypto, runtime_args, system::mint, ApiError, Burn,
+    EraId, Key, ProtocolVersion, RuntimeArgs, StoredValue, URef, URefAddr, U512,
+};
+
+const PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion::V1_0_0;
+const DEFAULT_ACTIVATION_POINT: EraId = EraId::new(1);
+const ACCOUNT_1_ADDR: AccountHash = AccountHash::new([201; 32]);
+const CONTRACT_TRANSFER_TO_NAMED_PURSE: &str = "transfer_to_named_purse.wasm";
+const ARG_PURSE_NAME: &str = "purse_name";
+const ARG_AMOUNT: &str = "amount";
+const NAMED_PURSE_NAME: &str = "purse_1";
+
+fn transfer_request() -> ExecuteRequest {
+    ExecuteRequestBuilder::transfer(
+        *DEFAULT_ACCOUNT_ADDR,
+        runtime_args! {
+            mint::ARG_TARGET => ACCOUNT_1_ADDR,
+            mint::ARG_AMOUNT => U512::from(MINIMUM_ACCOUNT_CREATION_BALANCE),
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build()
+}
+
+fn upgrade_mint_paused(
+    builder: &mut InMemoryWasmTestBuilder,
+    current_protocol_version: ProtocolVersion,
+    mint_paused: bool,
+) -> ProtocolVersion {
+    let sem_ver = current_protocol_version.value();
+    let new_protocol_version =
+        ProtocolVersion::from_parts(sem_ver.major, sem_ver.minor, sem_ver.patch + 1);
+
+    let mut upgrade_request = UpgradeRequestBuilder::new()
+        .with_current_protocol_version(current_protocol_version)
+        .with_new_protocol_version(new_protocol_version)
+        .with_activation_point(DEFAULT_ACTIVATION_POINT)
+        .with_new_mint_paused(mint_paused)
+        .build();
+
+    builder
+        .upgrade_with_upgrade_request_and_config(None, &mut upgrade_request)
+        .expect_upgrade_success();
+
+    new_protocol_version
+}
+
+fn assert_mint_error(builder: &InMemoryWasmTestBuilder, expected: mint::Error) {
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(
+            error,
+            Error::Exec(execution::Error::Revert(ApiError::Mint(mint_error)))
+            if mint_error == expected as u8
+        ),
+        "expected {:?}, found {:?}",
+        expected,
+        error
+    );
+}
+
+#[ignore]
+#[test]
+fn should_pause_and_resume_transfers_via_upgrade() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let protocol_version = upgrade_mint_paused(&mut builder, PROTOCOL_VERSION, true);
+
+    builder.exec(transfer_request()).expect_failure().commit();
+    assert_mint_error(&builder, mint::Error::MintPaused);
+
+    upgrade_mint_paused(&mut builder, protocol_version, false);
+
+    builder.exec(transfer_request()).expect_success().commit();
+}
+
+#[ignore]
+#[test]
+fn should_not_allow_user_to_pause_mint() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let pause_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        builder.get_mint_contract_hash(),
+        mint::METHOD_SET_MINT_PAUSED,
+        runtime_args! {
+            mint::ARG_PAUSED => true,
+        },
+    )
+    .build();
+
+    // The mint's error is returned to the caller rather than reverting the deploy.
+    builder.exec(pause_request).expect_success().commit();
+
+    let mint_paused_key = builder
+        .get_contract(builder.get_mint_contract_hash())
+        .expect("mint should exist")
+        .named_keys()[mint::MINT_PAUSED_KEY];
+    let mint_paused: bool = builder
+        .query(None, mint_paused_key, &[])
+        .expect("should have mint paused flag")
+        .as_cl_value()
+        .expect("should be a CLValue")
+        .clone()
+        .into_t()
+        .expect("should be bool");
+    assert!(!mint_paused);
+
+    builder.exec(transfer_request()).expect_success().commit();
+}
+
+fn burn_request(builder: &InMemoryWasmTestBuilder, amount: U512) -> ExecuteRequest {
+    let main_purse = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .main_purse();
+    ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        builder.get_mint_contract_hash(),
+        mint::METHOD_BURN,
+        runtime_args! {
+            mint::ARG_PURSE => main_purse,
+            mint::ARG_AMOUNT => amount,
+        },
+    )
+    .build()
+}
+
+#[ignore]
+#[test]
+fn should_burn_from_own_purse() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let burn_amount = U512::from(1_000_000);
+    let total_supply_before = builder.total_supply(None);
+
+    let request = burn_request(&builder, burn_amount);
+    builder.exec(request).expect_success().commit();
+
+    assert_eq!(
+        builder.total_supply(None),
+        total_supply_before - burn_amount
+    );
+
+    let journal = builder
+        .get_execution_journals()
+        .pop()
+        .expect("should have execution journal");
+    let burns: Vec<Burn> = journal
+        .into_iter()
+        .filter_map(|(key, transform)| match (key, transform) {
+            (Key::Burn(_), Transform::Write(StoredValue::Burn(burn))) => Some(burn),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(burns.len(), 1, "the burn should be recorded");
+    assert_eq!(*burns[0].from(), *DEFAULT_ACCOUNT_ADDR);
+    assert_eq!(
+        burns[0].purse().addr(),
+        builder
+            .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+            .main_purse()
+            .addr()
+    );
+    assert_eq!(*burns[0].amount(), burn_amount);
+}
+
+#[ignore]
+#[test]
+fn should_not_burn_more_than_purse_balance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let total_supply_before = builder.total_supply(None);
+
+    let request = burn_request(&builder, U512::MAX);
+    // The mint's error is returned to the caller rather than reverting the deploy.
+    builder.exec(request).expect_success().commit();
+
+    assert_eq!(builder.total_supply(None), total_supply_before);
+}
+
+fn transfer_from_request(
+    builder: &InMemoryWasmTestBuilder,
+    source_addr: URefAddr,
+    amount: U512,
+) -> ExecuteRequest {
+    let target = builder.get_expected_account(ACCOUNT_1_ADDR).main_purse();
+    ExecuteRequestBuilder::contract_call_by_hash(
+        ACCOUNT_1_ADDR,
+        builder.get_mint_contract_hash(),
+        mint::METHOD_TRANSFER_FROM,
+        runtime_args! {
+            mint::ARG_SOURCE => source_addr,
+            mint::ARG_TARGET => target,
+            mint::ARG_AMOUNT => amount,
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build()
+}
+
+fn query_allowance(builder: &InMemoryWasmTestBuilder, purse_addr: URefAddr, spender: Key) -> U512 {
+    let allowances_uref = builder
+        .get_contract(builder.get_mint_contract_hash())
+        .expect("mint should exist")
+        .named_keys()[mint::ALLOWANCES_KEY]
+        .into_uref()
+        .expect("should be uref");
+    let item_key = base16::encode_lower(&crypto::blake2b(
+        (purse_addr, spender).to_bytes().expect("should serialize"),
+    ));
+    builder
+        .query(
+            None,
+            Key::dictionary(allowances_uref, item_key.as_bytes()),
+            &[],
+        )
+        .expect("should have allowance")
+        .as_cl_value()
+        .expect("should be a CLValue")
+        .clone()
+        .into_t()
+        .expect("should be U512")
+}
+
+#[ignore]
+#[test]
+fn should_transfer_from_purse_within_allowance() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder.exec(transfer_request()).expect_success().commit();
+
+    let main_purse = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .main_purse();
+    let spender = Key::Account(ACCOUNT_1_ADDR);
+
+    let approve_request = ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        builder.get_mint_contract_hash(),
+        mint::METHOD_APPROVE,
+        runtime_args! {
+            mint::ARG_PURSE => main_purse,
+            mint::ARG_SPENDER => spender,
+            mint::ARG_AMOUNT => U512::from(1_000_000),
+        },
+    )
+    .build();
+    builder.exec(approve_request).expect_success().commit();
+    assert_eq!(
+        query_allowance(&builder, main_purse.addr(), spender),
+        U512::from(1_000_000)
+    );
+
+    let request = transfer_from_request(&builder, main_purse.addr(), U512::from(400_000));
+    builder.exec(request).expect_success().commit();
+    assert_eq!(
+        query_allowance(&builder, main_purse.addr(), spender),
+        U512::from(600_000)
+    );
+
+    // The mint's error is returned to the caller rather than reverting the deploy.
+    let request = transfer_from_request(&builder, main_purse.addr(), U512::from(700_000));
+    builder.exec(request).expect_success().commit();
+    assert_eq!(
+        query_allowance(&builder, main_purse.addr(), spender),
+        U512::from(600_000)
+    );
+}
+
+#[ignore]
+#[test]
+fn should_record_total_supply_at_each_switch_block() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let historical_total_supply_uref = builder
+        .get_contract(builder.get_mint_contract_hash())
+        .expect("mint should exist")
+        .named_keys()[mint::HISTORICAL_TOTAL_SUPPLY_KEY]
+        .into_uref()
+        .expect("should be uref");
+
+    for _ in 0..3 {
+        builder.advance_era(Vec::new(), Vec::new());
+
+        let era_id = builder.get_era();
+        let item_key = mint::historical_total_supply_item_key(era_id);
+        let recorded_total_supply: U512 = builder
+            .query(
+                None,
+                Key::dictionary(historical_total_supply_uref, item_key.as_bytes()),
+                &[],
+            )
+            .expect("should have total supply of era")
+            .as_cl_value()
+            .expect("should be a CLValue")
+            .clone()
+            .into_t()
+            .expect("should be U512");
+        assert_eq!(recorded_total_supply, builder.total_supply(None));
+    }
+}
+
+fn create_named_purse_request() -> ExecuteRequest {
+    ExecuteRequestBuilder::standard(
+        *DEFAULT_ACCOUNT_ADDR,
+        CONTRACT_TRANSFER_TO_NAMED_PURSE,
+        runtime_args! {
+            ARG_PURSE_NAME => NAMED_PURSE_NAME,
+            ARG_AMOUNT => U512::zero(),
+        },
+    )
+    .build()
+}
+
+fn transfer_batch_request(builder: &InMemoryWasmTestBuilder, amounts: &[U512]) -> ExecuteRequest {
+    let account = builder.get_expected_account(*DEFAULT_ACCOUNT_ADDR);
+    let target = account.named_keys()[NAMED_PURSE_NAME]
+        .into_uref()
+        .expect("should be uref");
+    let transfers: Vec<(Option<AccountHash>, URef, U512)> = amounts
+        .iter()
+        .map(|amount| (None, target, *amount))
+        .collect();
+    ExecuteRequestBuilder::contract_call_by_hash(
+        *DEFAULT_ACCOUNT_ADDR,
+        builder.get_mint_contract_hash(),
+        mint::METHOD_TRANSFER_BATCH,
+        runtime_args! {
+            mint::ARG_SOURCE => account.main_purse(),
+            mint::ARG_TRANSFERS => transfers,
+            mint::ARG_ID => <Option<u64>>::None,
+        },
+    )
+    .build()
+}
+
+fn named_purse_balance(builder: &InMemoryWasmTestBuilder) -> U512 {
+    let purse = builder
+        .get_expected_account(*DEFAULT_ACCOUNT_ADDR)
+        .named_keys()[NAMED_PURSE_NAME]
+        .into_uref()
+        .expect("should be uref");
+    builder.get_purse_balance(purse)
+}
+
+#[ignore]
+#[test]
+fn should_make_all_transfers_of_a_batch() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder
+        .exec(create_named_purse_request())
+        .expect_success()
+        .commit();
+
+    let request = transfer_batch_request(&builder, &[U512::from(100), U512::from(200)]);
+    builder.exec(request).expect_success().commit();
+
+    assert_eq!(named_purse_balance(&builder), U512::from(300));
+}
+
+#[ignore]
+#[test]
+fn should_make_no_transfer_of_a_batch_with_an_invalid_transfer() {
+    let mut builder = InMemoryWasmTestBuilder::default();
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+    builder
+        .exec(create_named_purse_request())
+        .expect_success()
+        .commit();
+
+    // The results of the transfers are returned to the caller rather than reverting the deploy.
+    let request = transfer_batch_request(&builder, &[U512::from(100), U512::MAX]);
+    builder.exec(request).expect_success().commit();
+
+    assert_eq!(named_purse_balance(&builder), U512::zero());
+}
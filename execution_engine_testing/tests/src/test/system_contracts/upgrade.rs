@@ -27,7 +27,10 @@ use casper_execution_engine::{
             DEFAULT_UNREACHABLE_COST,
         },
         storage_costs::StorageCosts,
-        wasm_config::{WasmConfig, DEFAULT_MAX_STACK_HEIGHT, DEFAULT_WASM_MAX_MEMORY},
+        wasm_config::{
+            WasmConfig, DEFAULT_MAX_INDIRECT_CALL_SIGNATURES, DEFAULT_MAX_STACK_HEIGHT,
+            DEFAULT_MAX_TABLE_ELEMENTS, DEFAULT_MAX_TABLE_SIZE, DEFAULT_WASM_MAX_MEMORY,
+        },
     },
 };
 use casper_types::{
@@ -87,6 +90,9 @@ fn get_upgraded_wasm_config() -> WasmConfig {
     WasmConfig::new(
         DEFAULT_WASM_MAX_MEMORY,
         DEFAULT_MAX_STACK_HEIGHT * 2,
+        DEFAULT_MAX_TABLE_SIZE,
+        DEFAULT_MAX_TABLE_ELEMENTS,
+        DEFAULT_MAX_INDIRECT_CALL_SIGNATURES,
         opcode_cost,
         storage_costs,
         host_function_costs,
@@ -8,8 +8,8 @@ use casper_engine_test_support::{
 use casper_execution_engine::{
     core::{
         engine_state::{
-            engine_config::DEFAULT_MAX_ASSOCIATED_KEYS, EngineConfigBuilder, Error as CoreError,
-            WASMLESS_TRANSFER_FIXED_GAS_PRICE,
+            engine_config::{AccountCreationPolicy, DEFAULT_MAX_ASSOCIATED_KEYS},
+            EngineConfigBuilder, Error as CoreError, WASMLESS_TRANSFER_FIXED_GAS_PRICE,
         },
         execution::Error as ExecError,
     },
@@ -1054,3 +1054,131 @@ fn transfer_wasmless_should_observe_upgraded_cost() {
         default_account_balance_before - default_account_balance_after - transfer_amount
     );
 }
+
+#[ignore]
+#[test]
+fn transfer_should_observe_account_creation_policy() {
+    const MINIMUM_AMOUNT: u64 = 2000;
+
+    let engine_config = EngineConfigBuilder::default()
+        .with_account_creation_policy(AccountCreationPolicy::AllowedAboveMinimum {
+            minimum_amount: MINIMUM_AMOUNT,
+        })
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let wasm_transfer_request = |target: AccountHash, amount: u64| {
+        ExecuteRequestBuilder::standard(
+            *DEFAULT_ACCOUNT_ADDR,
+            CONTRACT_TRANSFER_PURSE_TO_ACCOUNT,
+            runtime_args! {
+                mint::ARG_TARGET => target,
+                mint::ARG_AMOUNT => U512::from(amount),
+                mint::ARG_ID => <Option<u64>>::None
+            },
+        )
+        .build()
+    };
+    let wasmless_transfer_request = |target: AccountHash, amount: u64| {
+        let deploy_item = DeployItemBuilder::new()
+            .with_address(*DEFAULT_ACCOUNT_ADDR)
+            .with_empty_payment_bytes(runtime_args! {})
+            .with_transfer_args(runtime_args! {
+                mint::ARG_TARGET => target,
+                mint::ARG_AMOUNT => U512::from(amount),
+                mint::ARG_ID => <Option<u64>>::None
+            })
+            .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+            .with_deploy_hash([amount as u8; 32])
+            .build();
+        ExecuteRequestBuilder::from_deploy_item(deploy_item).build()
+    };
+    let assert_amount_too_low = |builder: &InMemoryWasmTestBuilder| {
+        let error = builder.get_error().expect("should have error");
+        assert!(
+            matches!(
+                error,
+                CoreError::Exec(ExecError::AccountCreationAmountTooLow {
+                    minimum_amount: MINIMUM_AMOUNT,
+                    ..
+                })
+            ),
+            "expected AccountCreationAmountTooLow error, found {:?}",
+            error
+        );
+    };
+
+    // Transfers below the minimum amount do not create their target account.
+    builder
+        .exec(wasm_transfer_request(*ACCOUNT_1_ADDR, MINIMUM_AMOUNT - 1))
+        .expect_failure()
+        .commit();
+    assert_amount_too_low(&builder);
+    builder
+        .exec(wasmless_transfer_request(
+            *ACCOUNT_1_ADDR,
+            MINIMUM_AMOUNT - 1,
+        ))
+        .expect_failure()
+        .commit();
+    assert_amount_too_low(&builder);
+    assert_eq!(builder.get_account(*ACCOUNT_1_ADDR), None);
+
+    builder
+        .exec(wasm_transfer_request(*ACCOUNT_1_ADDR, MINIMUM_AMOUNT))
+        .expect_success()
+        .commit();
+    builder
+        .exec(wasmless_transfer_request(*ACCOUNT_2_ADDR, MINIMUM_AMOUNT))
+        .expect_success()
+        .commit();
+    assert!(builder.get_account(*ACCOUNT_2_ADDR).is_some());
+
+    // Transfers to existing accounts are not restricted.
+    builder
+        .exec(wasmless_transfer_request(*ACCOUNT_1_ADDR, 1))
+        .expect_success()
+        .commit();
+    let account_1 = builder
+        .get_account(*ACCOUNT_1_ADDR)
+        .expect("account 1 should exist");
+    assert_eq!(
+        builder.get_purse_balance(account_1.main_purse()),
+        U512::from(MINIMUM_AMOUNT + 1)
+    );
+}
+
+#[ignore]
+#[test]
+fn transfer_should_not_create_account_when_disallowed() {
+    let engine_config = EngineConfigBuilder::default()
+        .with_account_creation_policy(AccountCreationPolicy::Disallowed)
+        .build();
+    let mut builder = InMemoryWasmTestBuilder::new_with_config(engine_config);
+    builder.run_genesis(&PRODUCTION_RUN_GENESIS_REQUEST);
+
+    let deploy_item = DeployItemBuilder::new()
+        .with_address(*DEFAULT_ACCOUNT_ADDR)
+        .with_empty_payment_bytes(runtime_args! {})
+        .with_transfer_args(runtime_args! {
+            mint::ARG_TARGET => *ACCOUNT_1_ADDR,
+            mint::ARG_AMOUNT => *DEFAULT_PAYMENT,
+            mint::ARG_ID => <Option<u64>>::None
+        })
+        .with_authorization_keys(&[*DEFAULT_ACCOUNT_ADDR])
+        .with_deploy_hash([42; 32])
+        .build();
+    builder
+        .exec(ExecuteRequestBuilder::from_deploy_item(deploy_item).build())
+        .expect_failure()
+        .commit();
+
+    let error = builder.get_error().expect("should have error");
+    assert!(
+        matches!(error, CoreError::Exec(ExecError::DisabledAccountCreation)),
+        "expected DisabledAccountCreation error, found {:?}",
+        error
+    );
+    assert_eq!(builder.get_account(*ACCOUNT_1_ADDR), None);
+}
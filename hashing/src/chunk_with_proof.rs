@@ -48,13 +48,8 @@ impl FromBytes for ChunkWithProof {
 }
 
 impl ChunkWithProof {
-    #[cfg(test)]
-    /// 10 bytes for testing purposes.
-    pub const CHUNK_SIZE_BYTES: usize = 10;
-
-    #[cfg(not(test))]
-    /// 8 MiB
-    pub const CHUNK_SIZE_BYTES: usize = 8 * 1024 * 1024;
+    /// The size of a chunk: 8 MiB, or 10 bytes for testing purposes.
+    pub const CHUNK_SIZE_BYTES: usize = crate::CHUNK_SIZE_BYTES;
 
     /// Constructs the [`ChunkWithProof`] that contains the chunk of data with the appropriate index
     /// and the cryptographic proof.
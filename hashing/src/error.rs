@@ -1,21 +1,42 @@
 //! Errors in constructing and validating indexed Merkle proofs, chunks with indexed Merkle proofs.
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(feature = "std")]
 use casper_types::bytesrepr;
 
-use crate::{ChunkWithProof, Digest};
+#[cfg(feature = "std")]
+use crate::ChunkWithProof;
+use crate::Digest;
 
 /// Possible hashing errors.
-#[derive(Debug, thiserror::Error)]
+#[derive(Debug)]
 #[non_exhaustive]
 pub enum Error {
-    #[error("Incorrect digest length {0}, expected length {}.", Digest::LENGTH)]
     /// The digest length was an incorrect size.
     IncorrectDigestLength(usize),
     /// There was a decoding error.
-    #[error("Base16 decode error {0}.")]
     Base16DecodeError(base16::DecodeError),
 }
 
+impl Display for Error {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::IncorrectDigestLength(length) => write!(
+                formatter,
+                "Incorrect digest length {}, expected length {}.",
+                length,
+                Digest::LENGTH
+            ),
+            Error::Base16DecodeError(error) => write!(formatter, "Base16 decode error {}.", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
 /// Error validating a Merkle proof of a chunk.
+#[cfg(feature = "std")]
 #[derive(thiserror::Error, Debug, PartialEq, Eq)]
 #[non_exhaustive]
 pub enum MerkleVerificationError {
@@ -47,6 +68,7 @@ pub enum MerkleVerificationError {
 }
 
 /// Error validating a chunk with proof.
+#[cfg(feature = "std")]
 #[derive(thiserror::Error, Debug)]
 #[non_exhaustive]
 pub enum ChunkWithProofVerificationError {
@@ -82,6 +104,7 @@ pub enum ChunkWithProofVerificationError {
 }
 
 /// Error during the construction of a Merkle proof.
+#[cfg(feature = "std")]
 #[derive(thiserror::Error, Debug, Eq, PartialEq, Clone)]
 #[non_exhaustive]
 pub enum MerkleConstructionError {
@@ -1,4 +1,8 @@
 //! A library providing hashing functionality including Merkle Proof utilities.
+//!
+//! Without the default `std` feature, the crate is `no_std` and provides only [`Digest`] and its
+//! errors, for use by light clients verifying hashes in constrained environments.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
 #![doc(html_root_url = "https://docs.rs/casper-hashing/3.0.0")]
 #![doc(
     html_favicon_url = "https://raw.githubusercontent.com/casper-network/casper-node/blob/dev/images/Casper_Logo_Favicon_48.png",
@@ -7,13 +11,17 @@
 )]
 #![warn(missing_docs)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
 mod chunk_with_proof;
 mod error;
+#[cfg(feature = "std")]
 mod indexed_merkle_proof;
 
-use std::{
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use core::{
     array::TryFromSliceError,
-    collections::BTreeMap,
     convert::{TryFrom, TryInto},
     fmt::{self, Debug, Display, Formatter, LowerHex, UpperHex},
 };
@@ -22,12 +30,15 @@ use blake2::{
     digest::{Update, VariableOutput},
     VarBlake2b,
 };
+#[cfg(feature = "std")]
 use datasize::DataSize;
 use hex_fmt::HexFmt;
 use itertools::Itertools;
+#[cfg(feature = "std")]
 use once_cell::sync::OnceCell;
 #[cfg(test)]
 use rand::{distributions::Standard, prelude::Distribution, Rng};
+#[cfg(feature = "std")]
 use schemars::JsonSchema;
 use serde::{de::Error as SerdeError, Deserialize, Deserializer, Serialize, Serializer};
 
@@ -35,19 +46,37 @@ use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     checksummed_hex, CLType, CLTyped,
 };
+#[cfg(feature = "std")]
 pub use chunk_with_proof::ChunkWithProof;
+pub use error::Error;
+#[cfg(feature = "std")]
 pub use error::{
-    ChunkWithProofVerificationError, Error, MerkleConstructionError, MerkleVerificationError,
+    ChunkWithProofVerificationError, MerkleConstructionError, MerkleVerificationError,
 };
+#[cfg(feature = "std")]
 pub use indexed_merkle_proof::IndexedMerkleProof;
 
 /// The output of the hash function.
-#[derive(Copy, Clone, DataSize, Ord, PartialOrd, Eq, PartialEq, Hash, Default, JsonSchema)]
-#[serde(deny_unknown_fields)]
-#[schemars(with = "String", description = "Hex-encoded hash digest.")]
-pub struct Digest(#[schemars(skip, with = "String")] [u8; Digest::LENGTH]);
+#[derive(Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Default)]
+#[cfg_attr(feature = "std", derive(DataSize, JsonSchema))]
+#[cfg_attr(feature = "std", serde(deny_unknown_fields))]
+#[cfg_attr(
+    feature = "std",
+    schemars(with = "String", description = "Hex-encoded hash digest.")
+)]
+pub struct Digest(
+    #[cfg_attr(feature = "std", schemars(skip, with = "String"))] [u8; Digest::LENGTH],
+);
+
+#[cfg(test)]
+/// 10 bytes for testing purposes.
+pub(crate) const CHUNK_SIZE_BYTES: usize = 10;
+
+#[cfg(not(test))]
+/// 8 MiB
+pub(crate) const CHUNK_SIZE_BYTES: usize = 8 * 1024 * 1024;
 
-const CHUNK_DATA_ZEROED: &[u8] = &[0u8; ChunkWithProof::CHUNK_SIZE_BYTES];
+const CHUNK_DATA_ZEROED: &[u8] = &[0u8; CHUNK_SIZE_BYTES];
 
 impl Digest {
     /// The number of bytes in a `Digest`.
@@ -98,19 +127,20 @@ impl Digest {
     /// longer item's Merkle tree root)`.
     ///
     /// This function computes the correct final hash by ensuring the hasher used has been
-    /// initialized with padding before. For efficiency reasons it uses a memoized hasher state
-    /// computed on first run and cloned afterwards.
+    /// initialized with padding before. For efficiency reasons it uses, with the `std` feature, a
+    /// memoized hasher state computed on first run and cloned afterwards.
     fn hash_merkle_root(leaf_count: u64, root: Digest) -> Digest {
-        static PAIR_PREFIX_HASHER: OnceCell<VarBlake2b> = OnceCell::new();
+        #[cfg(feature = "std")]
+        let mut hasher = {
+            static PAIR_PREFIX_HASHER: OnceCell<VarBlake2b> = OnceCell::new();
+            PAIR_PREFIX_HASHER
+                .get_or_init(Digest::pair_prefix_hasher)
+                .clone()
+        };
+        #[cfg(not(feature = "std"))]
+        let mut hasher = Digest::pair_prefix_hasher();
 
         let mut result = [0; Digest::LENGTH];
-        let mut hasher = PAIR_PREFIX_HASHER
-            .get_or_init(|| {
-                let mut hasher = VarBlake2b::new(Digest::LENGTH).unwrap();
-                hasher.update(CHUNK_DATA_ZEROED);
-                hasher
-            })
-            .clone();
 
         hasher.update(leaf_count.to_le_bytes());
         hasher.update(root);
@@ -120,8 +150,20 @@ impl Digest {
         Digest(result)
     }
 
+    /// Returns a hasher initialized with the padding prepended by `hash_merkle_root`.
+    fn pair_prefix_hasher() -> VarBlake2b {
+        let mut hasher = VarBlake2b::new(Digest::LENGTH).unwrap();
+        hasher.update(CHUNK_DATA_ZEROED);
+        hasher
+    }
+
+    /// Creates a `Digest` from the raw bytes of a BLAKE2b hash.
+    pub const fn from_raw(raw_digest: [u8; Digest::LENGTH]) -> Digest {
+        Digest(raw_digest)
+    }
+
     /// Returns the underlying BLAKE2b hash bytes
-    pub fn value(&self) -> [u8; Digest::LENGTH] {
+    pub const fn value(&self) -> [u8; Digest::LENGTH] {
         self.0
     }
 
@@ -222,14 +264,10 @@ impl Digest {
 
     /// Hash data into chunks if necessary.
     pub fn hash_into_chunks_if_necessary(bytes: &[u8]) -> Digest {
-        if bytes.len() <= ChunkWithProof::CHUNK_SIZE_BYTES {
+        if bytes.len() <= CHUNK_SIZE_BYTES {
             Digest::blake2b_hash(bytes)
         } else {
-            Digest::hash_merkle_tree(
-                bytes
-                    .chunks(ChunkWithProof::CHUNK_SIZE_BYTES)
-                    .map(Digest::blake2b_hash),
-            )
+            Digest::hash_merkle_tree(bytes.chunks(CHUNK_SIZE_BYTES).map(Digest::blake2b_hash))
         }
     }
 
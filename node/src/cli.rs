@@ -6,13 +6,14 @@ pub mod arglang;
 
 use std::{
     alloc::System,
-    fs,
+    fs::{self, File},
+    io::{BufReader, BufWriter},
     path::{Path, PathBuf},
     str::FromStr,
     sync::Arc,
 };
 
-use anyhow::{self, bail, Context};
+use anyhow::{self, anyhow, bail, Context};
 use prometheus::Registry;
 use regex::Regex;
 use stats_alloc::{StatsAlloc, INSTRUMENTED_SYSTEM};
@@ -20,8 +21,16 @@ use structopt::StructOpt;
 use toml::{value::Table, Value};
 use tracing::info;
 
+use casper_execution_engine::{
+    core::engine_state::EngineState, shared::newtypes::CorrelationId,
+    storage::global_state::lmdb::LmdbGlobalState,
+};
+use casper_hashing::Digest;
+
 use crate::{
-    components::network::Identity as NetworkIdentity,
+    components::{
+        contract_runtime::ContractRuntime, network::Identity as NetworkIdentity, storage,
+    },
     logging,
     reactor::{main_reactor, Runner},
     setup_signal_hooks,
@@ -78,6 +87,40 @@ pub enum Cli {
         #[structopt(long)]
         new_config: PathBuf,
     },
+    /// Print a JSON description of every network message variant supported by this version of
+    /// node.
+    ///
+    /// Lists each variant's name, tag, kinds of traffic and a hash of its layout, so that the
+    /// output of two versions can be compared to detect incompatible changes.
+    DumpMessageSchema {
+        /// Path to configuration file, next to which the chainspec is located.
+        config: PathBuf,
+    },
+    /// Export the global state under the given state root hash into a snapshot file.
+    ///
+    /// The snapshot is made of checksummed chunks, and can be imported into the global state of a
+    /// fresh node using `import-snapshot`.
+    ExportSnapshot {
+        /// Path to configuration file of the node whose global state is exported.
+        config: PathBuf,
+        /// State root hash of the global state to export, hex-encoded.
+        #[structopt(long, parse(try_from_str = Digest::from_hex))]
+        state_root_hash: Digest,
+        /// Path to the snapshot file to write.
+        #[structopt(long)]
+        output: PathBuf,
+    },
+    /// Import a global state snapshot created by `export-snapshot`.
+    ///
+    /// Once imported, a node started with a trusted hash of a block whose state root hash is the
+    /// snapshot's doesn't need to fetch that global state from its peers.
+    ImportSnapshot {
+        /// Path to configuration file of the node importing the snapshot.
+        config: PathBuf,
+        /// Path to the snapshot file to read.
+        #[structopt(long)]
+        input: PathBuf,
+    },
 }
 
 #[derive(Debug)]
@@ -237,9 +280,82 @@ impl Cli {
                 )?;
                 Ok(ExitCode::Success as i32)
             }
+            Cli::DumpMessageSchema { config } => {
+                let root = config
+                    .parent()
+                    .map(|path| path.to_owned())
+                    .unwrap_or_else(|| "/".into());
+                let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(&root)
+                    .context("could not load chainspec")?;
+
+                let schema = crate::protocol::schema::message_schema(&chainspec);
+                println!("{}", serde_json::to_string_pretty(&schema)?);
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ExportSnapshot {
+                config,
+                state_root_hash,
+                output,
+            } => {
+                let engine_state = Self::open_engine_state(&config)?;
+                let file = File::create(&output)
+                    .context("could not create snapshot file")
+                    .with_context(|| output.display().to_string())?;
+
+                info!(%state_root_hash, "exporting global state snapshot");
+                let summary = engine_state
+                    .export_snapshot(CorrelationId::new(), state_root_hash, BufWriter::new(file))
+                    .map_err(|error| anyhow!("could not export snapshot: {}", error))?;
+                info!(
+                    tries = summary.trie_count,
+                    chunks = summary.chunk_count,
+                    "exported global state snapshot"
+                );
+                Ok(ExitCode::Success as i32)
+            }
+            Cli::ImportSnapshot { config, input } => {
+                let engine_state = Self::open_engine_state(&config)?;
+                let file = File::open(&input)
+                    .context("could not open snapshot file")
+                    .with_context(|| input.display().to_string())?;
+
+                info!("importing global state snapshot");
+                let summary = engine_state
+                    .import_snapshot(CorrelationId::new(), BufReader::new(file))
+                    .map_err(|error| anyhow!("could not import snapshot: {}", error))?;
+                engine_state
+                    .flush_environment()
+                    .context("could not flush global state")?;
+                info!(
+                    state_root_hash = %summary.state_root_hash,
+                    tries = summary.trie_count,
+                    chunks = summary.chunk_count,
+                    "imported global state snapshot"
+                );
+                Ok(ExitCode::Success as i32)
+            }
         }
     }
 
+    /// Opens the global state of the node configured by the given config file.
+    fn open_engine_state(config: &Path) -> anyhow::Result<EngineState<LmdbGlobalState>> {
+        let node_config = Self::init(config, vec![])?;
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_path(node_config.dir())
+            .context("could not load chainspec")?;
+
+        let storage_dir = storage::storage_dir(
+            &node_config.with_dir(node_config.value().storage.path.clone()),
+            &chainspec.network_config.name,
+            chainspec.protocol_version(),
+        );
+        fs::create_dir_all(&storage_dir)
+            .context("could not create storage directory")
+            .with_context(|| storage_dir.display().to_string())?;
+
+        ContractRuntime::open_engine_state(&storage_dir, &node_config.value().contract_runtime)
+            .context("could not open global state")
+    }
+
     /// Parses the config file for the current version of casper-node, and initializes logging.
     fn init(
         config: &Path,
@@ -12,9 +12,12 @@ use serde::Serialize;
 use thiserror::Error;
 use tracing::{debug, error, warn};
 
-use casper_execution_engine::{core::engine_state, storage::trie::TrieRaw};
+use casper_execution_engine::{
+    core::engine_state,
+    storage::trie::{Trie, TrieRaw},
+};
 use casper_hashing::Digest;
-use casper_types::Timestamp;
+use casper_types::{bytesrepr, Key, StoredValue, Timestamp};
 
 use super::{TrieAccumulator, TrieAccumulatorError, TrieAccumulatorEvent, TrieAccumulatorResponse};
 use crate::{
@@ -253,6 +256,25 @@ impl GlobalStateSynchronizer {
         self.last_progress
     }
 
+    /// Returns the hashes of the tries of the global state being synced which may already have
+    /// been written: its root and the children of the tries awaiting some of theirs.
+    pub(super) fn tries_being_synced(&self) -> Vec<Digest> {
+        let mut trie_hashes: Vec<Digest> = self
+            .request_state
+            .iter()
+            .map(|request_state| request_state.root_hash.0)
+            .collect();
+        for trie_awaiting in self.tries_awaiting_children.values() {
+            match bytesrepr::deserialize::<Trie<Key, StoredValue>>(
+                trie_awaiting.trie_raw.inner().clone().into(),
+            ) {
+                Ok(trie) => trie_hashes.extend(trie.iter_children()),
+                Err(error) => warn!(%error, "couldn't parse trie awaiting children"),
+            }
+        }
+        trie_hashes
+    }
+
     fn handle_request<REv>(
         &mut self,
         request: SyncGlobalStateRequest,
@@ -4,11 +4,14 @@
 //! locally or on the network.
 //!
 //! When multiple requests are made to validate the same block payload, they will eagerly return
-//! true if valid, but only fail if all sources have been exhausted. This is only relevant when
-//! calling for validation of the same proposed block multiple times at the same time.
+//! `Ok` if valid, but only fail if all sources have been exhausted. Failures carry the reason the
+//! block was rejected. This is only relevant when calling for validation of the same proposed
+//! block multiple times at the same time.
 
 mod config;
+mod error;
 mod event;
+mod fetch_scheduler;
 mod state;
 #[cfg(test)]
 mod tests;
@@ -18,7 +21,7 @@ use std::{collections::HashMap, sync::Arc};
 use datasize::DataSize;
 use tracing::{debug, error, warn};
 
-use casper_types::Timestamp;
+use casper_types::{TimeDiff, Timestamp};
 
 use crate::{
     components::{
@@ -31,13 +34,15 @@ use crate::{
         EffectBuilder, EffectExt, Effects, Responder,
     },
     types::{
-        ApprovalsHash, Chainspec, Deploy, DeployHashWithApprovals, DeployId, DeployOrTransferHash,
-        NodeId,
+        ApprovalsHash, Chainspec, Deploy, DeployFootprint, DeployHashWithApprovals, DeployId,
+        DeployOrTransferHash, FootprintCache, NodeId,
     },
     NodeRng,
 };
 pub use config::Config;
+pub(crate) use error::BlockValidationError;
 pub(crate) use event::Event;
+use fetch_scheduler::FetchScheduler;
 use state::{AddResponderResult, BlockValidationState, MaybeStartFetching};
 
 const COMPONENT_NAME: &str = "block_validator";
@@ -72,15 +77,26 @@ pub(crate) struct BlockValidator {
     config: Config,
     /// State of validation of a specific block.
     validation_states: HashMap<ProposedBlock<ClContext>, BlockValidationState>,
+    /// Limits the number of deploys being fetched from any single peer at the same time.
+    fetch_scheduler: FetchScheduler,
+    /// Footprints of deploys, shared with the deploy buffer.
+    #[data_size(skip)]
+    footprint_cache: FootprintCache,
 }
 
 impl BlockValidator {
     /// Creates a new block validator instance.
-    pub(crate) fn new(chainspec: Arc<Chainspec>, config: Config) -> Self {
+    pub(crate) fn new(
+        chainspec: Arc<Chainspec>,
+        config: Config,
+        footprint_cache: FootprintCache,
+    ) -> Self {
         BlockValidator {
             chainspec,
             config,
             validation_states: HashMap::new(),
+            fetch_scheduler: FetchScheduler::new(config.max_concurrent_fetches_per_peer),
+            footprint_cache,
         }
     }
 
@@ -107,7 +123,10 @@ impl BlockValidator {
                     responder,
                     response_to_send,
                 } => {
-                    debug!(%response_to_send, "proposed block validation already completed");
+                    debug!(
+                        ?response_to_send,
+                        "proposed block validation already completed"
+                    );
                     return MaybeHandled::Handled(responder.respond(response_to_send).ignore());
                 }
             }
@@ -117,14 +136,27 @@ impl BlockValidator {
                 MaybeStartFetching::Start {
                     holder,
                     missing_deploys,
-                } => fetch_deploys(effect_builder, holder, missing_deploys),
+                } => {
+                    let delay = self.config.fetch_retry_delay(state.failed_holder_count());
+                    fetch_deploys_after(
+                        effect_builder,
+                        &mut self.fetch_scheduler,
+                        &self.footprint_cache,
+                        delay,
+                        holder,
+                        missing_deploys,
+                    )
+                }
                 MaybeStartFetching::Ongoing => {
                     debug!("ongoing fetches while validating proposed block - noop");
                     Effects::new()
                 }
                 MaybeStartFetching::Unable => {
-                    debug!("no new info while validating proposed block - responding `false`");
-                    respond(false, state.take_responders())
+                    debug!("no new info while validating proposed block - responding with error");
+                    let error = BlockValidationError::MissingDeploys {
+                        missing_deploy_count: state.missing_deploy_count(),
+                    };
+                    respond(Err(error), state.take_responders())
                 }
                 MaybeStartFetching::ValidationSucceeded | MaybeStartFetching::ValidationFailed => {
                     // If validation is already completed, we should have exited in the
@@ -159,21 +191,33 @@ impl BlockValidator {
             MaybeStartFetching::Start {
                 holder,
                 missing_deploys,
-            } => fetch_deploys(effect_builder, holder, missing_deploys),
+            } => fetch_deploys(
+                effect_builder,
+                &mut self.fetch_scheduler,
+                &self.footprint_cache,
+                holder,
+                missing_deploys,
+            ),
             MaybeStartFetching::ValidationSucceeded => {
                 debug!("no deploys - block validation complete");
                 debug_assert!(maybe_responder.is_some());
-                respond(true, maybe_responder)
+                respond(Ok(()), maybe_responder)
             }
             MaybeStartFetching::ValidationFailed => {
                 debug_assert!(maybe_responder.is_some());
-                respond(false, maybe_responder)
+                let result = state
+                    .completed_result()
+                    .unwrap_or(Err(BlockValidationError::InvalidState));
+                respond(result, maybe_responder)
             }
             MaybeStartFetching::Ongoing | MaybeStartFetching::Unable => {
                 // This `MaybeStartFetching` variant should never be returned here.
                 error!(%state, "invalid state while handling new block validation");
                 debug_assert!(false, "invalid state {}", state);
-                respond(false, state.take_responders())
+                respond(
+                    Err(BlockValidationError::InvalidState),
+                    state.take_responders(),
+                )
             }
         };
         self.validation_states.insert(block, state);
@@ -213,6 +257,119 @@ impl BlockValidator {
     }
 
     fn handle_deploy_fetched<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        dt_hash: DeployOrTransferHash,
+        holder: NodeId,
+        result: FetchResult<Deploy>,
+    ) -> Effects<Event>
+    where
+        REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+    {
+        let mut effects = self.handle_fetch_result(effect_builder, dt_hash, result);
+        effects.extend(self.fetch_completed(effect_builder, holder));
+        effects
+    }
+
+    fn handle_cached_footprint<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        dt_hash: DeployOrTransferHash,
+        holder: NodeId,
+        footprint: DeployFootprint,
+    ) -> Effects<Event>
+    where
+        REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+    {
+        debug!(%dt_hash, "found deploy footprint in cache");
+        let actual = footprint
+            .category
+            .deploy_or_transfer_hash(*dt_hash.deploy_hash());
+        let mut effects = if actual != dt_hash {
+            warn!(
+                expected_deploy_or_transfer_hash = %dt_hash,
+                actual_deploy_or_transfer_hash = %actual,
+                "deploy has incorrect deploy-or-transfer hash"
+            );
+            // Hard failure - change state to Invalid.
+            let error = BlockValidationError::WrongDeployKind {
+                expected: dt_hash,
+                actual,
+            };
+            let responders = self
+                .validation_states
+                .values_mut()
+                .flat_map(|state| state.try_mark_invalid(&dt_hash, error.clone()));
+            respond(Err(error.clone()), responders)
+        } else {
+            self.add_deploy_footprint(dt_hash, &footprint)
+        };
+        effects.extend(self.fetch_completed(effect_builder, holder));
+        effects
+    }
+
+    /// Records that a fetch from `holder` has completed, starting the next one queued for it (if
+    /// any).
+    fn fetch_completed<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        holder: NodeId,
+    ) -> Effects<Event>
+    where
+        REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+    {
+        let validation_states = &self.validation_states;
+        let maybe_next = self.fetch_scheduler.fetch_completed(&holder, |dt_hash| {
+            validation_states
+                .values()
+                .any(|state| state.is_missing(dt_hash))
+        });
+        match maybe_next {
+            Some((dt_hash, approvals_hash)) => fetch_deploy(
+                effect_builder,
+                &self.footprint_cache,
+                holder,
+                dt_hash,
+                approvals_hash,
+            ),
+            None => Effects::new(),
+        }
+    }
+
+    fn handle_retry_fetch<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        holder: NodeId,
+        missing_deploys: HashMap<DeployOrTransferHash, ApprovalsHash>,
+    ) -> Effects<Event>
+    where
+        REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+    {
+        // Some of the deploys may have been fetched while backing off.
+        let validation_states = &self.validation_states;
+        let missing_deploys: HashMap<_, _> = missing_deploys
+            .into_iter()
+            .filter(|(dt_hash, _)| {
+                validation_states
+                    .values()
+                    .any(|state| state.is_missing(dt_hash))
+            })
+            .collect();
+        debug!(
+            %holder,
+            missing_deploys_len = missing_deploys.len(),
+            "fetching missing deploys from different peer"
+        );
+        fetch_deploys(
+            effect_builder,
+            &mut self.fetch_scheduler,
+            &self.footprint_cache,
+            holder,
+            missing_deploys,
+        )
+    }
+
+    fn handle_fetch_result<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
         dt_hash: DeployOrTransferHash,
@@ -238,13 +395,17 @@ impl BlockValidator {
                         "deploy has incorrect deploy-or-transfer hash"
                     );
                     // Hard failure - change state to Invalid.
+                    let error = BlockValidationError::WrongDeployKind {
+                        expected: dt_hash,
+                        actual: item.deploy_or_transfer_hash(),
+                    };
                     let responders = self
                         .validation_states
                         .values_mut()
-                        .flat_map(|state| state.try_mark_invalid(&dt_hash));
-                    return respond(false, responders);
+                        .flat_map(|state| state.try_mark_invalid(&dt_hash, error.clone()));
+                    return respond(Err(error.clone()), responders);
                 }
-                let deploy_footprint = match item.footprint() {
+                let deploy_footprint = match self.footprint_cache.footprint(&item) {
                     Ok(footprint) => footprint,
                     Err(error) => {
                         warn!(
@@ -254,23 +415,19 @@ impl BlockValidator {
                             "could not convert deploy",
                         );
                         // Hard failure - change state to Invalid.
+                        let error = BlockValidationError::InvalidDeploy {
+                            dt_hash,
+                            error: error.to_string(),
+                        };
                         let responders = self
                             .validation_states
                             .values_mut()
-                            .flat_map(|state| state.try_mark_invalid(&dt_hash));
-                        return respond(false, responders);
+                            .flat_map(|state| state.try_mark_invalid(&dt_hash, error.clone()));
+                        return respond(Err(error.clone()), responders);
                     }
                 };
 
-                let mut effects = Effects::new();
-                for state in self.validation_states.values_mut() {
-                    let responders = state.try_add_deploy_footprint(&dt_hash, &deploy_footprint);
-                    if !responders.is_empty() {
-                        let is_valid = matches!(state, BlockValidationState::Valid(_));
-                        effects.extend(respond(is_valid, responders));
-                    }
-                }
-                effects
+                self.add_deploy_footprint(dt_hash, &deploy_footprint)
             }
             Err(error) => {
                 match error {
@@ -278,7 +435,12 @@ impl BlockValidator {
                     | fetcher::Error::Rejected { peer, .. }
                     | fetcher::Error::TimedOut { peer, .. } => {
                         // Soft failure - just mark the holder as failed and see if we can start
-                        // fetching using a different holder.
+                        // fetching using a different holder, backing off more for every holder
+                        // which failed.
+                        let dropped = self.fetch_scheduler.drop_queued(&peer);
+                        if dropped > 0 {
+                            debug!(%peer, dropped, "dropped queued fetches from failed peer");
+                        }
                         let mut effects = Effects::new();
                         self.validation_states.values_mut().for_each(|state| {
                             state.try_mark_holder_failed(&peer);
@@ -287,13 +449,13 @@ impl BlockValidator {
                                     holder,
                                     missing_deploys,
                                 } => {
-                                    debug!(
-                                        %holder,
-                                        missing_deploys_len = missing_deploys.len(),
-                                        "fetching missing deploys from different peer"
-                                    );
-                                    effects.extend(fetch_deploys(
+                                    let delay =
+                                        self.config.fetch_retry_delay(state.failed_holder_count());
+                                    effects.extend(fetch_deploys_after(
                                         effect_builder,
+                                        &mut self.fetch_scheduler,
+                                        &self.footprint_cache,
+                                        delay,
                                         holder,
                                         missing_deploys,
                                     ))
@@ -301,9 +463,12 @@ impl BlockValidator {
                                 MaybeStartFetching::Unable => {
                                     debug!(
                                         "exhausted peers while validating proposed block - \
-                                        responding `false`"
+                                        responding with error"
                                     );
-                                    effects.extend(respond(false, state.take_responders()));
+                                    let error = BlockValidationError::MissingDeploys {
+                                        missing_deploy_count: state.missing_deploy_count(),
+                                    };
+                                    effects.extend(respond(Err(error), state.take_responders()));
                                 }
                                 MaybeStartFetching::Ongoing
                                 | MaybeStartFetching::ValidationSucceeded
@@ -315,16 +480,39 @@ impl BlockValidator {
                     fetcher::Error::CouldNotConstructGetRequest { .. }
                     | fetcher::Error::ValidationMetadataMismatch { .. } => {
                         // Hard failure - change state to Invalid.
+                        let error = BlockValidationError::FetchFailed {
+                            dt_hash,
+                            error: error.to_string(),
+                        };
                         let responders = self
                             .validation_states
                             .values_mut()
-                            .flat_map(|state| state.try_mark_invalid(&dt_hash));
-                        respond(false, responders)
+                            .flat_map(|state| state.try_mark_invalid(&dt_hash, error.clone()));
+                        respond(Err(error.clone()), responders)
                     }
                 }
             }
         }
     }
+
+    /// Adds the footprint of the deploy `dt_hash` to every validation state still missing it.
+    fn add_deploy_footprint(
+        &mut self,
+        dt_hash: DeployOrTransferHash,
+        footprint: &DeployFootprint,
+    ) -> Effects<Event> {
+        let mut effects = Effects::new();
+        for state in self.validation_states.values_mut() {
+            let responders = state.try_add_deploy_footprint(&dt_hash, footprint);
+            if !responders.is_empty() {
+                let result = state
+                    .completed_result()
+                    .unwrap_or(Err(BlockValidationError::InvalidState));
+                effects.extend(respond(result, responders));
+            }
+        }
+        effects
+    }
 }
 
 impl<REv> Component<REv> for BlockValidator
@@ -352,9 +540,20 @@ where
                     }
                 }
             }
-            Event::DeployFetched { dt_hash, result } => {
-                self.handle_deploy_fetched(effect_builder, dt_hash, result)
-            }
+            Event::DeployFetched {
+                dt_hash,
+                holder,
+                result,
+            } => self.handle_deploy_fetched(effect_builder, dt_hash, holder, result),
+            Event::RetryFetch {
+                holder,
+                missing_deploys,
+            } => self.handle_retry_fetch(effect_builder, holder, missing_deploys),
+            Event::FootprintCached {
+                dt_hash,
+                holder,
+                footprint,
+            } => self.handle_cached_footprint(effect_builder, dt_hash, holder, *footprint),
         }
     }
 
@@ -363,31 +562,112 @@ where
     }
 }
 
+/// Fetches the missing deploys from `holder` once `delay` has elapsed, or right away if `delay` is
+/// zero.
+fn fetch_deploys_after<REv>(
+    effect_builder: EffectBuilder<REv>,
+    fetch_scheduler: &mut FetchScheduler,
+    footprint_cache: &FootprintCache,
+    delay: TimeDiff,
+    holder: NodeId,
+    missing_deploys: HashMap<DeployOrTransferHash, ApprovalsHash>,
+) -> Effects<Event>
+where
+    REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+{
+    if delay == TimeDiff::from_millis(0) {
+        debug!(
+            %holder,
+            missing_deploys_len = missing_deploys.len(),
+            "fetching missing deploys from different peer"
+        );
+        return fetch_deploys(
+            effect_builder,
+            fetch_scheduler,
+            footprint_cache,
+            holder,
+            missing_deploys,
+        );
+    }
+    debug!(
+        %holder,
+        %delay,
+        missing_deploys_len = missing_deploys.len(),
+        "backing off before fetching missing deploys from different peer"
+    );
+    effect_builder
+        .set_timeout(delay.into())
+        .event(move |_| Event::RetryFetch {
+            holder,
+            missing_deploys,
+        })
+}
+
+/// Fetches the missing deploys from `holder`, as far as the limit of concurrent fetches from
+/// `holder` allows.  The rest are fetched as the earlier fetches complete.
 fn fetch_deploys<REv>(
     effect_builder: EffectBuilder<REv>,
+    fetch_scheduler: &mut FetchScheduler,
+    footprint_cache: &FootprintCache,
     holder: NodeId,
     missing_deploys: HashMap<DeployOrTransferHash, ApprovalsHash>,
 ) -> Effects<Event>
 where
     REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
 {
-    missing_deploys
+    fetch_scheduler
+        .schedule(holder, missing_deploys)
         .into_iter()
         .flat_map(|(dt_hash, approvals_hash)| {
-            let deploy_id = DeployId::new(dt_hash.into(), approvals_hash);
-            effect_builder
-                .fetch::<Deploy>(deploy_id, holder, Box::new(EmptyValidationMetadata))
-                .event(move |result| Event::DeployFetched { dt_hash, result })
+            fetch_deploy(
+                effect_builder,
+                footprint_cache,
+                holder,
+                dt_hash,
+                approvals_hash,
+            )
         })
         .collect()
 }
 
+/// Fetches the deploy from `holder`, unless its footprint is already cached.
+fn fetch_deploy<REv>(
+    effect_builder: EffectBuilder<REv>,
+    footprint_cache: &FootprintCache,
+    holder: NodeId,
+    dt_hash: DeployOrTransferHash,
+    approvals_hash: ApprovalsHash,
+) -> Effects<Event>
+where
+    REv: From<Event> + From<FetcherRequest<Deploy>> + Send,
+{
+    let deploy_id = DeployId::new(dt_hash.into(), approvals_hash);
+    // A cached footprint means the deploy with these approvals has already been validated and
+    // stored, so there is no need to fetch it again.
+    if let Some(footprint) = footprint_cache.get(&deploy_id) {
+        return effect_builder
+            .immediately()
+            .event(move |_| Event::FootprintCached {
+                dt_hash,
+                holder,
+                footprint: Box::new(footprint),
+            });
+    }
+    effect_builder
+        .fetch::<Deploy>(deploy_id, holder, Box::new(EmptyValidationMetadata))
+        .event(move |result| Event::DeployFetched {
+            dt_hash,
+            holder,
+            result,
+        })
+}
+
 fn respond(
-    is_valid: bool,
-    responders: impl IntoIterator<Item = Responder<bool>>,
+    result: Result<(), BlockValidationError>,
+    responders: impl IntoIterator<Item = Responder<Result<(), BlockValidationError>>>,
 ) -> Effects<Event> {
     responders
         .into_iter()
-        .flat_map(|responder| responder.respond(is_valid).ignore())
+        .flat_map(|responder| responder.respond(result.clone()).ignore())
         .collect()
 }
@@ -1,16 +1,48 @@
+use std::str::FromStr;
+
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use casper_types::TimeDiff;
+
+const DEFAULT_FETCH_RETRY_INITIAL_DELAY: &str = "500ms";
+const DEFAULT_FETCH_RETRY_MAX_DELAY: &str = "5sec";
+
 /// Configuration options for block validation.
 #[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
 pub struct Config {
     pub max_completed_entries: u32,
+    /// The maximum number of deploys fetched from a single peer at the same time.
+    pub max_concurrent_fetches_per_peer: u32,
+    /// The delay before fetching missing deploys from another peer once the first peer failed to
+    /// provide them.  It is doubled for every further peer which failed.
+    pub fetch_retry_initial_delay: TimeDiff,
+    /// The upper limit of the delay before fetching missing deploys from another peer.
+    pub fetch_retry_max_delay: TimeDiff,
+}
+
+impl Config {
+    /// Returns the delay before fetching missing deploys from another peer once `failed_holders`
+    /// peers have failed to provide them.
+    pub(super) fn fetch_retry_delay(&self, failed_holders: usize) -> TimeDiff {
+        if failed_holders == 0 {
+            return TimeDiff::from_millis(0);
+        }
+        let exponent = (failed_holders - 1).min(63) as u32;
+        self.fetch_retry_initial_delay
+            .saturating_mul(1 << exponent)
+            .min(self.fetch_retry_max_delay)
+    }
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             max_completed_entries: 3,
+            max_concurrent_fetches_per_peer: 20,
+            fetch_retry_initial_delay: TimeDiff::from_str(DEFAULT_FETCH_RETRY_INITIAL_DELAY)
+                .unwrap(),
+            fetch_retry_max_delay: TimeDiff::from_str(DEFAULT_FETCH_RETRY_MAX_DELAY).unwrap(),
         }
     }
 }
@@ -0,0 +1,81 @@
+use datasize::DataSize;
+use serde::Serialize;
+use thiserror::Error;
+
+use casper_types::{EraId, Timestamp};
+
+use crate::types::{DeployHash, DeployOrTransferHash};
+
+/// The reason a proposed block failed validation.
+#[derive(Clone, DataSize, Debug, Error, PartialEq, Eq, Serialize)]
+pub(crate) enum BlockValidationError {
+    /// The block contains more non-transfer deploys than allowed.
+    #[error("block contains {deploy_count} deploys, more than the maximum of {max_deploy_count}")]
+    TooManyDeploys {
+        deploy_count: usize,
+        max_deploy_count: u32,
+    },
+    /// The block contains more transfers than allowed.
+    #[error(
+        "block contains {transfer_count} transfers, more than the maximum of {max_transfer_count}"
+    )]
+    TooManyTransfers {
+        transfer_count: usize,
+        max_transfer_count: u32,
+    },
+    /// A deploy is included in the block more than once.
+    #[error("{0} is included more than once")]
+    DuplicateDeploy(DeployOrTransferHash),
+    /// A deploy was already included in an ancestor block or an earlier era.
+    #[error("deploy {0} was already included in an earlier block")]
+    ReplayedDeploy(DeployHash),
+    /// The block contains a deploy that was already executed in an era before the block's era.
+    #[error("block in {block_era_id} contains a deploy already executed in {deploy_era_id}")]
+    ReplayedDeployFromPreviousEra {
+        deploy_era_id: EraId,
+        block_era_id: EraId,
+    },
+    /// The approvals hash of a deploy could not be computed.
+    #[error("could not compute the approvals hash of {dt_hash}: {error}")]
+    ApprovalsHash {
+        dt_hash: DeployOrTransferHash,
+        error: String,
+    },
+    /// Some deploys could not be fetched from any of the peers holding the block.
+    #[error("{missing_deploy_count} deploys could not be fetched from any peer")]
+    MissingDeploys { missing_deploy_count: usize },
+    /// Fetching a deploy failed irrecoverably.
+    #[error("could not fetch {dt_hash}: {error}")]
+    FetchFailed {
+        dt_hash: DeployOrTransferHash,
+        error: String,
+    },
+    /// The fetched deploy is a transfer while the block lists it as a deploy, or vice versa.
+    #[error("{actual} was included in the block as {expected}")]
+    WrongDeployKind {
+        expected: DeployOrTransferHash,
+        actual: DeployOrTransferHash,
+    },
+    /// A deploy is invalid on its own.
+    #[error("{dt_hash} is invalid: {error}")]
+    InvalidDeploy {
+        dt_hash: DeployOrTransferHash,
+        error: String,
+    },
+    /// A deploy expired before the block's timestamp.
+    #[error("{dt_hash} expired at {expiry}, before the block timestamp {block_timestamp}")]
+    ExpiredDeploy {
+        dt_hash: DeployOrTransferHash,
+        expiry: Timestamp,
+        block_timestamp: Timestamp,
+    },
+    /// Adding a deploy to the block would exceed the block's limits.
+    #[error("{dt_hash} cannot be added to the block: {error}")]
+    ExceedsBlockLimits {
+        dt_hash: DeployOrTransferHash,
+        error: String,
+    },
+    /// The validation state was inconsistent.
+    #[error("invalid block validation state")]
+    InvalidState,
+}
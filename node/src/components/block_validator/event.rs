@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use derive_more::{Display, From};
 
 use crate::{
     components::fetcher::FetchResult,
     effect::requests::BlockValidationRequest,
-    types::{Deploy, DeployOrTransferHash},
+    types::{ApprovalsHash, Deploy, DeployFootprint, DeployOrTransferHash, NodeId},
 };
 
 #[derive(Debug, From, Display)]
@@ -11,9 +13,27 @@ pub(crate) enum Event {
     #[from]
     Request(BlockValidationRequest),
 
-    #[display(fmt = "{} fetched", dt_hash)]
+    #[display(fmt = "{} fetched from {}", dt_hash, holder)]
     DeployFetched {
         dt_hash: DeployOrTransferHash,
+        holder: NodeId,
         result: FetchResult<Deploy>,
     },
+
+    #[display(
+        fmt = "retry fetching {} deploys from {}",
+        "missing_deploys.len()",
+        holder
+    )]
+    RetryFetch {
+        holder: NodeId,
+        missing_deploys: HashMap<DeployOrTransferHash, ApprovalsHash>,
+    },
+
+    #[display(fmt = "footprint of {} found in cache", dt_hash)]
+    FootprintCached {
+        dt_hash: DeployOrTransferHash,
+        holder: NodeId,
+        footprint: Box<DeployFootprint>,
+    },
 }
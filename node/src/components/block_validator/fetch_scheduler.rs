@@ -0,0 +1,166 @@
+use std::collections::{hash_map::Entry, HashMap, VecDeque};
+
+use datasize::DataSize;
+
+use crate::types::{ApprovalsHash, DeployOrTransferHash, NodeId};
+
+/// Limits the number of deploys being fetched from any single peer at the same time.
+///
+/// Fetches beyond the limit are queued per peer, and started one at a time as earlier fetches from
+/// that peer complete.
+#[derive(DataSize, Debug)]
+pub(super) struct FetchScheduler {
+    /// The maximum number of fetches in flight per peer.
+    max_in_flight_per_peer: usize,
+    /// The number of fetches in flight per peer.
+    in_flight: HashMap<NodeId, usize>,
+    /// The fetches waiting for a free slot, per peer.
+    queued: HashMap<NodeId, VecDeque<(DeployOrTransferHash, ApprovalsHash)>>,
+}
+
+impl FetchScheduler {
+    /// Returns a new `FetchScheduler`.  A limit of zero is treated as one.
+    pub(super) fn new(max_in_flight_per_peer: u32) -> Self {
+        FetchScheduler {
+            max_in_flight_per_peer: (max_in_flight_per_peer as usize).max(1),
+            in_flight: HashMap::new(),
+            queued: HashMap::new(),
+        }
+    }
+
+    /// Schedules fetching the given deploys from `holder` and returns the ones which should be
+    /// fetched right away.
+    ///
+    /// The rest are queued until fetches from `holder` complete.
+    pub(super) fn schedule(
+        &mut self,
+        holder: NodeId,
+        deploys: impl IntoIterator<Item = (DeployOrTransferHash, ApprovalsHash)>,
+    ) -> Vec<(DeployOrTransferHash, ApprovalsHash)> {
+        let in_flight = self.in_flight.entry(holder).or_default();
+        let mut to_fetch = vec![];
+        for deploy in deploys {
+            if *in_flight < self.max_in_flight_per_peer {
+                *in_flight += 1;
+                to_fetch.push(deploy);
+            } else {
+                self.queued.entry(holder).or_default().push_back(deploy);
+            }
+        }
+        if *in_flight == 0 {
+            self.in_flight.remove(&holder);
+        }
+        to_fetch
+    }
+
+    /// Records that a fetch from `holder` has completed.
+    ///
+    /// Returns the next queued deploy for which `is_needed` returns `true`, which should then be
+    /// fetched from `holder` in place of the completed fetch.  Queued deploys which are no longer
+    /// needed are discarded.
+    pub(super) fn fetch_completed(
+        &mut self,
+        holder: &NodeId,
+        mut is_needed: impl FnMut(&DeployOrTransferHash) -> bool,
+    ) -> Option<(DeployOrTransferHash, ApprovalsHash)> {
+        if let Entry::Occupied(mut entry) = self.queued.entry(*holder) {
+            let mut next = None;
+            while let Some(deploy) = entry.get_mut().pop_front() {
+                if is_needed(&deploy.0) {
+                    next = Some(deploy);
+                    break;
+                }
+            }
+            if entry.get().is_empty() {
+                entry.remove();
+            }
+            if next.is_some() {
+                // The fetch takes over the slot of the completed one.
+                return next;
+            }
+        }
+        if let Entry::Occupied(mut entry) = self.in_flight.entry(*holder) {
+            *entry.get_mut() -= 1;
+            if *entry.get() == 0 {
+                entry.remove();
+            }
+        }
+        None
+    }
+
+    /// Discards all fetches queued for `holder`, returning how many were discarded.
+    pub(super) fn drop_queued(&mut self, holder: &NodeId) -> usize {
+        self.queued.remove(holder).map_or(0, |queue| queue.len())
+    }
+
+    #[cfg(test)]
+    pub(super) fn in_flight(&self, holder: &NodeId) -> usize {
+        self.in_flight.get(holder).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+    use crate::types::DeployHash;
+
+    fn new_deploys(rng: &mut TestRng, count: usize) -> Vec<(DeployOrTransferHash, ApprovalsHash)> {
+        (0..count)
+            .map(|_| {
+                let dt_hash = DeployOrTransferHash::Deploy(DeployHash::random(rng));
+                (dt_hash, ApprovalsHash::random(rng))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_queue_fetches_beyond_limit() {
+        let mut rng = TestRng::new();
+        let mut scheduler = FetchScheduler::new(2);
+        let holder = NodeId::random(&mut rng);
+        let other_holder = NodeId::random(&mut rng);
+        let deploys = new_deploys(&mut rng, 3);
+
+        let to_fetch = scheduler.schedule(holder, deploys.clone());
+        assert_eq!(to_fetch, deploys[..2].to_vec());
+        assert_eq!(scheduler.in_flight(&holder), 2);
+
+        // The limit applies per peer.
+        let to_fetch = scheduler.schedule(other_holder, deploys.clone());
+        assert_eq!(to_fetch, deploys[..2].to_vec());
+
+        // Completing a fetch starts the queued one, without changing the count in flight.
+        assert_eq!(
+            scheduler.fetch_completed(&holder, |_| true),
+            Some(deploys[2])
+        );
+        assert_eq!(scheduler.in_flight(&holder), 2);
+        assert_eq!(scheduler.fetch_completed(&holder, |_| true), None);
+        assert_eq!(scheduler.fetch_completed(&holder, |_| true), None);
+        assert_eq!(scheduler.in_flight(&holder), 0);
+    }
+
+    #[test]
+    fn should_discard_queued_fetches() {
+        let mut rng = TestRng::new();
+        let mut scheduler = FetchScheduler::new(1);
+        let holder = NodeId::random(&mut rng);
+        let deploys = new_deploys(&mut rng, 4);
+
+        let to_fetch = scheduler.schedule(holder, deploys.clone());
+        assert_eq!(to_fetch, deploys[..1].to_vec());
+
+        // Deploys no longer needed are skipped.
+        let needed = deploys[2].0;
+        assert_eq!(
+            scheduler.fetch_completed(&holder, |dt_hash| *dt_hash == needed),
+            Some(deploys[2])
+        );
+
+        assert_eq!(scheduler.drop_queued(&holder), 1);
+        assert_eq!(scheduler.fetch_completed(&holder, |_| true), None);
+        assert_eq!(scheduler.in_flight(&holder), 0);
+    }
+}
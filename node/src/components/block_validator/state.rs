@@ -9,14 +9,16 @@ use tracing::{debug, error, warn};
 
 use casper_types::Timestamp;
 
+use super::BlockValidationError;
 #[cfg(test)]
 use crate::types::DeployHash;
 use crate::{
     components::consensus::{ClContext, ProposedBlock},
     effect::Responder,
     types::{
-        appendable_block::AppendableBlock, Approval, ApprovalsHash, Chainspec, DeployFootprint,
-        DeployHashWithApprovals, DeployOrTransferHash, NodeId,
+        appendable_block::{AddError, AppendableBlock},
+        Approval, ApprovalsHash, Chainspec, DeployFootprint, DeployHashWithApprovals,
+        DeployOrTransferHash, NodeId,
     },
 };
 
@@ -38,8 +40,8 @@ pub(super) enum AddResponderResult {
     Added,
     /// Validation is completed, so the responder should be called with the provided value.
     ValidationCompleted {
-        responder: Responder<bool>,
-        response_to_send: bool,
+        responder: Responder<Result<(), BlockValidationError>>,
+        response_to_send: Result<(), BlockValidationError>,
     },
 }
 
@@ -91,17 +93,17 @@ pub(super) enum BlockValidationState {
         /// The set of peers which each claim to hold all the deploys.
         holders: HashMap<NodeId, HolderState>,
         /// A list of responders that are awaiting an answer.
-        responders: Vec<Responder<bool>>,
+        responders: Vec<Responder<Result<(), BlockValidationError>>>,
     },
     /// The proposed block with the given timestamp is valid.
     Valid(Timestamp),
-    /// The proposed block with the given timestamp is invalid.
+    /// The proposed block with the given timestamp is invalid for the given reason.
     ///
     /// Note that only hard failures in validation will result in this state.  For soft failures,
     /// like failing to fetch from a peer, the state will remain `Unknown`, even if there are no
     /// more peers to ask, since more peers could be provided before this `BlockValidationState` is
     /// purged.
-    Invalid(Timestamp),
+    Invalid(Timestamp, BlockValidationError),
 }
 
 impl BlockValidationState {
@@ -112,23 +114,33 @@ impl BlockValidationState {
     pub(super) fn new(
         block: &ProposedBlock<ClContext>,
         sender: NodeId,
-        responder: Responder<bool>,
+        responder: Responder<Result<(), BlockValidationError>>,
         chainspec: &Chainspec,
-    ) -> (Self, Option<Responder<bool>>) {
+    ) -> (Self, Option<Responder<Result<(), BlockValidationError>>>) {
         let deploy_count = block.deploys().len() + block.transfers().len();
         if deploy_count == 0 {
             let state = BlockValidationState::Valid(block.timestamp());
             return (state, Some(responder));
         }
 
-        if block.deploys().len() > chainspec.deploy_config.block_max_deploy_count as usize {
+        let max_deploy_count = chainspec.deploy_config.block_max_deploy_count;
+        if block.deploys().len() > max_deploy_count as usize {
             warn!("too many non-transfer deploys");
-            let state = BlockValidationState::Invalid(block.timestamp());
+            let error = BlockValidationError::TooManyDeploys {
+                deploy_count: block.deploys().len(),
+                max_deploy_count,
+            };
+            let state = BlockValidationState::Invalid(block.timestamp(), error);
             return (state, Some(responder));
         }
-        if block.transfers().len() > chainspec.deploy_config.block_max_transfer_count as usize {
+        let max_transfer_count = chainspec.deploy_config.block_max_transfer_count;
+        if block.transfers().len() > max_transfer_count as usize {
             warn!("too many transfers");
-            let state = BlockValidationState::Invalid(block.timestamp());
+            let error = BlockValidationError::TooManyTransfers {
+                transfer_count: block.transfers().len(),
+                max_transfer_count,
+            };
+            let state = BlockValidationState::Invalid(block.timestamp(), error);
             return (state, Some(responder));
         }
 
@@ -148,18 +160,25 @@ impl BlockValidationState {
                 Ok(approvals_hash) => ApprovalInfo::new(approvals, approvals_hash),
                 Err(error) => {
                     warn!(%dt_hash, %error, "could not compute approvals hash");
-                    let state = BlockValidationState::Invalid(block.timestamp());
+                    let error = BlockValidationError::ApprovalsHash {
+                        dt_hash,
+                        error: error.to_string(),
+                    };
+                    let state = BlockValidationState::Invalid(block.timestamp(), error);
                     return (state, Some(responder));
                 }
             };
 
             if missing_deploys.insert(dt_hash, approval_info).is_some() {
                 warn!(%dt_hash, "duplicated deploy in proposed block");
-                let state = BlockValidationState::Invalid(block.timestamp());
+                let error = BlockValidationError::DuplicateDeploy(dt_hash);
+                let state = BlockValidationState::Invalid(block.timestamp(), error);
                 return (state, Some(responder));
             }
         }
 
+        // The sender is the only holder initially, so it is always the first peer asked for the
+        // missing deploys.
         let state = BlockValidationState::InProgress {
             appendable_block,
             missing_deploys,
@@ -175,23 +194,67 @@ impl BlockValidationState {
     ///
     /// If the state is not `InProgress`, `ValidationCompleted` is returned with the responder and
     /// the value which should be provided to the responder.
-    pub(super) fn add_responder(&mut self, responder: Responder<bool>) -> AddResponderResult {
-        match self {
-            BlockValidationState::InProgress { responders, .. } => {
-                responders.push(responder);
+    pub(super) fn add_responder(
+        &mut self,
+        responder: Responder<Result<(), BlockValidationError>>,
+    ) -> AddResponderResult {
+        match self.completed_result() {
+            None => {
+                if let BlockValidationState::InProgress { responders, .. } = self {
+                    responders.push(responder);
+                }
                 AddResponderResult::Added
             }
-            BlockValidationState::Valid(_) => AddResponderResult::ValidationCompleted {
+            Some(response_to_send) => AddResponderResult::ValidationCompleted {
                 responder,
-                response_to_send: true,
-            },
-            BlockValidationState::Invalid(_) => AddResponderResult::ValidationCompleted {
-                responder,
-                response_to_send: false,
+                response_to_send,
             },
         }
     }
 
+    /// Returns the result of validation if it is completed, or `None` if the state is
+    /// `InProgress`.
+    pub(super) fn completed_result(&self) -> Option<Result<(), BlockValidationError>> {
+        match self {
+            BlockValidationState::InProgress { .. } => None,
+            BlockValidationState::Valid(_) => Some(Ok(())),
+            BlockValidationState::Invalid(_, error) => Some(Err(error.clone())),
+        }
+    }
+
+    /// Returns the number of deploys still missing if the current state is `InProgress`, or zero
+    /// otherwise.
+    pub(super) fn missing_deploy_count(&self) -> usize {
+        match self {
+            BlockValidationState::InProgress {
+                missing_deploys, ..
+            } => missing_deploys.len(),
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => 0,
+        }
+    }
+
+    /// Returns `true` if the current state is `InProgress` and `dt_hash` is still missing.
+    pub(super) fn is_missing(&self, dt_hash: &DeployOrTransferHash) -> bool {
+        match self {
+            BlockValidationState::InProgress {
+                missing_deploys, ..
+            } => missing_deploys.contains_key(dt_hash),
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => false,
+        }
+    }
+
+    /// Returns the number of holders which failed to provide deploys if the current state is
+    /// `InProgress`, or zero otherwise.
+    pub(super) fn failed_holder_count(&self) -> usize {
+        match self {
+            BlockValidationState::InProgress { holders, .. } => holders
+                .values()
+                .filter(|holder_state| **holder_state == HolderState::Failed)
+                .count(),
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => 0,
+        }
+    }
+
     /// If the current state is `InProgress` and the peer isn't already known, adds the peer.
     /// Otherwise any existing entry is not updated and `false` is returned.
     pub(super) fn add_holder(&mut self, holder: NodeId) {
@@ -212,7 +275,7 @@ impl BlockValidationState {
                     entry.insert(HolderState::Unasked);
                 }
             },
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => {
                 error!(state = %self, "unexpected state when adding holder");
             }
         }
@@ -275,14 +338,14 @@ impl BlockValidationState {
                 }
             }
             BlockValidationState::Valid(_) => MaybeStartFetching::ValidationSucceeded,
-            BlockValidationState::Invalid(_) => MaybeStartFetching::ValidationFailed,
+            BlockValidationState::Invalid(..) => MaybeStartFetching::ValidationFailed,
         }
     }
 
-    pub(super) fn take_responders(&mut self) -> Vec<Responder<bool>> {
+    pub(super) fn take_responders(&mut self) -> Vec<Responder<Result<(), BlockValidationError>>> {
         match self {
             BlockValidationState::InProgress { responders, .. } => mem::take(responders),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => vec![],
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => vec![],
         }
     }
 
@@ -292,7 +355,7 @@ impl BlockValidationState {
         &mut self,
         dt_hash: &DeployOrTransferHash,
         footprint: &DeployFootprint,
-    ) -> Vec<Responder<bool>> {
+    ) -> Vec<Responder<Result<(), BlockValidationError>>> {
         let (new_state, responders) = match self {
             BlockValidationState::InProgress {
                 appendable_block,
@@ -331,30 +394,40 @@ impl BlockValidationState {
                         }
                         debug!(
                             block_timestamp = %appendable_block.timestamp(),
+                            total_gas = %appendable_block.total_gas(),
                             "no further missing deploys - block validation complete"
                         );
                         let new_state = BlockValidationState::Valid(appendable_block.timestamp());
                         (new_state, mem::take(responders))
                     }
                     Err(error) => {
-                        warn!(%dt_hash, ?footprint, %error, "block invalid");
-                        let new_state = BlockValidationState::Invalid(appendable_block.timestamp());
+                        warn!(
+                            block_timestamp = %appendable_block.timestamp(),
+                            %dt_hash,
+                            ?footprint,
+                            %error,
+                            "block invalid"
+                        );
+                        let error = validation_error(*dt_hash, error);
+                        let new_state =
+                            BlockValidationState::Invalid(appendable_block.timestamp(), error);
                         (new_state, mem::take(responders))
                     }
                 }
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => return vec![],
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => return vec![],
         };
         *self = new_state;
         responders
     }
 
     /// If the current state is `InProgress` and `dt_hash` is present, sets the state to `Invalid`
-    /// and returns the responders.
+    /// with the given `error` and returns the responders.
     pub(super) fn try_mark_invalid(
         &mut self,
         dt_hash: &DeployOrTransferHash,
-    ) -> Vec<Responder<bool>> {
+        error: BlockValidationError,
+    ) -> Vec<Responder<Result<(), BlockValidationError>>> {
         let (timestamp, responders) = match self {
             BlockValidationState::InProgress {
                 appendable_block,
@@ -367,18 +440,17 @@ impl BlockValidationState {
                 }
                 (appendable_block.timestamp(), mem::take(responders))
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => return vec![],
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => return vec![],
         };
-        *self = BlockValidationState::Valid(timestamp);
+        *self = BlockValidationState::Invalid(timestamp, error);
         responders
     }
 
     pub(super) fn block_timestamp_if_completed(&self) -> Option<Timestamp> {
         match self {
             BlockValidationState::InProgress { .. } => None,
-            BlockValidationState::Valid(timestamp) | BlockValidationState::Invalid(timestamp) => {
-                Some(*timestamp)
-            }
+            BlockValidationState::Valid(timestamp)
+            | BlockValidationState::Invalid(timestamp, _) => Some(*timestamp),
         }
     }
 
@@ -391,7 +463,7 @@ impl BlockValidationState {
                 .keys()
                 .map(|dt_hash| *dt_hash.deploy_hash())
                 .collect(),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => vec![],
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => vec![],
         }
     }
 
@@ -399,7 +471,7 @@ impl BlockValidationState {
     pub(super) fn holders_mut(&mut self) -> Option<&mut HashMap<NodeId, HolderState>> {
         match self {
             BlockValidationState::InProgress { holders, .. } => Some(holders),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => None,
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => None,
         }
     }
 
@@ -407,7 +479,7 @@ impl BlockValidationState {
     pub(super) fn responder_count(&self) -> usize {
         match self {
             BlockValidationState::InProgress { responders, .. } => responders.len(),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => 0,
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => 0,
         }
     }
 
@@ -438,13 +510,44 @@ impl Display for BlockValidationState {
             BlockValidationState::Valid(timestamp) => {
                 write!(formatter, "BlockValidationState::Valid({timestamp})")
             }
-            BlockValidationState::Invalid(timestamp) => {
-                write!(formatter, "BlockValidationState::Invalid({timestamp})")
+            BlockValidationState::Invalid(timestamp, error) => {
+                write!(
+                    formatter,
+                    "BlockValidationState::Invalid({timestamp}, {error})"
+                )
             }
         }
     }
 }
 
+/// Converts an error adding the deploy `dt_hash` to the appendable block into the reason the block
+/// is invalid.
+fn validation_error(dt_hash: DeployOrTransferHash, error: AddError) -> BlockValidationError {
+    match error {
+        AddError::Duplicate => BlockValidationError::DuplicateDeploy(dt_hash),
+        AddError::Expired {
+            expiry,
+            block_timestamp,
+        } => BlockValidationError::ExpiredDeploy {
+            dt_hash,
+            expiry,
+            block_timestamp,
+        },
+        AddError::InvalidDeploy(error) => BlockValidationError::InvalidDeploy {
+            dt_hash,
+            error: error.to_string(),
+        },
+        AddError::TransferCount
+        | AddError::DeployCount
+        | AddError::ApprovalCount
+        | AddError::GasLimit { .. }
+        | AddError::BlockSize => BlockValidationError::ExceedsBlockLimits {
+            dt_hash,
+            error: error.to_string(),
+        },
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use futures::channel::oneshot;
@@ -482,7 +585,10 @@ mod tests {
             &mut self,
             deploy_count: u64,
             transfer_count: u64,
-        ) -> (BlockValidationState, Option<Responder<bool>>) {
+        ) -> (
+            BlockValidationState,
+            Option<Responder<Result<(), BlockValidationError>>>,
+        ) {
             let ttl = TimeDiff::from_seconds(10);
             let deploys: Vec<_> = (0..deploy_count)
                 .map(|index| new_deploy(&mut self.rng, Timestamp::from(1000 + index), ttl))
@@ -539,7 +645,7 @@ mod tests {
         }
     }
 
-    fn new_responder() -> Responder<bool> {
+    fn new_responder() -> Responder<Result<(), BlockValidationError>> {
         let (sender, _receiver) = oneshot::channel();
         Responder::without_shutdown(sender)
     }
@@ -558,7 +664,10 @@ mod tests {
         let deploy_count = 5_u64;
         fixture.chainspec.deploy_config.block_max_deploy_count = deploy_count as u32 - 1;
         let (state, maybe_responder) = fixture.new_state(deploy_count, 0);
-        assert!(matches!(state, BlockValidationState::Invalid(_)));
+        assert!(matches!(
+            state,
+            BlockValidationState::Invalid(_, BlockValidationError::TooManyDeploys { .. })
+        ));
         assert!(maybe_responder.is_some());
     }
 
@@ -568,7 +677,10 @@ mod tests {
         let transfer_count = 5_u64;
         fixture.chainspec.deploy_config.block_max_transfer_count = transfer_count as u32 - 1;
         let (state, maybe_responder) = fixture.new_state(0, transfer_count);
-        assert!(matches!(state, BlockValidationState::Invalid(_)));
+        assert!(matches!(
+            state,
+            BlockValidationState::Invalid(_, BlockValidationError::TooManyTransfers { .. })
+        ));
         assert!(maybe_responder.is_some());
     }
 
@@ -593,7 +705,10 @@ mod tests {
             &fixture.chainspec,
         );
 
-        assert!(matches!(state, BlockValidationState::Invalid(_)));
+        assert!(matches!(
+            state,
+            BlockValidationState::Invalid(_, BlockValidationError::DuplicateDeploy(_))
+        ));
         assert!(maybe_responder.is_some());
     }
 
@@ -616,7 +731,7 @@ mod tests {
                 assert_eq!(holders.values().next().unwrap(), &HolderState::Unasked);
                 assert_eq!(responders.len(), 1);
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => {
                 panic!("unexpected state")
             }
         }
@@ -642,7 +757,7 @@ mod tests {
         assert!(matches!(
             add_responder_result,
             AddResponderResult::ValidationCompleted {
-                response_to_send: true,
+                response_to_send: Ok(()),
                 ..
             }
         ));
@@ -651,12 +766,15 @@ mod tests {
 
     #[test]
     fn should_not_add_responder_if_invalid() {
-        let mut state = BlockValidationState::Invalid(Timestamp::from(1000));
+        let mut state = BlockValidationState::Invalid(
+            Timestamp::from(1000),
+            BlockValidationError::InvalidState,
+        );
         let add_responder_result = state.add_responder(new_responder());
         assert!(matches!(
             add_responder_result,
             AddResponderResult::ValidationCompleted {
-                response_to_send: false,
+                response_to_send: Err(BlockValidationError::InvalidState),
                 ..
             }
         ));
@@ -831,7 +949,10 @@ mod tests {
 
     #[test]
     fn start_fetching_should_return_validation_failed_if_invalid() {
-        let mut state = BlockValidationState::Invalid(Timestamp::from(1000));
+        let mut state = BlockValidationState::Invalid(
+            Timestamp::from(1000),
+            BlockValidationError::InvalidState,
+        );
         let maybe_start_fetching = state.start_fetching();
         assert_eq!(maybe_start_fetching, MaybeStartFetching::ValidationFailed);
     }
@@ -879,7 +1000,7 @@ mod tests {
                 missing_deploys.clone(),
                 holders.clone(),
             ),
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => {
                 panic!("unexpected state")
             }
         };
@@ -903,7 +1024,7 @@ mod tests {
                 assert_eq!(&missing_deploys_before, missing_deploys);
                 assert_eq!(&holders_before, holders);
             }
-            BlockValidationState::Valid(_) | BlockValidationState::Invalid(_) => {
+            BlockValidationState::Valid(_) | BlockValidationState::Invalid(..) => {
                 panic!("unexpected state")
             }
         };
@@ -936,6 +1057,72 @@ mod tests {
         let footprint = invalid_deploy.footprint().unwrap();
         let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
         assert_eq!(responders.len(), 1);
-        assert!(matches!(state, BlockValidationState::Invalid(_)));
+        assert!(matches!(state, BlockValidationState::Invalid(..)));
+    }
+
+    #[test]
+    fn state_should_change_to_validation_failed_if_deploy_expired_before_block() {
+        let mut fixture = Fixture::new();
+        // Add a deploy which expires before the proposed block's timestamp.
+        let expired_deploy = new_deploy(&mut fixture.rng, 0.into(), TimeDiff::from_millis(1));
+        fixture.deploys.push(expired_deploy.clone());
+        let (mut state, _maybe_responder) = fixture.new_state(2, 2);
+        assert!(matches!(state, BlockValidationState::InProgress { .. }));
+
+        let dt_hash = DeployOrTransferHash::Deploy(*expired_deploy.hash());
+        let footprint = expired_deploy.footprint().unwrap();
+        let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(
+            state,
+            BlockValidationState::Invalid(_, BlockValidationError::ExpiredDeploy { .. })
+        ));
+    }
+
+    #[test]
+    fn state_should_change_to_invalid_when_marked_invalid() {
+        let mut fixture = Fixture::new();
+        let (mut state, _maybe_responder) = fixture.new_state(2, 2);
+        let (dt_hash, _footprint) = fixture.footprints().pop().unwrap();
+
+        let error = BlockValidationError::FetchFailed {
+            dt_hash,
+            error: "test".to_string(),
+        };
+        let responders = state.try_mark_invalid(&dt_hash, error.clone());
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(state, BlockValidationState::Invalid(..)));
+
+        // Later requests must not be told the block is valid.
+        assert!(matches!(
+            state.add_responder(new_responder()),
+            AddResponderResult::ValidationCompleted {
+                response_to_send: Err(ref response_error),
+                ..
+            } if *response_error == error
+        ));
+    }
+
+    #[test]
+    fn state_should_change_to_validation_failed_if_block_gas_limit_exceeded() {
+        let mut fixture = Fixture::new();
+        // Each of the fixture's deploys has a gas estimate of 1, so only one fits in the block.
+        fixture.chainspec.deploy_config.block_gas_limit = 1;
+        let (mut state, _maybe_responder) = fixture.new_state(2, 0);
+        assert!(matches!(state, BlockValidationState::InProgress { .. }));
+
+        let mut footprints = fixture.footprints();
+        let (dt_hash, footprint) = footprints.pop().unwrap();
+        let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        assert!(responders.is_empty());
+        assert!(matches!(state, BlockValidationState::InProgress { .. }));
+
+        let (dt_hash, footprint) = footprints.pop().unwrap();
+        let responders = state.try_add_deploy_footprint(&dt_hash, &footprint);
+        assert_eq!(responders.len(), 1);
+        assert!(matches!(
+            state,
+            BlockValidationState::Invalid(_, BlockValidationError::ExceedsBlockLimits { .. })
+        ));
     }
 }
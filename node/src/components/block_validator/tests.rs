@@ -99,6 +99,41 @@ impl MockReactor {
             }
         }
     }
+
+    /// Expects a request to fetch one of `deploys` from `expected_peer`, and responds with the
+    /// deploy if `provide` is `true`, or with `Absent` otherwise.
+    async fn expect_fetch_from(&self, expected_peer: NodeId, deploys: &[Deploy], provide: bool) {
+        let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
+        if let ReactorEvent::Fetcher(FetcherRequest {
+            id,
+            peer,
+            validation_metadata: _,
+            responder,
+        }) = reactor_event
+        {
+            assert_eq!(peer, expected_peer);
+            let deploy = deploys
+                .iter()
+                .find(|deploy| deploy.fetch_id() == id)
+                .unwrap_or_else(|| panic!("unexpected fetch request: {}", id));
+            if provide {
+                let response = FetchedData::FromPeer {
+                    item: Box::new(deploy.clone()),
+                    peer,
+                };
+                responder.respond(Ok(response)).await;
+            } else {
+                responder
+                    .respond(Err(fetcher::Error::Absent {
+                        id: Box::new(id),
+                        peer,
+                    }))
+                    .await;
+            }
+        } else {
+            panic!("unexpected event: {:?}", reactor_event);
+        }
+    }
 }
 
 pub(super) fn new_proposed_block(
@@ -188,7 +223,11 @@ async fn validate_block(
     let reactor = MockReactor::new();
     let effect_builder = EffectBuilder::new(EventQueueHandle::without_shutdown(reactor.scheduler));
     let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
-    let mut block_validator = BlockValidator::new(Arc::new(chainspec), Config::default());
+    let mut block_validator = BlockValidator::new(
+        Arc::new(chainspec),
+        Config::default(),
+        FootprintCache::default(),
+    );
 
     // Pass the block to the component. This future will eventually resolve to the result, i.e.
     // whether the block is valid or not.
@@ -244,7 +283,11 @@ async fn validate_block(
 /// Verifies that a block without any deploys or transfers is valid.
 #[tokio::test]
 async fn empty_block() {
-    assert!(validate_block(&mut TestRng::new(), 1000.into(), vec![], vec![]).await);
+    assert!(
+        validate_block(&mut TestRng::new(), 1000.into(), vec![], vec![])
+            .await
+            .is_ok()
+    );
 }
 
 /// Verifies that the block validator checks deploy and transfer timestamps and ttl.
@@ -264,18 +307,48 @@ async fn ttl() {
     ];
 
     // Both 1000 and 1100 are timestamps compatible with the deploys and transfers.
-    assert!(validate_block(&mut rng, 1000.into(), deploys.clone(), transfers.clone()).await);
-    assert!(validate_block(&mut rng, 1100.into(), deploys.clone(), transfers.clone()).await);
+    assert!(
+        validate_block(&mut rng, 1000.into(), deploys.clone(), transfers.clone())
+            .await
+            .is_ok()
+    );
+    assert!(
+        validate_block(&mut rng, 1100.into(), deploys.clone(), transfers.clone())
+            .await
+            .is_ok()
+    );
 
     // A block with timestamp 999 can't contain a transfer or deploy with timestamp 1000.
-    assert!(!validate_block(&mut rng, 999.into(), deploys.clone(), vec![]).await);
-    assert!(!validate_block(&mut rng, 999.into(), vec![], transfers.clone()).await);
-    assert!(!validate_block(&mut rng, 999.into(), deploys.clone(), transfers.clone()).await);
+    assert!(
+        validate_block(&mut rng, 999.into(), deploys.clone(), vec![])
+            .await
+            .is_err()
+    );
+    assert!(
+        validate_block(&mut rng, 999.into(), vec![], transfers.clone())
+            .await
+            .is_err()
+    );
+    assert!(
+        validate_block(&mut rng, 999.into(), deploys.clone(), transfers.clone())
+            .await
+            .is_err()
+    );
 
     // At time 1101, the deploy and transfer from time 900 have expired.
-    assert!(!validate_block(&mut rng, 1101.into(), deploys.clone(), vec![]).await);
-    assert!(!validate_block(&mut rng, 1101.into(), vec![], transfers.clone()).await);
-    assert!(!validate_block(&mut rng, 1101.into(), deploys, transfers).await);
+    assert!(
+        validate_block(&mut rng, 1101.into(), deploys.clone(), vec![])
+            .await
+            .is_err()
+    );
+    assert!(
+        validate_block(&mut rng, 1101.into(), vec![], transfers.clone())
+            .await
+            .is_err()
+    );
+    assert!(validate_block(&mut rng, 1101.into(), deploys, transfers)
+        .await
+        .is_err());
 }
 
 /// Verifies that a block is invalid if it contains a transfer in the `deploy_hashes` or a
@@ -293,27 +366,41 @@ async fn transfer_deploy_mixup_and_replay() {
     // First we make sure that our transfers and deploys would normally be valid.
     let deploys = vec![deploy1.clone(), deploy2.clone()];
     let transfers = vec![transfer1.clone(), transfer2.clone()];
-    assert!(validate_block(&mut rng, timestamp, deploys, transfers).await);
+    assert!(validate_block(&mut rng, timestamp, deploys, transfers)
+        .await
+        .is_ok());
 
     // Now we hide a transfer in the deploys section. This should be invalid.
     let deploys = vec![deploy1.clone(), deploy2.clone(), transfer2.clone()];
     let transfers = vec![transfer1.clone()];
-    assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
+    assert!(matches!(
+        validate_block(&mut rng, timestamp, deploys, transfers).await,
+        Err(BlockValidationError::WrongDeployKind { .. })
+    ));
 
     // A regular deploy in the transfers section is also invalid.
     let deploys = vec![deploy2.clone()];
     let transfers = vec![transfer1.clone(), deploy1.clone(), transfer2.clone()];
-    assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
+    assert!(matches!(
+        validate_block(&mut rng, timestamp, deploys, transfers).await,
+        Err(BlockValidationError::WrongDeployKind { .. })
+    ));
 
     // Each deploy must be unique
     let deploys = vec![deploy1.clone(), deploy2.clone(), deploy1.clone()];
     let transfers = vec![transfer1.clone(), transfer2.clone()];
-    assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
+    assert!(matches!(
+        validate_block(&mut rng, timestamp, deploys, transfers).await,
+        Err(BlockValidationError::DuplicateDeploy(_))
+    ));
 
     // And each transfer must be unique, too.
     let deploys = vec![deploy1.clone(), deploy2.clone()];
     let transfers = vec![transfer1.clone(), transfer2.clone(), transfer2.clone()];
-    assert!(!validate_block(&mut rng, timestamp, deploys, transfers).await);
+    assert!(matches!(
+        validate_block(&mut rng, timestamp, deploys, transfers).await,
+        Err(BlockValidationError::DuplicateDeploy(_))
+    ));
 }
 
 /// Verifies that the block validator fetches from multiple peers.
@@ -348,7 +435,13 @@ async fn should_fetch_from_multiple_peers() {
         let effect_builder =
             EffectBuilder::new(EventQueueHandle::without_shutdown(reactor.scheduler));
         let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
-        let mut block_validator = BlockValidator::new(Arc::new(chainspec), Config::default());
+        // Don't back off before falling back to a different peer.
+        let config = Config {
+            fetch_retry_initial_delay: TimeDiff::from_millis(0),
+            ..Config::default()
+        };
+        let mut block_validator =
+            BlockValidator::new(Arc::new(chainspec), config, FootprintCache::default());
 
         // Have a validation request for each one of the peers. These futures will eventually all
         // resolve to the same result, i.e. whether the block is valid or not.
@@ -480,8 +573,148 @@ async fn should_fetch_from_multiple_peers() {
         }
 
         for validation_result in validation_results {
-            assert!(validation_result.await.unwrap());
+            assert!(validation_result.await.unwrap().is_ok());
+        }
+    })
+    .await
+    .expect("should not hang");
+}
+
+/// Verifies that the block validator asks the block's sender first, backs off before falling back
+/// to a different peer, and limits the number of concurrent fetches per peer.
+#[tokio::test]
+async fn should_limit_concurrent_fetches_and_back_off() {
+    let _ = crate::logging::init();
+    tokio::time::timeout(Duration::from_secs(5), async move {
+        let mut rng = TestRng::new();
+        let ttl = TimeDiff::from_seconds(200);
+        let deploys = (0..2)
+            .map(|i| new_deploy(&mut rng, (900 + i).into(), ttl))
+            .collect_vec();
+        let deploys_for_block = deploys
+            .iter()
+            .map(DeployHashWithApprovals::from)
+            .collect_vec();
+        let proposed_block = new_proposed_block(1100.into(), deploys_for_block, vec![]);
+
+        let reactor = MockReactor::new();
+        let effect_builder =
+            EffectBuilder::new(EventQueueHandle::without_shutdown(reactor.scheduler));
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        let config = Config {
+            max_concurrent_fetches_per_peer: 1,
+            fetch_retry_initial_delay: TimeDiff::from_millis(10),
+            ..Config::default()
+        };
+        let mut block_validator =
+            BlockValidator::new(Arc::new(chainspec), config, FootprintCache::default());
+
+        // Only a single deploy is fetched, from the sender.
+        let sender = NodeId::random(&mut rng);
+        let mut validation_results = vec![tokio::spawn(
+            effect_builder.validate_block(sender, proposed_block.clone()),
+        )];
+        let event = reactor.expect_block_validator_event().await;
+        let effects = block_validator.handle_event(effect_builder, &mut rng, event);
+        assert_eq!(effects.len(), 1);
+        let fetch_effect = effects.into_iter().next().unwrap();
+
+        // Another peer holding the block doesn't cause any further fetches for now.
+        let other_peer = NodeId::random(&mut rng);
+        validation_results.push(tokio::spawn(
+            effect_builder.validate_block(other_peer, proposed_block.clone()),
+        ));
+        let event = reactor.expect_block_validator_event().await;
+        assert!(block_validator
+            .handle_event(effect_builder, &mut rng, event)
+            .is_empty());
+
+        // The sender fails to provide it, so after backing off, the other peer is asked.
+        let fetch_result = tokio::spawn(fetch_effect);
+        reactor.expect_fetch_from(sender, &deploys, false).await;
+        let mut events = fetch_result.await.unwrap();
+        let effects = block_validator.handle_event(effect_builder, &mut rng, events.pop().unwrap());
+        assert_eq!(effects.len(), 1);
+        let mut events = tokio::spawn(effects.into_iter().next().unwrap())
+            .await
+            .unwrap();
+        let event = events.pop().unwrap();
+        assert!(matches!(event, Event::RetryFetch { holder, .. } if holder == other_peer));
+
+        // The deploys are fetched one after the other from the other peer.
+        let mut effects = block_validator.handle_event(effect_builder, &mut rng, event);
+        for _ in 0..deploys.len() {
+            assert_eq!(effects.len(), 1);
+            let fetch_result = tokio::spawn(effects.into_iter().next().unwrap());
+            reactor.expect_fetch_from(other_peer, &deploys, true).await;
+            let mut events = fetch_result.await.unwrap();
+            effects = block_validator.handle_event(effect_builder, &mut rng, events.pop().unwrap());
+        }
+
+        // The block is valid, so the effects are the two validation responses.
+        assert_eq!(effects.len(), 2);
+        for effect in effects {
+            tokio::spawn(effect).await.unwrap();
+        }
+        for validation_result in validation_results {
+            assert!(validation_result.await.unwrap().is_ok());
+        }
+    })
+    .await
+    .expect("should not hang");
+}
+
+/// Verifies that deploys whose footprints are already cached are not fetched.
+#[tokio::test]
+async fn should_not_fetch_deploys_with_cached_footprints() {
+    let _ = crate::logging::init();
+    tokio::time::timeout(Duration::from_secs(5), async move {
+        let mut rng = TestRng::new();
+        let ttl = TimeDiff::from_seconds(200);
+        let deploy = new_deploy(&mut rng, 900.into(), ttl);
+        let transfer = new_transfer(&mut rng, 1000.into(), ttl);
+        let proposed_block = new_proposed_block(
+            1100.into(),
+            vec![DeployHashWithApprovals::from(&deploy)],
+            vec![DeployHashWithApprovals::from(&transfer)],
+        );
+
+        // Only the deploy's footprint is cached, so only the transfer is fetched.
+        let footprint_cache = FootprintCache::default();
+        footprint_cache.footprint(&deploy).unwrap();
+
+        let reactor = MockReactor::new();
+        let effect_builder =
+            EffectBuilder::new(EventQueueHandle::without_shutdown(reactor.scheduler));
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        let mut block_validator =
+            BlockValidator::new(Arc::new(chainspec), Config::default(), footprint_cache);
+
+        let sender = NodeId::random(&mut rng);
+        let validation_result =
+            tokio::spawn(effect_builder.validate_block(sender, proposed_block.clone()));
+        let event = reactor.expect_block_validator_event().await;
+        let effects = block_validator.handle_event(effect_builder, &mut rng, event);
+        assert_eq!(effects.len(), 2);
+        let results = effects.into_iter().map(tokio::spawn).collect_vec();
+        reactor
+            .expect_fetch_deploys(vec![transfer.clone()], HashSet::new())
+            .await;
+
+        let mut effects = Effects::new();
+        for result in results {
+            let mut events = result.await.unwrap();
+            assert_eq!(events.len(), 1);
+            let event = events.pop().unwrap();
+            effects.extend(block_validator.handle_event(effect_builder, &mut rng, event));
+        }
+
+        // The only effect is the validation response.
+        assert_eq!(effects.len(), 1);
+        for effect in effects {
+            tokio::spawn(effect).await.unwrap();
         }
+        assert!(validation_result.await.unwrap().is_ok());
     })
     .await
     .expect("should not hang");
@@ -9,6 +9,7 @@ mod era_supervisor;
 #[macro_use]
 pub mod highway_core;
 pub(crate) mod error;
+mod leader_schedule;
 mod leader_sequence;
 mod metrics;
 pub mod protocols;
@@ -33,7 +34,7 @@ use tracing::{info, trace};
 use casper_types::{EraId, Timestamp};
 
 use crate::{
-    components::Component,
+    components::{block_validator::BlockValidationError, Component},
     effect::{
         announcements::{
             ConsensusAnnouncement, FatalAnnouncement, MetaBlockAnnouncement,
@@ -63,6 +64,7 @@ pub(crate) use consensus_protocol::{BlockContext, EraReport, ProposedBlock};
 pub(crate) use era_supervisor::{debug::EraDump, EraSupervisor, SerializedMessage};
 #[cfg(test)]
 pub(crate) use highway_core::highway::Vertex as HighwayVertex;
+pub use leader_schedule::{LeaderSchedule, ProposerSlot};
 pub(crate) use leader_sequence::LeaderSequence;
 pub(crate) use protocols::highway::max_rounds_per_era;
 #[cfg(test)]
@@ -138,7 +140,7 @@ pub struct ResolveValidity {
     era_id: EraId,
     sender: NodeId,
     proposed_block: ProposedBlock<ClContext>,
-    valid: bool,
+    validation_result: Result<(), BlockValidationError>,
 }
 
 /// Consensus component event.
@@ -284,15 +286,19 @@ impl Display for Event {
                 era_id,
                 sender,
                 proposed_block,
-                valid,
-            }) => write!(
-                f,
-                "Proposed block received from {:?} for {} is {}: {:?}",
-                sender,
-                era_id,
-                if *valid { "valid" } else { "invalid" },
-                proposed_block,
-            ),
+                validation_result,
+            }) => match validation_result {
+                Ok(()) => write!(
+                    f,
+                    "Proposed block received from {:?} for {} is valid: {:?}",
+                    sender, era_id, proposed_block,
+                ),
+                Err(error) => write!(
+                    f,
+                    "Proposed block received from {:?} for {} is invalid ({}): {:?}",
+                    sender, era_id, error, proposed_block,
+                ),
+            },
             Event::DeactivateEra {
                 era_id, faulty_num, ..
             } => write!(
@@ -473,6 +479,10 @@ where
                 let validator_changes = self.get_validator_changes();
                 responder.respond(validator_changes).ignore()
             }
+            Event::ConsensusRequest(ConsensusRequest::LeaderSchedule { rounds, responder }) => {
+                let leader_schedule = self.leader_schedule(rounds, Timestamp::now());
+                responder.respond(leader_schedule).ignore()
+            }
             Event::DumpState(req @ DumpConsensusStateRequest { era_id, .. }) => {
                 let current_era = match self.current_era() {
                     None => {
@@ -323,4 +323,12 @@ pub(crate) trait ConsensusProtocol<C: Context>: Send {
 
     // TODO: Make this less Highway-specific.
     fn next_round_length(&self) -> Option<TimeDiff>;
+
+    /// Returns the round ID, the start time if known in advance, and the leader of the current
+    /// round and of the `count - 1` following ones.
+    fn upcoming_leaders(
+        &self,
+        now: Timestamp,
+        count: usize,
+    ) -> Vec<(u64, Option<Timestamp>, C::ValidatorId)>;
 }
@@ -17,6 +17,7 @@ use std::{
     fmt::{self, Debug, Formatter},
     fs, io,
     path::{Path, PathBuf},
+    str::FromStr,
     sync::Arc,
     time::Duration,
 };
@@ -31,10 +32,13 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tracing::{debug, error, info, trace, warn};
 
 use casper_hashing::Digest;
-use casper_types::{AsymmetricType, EraId, PublicKey, SecretKey, TimeDiff, Timestamp};
+use casper_types::{
+    AsymmetricType, EraId, ProtocolVersion, PublicKey, SecretKey, TimeDiff, Timestamp, U512,
+};
 
 use crate::{
     components::{
+        block_validator::BlockValidationError,
         consensus::{
             cl_context::{ClContext, Keypair},
             consensus_protocol::{
@@ -44,7 +48,8 @@ use crate::{
             metrics::Metrics,
             validator_change::{ValidatorChange, ValidatorChanges},
             ActionId, ChainspecConsensusExt, Config, ConsensusMessage, ConsensusRequestMessage,
-            Event, HighwayProtocol, NewBlockPayload, ReactorEventT, ResolveValidity, TimerId, Zug,
+            Event, HighwayProtocol, LeaderSchedule, NewBlockPayload, ProposerSlot, ReactorEventT,
+            ResolveValidity, TimerId, Zug,
         },
         network::blocklist::BlocklistJustification,
     },
@@ -119,6 +124,63 @@ pub struct EraSupervisor {
     pub(super) message_delay_failpoint: Failpoint<u64>,
 }
 
+/// The name of the folder, under the storage directory, holding the unit files of each protocol
+/// version in a separate subfolder.
+const UNIT_FILES_FOLDER_NAME: &str = "unit_files";
+
+/// Moves unit files written directly into `unit_files_root` by previous versions of the node into
+/// the current protocol version's subfolder, so that they keep protecting us from equivocating.
+fn move_legacy_unit_files(unit_files_root: &Path, unit_files_folder: &Path) -> io::Result<()> {
+    for entry in fs::read_dir(unit_files_root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let dest_path = unit_files_folder.join(entry.file_name());
+        info!(
+            source = %entry.path().display(),
+            dest = %dest_path.display(),
+            "moving legacy unit file"
+        );
+        fs::rename(entry.path(), dest_path)?;
+    }
+    Ok(())
+}
+
+/// Deletes the unit files of protocol versions older than `protocol_version`.
+///
+/// Eras are never created from before the last activation point, so these unit files can't protect
+/// us from equivocating anymore.
+fn remove_obsolete_unit_files(unit_files_root: &Path, protocol_version: ProtocolVersion) {
+    let entries = match fs::read_dir(unit_files_root) {
+        Ok(entries) => entries,
+        Err(err) => {
+            warn!(?err, "could not read unit files folder");
+            return;
+        }
+    };
+    for entry in entries.flatten() {
+        if !entry
+            .file_type()
+            .map_or(false, |file_type| file_type.is_dir())
+        {
+            continue;
+        }
+        let is_obsolete = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| ProtocolVersion::from_str(name).ok())
+            .map_or(false, |version| version < protocol_version);
+        if !is_obsolete {
+            continue;
+        }
+        info!(path = %entry.path().display(), "removing obsolete unit files");
+        if let Err(err) = fs::remove_dir_all(entry.path()) {
+            warn!(?err, path = %entry.path().display(), "could not remove obsolete unit files");
+        }
+    }
+}
+
 impl Debug for EraSupervisor {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
         let ae: Vec<_> = self.open_eras.keys().collect();
@@ -137,8 +199,11 @@ impl EraSupervisor {
         chainspec: Arc<Chainspec>,
         registry: &Registry,
     ) -> Result<Self, Error> {
-        let unit_files_folder = storage_dir.join("unit_files");
+        let unit_files_root = storage_dir.join(UNIT_FILES_FOLDER_NAME);
+        let unit_files_folder = unit_files_root.join(chainspec.protocol_version().to_string());
         std::fs::create_dir_all(&unit_files_folder)?;
+        move_legacy_unit_files(&unit_files_root, &unit_files_folder)?;
+        remove_obsolete_unit_files(&unit_files_root, chainspec.protocol_version());
         info!(our_id = %public_signing_key, "EraSupervisor pubkey",);
         let metrics = Metrics::new(registry)?;
 
@@ -257,6 +322,29 @@ impl EraSupervisor {
         result
     }
 
+    /// Returns the proposers of the current round of the current era and of the `rounds - 1`
+    /// following ones.
+    ///
+    /// The schedule of the next era becomes available once it is the current one, i.e. once the
+    /// switch block concluding the current era has been executed.
+    pub(super) fn leader_schedule(&self, rounds: usize, now: Timestamp) -> Option<LeaderSchedule> {
+        let (era_id, era) = self.open_eras.iter().last()?;
+        let slots = era
+            .consensus
+            .upcoming_leaders(now, rounds)
+            .into_iter()
+            .map(|(round_id, start, proposer)| ProposerSlot {
+                round_id,
+                start,
+                proposer,
+            })
+            .collect();
+        Some(LeaderSchedule {
+            era_id: *era_id,
+            slots,
+        })
+    }
+
     fn era_seed(booking_block_hash: BlockHash, key_block_seed: Digest) -> u64 {
         let result = Digest::hash_pair(booking_block_hash, key_block_seed).value();
         u64::from_le_bytes(result[0..std::mem::size_of::<u64>()].try_into().unwrap())
@@ -470,9 +558,19 @@ impl EraSupervisor {
         let start_height = key_block.height() + 1;
         let start_time = key_block.timestamp();
 
-        // Validators that were inactive in the previous era will be excluded from leader selection
-        // in the new era.
-        let inactive = report.inactive_validators.iter().cloned().collect();
+        // Validators that were inactive in the previous era, or whose stake dropped below the
+        // minimum bid amount, will be excluded from leader selection in the new era.
+        let minimum_bid_amount = U512::from(self.chainspec.core_config.minimum_bid_amount);
+        let below_minimum_bid = validators
+            .iter()
+            .filter(|(_, stake)| **stake < minimum_bid_amount)
+            .map(|(pub_key, _)| pub_key);
+        let inactive = report
+            .inactive_validators
+            .iter()
+            .chain(below_minimum_bid)
+            .cloned()
+            .collect();
 
         // Validators that were only exposed as faulty after the booking block are still in the new
         // era's validator set but get banned.
@@ -579,13 +677,13 @@ impl EraSupervisor {
         // information necessary to validate evidence that units in the two most recent eras may
         // refer to for cross-era fault tracking.
         if let Some(current_era) = self.current_era() {
-            let mut removed_instance_ids = vec![];
+            let mut removed_eras = vec![];
             let earliest_open_era = current_era.saturating_sub(PAST_OPEN_ERAS);
             let earliest_active_era = current_era.saturating_sub(PAST_EVIDENCE_ERAS);
             self.open_eras.retain(|era_id, era| {
                 if earliest_open_era > *era_id {
                     trace!(era = era_id.value(), "removing obsolete era");
-                    removed_instance_ids.push(*era.consensus.instance_id());
+                    removed_eras.push((*era_id, *era.consensus.instance_id()));
                     false
                 } else if earliest_active_era > *era_id {
                     trace!(era = era_id.value(), "setting old era to evidence only");
@@ -595,7 +693,8 @@ impl EraSupervisor {
                     true
                 }
             });
-            for instance_id in removed_instance_ids {
+            for (removed_era_id, instance_id) in removed_eras {
+                self.metrics.remove_era(removed_era_id);
                 if let Err(err) = fs::remove_file(self.unit_file(&instance_id)) {
                     match err.kind() {
                         io::ErrorKind::NotFound => {}
@@ -603,11 +702,22 @@ impl EraSupervisor {
                     }
                 }
             }
+            for era_id in self.open_eras.keys() {
+                self.update_protocol_state_size(*era_id);
+            }
         }
 
         Ok((era_id, outcomes))
     }
 
+    /// Updates the metric tracking the estimated heap size of the era's protocol state.
+    fn update_protocol_state_size(&self, era_id: EraId) {
+        if let Some(era) = self.open_eras.get(&era_id) {
+            self.metrics
+                .set_protocol_state_size(era_id, era.estimate_heap_size());
+        }
+    }
+
     /// Returns the path to the era's unit file.
     fn unit_file(&self, instance_id: &Digest) -> PathBuf {
         self.unit_files_folder.join(format!(
@@ -882,11 +992,13 @@ impl EraSupervisor {
             era_id,
             sender,
             proposed_block,
-            valid,
+            validation_result,
         } = resolve_validity;
         self.metrics.proposed_block();
+        let valid = validation_result.is_ok();
         let mut effects = Effects::new();
-        if !valid {
+        if let Err(error) = validation_result {
+            info!(%sender, %era_id, %error, "proposed block is invalid");
             effects.extend({
                 effect_builder
                     .announce_block_peer_with_justification(
@@ -1119,6 +1231,7 @@ impl EraSupervisor {
                     "finalized block"
                 );
                 self.metrics.finalized_block(&finalized_block);
+                self.update_protocol_state_size(era_id);
                 // Announce the finalized block.
                 let mut effects = effect_builder
                     .announce_finalized_block(finalized_block.clone())
@@ -1161,7 +1274,9 @@ impl EraSupervisor {
                             era_id,
                             sender,
                             proposed_block,
-                            valid: false,
+                            validation_result: Err(BlockValidationError::ReplayedDeploy(
+                                deploy_hash,
+                            )),
                         },
                     );
                 }
@@ -1409,13 +1524,16 @@ where
                 era_id: proposed_block_era_id,
                 sender,
                 proposed_block: proposed_block.clone(),
-                valid: false,
+                validation_result: Err(BlockValidationError::ReplayedDeployFromPreviousEra {
+                    deploy_era_id,
+                    block_era_id: proposed_block_era_id,
+                }),
             });
         }
     }
 
     let sender_for_validate_block: NodeId = sender;
-    let valid = effect_builder
+    let validation_result = effect_builder
         .validate_block(sender_for_validate_block, proposed_block.clone())
         .await;
 
@@ -1423,7 +1541,7 @@ where
         era_id: proposed_block_era_id,
         sender,
         proposed_block,
-        valid,
+        validation_result,
     })
 }
 
@@ -908,15 +908,16 @@ impl<C: Context> State<C> {
 
     /// Drops all state other than evidence.
     pub(crate) fn retain_evidence_only(&mut self) {
-        self.units.clear();
-        self.blocks.clear();
+        // Replace rather than clear the maps, so that their memory is released.
+        self.units = HashMap::new();
+        self.blocks = HashMap::new();
         for obs in self.panorama.iter_mut() {
             if obs.is_correct() {
                 *obs = Observation::None;
             }
         }
-        self.endorsements.clear();
-        self.incomplete_endorsements.clear();
+        self.endorsements = HashMap::new();
+        self.incomplete_endorsements = HashMap::new();
     }
 
     /// Validates whether a unit with the given panorama and `endorsed` set satisfies the
@@ -858,10 +858,13 @@ fn retain_evidence_only() -> Result<(), AddUnitError<TestContext>> {
     let b0 = add_unit!(state, BOB, 0xB; a0, N, N)?;
     let _a0_prime = add_unit!(state, ALICE, 0xA2; N, N, N)?;
     assert_eq!(&panorama!(F, b0, N), state.panorama());
+    let heap_size_before = state.estimate_heap_size();
     state.retain_evidence_only();
     assert_eq!(&panorama!(F, N, N), state.panorama());
     assert!(!state.has_unit(&a0));
     assert!(state.has_evidence(ALICE));
+    // The memory held by the dropped units is released.
+    assert!(state.estimate_heap_size() < heap_size_before);
     Ok(())
 }
 
@@ -102,6 +102,7 @@ impl<C: Context> PendingVertices<C> {
     /// Drops all pending vertices other than evidence.
     pub(crate) fn retain_evidence_only(&mut self) {
         self.0.retain(|pvv, _| pvv.inner().is_evidence());
+        self.0.shrink_to_fit();
     }
 
     /// Returns number of unique vertices pending in the queue.
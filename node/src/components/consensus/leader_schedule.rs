@@ -0,0 +1,32 @@
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{EraId, PublicKey, Timestamp};
+
+/// A round of consensus and the validator scheduled to propose a block in it.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProposerSlot {
+    /// The round ID: the start of the round in milliseconds since the epoch with Highway, the
+    /// round number with Zug.
+    pub round_id: u64,
+    /// The start of the round, if it is known in advance.
+    pub start: Option<Timestamp>,
+    /// The validator scheduled to propose a block in the round.
+    pub proposer: PublicKey,
+}
+
+/// The upcoming proposers of an era, starting with the current round.
+///
+/// The schedule is deterministic: all nodes compute the same proposer for a given round.  With
+/// Highway, rounds are assumed to have this node's round length if it is a validator, and the
+/// minimum round length otherwise.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct LeaderSchedule {
+    /// The era the rounds belong to.
+    pub era_id: EraId,
+    /// The upcoming rounds, in order.
+    pub slots: Vec<ProposerSlot>,
+}
@@ -1,6 +1,6 @@
-use prometheus::{Gauge, IntGauge, Registry};
+use prometheus::{Gauge, IntGauge, IntGaugeVec, Opts, Registry};
 
-use casper_types::Timestamp;
+use casper_types::{EraId, Timestamp};
 
 use crate::{types::FinalizedBlock, unregister_metric};
 
@@ -17,6 +17,8 @@ pub(super) struct Metrics {
     time_of_last_finalized_block: IntGauge,
     /// The current era.
     pub(super) consensus_current_era: IntGauge,
+    /// The estimated heap size of the protocol state of each open era, in bytes.
+    protocol_state_bytes: IntGaugeVec,
     /// Registry component.
     registry: Registry,
 }
@@ -39,17 +41,26 @@ impl Metrics {
         )?;
         let consensus_current_era =
             IntGauge::new("consensus_current_era", "the current era in consensus")?;
+        let protocol_state_bytes = IntGaugeVec::new(
+            Opts::new(
+                "consensus_protocol_state_bytes",
+                "the estimated heap size of the consensus protocol state of each open era, in bytes",
+            ),
+            &["era"],
+        )?;
         registry.register(Box::new(finalization_time.clone()))?;
         registry.register(Box::new(finalized_block_count.clone()))?;
         registry.register(Box::new(consensus_current_era.clone()))?;
         registry.register(Box::new(time_of_last_proposed_block.clone()))?;
         registry.register(Box::new(time_of_last_finalized_block.clone()))?;
+        registry.register(Box::new(protocol_state_bytes.clone()))?;
         Ok(Metrics {
             finalization_time,
             finalized_block_count,
             time_of_last_proposed_block,
             time_of_last_finalized_block,
             consensus_current_era,
+            protocol_state_bytes,
             registry: registry.clone(),
         })
     }
@@ -69,6 +80,20 @@ impl Metrics {
         self.time_of_last_proposed_block
             .set(Timestamp::now().millis() as i64);
     }
+
+    /// Records the estimated heap size of the era's protocol state.
+    pub(super) fn set_protocol_state_size(&self, era_id: EraId, bytes: usize) {
+        self.protocol_state_bytes
+            .with_label_values(&[&era_id.value().to_string()])
+            .set(bytes as i64);
+    }
+
+    /// Stops tracking the protocol state of an era which has been removed.
+    pub(super) fn remove_era(&self, era_id: EraId) {
+        let _ = self
+            .protocol_state_bytes
+            .remove_label_values(&[&era_id.value().to_string()]);
+    }
 }
 
 impl Drop for Metrics {
@@ -78,5 +103,6 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.consensus_current_era);
         unregister_metric!(self.registry, self.time_of_last_finalized_block);
         unregister_metric!(self.registry, self.time_of_last_proposed_block);
+        unregister_metric!(self.registry, self.protocol_state_bytes);
     }
 }
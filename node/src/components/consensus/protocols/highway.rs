@@ -35,7 +35,7 @@ use crate::{
                 Dependency, GetDepOutcome, Highway, Params, PreValidatedVertex, ValidVertex,
                 Vertex, VertexError,
             },
-            state::{IndexObservation, IndexPanorama, Observation},
+            state::{self, IndexObservation, IndexPanorama, Observation},
             synchronizer::Synchronizer,
         },
         protocols,
@@ -1088,7 +1088,9 @@ where
     fn set_evidence_only(&mut self) {
         // TODO: We could also drop the finality detector and round success meter here. Maybe make
         // HighwayProtocol an enum with an EvidenceOnly variant?
-        self.pending_values.clear();
+        self.pending_values = HashMap::new();
+        self.pvv_cache.retain(|_, pvv| pvv.inner().is_evidence());
+        self.pvv_cache.shrink_to_fit();
         self.synchronizer.retain_evidence_only();
         self.highway.retain_evidence_only();
         self.evidence_only = true;
@@ -1147,6 +1149,27 @@ where
     fn next_round_length(&self) -> Option<TimeDiff> {
         self.highway.next_round_length()
     }
+
+    fn upcoming_leaders(
+        &self,
+        now: Timestamp,
+        count: usize,
+    ) -> Vec<(u64, Option<Timestamp>, C::ValidatorId)> {
+        let state = self.highway.state();
+        let round_len = self
+            .highway
+            .next_round_length()
+            .unwrap_or_else(|| state.params().min_round_length());
+        let mut round_id = state::round_id(now, round_len);
+        let mut leaders = Vec::with_capacity(count);
+        for _ in 0..count {
+            if let Some(leader) = self.highway.validators().id(state.leader(round_id)) {
+                leaders.push((round_id.millis(), Some(round_id), leader.clone()));
+            }
+            round_id += round_len;
+        }
+        leaders
+    }
 }
 
 /// Maximum possible rounds in one era.
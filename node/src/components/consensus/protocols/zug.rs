@@ -2427,6 +2427,21 @@ where
     fn next_round_length(&self) -> Option<TimeDiff> {
         Some(self.params.min_block_time())
     }
+
+    fn upcoming_leaders(
+        &self,
+        _now: Timestamp,
+        count: usize,
+    ) -> Vec<(u64, Option<Timestamp>, C::ValidatorId)> {
+        // Zug rounds end when they are decided, so their start times can't be predicted.
+        (self.current_round..=RoundId::MAX)
+            .take(count)
+            .filter_map(|round_id| {
+                let leader = self.validators.id(self.leader(round_id))?;
+                Some((u64::from(round_id), None, leader.clone()))
+            })
+            .collect()
+    }
 }
 
 mod specimen_support {
@@ -1034,3 +1034,24 @@ fn update_proposal_timeout() {
         );
     }
 }
+
+#[test]
+fn zug_lists_upcoming_leaders() {
+    let (weights, validators) = abc_weights(60, 30, 10);
+    let alice_idx = validators.get_index(&*ALICE_PUBLIC_KEY).unwrap();
+    let bob_idx = validators.get_index(&*BOB_PUBLIC_KEY).unwrap();
+    let carol_idx = validators.get_index(&*CAROL_PUBLIC_KEY).unwrap();
+
+    let leader_seq = &[bob_idx, alice_idx, carol_idx];
+    let zug = new_test_zug(weights, vec![], leader_seq);
+
+    let leaders = zug.upcoming_leaders(Timestamp::from(100000), 3);
+    assert_eq!(
+        leaders,
+        vec![
+            (0, None, BOB_PUBLIC_KEY.clone()),
+            (1, None, ALICE_PUBLIC_KEY.clone()),
+            (2, None, CAROL_PUBLIC_KEY.clone()),
+        ]
+    );
+}
@@ -4,14 +4,16 @@ mod config;
 mod error;
 mod metrics;
 mod operations;
+mod speculative_exec_cache;
+mod step_outcome;
 #[cfg(test)]
 mod tests;
 mod types;
+mod utilization;
 
 use std::{
     cmp::Ordering,
     collections::{BTreeMap, BTreeSet, HashMap},
-    convert::TryInto,
     fmt::{self, Debug, Display, Formatter},
     path::Path,
     sync::{Arc, Mutex},
@@ -30,10 +32,13 @@ use tracing::{debug, error, info, trace};
 use casper_execution_engine::{
     core::engine_state::{
         self,
-        engine_config::{FeeHandling, RefundHandling},
+        engine_config::{
+            AccountCreationPolicy, DelegationRateChangeLimit, FeeHandling, RefundHandling,
+            StorageRent,
+        },
         genesis::GenesisError,
-        ChainspecRegistry, DeployItem, EngineConfigBuilder, EngineState, GenesisSuccess,
-        SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
+        AuctionCompactionResult, ChainspecRegistry, DeployItem, EngineConfig, EngineConfigBuilder,
+        EngineState, GenesisSuccess, SystemContractRegistry, UpgradeConfig, UpgradeSuccess,
     },
     shared::{newtypes::CorrelationId, system_config::SystemConfig, wasm_config::WasmConfig},
     storage::{
@@ -42,24 +47,33 @@ use casper_execution_engine::{
     },
 };
 use casper_hashing::Digest;
-use casper_types::{bytesrepr::Bytes, EraId, ProtocolVersion, PublicKey, Timestamp};
+use casper_types::{
+    bytesrepr::{Bytes, ToBytes},
+    EraId, ProtocolVersion, PublicKey, Timestamp,
+};
 
 use crate::{
-    components::{fetcher::FetchResponse, Component, ComponentState},
+    components::{
+        fetcher::{FetchItem, FetchResponse},
+        Component, ComponentState,
+    },
     effect::{
         announcements::{
             ContractRuntimeAnnouncement, FatalAnnouncement, MetaBlockAnnouncement,
             UnexecutedBlockAnnouncement,
         },
         incoming::{TrieDemand, TrieRequest, TrieRequestIncoming},
-        requests::{ContractRuntimeRequest, NetworkRequest, StorageRequest},
-        EffectBuilder, EffectExt, Effects,
+        requests::{
+            BlockSynchronizerRequest, ContractRuntimeRequest, NetworkRequest, StorageRequest,
+        },
+        EffectBuilder, EffectExt, EffectOptionExt, Effects,
     },
     fatal,
     protocol::Message,
     types::{
-        ActivationPoint, BlockHash, BlockHeader, Chainspec, ChainspecRawBytes, ChunkingError,
-        Deploy, FinalizedBlock, MetaBlock, MetaBlockState, TrieOrChunk, TrieOrChunkId,
+        chainspec::DeployConfig, ActivationPoint, BlockHash, BlockHeader, Chainspec,
+        ChainspecRawBytes, ChunkingError, Deploy, FinalizedBlock, MetaBlock, MetaBlockState,
+        TrieOrChunk, TrieOrChunkId,
     },
     NodeRng,
 };
@@ -68,9 +82,15 @@ pub(crate) use error::{BlockExecutionError, ConfigError};
 use metrics::Metrics;
 pub use operations::execute_finalized_block;
 use operations::execute_only;
+use speculative_exec_cache::{CacheKey, SpeculativeExecCache};
+pub(crate) use step_outcome::StepOutcome;
 pub(crate) use types::{
     BlockAndExecutionResults, EraValidatorsRequest, StepEffectAndUpcomingEraValidators,
 };
+use utilization::UtilizationTracker;
+pub(crate) use utilization::{
+    BlockUtilization, EraUtilization, ResourceUtilization, UtilizationSummary,
+};
 
 const COMPONENT_NAME: &str = "contract_runtime";
 
@@ -93,6 +113,10 @@ pub(crate) enum ContractRuntimeError {
 /// TODO: Fine tune this constant to the machine executing the node.
 const MAX_PARALLEL_INTENSIVE_TASKS: usize = 4;
 
+/// Maximum number of trie nodes marked or swept in a single transaction while pruning global state,
+/// which commits wait for.
+const TRIE_PRUNING_BATCH_SIZE: usize = 10_000;
+
 pub(crate) const APPROVALS_CHECKSUM_NAME: &str = "approvals_checksum";
 pub(crate) const EXECUTION_RESULTS_CHECKSUM_NAME: &str = "execution_results_checksum";
 
@@ -130,7 +154,7 @@ pub struct SpeculativeExecutionState {
 
 /// State to use to construct the next block in the blockchain. Includes the state root hash for the
 /// execution engine as well as certain values the next header will be based on.
-#[derive(DataSize, Debug, Clone, Serialize)]
+#[derive(DataSize, Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct ExecutionPreState {
     /// The height of the next `Block` to be constructed. Note that this must match the height of
     /// the `FinalizedBlock` used to generate the block.
@@ -171,7 +195,21 @@ impl ExecutionPreState {
     }
 }
 
-type ExecQueue = Arc<Mutex<BTreeMap<u64, (FinalizedBlock, Vec<Deploy>, MetaBlockState)>>>;
+/// A finalized block waiting in the execution queue for its parent to be executed.
+#[derive(DataSize)]
+struct QueuedBlock {
+    finalized_block: FinalizedBlock,
+    deploys: Vec<Deploy>,
+    meta_block_state: MetaBlockState,
+    /// When the block was put into the queue.
+    enqueued_at: Timestamp,
+}
+
+type ExecQueue = Arc<Mutex<BTreeMap<u64, QueuedBlock>>>;
+
+/// The post-state of each executed block, by the height of its child, which must be executed
+/// against it.
+type PinnedPreStates = Arc<Mutex<BTreeMap<u64, ExecutionPreState>>>;
 
 #[derive(Debug, From, Serialize)]
 pub(crate) enum Event {
@@ -183,6 +221,9 @@ pub(crate) enum Event {
 
     #[from]
     TrieDemand(TrieDemand),
+
+    /// Tries started being pruned after executing the switch block of the given era.
+    PruneGlobalState(EraId),
 }
 
 impl Display for Event {
@@ -193,6 +234,9 @@ impl Display for Event {
             }
             Event::TrieRequestIncoming(req) => write!(f, "trie request incoming: {}", req),
             Event::TrieDemand(demand) => write!(f, "trie demand: {}", demand),
+            Event::PruneGlobalState(era_id) => {
+                write!(f, "prune global state after switch block of era {}", era_id)
+            }
         }
     }
 }
@@ -208,10 +252,24 @@ pub(crate) struct ContractRuntime {
 
     /// Finalized blocks waiting for their pre-state hash to start executing.
     exec_queue: ExecQueue,
+    /// The execution queue depth above which the queue is considered full.
+    max_exec_queue_depth: usize,
+    /// The pre-states pinned by executed blocks for their children.
+    pinned_pre_states: PinnedPreStates,
     /// Cached instance of a [`SystemContractRegistry`].
     system_contract_registry: Option<SystemContractRegistry>,
     activation_point: ActivationPoint,
     prune_batch_size: u64,
+    /// The era from which execution results are reported as V2 execution results, if any.
+    execution_results_v2_activation_point: Option<EraId>,
+    /// The number of eras preceding the era following the latest executed switch block whose
+    /// global state is retained, if tries unreachable from it are pruned.
+    trie_pruning_retention_eras: Option<u64>,
+    /// Results of speculative executions, reused for identical requests.
+    #[data_size(skip)]
+    speculative_exec_cache: SpeculativeExecCache,
+    /// Utilization of the capacity of executed blocks.
+    utilization: Arc<Mutex<UtilizationTracker>>,
 }
 
 impl Debug for ContractRuntime {
@@ -226,6 +284,7 @@ where
         + From<ContractRuntimeAnnouncement>
         + From<NetworkRequest<Message>>
         + From<StorageRequest>
+        + From<BlockSynchronizerRequest>
         + From<MetaBlockAnnouncement>
         + From<UnexecutedBlockAnnouncement>
         + From<FatalAnnouncement>
@@ -247,6 +306,9 @@ where
                 self.handle_trie_request(effect_builder, request)
             }
             Event::TrieDemand(demand) => self.handle_trie_demand(demand),
+            Event::PruneGlobalState(switch_block_era_id) => {
+                self.handle_prune_global_state(effect_builder, switch_block_era_id)
+            }
         }
     }
 
@@ -266,6 +328,19 @@ impl ContractRuntime {
             .len()
     }
 
+    /// Whether the execution queue holds more blocks than configured, in which case no further
+    /// blocks should be acquired for execution until the backlog has been worked off.
+    pub(crate) fn is_exec_queue_full(&self) -> bool {
+        self.queue_depth() > self.max_exec_queue_depth
+    }
+
+    fn update_exec_queue_metrics(&self) {
+        let exec_queue = self.exec_queue.lock().expect(
+            "components::contract_runtime: couldn't update execution queue metrics; mutex poisoned",
+        );
+        self.metrics.update_exec_queue(&exec_queue);
+    }
+
     /// Handles an incoming request to get a trie.
     fn handle_trie_request<REv>(
         &self,
@@ -322,6 +397,45 @@ impl ContractRuntime {
         }
     }
 
+    /// Handles tries having started being pruned after executing the switch block of the given era.
+    fn handle_prune_global_state<REv>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        switch_block_era_id: EraId,
+    ) -> Effects<Event>
+    where
+        REv: From<StorageRequest> + From<BlockSynchronizerRequest> + Send,
+    {
+        let retention_eras = match self.trie_pruning_retention_eras {
+            Some(retention_eras) => retention_eras,
+            None => return Effects::new(),
+        };
+        // Blocks executed while pruning are executed against these pre-states.
+        let mut pending_state_root_hashes = vec![
+            self.execution_pre_state
+                .lock()
+                .expect("components::contract_runtime: couldn't get pre-state; mutex poisoned")
+                .pre_state_root_hash,
+        ];
+        pending_state_root_hashes.extend(
+            self.pinned_pre_states
+                .lock()
+                .expect(
+                    "components::contract_runtime: couldn't get pinned pre-states; mutex poisoned",
+                )
+                .values()
+                .map(|pinned_pre_state| pinned_pre_state.pre_state_root_hash),
+        );
+        Self::prune_global_state(
+            Arc::clone(&self.engine_state),
+            effect_builder,
+            switch_block_era_id,
+            retention_eras,
+            pending_state_root_hashes,
+        )
+        .ignore()
+    }
+
     /// Handles a contract runtime request.
     fn handle_contract_runtime_request<REv>(
         &mut self,
@@ -377,6 +491,23 @@ impl ContractRuntime {
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetBalances {
+                balances_request,
+                responder,
+            } => {
+                trace!(?balances_request, "balances");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let start = Instant::now();
+                    let result = engine_state.get_balances(correlation_id, balances_request);
+                    metrics.get_balances.observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "balances result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::GetEraValidators { request, responder } => {
                 trace!(?request, "get era validators request");
                 let engine_state = Arc::clone(&self.engine_state);
@@ -492,14 +623,23 @@ impl ContractRuntime {
                         let engine_state = Arc::clone(&self.engine_state);
                         let metrics = Arc::clone(&self.metrics);
                         let shared_pre_state = Arc::clone(&self.execution_pre_state);
+                        let pinned_pre_states = Arc::clone(&self.pinned_pre_states);
                         let activation_point = self.activation_point;
                         let prune_batch_size = self.prune_batch_size;
+                        let execution_results_v2_activation_point =
+                            self.execution_results_v2_activation_point;
+                        let trie_pruning_retention_eras = self.trie_pruning_retention_eras;
+                        let speculative_exec_cache = self.speculative_exec_cache.clone();
+                        let utilization = Arc::clone(&self.utilization);
                         effects.extend(
                             Self::execute_finalized_block_or_requeue(
                                 engine_state,
                                 metrics,
                                 exec_queue,
                                 shared_pre_state,
+                                pinned_pre_states,
+                                speculative_exec_cache,
+                                utilization,
                                 current_pre_state.clone(),
                                 effect_builder,
                                 protocol_version,
@@ -508,9 +648,11 @@ impl ContractRuntime {
                                 activation_point,
                                 key_block_height_for_activation_point,
                                 prune_batch_size,
+                                execution_results_v2_activation_point,
+                                trie_pruning_retention_eras,
                                 meta_block_state,
                             )
-                            .ignore(),
+                            .map_some(Event::PruneGlobalState),
                         )
                     }
                     Ordering::Greater => {
@@ -523,15 +665,19 @@ impl ContractRuntime {
                             finalized_block_height,
                             deploys.len()
                         );
+                        let queued_block = QueuedBlock {
+                            finalized_block,
+                            deploys,
+                            meta_block_state,
+                            enqueued_at: Timestamp::now(),
+                        };
                         exec_queue
                             .lock()
                             .expect("components::contract_runtime: couldn't enqueue block for execution; mutex poisoned")
-                            .insert(finalized_block_height, (finalized_block, deploys, meta_block_state));
+                            .insert(finalized_block_height, queued_block);
                     }
                 }
-                self.metrics
-                    .exec_queue_size
-                    .set(self.queue_depth().try_into().unwrap_or(i64::MIN));
+                self.update_exec_queue_metrics();
                 effects
             }
             ContractRuntimeRequest::GetBids {
@@ -551,6 +697,46 @@ impl ContractRuntime {
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetUnbondingPurses {
+                get_unbonding_purses_request,
+                responder,
+            } => {
+                trace!(
+                    ?get_unbonding_purses_request,
+                    "get unbonding purses request"
+                );
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let start = Instant::now();
+                    let result = engine_state
+                        .get_unbonding_purses(correlation_id, get_unbonding_purses_request);
+                    metrics
+                        .get_unbonding_purses
+                        .observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "get unbonding purses result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
+            ContractRuntimeRequest::GetEntity {
+                get_entity_request,
+                responder,
+            } => {
+                trace!(?get_entity_request, "get entity request");
+                let engine_state = Arc::clone(&self.engine_state);
+                let metrics = Arc::clone(&self.metrics);
+                async move {
+                    let correlation_id = CorrelationId::new();
+                    let start = Instant::now();
+                    let result = engine_state.get_entity(correlation_id, get_entity_request);
+                    metrics.get_entity.observe(start.elapsed().as_secs_f64());
+                    trace!(?result, "get entity result");
+                    responder.respond(result).await
+                }
+                .ignore()
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash,
                 responder,
@@ -569,22 +755,45 @@ impl ContractRuntime {
             ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
+                collect_gas_profile,
                 responder,
             } => {
+                let cache_key = CacheKey::new(
+                    deploy.fetch_id(),
+                    execution_prestate.state_root_hash,
+                    collect_gas_profile,
+                );
+                if let Some(result) = self.speculative_exec_cache.get(&cache_key) {
+                    debug!(deploy_hash = %deploy.hash(), "reusing speculative execution result");
+                    return responder.respond(Ok(result)).ignore();
+                }
                 let engine_state = Arc::clone(&self.engine_state);
+                let speculative_exec_cache = self.speculative_exec_cache.clone();
                 async move {
                     let result = run_intensive_task(move || {
                         execute_only(
                             engine_state.as_ref(),
                             execution_prestate,
                             DeployItem::from((*deploy).clone()),
+                            collect_gas_profile,
                         )
                     })
                     .await;
+                    if let Ok(result) = &result {
+                        speculative_exec_cache.insert(cache_key, result.clone());
+                    }
                     responder.respond(result).await
                 }
                 .ignore()
             }
+            ContractRuntimeRequest::GetBlockUtilization { responder } => {
+                let block_utilization = self
+                    .utilization
+                    .lock()
+                    .expect("components::contract_runtime: couldn't get block utilization; mutex poisoned")
+                    .block_utilization();
+                responder.respond(block_utilization).ignore()
+            }
         }
     }
 }
@@ -599,9 +808,15 @@ impl ContractRuntime {
         system_config: SystemConfig,
         max_associated_keys: u32,
         max_runtime_call_stack_height: u32,
+        max_cl_value_size: u32,
+        max_named_keys: u32,
         minimum_delegation_amount: u64,
+        minimum_bid_amount: u64,
+        record_auction_snapshots: bool,
         activation_point: ActivationPoint,
         prune_batch_size: u64,
+        execution_results_v2_activation_point: Option<EraId>,
+        unbonding_delay: u64,
         strict_argument_checking: bool,
         vesting_schedule_period_millis: u64,
         max_delegators_per_validator: Option<u32>,
@@ -611,6 +826,10 @@ impl ContractRuntime {
         allow_unrestricted_transfers: bool,
         refund_handling: RefundHandling,
         fee_handling: FeeHandling,
+        storage_rent: StorageRent,
+        delegation_rate_change_limit: DelegationRateChangeLimit,
+        account_creation_policy: AccountCreationPolicy,
+        deploy_config: &DeployConfig,
     ) -> Result<Self, ConfigError> {
         // TODO: This is bogus, get rid of this
         let execution_pre_state = Arc::new(Mutex::new(ExecutionPreState {
@@ -620,25 +839,16 @@ impl ContractRuntime {
             parent_seed: Default::default(),
         }));
 
-        let environment = Arc::new(LmdbEnvironment::new(
-            storage_dir,
-            contract_runtime_config.max_global_state_size_or_default(),
-            contract_runtime_config.max_readers_or_default(),
-            contract_runtime_config.manual_sync_enabled_or_default(),
-        )?);
-
-        let trie_store = Arc::new(LmdbTrieStore::new(
-            &environment,
-            None,
-            DatabaseFlags::empty(),
-        )?);
-
-        let global_state = LmdbGlobalState::empty(environment, trie_store)?;
+        let global_state = open_global_state(storage_dir, contract_runtime_config)?;
         let engine_config = EngineConfigBuilder::new()
             .with_max_query_depth(contract_runtime_config.max_query_depth_or_default())
             .with_max_associated_keys(max_associated_keys)
             .with_max_runtime_call_stack_height(max_runtime_call_stack_height)
+            .with_max_cl_value_size(max_cl_value_size)
+            .with_max_named_keys(max_named_keys)
             .with_minimum_delegation_amount(minimum_delegation_amount)
+            .with_minimum_bid_amount(minimum_bid_amount)
+            .with_record_auction_snapshots(record_auction_snapshots)
             .with_strict_argument_checking(strict_argument_checking)
             .with_vesting_schedule_period_millis(vesting_schedule_period_millis)
             .with_max_delegators_per_validator(max_delegators_per_validator)
@@ -649,6 +859,10 @@ impl ContractRuntime {
             .with_allow_unrestricted_transfers(allow_unrestricted_transfers)
             .with_refund_handling(refund_handling)
             .with_fee_handling(fee_handling)
+            .with_storage_rent(storage_rent)
+            .with_delegation_rate_change_limit(delegation_rate_change_limit)
+            .with_account_creation_policy(account_creation_policy)
+            .with_module_cache_capacity(contract_runtime_config.module_cache_size_or_default())
             .build();
 
         let engine_state = Arc::new(EngineState::new(global_state, engine_config));
@@ -662,12 +876,33 @@ impl ContractRuntime {
             metrics,
             protocol_version,
             exec_queue: Arc::new(Mutex::new(BTreeMap::new())),
+            max_exec_queue_depth: contract_runtime_config.max_exec_queue_depth_or_default(),
+            pinned_pre_states: Arc::new(Mutex::new(BTreeMap::new())),
             system_contract_registry: None,
             activation_point,
             prune_batch_size,
+            execution_results_v2_activation_point,
+            // Global state is kept for at least the unbonding delay.
+            trie_pruning_retention_eras: contract_runtime_config
+                .trie_pruning_retention_eras
+                .map(|retention_eras| retention_eras.max(unbonding_delay)),
+            speculative_exec_cache: SpeculativeExecCache::new(
+                contract_runtime_config.speculative_exec_cache_ttl_or_default(),
+            ),
+            utilization: Arc::new(Mutex::new(UtilizationTracker::new(deploy_config))),
         })
     }
 
+    /// Opens the global state stored in `storage_dir` outside of a running node, e.g. to export or
+    /// import a global state snapshot.
+    pub(crate) fn open_engine_state(
+        storage_dir: &Path,
+        contract_runtime_config: &Config,
+    ) -> Result<EngineState<LmdbGlobalState>, ConfigError> {
+        let global_state = open_global_state(storage_dir, contract_runtime_config)?;
+        Ok(EngineState::new(global_state, EngineConfig::default()))
+    }
+
     /// Commits a genesis request.
     pub(crate) fn commit_genesis(
         &self,
@@ -703,9 +938,12 @@ impl ContractRuntime {
         result
     }
 
+    /// Commits the upgrade, then deletes the auction's stale records from the resulting state if
+    /// `compact_auction_state` is set.
     pub(crate) fn commit_upgrade(
         &self,
         upgrade_config: UpgradeConfig,
+        compact_auction_state: bool,
     ) -> Result<UpgradeSuccess, engine_state::Error> {
         debug!(?upgrade_config, "upgrade");
         let start = Instant::now();
@@ -715,7 +953,35 @@ impl ContractRuntime {
         result.post_state_hash = self
             .engine_state
             .write_scratch_to_db(pre_state_hash, scratch_state.into_inner())?;
+        // Deletions bypass the scratch state, so the compaction applies to the written state.
+        if compact_auction_state {
+            match self
+                .engine_state
+                .commit_auction_compaction(CorrelationId::new(), result.post_state_hash)?
+            {
+                AuctionCompactionResult::Success {
+                    post_state_hash,
+                    reclaimed_keys,
+                } => {
+                    info!(
+                        reclaimed_keys = reclaimed_keys.len(),
+                        %post_state_hash,
+                        "compacted auction state"
+                    );
+                    debug!(?reclaimed_keys, "reclaimed auction keys");
+                    result.post_state_hash = post_state_hash;
+                }
+                AuctionCompactionResult::RootNotFound | AuctionCompactionResult::DoesNotExist => {
+                    return Err(engine_state::Error::RootNotFound(result.post_state_hash));
+                }
+            }
+        }
         self.engine_state.flush_environment()?;
+        // The upgrade changes the global state between the switch block and its child.
+        self.pinned_pre_states
+            .lock()
+            .expect("components::contract_runtime: couldn't unpin pre-states; mutex poisoned")
+            .clear();
         self.metrics
             .commit_upgrade
             .observe(start.elapsed().as_secs_f64());
@@ -727,6 +993,7 @@ impl ContractRuntime {
         let next_block_height = sequential_block_state.next_block_height;
         let mut execution_pre_state = self.execution_pre_state.lock().unwrap();
         *execution_pre_state = sequential_block_state;
+        self.speculative_exec_cache.clear();
 
         {
             let mut exec_queue = self.exec_queue
@@ -735,19 +1002,32 @@ impl ContractRuntime {
                     "components::contract_runtime: couldn't initialize contract runtime block execution queue; mutex poisoned"
                 );
             *exec_queue = exec_queue.split_off(&execution_pre_state.next_block_height);
-            self.metrics
-                .exec_queue_size
-                .set(exec_queue.len().try_into().unwrap_or(i64::MIN));
+            self.metrics.update_exec_queue(&exec_queue);
+        }
+        {
+            // Pins of skipped blocks are not needed anymore, but a pin of the next block is kept:
+            // if it differs from the new pre-state, our execution of its parent diverged.
+            let mut pinned_pre_states = self.pinned_pre_states.lock().expect(
+                "components::contract_runtime: couldn't prune pinned pre-states; mutex poisoned",
+            );
+            *pinned_pre_states = pinned_pre_states.split_off(&next_block_height);
         }
         debug!(next_block_height, "ContractRuntime: set initial state");
     }
 
+    /// Executes the given block, or requeues it if it's not the next block to execute.
+    ///
+    /// Returns the era ID of the executed block if it is a switch block after which tries started
+    /// being pruned.
     #[allow(clippy::too_many_arguments)]
     async fn execute_finalized_block_or_requeue<REv>(
         engine_state: Arc<EngineState<LmdbGlobalState>>,
         metrics: Arc<Metrics>,
         exec_queue: ExecQueue,
         shared_pre_state: Arc<Mutex<ExecutionPreState>>,
+        pinned_pre_states: PinnedPreStates,
+        speculative_exec_cache: SpeculativeExecCache,
+        utilization: Arc<Mutex<UtilizationTracker>>,
         current_pre_state: ExecutionPreState,
         effect_builder: EffectBuilder<REv>,
         protocol_version: ProtocolVersion,
@@ -756,8 +1036,11 @@ impl ContractRuntime {
         activation_point: ActivationPoint,
         key_block_height_for_activation_point: u64,
         prune_batch_size: u64,
+        execution_results_v2_activation_point: Option<EraId>,
+        trie_pruning_retention_eras: Option<u64>,
         mut meta_block_state: MetaBlockState,
-    ) where
+    ) -> Option<EraId>
+    where
         REv: From<ContractRuntimeRequest>
             + From<ContractRuntimeAnnouncement>
             + From<StorageRequest>
@@ -767,35 +1050,98 @@ impl ContractRuntime {
     {
         debug!("ContractRuntime: execute_finalized_block_or_requeue");
         let contract_runtime_metrics = metrics.clone();
+        let pruning_engine_state = Arc::clone(&engine_state);
+        let deploys_size = deploys
+            .iter()
+            .filter(|deploy| !deploy.category().is_transfer())
+            .map(|deploy| deploy.serialized_length() as u64)
+            .sum();
+        let block_height = finalized_block.height();
+        let pinned_pre_state = {
+            let mut pinned_pre_states = pinned_pre_states.lock().expect(
+                "components::contract_runtime: couldn't get pinned pre-state; mutex poisoned",
+            );
+            let pinned_pre_state = pinned_pre_states.remove(&block_height);
+            *pinned_pre_states = pinned_pre_states.split_off(&block_height);
+            pinned_pre_state
+        };
+        let result = match pinned_pre_state.filter(|pinned| *pinned != current_pre_state) {
+            Some(pinned_pre_state) => Err(BlockExecutionError::PreStateMismatch {
+                block_height,
+                expected: Box::new(pinned_pre_state),
+                actual: Box::new(current_pre_state),
+            }),
+            None => {
+                run_intensive_task(move || {
+                    debug!("ContractRuntime: execute_finalized_block");
+                    execute_finalized_block(
+                        engine_state.as_ref(),
+                        Some(contract_runtime_metrics),
+                        protocol_version,
+                        current_pre_state,
+                        finalized_block,
+                        deploys,
+                        activation_point.era_id(),
+                        key_block_height_for_activation_point,
+                        prune_batch_size,
+                        execution_results_v2_activation_point,
+                    )
+                })
+                .await
+            }
+        };
         let BlockAndExecutionResults {
             block,
             approvals_hashes,
             execution_results,
             maybe_step_effect_and_upcoming_era_validators,
-        } = match run_intensive_task(move || {
-            debug!("ContractRuntime: execute_finalized_block");
-            execute_finalized_block(
-                engine_state.as_ref(),
-                Some(contract_runtime_metrics),
-                protocol_version,
-                current_pre_state,
-                finalized_block,
-                deploys,
-                activation_point.era_id(),
-                key_block_height_for_activation_point,
-                prune_batch_size,
-            )
-        })
-        .await
-        {
+        } = match result {
             Ok(block_and_execution_results) => block_and_execution_results,
+            Err(
+                error @ (BlockExecutionError::MissingPreStateRoot { .. }
+                | BlockExecutionError::PreStateMismatch { .. }),
+            ) => {
+                // The block is not executed: this node's view of the parent's global state differs
+                // from the one the block was finalized on, and executing it would fork the chain.
+                metrics.refused_blocks.inc();
+                error!(%error, "possible fork: refusing to execute block");
+                fatal!(effect_builder, "{}", error).await;
+                return None;
+            }
             Err(error) => {
                 error!(%error, "failed to execute block");
-                return fatal!(effect_builder, "{}", error).await;
+                fatal!(effect_builder, "{}", error).await;
+                return None;
             }
         };
 
+        // Tries written from now on, including by the execution of the following blocks, are
+        // retained by the pruning, which runs once the block is announced.
+        let pruned_era_id =
+            if trie_pruning_retention_eras.is_some() && block.header().is_switch_block() {
+                match pruning_engine_state.start_trie_pruning() {
+                    Ok(true) => Some(block.header().era_id()),
+                    Ok(false) => {
+                        debug!("ContractRuntime: not pruning global state, already being pruned");
+                        None
+                    }
+                    Err(error) => {
+                        error!(%error, "ContractRuntime: failed to start pruning global state");
+                        None
+                    }
+                }
+            } else {
+                None
+            };
+
         let new_execution_pre_state = ExecutionPreState::from_block_header(block.header());
+        pinned_pre_states
+            .lock()
+            .expect("components::contract_runtime: couldn't pin pre-state; mutex poisoned")
+            .insert(
+                new_execution_pre_state.next_block_height,
+                new_execution_pre_state.clone(),
+            );
         {
             // The `shared_pre_state` could have been set to a block we just fully synced after
             // doing a sync leap (via a call to `set_initial_state`).  We should not allow a block
@@ -808,6 +1154,8 @@ impl ContractRuntime {
                     "ContractRuntime: updating shared pre-state",
                 );
                 *shared_pre_state = new_execution_pre_state.clone();
+                // Speculative executions are made against the new tip from now on.
+                speculative_exec_cache.clear();
             } else {
                 debug!(
                     current_next_block_height = shared_pre_state.next_block_height,
@@ -824,6 +1172,15 @@ impl ContractRuntime {
             mut upcoming_era_validators,
         }) = maybe_step_effect_and_upcoming_era_validators
         {
+            let maybe_step_outcome = block.header().era_end().map(|era_end| {
+                StepOutcome::new(
+                    current_era_id,
+                    era_end.era_report(),
+                    &step_execution_journal,
+                    upcoming_era_validators.clone(),
+                )
+            });
+
             effect_builder
                 .announce_commit_step_success(current_era_id, step_execution_journal)
                 .await;
@@ -845,6 +1202,10 @@ impl ContractRuntime {
             effect_builder
                 .announce_upcoming_era_validators(current_era_id, upcoming_era_validators)
                 .await;
+
+            if let Some(step_outcome) = maybe_step_outcome {
+                effect_builder.announce_step_outcome(step_outcome).await;
+            }
         }
 
         info!(
@@ -855,6 +1216,25 @@ impl ContractRuntime {
             "executed block"
         );
 
+        let maybe_era_utilization = utilization
+            .lock()
+            .expect(
+                "components::contract_runtime: couldn't record block utilization; mutex poisoned",
+            )
+            .record_block(&block, deploys_size, &execution_results);
+        if let Some(era_utilization) = maybe_era_utilization {
+            info!(
+                era = era_utilization.era_id.value(),
+                switch_block_hash = %era_utilization.switch_block_hash,
+                block_count = era_utilization.summary.block_count,
+                gas_used = era_utilization.summary.gas.used,
+                gas_limit = era_utilization.summary.gas.limit,
+                size = era_utilization.summary.size.used,
+                max_size = era_utilization.summary.size.limit,
+                "era block utilization"
+            );
+        }
+
         let execution_results_map: HashMap<_, _> = execution_results
             .iter()
             .cloned()
@@ -898,15 +1278,92 @@ impl ContractRuntime {
             let queue = &mut *exec_queue
                 .lock()
                 .expect("components::contract_runtime: couldn't get next block for execution; mutex poisoned");
-            queue.remove(&new_execution_pre_state.next_block_height)
+            let next_block = queue.remove(&new_execution_pre_state.next_block_height);
+            metrics.update_exec_queue(queue);
+            next_block
         };
-        if let Some((finalized_block, deploys, meta_block_state)) = next_block {
-            metrics.exec_queue_size.dec();
-            debug!("ContractRuntime: next block enqueue_block_for_execution");
+        if let Some(QueuedBlock {
+            finalized_block,
+            deploys,
+            meta_block_state,
+            enqueued_at,
+        }) = next_block
+        {
+            debug!(
+                waited = %enqueued_at.elapsed(),
+                "ContractRuntime: next block enqueue_block_for_execution"
+            );
             effect_builder
                 .enqueue_block_for_execution(finalized_block, deploys, meta_block_state)
                 .await;
         }
+        pruned_era_id
+    }
+
+    /// Prunes the tries only reachable from the global state of blocks preceding the
+    /// `retention_eras` eras before the era following the switch block of `switch_block_era_id`,
+    /// once tries started being pruned.
+    ///
+    /// The global state of the switch block concluding the era preceding the oldest retained era is
+    /// retained too, as it is the pre-state of the oldest retained block, as are the given
+    /// pre-states of pending executions and the tries being synced.
+    async fn prune_global_state<REv>(
+        engine_state: Arc<EngineState<LmdbGlobalState>>,
+        effect_builder: EffectBuilder<REv>,
+        switch_block_era_id: EraId,
+        retention_eras: u64,
+        pending_state_root_hashes: Vec<Digest>,
+    ) where
+        REv: From<StorageRequest> + From<BlockSynchronizerRequest>,
+    {
+        let oldest_retained_era_id = switch_block_era_id
+            .successor()
+            .saturating_sub(retention_eras);
+        let maybe_switch_block_header = match oldest_retained_era_id.predecessor() {
+            Some(preceding_era_id) => {
+                effect_builder
+                    .get_switch_block_header_by_era_from_storage(preceding_era_id)
+                    .await
+            }
+            None => None,
+        };
+        let lowest_height = match maybe_switch_block_header {
+            Some(switch_block_header) => switch_block_header.height(),
+            None => {
+                debug!(
+                    era = oldest_retained_era_id.value(),
+                    "ContractRuntime: not pruning global state, no switch block preceding the era"
+                );
+                if let Err(error) = engine_state.cancel_trie_pruning() {
+                    error!(%error, "ContractRuntime: failed to cancel pruning global state");
+                }
+                return;
+            }
+        };
+        let mut retained_state_root_hashes = effect_builder
+            .get_state_root_hashes_from_storage(lowest_height)
+            .await;
+        retained_state_root_hashes.extend(pending_state_root_hashes);
+        retained_state_root_hashes.extend(effect_builder.get_tries_being_synced().await);
+
+        let start = Instant::now();
+        let result = run_intensive_task(move || {
+            let summary = engine_state
+                .prune_unreachable_tries(&retained_state_root_hashes, TRIE_PRUNING_BATCH_SIZE)?;
+            engine_state.flush_environment()?;
+            Ok::<_, engine_state::Error>(summary)
+        })
+        .await;
+        match result {
+            Ok(summary) => info!(
+                lowest_retained_height = lowest_height,
+                retained_tries = summary.retained_count,
+                pruned_tries = summary.pruned_count,
+                elapsed = ?start.elapsed(),
+                "ContractRuntime: pruned global state"
+            ),
+            Err(error) => error!(%error, "ContractRuntime: failed to prune global state"),
+        }
     }
 
     /// Reads the trie (or chunk of a trie) under the given key and index.
@@ -957,6 +1414,21 @@ impl ContractRuntime {
         &self.engine_state
     }
 
+    /// Returns the current execution pre-state, for testing only.
+    #[cfg(test)]
+    pub(crate) fn execution_pre_state(&self) -> ExecutionPreState {
+        self.execution_pre_state
+            .lock()
+            .expect("ContractRuntime: execution_pre_state poisoned mutex")
+            .clone()
+    }
+
+    /// Returns the number of blocks refused for execution, for testing only.
+    #[cfg(test)]
+    pub(crate) fn refused_block_count(&self) -> u64 {
+        self.metrics.refused_blocks.get()
+    }
+
     #[inline]
     fn try_init_system_contract_registry_cache(&mut self) {
         // The system contract registry is stable so we can use the latest state root hash that we
@@ -977,10 +1449,34 @@ impl ContractRuntime {
     }
 }
 
+/// Opens the LMDB global state stored in `storage_dir`.
+fn open_global_state(
+    storage_dir: &Path,
+    contract_runtime_config: &Config,
+) -> Result<LmdbGlobalState, ConfigError> {
+    let environment = Arc::new(LmdbEnvironment::new(
+        storage_dir,
+        contract_runtime_config.max_global_state_size_or_default(),
+        contract_runtime_config.max_readers_or_default(),
+        contract_runtime_config.manual_sync_enabled_or_default(),
+    )?);
+
+    let trie_store = Arc::new(LmdbTrieStore::new(
+        &environment,
+        None,
+        DatabaseFlags::empty(),
+    )?);
+
+    Ok(LmdbGlobalState::empty(environment, trie_store)?)
+}
+
 #[cfg(test)]
 mod trie_chunking_tests {
     use casper_execution_engine::{
-        core::engine_state::engine_config::{DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING},
+        core::engine_state::engine_config::{
+            DEFAULT_ACCOUNT_CREATION_POLICY, DEFAULT_DELEGATION_RATE_CHANGE_LIMIT,
+            DEFAULT_FEE_HANDLING, DEFAULT_REFUND_HANDLING, DEFAULT_STORAGE_RENT,
+        },
         shared::{
             additive_map::AdditiveMap, newtypes::CorrelationId, system_config::SystemConfig,
             transform::Transform, wasm_config::WasmConfig,
@@ -997,7 +1493,10 @@ mod trie_chunking_tests {
     use crate::{
         components::fetcher::FetchResponse,
         contract_runtime::{Config as ContractRuntimeConfig, ContractRuntime},
-        types::{ActivationPoint, ChunkingError, TrieOrChunk, TrieOrChunkId, ValueOrChunk},
+        types::{
+            chainspec::DeployConfig, ActivationPoint, ChunkingError, TrieOrChunk, TrieOrChunkId,
+            ValueOrChunk,
+        },
     };
 
     use super::ContractRuntimeError;
@@ -1059,9 +1558,15 @@ mod trie_chunking_tests {
             SystemConfig::default(),
             10,
             10,
+            u32::MAX,
+            u32::MAX,
             10,
+            0,
+            false,
             ActivationPoint::EraId(EraId::from(2)),
             5,
+            None,
+            7,
             true,
             1,
             None,
@@ -1071,6 +1576,10 @@ mod trie_chunking_tests {
             true,
             DEFAULT_REFUND_HANDLING,
             DEFAULT_FEE_HANDLING,
+            DEFAULT_STORAGE_RENT,
+            DEFAULT_DELEGATION_RATE_CHANGE_LIMIT,
+            DEFAULT_ACCOUNT_CREATION_POLICY,
+            &DeployConfig::default(),
         )
         .unwrap();
         let empty_state_root = contract_runtime
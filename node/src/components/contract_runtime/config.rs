@@ -2,11 +2,15 @@ use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::shared::utils;
+use casper_types::TimeDiff;
 
 const DEFAULT_MAX_GLOBAL_STATE_SIZE: usize = 805_306_368_000; // 750 GiB
 const DEFAULT_MAX_READERS: u32 = 512;
 const DEFAULT_MAX_QUERY_DEPTH: u64 = 5;
 const DEFAULT_MANUAL_SYNC_ENABLED: bool = true;
+const DEFAULT_MAX_EXEC_QUEUE_DEPTH: usize = 32;
+const DEFAULT_SPECULATIVE_EXEC_CACHE_TTL: TimeDiff = TimeDiff::from_seconds(10);
+const DEFAULT_MODULE_CACHE_SIZE: usize = 134_217_728; // 128 MiB
 
 /// Contract runtime configuration.
 #[derive(Clone, Copy, DataSize, Debug, Deserialize, Serialize)]
@@ -31,6 +35,28 @@ pub struct Config {
     ///
     /// Defaults to `true`.
     pub enable_manual_sync: Option<bool>,
+    /// The number of blocks waiting in the execution queue above which the node stops acquiring
+    /// further blocks from the network while keeping up.
+    ///
+    /// Defaults to 32.
+    pub max_exec_queue_depth: Option<usize>,
+    /// How long the result of a speculative execution is reused for identical requests made
+    /// against the same state root.  Zero disables caching.
+    ///
+    /// Defaults to 10 seconds.
+    pub speculative_exec_cache_ttl: Option<TimeDiff>,
+    /// The total size in bytes of the Wasm modules cached once prepared for execution.  Zero
+    /// disables caching.
+    ///
+    /// Defaults to 134,217,728 == 128 MiB.
+    pub module_cache_size: Option<usize>,
+    /// The number of eras preceding the era following the latest executed switch block whose
+    /// global state is retained when pruning the tries unreachable from it, which starts after
+    /// executing each switch block and runs alongside the execution of the following blocks.
+    /// Values below the chainspec's unbonding delay are raised to it.
+    ///
+    /// Defaults to `None`, i.e. global state is never pruned.
+    pub trie_pruning_retention_eras: Option<u64>,
 }
 
 impl Config {
@@ -58,6 +84,23 @@ impl Config {
         self.enable_manual_sync
             .unwrap_or(DEFAULT_MANUAL_SYNC_ENABLED)
     }
+
+    /// Max execution queue depth.
+    pub fn max_exec_queue_depth_or_default(&self) -> usize {
+        self.max_exec_queue_depth
+            .unwrap_or(DEFAULT_MAX_EXEC_QUEUE_DEPTH)
+    }
+
+    /// Time to live of cached speculative execution results.
+    pub fn speculative_exec_cache_ttl_or_default(&self) -> TimeDiff {
+        self.speculative_exec_cache_ttl
+            .unwrap_or(DEFAULT_SPECULATIVE_EXEC_CACHE_TTL)
+    }
+
+    /// Total size of cached Wasm modules in bytes.
+    pub fn module_cache_size_or_default(&self) -> usize {
+        self.module_cache_size.unwrap_or(DEFAULT_MODULE_CACHE_SIZE)
+    }
 }
 
 impl Default for Config {
@@ -67,6 +110,10 @@ impl Default for Config {
             max_readers: Some(DEFAULT_MAX_READERS),
             max_query_depth: Some(DEFAULT_MAX_QUERY_DEPTH),
             enable_manual_sync: Some(DEFAULT_MANUAL_SYNC_ENABLED),
+            max_exec_queue_depth: Some(DEFAULT_MAX_EXEC_QUEUE_DEPTH),
+            speculative_exec_cache_ttl: Some(DEFAULT_SPECULATIVE_EXEC_CACHE_TTL),
+            module_cache_size: Some(DEFAULT_MODULE_CACHE_SIZE),
+            trie_pruning_retention_eras: None,
         }
     }
 }
@@ -7,6 +7,7 @@ use casper_execution_engine::{
     core::engine_state::{Error as EngineStateError, StepError},
     storage::error::lmdb::Error as StorageLmdbError,
 };
+use casper_hashing::Digest;
 
 use crate::{
     components::contract_runtime::ExecutionPreState,
@@ -45,6 +46,31 @@ pub enum BlockExecutionError {
         /// The state of the block chain prior to block execution that was to be used.
         execution_pre_state: Box<ExecutionPreState>,
     },
+    /// The root of the global state the block was to be executed against is missing.
+    #[error(
+        "pre-state root hash {pre_state_root_hash} of block {block_height} is missing from global \
+         state"
+    )]
+    MissingPreStateRoot {
+        /// The height of the block.
+        block_height: u64,
+        /// The missing state root hash.
+        pre_state_root_hash: Digest,
+    },
+    /// The execution pre-state of the block doesn't match the post-state of its executed parent.
+    #[error(
+        "execution pre-state of block {block_height} does not match the post-state of its parent. \
+         expected: {expected:?}, \
+         actual: {actual:?}"
+    )]
+    PreStateMismatch {
+        /// The height of the block.
+        block_height: u64,
+        /// The post-state of the block's parent.
+        expected: Box<ExecutionPreState>,
+        /// The pre-state the block was to be executed against.
+        actual: Box<ExecutionPreState>,
+    },
     /// A core error thrown by the execution engine.
     #[error(transparent)]
     EngineState(
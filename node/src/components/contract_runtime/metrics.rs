@@ -1,5 +1,10 @@
-use prometheus::{self, Gauge, Histogram, IntGauge, Registry};
+use std::collections::BTreeMap;
 
+use prometheus::{self, Gauge, Histogram, IntCounter, IntGauge, Registry};
+
+use casper_execution_engine::shared::module_cache::ModuleCacheStats;
+
+use super::QueuedBlock;
 use crate::{unregister_metric, utils};
 
 /// Value of upper bound of histogram.
@@ -32,6 +37,10 @@ const COMMIT_STEP_HELP: &str = "time in seconds to commit the step at era end";
 const GET_BALANCE_NAME: &str = "contract_runtime_get_balance";
 const GET_BALANCE_HELP: &str = "time in seconds to get the balance of a purse from global state";
 
+const GET_BALANCES_NAME: &str = "contract_runtime_get_balances";
+const GET_BALANCES_HELP: &str =
+    "time in seconds to get the balances of several purses from global state";
+
 const GET_ERA_VALIDATORS_NAME: &str = "contract_runtime_get_era_validators";
 const GET_ERA_VALIDATORS_HELP: &str =
     "time in seconds to get validators for a given era from global state";
@@ -39,6 +48,13 @@ const GET_ERA_VALIDATORS_HELP: &str =
 const GET_BIDS_NAME: &str = "contract_runtime_get_bids";
 const GET_BIDS_HELP: &str = "time in seconds to get bids from global state";
 
+const GET_UNBONDING_PURSES_NAME: &str = "contract_runtime_get_unbonding_purses";
+const GET_UNBONDING_PURSES_HELP: &str =
+    "time in seconds to get the unbonding purses of a public key from global state";
+
+const GET_ENTITY_NAME: &str = "contract_runtime_get_entity";
+const GET_ENTITY_HELP: &str = "time in seconds to get an account or a contract from global state";
+
 const PUT_TRIE_NAME: &str = "contract_runtime_put_trie";
 const PUT_TRIE_HELP: &str = "time in seconds to put a trie";
 
@@ -55,6 +71,45 @@ const EXEC_QUEUE_SIZE_NAME: &str = "execution_queue_size";
 const EXEC_QUEUE_SIZE_HELP: &str =
     "number of blocks that are currently enqueued and waiting for execution";
 
+const EXEC_QUEUE_LOWEST_HEIGHT_NAME: &str = "execution_queue_lowest_height";
+const EXEC_QUEUE_LOWEST_HEIGHT_HELP: &str =
+    "height of the lowest block waiting for execution, or 0 if the execution queue is empty";
+
+const EXEC_QUEUE_HIGHEST_HEIGHT_NAME: &str = "execution_queue_highest_height";
+const EXEC_QUEUE_HIGHEST_HEIGHT_HELP: &str =
+    "height of the highest block waiting for execution, or 0 if the execution queue is empty";
+
+const EXEC_QUEUE_OLDEST_ENQUEUED_NAME: &str = "execution_queue_oldest_enqueued_at";
+const EXEC_QUEUE_OLDEST_ENQUEUED_HELP: &str =
+    "unix timestamp in milliseconds at which the longest waiting block was put into the execution \
+    queue, or 0 if the execution queue is empty";
+
+const MODULE_CACHE_HITS_NAME: &str = "contract_runtime_module_cache_hits";
+const MODULE_CACHE_HITS_HELP: &str = "number of Wasm modules found in the module cache";
+
+const MODULE_CACHE_MISSES_NAME: &str = "contract_runtime_module_cache_misses";
+const MODULE_CACHE_MISSES_HELP: &str =
+    "number of Wasm modules which had to be prepared as they were not in the module cache";
+
+const MODULE_CACHE_HIT_RATE_NAME: &str = "contract_runtime_module_cache_hit_rate";
+const MODULE_CACHE_HIT_RATE_HELP: &str =
+    "ratio of Wasm modules found in the module cache to all modules looked up";
+
+const MODULE_CACHE_EVICTIONS_NAME: &str = "contract_runtime_module_cache_evictions";
+const MODULE_CACHE_EVICTIONS_HELP: &str =
+    "number of Wasm modules evicted to keep the module cache within its size";
+
+const MODULE_CACHE_ENTRIES_NAME: &str = "contract_runtime_module_cache_entries";
+const MODULE_CACHE_ENTRIES_HELP: &str = "number of Wasm modules in the module cache";
+
+const MODULE_CACHE_SIZE_NAME: &str = "contract_runtime_module_cache_size";
+const MODULE_CACHE_SIZE_HELP: &str = "total size in bytes of the Wasm modules in the module cache";
+
+const REFUSED_BLOCKS_NAME: &str = "contract_runtime_refused_blocks";
+const REFUSED_BLOCKS_HELP: &str =
+    "number of blocks not executed as their pre-state was missing or differed from the post-state \
+    of their parent";
+
 /// Metrics for the contract runtime component.
 #[derive(Debug)]
 pub struct Metrics {
@@ -64,13 +119,26 @@ pub struct Metrics {
     pub(super) run_query: Histogram,
     pub(super) commit_step: Histogram,
     pub(super) get_balance: Histogram,
+    pub(super) get_balances: Histogram,
     pub(super) get_era_validators: Histogram,
     pub(super) get_bids: Histogram,
+    pub(super) get_unbonding_purses: Histogram,
+    pub(super) get_entity: Histogram,
     pub(super) put_trie: Histogram,
     pub(super) get_trie: Histogram,
     pub(super) exec_block: Histogram,
     pub(super) latest_commit_step: Gauge,
-    pub(super) exec_queue_size: IntGauge,
+    exec_queue_size: IntGauge,
+    exec_queue_lowest_height: IntGauge,
+    exec_queue_highest_height: IntGauge,
+    exec_queue_oldest_enqueued_at: IntGauge,
+    module_cache_hits: IntGauge,
+    module_cache_misses: IntGauge,
+    module_cache_hit_rate: Gauge,
+    module_cache_evictions: IntGauge,
+    module_cache_entries: IntGauge,
+    module_cache_size: IntGauge,
+    pub(super) refused_blocks: IntCounter,
     registry: Registry,
 }
 
@@ -95,6 +163,47 @@ impl Metrics {
         let exec_queue_size = IntGauge::new(EXEC_QUEUE_SIZE_NAME, EXEC_QUEUE_SIZE_HELP)?;
         registry.register(Box::new(exec_queue_size.clone()))?;
 
+        let exec_queue_lowest_height =
+            IntGauge::new(EXEC_QUEUE_LOWEST_HEIGHT_NAME, EXEC_QUEUE_LOWEST_HEIGHT_HELP)?;
+        registry.register(Box::new(exec_queue_lowest_height.clone()))?;
+
+        let exec_queue_highest_height = IntGauge::new(
+            EXEC_QUEUE_HIGHEST_HEIGHT_NAME,
+            EXEC_QUEUE_HIGHEST_HEIGHT_HELP,
+        )?;
+        registry.register(Box::new(exec_queue_highest_height.clone()))?;
+
+        let exec_queue_oldest_enqueued_at = IntGauge::new(
+            EXEC_QUEUE_OLDEST_ENQUEUED_NAME,
+            EXEC_QUEUE_OLDEST_ENQUEUED_HELP,
+        )?;
+        registry.register(Box::new(exec_queue_oldest_enqueued_at.clone()))?;
+
+        let module_cache_hits = IntGauge::new(MODULE_CACHE_HITS_NAME, MODULE_CACHE_HITS_HELP)?;
+        registry.register(Box::new(module_cache_hits.clone()))?;
+
+        let module_cache_misses =
+            IntGauge::new(MODULE_CACHE_MISSES_NAME, MODULE_CACHE_MISSES_HELP)?;
+        registry.register(Box::new(module_cache_misses.clone()))?;
+
+        let module_cache_hit_rate =
+            Gauge::new(MODULE_CACHE_HIT_RATE_NAME, MODULE_CACHE_HIT_RATE_HELP)?;
+        registry.register(Box::new(module_cache_hit_rate.clone()))?;
+
+        let module_cache_evictions =
+            IntGauge::new(MODULE_CACHE_EVICTIONS_NAME, MODULE_CACHE_EVICTIONS_HELP)?;
+        registry.register(Box::new(module_cache_evictions.clone()))?;
+
+        let module_cache_entries =
+            IntGauge::new(MODULE_CACHE_ENTRIES_NAME, MODULE_CACHE_ENTRIES_HELP)?;
+        registry.register(Box::new(module_cache_entries.clone()))?;
+
+        let module_cache_size = IntGauge::new(MODULE_CACHE_SIZE_NAME, MODULE_CACHE_SIZE_HELP)?;
+        registry.register(Box::new(module_cache_size.clone()))?;
+
+        let refused_blocks = IntCounter::new(REFUSED_BLOCKS_NAME, REFUSED_BLOCKS_HELP)?;
+        registry.register(Box::new(refused_blocks.clone()))?;
+
         Ok(Metrics {
             run_execute: utils::register_histogram_metric(
                 registry,
@@ -132,6 +241,12 @@ impl Metrics {
                 GET_BALANCE_HELP,
                 common_buckets.clone(),
             )?,
+            get_balances: utils::register_histogram_metric(
+                registry,
+                GET_BALANCES_NAME,
+                GET_BALANCES_HELP,
+                common_buckets.clone(),
+            )?,
             get_era_validators: utils::register_histogram_metric(
                 registry,
                 GET_ERA_VALIDATORS_NAME,
@@ -144,6 +259,18 @@ impl Metrics {
                 GET_BIDS_HELP,
                 common_buckets.clone(),
             )?,
+            get_unbonding_purses: utils::register_histogram_metric(
+                registry,
+                GET_UNBONDING_PURSES_NAME,
+                GET_UNBONDING_PURSES_HELP,
+                common_buckets.clone(),
+            )?,
+            get_entity: utils::register_histogram_metric(
+                registry,
+                GET_ENTITY_NAME,
+                GET_ENTITY_HELP,
+                common_buckets.clone(),
+            )?,
             get_trie: utils::register_histogram_metric(
                 registry,
                 GET_TRIE_NAME,
@@ -164,9 +291,49 @@ impl Metrics {
             )?,
             latest_commit_step,
             exec_queue_size,
+            exec_queue_lowest_height,
+            exec_queue_highest_height,
+            exec_queue_oldest_enqueued_at,
+            module_cache_hits,
+            module_cache_misses,
+            module_cache_hit_rate,
+            module_cache_evictions,
+            module_cache_entries,
+            module_cache_size,
+            refused_blocks,
             registry: registry.clone(),
         })
     }
+
+    /// Updates the execution queue metrics from the current contents of the queue.
+    pub(super) fn update_exec_queue(&self, exec_queue: &BTreeMap<u64, QueuedBlock>) {
+        let to_i64 = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+        self.exec_queue_size
+            .set(i64::try_from(exec_queue.len()).unwrap_or(i64::MAX));
+        self.exec_queue_lowest_height
+            .set(exec_queue.keys().next().copied().map_or(0, to_i64));
+        self.exec_queue_highest_height
+            .set(exec_queue.keys().next_back().copied().map_or(0, to_i64));
+        let oldest_enqueued_at = exec_queue
+            .values()
+            .map(|queued_block| queued_block.enqueued_at)
+            .min();
+        self.exec_queue_oldest_enqueued_at
+            .set(oldest_enqueued_at.map_or(0, |timestamp| to_i64(timestamp.millis())));
+    }
+
+    /// Updates the module cache metrics from the statistics of the cache.
+    pub(super) fn update_module_cache(&self, stats: &ModuleCacheStats) {
+        let to_i64 = |value: u64| i64::try_from(value).unwrap_or(i64::MAX);
+        self.module_cache_hits.set(to_i64(stats.hits));
+        self.module_cache_misses.set(to_i64(stats.misses));
+        self.module_cache_hit_rate.set(stats.hit_rate());
+        self.module_cache_evictions.set(to_i64(stats.evictions));
+        self.module_cache_entries
+            .set(i64::try_from(stats.entry_count).unwrap_or(i64::MAX));
+        self.module_cache_size
+            .set(i64::try_from(stats.size).unwrap_or(i64::MAX));
+    }
 }
 
 impl Drop for Metrics {
@@ -177,12 +344,25 @@ impl Drop for Metrics {
         unregister_metric!(self.registry, self.run_query);
         unregister_metric!(self.registry, self.commit_step);
         unregister_metric!(self.registry, self.get_balance);
+        unregister_metric!(self.registry, self.get_balances);
         unregister_metric!(self.registry, self.get_era_validators);
         unregister_metric!(self.registry, self.get_bids);
+        unregister_metric!(self.registry, self.get_unbonding_purses);
+        unregister_metric!(self.registry, self.get_entity);
         unregister_metric!(self.registry, self.put_trie);
         unregister_metric!(self.registry, self.get_trie);
         unregister_metric!(self.registry, self.exec_block);
         unregister_metric!(self.registry, self.latest_commit_step);
         unregister_metric!(self.registry, self.exec_queue_size);
+        unregister_metric!(self.registry, self.exec_queue_lowest_height);
+        unregister_metric!(self.registry, self.exec_queue_highest_height);
+        unregister_metric!(self.registry, self.exec_queue_oldest_enqueued_at);
+        unregister_metric!(self.registry, self.module_cache_hits);
+        unregister_metric!(self.registry, self.module_cache_misses);
+        unregister_metric!(self.registry, self.module_cache_hit_rate);
+        unregister_metric!(self.registry, self.module_cache_evictions);
+        unregister_metric!(self.registry, self.module_cache_entries);
+        unregister_metric!(self.registry, self.module_cache_size);
+        unregister_metric!(self.registry, self.refused_blocks);
     }
 }
@@ -13,7 +13,12 @@ use casper_execution_engine::{
         },
         execution,
     },
-    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+    shared::{
+        additive_map::AdditiveMap,
+        gas_profile::{self, GasProfile},
+        newtypes::CorrelationId,
+        transform::Transform,
+    },
     storage::global_state::{lmdb::LmdbGlobalState, CommitProvider, StateProvider},
 };
 use casper_hashing::Digest;
@@ -99,6 +104,7 @@ pub fn execute_finalized_block(
     activation_point_era_id: EraId,
     key_block_height_for_activation_point: u64,
     prune_batch_size: u64,
+    execution_results_v2_activation_point: Option<EraId>,
 ) -> Result<BlockAndExecutionResults, BlockExecutionError> {
     if finalized_block.height() != execution_pre_state.next_block_height {
         return Err(BlockExecutionError::WrongBlockHeight {
@@ -106,6 +112,19 @@ pub fn execute_finalized_block(
             execution_pre_state: Box::new(execution_pre_state),
         });
     }
+    // Executing against the wrong state would produce a bad block, so the pre-state must exist.
+    if engine_state
+        .get_trie_full(
+            CorrelationId::new(),
+            execution_pre_state.pre_state_root_hash,
+        )?
+        .is_none()
+    {
+        return Err(BlockExecutionError::MissingPreStateRoot {
+            block_height: finalized_block.height(),
+            pre_state_root_hash: execution_pre_state.pre_state_root_hash,
+        });
+    }
     let ExecutionPreState {
         pre_state_root_hash,
         parent_hash,
@@ -121,6 +140,10 @@ pub fn execute_finalized_block(
     let deploy_ids = deploys.iter().map(|deploy| deploy.fetch_id()).collect_vec();
     let approvals_checksum = types::compute_approvals_checksum(deploy_ids.clone())
         .map_err(BlockCreationError::BytesRepr)?;
+    let execution_results_v2 = reports_execution_results_v2(
+        finalized_block.era_id(),
+        execution_results_v2_activation_point,
+    );
 
     // Create a new EngineState that reads from LMDB but only caches changes in memory.
     let scratch_state = engine_state.get_scratch_engine_state();
@@ -152,6 +175,7 @@ pub fn execute_finalized_block(
             state_root_hash,
             deploy_hash.into(),
             result,
+            execution_results_v2,
         )?;
         execution_results.push((deploy_hash, deploy_header, execution_result));
         state_root_hash = state_hash;
@@ -183,6 +207,7 @@ pub fn execute_finalized_block(
 
     if let Some(metrics) = metrics.as_ref() {
         metrics.exec_block.observe(start.elapsed().as_secs_f64());
+        metrics.update_module_cache(&engine_state.config().module_cache().stats());
     }
 
     // If the finalized block has an era report, run the auction contract and get the upcoming era
@@ -335,13 +360,27 @@ pub fn execute_finalized_block(
     })
 }
 
+/// Returns whether the execution results of the deploys of a block in `era_id` are reported as
+/// V2 execution results, which is the case from the configured activation point on.
+fn reports_execution_results_v2(
+    era_id: EraId,
+    execution_results_v2_activation_point: Option<EraId>,
+) -> bool {
+    execution_results_v2_activation_point
+        .map_or(false, |activation_point| era_id >= activation_point)
+}
+
 /// Commits the execution results.
+///
+/// The result is reported as a V2 execution result if `execution_results_v2` is set and the deploy
+/// went through the finalization of its payment.
 fn commit_execution_results<S>(
     engine_state: &EngineState<S>,
     metrics: Option<Arc<Metrics>>,
     state_root_hash: Digest,
     deploy_hash: DeployHash,
     execution_results: ExecutionResults,
+    execution_results_v2: bool,
 ) -> Result<(Digest, ExecutionResult), BlockExecutionError>
 where
     S: StateProvider + CommitProvider,
@@ -351,7 +390,11 @@ where
         .into_iter()
         .exactly_one()
         .map_err(|_| BlockExecutionError::MoreThanOneExecutionResult)?;
-    let json_execution_result = ExecutionResult::from(&ee_execution_result);
+    let json_execution_result = if execution_results_v2 {
+        ExecutionResult::from(&ee_execution_result)
+    } else {
+        ee_execution_result.to_execution_result_v1()
+    };
 
     let execution_effect: AdditiveMap<Key, Transform> = match ee_execution_result {
         EngineExecutionResult::Success {
@@ -407,12 +450,14 @@ where
 /// Execute the transaction without commiting the effects.
 /// Intended to be used for discovery operations on read-only nodes.
 ///
-/// Returns effects of the execution.
+/// Returns effects of the execution, along with the gas consumed per host function if
+/// `collect_gas_profile` is set.
 pub fn execute_only<S>(
     engine_state: &EngineState<S>,
     execution_state: SpeculativeExecutionState,
     deploy: DeployItem,
-) -> Result<Option<ExecutionResult>, engine_state::Error>
+    collect_gas_profile: bool,
+) -> Result<Option<(ExecutionResult, Option<GasProfile>)>, engine_state::Error>
 where
     S: StateProvider + CommitProvider,
     S::Error: Into<execution::Error>,
@@ -430,7 +475,13 @@ where
         protocol_version,
         PublicKey::System,
     );
-    let results = execute(engine_state, None, execute_request);
+    let (results, maybe_gas_profile) = if collect_gas_profile {
+        let (results, gas_profile) =
+            gas_profile::collect(|| execute(engine_state, None, execute_request));
+        (results, Some(gas_profile))
+    } else {
+        (execute(engine_state, None, execute_request), None)
+    };
     results.map(|mut execution_results| {
         let len = execution_results.len();
         if len != 1 {
@@ -444,7 +495,9 @@ where
             // with `Some(_)` but `pop_front` already returns an `Option`.
             // We need to transform the `engine_state::ExecutionResult` into
             // `casper_types::ExecutionResult` as well.
-            execution_results.pop_front().map(Into::into)
+            execution_results
+                .pop_front()
+                .map(|execution_result| (execution_result.into(), maybe_gas_profile))
         }
     })
 }
@@ -540,8 +593,39 @@ fn compute_execution_results_checksum(
 
 #[cfg(test)]
 mod tests {
+    use casper_execution_engine::core::engine_state::PaymentSummary;
+
     use super::*;
 
+    #[test]
+    fn should_report_execution_results_v1_before_activation_point() {
+        let activation_point = EraId::new(5);
+        assert!(!reports_execution_results_v2(EraId::new(4), None));
+        assert!(!reports_execution_results_v2(
+            EraId::new(4),
+            Some(activation_point)
+        ));
+        assert!(reports_execution_results_v2(
+            EraId::new(5),
+            Some(activation_point)
+        ));
+
+        let ee_execution_result = EngineExecutionResult::Success {
+            execution_journal: Default::default(),
+            transfers: Default::default(),
+            cost: Default::default(),
+            payment_summary: Some(PaymentSummary::default()),
+        };
+        assert!(matches!(
+            ee_execution_result.to_execution_result_v1(),
+            ExecutionResult::Success { .. }
+        ));
+        assert!(matches!(
+            ExecutionResult::from(&ee_execution_result),
+            ExecutionResult::V2(_)
+        ));
+    }
+
     #[test]
     fn calculation_is_safe_with_invalid_input() {
         assert_eq!(calculate_prune_eras(EraId::new(0), 0, 0, 0,), None);
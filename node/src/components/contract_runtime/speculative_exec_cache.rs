@@ -0,0 +1,181 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use casper_execution_engine::shared::gas_profile::GasProfile;
+use casper_hashing::Digest;
+use casper_types::{ExecutionResult, TimeDiff, Timestamp};
+
+use crate::types::DeployId;
+
+/// The maximum number of results held by a [`SpeculativeExecCache`].
+const MAX_ENTRIES: usize = 1_000;
+
+/// The result of a successful speculative execution.
+pub(super) type SpeculativeExecResult = Option<(ExecutionResult, Option<GasProfile>)>;
+
+/// Identifies a speculative execution whose result can be reused.
+///
+/// The approvals are part of the key, as they determine whether the deploy is authorized.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub(super) struct CacheKey {
+    deploy_id: DeployId,
+    state_root_hash: Digest,
+    collect_gas_profile: bool,
+}
+
+impl CacheKey {
+    pub(super) fn new(
+        deploy_id: DeployId,
+        state_root_hash: Digest,
+        collect_gas_profile: bool,
+    ) -> Self {
+        CacheKey {
+            deploy_id,
+            state_root_hash,
+            collect_gas_profile,
+        }
+    }
+}
+
+/// A cache of the results of speculative executions, so that identical requests arriving in a
+/// burst are only executed once.
+///
+/// Results expire after a time to live, and the whole cache is cleared whenever a new block has
+/// been executed, as requests are then made against the new tip.
+#[derive(Clone, Debug)]
+pub(super) struct SpeculativeExecCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    ttl: TimeDiff,
+    results: HashMap<CacheKey, (Timestamp, SpeculativeExecResult)>,
+    /// The cached keys, oldest first.
+    insertion_order: VecDeque<CacheKey>,
+}
+
+impl SpeculativeExecCache {
+    /// Returns a new cache whose results expire after `ttl`.  A `ttl` of zero disables caching.
+    pub(super) fn new(ttl: TimeDiff) -> Self {
+        SpeculativeExecCache {
+            inner: Arc::new(Mutex::new(Inner {
+                ttl,
+                results: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Returns the cached result of the given execution if it hasn't expired yet.
+    pub(super) fn get(&self, key: &CacheKey) -> Option<SpeculativeExecResult> {
+        let mut inner = self.lock();
+        inner.purge_expired();
+        inner.results.get(key).map(|(_, result)| result.clone())
+    }
+
+    /// Caches the result of the given execution.
+    pub(super) fn insert(&self, key: CacheKey, result: SpeculativeExecResult) {
+        let mut inner = self.lock();
+        if inner.ttl == TimeDiff::from_millis(0) {
+            return;
+        }
+        inner.purge_expired();
+        if inner
+            .results
+            .insert(key, (Timestamp::now(), result))
+            .is_none()
+        {
+            inner.insertion_order.push_back(key);
+        }
+        while inner.insertion_order.len() > MAX_ENTRIES {
+            if let Some(evicted) = inner.insertion_order.pop_front() {
+                inner.results.remove(&evicted);
+            }
+        }
+    }
+
+    /// Removes all cached results.
+    pub(super) fn clear(&self) {
+        let mut inner = self.lock();
+        inner.results.clear();
+        inner.insertion_order.clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .expect("speculative execution cache lock should not be poisoned")
+    }
+}
+
+impl Inner {
+    /// Removes the results which have outlived the time to live.
+    fn purge_expired(&mut self) {
+        while let Some(key) = self.insertion_order.front() {
+            let expired = match self.results.get(key) {
+                Some((inserted_at, _)) => inserted_at.elapsed() >= self.ttl,
+                None => true,
+            };
+            if !expired {
+                break;
+            }
+            if let Some(key) = self.insertion_order.pop_front() {
+                self.results.remove(&key);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use rand::Rng;
+
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    fn new_key(rng: &mut TestRng) -> CacheKey {
+        CacheKey::new(DeployId::random(rng), rng.gen(), false)
+    }
+
+    #[test]
+    fn should_cache_until_cleared() {
+        let mut rng = TestRng::new();
+        let cache = SpeculativeExecCache::new(TimeDiff::from_seconds(60));
+        let key = new_key(&mut rng);
+
+        assert!(cache.get(&key).is_none());
+        cache.insert(key, None);
+        assert_eq!(cache.get(&key), Some(None));
+        assert!(cache.get(&new_key(&mut rng)).is_none());
+
+        cache.clear();
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn should_expire_results() {
+        let mut rng = TestRng::new();
+        let cache = SpeculativeExecCache::new(TimeDiff::from_millis(10));
+        let key = new_key(&mut rng);
+
+        cache.insert(key, None);
+        thread::sleep(Duration::from_millis(20));
+        assert!(cache.get(&key).is_none());
+    }
+
+    #[test]
+    fn should_not_cache_with_zero_ttl() {
+        let mut rng = TestRng::new();
+        let cache = SpeculativeExecCache::new(TimeDiff::from_millis(0));
+        let key = new_key(&mut rng);
+
+        cache.insert(key, None);
+        assert!(cache.get(&key).is_none());
+    }
+}
@@ -0,0 +1,124 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_execution_engine::shared::{execution_journal::ExecutionJournal, transform::Transform};
+use casper_types::{
+    system::auction::SeigniorageAllocation, EraId, Key, PublicKey, StoredValue, U512,
+};
+
+use crate::types::EraReport;
+
+/// The outcome of the step run at the end of an era.
+#[derive(Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct StepOutcome {
+    /// The era which ended.
+    pub era_id: EraId,
+    /// The validators slashed by the step.
+    ///
+    /// The Casper Network does not slash, so this is currently always empty.
+    pub slashed_validators: Vec<PublicKey>,
+    /// The validators evicted from the auction, either for equivocating or for being inactive
+    /// during the era.
+    pub evicted_validators: Vec<PublicKey>,
+    /// The rewards distributed to validators and their delegators for the era.
+    pub seigniorage_allocations: Vec<SeigniorageAllocation>,
+    /// The validator weights of all upcoming eras which have been determined, including the era
+    /// whose validators were newly determined by the step.
+    pub upcoming_era_validators: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
+}
+
+impl StepOutcome {
+    /// Collects the outcome of the step run at the end of `era_id` from the era report passed to
+    /// the step and the effects it committed.
+    pub(crate) fn new(
+        era_id: EraId,
+        era_report: &EraReport,
+        step_execution_journal: &ExecutionJournal,
+        upcoming_era_validators: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
+    ) -> Self {
+        // Both inactive validators and equivocators are evicted, see `operations::commit_step`.
+        let evicted_validators: BTreeSet<_> = era_report
+            .inactive_validators
+            .iter()
+            .chain(&era_report.equivocators)
+            .cloned()
+            .collect();
+        // The auction records the rewards it distributed as the era summary.
+        let seigniorage_allocations = step_execution_journal
+            .iter()
+            .filter_map(|(key, transform)| match (key, transform) {
+                (Key::EraSummary, Transform::Write(StoredValue::EraInfo(era_info))) => {
+                    Some(era_info.seigniorage_allocations().clone())
+                }
+                _ => None,
+            })
+            .last()
+            .unwrap_or_default();
+        StepOutcome {
+            era_id,
+            slashed_validators: vec![],
+            evicted_validators: evicted_validators.into_iter().collect(),
+            seigniorage_allocations,
+            upcoming_era_validators,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{system::auction::EraInfo, testing::TestRng};
+
+    use super::*;
+
+    #[test]
+    fn should_collect_step_outcome() {
+        let mut rng = TestRng::new();
+        let equivocator = PublicKey::random(&mut rng);
+        let inactive_validator = PublicKey::random(&mut rng);
+        let validator = PublicKey::random(&mut rng);
+
+        let era_report = EraReport {
+            equivocators: vec![equivocator.clone()],
+            rewards: BTreeMap::new(),
+            inactive_validators: vec![inactive_validator.clone(), equivocator.clone()],
+        };
+        let allocation = SeigniorageAllocation::validator(validator.clone(), U512::from(100));
+        let mut era_info = EraInfo::new();
+        era_info
+            .seigniorage_allocations_mut()
+            .push(allocation.clone());
+        let step_execution_journal = ExecutionJournal::new(vec![
+            (Key::EraSummary, Transform::Identity),
+            (
+                Key::EraSummary,
+                Transform::Write(StoredValue::EraInfo(era_info)),
+            ),
+        ]);
+        let mut upcoming_era_validators = BTreeMap::new();
+        upcoming_era_validators.insert(
+            EraId::new(4),
+            [(validator, U512::from(10))].into_iter().collect(),
+        );
+
+        let step_outcome = StepOutcome::new(
+            EraId::new(2),
+            &era_report,
+            &step_execution_journal,
+            upcoming_era_validators.clone(),
+        );
+        assert_eq!(step_outcome.era_id, EraId::new(2));
+        assert!(step_outcome.slashed_validators.is_empty());
+        let mut expected_evicted = vec![equivocator, inactive_validator];
+        expected_evicted.sort();
+        assert_eq!(step_outcome.evicted_validators, expected_evicted);
+        assert_eq!(step_outcome.seigniorage_allocations, vec![allocation]);
+        assert_eq!(
+            step_outcome.upcoming_era_validators,
+            upcoming_era_validators
+        );
+    }
+}
@@ -65,6 +65,7 @@ impl<T: Unhandled> From<T> for Event {
     }
 }
 
+impl Unhandled for BlockSynchronizerRequest {}
 impl Unhandled for ControlAnnouncement {}
 impl Unhandled for FatalAnnouncement {}
 impl Unhandled for NetworkRequest<Message> {}
@@ -113,9 +114,15 @@ impl reactor::Reactor for Reactor {
             chainspec.system_costs_config,
             chainspec.core_config.max_associated_keys,
             chainspec.core_config.max_runtime_call_stack_height,
+            chainspec.core_config.max_cl_value_size,
+            chainspec.core_config.max_named_keys,
             chainspec.core_config.minimum_delegation_amount,
+            chainspec.core_config.minimum_bid_amount,
+            chainspec.core_config.record_auction_snapshots,
             chainspec.protocol_config.activation_point,
             chainspec.core_config.prune_batch_size,
+            chainspec.core_config.execution_results_v2_activation_point,
+            chainspec.core_config.unbonding_delay,
             chainspec.core_config.strict_argument_checking,
             chainspec.core_config.vesting_schedule_period.millis(),
             Some(chainspec.core_config.max_delegators_per_validator),
@@ -125,6 +132,10 @@ impl reactor::Reactor for Reactor {
             chainspec.core_config.allow_unrestricted_transfers,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
+            chainspec.core_config.storage_rent,
+            chainspec.core_config.delegation_rate_change_limit,
+            chainspec.core_config.account_creation_policy,
+            &chainspec.deploy_config,
         )?;
 
         let reactor = Reactor {
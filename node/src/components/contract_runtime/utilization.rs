@@ -0,0 +1,290 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    mem,
+};
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{EraId, ExecutionResult};
+
+use crate::types::{chainspec::DeployConfig, Block, BlockHash, DeployHash, DeployHeader};
+
+/// The number of most recently executed blocks whose utilization is summarized.
+const WINDOW_SIZE: usize = 100;
+
+/// The utilization of one of the limited resources of a block, summed over a number of blocks.
+#[derive(
+    Clone, Copy, Default, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema,
+)]
+#[serde(deny_unknown_fields)]
+pub struct ResourceUtilization {
+    /// The amount of the resource used.
+    pub used: u64,
+    /// The amount of the resource available.
+    pub limit: u64,
+    /// The highest utilization of any single block, in percent of its limit.
+    pub peak_percent: u64,
+}
+
+impl ResourceUtilization {
+    fn of_block(used: u64, limit: u64) -> Self {
+        ResourceUtilization {
+            used,
+            limit,
+            peak_percent: percent(used, limit),
+        }
+    }
+
+    fn add(&mut self, other: &ResourceUtilization) {
+        self.used = self.used.saturating_add(other.used);
+        self.limit = self.limit.saturating_add(other.limit);
+        self.peak_percent = self.peak_percent.max(other.peak_percent);
+    }
+}
+
+/// The utilization of the capacity of a number of consecutive blocks.
+#[derive(
+    Clone, Copy, Default, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema,
+)]
+#[serde(deny_unknown_fields)]
+pub struct UtilizationSummary {
+    /// The number of blocks covered.
+    pub block_count: u64,
+    /// The gas consumed by deploys other than transfers, against the block gas limit.
+    pub gas: ResourceUtilization,
+    /// The serialized size of deploys other than transfers, against the maximum block size.
+    pub size: ResourceUtilization,
+    /// The number of deploys other than transfers, against the maximum deploy count.
+    pub deploy_slots: ResourceUtilization,
+    /// The number of transfers, against the maximum transfer count.
+    pub transfer_slots: ResourceUtilization,
+}
+
+impl UtilizationSummary {
+    fn add(&mut self, other: &UtilizationSummary) {
+        self.block_count += other.block_count;
+        self.gas.add(&other.gas);
+        self.size.add(&other.size);
+        self.deploy_slots.add(&other.deploy_slots);
+        self.transfer_slots.add(&other.transfer_slots);
+    }
+}
+
+/// The utilization of the capacity of the blocks of an era.
+#[derive(Clone, DataSize, PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct EraUtilization {
+    /// The era.
+    pub era_id: EraId,
+    /// The hash of the switch block concluding the era.
+    pub switch_block_hash: BlockHash,
+    /// The utilization of the blocks of the era executed by this node, including the switch block.
+    pub summary: UtilizationSummary,
+}
+
+/// The utilization of the capacity of the most recently executed blocks.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub(crate) struct BlockUtilization {
+    /// The utilization of the most recently executed blocks.
+    pub(crate) recent_blocks: UtilizationSummary,
+    /// The utilization of the last era concluded by a switch block executed by this node.
+    pub(crate) last_era: Option<EraUtilization>,
+}
+
+/// Tracks the utilization of the capacity of executed blocks over a rolling window, and per era.
+#[derive(DataSize, Debug)]
+pub(super) struct UtilizationTracker {
+    block_gas_limit: u64,
+    max_block_size: u64,
+    max_deploy_count: u64,
+    max_transfer_count: u64,
+    /// The utilization of each of the most recently executed blocks, oldest first.
+    recent_blocks: VecDeque<UtilizationSummary>,
+    /// The utilization of the blocks of the current era executed so far.
+    current_era: UtilizationSummary,
+    /// The utilization of the last era concluded by an executed switch block.
+    last_era: Option<EraUtilization>,
+}
+
+impl UtilizationTracker {
+    pub(super) fn new(deploy_config: &DeployConfig) -> Self {
+        UtilizationTracker {
+            block_gas_limit: deploy_config.block_gas_limit,
+            max_block_size: deploy_config.max_block_size.into(),
+            max_deploy_count: deploy_config.block_max_deploy_count.into(),
+            max_transfer_count: deploy_config.block_max_transfer_count.into(),
+            recent_blocks: VecDeque::with_capacity(WINDOW_SIZE),
+            current_era: UtilizationSummary::default(),
+            last_era: None,
+        }
+    }
+
+    /// Records the utilization of an executed block, given the total serialized size of its
+    /// deploys other than transfers.
+    ///
+    /// Returns the utilization of the era if the block is a switch block.
+    pub(super) fn record_block(
+        &mut self,
+        block: &Block,
+        deploys_size: u64,
+        execution_results: &[(DeployHash, DeployHeader, ExecutionResult)],
+    ) -> Option<EraUtilization> {
+        let deploy_hashes: HashSet<_> = block.deploy_hashes().iter().collect();
+        let gas_used = execution_results
+            .iter()
+            .filter(|(deploy_hash, _, _)| deploy_hashes.contains(deploy_hash))
+            .map(|(_, _, execution_result)| gas_consumed(execution_result))
+            .fold(0, u64::saturating_add);
+        let block_utilization = UtilizationSummary {
+            block_count: 1,
+            gas: ResourceUtilization::of_block(gas_used, self.block_gas_limit),
+            size: ResourceUtilization::of_block(deploys_size, self.max_block_size),
+            deploy_slots: ResourceUtilization::of_block(
+                block.deploy_hashes().len() as u64,
+                self.max_deploy_count,
+            ),
+            transfer_slots: ResourceUtilization::of_block(
+                block.transfer_hashes().len() as u64,
+                self.max_transfer_count,
+            ),
+        };
+
+        if self.recent_blocks.len() == WINDOW_SIZE {
+            self.recent_blocks.pop_front();
+        }
+        self.recent_blocks.push_back(block_utilization);
+        self.current_era.add(&block_utilization);
+
+        if !block.header().is_switch_block() {
+            return None;
+        }
+        let era_utilization = EraUtilization {
+            era_id: block.header().era_id(),
+            switch_block_hash: *block.hash(),
+            summary: mem::take(&mut self.current_era),
+        };
+        self.last_era = Some(era_utilization.clone());
+        Some(era_utilization)
+    }
+
+    /// Returns the utilization of the most recently executed blocks and of the last era.
+    pub(super) fn block_utilization(&self) -> BlockUtilization {
+        let mut recent_blocks = UtilizationSummary::default();
+        for block_utilization in &self.recent_blocks {
+            recent_blocks.add(block_utilization);
+        }
+        BlockUtilization {
+            recent_blocks,
+            last_era: self.last_era.clone(),
+        }
+    }
+}
+
+/// Returns the gas consumed by executing a deploy, saturating at `u64::MAX`.
+fn gas_consumed(execution_result: &ExecutionResult) -> u64 {
+    let consumed = match execution_result {
+        ExecutionResult::Failure { cost, .. } | ExecutionResult::Success { cost, .. } => cost,
+        ExecutionResult::V2(execution_result) => &execution_result.consumed,
+    };
+    u64::try_from(*consumed).unwrap_or(u64::MAX)
+}
+
+/// Returns `used` in percent of `limit`, rounded down, or zero if there is no limit.
+fn percent(used: u64, limit: u64) -> u64 {
+    if limit == 0 {
+        return 0;
+    }
+    (u128::from(used) * 100 / u128::from(limit)) as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::{testing::TestRng, ExecutionEffect, ProtocolVersion, U512};
+
+    use super::*;
+    use crate::types::Deploy;
+
+    fn execution_results(
+        deploys: &[Deploy],
+        cost: u64,
+    ) -> Vec<(DeployHash, DeployHeader, ExecutionResult)> {
+        deploys
+            .iter()
+            .map(|deploy| {
+                let execution_result = ExecutionResult::Success {
+                    effect: ExecutionEffect::default(),
+                    transfers: vec![],
+                    cost: U512::from(cost),
+                };
+                (*deploy.hash(), deploy.header().clone(), execution_result)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_summarize_recent_blocks_and_eras() {
+        let mut rng = TestRng::new();
+        let deploy_config = DeployConfig::default();
+        let mut tracker = UtilizationTracker::new(&deploy_config);
+        let gas_limit = deploy_config.block_gas_limit;
+
+        let deploys: Vec<_> = (0..2).map(|_| Deploy::random(&mut rng)).collect();
+        let block = Block::random_with_specifics(
+            &mut rng,
+            EraId::new(1),
+            10,
+            ProtocolVersion::V1_0_0,
+            false,
+            &deploys,
+        );
+        let era_utilization =
+            tracker.record_block(&block, 1_000, &execution_results(&deploys, gas_limit / 4));
+        assert!(era_utilization.is_none());
+
+        let switch_block = Block::random_with_specifics(
+            &mut rng,
+            EraId::new(1),
+            11,
+            ProtocolVersion::V1_0_0,
+            true,
+            &deploys[..1],
+        );
+        let era_utilization = tracker
+            .record_block(
+                &switch_block,
+                500,
+                &execution_results(&deploys[..1], gas_limit),
+            )
+            .expect("should conclude era");
+        assert_eq!(era_utilization.era_id, EraId::new(1));
+        assert_eq!(era_utilization.switch_block_hash, *switch_block.hash());
+
+        let summary = era_utilization.summary;
+        assert_eq!(summary.block_count, 2);
+        assert_eq!(summary.gas.used, gas_limit + gas_limit / 2);
+        assert_eq!(summary.gas.limit, 2 * gas_limit);
+        assert_eq!(summary.gas.peak_percent, 100);
+        assert_eq!(summary.size.used, 1_500);
+        assert_eq!(summary.deploy_slots.used, 3);
+        assert_eq!(summary.transfer_slots.used, 0);
+
+        let block_utilization = tracker.block_utilization();
+        assert_eq!(block_utilization.recent_blocks, summary);
+        assert_eq!(block_utilization.last_era, Some(era_utilization));
+
+        // Once the era has been concluded, only the recent blocks keep accumulating.
+        let block = Block::random_with_specifics(
+            &mut rng,
+            EraId::new(2),
+            12,
+            ProtocolVersion::V1_0_0,
+            false,
+            &deploys,
+        );
+        assert!(tracker.record_block(&block, 0, &[]).is_none());
+        assert_eq!(tracker.block_utilization().recent_blocks.block_count, 3);
+        assert_eq!(tracker.current_era.block_count, 1);
+    }
+}
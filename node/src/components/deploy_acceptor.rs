@@ -13,22 +13,29 @@ use serde::Serialize;
 use thiserror::Error;
 use tracing::{debug, error, trace};
 
-use casper_execution_engine::core::engine_state::{
-    executable_deploy_item::{
-        ContractIdentifier, ContractPackageIdentifier, ExecutableDeployItemIdentifier,
+use casper_execution_engine::{
+    core::{
+        engine_state::{
+            executable_deploy_item::{
+                ContractIdentifier, ContractPackageIdentifier, ExecutableDeployItemIdentifier,
+            },
+            ExecutableDeployItem, MAX_PAYMENT,
+        },
+        resolvers,
     },
-    ExecutableDeployItem, MAX_PAYMENT,
+    shared::wasm_prep::{self, ImportViolation},
 };
 use casper_hashing::Digest;
 use casper_types::{
     account::{Account, AccountHash},
+    bytesrepr::Bytes,
     system::auction::ARG_AMOUNT,
     Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion,
-    ContractVersionKey, Key, ProtocolVersion, Timestamp, U512,
+    ContractVersionKey, ExecutionResult, ExecutionResultV2, Key, ProtocolVersion, Timestamp, U512,
 };
 
 use crate::{
-    components::Component,
+    components::{contract_runtime::SpeculativeExecutionState, Component},
     effect::{
         announcements::{DeployAcceptorAnnouncement, FatalAnnouncement},
         requests::{ContractRuntimeRequest, StorageRequest},
@@ -38,8 +45,9 @@ use crate::{
     types::{
         chainspec::{CoreConfig, DeployConfig},
         BlockHash, BlockHeader, Chainspec, Deploy, DeployConfigurationFailure, FinalizedApprovals,
+        ValidatorMatrix,
     },
-    utils::Source,
+    utils::{DisplayIter, Source},
     NodeRng,
 };
 
@@ -84,6 +92,24 @@ pub(crate) enum Error {
         /// The timestamp when the node validated the expiry timestamp.
         current_node_timestamp: Timestamp,
     },
+
+    /// The deploy failed when speculatively executed against the highest complete block.
+    #[error(
+        "deploy execution failed at state root hash {:?} of block {:?} at height {block_height}: \
+        {error_message}",
+        state_root_hash,
+        block_hash.inner(),
+    )]
+    SimulatedExecutionFailure {
+        state_root_hash: Digest,
+        block_hash: BlockHash,
+        block_height: u64,
+        error_message: String,
+    },
+
+    /// The node serves historical reads only, and does not accept deploys.
+    #[error("node is in archive mode and does not accept deploys")]
+    ArchiveMode,
 }
 
 impl Error {
@@ -144,6 +170,43 @@ pub(crate) enum DeployParameterFailure {
     /// Module bytes for session code cannot be empty.
     #[error("module bytes for session code cannot be empty")]
     MissingModuleBytes,
+    /// Module bytes are not a valid Wasm module.
+    #[error("module bytes are not a valid Wasm module: {error}")]
+    InvalidModuleBytes { error: String },
+    /// Module bytes import functions the host does not provide at the current protocol version.
+    #[error(
+        "module bytes import functions which are not provided by the host: {}",
+        DisplayIter::new(violations)
+    )]
+    DisallowedImports { violations: Vec<ImportViolation> },
+}
+
+impl DeployParameterFailure {
+    /// Checks that the given module bytes are a Wasm module which only imports host functions
+    /// provided at the given protocol version.
+    ///
+    /// This rejects contracts built against a future or foreign runtime before they are executed.
+    fn check_module_bytes(
+        module_bytes: &Bytes,
+        protocol_version: ProtocolVersion,
+    ) -> Result<(), DeployParameterFailure> {
+        let module = wasm_prep::deserialize(module_bytes).map_err(|error| {
+            DeployParameterFailure::InvalidModuleBytes {
+                error: error.to_string(),
+            }
+        })?;
+        let host_function_names = match resolvers::host_function_names(protocol_version) {
+            Ok(host_function_names) => host_function_names,
+            // Execution of the deploy will fail on the unknown protocol version regardless.
+            Err(_) => return Ok(()),
+        };
+        let violations = wasm_prep::validate_imports(&module, host_function_names);
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(DeployParameterFailure::DisallowedImports { violations })
+        }
+    }
 }
 
 /// A helper trait constraining `DeployAcceptor` compatible reactor events.
@@ -186,7 +249,11 @@ impl<REv> ReactorEventT for REv where
 ///
 ///     Start --> A{has valid size?}
 ///     A -->|Yes| B{"is compliant with config?<br/>(size, chain name, ttl, etc.)"}
-///     G -->|Yes| ZZ[Accept]
+///     G -->|Yes| S{"should simulate<br/>against tip?"}
+///     S -->|No| ZZ[Accept]
+///     S -->|Yes| H{does speculative<br/>execution succeed?}
+///     H -->|Yes| ZZ
+///     H -->|No| Z
 ///     B -->|Yes| C{is from<br/>client?}
 ///     C -->|Yes| CLIENT{has expired?}
 ///     B -->|No| Z[Reject]
@@ -211,6 +278,7 @@ pub struct DeployAcceptor {
     deploy_config: DeployConfig,
     core_config: CoreConfig,
     max_associated_keys: u32,
+    validator_matrix: ValidatorMatrix,
     #[data_size(skip)]
     metrics: metrics::Metrics,
 }
@@ -219,6 +287,7 @@ impl DeployAcceptor {
     pub(crate) fn new(
         acceptor_config: Config,
         chainspec: &Chainspec,
+        validator_matrix: ValidatorMatrix,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(DeployAcceptor {
@@ -228,6 +297,7 @@ impl DeployAcceptor {
             deploy_config: chainspec.deploy_config,
             core_config: chainspec.core_config.clone(),
             max_associated_keys: chainspec.core_config.max_associated_keys,
+            validator_matrix,
             metrics: metrics::Metrics::new(registry)?,
         })
     }
@@ -557,6 +627,18 @@ impl DeployAcceptor {
                             verification_start_timestamp,
                         );
                     }
+                } else if let Err(failure) = DeployParameterFailure::check_module_bytes(
+                    module_bytes,
+                    block_header.protocol_version(),
+                ) {
+                    let error = Error::parameter_failure(&block_header, failure);
+                    debug!(%error, "invalid module bytes in payment logic");
+                    return self.handle_invalid_deploy_result(
+                        effect_builder,
+                        event_metadata,
+                        error,
+                        verification_start_timestamp,
+                    );
                 }
             }
             ExecutableDeployItem::StoredContractByHash { .. }
@@ -667,6 +749,19 @@ impl DeployAcceptor {
                         verification_start_timestamp,
                     );
                 }
+                if let Err(failure) = DeployParameterFailure::check_module_bytes(
+                    module_bytes,
+                    block_header.protocol_version(),
+                ) {
+                    let error = Error::parameter_failure(&block_header, failure);
+                    debug!(%error, "invalid module bytes in session logic");
+                    return self.handle_invalid_deploy_result(
+                        effect_builder,
+                        event_metadata,
+                        error,
+                        verification_start_timestamp,
+                    );
+                }
             }
             ExecutableDeployItem::StoredContractByHash { .. }
             | ExecutableDeployItem::StoredContractByName { .. }
@@ -686,6 +781,7 @@ impl DeployAcceptor {
                 self.validate_deploy_cryptography(
                     effect_builder,
                     event_metadata,
+                    block_header,
                     verification_start_timestamp,
                 )
             }
@@ -775,6 +871,7 @@ impl DeployAcceptor {
             return self.validate_deploy_cryptography(
                 effect_builder,
                 event_metadata,
+                block_header,
                 verification_start_timestamp,
             );
         }
@@ -881,6 +978,7 @@ impl DeployAcceptor {
                         self.validate_deploy_cryptography(
                             effect_builder,
                             event_metadata,
+                            block_header,
                             verification_start_timestamp,
                         )
                     }
@@ -893,6 +991,7 @@ impl DeployAcceptor {
         &self,
         effect_builder: EffectBuilder<REv>,
         event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
         verification_start_timestamp: Timestamp,
     ) -> Effects<Event> {
         if let Err(deploy_configuration_failure) = event_metadata.deploy.is_valid() {
@@ -919,6 +1018,96 @@ impl DeployAcceptor {
             return effects;
         }
 
+        if self.should_simulate(&event_metadata.source) {
+            let execution_prestate = SpeculativeExecutionState {
+                state_root_hash: *block_header.state_root_hash(),
+                block_time: block_header.timestamp(),
+                protocol_version: block_header.protocol_version(),
+            };
+            return effect_builder
+                .speculative_execute_deploy(
+                    execution_prestate,
+                    Arc::clone(&event_metadata.deploy),
+                    false,
+                )
+                .event(move |result| {
+                    let maybe_execution_result = match result {
+                        Ok(maybe_result) => {
+                            maybe_result.map(|(execution_result, _)| Box::new(execution_result))
+                        }
+                        Err(error) => {
+                            debug!(%error, "failed to speculatively execute deploy");
+                            None
+                        }
+                    };
+                    Event::SimulationResult {
+                        event_metadata,
+                        block_header,
+                        maybe_execution_result,
+                        verification_start_timestamp,
+                    }
+                });
+        }
+
+        self.put_to_storage(effect_builder, event_metadata, verification_start_timestamp)
+    }
+
+    /// Returns whether a deploy from the given source should be speculatively executed against
+    /// the highest complete block before being accepted.
+    ///
+    /// Deploys fetched from peers are part of proposed or finalized blocks, so are never
+    /// simulated.  Neither are any deploys while we are an active or upcoming validator, as the
+    /// simulation would delay them becoming available for our own proposals.
+    fn should_simulate(&self, source: &Source) -> bool {
+        self.acceptor_config.simulate_against_tip
+            && matches!(source, Source::Client | Source::PeerGossiped(_))
+            && !self
+                .validator_matrix
+                .is_active_or_upcoming_validator(self.validator_matrix.public_signing_key())
+    }
+
+    fn handle_simulation_result<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
+        maybe_execution_result: Option<Box<ExecutionResult>>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
+        // An inconclusive simulation (e.g. due to the state root being unavailable) is not
+        // grounds for rejecting the deploy.
+        if let Some(
+            ExecutionResult::Failure { error_message, .. }
+            | ExecutionResult::V2(ExecutionResultV2 {
+                error_message: Some(error_message),
+                ..
+            }),
+        ) = maybe_execution_result.map(|execution_result| *execution_result)
+        {
+            debug!(%error_message, "deploy failed speculative execution against tip");
+            let error = Error::SimulatedExecutionFailure {
+                state_root_hash: *block_header.state_root_hash(),
+                block_hash: block_header.block_hash(),
+                block_height: block_header.height(),
+                error_message,
+            };
+            return self.handle_invalid_deploy_result(
+                effect_builder,
+                event_metadata,
+                error,
+                verification_start_timestamp,
+            );
+        }
+
+        self.put_to_storage(effect_builder, event_metadata, verification_start_timestamp)
+    }
+
+    fn put_to_storage<REv: ReactorEventT>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+        event_metadata: Box<EventMetadata>,
+        verification_start_timestamp: Timestamp,
+    ) -> Effects<Event> {
         effect_builder
             .put_deploy_to_storage(event_metadata.deploy.clone())
             .event(move |is_new| Event::PutToStorageResult {
@@ -1127,6 +1316,18 @@ impl<REv: ReactorEventT> Component<REv> for DeployAcceptor {
                 maybe_contract_package,
                 verification_start_timestamp,
             ),
+            Event::SimulationResult {
+                event_metadata,
+                block_header,
+                maybe_execution_result,
+                verification_start_timestamp,
+            } => self.handle_simulation_result(
+                effect_builder,
+                event_metadata,
+                block_header,
+                maybe_execution_result,
+                verification_start_timestamp,
+            ),
             Event::PutToStorageResult {
                 event_metadata,
                 is_new,
@@ -18,12 +18,20 @@ pub struct Config {
     /// The maximum value to which `timestamp_leeway` can be set is defined by the chainspec
     /// setting `deploys.max_timestamp_leeway`.
     pub timestamp_leeway: TimeDiff,
+    /// Whether to speculatively execute deploys received from clients or gossiped by peers against
+    /// the state of the highest complete block before storing and gossiping them.
+    ///
+    /// Deploys whose execution fails are rejected.  The check is skipped while this node is an
+    /// active or upcoming validator, so as not to delay deploys becoming available for proposals.
+    #[serde(default)]
+    pub simulate_against_tip: bool,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             timestamp_leeway: TimeDiff::from_str(DEFAULT_TIMESTAMP_LEEWAY).unwrap(),
+            simulate_against_tip: false,
         }
     }
 }
@@ -7,7 +7,8 @@ use serde::Serialize;
 
 use casper_types::{
     account::{Account, AccountHash},
-    Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion, Timestamp, U512,
+    Contract, ContractHash, ContractPackage, ContractPackageHash, ContractVersion, ExecutionResult,
+    Timestamp, U512,
 };
 
 use super::Source;
@@ -48,6 +49,13 @@ pub(crate) enum Event {
         source: Source,
         maybe_responder: Option<Responder<Result<(), Error>>>,
     },
+    /// The result of speculatively executing the `Deploy` against the highest complete block.
+    SimulationResult {
+        event_metadata: Box<EventMetadata>,
+        block_header: Box<BlockHeader>,
+        maybe_execution_result: Option<Box<ExecutionResult>>,
+        verification_start_timestamp: Timestamp,
+    },
     /// The result of the `DeployAcceptor` putting a `Deploy` to the storage component.
     PutToStorageResult {
         event_metadata: Box<EventMetadata>,
@@ -109,6 +117,18 @@ impl Display for Event {
             Event::Accept { deploy, source, .. } => {
                 write!(formatter, "accept {} from {}", deploy.hash(), source)
             }
+            Event::SimulationResult {
+                event_metadata,
+                block_header,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "simulated deploy with hash {} with state hash {}",
+                    event_metadata.deploy.hash(),
+                    block_header.state_root_hash()
+                )
+            }
             Event::PutToStorageResult {
                 event_metadata,
                 is_new,
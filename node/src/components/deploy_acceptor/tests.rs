@@ -12,6 +12,7 @@ use futures::{
     channel::oneshot::{self, Sender},
     FutureExt,
 };
+use num_rational::Ratio;
 use prometheus::Registry;
 use reactor::ReactorEvent;
 use serde::Serialize;
@@ -57,6 +58,13 @@ const POLL_INTERVAL: Duration = Duration::from_millis(10);
 const TIMEOUT: Duration = Duration::from_secs(10);
 const ALICE_SECRET_KEY_BYTES: [u8; 32] = [123; 32];
 const BOB_SECRET_KEY_BYTES: [u8; 32] = [124; 32];
+/// A Wasm module importing `env::casper_future_host_function`, which no host provides.
+const MODULE_IMPORTING_UNKNOWN_HOST_FUNCTION: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x02, 0x23,
+    0x01, 0x03, 0x65, 0x6e, 0x76, 0x1b, 0x63, 0x61, 0x73, 0x70, 0x65, 0x72, 0x5f, 0x66, 0x75, 0x74,
+    0x75, 0x72, 0x65, 0x5f, 0x68, 0x6f, 0x73, 0x74, 0x5f, 0x66, 0x75, 0x6e, 0x63, 0x74, 0x69, 0x6f,
+    0x6e, 0x00, 0x00,
+];
 
 /// Top-level event for the reactor.
 #[derive(Debug, From, Serialize)]
@@ -180,6 +188,7 @@ enum TestScenario {
     FromClientSessionContractPackage(ContractPackageScenario),
     DeployWithNativeTransferInPayment,
     DeployWithEmptySessionModuleBytes,
+    DeployWithDisallowedSessionImports,
     DeployWithoutPaymentAmount,
     DeployWithMangledPaymentAmount,
     DeployWithMangledTransferAmount,
@@ -196,6 +205,8 @@ enum TestScenario {
         administrators: BTreeSet<PublicKey>,
         secret_keys: Vec<[u8; 32]>,
     },
+    FromClientFailingSimulation,
+    FromClientFailingSimulationWhileValidator,
 }
 
 impl TestScenario {
@@ -235,10 +246,13 @@ impl TestScenario {
             | TestScenario::FromClientSessionContract(_)
             | TestScenario::FromClientSessionContractPackage(_)
             | TestScenario::DeployWithEmptySessionModuleBytes
+            | TestScenario::DeployWithDisallowedSessionImports
             | TestScenario::DeployWithNativeTransferInPayment
             | TestScenario::ShouldNotAcceptExpiredDeploySentByClient
             | TestScenario::ShouldAcceptDeployFromAdministrator { .. }
-            | TestScenario::ShouldRejectDeployFromNonAdministrator { .. } => Source::Client,
+            | TestScenario::ShouldRejectDeployFromNonAdministrator { .. }
+            | TestScenario::FromClientFailingSimulation
+            | TestScenario::FromClientFailingSimulationWhileValidator => Source::Client,
         }
     }
 
@@ -261,7 +275,9 @@ impl TestScenario {
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::AccountWithUnknownBalance
-            | TestScenario::BalanceCheckForDeploySentByPeer => {
+            | TestScenario::BalanceCheckForDeploySentByPeer
+            | TestScenario::FromClientFailingSimulation
+            | TestScenario::FromClientFailingSimulationWhileValidator => {
                 Deploy::random_valid_native_transfer(rng)
             }
             TestScenario::DeployWithoutPaymentAmount => Deploy::random_without_payment_amount(rng),
@@ -338,6 +354,12 @@ impl TestScenario {
             TestScenario::DeployWithEmptySessionModuleBytes => {
                 Deploy::random_with_empty_session_module_bytes(rng)
             }
+            TestScenario::DeployWithDisallowedSessionImports => {
+                Deploy::random_with_session_module_bytes(
+                    rng,
+                    Bytes::from(MODULE_IMPORTING_UNKNOWN_HOST_FUNCTION.to_vec()),
+                )
+            }
             TestScenario::DeployWithNativeTransferInPayment => {
                 Deploy::random_with_native_transfer_in_payment_logic(rng)
             }
@@ -383,6 +405,7 @@ impl TestScenario {
             | TestScenario::FromClientRepeatedValidDeploy
             | TestScenario::FromClientValidDeploy
             | TestScenario::FromClientSlightlyFutureDatedDeploy
+            | TestScenario::FromClientFailingSimulationWhileValidator
             | TestScenario::ShouldAcceptExpiredDeploySentByPeer=> true,
             TestScenario::FromPeerInvalidDeploy
             | TestScenario::FromClientInsufficientBalance
@@ -393,6 +416,7 @@ impl TestScenario {
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::AccountWithUnknownBalance
             | TestScenario::DeployWithEmptySessionModuleBytes
+            | TestScenario::DeployWithDisallowedSessionImports
             | TestScenario::DeployWithNativeTransferInPayment
             | TestScenario::DeployWithoutPaymentAmount
             | TestScenario::DeployWithMangledPaymentAmount
@@ -400,6 +424,7 @@ impl TestScenario {
             | TestScenario::DeployWithoutTransferAmount
             | TestScenario::DeployWithoutTransferTarget
             | TestScenario::BalanceCheckForDeploySentByPeer
+            | TestScenario::FromClientFailingSimulation
             | TestScenario::ShouldNotAcceptExpiredDeploySentByClient => false,
             TestScenario::FromPeerCustomPaymentContract(contract_scenario)
             | TestScenario::FromPeerSessionContract(contract_scenario)
@@ -434,6 +459,30 @@ impl TestScenario {
         )
     }
 
+    fn is_simulation_case(&self) -> bool {
+        matches!(
+            self,
+            TestScenario::FromClientFailingSimulation
+                | TestScenario::FromClientFailingSimulationWhileValidator
+        )
+    }
+
+    fn create_validator_matrix(&self, rng: &mut NodeRng) -> ValidatorMatrix {
+        let secret_key = Arc::new(SecretKey::random(rng));
+        if *self == TestScenario::FromClientFailingSimulationWhileValidator {
+            return ValidatorMatrix::new_with_validator(secret_key);
+        }
+        let public_key = PublicKey::from(&*secret_key);
+        ValidatorMatrix::new(
+            Ratio::new(1, 3),
+            None,
+            EraId::from(0),
+            secret_key,
+            public_key,
+            1,
+        )
+    }
+
     pub(crate) fn create_chainspec_for_test(&self, chainspec: Arc<Chainspec>) -> Arc<Chainspec> {
         match self {
             TestScenario::ShouldAcceptDeployFromAdministrator { administrators, .. }
@@ -441,6 +490,7 @@ impl TestScenario {
                 let mut chainspec = Chainspec {
                     protocol_config: chainspec.protocol_config.clone(),
                     network_config: chainspec.network_config.clone(),
+                    network_features_config: chainspec.network_features_config,
                     core_config: chainspec.core_config.clone(),
                     highway_config: chainspec.highway_config,
                     deploy_config: chainspec.deploy_config,
@@ -505,14 +555,19 @@ impl reactor::Reactor for Reactor {
         _network_identity: NetworkIdentity,
         registry: &Registry,
         _event_queue: EventQueueHandle<Self::Event>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
     ) -> Result<(Self, Effects<Self::Event>), Self::Error> {
         let (storage_config, storage_tempdir) = storage::Config::default_for_tests();
         let storage_withdir = WithDir::new(storage_tempdir.path(), storage_config);
 
+        let acceptor_config = Config {
+            simulate_against_tip: config.is_simulation_case(),
+            ..Config::default()
+        };
         let deploy_acceptor = DeployAcceptor::new(
-            Config::default(),
+            acceptor_config,
             &config.create_chainspec_for_test(Arc::clone(&chainspec)),
+            config.create_validator_matrix(rng),
             registry,
         )
         .unwrap();
@@ -707,6 +762,17 @@ impl reactor::Reactor for Reactor {
                         };
                     responder.respond(Ok(balance_result)).ignore()
                 }
+                ContractRuntimeRequest::SpeculativeDeployExecution { responder, .. } => {
+                    let execution_result = ExecutionResult::Failure {
+                        effect: Default::default(),
+                        transfers: vec![],
+                        cost: U512::zero(),
+                        error_message: "simulated failure".to_string(),
+                    };
+                    responder
+                        .respond(Ok(Some((execution_result, None))))
+                        .ignore()
+                }
                 _ => panic!("should not receive {:?}", event),
             },
             Event::NetworkRequest(_) => panic!("test does not handle network requests"),
@@ -891,6 +957,7 @@ async fn run_deploy_acceptor_without_timeout(
             | TestScenario::FromClientAccountWithInvalidAssociatedKeys
             | TestScenario::FromClientAccountWithInsufficientWeight
             | TestScenario::DeployWithEmptySessionModuleBytes
+            | TestScenario::DeployWithDisallowedSessionImports
             | TestScenario::AccountWithUnknownBalance
             | TestScenario::DeployWithNativeTransferInPayment
             | TestScenario::DeployWithoutPaymentAmount
@@ -898,6 +965,7 @@ async fn run_deploy_acceptor_without_timeout(
             | TestScenario::DeployWithMangledTransferAmount
             | TestScenario::DeployWithoutTransferTarget
             | TestScenario::DeployWithoutTransferAmount
+            | TestScenario::FromClientFailingSimulation
             | TestScenario::ShouldNotAcceptExpiredDeploySentByClient => {
                 matches!(
                     event,
@@ -992,7 +1060,8 @@ async fn run_deploy_acceptor_without_timeout(
             // Check that a, new and valid, deploy sent by a client raises an `AcceptedNewDeploy`
             // announcement with the appropriate source.
             TestScenario::FromClientValidDeploy
-            | TestScenario::FromClientSlightlyFutureDatedDeploy => {
+            | TestScenario::FromClientSlightlyFutureDatedDeploy
+            | TestScenario::FromClientFailingSimulationWhileValidator => {
                 matches!(
                     event,
                     Event::DeployAcceptorAnnouncement(
@@ -1587,6 +1656,19 @@ async fn should_reject_deploy_with_empty_module_bytes_in_session() {
     ))
 }
 
+#[tokio::test]
+async fn should_reject_deploy_with_disallowed_imports_in_session() {
+    let test_scenario = TestScenario::DeployWithDisallowedSessionImports;
+    let result = run_deploy_acceptor(test_scenario).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::InvalidDeployParameters {
+            failure: DeployParameterFailure::DisallowedImports { .. },
+            ..
+        })
+    ))
+}
+
 #[tokio::test]
 async fn should_reject_deploy_with_transfer_in_payment() {
     let test_scenario = TestScenario::DeployWithNativeTransferInPayment;
@@ -1709,3 +1791,18 @@ async fn should_reject_valid_deploy_from_peer_signed_by_non_administrator() {
     let result = run_deploy_acceptor(test_scenario).await;
     assert!(result.is_err())
 }
+
+#[tokio::test]
+async fn should_reject_deploy_from_client_failing_simulation() {
+    let result = run_deploy_acceptor(TestScenario::FromClientFailingSimulation).await;
+    assert!(matches!(
+        result,
+        Err(super::Error::SimulatedExecutionFailure { .. })
+    ))
+}
+
+#[tokio::test]
+async fn should_not_simulate_deploy_while_validator() {
+    let result = run_deploy_acceptor(TestScenario::FromClientFailingSimulationWhileValidator).await;
+    assert!(result.is_ok())
+}
@@ -1,6 +1,7 @@
 mod config;
 mod event;
 mod metrics;
+mod proposal_policy;
 #[cfg(test)]
 mod tests;
 
@@ -36,14 +37,15 @@ use crate::{
     types::{
         appendable_block::{AddError, AppendableBlock},
         chainspec::DeployConfig,
-        Approval, Block, Deploy, DeployFootprint, DeployHash, DeployHashWithApprovals, DeployId,
-        FinalizedBlock,
+        Approval, Block, Deploy, DeployExpiryReason, DeployFootprint, DeployHash,
+        DeployHashWithApprovals, DeployId, FinalizedBlock, FootprintCache,
     },
     utils::DisplayIter,
     NodeRng,
 };
 pub(crate) use config::Config;
 pub(crate) use event::Event;
+pub(crate) use proposal_policy::{configured_proposal_policy, ProposalCandidate, ProposalPolicy};
 
 use metrics::Metrics;
 
@@ -72,6 +74,15 @@ pub(crate) struct DeployBuffer {
     hold: BTreeMap<Timestamp, HashSet<DeployHash>>,
     // deploy_hashes that should not be proposed, ever
     dead: HashSet<DeployHash>,
+    // deploy_hashes put in `dead` when proposing a block rather than by being included in one,
+    // with the reason they were dropped, reported once they expire
+    rejected: HashMap<DeployHash, DeployExpiryReason>,
+    // footprints of deploys, shared with the block validator
+    #[data_size(skip)]
+    footprint_cache: FootprintCache,
+    // orders and filters the deploys included in this node's proposals
+    #[data_size(skip)]
+    proposal_policy: Box<dyn ProposalPolicy>,
     // deploy buffer metrics
     #[data_size(skip)]
     metrics: Metrics,
@@ -82,6 +93,7 @@ impl DeployBuffer {
     pub(crate) fn new(
         deploy_config: DeployConfig,
         cfg: Config,
+        footprint_cache: FootprintCache,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(DeployBuffer {
@@ -91,10 +103,19 @@ impl DeployBuffer {
             buffer: HashMap::new(),
             hold: BTreeMap::new(),
             dead: HashSet::new(),
+            rejected: HashMap::new(),
+            footprint_cache,
+            proposal_policy: Box::new(proposal_policy::DefaultProposalPolicy),
             metrics: Metrics::new(registry)?,
         })
     }
 
+    /// Registers the policy ordering and filtering the deploys included in this node's proposals,
+    /// replacing the default one which proposes all eligible deploys.
+    pub(crate) fn set_proposal_policy(&mut self, proposal_policy: Box<dyn ProposalPolicy>) {
+        self.proposal_policy = proposal_policy;
+    }
+
     pub(crate) fn initialize_component(
         &mut self,
         effect_builder: EffectBuilder<MainEvent>,
@@ -152,7 +173,7 @@ impl DeployBuffer {
         REv: From<Event> + From<DeployBufferAnnouncement> + Send,
     {
         let now = Timestamp::now();
-        let (buffer, mut freed): (HashMap<_, _>, _) = mem::take(&mut self.buffer)
+        let (buffer, freed): (HashMap<_, _>, _) = mem::take(&mut self.buffer)
             .into_iter()
             .partition(|(_, (expiry_time, _))| *expiry_time >= now);
 
@@ -166,27 +187,31 @@ impl DeployBuffer {
         });
         self.hold.retain(|_, remaining| !remaining.is_empty());
 
-        // retain all those in `dead` which are not in `freed`, at the same time reducing `freed` to
-        // only those entries not also in `dead` - i.e. deploys which expired without being included
-        // in a block
-        self.dead
-            .retain(|deploy_hash| freed.remove(deploy_hash).is_none());
+        // deploys which expired without being included in a block are those not in `dead`, and
+        // those put in `dead` when they were dropped from a proposed block
+        let mut expired = Vec::new();
+        for deploy_hash in freed.into_keys() {
+            let was_dead = self.dead.remove(&deploy_hash);
+            match self.rejected.remove(&deploy_hash) {
+                Some(reason) => expired.push((deploy_hash, reason)),
+                None if !was_dead => expired.push((deploy_hash, DeployExpiryReason::Expired)),
+                None => (),
+            }
+        }
         self.buffer = buffer;
 
-        if !freed.is_empty() {
+        if !expired.is_empty() {
             info!(
                 "DeployBuffer: expiring without executing {} deploy(s)",
-                freed.len()
+                expired.len()
             );
             debug!(
                 "DeployBuffer: expiring without executing {}",
-                DisplayIter::new(freed.keys())
+                DisplayIter::new(expired.iter().map(|(deploy_hash, _)| deploy_hash))
             );
         }
 
-        let mut effects = effect_builder
-            .announce_expired_deploys(freed.keys().cloned().collect())
-            .ignore();
+        let mut effects = effect_builder.announce_expired_deploys(expired).ignore();
         effects.extend(
             effect_builder
                 .set_timeout(self.cfg.expiry_check_interval().into())
@@ -225,7 +250,7 @@ impl DeployBuffer {
             info!(%deploy_hash, "DeployBuffer: attempt to register already held deploy");
             return;
         }
-        let footprint = match deploy.footprint() {
+        let footprint = match self.footprint_cache.footprint(&deploy) {
             Ok(footprint) => footprint,
             Err(err) => {
                 error!(%deploy_hash, %err, "DeployBuffer: deploy footprint exceeds tolerances");
@@ -283,6 +308,7 @@ impl DeployBuffer {
                 self.buffer.insert(*deploy_hash, (expiry_timestamp, None));
             }
             self.dead.insert(*deploy_hash);
+            self.rejected.remove(deploy_hash);
         }
         // deploys held for proposed blocks which did not get finalized in time are eligible again
         let (hold, _) = mem::take(&mut self.hold)
@@ -332,18 +358,29 @@ impl DeployBuffer {
         let mut holds = HashSet::new();
         let mut have_hit_transfer_limit = false;
         let mut have_hit_deploy_limit = false;
-        for (with_approvals, footprint) in self.proposable() {
-            if footprint.is_transfer && have_hit_transfer_limit {
+        let candidates = self
+            .proposable()
+            .into_iter()
+            .map(|(with_approvals, footprint)| ProposalCandidate::new(with_approvals, footprint))
+            .collect();
+        for candidate in self.proposal_policy.select(candidates) {
+            let (with_approvals, footprint) = candidate.into_inner();
+            let is_transfer = footprint.category.is_transfer();
+            if is_transfer && have_hit_transfer_limit {
                 continue;
             }
-            if !footprint.is_transfer && have_hit_deploy_limit {
+            if !is_transfer && have_hit_deploy_limit {
                 continue;
             }
             let deploy_hash = *with_approvals.deploy_hash();
             let has_multiple_approvals = with_approvals.approvals().len() > 1;
             match ret.add(with_approvals, &footprint) {
                 Ok(_) => {
-                    debug!(%deploy_hash, "DeployBuffer: proposing deploy");
+                    debug!(
+                        %deploy_hash,
+                        category = %footprint.category,
+                        "DeployBuffer: proposing deploy"
+                    );
                     holds.insert(deploy_hash);
                 }
                 Err(error) => {
@@ -357,14 +394,16 @@ impl DeployBuffer {
                             );
                             self.dead.insert(deploy_hash);
                         }
-                        AddError::Expired => {
+                        AddError::Expired { .. } => {
                             info!(
                                 ?deploy_hash,
                                 "DeployBuffer: expired deploy in deploy buffer"
                             );
                             self.dead.insert(deploy_hash);
+                            self.rejected
+                                .insert(deploy_hash, DeployExpiryReason::Expired);
                         }
-                        AddError::InvalidDeploy => {
+                        AddError::InvalidDeploy(_) => {
                             // It should not generally be possible for an invalid deploy to get
                             // buffered in the first place, thus this should be unreachable.  There
                             // is a small potential for a slightly future-dated deploy to be
@@ -372,9 +411,12 @@ impl DeployBuffer {
                             // the time we try and add it to a proposed block here.
                             warn!(
                                 ?deploy_hash,
+                                %error,
                                 "DeployBuffer: invalid deploy in deploy buffer"
                             );
                             self.dead.insert(deploy_hash);
+                            self.rejected
+                                .insert(deploy_hash, DeployExpiryReason::InvalidAtProposal);
                         }
                         AddError::TransferCount => {
                             if have_hit_deploy_limit {
@@ -399,7 +441,9 @@ impl DeployBuffer {
                         AddError::ApprovalCount if has_multiple_approvals => {
                             // keep iterating, we can maybe fit in a deploy with fewer approvals
                         }
-                        AddError::ApprovalCount | AddError::GasLimit | AddError::BlockSize => {
+                        AddError::ApprovalCount
+                        | AddError::GasLimit { .. }
+                        | AddError::BlockSize => {
                             info!(
                                 ?deploy_hash,
                                 %error,
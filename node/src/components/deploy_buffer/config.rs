@@ -1,15 +1,23 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use casper_types::TimeDiff;
+use casper_types::{ContractHash, ContractPackageHash, TimeDiff};
 
 const DEFAULT_EXPIRY_CHECK_INTERVAL: &str = "1min";
 
-#[derive(Copy, Clone, DataSize, Debug, Deserialize, Serialize)]
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct Config {
     /// The interval of checking for expired deploys.
     pub expiry_check_interval: TimeDiff,
+    /// The hashes of the stored contracts which deploys proposed by this node must not call by
+    /// hash.
+    #[serde(default)]
+    pub excluded_contract_hashes: Vec<ContractHash>,
+    /// The hashes of the stored contract packages which deploys proposed by this node must not
+    /// call by hash.
+    #[serde(default)]
+    pub excluded_contract_package_hashes: Vec<ContractPackageHash>,
 }
 
 impl Config {
@@ -23,6 +31,8 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             expiry_check_interval: DEFAULT_EXPIRY_CHECK_INTERVAL.parse().unwrap(),
+            excluded_contract_hashes: Vec::new(),
+            excluded_contract_package_hashes: Vec::new(),
         }
     }
 }
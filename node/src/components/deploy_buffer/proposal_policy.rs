@@ -0,0 +1,139 @@
+//! Policies letting operators order and filter the deploys included in their own proposals.
+//!
+//! A policy is handed the deploys the deploy buffer would propose, and returns those to actually
+//! propose, in order of preference.  It can neither add deploys nor duplicate them, as candidates
+//! can only be created by the deploy buffer, and every deploy it returns is still checked against
+//! the block limits.  Other validators validate proposed blocks regardless of any policy, so a
+//! policy only affects which valid deploys this node proposes, never the validity of its
+//! proposals.  Deploys a policy leaves out stay buffered, and may be proposed later.
+
+use std::{collections::BTreeSet, fmt::Debug};
+
+use tracing::debug;
+
+use casper_execution_engine::core::engine_state::executable_deploy_item::{
+    ContractIdentifier, ContractPackageIdentifier, ExecutableDeployItemIdentifier,
+};
+use casper_types::{ContractHash, ContractPackageHash};
+
+use super::Config;
+use crate::types::{DeployFootprint, DeployHash, DeployHashWithApprovals};
+
+/// A deploy the deploy buffer could include in a proposal.
+#[derive(Debug)]
+pub(crate) struct ProposalCandidate {
+    with_approvals: DeployHashWithApprovals,
+    footprint: DeployFootprint,
+}
+
+impl ProposalCandidate {
+    pub(super) fn new(with_approvals: DeployHashWithApprovals, footprint: DeployFootprint) -> Self {
+        ProposalCandidate {
+            with_approvals,
+            footprint,
+        }
+    }
+
+    /// Returns the hash of the deploy.
+    pub(crate) fn deploy_hash(&self) -> &DeployHash {
+        self.with_approvals.deploy_hash()
+    }
+
+    /// Returns the footprint of the deploy.
+    pub(crate) fn footprint(&self) -> &DeployFootprint {
+        &self.footprint
+    }
+
+    pub(super) fn into_inner(self) -> (DeployHashWithApprovals, DeployFootprint) {
+        (self.with_approvals, self.footprint)
+    }
+}
+
+/// Orders and filters the deploys included in this node's proposals.
+pub(crate) trait ProposalPolicy: Debug + Send {
+    /// Returns the candidates to propose, in order of preference.
+    fn select(&self, candidates: Vec<ProposalCandidate>) -> Vec<ProposalCandidate>;
+}
+
+/// The default policy, proposing all candidates in the order the deploy buffer yields them.
+#[derive(Debug, Default)]
+pub(crate) struct DefaultProposalPolicy;
+
+impl ProposalPolicy for DefaultProposalPolicy {
+    fn select(&self, candidates: Vec<ProposalCandidate>) -> Vec<ProposalCandidate> {
+        candidates
+    }
+}
+
+/// A policy leaving out the deploys whose session code calls any of the given stored contracts or
+/// contract packages by hash.
+///
+/// Deploys calling them by name are not left out, as names are only resolved during execution.
+#[derive(Debug)]
+pub(crate) struct ExcludedContractsPolicy {
+    contract_hashes: BTreeSet<ContractHash>,
+    contract_package_hashes: BTreeSet<ContractPackageHash>,
+}
+
+impl ExcludedContractsPolicy {
+    pub(crate) fn new(
+        contract_hashes: BTreeSet<ContractHash>,
+        contract_package_hashes: BTreeSet<ContractPackageHash>,
+    ) -> Self {
+        ExcludedContractsPolicy {
+            contract_hashes,
+            contract_package_hashes,
+        }
+    }
+
+    fn is_excluded(&self, footprint: &DeployFootprint) -> bool {
+        match &footprint.session {
+            ExecutableDeployItemIdentifier::Contract(ContractIdentifier::Hash(contract_hash)) => {
+                self.contract_hashes.contains(contract_hash)
+            }
+            ExecutableDeployItemIdentifier::Package(ContractPackageIdentifier::Hash {
+                contract_package_hash,
+                ..
+            }) => self.contract_package_hashes.contains(contract_package_hash),
+            ExecutableDeployItemIdentifier::Module
+            | ExecutableDeployItemIdentifier::Contract(ContractIdentifier::Name(_))
+            | ExecutableDeployItemIdentifier::Package(ContractPackageIdentifier::Name { .. })
+            | ExecutableDeployItemIdentifier::Transfer => false,
+        }
+    }
+}
+
+impl ProposalPolicy for ExcludedContractsPolicy {
+    fn select(&self, candidates: Vec<ProposalCandidate>) -> Vec<ProposalCandidate> {
+        candidates
+            .into_iter()
+            .filter(|candidate| {
+                let is_excluded = self.is_excluded(candidate.footprint());
+                if is_excluded {
+                    debug!(
+                        deploy_hash = %candidate.deploy_hash(),
+                        "DeployBuffer: not proposing deploy calling an excluded contract"
+                    );
+                }
+                !is_excluded
+            })
+            .collect()
+    }
+}
+
+/// Returns the policy set up in `config`.
+pub(crate) fn configured_proposal_policy(config: &Config) -> Box<dyn ProposalPolicy> {
+    if config.excluded_contract_hashes.is_empty()
+        && config.excluded_contract_package_hashes.is_empty()
+    {
+        return Box::new(DefaultProposalPolicy);
+    }
+    Box::new(ExcludedContractsPolicy::new(
+        config.excluded_contract_hashes.iter().copied().collect(),
+        config
+            .excluded_contract_package_hashes
+            .iter()
+            .copied()
+            .collect(),
+    ))
+}
@@ -2,10 +2,10 @@ use super::*;
 use crate::{
     effect::announcements::DeployBufferAnnouncement::{self, DeploysExpired},
     reactor::{EventQueueHandle, QueueKind, Scheduler},
-    types::{Block, FinalizedBlock},
+    types::{Block, FinalizedBlock, FootprintCache},
     utils,
 };
-use casper_types::{testing::TestRng, EraId, TimeDiff};
+use casper_types::{testing::TestRng, ContractHash, EraId, TimeDiff};
 use prometheus::Registry;
 use rand::Rng;
 
@@ -108,8 +108,13 @@ fn assert_container_sizes(
 #[test]
 fn register_deploy_and_check_size() {
     let mut rng = TestRng::new();
-    let mut deploy_buffer =
-        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
 
     // Try to register valid deploys
     let num_valid_deploys: usize = rng.gen_range(50..500);
@@ -145,8 +150,13 @@ fn register_deploy_and_check_size() {
 #[test]
 fn register_block_with_valid_deploys() {
     let mut rng = TestRng::new();
-    let mut deploy_buffer =
-        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
 
     let deploys = create_valid_deploys(&mut rng, 10, DeployType::Random, None, None);
     let block = Block::random_with_deploys(&mut rng, deploys.iter());
@@ -158,8 +168,13 @@ fn register_block_with_valid_deploys() {
 #[test]
 fn register_finalized_block_with_valid_deploys() {
     let mut rng = TestRng::new();
-    let mut deploy_buffer =
-        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
 
     let deploys = create_valid_deploys(&mut rng, 10, DeployType::Random, None, None);
     let block = FinalizedBlock::random_with_deploys(&mut rng, deploys.iter());
@@ -171,8 +186,13 @@ fn register_finalized_block_with_valid_deploys() {
 #[test]
 fn get_proposable_deploys() {
     let mut rng = TestRng::new();
-    let mut deploy_buffer =
-        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
 
     // populate deploy buffer with some deploys
     let deploys = create_valid_deploys(&mut rng, 50, DeployType::Random, None, None);
@@ -234,8 +254,13 @@ fn get_appendable_block_with_native_transfers() {
         block_max_approval_count: 210,
         ..Default::default()
     };
-    let mut deploy_buffer =
-        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        deploy_config,
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
     get_appendable_block(
         &mut rng,
         &mut deploy_buffer,
@@ -253,8 +278,13 @@ fn get_appendable_block_with_standard_deploys() {
         block_max_approval_count: 210,
         ..Default::default()
     };
-    let mut deploy_buffer =
-        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        deploy_config,
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
     get_appendable_block(
         &mut rng,
         &mut deploy_buffer,
@@ -272,8 +302,13 @@ fn get_appendable_block_with_random_deploys() {
         block_max_approval_count: 210,
         ..Default::default()
     };
-    let mut deploy_buffer =
-        DeployBuffer::new(deploy_config, Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        deploy_config,
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
     get_appendable_block(
         &mut rng,
         &mut deploy_buffer,
@@ -310,8 +345,13 @@ fn get_appendable_block(
 #[test]
 fn register_deploys_and_blocks() {
     let mut rng = TestRng::new();
-    let mut deploy_buffer =
-        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
 
     // try to register valid deploys
     let num_valid_deploys: usize = rng.gen_range(50..500);
@@ -426,7 +466,7 @@ impl MockReactor {
 
     async fn expect_deploy_buffer_expire_announcement(
         &self,
-        should_be_expired: &HashSet<DeployHash>,
+        should_be_expired: &HashSet<(DeployHash, DeployExpiryReason)>,
     ) {
         let ((_ancestor, reactor_event), _) = self.scheduler.pop().await;
         match reactor_event {
@@ -444,8 +484,13 @@ impl MockReactor {
 #[tokio::test]
 async fn expire_deploys_and_check_announcement() {
     let mut rng = TestRng::new();
-    let mut deploy_buffer =
-        DeployBuffer::new(DeployConfig::default(), Config::default(), &Registry::new()).unwrap();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
 
     let reactor = MockReactor::new();
     let event_queue_handle = EventQueueHandle::without_shutdown(reactor.scheduler);
@@ -491,7 +536,7 @@ async fn expire_deploys_and_check_announcement() {
     let expired_deploy_hashes: HashSet<_> = expired_deploys
         .iter()
         .take(expired_deploys.len() - 1)
-        .map(|deploy| *deploy.hash())
+        .map(|deploy| (*deploy.hash(), DeployExpiryReason::Expired))
         .collect();
     reactor
         .expect_deploy_buffer_expire_announcement(&expired_deploy_hashes)
@@ -500,3 +545,123 @@ async fn expire_deploys_and_check_announcement() {
     // the valid deploys should still be in the buffer
     assert_container_sizes(&deploy_buffer, deploys.len(), 0, 0);
 }
+
+#[tokio::test]
+async fn expire_deploys_dropped_from_proposals_with_reason() {
+    let mut rng = TestRng::new();
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        Config::default(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
+
+    let reactor = MockReactor::new();
+    let event_queue_handle = EventQueueHandle::without_shutdown(reactor.scheduler);
+    let effect_builder = EffectBuilder::new(event_queue_handle);
+
+    // generate and register some already expired deploys
+    let ttl = TimeDiff::from_seconds(rng.gen_range(30..300));
+    let past_timestamp = Timestamp::now()
+        .saturating_sub(ttl)
+        .saturating_sub(TimeDiff::from_seconds(5));
+    let num_deploys: usize = rng.gen_range(5..50);
+    let invalid_deploys = create_valid_deploys(
+        &mut rng,
+        num_deploys,
+        DeployType::Transfer,
+        Some(past_timestamp),
+        Some(ttl),
+    );
+    invalid_deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+
+    // the deploys are future-dated relative to a proposal made before their timestamp
+    let appendable_block =
+        deploy_buffer.appendable_block(past_timestamp.saturating_sub(TimeDiff::from_seconds(1)));
+    assert!(appendable_block.deploy_and_transfer_set().is_empty());
+    assert_container_sizes(&deploy_buffer, num_deploys, num_deploys, 0);
+
+    // the last one is included in a block by another validator nonetheless
+    let block = Block::random_with_deploys(&mut rng, invalid_deploys.last());
+    deploy_buffer.register_block(&block);
+
+    // deploys registered after that are expired relative to a proposal made now
+    let expired_deploys = create_valid_deploys(
+        &mut rng,
+        num_deploys,
+        DeployType::Transfer,
+        Some(past_timestamp),
+        Some(ttl),
+    );
+    expired_deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    assert!(appendable_block.deploy_and_transfer_set().is_empty());
+    assert_container_sizes(&deploy_buffer, 2 * num_deploys, 2 * num_deploys, 0);
+
+    let mut effects = deploy_buffer.expire(effect_builder);
+    tokio::spawn(effects.remove(0)).await.unwrap();
+
+    let expected: HashSet<_> = invalid_deploys
+        .iter()
+        .take(num_deploys - 1)
+        .map(|deploy| (*deploy.hash(), DeployExpiryReason::InvalidAtProposal))
+        .chain(
+            expired_deploys
+                .iter()
+                .map(|deploy| (*deploy.hash(), DeployExpiryReason::Expired)),
+        )
+        .collect();
+    reactor
+        .expect_deploy_buffer_expire_announcement(&expected)
+        .await;
+    assert_container_sizes(&deploy_buffer, 0, 0, 0);
+    assert!(deploy_buffer.rejected.is_empty());
+}
+
+#[test]
+fn should_not_propose_deploys_excluded_by_policy() {
+    let mut rng = TestRng::new();
+    let config = Config {
+        excluded_contract_hashes: vec![ContractHash::default()],
+        ..Config::default()
+    };
+    let mut deploy_buffer = DeployBuffer::new(
+        DeployConfig::default(),
+        config.clone(),
+        FootprintCache::default(),
+        &Registry::new(),
+    )
+    .unwrap();
+    deploy_buffer.set_proposal_policy(configured_proposal_policy(&config));
+
+    let deploys = create_valid_deploys(&mut rng, 10, DeployType::Standard, None, None);
+    deploys
+        .iter()
+        .for_each(|deploy| deploy_buffer.register_deploy(deploy.clone()));
+    // Calls the stored contract with the default hash.
+    let excluded_deploy = Deploy::random_with_missing_session_contract_by_hash(&mut rng);
+    deploy_buffer.register_deploy(excluded_deploy.clone());
+    assert_container_sizes(&deploy_buffer, deploys.len() + 1, 0, 0);
+
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    assert_eq!(
+        appendable_block.deploy_and_transfer_set().len(),
+        deploys.len()
+    );
+    assert!(!appendable_block
+        .deploy_and_transfer_set()
+        .contains(excluded_deploy.hash()));
+
+    // The excluded deploy stays buffered, and is proposed under the default policy.
+    assert_container_sizes(&deploy_buffer, deploys.len() + 1, 0, deploys.len());
+    deploy_buffer.set_proposal_policy(Box::new(proposal_policy::DefaultProposalPolicy));
+    let appendable_block = deploy_buffer.appendable_block(Timestamp::now());
+    assert!(appendable_block
+        .deploy_and_transfer_set()
+        .contains(excluded_deploy.hash()));
+}
@@ -4,7 +4,9 @@
 //! deep debug access to a running node via special commands.
 
 mod command;
+mod memory_usage;
 mod stop_at;
+mod support_bundle;
 mod tasks;
 mod util;
 
@@ -12,6 +14,7 @@ use std::{
     fmt::{self, Display, Formatter},
     fs, io,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use datasize::DataSize;
@@ -25,7 +28,10 @@ use crate::{
     effect::{
         announcements::ControlAnnouncement,
         diagnostics_port::DumpConsensusStateRequest,
-        requests::{NetworkInfoRequest, SetNodeStopRequest},
+        requests::{
+            BlockSynchronizerRequest, MetricsRequest, NetworkInfoRequest, ReactorStatusRequest,
+            SetNodeStopRequest, StorageRequest,
+        },
         EffectBuilder, EffectExt, Effects,
     },
     reactor::main_reactor::MainEvent,
@@ -34,6 +40,7 @@ use crate::{
     WithDir,
 };
 pub(crate) use stop_at::StopAtSpec;
+pub(crate) use support_bundle::NodeInfo;
 pub use tasks::FileSerializer;
 use util::ShowUnixAddr;
 
@@ -68,15 +75,19 @@ pub(crate) struct DiagnosticsPort {
     #[data_size(skip)]
     _shutdown_sender: Option<watch::Sender<()>>, // only used for its `Drop` impl
     config: WithDir<Config>,
+    /// Information about the node included in support bundles.
+    #[data_size(skip)]
+    node_info: Arc<NodeInfo>,
 }
 
 impl DiagnosticsPort {
     /// Creates a new diagnostics port component.
-    pub(crate) fn new(config: WithDir<Config>) -> Self {
+    pub(crate) fn new(config: WithDir<Config>, node_info: NodeInfo) -> Self {
         DiagnosticsPort {
             state: ComponentState::Uninitialized,
             config,
             _shutdown_sender: None,
+            node_info: Arc::new(node_info),
         }
     }
 }
@@ -106,8 +117,12 @@ where
     REv: From<Event>
         + From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
+        + From<BlockSynchronizerRequest>
+        + From<MetricsRequest>
         + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
         + From<SetNodeStopRequest>
+        + From<StorageRequest>
         + Send,
 {
     type Event = Event;
@@ -160,8 +175,12 @@ where
     REv: From<Event>
         + From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
+        + From<BlockSynchronizerRequest>
+        + From<MetricsRequest>
         + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
         + From<SetNodeStopRequest>
+        + From<StorageRequest>
         + Send,
 {
     fn state(&self) -> &ComponentState {
@@ -184,8 +203,12 @@ where
     REv: From<Event>
         + From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
+        + From<BlockSynchronizerRequest>
+        + From<MetricsRequest>
         + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
         + From<SetNodeStopRequest>
+        + From<StorageRequest>
         + Send,
 {
     type Error = Error;
@@ -208,7 +231,13 @@ where
             #[allow(clippy::useless_conversion)]
             cfg.socket_umask.into(),
         )?;
-        let server = tasks::server(effect_builder, socket_path, listener, shutdown_receiver);
+        let server = tasks::server(
+            effect_builder,
+            Arc::clone(&self.node_info),
+            socket_path,
+            listener,
+            shutdown_receiver,
+        );
         Ok(server.ignore())
     }
 }
@@ -89,8 +89,25 @@ pub(super) enum Action {
     },
     /// Dump the event queues.
     DumpQueues,
+    /// Show the estimated heap memory usage of each component.
+    ///
+    /// The estimates are those of the last periodic update of the metrics, alongside the memory
+    /// allocated by the node if the allocator reports it.
+    DumpMemory,
     /// Get detailed networking insights.
     NetInfo,
+    /// Change the rate limits applied to non-validator peers.
+    ///
+    /// Limits which are omitted are left unchanged, a limit of `0` disables rate limiting. Returns
+    /// the limits in effect afterwards.
+    SetRateLimits {
+        /// Maximum number of bytes per second sent to non-validating peers.
+        #[structopt(short, long)]
+        outgoing: Option<u32>,
+        /// Maximum number of messages per second received from non-validating peers.
+        #[structopt(short, long)]
+        incoming: Option<u32>,
+    },
     /// Stop the node at a certain condition.
     Stop {
         /// When to stop the node.
@@ -131,6 +148,31 @@ pub(super) enum Action {
         /// The failpoint activation/deactivation.
         activation: String,
     },
+    /// Start recording dispatched reactor events.
+    ///
+    /// Each event's component, time spent queued and time taken to dispatch are recorded until
+    /// `stop-event-trace` is sent.
+    StartEventTrace {
+        /// Maximum number of events to record, further events are counted but discarded.
+        #[structopt(short, long, default_value = "1000000")]
+        max_events: usize,
+    },
+    /// Stop recording dispatched reactor events and send the recording.
+    ///
+    /// The recording is always sent in the Chrome trace event JSON format, regardless of the
+    /// session's output format, and can be loaded into `chrome://tracing` or Perfetto.
+    StopEventTrace,
+    /// Collect a support bundle and send it as a gzip-compressed tar archive.
+    ///
+    /// The bundle holds the node's config with secrets redacted, its status including the
+    /// chainspec hash, sync state, peer table, event queue depths, a metrics snapshot and the most
+    /// recent log lines. It is always sent as raw bytes, regardless of the session's output
+    /// format.
+    SupportBundle {
+        /// Maximum number of recent log lines to include.
+        #[structopt(short, long, default_value = "1000")]
+        log_lines: usize,
+    },
     /// Close connection server-side.
     Quit,
 }
@@ -162,5 +204,30 @@ mod tests {
 
         let cmd = Command::from_line("dump-queues").expect("command parsing failed");
         assert!(matches!(cmd.action, Action::DumpQueues));
+
+        let cmd = Command::from_line("dump-memory").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::DumpMemory));
+
+        let cmd = Command::from_line("start-event-trace -m 500").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::StartEventTrace { max_events } if max_events == 500
+        ));
+
+        let cmd = Command::from_line("stop-event-trace").expect("command parsing failed");
+        assert!(matches!(cmd.action, Action::StopEventTrace));
+
+        let cmd = Command::from_line("support-bundle -l 20").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::SupportBundle { log_lines } if log_lines == 20
+        ));
+
+        let cmd = Command::from_line("set-rate-limits -o 1000").expect("command parsing failed");
+        assert!(matches!(
+            cmd.action,
+            Action::SetRateLimits { outgoing, incoming }
+                if outgoing == Some(1000) && incoming.is_none()
+        ));
     }
 }
@@ -0,0 +1,107 @@
+//! Reporting of the node's memory usage, broken down by component.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Display, Formatter},
+};
+
+use serde::Serialize;
+
+/// Prefix of the metrics holding the estimated heap memory usage of the reactor's components.
+const ESTIMATED_MEMORY_METRICS_PREFIX: &str = "mem_";
+
+/// Name of the metric holding the estimated total heap memory usage.
+const ESTIMATED_TOTAL_METRIC: &str = "mem_total";
+
+/// Name of the metric holding the time taken to estimate memory usage, which is not a size.
+const ESTIMATOR_RUNTIME_METRIC: &str = "mem_estimator_runtime_s";
+
+/// Name of the metric holding the memory allocated by the node, as measured by the allocator.
+const ALLOCATED_MEMORY_METRIC: &str = "allocated_ram_bytes";
+
+/// The memory usage of the node as recorded by the most recent update of its metrics.
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub(super) struct MemoryUsage {
+    /// The estimated heap memory usage of all components, in bytes.
+    estimated_total: Option<i64>,
+    /// The memory allocated by the node, in bytes, if measured.
+    ///
+    /// A large difference to the estimated total points at memory not covered by the estimates.
+    allocated: Option<i64>,
+    /// The estimated heap memory usage of each component, in bytes.
+    components: BTreeMap<String, i64>,
+}
+
+impl MemoryUsage {
+    /// Extracts the memory usage from metrics rendered in the Prometheus text format.
+    pub(super) fn from_metrics(metrics: &str) -> Self {
+        let mut memory_usage = MemoryUsage::default();
+        let samples = metrics
+            .lines()
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.split_once(' '))
+            .filter_map(|(name, value)| Some((name, value.trim().parse::<i64>().ok()?)));
+        for (name, bytes) in samples {
+            if name == ESTIMATED_TOTAL_METRIC {
+                memory_usage.estimated_total = Some(bytes);
+            } else if name == ALLOCATED_MEMORY_METRIC {
+                memory_usage.allocated = Some(bytes);
+            } else if name.starts_with(ESTIMATOR_RUNTIME_METRIC) {
+                continue;
+            } else if let Some(component) = name.strip_prefix(ESTIMATED_MEMORY_METRICS_PREFIX) {
+                memory_usage.components.insert(component.to_string(), bytes);
+            }
+        }
+        memory_usage
+    }
+}
+
+impl Display for MemoryUsage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let display_bytes = |bytes: Option<i64>| match bytes {
+            Some(bytes) => bytes.to_string(),
+            None => "unknown".to_string(),
+        };
+        writeln!(
+            f,
+            "estimated total: {} bytes",
+            display_bytes(self.estimated_total)
+        )?;
+        write!(f, "allocated: {} bytes", display_bytes(self.allocated))?;
+
+        // List the largest components first.
+        let mut components: Vec<_> = self.components.iter().collect();
+        components.sort_by(|(_, bytes1), (_, bytes2)| bytes2.cmp(bytes1));
+        for (component, bytes) in components {
+            write!(f, "\n  {}: {} bytes", component, bytes)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_extract_memory_usage_from_metrics() {
+        let metrics = "\
+# HELP mem_consensus consensus memory usage in bytes
+# TYPE mem_consensus gauge
+mem_consensus 300
+mem_estimator_runtime_s_bucket{le=\"0.000000004\"} 0
+mem_estimator_runtime_s_count 12
+mem_storage 500
+mem_total 800
+allocated_ram_bytes 2000
+scheduler_queue_regular_count 3
+";
+        let memory_usage = MemoryUsage::from_metrics(metrics);
+        assert_eq!(memory_usage.estimated_total, Some(800));
+        assert_eq!(memory_usage.allocated, Some(2000));
+        assert_eq!(
+            memory_usage.components.into_iter().collect::<Vec<_>>(),
+            vec![("consensus".to_string(), 300), ("storage".to_string(), 500)]
+        );
+    }
+}
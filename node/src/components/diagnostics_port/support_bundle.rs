@@ -0,0 +1,346 @@
+//! Support bundles.
+//!
+//! A support bundle gathers the information usually needed to diagnose an issue with a node into a
+//! single gzip-compressed tar archive, suitable for attaching to bug reports. Secrets contained in
+//! the node's configuration are redacted before it is added to the bundle.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+};
+
+use flate2::{write::GzEncoder, Compression};
+use serde::Serialize;
+use serde_json::Value;
+
+use casper_hashing::Digest;
+use casper_types::{ProtocolVersion, Timestamp};
+
+use crate::{
+    effect::{
+        requests::{
+            BlockSynchronizerRequest, MetricsRequest, NetworkInfoRequest, ReactorStatusRequest,
+            StorageRequest,
+        },
+        EffectBuilder,
+    },
+    logging,
+    reactor::main_reactor::ReactorState,
+    types::{AvailableBlockRange, Chainspec, MinimalBlockInfo},
+    VERSION_STRING,
+};
+
+/// The directory holding all files within the archive.
+const BUNDLE_DIR: &str = "casper-node-support-bundle";
+
+/// Placeholder replacing the values of redacted config fields.
+const REDACTED: &str = "<redacted>";
+
+/// Config fields whose name contains any of these (case-insensitively) are redacted.
+const SENSITIVE_FIELD_MARKERS: &[&str] = &["secret", "password", "token", "identity"];
+
+/// Prefix of the metrics holding the number of events in the reactor's queues.
+const QUEUE_DEPTH_METRICS_PREFIX: &str = "scheduler_queue_";
+
+/// Size of a block in a tar archive.
+const TAR_BLOCK_SIZE: usize = 512;
+
+/// Information about the node which is fixed while it runs, included in support bundles.
+#[derive(Debug)]
+pub(crate) struct NodeInfo {
+    /// The node's configuration, with secrets redacted.
+    sanitized_config: Value,
+    /// The name of the network.
+    chainspec_name: String,
+    /// The hash of the active chainspec.
+    chainspec_hash: Digest,
+    /// The active protocol version.
+    protocol_version: ProtocolVersion,
+}
+
+impl NodeInfo {
+    /// Creates a new `NodeInfo`, redacting the secrets contained in `config`.
+    pub(crate) fn new<C: Serialize>(config: &C, chainspec: &Chainspec) -> Self {
+        let sanitized_config = match serde_json::to_value(config) {
+            Ok(mut config) => {
+                redact(&mut config);
+                config
+            }
+            Err(error) => Value::String(format!("failed to serialize config: {}", error)),
+        };
+        NodeInfo {
+            sanitized_config,
+            chainspec_name: chainspec.network_config.name.clone(),
+            chainspec_hash: chainspec.hash(),
+            protocol_version: chainspec.protocol_config.version,
+        }
+    }
+}
+
+/// The node's status at the time the support bundle was collected.
+#[derive(Debug, Serialize)]
+struct Status<'a> {
+    build_version: &'a str,
+    chainspec_name: &'a str,
+    chainspec_hash: Digest,
+    protocol_version: ProtocolVersion,
+    reactor_state: ReactorState,
+    last_progress: Timestamp,
+    highest_complete_block: Option<MinimalBlockInfo>,
+    available_block_range: AvailableBlockRange,
+    collected_at: Timestamp,
+}
+
+/// Collects a support bundle, including up to `log_lines` of the most recent log lines.
+///
+/// Returns the bundle as a gzip-compressed tar archive.
+pub(super) async fn collect<REv>(
+    effect_builder: EffectBuilder<REv>,
+    node_info: &NodeInfo,
+    log_lines: usize,
+) -> io::Result<Vec<u8>>
+where
+    REv: From<BlockSynchronizerRequest>
+        + From<MetricsRequest>
+        + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
+        + From<StorageRequest>
+        + Send,
+{
+    let collected_at = Timestamp::now();
+    let (reactor_state, last_progress) = effect_builder.get_reactor_status().await;
+    let status = Status {
+        build_version: VERSION_STRING.as_str(),
+        chainspec_name: &node_info.chainspec_name,
+        chainspec_hash: node_info.chainspec_hash,
+        protocol_version: node_info.protocol_version,
+        reactor_state,
+        last_progress,
+        highest_complete_block: effect_builder
+            .get_highest_complete_block_from_storage()
+            .await
+            .map(MinimalBlockInfo::from),
+        available_block_range: effect_builder
+            .get_available_block_range_from_storage()
+            .await,
+        collected_at,
+    };
+    let sync_status = effect_builder.get_block_synchronizer_status().await;
+    let peers: BTreeMap<String, String> = effect_builder
+        .network_peers()
+        .await
+        .into_iter()
+        .map(|(node_id, addr)| (node_id.to_string(), addr))
+        .collect();
+    let network_insights = effect_builder.get_network_insights().await;
+    let metrics = effect_builder
+        .get_metrics()
+        .await
+        .unwrap_or_else(|| "failed to render metrics\n".to_string());
+    let queue_depths: String = metrics
+        .lines()
+        .filter(|line| line.starts_with(QUEUE_DEPTH_METRICS_PREFIX))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    let mut logs = logging::recent_log_lines(log_lines).join("\n");
+    logs.push('\n');
+
+    let mut archive = TarGzBuilder::new(collected_at.millis() / 1000);
+    archive.append_json("config.json", &node_info.sanitized_config)?;
+    archive.append_json("status.json", &status)?;
+    archive.append_json("sync_state.json", &sync_status)?;
+    archive.append_json("peers.json", &peers)?;
+    archive.append_json("network_insights.json", &network_insights)?;
+    archive.append("queue_depths.txt", queue_depths.as_bytes())?;
+    archive.append("metrics.txt", metrics.as_bytes())?;
+    archive.append("logs.txt", logs.as_bytes())?;
+    archive.finish()
+}
+
+/// Replaces the values of all fields considered sensitive with a placeholder, recursively.
+///
+/// Fields which are unset are left as they are.
+fn redact(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field_value) in fields.iter_mut() {
+                let name = name.to_lowercase();
+                if SENSITIVE_FIELD_MARKERS
+                    .iter()
+                    .any(|marker| name.contains(marker))
+                {
+                    if !field_value.is_null() {
+                        *field_value = Value::String(REDACTED.to_string());
+                    }
+                } else {
+                    redact(field_value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact),
+        Value::Null | Value::Bool(_) | Value::Number(_) | Value::String(_) => {}
+    }
+}
+
+/// A minimal writer of gzip-compressed tar archives, holding regular files only.
+struct TarGzBuilder {
+    encoder: GzEncoder<Vec<u8>>,
+    /// The modification time of all files, in seconds since the Unix epoch.
+    mtime: u64,
+}
+
+impl TarGzBuilder {
+    fn new(mtime: u64) -> Self {
+        TarGzBuilder {
+            encoder: GzEncoder::new(Vec::new(), Compression::default()),
+            mtime,
+        }
+    }
+
+    /// Appends `value` serialized as pretty-printed JSON as the file `name`.
+    fn append_json<T: Serialize>(&mut self, name: &str, value: &T) -> io::Result<()> {
+        let mut data = serde_json::to_vec_pretty(value)?;
+        data.push(b'\n');
+        self.append(name, &data)
+    }
+
+    /// Appends `data` as the file `name` within the bundle directory.
+    fn append(&mut self, name: &str, data: &[u8]) -> io::Result<()> {
+        let path = format!("{}/{}", BUNDLE_DIR, name);
+        self.encoder
+            .write_all(&tar_header(&path, data.len() as u64, self.mtime)?)?;
+        self.encoder.write_all(data)?;
+        let padding = (TAR_BLOCK_SIZE - data.len() % TAR_BLOCK_SIZE) % TAR_BLOCK_SIZE;
+        self.encoder.write_all(&[0; TAR_BLOCK_SIZE][..padding])
+    }
+
+    /// Terminates the archive and returns its compressed bytes.
+    fn finish(mut self) -> io::Result<Vec<u8>> {
+        // An archive ends with two zero-filled blocks.
+        self.encoder.write_all(&[0; 2 * TAR_BLOCK_SIZE])?;
+        self.encoder.finish()
+    }
+}
+
+/// Returns the ustar header of a regular file.
+fn tar_header(path: &str, size: u64, mtime: u64) -> io::Result<[u8; TAR_BLOCK_SIZE]> {
+    if path.len() >= 100 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("path too long for tar header: {}", path),
+        ));
+    }
+    let mut header = [0; TAR_BLOCK_SIZE];
+    header[..path.len()].copy_from_slice(path.as_bytes());
+    write_octal(&mut header[100..108], 0o644); // mode
+    write_octal(&mut header[108..116], 0); // uid
+    write_octal(&mut header[116..124], 0); // gid
+    write_octal(&mut header[124..136], size);
+    write_octal(&mut header[136..148], mtime);
+    header[156] = b'0'; // regular file
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+
+    // The checksum is computed with its own field filled with spaces, and stored as six octal
+    // digits followed by a NUL byte and a space.
+    header[148..156].fill(b' ');
+    let checksum: u64 = header.iter().map(|byte| u64::from(*byte)).sum();
+    write_octal(&mut header[148..155], checksum);
+    Ok(header)
+}
+
+/// Fills `field` with `value` as zero-padded octal digits, followed by a NUL byte.
+fn write_octal(field: &mut [u8], value: u64) {
+    let width = field.len() - 1;
+    let digits = format!("{:0width$o}", value, width = width);
+    field[..width].copy_from_slice(&digits.as_bytes()[digits.len() - width..]);
+    field[width] = 0;
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+    use serde_json::json;
+
+    use super::*;
+
+    fn parse_octal(field: &[u8]) -> u64 {
+        let digits = std::str::from_utf8(field)
+            .unwrap()
+            .trim_end_matches(|c| c == '\0' || c == ' ');
+        u64::from_str_radix(digits, 8).unwrap()
+    }
+
+    #[test]
+    fn should_redact_secrets() {
+        let mut config = json!({
+            "consensus": { "secret_key_path": "/etc/casper/validator_keys/secret_key.pem" },
+            "network": {
+                "bind_address": "0.0.0.0:35000",
+                "identity": { "tls_certificate": "node.crt", "secret_key": "node.pem" },
+            },
+            "rpc_server": { "api_token": null },
+        });
+        redact(&mut config);
+
+        assert_eq!(
+            config,
+            json!({
+                "consensus": { "secret_key_path": REDACTED },
+                "network": { "bind_address": "0.0.0.0:35000", "identity": REDACTED },
+                "rpc_server": { "api_token": null },
+            })
+        );
+    }
+
+    #[test]
+    fn should_write_valid_tar_gz() {
+        let first_file = b"hello, world!\n".to_vec();
+        let second_file = vec![7; TAR_BLOCK_SIZE + 1];
+
+        let mut archive = TarGzBuilder::new(1_600_000_000);
+        archive.append("first.txt", &first_file).unwrap();
+        archive.append("second.bin", &second_file).unwrap();
+        let compressed = archive.finish().unwrap();
+
+        let mut tar = vec![];
+        GzDecoder::new(compressed.as_slice())
+            .read_to_end(&mut tar)
+            .unwrap();
+        // Two headers, one block of data for the first file, two for the second, and two blocks
+        // terminating the archive.
+        assert_eq!(tar.len(), 7 * TAR_BLOCK_SIZE);
+
+        let mut offset = 0;
+        for (name, data) in [("first.txt", &first_file), ("second.bin", &second_file)] {
+            let header = &tar[offset..offset + TAR_BLOCK_SIZE];
+            let path = format!("{}/{}", BUNDLE_DIR, name);
+            assert_eq!(&header[..path.len()], path.as_bytes());
+            assert_eq!(header[path.len()], 0);
+            assert_eq!(parse_octal(&header[124..136]), data.len() as u64);
+            assert_eq!(parse_octal(&header[136..148]), 1_600_000_000);
+            assert_eq!(&header[257..263], b"ustar\0");
+
+            let stored_checksum = parse_octal(&header[148..156]);
+            let checksum: u64 = header
+                .iter()
+                .enumerate()
+                .map(|(index, byte)| {
+                    if (148..156).contains(&index) {
+                        u64::from(b' ')
+                    } else {
+                        u64::from(*byte)
+                    }
+                })
+                .sum();
+            assert_eq!(stored_checksum, checksum);
+
+            offset += TAR_BLOCK_SIZE;
+            assert_eq!(&tar[offset..offset + data.len()], data.as_slice());
+            offset += (data.len() + TAR_BLOCK_SIZE - 1) / TAR_BLOCK_SIZE * TAR_BLOCK_SIZE;
+        }
+        assert!(tar[offset..].iter().all(|byte| *byte == 0));
+    }
+}
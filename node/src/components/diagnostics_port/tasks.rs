@@ -5,6 +5,7 @@ use std::{
     io,
     path::PathBuf,
     str::FromStr,
+    sync::Arc,
 };
 
 use bincode::{
@@ -27,6 +28,8 @@ use tracing_subscriber::{filter::ParseError, EnvFilter};
 
 use super::{
     command::{Action, Command, OutputFormat},
+    memory_usage::MemoryUsage,
+    support_bundle::{self, NodeInfo},
     util::ShowUnixAddr,
 };
 use crate::{
@@ -34,7 +37,10 @@ use crate::{
     effect::{
         announcements::{ControlAnnouncement, QueueDumpFormat},
         diagnostics_port::DumpConsensusStateRequest,
-        requests::{NetworkInfoRequest, SetNodeStopRequest},
+        requests::{
+            BlockSynchronizerRequest, MetricsRequest, NetworkInfoRequest, ReactorStatusRequest,
+            SetNodeStopRequest, StorageRequest,
+        },
         EffectBuilder,
     },
     failpoints::FailpointActivation,
@@ -196,14 +202,19 @@ impl Session {
     async fn process_line<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
+        node_info: &NodeInfo,
         writer: &mut OwnedWriteHalf,
         line: &str,
     ) -> io::Result<bool>
     where
         REv: From<DumpConsensusStateRequest>
             + From<ControlAnnouncement>
+            + From<BlockSynchronizerRequest>
+            + From<MetricsRequest>
             + From<NetworkInfoRequest>
+            + From<ReactorStatusRequest>
             + From<SetNodeStopRequest>
+            + From<StorageRequest>
             + Send,
     {
         debug!(%line, "line received");
@@ -318,12 +329,32 @@ impl Session {
                             }
                         };
                     }
+                    Action::DumpMemory => match effect_builder.get_metrics().await {
+                        Some(metrics) => {
+                            self.send_outcome(writer, &Outcome::success("dumping memory usage"))
+                                .await?;
+                            let memory_usage = MemoryUsage::from_metrics(&metrics);
+                            self.send_to_client(writer, &memory_usage).await?;
+                        }
+                        None => {
+                            self.send_outcome(writer, &Outcome::failed("failed to render metrics"))
+                                .await?;
+                        }
+                    },
                     Action::NetInfo => {
                         self.send_outcome(writer, &Outcome::success("collecting insights"))
                             .await?;
                         let insights = effect_builder.get_network_insights().await;
                         self.send_to_client(writer, &insights).await?;
                     }
+                    Action::SetRateLimits { outgoing, incoming } => {
+                        self.send_outcome(writer, &Outcome::success("setting rate limits"))
+                            .await?;
+                        let rate_limits = effect_builder
+                            .set_network_rate_limits(outgoing, incoming)
+                            .await;
+                        self.send_to_client(writer, &rate_limits).await?;
+                    }
                     Action::Stop { at, clear } => {
                         let (msg, stop_at) = if clear {
                             ("clearing stopping point", None)
@@ -361,6 +392,77 @@ impl Session {
                             }
                         }
                     }
+                    Action::StartEventTrace { max_events } => {
+                        let outcome = if effect_builder
+                            .diagnostics_port_start_event_trace(max_events)
+                            .await
+                        {
+                            Outcome::success("started event trace")
+                        } else {
+                            Outcome::failed("event trace already in progress")
+                        };
+                        self.send_outcome(writer, &outcome).await?;
+                    }
+                    Action::StopEventTrace => {
+                        match effect_builder.diagnostics_port_stop_event_trace().await {
+                            Some(event_trace) => match event_trace.to_chrome_trace() {
+                                Ok(data) => {
+                                    self.send_outcome(
+                                        writer,
+                                        &Outcome::success(format!(
+                                            "sending trace of {} events",
+                                            event_trace.len()
+                                        )),
+                                    )
+                                    .await?;
+                                    self.stream_to_client(writer, &mut data.as_slice()).await?;
+                                }
+                                Err(err) => {
+                                    self.send_outcome(
+                                        writer,
+                                        &Outcome::failed(format!(
+                                            "failed to serialize event trace: {}",
+                                            display_error(&err)
+                                        )),
+                                    )
+                                    .await?;
+                                }
+                            },
+                            None => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed("no event trace in progress"),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
+                    Action::SupportBundle { log_lines } => {
+                        match support_bundle::collect(effect_builder, node_info, log_lines).await {
+                            Ok(bundle) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::success(format!(
+                                        "sending support bundle of {} bytes",
+                                        bundle.len()
+                                    )),
+                                )
+                                .await?;
+                                self.stream_to_client(writer, &mut bundle.as_slice())
+                                    .await?;
+                            }
+                            Err(err) => {
+                                self.send_outcome(
+                                    writer,
+                                    &Outcome::failed(format!(
+                                        "failed to collect support bundle: {}",
+                                        display_error(&err)
+                                    )),
+                                )
+                                .await?;
+                            }
+                        }
+                    }
                     Action::Quit => {
                         self.send_outcome(writer, &Outcome::success("goodbye!"))
                             .await?;
@@ -495,14 +597,19 @@ fn set_log_filter(filter_str: &str) -> Result<(), SetLogFilterError> {
 /// the passed in `stream`.
 async fn handler<REv>(
     effect_builder: EffectBuilder<REv>,
+    node_info: Arc<NodeInfo>,
     stream: UnixStream,
     mut shutdown_receiver: watch::Receiver<()>,
 ) -> io::Result<()>
 where
     REv: From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
+        + From<BlockSynchronizerRequest>
+        + From<MetricsRequest>
         + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
         + From<SetNodeStopRequest>
+        + From<StorageRequest>
         + Send,
 {
     debug!("accepted new connection on diagnostics port");
@@ -523,7 +630,7 @@ where
             Either::Right((line_result, _)) => {
                 if let Some(line) = line_result? {
                     keep_going = session
-                        .process_line(effect_builder, &mut writer, line.as_str())
+                        .process_line(effect_builder, &node_info, &mut writer, line.as_str())
                         .await?;
                 } else {
                     info!("client closed diagnostics port connection");
@@ -539,14 +646,19 @@ where
 /// Server task for diagnostics port.
 pub(super) async fn server<REv>(
     effect_builder: EffectBuilder<REv>,
+    node_info: Arc<NodeInfo>,
     socket_path: PathBuf,
     listener: UnixListener,
     mut shutdown_receiver: watch::Receiver<()>,
 ) where
     REv: From<DumpConsensusStateRequest>
         + From<ControlAnnouncement>
+        + From<BlockSynchronizerRequest>
+        + From<MetricsRequest>
         + From<NetworkInfoRequest>
+        + From<ReactorStatusRequest>
         + From<SetNodeStopRequest>
+        + From<StorageRequest>
         + Send,
 {
     let handling_shutdown_receiver = shutdown_receiver.clone();
@@ -566,8 +678,13 @@ pub(super) async fn server<REv>(
                     next_client_id += 1;
 
                     tokio::spawn(
-                        handler(effect_builder, stream, handling_shutdown_receiver.clone())
-                            .instrument(span),
+                        handler(
+                            effect_builder,
+                            node_info.clone(),
+                            stream,
+                            handling_shutdown_receiver.clone(),
+                        )
+                        .instrument(span),
                     );
                 }
                 Err(err) => {
@@ -626,7 +743,10 @@ mod tests {
         effect::{
             announcements::ControlAnnouncement,
             diagnostics_port::DumpConsensusStateRequest,
-            requests::{NetworkInfoRequest, SetNodeStopRequest},
+            requests::{
+                BlockSynchronizerRequest, MetricsRequest, NetworkInfoRequest, ReactorStatusRequest,
+                SetNodeStopRequest, StorageRequest,
+            },
             EffectBuilder, EffectExt, Effects,
         },
         reactor::{
@@ -686,6 +806,14 @@ mod tests {
         NetworkInfoRequest(NetworkInfoRequest),
         #[from]
         SetNodeStopRequest(SetNodeStopRequest),
+        #[from]
+        BlockSynchronizerRequest(BlockSynchronizerRequest),
+        #[from]
+        MetricsRequest(#[serde(skip_serializing)] MetricsRequest),
+        #[from]
+        ReactorStatusRequest(ReactorStatusRequest),
+        #[from]
+        StorageRequest(StorageRequest),
     }
 
     impl Display for Event {
@@ -732,7 +860,11 @@ mod tests {
                 Event::DumpConsensusStateRequest(_)
                 | Event::SetNodeStopRequest(_)
                 | Event::ControlAnnouncement(_)
-                | Event::NetworkInfoRequest(_) => {
+                | Event::NetworkInfoRequest(_)
+                | Event::BlockSynchronizerRequest(_)
+                | Event::MetricsRequest(_)
+                | Event::ReactorStatusRequest(_)
+                | Event::StorageRequest(_) => {
                     panic!("unexpected: {}", event)
                 }
             }
@@ -740,15 +872,18 @@ mod tests {
 
         fn new(
             cfg: TestReactorConfig,
-            _chainspec: Arc<Chainspec>,
+            chainspec: Arc<Chainspec>,
             _chainspec_raw_bytes: Arc<ChainspecRawBytes>,
             _network_identity: NetworkIdentity,
             _registry: &Registry,
             _event_queue: EventQueueHandle<Event>,
             _rng: &mut NodeRng,
         ) -> Result<(Self, Effects<Event>), Error> {
-            let mut diagnostics_console =
-                DiagnosticsPort::new(WithDir::new(cfg.base_dir.clone(), cfg.diagnostics_port));
+            let node_info = diagnostics_port::NodeInfo::new(&cfg.diagnostics_port, &chainspec);
+            let mut diagnostics_console = DiagnosticsPort::new(
+                WithDir::new(cfg.base_dir.clone(), cfg.diagnostics_port),
+                node_info,
+            );
             <DiagnosticsPort as InitializedComponent<Event>>::start_initialization(
                 &mut diagnostics_console,
             );
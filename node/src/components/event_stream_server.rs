@@ -25,7 +25,7 @@ mod sse_server;
 #[cfg(test)]
 mod tests;
 
-use std::{fmt::Debug, net::SocketAddr, path::PathBuf};
+use std::{collections::HashMap, fmt::Debug, net::SocketAddr, path::PathBuf};
 
 use datasize::DataSize;
 use tokio::sync::{
@@ -35,22 +35,24 @@ use tokio::sync::{
 use tracing::{error, info, warn};
 use warp::Filter;
 
-use casper_types::ProtocolVersion;
+use casper_types::{ProtocolVersion, Timestamp};
 
 use super::Component;
 use crate::{
     components::{ComponentState, InitializedComponent, PortBoundComponent},
-    effect::{EffectBuilder, Effects},
+    effect::{EffectBuilder, EffectExt, Effects},
     reactor::main_reactor::MainEvent,
-    types::JsonBlock,
+    types::{DeployHash, JsonBlock},
     utils::{self, ListeningError},
     NodeRng,
 };
 pub use config::Config;
 pub(crate) use event::Event;
 use event_indexer::{EventIndex, EventIndexer};
-use sse_server::ChannelsAndFilter;
-pub(crate) use sse_server::SseData;
+use sse_server::{ChannelsAndFilter, DeployMilestone};
+pub(crate) use sse_server::{NodeMilestone, SseData};
+#[cfg(test)]
+pub(crate) use sse_server::{SSE_API_MAIN_PATH, SSE_API_ROOT_PATH};
 
 const COMPONENT_NAME: &str = "event_stream_server";
 
@@ -87,6 +89,8 @@ pub(crate) struct EventStreamServer {
     storage_path: PathBuf,
     api_version: ProtocolVersion,
     sse_server: Option<InnerServer>,
+    /// The deploys whose milestones are published, along with the time they stop being watched.
+    watched_deploys: HashMap<DeployHash, Timestamp>,
 }
 
 impl EventStreamServer {
@@ -97,6 +101,7 @@ impl EventStreamServer {
             storage_path,
             api_version,
             sse_server: None,
+            watched_deploys: HashMap::new(),
         }
     }
 
@@ -209,6 +214,14 @@ impl EventStreamServer {
         Ok(())
     }
 
+    /// Returns the address the server is listening on, or `None` if it is not running.
+    #[cfg(test)]
+    pub(crate) fn listening_address(&self) -> Option<SocketAddr> {
+        self.sse_server
+            .as_ref()
+            .map(|server| server.listening_address)
+    }
+
     /// Broadcasts the SSE data to all clients connected to the event stream.
     fn broadcast(&mut self, sse_data: SseData) -> Effects<Event> {
         if let Some(server) = self.sse_server.as_mut() {
@@ -217,6 +230,35 @@ impl EventStreamServer {
         }
         Effects::new()
     }
+
+    /// Starts watching the given deploy, returning the ID of the `Accepted` milestone event, or
+    /// `None` if the server is disabled.
+    fn watch_deploy(&mut self, deploy_hash: DeployHash, expires: Timestamp) -> Option<EventIndex> {
+        let watch_id = self.sse_server.as_ref()?.event_indexer.current_index();
+        let now = Timestamp::now();
+        self.watched_deploys.retain(|_, expires| *expires > now);
+        self.watched_deploys.insert(deploy_hash, expires);
+        let _ = self.broadcast_deploy_milestone(deploy_hash, DeployMilestone::Accepted);
+        Some(watch_id)
+    }
+
+    /// Broadcasts the milestone if the deploy is being watched.
+    fn broadcast_deploy_milestone(
+        &mut self,
+        deploy_hash: DeployHash,
+        milestone: DeployMilestone,
+    ) -> Effects<Event> {
+        if !self.watched_deploys.contains_key(&deploy_hash) {
+            return Effects::new();
+        }
+        if let DeployMilestone::Executed { .. } = milestone {
+            self.watched_deploys.remove(&deploy_hash);
+        }
+        self.broadcast(SseData::DeployMilestone {
+            deploy_hash,
+            milestone,
+        })
+    }
 }
 
 impl Drop for EventStreamServer {
@@ -267,7 +309,11 @@ where
                 | Event::DeploysExpired(_)
                 | Event::Fault { .. }
                 | Event::FinalitySignature(_)
-                | Event::Step { .. } => {
+                | Event::Step { .. }
+                | Event::StepOutcome(_)
+                | Event::NodeMilestone(_)
+                | Event::BlockProposed { .. }
+                | Event::BlockFinalized { .. } => {
                     warn!(
                         ?event,
                         name = <Self as Component<MainEvent>>::name(self),
@@ -275,6 +321,7 @@ where
                     );
                     Effects::new()
                 }
+                Event::WatchDeploy(request) => request.responder.respond(None).ignore(),
             },
             ComponentState::Initialized => match event {
                 Event::Initialize => {
@@ -295,18 +342,33 @@ where
                     deploy_header,
                     block_hash,
                     execution_result,
-                } => self.broadcast(SseData::DeployProcessed {
-                    deploy_hash: Box::new(deploy_hash),
-                    account: Box::new(deploy_header.account().clone()),
-                    timestamp: deploy_header.timestamp(),
-                    ttl: deploy_header.ttl(),
-                    dependencies: deploy_header.dependencies().clone(),
-                    block_hash: Box::new(block_hash),
-                    execution_result,
-                }),
-                Event::DeploysExpired(deploy_hashes) => deploy_hashes
+                } => {
+                    let mut effects = self.broadcast_deploy_milestone(
+                        deploy_hash,
+                        DeployMilestone::Executed {
+                            block_hash,
+                            execution_result: execution_result.clone(),
+                        },
+                    );
+                    effects.extend(self.broadcast(SseData::DeployProcessed {
+                        deploy_hash: Box::new(deploy_hash),
+                        account: Box::new(deploy_header.account().clone()),
+                        timestamp: deploy_header.timestamp(),
+                        ttl: deploy_header.ttl(),
+                        dependencies: deploy_header.dependencies().clone(),
+                        block_hash: Box::new(block_hash),
+                        execution_result,
+                    }));
+                    effects
+                }
+                Event::DeploysExpired(expired) => expired
                     .into_iter()
-                    .flat_map(|deploy_hash| self.broadcast(SseData::DeployExpired { deploy_hash }))
+                    .flat_map(|(deploy_hash, reason)| {
+                        self.broadcast(SseData::DeployExpired {
+                            deploy_hash,
+                            reason,
+                        })
+                    })
                     .collect(),
                 Event::Fault {
                     era_id,
@@ -325,6 +387,41 @@ where
                     era_id,
                     execution_effect,
                 }),
+                Event::StepOutcome(step_outcome) => {
+                    self.broadcast(SseData::StepOutcome(step_outcome))
+                }
+                Event::NodeMilestone(milestone) => {
+                    self.broadcast(SseData::NodeMilestone(milestone))
+                }
+                Event::WatchDeploy(request) => {
+                    let watch_id = self.watch_deploy(request.deploy_hash, request.expires);
+                    request.responder.respond(watch_id).ignore()
+                }
+                Event::BlockProposed {
+                    timestamp,
+                    deploy_hashes,
+                } => deploy_hashes
+                    .into_iter()
+                    .flat_map(|deploy_hash| {
+                        self.broadcast_deploy_milestone(
+                            deploy_hash,
+                            DeployMilestone::Proposed { timestamp },
+                        )
+                    })
+                    .collect(),
+                Event::BlockFinalized {
+                    era_id,
+                    height,
+                    deploy_hashes,
+                } => deploy_hashes
+                    .into_iter()
+                    .flat_map(|deploy_hash| {
+                        self.broadcast_deploy_milestone(
+                            deploy_hash,
+                            DeployMilestone::Finalized { era_id, height },
+                        )
+                    })
+                    .collect(),
             },
         }
     }
@@ -6,7 +6,14 @@ use std::{
 use casper_types::{EraId, ExecutionEffect, ExecutionResult, PublicKey, Timestamp};
 use itertools::Itertools;
 
-use crate::types::{Block, BlockHash, Deploy, DeployHash, DeployHeader, FinalitySignature};
+use super::NodeMilestone;
+use crate::{
+    components::contract_runtime::StepOutcome,
+    effect::requests::WatchDeployRequest,
+    types::{
+        Block, BlockHash, Deploy, DeployExpiryReason, DeployHash, DeployHeader, FinalitySignature,
+    },
+};
 
 #[derive(Debug)]
 pub enum Event {
@@ -19,7 +26,7 @@ pub enum Event {
         block_hash: BlockHash,
         execution_result: Box<ExecutionResult>,
     },
-    DeploysExpired(Vec<DeployHash>),
+    DeploysExpired(Vec<(DeployHash, DeployExpiryReason)>),
     Fault {
         era_id: EraId,
         public_key: Box<PublicKey>,
@@ -30,6 +37,18 @@ pub enum Event {
         era_id: EraId,
         execution_effect: ExecutionEffect,
     },
+    StepOutcome(Box<StepOutcome>),
+    NodeMilestone(NodeMilestone),
+    WatchDeploy(WatchDeployRequest),
+    BlockProposed {
+        timestamp: Timestamp,
+        deploy_hashes: Vec<DeployHash>,
+    },
+    BlockFinalized {
+        era_id: EraId,
+        height: u64,
+        deploy_hashes: Vec<DeployHash>,
+    },
 }
 
 impl Display for Event {
@@ -40,11 +59,14 @@ impl Display for Event {
             Event::DeployAccepted(deploy_hash) => {
                 write!(formatter, "deploy accepted {}", deploy_hash)
             }
-            Event::DeploysExpired(deploy_hashes) => {
+            Event::DeploysExpired(expired) => {
                 write!(
                     formatter,
                     "deploys expired: {}",
-                    deploy_hashes.iter().join(", ")
+                    expired
+                        .iter()
+                        .map(|(deploy_hash, _reason)| deploy_hash)
+                        .join(", ")
                 )
             }
             Event::DeployProcessed { deploy_hash, .. } => {
@@ -61,6 +83,19 @@ impl Display for Event {
             ),
             Event::FinalitySignature(fs) => write!(formatter, "finality signature {}", fs),
             Event::Step { era_id, .. } => write!(formatter, "step committed for {}", era_id),
+            Event::StepOutcome(step_outcome) => {
+                write!(formatter, "step outcome for {}", step_outcome.era_id)
+            }
+            Event::NodeMilestone(milestone) => {
+                write!(formatter, "node milestone {:?}", milestone)
+            }
+            Event::WatchDeploy(request) => write!(formatter, "{}", request),
+            Event::BlockProposed { timestamp, .. } => {
+                write!(formatter, "block proposed at {}", timestamp)
+            }
+            Event::BlockFinalized { height, .. } => {
+                write!(formatter, "block finalized at height {}", height)
+            }
         }
     }
 }
@@ -55,7 +55,7 @@ impl EventIndexer {
         index
     }
 
-    #[cfg(test)]
+    /// Returns the index which will be assigned to the next event.
     pub(super) fn current_index(&self) -> EventIndex {
         self.index
     }
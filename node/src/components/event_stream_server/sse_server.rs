@@ -1,5 +1,7 @@
 //! Types and functions used by the http server to manage the event-stream.
 
+#[cfg(test)]
+use std::collections::BTreeMap;
 use std::{
     collections::{HashMap, HashSet},
     net::SocketAddr,
@@ -33,14 +35,20 @@ use warp::{
 };
 
 #[cfg(test)]
-use casper_types::testing::TestRng;
+use casper_types::{system::auction::SeigniorageAllocation, testing::TestRng, U512};
 use casper_types::{
     EraId, ExecutionEffect, ExecutionResult, ProtocolVersion, PublicKey, TimeDiff, Timestamp,
 };
 
-use crate::types::{BlockHash, Deploy, DeployHash, FinalitySignature, JsonBlock};
+use crate::{
+    components::{contract_runtime::StepOutcome, upgrade_watcher::NextUpgrade},
+    types::{BlockHash, Deploy, DeployExpiryReason, DeployHash, FinalitySignature, JsonBlock},
+};
 #[cfg(test)]
-use crate::{testing, types::Block};
+use crate::{
+    testing,
+    types::{ActivationPoint, Block},
+};
 
 /// The URL root path.
 pub const SSE_API_ROOT_PATH: &str = "events";
@@ -55,12 +63,15 @@ pub const SSE_API_SIGNATURES_PATH: &str = "sigs";
 pub const QUERY_FIELD: &str = "start_from";
 
 /// The filter associated with `/events/main` path.
-const MAIN_FILTER: [EventFilter; 5] = [
+const MAIN_FILTER: [EventFilter; 8] = [
     EventFilter::BlockAdded,
     EventFilter::DeployProcessed,
     EventFilter::DeployExpired,
     EventFilter::Fault,
     EventFilter::Step,
+    EventFilter::StepOutcome,
+    EventFilter::NodeMilestone,
+    EventFilter::DeployMilestone,
 ];
 /// The filter associated with `/events/deploys` path.
 const DEPLOYS_FILTER: [EventFilter; 1] = [EventFilter::DeployAccepted];
@@ -98,8 +109,12 @@ pub enum SseData {
         #[data_size(skip)]
         execution_result: Box<ExecutionResult>,
     },
-    /// The given deploy has expired.
-    DeployExpired { deploy_hash: DeployHash },
+    /// The given deploy has expired without being executed.
+    DeployExpired {
+        deploy_hash: DeployHash,
+        #[serde(default)]
+        reason: DeployExpiryReason,
+    },
     /// Generic representation of validator's fault in an era.
     Fault {
         era_id: EraId,
@@ -114,10 +129,67 @@ pub enum SseData {
         #[data_size(skip)]
         execution_effect: ExecutionEffect,
     },
+    /// The outcome of the step run at the end of an era.
+    #[data_size(skip)]
+    StepOutcome(Box<StepOutcome>),
+    /// The node has reached a milestone in its lifecycle.
+    NodeMilestone(NodeMilestone),
+    /// A deploy watched via the `account_put_deploy_and_watch` RPC has reached a milestone.
+    DeployMilestone {
+        deploy_hash: DeployHash,
+        milestone: DeployMilestone,
+    },
     /// The node is about to shut down.
     Shutdown,
 }
 
+/// A milestone in the lifecycle of the node, such as syncing or joining the validator set.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize, JsonSchema)]
+pub enum NodeMilestone {
+    /// The node has started syncing to the tip of the chain.
+    SyncStarted,
+    /// The node has leaped to the given block while syncing.
+    SyncLeaped {
+        block_hash: BlockHash,
+        block_height: u64,
+    },
+    /// The node has caught up with the tip of the chain.
+    CaughtUp,
+    /// The given era has started.
+    EraTransition { era_id: EraId },
+    /// An upgrade has been staged and will activate at the given activation point.
+    UpgradeStaged(NextUpgrade),
+    /// The node has committed the upgrade to the given protocol version.
+    UpgradeActivated {
+        #[data_size(skip)]
+        protocol_version: ProtocolVersion,
+    },
+    /// The node is in the validator set and has started participating in consensus in the given
+    /// era.
+    StartedValidating { era_id: EraId },
+    /// The node has stopped participating in consensus, either because it is no longer in the
+    /// validator set or because it fell behind and needs to catch up.
+    StoppedValidating,
+}
+
+/// A milestone reached by a deploy watched via the `account_put_deploy_and_watch` RPC.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize, Debug, DataSize, JsonSchema)]
+pub enum DeployMilestone {
+    /// The deploy has been accepted by this node.
+    Accepted,
+    /// The deploy has been included in a block proposed by this node.
+    Proposed { timestamp: Timestamp },
+    /// The deploy has been included in a block finalized by consensus.  Only published if this
+    /// node is a validator.
+    Finalized { era_id: EraId, height: u64 },
+    /// The deploy has been executed in the given block, which has been stored as complete.
+    Executed {
+        block_hash: BlockHash,
+        #[data_size(skip)]
+        execution_result: Box<ExecutionResult>,
+    },
+}
+
 impl SseData {
     pub(super) fn should_include(&self, filter: &[EventFilter]) -> bool {
         match self {
@@ -129,6 +201,9 @@ impl SseData {
             SseData::Fault { .. } => filter.contains(&EventFilter::Fault),
             SseData::FinalitySignature(_) => filter.contains(&EventFilter::FinalitySignature),
             SseData::Step { .. } => filter.contains(&EventFilter::Step),
+            SseData::StepOutcome(_) => filter.contains(&EventFilter::StepOutcome),
+            SseData::NodeMilestone(_) => filter.contains(&EventFilter::NodeMilestone),
+            SseData::DeployMilestone { .. } => filter.contains(&EventFilter::DeployMilestone),
         }
     }
 }
@@ -180,8 +255,14 @@ impl SseData {
     /// Returns a random `SseData::DeployExpired`
     pub(super) fn random_deploy_expired(rng: &mut TestRng) -> Self {
         let deploy = testing::create_expired_deploy(Timestamp::now(), rng);
+        let reason = if rng.gen() {
+            DeployExpiryReason::Expired
+        } else {
+            DeployExpiryReason::InvalidAtProposal
+        };
         SseData::DeployExpired {
             deploy_hash: *deploy.hash(),
+            reason,
         }
     }
 
@@ -208,12 +289,96 @@ impl SseData {
             ExecutionResult::Success { effect, .. } | ExecutionResult::Failure { effect, .. } => {
                 effect
             }
+            ExecutionResult::V2(execution_result) => execution_result.effect,
         };
         SseData::Step {
             era_id: EraId::new(rng.gen()),
             execution_effect,
         }
     }
+
+    /// Returns a random `SseData::StepOutcome`.
+    pub(super) fn random_step_outcome(rng: &mut TestRng) -> Self {
+        let evicted_validators = (0..rng.gen_range(0..3))
+            .map(|_| PublicKey::random(rng))
+            .collect();
+        let validators: Vec<_> = (0..rng.gen_range(1..4))
+            .map(|_| PublicKey::random(rng))
+            .collect();
+        let seigniorage_allocations = validators
+            .iter()
+            .map(|validator| {
+                SeigniorageAllocation::validator(validator.clone(), U512::from(rng.gen::<u64>()))
+            })
+            .collect();
+        let era_id = EraId::new(rng.gen_range(0..1_000));
+        let next_era_validators = validators
+            .into_iter()
+            .map(|validator| (validator, U512::from(rng.gen::<u64>())))
+            .collect();
+        let mut upcoming_era_validators = BTreeMap::new();
+        upcoming_era_validators.insert(era_id.successor(), next_era_validators);
+        SseData::StepOutcome(Box::new(StepOutcome {
+            era_id,
+            slashed_validators: vec![],
+            evicted_validators,
+            seigniorage_allocations,
+            upcoming_era_validators,
+        }))
+    }
+
+    /// Returns a random `SseData::NodeMilestone`.
+    pub(super) fn random_node_milestone(rng: &mut TestRng) -> Self {
+        let milestone = match rng.gen_range(0..8) {
+            0 => NodeMilestone::SyncStarted,
+            1 => NodeMilestone::SyncLeaped {
+                block_hash: BlockHash::random(rng),
+                block_height: rng.gen(),
+            },
+            2 => NodeMilestone::CaughtUp,
+            3 => NodeMilestone::EraTransition {
+                era_id: EraId::new(rng.gen()),
+            },
+            4 => NodeMilestone::UpgradeStaged(NextUpgrade::new(
+                ActivationPoint::EraId(EraId::new(rng.gen())),
+                ProtocolVersion::from_parts(rng.gen_range(0..10), rng.gen::<u8>() as u32, 0),
+            )),
+            5 => NodeMilestone::UpgradeActivated {
+                protocol_version: ProtocolVersion::from_parts(
+                    rng.gen_range(0..10),
+                    rng.gen::<u8>() as u32,
+                    0,
+                ),
+            },
+            6 => NodeMilestone::StartedValidating {
+                era_id: EraId::new(rng.gen()),
+            },
+            _ => NodeMilestone::StoppedValidating,
+        };
+        SseData::NodeMilestone(milestone)
+    }
+
+    /// Returns a random `SseData::DeployMilestone`.
+    pub(super) fn random_deploy_milestone(rng: &mut TestRng) -> Self {
+        let milestone = match rng.gen_range(0..4) {
+            0 => DeployMilestone::Accepted,
+            1 => DeployMilestone::Proposed {
+                timestamp: Timestamp::random(rng),
+            },
+            2 => DeployMilestone::Finalized {
+                era_id: EraId::new(rng.gen()),
+                height: rng.gen(),
+            },
+            _ => DeployMilestone::Executed {
+                block_hash: BlockHash::random(rng),
+                execution_result: Box::new(rng.gen()),
+            },
+        };
+        SseData::DeployMilestone {
+            deploy_hash: DeployHash::random(rng),
+            milestone,
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -273,6 +438,9 @@ pub(super) enum EventFilter {
     Fault,
     FinalitySignature,
     Step,
+    StepOutcome,
+    NodeMilestone,
+    DeployMilestone,
 }
 
 /// Filters the `event`, mapping it to a warp event, or `None` if it should be filtered out.
@@ -314,7 +482,10 @@ async fn filter_map_server_sent_event(
         | &SseData::DeployExpired { .. }
         | &SseData::Fault { .. }
         | &SseData::Step { .. }
+        | &SseData::StepOutcome(_)
         | &SseData::FinalitySignature(_)
+        | &SseData::NodeMilestone(_)
+        | &SseData::DeployMilestone { .. }
         | &SseData::Shutdown => Some(Ok(WarpServerSentEvent::default()
             .json_data(&event.data)
             .unwrap_or_else(|error| {
@@ -634,6 +805,18 @@ mod tests {
             id: Some(rng.gen()),
             data: SseData::random_step(&mut rng),
         };
+        let step_outcome = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_step_outcome(&mut rng),
+        };
+        let node_milestone = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_node_milestone(&mut rng),
+        };
+        let deploy_milestone = ServerSentEvent {
+            id: Some(rng.gen()),
+            data: SseData::random_deploy_milestone(&mut rng),
+        };
         let shutdown = ServerSentEvent {
             id: Some(rng.gen()),
             data: SseData::Shutdown,
@@ -646,6 +829,9 @@ mod tests {
         should_not_filter_out(&deploy_expired, &MAIN_FILTER[..]).await;
         should_not_filter_out(&fault, &MAIN_FILTER[..]).await;
         should_not_filter_out(&step, &MAIN_FILTER[..]).await;
+        should_not_filter_out(&step_outcome, &MAIN_FILTER[..]).await;
+        should_not_filter_out(&node_milestone, &MAIN_FILTER[..]).await;
+        should_not_filter_out(&deploy_milestone, &MAIN_FILTER[..]).await;
         should_not_filter_out(&shutdown, &MAIN_FILTER).await;
 
         should_filter_out(&deploy_accepted, &MAIN_FILTER[..]).await;
@@ -663,6 +849,9 @@ mod tests {
         should_filter_out(&fault, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&finality_signature, &DEPLOYS_FILTER[..]).await;
         should_filter_out(&step, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&step_outcome, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&node_milestone, &DEPLOYS_FILTER[..]).await;
+        should_filter_out(&deploy_milestone, &DEPLOYS_FILTER[..]).await;
 
         // `EventFilter::Signatures` should filter out everything except `ApiVersion`s and
         // `FinalitySignature`s.
@@ -676,6 +865,9 @@ mod tests {
         should_filter_out(&deploy_expired, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&fault, &SIGNATURES_FILTER[..]).await;
         should_filter_out(&step, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&step_outcome, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&node_milestone, &SIGNATURES_FILTER[..]).await;
+        should_filter_out(&deploy_milestone, &SIGNATURES_FILTER[..]).await;
     }
 
     /// This test checks that events with incorrect IDs (i.e. no types have an ID except for
@@ -720,6 +912,18 @@ mod tests {
             id: None,
             data: SseData::random_step(&mut rng),
         };
+        let malformed_step_outcome = ServerSentEvent {
+            id: None,
+            data: SseData::random_step_outcome(&mut rng),
+        };
+        let malformed_node_milestone = ServerSentEvent {
+            id: None,
+            data: SseData::random_node_milestone(&mut rng),
+        };
+        let malformed_deploy_milestone = ServerSentEvent {
+            id: None,
+            data: SseData::random_deploy_milestone(&mut rng),
+        };
         let malformed_shutdown = ServerSentEvent {
             id: None,
             data: SseData::Shutdown,
@@ -738,6 +942,9 @@ mod tests {
             should_filter_out(&malformed_fault, filter).await;
             should_filter_out(&malformed_finality_signature, filter).await;
             should_filter_out(&malformed_step, filter).await;
+            should_filter_out(&malformed_step_outcome, filter).await;
+            should_filter_out(&malformed_node_milestone, filter).await;
+            should_filter_out(&malformed_deploy_milestone, filter).await;
             should_filter_out(&malformed_shutdown, filter).await;
         }
     }
@@ -1,3 +1,4 @@
+mod batches;
 mod config;
 #[cfg(test)]
 mod error;
@@ -18,8 +19,11 @@ use std::{
 
 use datasize::DataSize;
 use prometheus::Registry;
+use tokio::sync::watch;
 use tracing::{debug, error, trace, warn};
 
+use casper_types::Timestamp;
+
 use crate::{
     components::Component,
     effect::{
@@ -28,16 +32,21 @@ use crate::{
         requests::{BeginGossipRequest, NetworkRequest, StorageRequest},
         EffectBuilder, EffectExt, Effects, GossipTarget,
     },
-    types::NodeId,
+    types::{
+        chainspec::{NetworkFeature, NetworkFeatures},
+        NodeId,
+    },
     utils::Source,
     NodeRng,
 };
+use batches::{into_batches, serialized_size, GossipGroup, PendingBatches};
 pub(crate) use config::Config;
 pub(crate) use event::Event;
 pub(crate) use gossip_item::{GossipItem, LargeGossipItem, SmallGossipItem};
 use gossip_table::{GossipAction, GossipTable};
 use item_provider::ItemProvider;
 pub(crate) use message::Message;
+use message::MAX_GOSSIP_BATCH_LEN;
 use metrics::Metrics;
 
 /// The component which gossips to peers and handles incoming gossip messages from peers.
@@ -50,6 +59,12 @@ where
     gossip_timeout: Duration,
     get_from_peer_timeout: Duration,
     validate_and_store_timeout: Duration,
+    batch_interval: Duration,
+    max_batch_size: usize,
+    /// Gossip announcements, responses and items waiting to be sent as part of a batch.
+    pending_batches: PendingBatches<T>,
+    /// The network features in use, deciding whether gossip messages may be batched.
+    network_features: watch::Receiver<NetworkFeatures>,
     name: &'static str,
     metrics: Metrics,
 }
@@ -62,6 +77,7 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
     pub(crate) fn new(
         name: &'static str,
         config: Config,
+        network_features: watch::Receiver<NetworkFeatures>,
         registry: &Registry,
     ) -> Result<Self, prometheus::Error> {
         Ok(Gossiper {
@@ -69,6 +85,10 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
             gossip_timeout: config.gossip_request_timeout().into(),
             get_from_peer_timeout: config.get_remainder_timeout().into(),
             validate_and_store_timeout: config.validate_and_store_timeout().into(),
+            batch_interval: config.batch_interval().into(),
+            max_batch_size: config.max_batch_size() as usize,
+            pending_batches: PendingBatches::default(),
+            network_features,
             name,
             metrics: Metrics::new(name, registry)?,
         })
@@ -97,7 +117,7 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         {
             GossipAction::ShouldGossip(should_gossip) => {
                 self.metrics.items_received.inc();
-                Self::gossip(
+                self.gossip(
                     effect_builder,
                     item_id,
                     should_gossip.target,
@@ -116,8 +136,29 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         }
     }
 
-    /// Gossips the given item ID to `count` random peers excluding the indicated ones.
+    /// Gossips the given item ID to `count` random peers excluding the indicated ones, or queues
+    /// it to be gossiped as part of a batch if batching is enabled and in use on the network.
     fn gossip<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_id: T::Id,
+        gossip_target: GossipTarget,
+        count: usize,
+        exclude_peers: HashSet<NodeId>,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>> + Send,
+    {
+        if !self.is_batching() {
+            return Self::gossip_now(effect_builder, item_id, gossip_target, count, exclude_peers);
+        }
+        self.queue_for_batch(effect_builder, |pending| {
+            pending.push_gossip(item_id, gossip_target, count, exclude_peers)
+        })
+    }
+
+    /// Gossips the given item ID to `count` random peers excluding the indicated ones right away.
+    fn gossip_now<REv>(
         effect_builder: EffectBuilder<REv>,
         item_id: T::Id,
         gossip_target: GossipTarget,
@@ -198,7 +239,7 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         REv: From<NetworkRequest<Message<T>>> + From<GossiperAnnouncement<T>> + Send,
     {
         match self.table.check_timeout(&item_id, peer) {
-            GossipAction::ShouldGossip(should_gossip) => Self::gossip(
+            GossipAction::ShouldGossip(should_gossip) => self.gossip(
                 effect_builder,
                 item_id,
                 should_gossip.target,
@@ -231,7 +272,7 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         REv: From<NetworkRequest<Message<T>>> + From<GossiperAnnouncement<T>> + Send,
     {
         match self.table.remove_holder_if_unresponsive(&item_id, peer) {
-            GossipAction::ShouldGossip(should_gossip) => Self::gossip(
+            GossipAction::ShouldGossip(should_gossip) => self.gossip(
                 effect_builder,
                 item_id,
                 should_gossip.target,
@@ -280,7 +321,7 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
                 debug!(item=%item_id, %sender, %should_gossip, "received gossip request");
                 self.metrics.items_received.inc();
                 // Gossip the item ID.
-                let mut effects = Self::gossip(
+                let mut effects = self.gossip(
                     effect_builder,
                     item_id.clone(),
                     should_gossip.target,
@@ -293,17 +334,18 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
                     debug!(item=%item_id, "announcing new complete gossip item received");
                     effects.extend(
                         effect_builder
-                            .announce_complete_item_received_via_gossip(item_id.clone())
+                            .announce_complete_item_received_via_gossip(item_id.clone(), sender)
                             .ignore(),
                     );
                 }
 
                 // Send a response to the sender indicating whether we already hold the item.
-                let reply = Message::GossipResponse {
-                    item_id: item_id.clone(),
-                    is_already_held: should_gossip.is_already_held,
-                };
-                effects.extend(effect_builder.send_message(sender, reply).ignore());
+                effects.extend(self.send_gossip_response(
+                    effect_builder,
+                    sender,
+                    item_id.clone(),
+                    should_gossip.is_already_held,
+                ));
                 effects
             }
             GossipAction::GetRemainder { .. } => {
@@ -311,11 +353,8 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
                 self.metrics.items_received.inc();
                 // Send a response to the sender indicating we want the full item from them, and set
                 // a timeout for this response.
-                let reply = Message::GossipResponse {
-                    item_id: item_id.clone(),
-                    is_already_held: false,
-                };
-                let mut effects = effect_builder.send_message(sender, reply).ignore();
+                let mut effects =
+                    self.send_gossip_response(effect_builder, sender, item_id.clone(), false);
                 let item_id_clone = item_id.clone();
                 effects.extend(
                     effect_builder
@@ -332,11 +371,8 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
             | GossipAction::AnnounceFinished => {
                 trace!(item=%item_id, %sender, %action, "received gossip request");
                 // Send a response to the sender indicating we already hold the item.
-                let reply = Message::GossipResponse {
-                    item_id: item_id.clone(),
-                    is_already_held: true,
-                };
-                let mut effects = effect_builder.send_message(sender, reply).ignore();
+                let mut effects =
+                    self.send_gossip_response(effect_builder, sender, item_id.clone(), true);
 
                 if action == GossipAction::AnnounceFinished {
                     effects.extend(
@@ -359,6 +395,184 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         effects
     }
 
+    /// Returns `true` if outgoing gossip messages should be batched, i.e. if batching is enabled
+    /// and in use on the network.
+    fn is_batching(&self) -> bool {
+        !self.batch_interval.is_zero()
+            && self
+                .network_features
+                .borrow()
+                .contains(NetworkFeature::GossipBatching)
+    }
+
+    /// Queues an outgoing message to be sent as part of a batch, scheduling a flush of the pending
+    /// batches if none is scheduled yet.
+    fn queue_for_batch<REv, F>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        push: F,
+    ) -> Effects<Event<T>>
+    where
+        REv: Send,
+        F: FnOnce(&mut PendingBatches<T>),
+    {
+        // Only the first queued message needs to schedule a flush; later ones join the batches.
+        let should_schedule_flush = self.pending_batches.is_empty();
+        push(&mut self.pending_batches);
+        if should_schedule_flush {
+            effect_builder
+                .set_timeout(self.batch_interval)
+                .event(|_| Event::FlushBatches)
+        } else {
+            Effects::new()
+        }
+    }
+
+    /// Sends a gossip response to the given peer, or queues it to be sent as part of a batch if
+    /// batching is enabled and in use on the network.
+    fn send_gossip_response<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        peer: NodeId,
+        item_id: T::Id,
+        is_already_held: bool,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>> + Send,
+    {
+        if !self.is_batching() {
+            let reply = Message::GossipResponse {
+                item_id,
+                is_already_held,
+            };
+            return effect_builder.send_message(peer, reply).ignore();
+        }
+        self.queue_for_batch(effect_builder, |pending| {
+            pending.push_response(peer, item_id, is_already_held)
+        })
+    }
+
+    /// Sends all queued gossip announcements, responses and items, combining the ones sharing a
+    /// destination into as few messages as the size budget allows.
+    fn flush_batches<REv>(&mut self, effect_builder: EffectBuilder<REv>) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>> + Send,
+    {
+        let pending = self.pending_batches.take();
+        let mut effects = Effects::new();
+
+        for (group, item_ids) in pending.gossip {
+            let GossipGroup {
+                gossip_target,
+                count,
+                exclude_peers,
+            } = group;
+            for mut item_ids in into_batches(item_ids, self.max_batch_size, serialized_size) {
+                if item_ids.len() == 1 {
+                    effects.extend(Self::gossip_now(
+                        effect_builder,
+                        item_ids.remove(0),
+                        gossip_target,
+                        count,
+                        exclude_peers.clone(),
+                    ));
+                    continue;
+                }
+                let message = Message::GossipBatch(item_ids.clone());
+                effects.extend(
+                    effect_builder
+                        .gossip_message(message, gossip_target, count, exclude_peers.clone())
+                        .event(move |peers| Event::GossipedBatchTo {
+                            item_ids,
+                            requested_count: count,
+                            peers,
+                        }),
+                );
+            }
+        }
+
+        for (peer, responses) in pending.responses {
+            let batches = into_batches(responses, self.max_batch_size, |(item_id, _)| {
+                serialized_size(item_id)
+            });
+            for mut batch in batches {
+                let message = if batch.len() == 1 {
+                    let (item_id, is_already_held) = batch.remove(0);
+                    Message::GossipResponse {
+                        item_id,
+                        is_already_held,
+                    }
+                } else {
+                    Message::GossipResponseBatch(batch)
+                };
+                effects.extend(effect_builder.send_message(peer, message).ignore());
+            }
+        }
+
+        for (peer, items) in pending.items {
+            for mut batch in into_batches(items, self.max_batch_size, serialized_size) {
+                let message = if batch.len() == 1 {
+                    Message::Item(Box::new(batch.remove(0)))
+                } else {
+                    Message::ItemBatch(batch)
+                };
+                effects.extend(effect_builder.send_message(peer, message).ignore());
+            }
+        }
+
+        effects
+    }
+
+    /// Handles the response from the network component detailing which peers it gossiped a batch
+    /// of item IDs to.
+    fn gossiped_batch_to<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_ids: Vec<T::Id>,
+        requested_count: usize,
+        peers: HashSet<NodeId>,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<GossiperAnnouncement<T>> + Send,
+    {
+        let mut effects = Effects::new();
+        for item_id in item_ids {
+            effects.extend(self.gossiped_to(
+                effect_builder,
+                item_id,
+                requested_count,
+                peers.clone(),
+            ));
+        }
+        effects
+    }
+
+    /// Handles an incoming batch of gossip responses from a peer on the network.
+    fn handle_gossip_response_batch<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        responses: Vec<(T::Id, bool)>,
+        sender: NodeId,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>>
+            + From<StorageRequest>
+            + From<GossiperAnnouncement<T>>
+            + Send,
+        Self: ItemProvider<T>,
+    {
+        let mut effects = Effects::new();
+        for (item_id, is_already_held) in responses {
+            effects.extend(self.handle_gossip_response(
+                effect_builder,
+                item_id,
+                is_already_held,
+                sender,
+            ));
+        }
+        effects
+    }
+
     /// Handles an incoming gossip response from a peer on the network.
     fn handle_gossip_response<REv>(
         &mut self,
@@ -405,7 +619,7 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
         };
 
         match action {
-            GossipAction::ShouldGossip(should_gossip) => effects.extend(Self::gossip(
+            GossipAction::ShouldGossip(should_gossip) => effects.extend(self.gossip(
                 effect_builder,
                 item_id,
                 should_gossip.target,
@@ -431,8 +645,9 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
     }
 
     /// Handles the `Some` case when attempting to get the item from storage in order to send it to
-    /// the requester.
+    /// the requester, either right away or as part of a batch.
     fn got_from_storage<REv>(
+        &mut self,
         effect_builder: EffectBuilder<REv>,
         item: Box<T>,
         requester: NodeId,
@@ -440,8 +655,13 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
     where
         REv: From<NetworkRequest<Message<T>>> + Send,
     {
-        let message = Message::Item(item);
-        effect_builder.send_message(requester, message).ignore()
+        if !self.is_batching() {
+            let message = Message::Item(item);
+            return effect_builder.send_message(requester, message).ignore();
+        }
+        self.queue_for_batch(effect_builder, |pending| {
+            pending.push_item(requester, *item)
+        })
     }
 
     /// Handles the `None` case when attempting to get the item from storage.
@@ -551,6 +771,28 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Gossiper<ID_IS_CO
     }
 }
 
+impl<T: LargeGossipItem + 'static> Gossiper<false, T> {
+    /// Handles an incoming gossip announcement of a large item by checking whether we already hold
+    /// the complete item.
+    fn check_is_stored<REv>(
+        effect_builder: EffectBuilder<REv>,
+        item_id: T::Id,
+        sender: NodeId,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<StorageRequest> + Send,
+        Self: ItemProvider<T>,
+    {
+        Self::is_stored(effect_builder, item_id.clone()).event(move |result| {
+            Event::IsStoredResult {
+                item_id,
+                sender,
+                result,
+            }
+        })
+    }
+}
+
 /// Impl for gossipers of large items, i.e. where `T::ID_IS_COMPLETE_ITEM` is false.
 impl<T, REv> Component<REv> for Gossiper<false, T>
 where
@@ -597,26 +839,44 @@ where
             Event::CheckGetFromPeerTimeout { item_id, peer } => {
                 self.check_get_from_peer_timeout(effect_builder, item_id, peer)
             }
+            Event::GossipedBatchTo {
+                item_ids,
+                requested_count,
+                peers,
+            } => self.gossiped_batch_to(effect_builder, item_ids, requested_count, peers),
             Event::Incoming(GossiperIncoming::<T> { sender, message }) => match *message {
-                Message::Gossip(item_id) => {
-                    Self::is_stored(effect_builder, item_id.clone()).event(move |result| {
-                        Event::IsStoredResult {
-                            item_id,
-                            sender,
-                            result,
-                        }
-                    })
+                Message::Gossip(item_id) => Self::check_is_stored(effect_builder, item_id, sender),
+                Message::GossipBatch(item_ids) => {
+                    let mut effects = Effects::new();
+                    for item_id in item_ids {
+                        effects.extend(Self::check_is_stored(effect_builder, item_id, sender));
+                    }
+                    effects
                 }
                 Message::GossipResponse {
                     item_id,
                     is_already_held,
                 } => self.handle_gossip_response(effect_builder, item_id, is_already_held, sender),
+                Message::GossipResponseBatch(responses) => {
+                    self.handle_gossip_response_batch(effect_builder, responses, sender)
+                }
                 Message::GetItem(item_id) => {
                     self.handle_get_item_request(effect_builder, item_id, sender)
                 }
                 Message::Item(item) => {
                     self.handle_item_received_from_peer(effect_builder, item, sender)
                 }
+                Message::ItemBatch(items) => {
+                    let mut effects = Effects::new();
+                    for item in items {
+                        effects.extend(self.handle_item_received_from_peer(
+                            effect_builder,
+                            Box::new(item),
+                            sender,
+                        ));
+                    }
+                    effects
+                }
             },
             Event::CheckItemReceivedTimeout { item_id } => {
                 self.check_item_received_timeout(effect_builder, item_id)
@@ -640,9 +900,10 @@ where
                 requester,
                 maybe_item,
             } => match maybe_item {
-                Some(item) => Self::got_from_storage(effect_builder, item, requester),
+                Some(item) => self.got_from_storage(effect_builder, item, requester),
                 None => self.failed_to_get_from_storage(effect_builder, item_id),
             },
+            Event::FlushBatches => self.flush_batches(effect_builder),
         };
         self.update_gossip_table_metrics();
         effects
@@ -653,6 +914,30 @@ where
     }
 }
 
+impl<T: SmallGossipItem + 'static> Gossiper<true, T> {
+    /// Handles an incoming gossip announcement of a small item, which is the complete item itself,
+    /// received at time `now`.
+    fn handle_small_item_gossip<REv>(
+        &mut self,
+        effect_builder: EffectBuilder<REv>,
+        item_id: T::Id,
+        sender: NodeId,
+        now: Timestamp,
+    ) -> Effects<Event<T>>
+    where
+        REv: From<NetworkRequest<Message<T>>> + From<GossiperAnnouncement<T>> + Send,
+    {
+        let item = <T as SmallGossipItem>::id_as_item(&item_id);
+        if !item.is_valid(now) {
+            debug!(%item_id, %sender, "received invalid small gossip item");
+            return Effects::new();
+        }
+        let target = item.gossip_target();
+        let action = self.table.new_complete_data(&item_id, Some(sender), target);
+        self.handle_gossip(effect_builder, item_id, sender, action)
+    }
+}
+
 /// Impl for gossipers of small items, i.e. where `T::ID_IS_COMPLETE_ITEM` is true.
 impl<T, REv> Component<REv> for Gossiper<true, T>
 where
@@ -700,16 +985,35 @@ where
                 error!(%item_id, %peer, "should not timeout getting small item from peer");
                 Effects::new()
             }
+            Event::GossipedBatchTo {
+                item_ids,
+                requested_count,
+                peers,
+            } => self.gossiped_batch_to(effect_builder, item_ids, requested_count, peers),
             Event::Incoming(GossiperIncoming::<T> { sender, message }) => match *message {
                 Message::Gossip(item_id) => {
-                    let target = <T as SmallGossipItem>::id_as_item(&item_id).gossip_target();
-                    let action = self.table.new_complete_data(&item_id, Some(sender), target);
-                    self.handle_gossip(effect_builder, item_id, sender, action)
+                    self.handle_small_item_gossip(effect_builder, item_id, sender, Timestamp::now())
+                }
+                Message::GossipBatch(item_ids) => {
+                    let now = Timestamp::now();
+                    let mut effects = Effects::new();
+                    for item_id in item_ids {
+                        effects.extend(self.handle_small_item_gossip(
+                            effect_builder,
+                            item_id,
+                            sender,
+                            now,
+                        ));
+                    }
+                    effects
                 }
                 Message::GossipResponse {
                     item_id,
                     is_already_held,
                 } => self.handle_gossip_response(effect_builder, item_id, is_already_held, sender),
+                Message::GossipResponseBatch(responses) => {
+                    self.handle_gossip_response_batch(effect_builder, responses, sender)
+                }
                 Message::GetItem(item_id) => {
                     debug!(%item_id, %sender, "unexpected get request for small item");
                     Effects::new()
@@ -719,6 +1023,14 @@ where
                     debug!(%item_id, %sender, "unexpected get response for small item");
                     Effects::new()
                 }
+                Message::ItemBatch(items) => {
+                    debug!(
+                        item_count = items.len(),
+                        %sender,
+                        "unexpected batched get response for small items"
+                    );
+                    Effects::new()
+                }
             },
             Event::CheckItemReceivedTimeout { item_id } => {
                 error!(%item_id, "should not timeout item-received for small item");
@@ -739,6 +1051,7 @@ where
                 );
                 Effects::new()
             }
+            Event::FlushBatches => self.flush_batches(effect_builder),
         };
         self.update_gossip_table_metrics();
         effects
@@ -762,6 +1075,10 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> Debug
                 "validate_and_store_timeout",
                 &self.validate_and_store_timeout,
             )
+            .field("batch_interval", &self.batch_interval)
+            .field("max_batch_size", &self.max_batch_size)
+            .field("pending_batches", &self.pending_batches)
+            .field("network_features", &*self.network_features.borrow())
             .finish()
     }
 }
@@ -780,6 +1097,10 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> DataSize
             gossip_timeout,
             get_from_peer_timeout,
             validate_and_store_timeout,
+            batch_interval,
+            max_batch_size,
+            pending_batches,
+            network_features: _,
             name,
             metrics: _,
         } = self;
@@ -788,6 +1109,9 @@ impl<const ID_IS_COMPLETE_ITEM: bool, T: GossipItem + 'static> DataSize
             + gossip_timeout.estimate_heap_size()
             + get_from_peer_timeout.estimate_heap_size()
             + validate_and_store_timeout.estimate_heap_size()
+            + batch_interval.estimate_heap_size()
+            + max_batch_size.estimate_heap_size()
+            + pending_batches.estimate_heap_size()
             + name.estimate_heap_size()
     }
 }
@@ -0,0 +1,155 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    mem,
+};
+
+use datasize::DataSize;
+use serde::Serialize;
+
+use super::{GossipItem, MAX_GOSSIP_BATCH_LEN};
+use crate::{effect::GossipTarget, types::NodeId};
+
+/// The parameters shared by all item IDs gossiped in the same batch.
+///
+/// Item IDs are only batched together if they are to be gossiped to the same number of peers of the
+/// same kind, excluding the same peers, so that each item is only withheld from the peers excluded
+/// for it.
+#[derive(Debug, PartialEq, Eq)]
+pub(super) struct GossipGroup {
+    pub(super) gossip_target: GossipTarget,
+    pub(super) count: usize,
+    pub(super) exclude_peers: HashSet<NodeId>,
+}
+
+/// Outgoing gossip messages held back so they can be sent as batched messages.
+#[derive(Debug)]
+pub(super) struct PendingBatches<T: GossipItem> {
+    /// Item IDs to gossip, grouped by gossip target, number of peers to gossip to and peers to
+    /// exclude, in the order in which the groups were first seen.
+    pub(super) gossip: Vec<(GossipGroup, Vec<T::Id>)>,
+    /// Gossip responses to send to each peer.
+    pub(super) responses: BTreeMap<NodeId, Vec<(T::Id, bool)>>,
+    /// Items to send to each peer.
+    pub(super) items: BTreeMap<NodeId, Vec<T>>,
+}
+
+impl<T: GossipItem> PendingBatches<T> {
+    pub(super) fn is_empty(&self) -> bool {
+        self.gossip.is_empty() && self.responses.is_empty() && self.items.is_empty()
+    }
+
+    pub(super) fn push_gossip(
+        &mut self,
+        item_id: T::Id,
+        gossip_target: GossipTarget,
+        count: usize,
+        exclude_peers: HashSet<NodeId>,
+    ) {
+        let key = GossipGroup {
+            gossip_target,
+            count,
+            exclude_peers,
+        };
+        match self.gossip.iter_mut().find(|(group, _)| *group == key) {
+            Some((_, item_ids)) => item_ids.push(item_id),
+            None => self.gossip.push((key, vec![item_id])),
+        }
+    }
+
+    pub(super) fn push_response(&mut self, peer: NodeId, item_id: T::Id, is_already_held: bool) {
+        self.responses
+            .entry(peer)
+            .or_default()
+            .push((item_id, is_already_held));
+    }
+
+    pub(super) fn push_item(&mut self, peer: NodeId, item: T) {
+        self.items.entry(peer).or_default().push(item);
+    }
+
+    /// Takes all pending messages, leaving `self` empty.
+    pub(super) fn take(&mut self) -> Self {
+        mem::take(self)
+    }
+}
+
+impl<T: GossipItem> Default for PendingBatches<T> {
+    fn default() -> Self {
+        PendingBatches {
+            gossip: vec![],
+            responses: BTreeMap::new(),
+            items: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: GossipItem> DataSize for PendingBatches<T> {
+    const IS_DYNAMIC: bool = true;
+
+    const STATIC_HEAP_SIZE: usize = 0;
+
+    fn estimate_heap_size(&self) -> usize {
+        let PendingBatches {
+            gossip,
+            responses,
+            items,
+        } = self;
+
+        gossip
+            .iter()
+            .map(|(group, item_ids)| {
+                group.exclude_peers.estimate_heap_size()
+                    + item_ids.capacity() * mem::size_of::<T::Id>()
+            })
+            .sum::<usize>()
+            + responses
+                .values()
+                .map(|responses| responses.capacity() * mem::size_of::<(T::Id, bool)>())
+                .sum::<usize>()
+            + items
+                .values()
+                .map(|items| items.capacity() * mem::size_of::<T>())
+                .sum::<usize>()
+    }
+}
+
+/// Splits the given entries into batches.
+///
+/// Each batch holds at most `MAX_GOSSIP_BATCH_LEN` entries, and the serialized size of its entries
+/// as given by `serialized_size_of` doesn't exceed `max_batch_size`, unless a single entry is
+/// larger than that on its own.
+pub(super) fn into_batches<E, F>(
+    entries: Vec<E>,
+    max_batch_size: usize,
+    serialized_size_of: F,
+) -> Vec<Vec<E>>
+where
+    F: Fn(&E) -> usize,
+{
+    let mut batches = vec![];
+    let mut current_batch = vec![];
+    let mut current_size = 0_usize;
+    for entry in entries {
+        let entry_size = serialized_size_of(&entry);
+        if !current_batch.is_empty()
+            && (current_batch.len() >= MAX_GOSSIP_BATCH_LEN
+                || current_size.saturating_add(entry_size) > max_batch_size)
+        {
+            batches.push(mem::take(&mut current_batch));
+            current_size = 0;
+        }
+        current_size = current_size.saturating_add(entry_size);
+        current_batch.push(entry);
+    }
+    if !current_batch.is_empty() {
+        batches.push(current_batch);
+    }
+    batches
+}
+
+/// Returns the serialized size of the given value, saturating at `usize::MAX` on failure.
+pub(super) fn serialized_size<V: Serialize>(value: &V) -> usize {
+    bincode::serialized_size(value).map_or(usize::MAX, |size| {
+        usize::try_from(size).unwrap_or(usize::MAX)
+    })
+}
@@ -19,6 +19,8 @@ pub(super) const DEFAULT_FINISHED_ENTRY_DURATION: &str = "60sec";
 const DEFAULT_GOSSIP_REQUEST_TIMEOUT: &str = "10sec";
 const DEFAULT_GET_REMAINDER_TIMEOUT: &str = "60sec";
 const DEFAULT_VALIDATE_AND_STORE_TIMEOUT: &str = "60sec";
+const DEFAULT_BATCH_INTERVAL: &str = "0sec";
+const DEFAULT_MAX_BATCH_SIZE: u32 = 16_384;
 #[cfg(test)]
 const SMALL_TIMEOUTS_FINISHED_ENTRY_DURATION: &str = "2sec";
 #[cfg(test)]
@@ -56,6 +58,13 @@ pub struct Config {
     /// The timeout duration for a newly-received, gossiped item to be validated and stored by
     /// another component before the gossiper abandons waiting to gossip the item onwards.
     pub validate_and_store_timeout: TimeDiff,
+    /// The maximum duration for which outgoing gossip announcements, responses and items are held
+    /// back so that the ones sharing a destination can be sent as a single batched message.  A
+    /// value of zero disables batching.
+    pub batch_interval: TimeDiff,
+    /// The maximum total serialized size in bytes of the item IDs or items in a single batched
+    /// message.
+    pub max_batch_size: u32,
 }
 
 impl Config {
@@ -78,6 +87,8 @@ impl Config {
             gossip_request_timeout,
             get_remainder_timeout,
             validate_and_store_timeout,
+            batch_interval: TimeDiff::from_str(DEFAULT_BATCH_INTERVAL).unwrap(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
         })
     }
 
@@ -121,6 +132,14 @@ impl Config {
     pub(crate) fn validate_and_store_timeout(&self) -> TimeDiff {
         self.validate_and_store_timeout
     }
+
+    pub(crate) fn batch_interval(&self) -> TimeDiff {
+        self.batch_interval
+    }
+
+    pub(crate) fn max_batch_size(&self) -> u32 {
+        self.max_batch_size
+    }
 }
 
 impl Default for Config {
@@ -133,6 +152,8 @@ impl Default for Config {
             get_remainder_timeout: TimeDiff::from_str(DEFAULT_GET_REMAINDER_TIMEOUT).unwrap(),
             validate_and_store_timeout: TimeDiff::from_str(DEFAULT_VALIDATE_AND_STORE_TIMEOUT)
                 .unwrap(),
+            batch_interval: TimeDiff::from_str(DEFAULT_BATCH_INTERVAL).unwrap(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
         }
     }
 }
@@ -172,6 +193,8 @@ mod tests {
             get_remainder_timeout: TimeDiff::from_str(DEFAULT_GET_REMAINDER_TIMEOUT).unwrap(),
             validate_and_store_timeout: TimeDiff::from_str(DEFAULT_VALIDATE_AND_STORE_TIMEOUT)
                 .unwrap(),
+            batch_interval: TimeDiff::from_str(DEFAULT_BATCH_INTERVAL).unwrap(),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
         };
 
         // Parsing should fail.
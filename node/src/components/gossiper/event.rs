@@ -31,6 +31,12 @@ pub(crate) enum Event<T: GossipItem> {
         requested_count: usize,
         peers: HashSet<NodeId>,
     },
+    /// The network component gossiped a batch of item IDs to the included peers.
+    GossipedBatchTo {
+        item_ids: Vec<T::Id>,
+        requested_count: usize,
+        peers: HashSet<NodeId>,
+    },
     /// The timeout for waiting for a gossip response has elapsed and we should check the response
     /// arrived.
     CheckGossipTimeout { item_id: T::Id, peer: NodeId },
@@ -56,6 +62,8 @@ pub(crate) enum Event<T: GossipItem> {
         requester: NodeId,
         maybe_item: Option<Box<T>>,
     },
+    /// The batching interval has elapsed and queued gossip messages should be sent.
+    FlushBatches,
 }
 
 impl<T: GossipItem> Display for Event<T> {
@@ -81,6 +89,14 @@ impl<T: GossipItem> Display for Event<T> {
                 item_id,
                 DisplayIter::new(peers)
             ),
+            Event::GossipedBatchTo {
+                item_ids, peers, ..
+            } => write!(
+                formatter,
+                "gossiped batch of {} items to {}",
+                item_ids.len(),
+                DisplayIter::new(peers)
+            ),
             Event::CheckGossipTimeout { item_id, peer } => write!(
                 formatter,
                 "check gossip timeout for {} with {}",
@@ -119,6 +135,7 @@ impl<T: GossipItem> Display for Event<T> {
                     write!(formatter, "failed to get {} from storage", item_id)
                 }
             }
+            Event::FlushBatches => write!(formatter, "flush batched gossip messages"),
         }
     }
 }
@@ -5,6 +5,8 @@ use std::{
 
 use serde::{de::DeserializeOwned, Serialize};
 
+use casper_types::Timestamp;
+
 use crate::effect::GossipTarget;
 
 /// A trait which allows an implementing type to be used by a gossiper component.
@@ -31,4 +33,8 @@ pub(crate) trait LargeGossipItem: GossipItem {}
 pub(crate) trait SmallGossipItem: GossipItem {
     /// Convert a `Self::Id` into `Self`.
     fn id_as_item(id: &Self::Id) -> &Self;
+
+    /// Returns `true` if the item is valid at time `now`, i.e. it may be announced and gossiped
+    /// onwards.
+    fn is_valid(&self, now: Timestamp) -> bool;
 }
@@ -1,13 +1,20 @@
 use std::{
     boxed::Box,
     fmt::{self, Display, Formatter},
+    marker::PhantomData,
 };
 
-use serde::{Deserialize, Serialize};
+use serde::{
+    de::{Error as SerdeError, SeqAccess, Visitor},
+    Deserialize, Deserializer, Serialize,
+};
 use strum::EnumDiscriminants;
 
 use super::GossipItem;
 
+/// The maximum number of entries in a single batched gossip message.
+pub(crate) const MAX_GOSSIP_BATCH_LEN: usize = 256;
+
 #[derive(Clone, Debug, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(strum::EnumIter))]
 #[serde(bound = "for<'a> T: Deserialize<'a>")]
@@ -26,6 +33,54 @@ pub(crate) enum Message<T: GossipItem> {
     // Response to either a `GossipResponse` with `is_already_held` set to `false` or to a
     // `GetItem` message. Contains the actual item requested.
     Item(Box<T>),
+    /// A batch of responses to `Gossip` messages, each to be handled as an individual
+    /// `GossipResponse`.  Holds at most `MAX_GOSSIP_BATCH_LEN` entries.
+    GossipResponseBatch(#[serde(deserialize_with = "deserialize_batch")] Vec<(T::Id, bool)>),
+    /// A batch of item IDs gossiped out to random peers, each to be handled as an individual
+    /// `Gossip` message.  Holds at most `MAX_GOSSIP_BATCH_LEN` entries.
+    GossipBatch(#[serde(deserialize_with = "deserialize_batch")] Vec<T::Id>),
+    /// A batch of items, each to be handled as an individual `Item` message.  Holds at most
+    /// `MAX_GOSSIP_BATCH_LEN` entries.
+    ItemBatch(#[serde(deserialize_with = "deserialize_batch")] Vec<T>),
+}
+
+/// Deserializes a batch, failing before decoding any entries if it holds more than
+/// `MAX_GOSSIP_BATCH_LEN` of them.
+fn deserialize_batch<'de, D, E>(deserializer: D) -> Result<Vec<E>, D::Error>
+where
+    D: Deserializer<'de>,
+    E: Deserialize<'de>,
+{
+    struct BatchVisitor<E>(PhantomData<E>);
+
+    impl<'de, E: Deserialize<'de>> Visitor<'de> for BatchVisitor<E> {
+        type Value = Vec<E>;
+
+        fn expecting(&self, formatter: &mut Formatter) -> fmt::Result {
+            write!(
+                formatter,
+                "a batch of at most {} entries",
+                MAX_GOSSIP_BATCH_LEN
+            )
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+            let len = seq.size_hint().unwrap_or_default();
+            if len > MAX_GOSSIP_BATCH_LEN {
+                return Err(A::Error::invalid_length(len, &self));
+            }
+            let mut batch = Vec::with_capacity(len);
+            while let Some(entry) = seq.next_element()? {
+                if batch.len() == MAX_GOSSIP_BATCH_LEN {
+                    return Err(A::Error::invalid_length(batch.len() + 1, &self));
+                }
+                batch.push(entry);
+            }
+            Ok(batch)
+        }
+    }
+
+    deserializer.deserialize_seq(BatchVisitor(PhantomData))
 }
 
 impl<T: GossipItem> Display for Message<T> {
@@ -42,6 +97,19 @@ impl<T: GossipItem> Display for Message<T> {
             ),
             Message::GetItem(item_id) => write!(formatter, "gossip-get-item({})", item_id),
             Message::Item(item) => write!(formatter, "gossip-item({})", item.gossip_id()),
+            Message::GossipResponseBatch(responses) => {
+                write!(
+                    formatter,
+                    "gossip-response-batch({} items)",
+                    responses.len()
+                )
+            }
+            Message::GossipBatch(item_ids) => {
+                write!(formatter, "gossip-batch({} items)", item_ids.len())
+            }
+            Message::ItemBatch(items) => {
+                write!(formatter, "gossip-item-batch({} items)", items.len())
+            }
         }
     }
 }
@@ -49,10 +117,12 @@ impl<T: GossipItem> Display for Message<T> {
 mod specimen_support {
     use crate::{
         components::gossiper::GossipItem,
-        utils::specimen::{largest_variant, Cache, LargestSpecimen, SizeEstimator},
+        utils::specimen::{
+            largest_variant, vec_of_largest_specimen, Cache, LargestSpecimen, SizeEstimator,
+        },
     };
 
-    use super::{Message, MessageDiscriminants};
+    use super::{Message, MessageDiscriminants, MAX_GOSSIP_BATCH_LEN};
 
     impl<T> LargestSpecimen for Message<T>
     where
@@ -76,6 +146,19 @@ mod specimen_support {
                     MessageDiscriminants::Item => {
                         Message::Item(LargestSpecimen::largest_specimen(estimator, cache))
                     }
+                    MessageDiscriminants::GossipResponseBatch => Message::GossipResponseBatch(
+                        vec_of_largest_specimen(estimator, MAX_GOSSIP_BATCH_LEN, cache),
+                    ),
+                    MessageDiscriminants::GossipBatch => Message::GossipBatch(
+                        vec_of_largest_specimen(estimator, MAX_GOSSIP_BATCH_LEN, cache),
+                    ),
+                    // Batches of more than one item are capped at the configured `max_batch_size`,
+                    // which is well below the size of the largest item.
+                    MessageDiscriminants::ItemBatch => {
+                        Message::ItemBatch(vec![LargestSpecimen::largest_specimen(
+                            estimator, cache,
+                        )])
+                    }
                 },
             )
         }
@@ -172,9 +172,12 @@ impl reactor::Reactor for Reactor {
         .unwrap();
 
         let fake_deploy_acceptor = FakeDeployAcceptor::new();
+        // The features in use never change, so the sender can be dropped right away.
+        let (_, network_features) = watch::channel(NetworkFeatures::SUPPORTED);
         let deploy_gossiper = Gossiper::<{ Deploy::ID_IS_COMPLETE_ITEM }, _>::new(
             "deploy_gossiper",
             config,
+            network_features,
             registry,
         )?;
 
@@ -749,3 +752,90 @@ async fn should_ignore_unexpected_get_item_message() {
 async fn should_ignore_unexpected_item_message() {
     should_ignore_unexpected_message(Unexpected::Item).await
 }
+
+#[test]
+fn should_split_batched_responses_by_size_and_length() {
+    // Each `u64` ID serializes to 8 bytes, so a budget of 20 bytes fits two IDs per batch.
+    let responses: Vec<(u64, bool)> = (0..5).map(|id| (id, id % 2 == 0)).collect();
+    let batches = into_batches(responses.clone(), 20, |(id, _)| serialized_size(id));
+    assert_eq!(batches.len(), 3);
+    assert_eq!(batches.concat(), responses);
+
+    // An ID larger than the budget on its own still gets a batch of its own.
+    let batches = into_batches(responses.clone(), 1, |(id, _)| serialized_size(id));
+    assert_eq!(batches.len(), responses.len());
+
+    // No batch exceeds the maximum length, regardless of the size budget.
+    let responses: Vec<(u64, bool)> = (0..(MAX_GOSSIP_BATCH_LEN as u64 + 1))
+        .map(|id| (id, true))
+        .collect();
+    let batches = into_batches(responses, usize::MAX, |(id, _)| serialized_size(id));
+    assert_eq!(batches.len(), 2);
+    assert_eq!(batches[0].len(), MAX_GOSSIP_BATCH_LEN);
+}
+
+#[test]
+fn should_only_batch_gossip_excluding_the_same_peers() {
+    let rng = &mut TestRng::new();
+    let peer = NodeId::random(rng);
+    let other_peer = NodeId::random(rng);
+    let deploy_ids: Vec<_> =
+        iter::repeat_with(|| Deploy::random_valid_native_transfer(rng).gossip_id())
+            .take(3)
+            .collect();
+
+    let mut pending = PendingBatches::<Deploy>::default();
+    for (deploy_id, excluded_peer) in deploy_ids.iter().zip([peer, other_peer, peer]) {
+        pending.push_gossip(
+            deploy_id.clone(),
+            GossipTarget::All,
+            3,
+            iter::once(excluded_peer).collect(),
+        );
+    }
+
+    // Each item is only withheld from the peers excluded for it.
+    let groups: Vec<(HashSet<NodeId>, Vec<_>)> = pending
+        .gossip
+        .iter()
+        .map(|(group, item_ids)| (group.exclude_peers.clone(), item_ids.clone()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            (
+                iter::once(peer).collect(),
+                vec![deploy_ids[0].clone(), deploy_ids[2].clone()]
+            ),
+            (
+                iter::once(other_peer).collect(),
+                vec![deploy_ids[1].clone()]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn should_reject_oversized_batches_when_decoding() {
+    let rng = &mut TestRng::new();
+
+    let deploys: Vec<Deploy> = iter::repeat_with(|| Deploy::random_valid_native_transfer(rng))
+        .take(2)
+        .collect();
+    let serialized = bincode::serialize(&Message::ItemBatch(deploys.clone())).unwrap();
+    match bincode::deserialize::<Message<Deploy>>(&serialized).unwrap() {
+        Message::ItemBatch(decoded) => assert_eq!(decoded, deploys),
+        message => panic!("unexpected message {}", message),
+    }
+
+    // Batches one entry longer than the maximum are rejected.
+    let deploy_id = deploys[0].gossip_id();
+    for message in [
+        Message::<Deploy>::GossipBatch(vec![deploy_id; MAX_GOSSIP_BATCH_LEN + 1]),
+        Message::<Deploy>::GossipResponseBatch(vec![(deploy_id, true); MAX_GOSSIP_BATCH_LEN + 1]),
+        Message::<Deploy>::ItemBatch(vec![deploys[0].clone(); MAX_GOSSIP_BATCH_LEN + 1]),
+    ] {
+        let serialized = bincode::serialize(&message).unwrap();
+        assert!(bincode::deserialize::<Message<Deploy>>(&serialized).is_err());
+    }
+}
@@ -22,6 +22,12 @@
 //!    fields of `self.metrics` (`: XYZMetrics`). **Important**: Metrics should never be read to
 //!    prevent any actual logic depending on them. If a counter is being increment as a metric and
 //!    also required for business logic, a second counter should be kept in the component's state.
+//!
+//! Besides being served by the REST server, metrics can be pushed to an OpenTelemetry collector
+//! via OTLP, see [`OtlpExporter`].
+
+mod config;
+mod otlp;
 
 use datasize::DataSize;
 use prometheus::{Encoder, Registry, TextEncoder};
@@ -33,6 +39,9 @@ use crate::{
     NodeRng,
 };
 
+pub use config::Config;
+pub(crate) use otlp::{OtlpConfigError, OtlpExporter};
+
 const COMPONENT_NAME: &str = "metrics";
 
 /// The metrics component.
@@ -0,0 +1,30 @@
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::TimeDiff;
+
+/// Default interval between two exports of the metrics to the OTLP endpoint.
+const DEFAULT_OTLP_EXPORT_INTERVAL: TimeDiff = TimeDiff::from_seconds(30);
+
+/// Metrics configuration.
+#[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// URL of an OpenTelemetry collector's OTLP/HTTP metrics endpoint, e.g.
+    /// `http://127.0.0.1:4318/v1/metrics`, to which all metrics are pushed periodically.
+    ///
+    /// Only plain HTTP is supported. If unset, metrics are only served by the REST server.
+    pub otlp_endpoint: Option<String>,
+    /// Interval between two exports of the metrics to the OTLP endpoint.
+    pub otlp_export_interval: TimeDiff,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            otlp_endpoint: None,
+            otlp_export_interval: DEFAULT_OTLP_EXPORT_INTERVAL,
+        }
+    }
+}
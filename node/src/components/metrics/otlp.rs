@@ -0,0 +1,392 @@
+//! Export of metrics to an OpenTelemetry collector.
+//!
+//! The metrics held by the registry are periodically converted into the JSON encoding of the OTLP
+//! protocol and pushed to the configured collector endpoint via HTTP. Counters are exported as
+//! monotonic cumulative sums, gauges and untyped metrics as gauges, and histograms and summaries
+//! as their OTLP equivalents.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use hyper::{
+    header::CONTENT_TYPE,
+    http::{self, uri::InvalidUri},
+    Body, Client, Request, StatusCode, Uri,
+};
+use prometheus::{
+    proto::{LabelPair, Metric, MetricFamily, MetricType},
+    Registry,
+};
+use serde_json::{json, Value};
+use thiserror::Error;
+use tokio::time;
+use tracing::{debug, info, warn};
+
+use super::Config;
+
+/// Name of the instrumentation scope all metrics are exported under.
+const SCOPE_NAME: &str = "casper-node";
+
+/// `AGGREGATION_TEMPORALITY_CUMULATIVE` in the OTLP protocol.
+const AGGREGATION_TEMPORALITY_CUMULATIVE: u8 = 2;
+
+/// An error setting up the OTLP exporter.
+#[derive(Debug, Error)]
+pub(crate) enum OtlpConfigError {
+    /// The endpoint is not a valid URI.
+    #[error("invalid OTLP endpoint: {0}")]
+    InvalidEndpoint(#[from] InvalidUri),
+    /// The endpoint uses a scheme other than `http`.
+    #[error("unsupported OTLP endpoint scheme, only `http` is supported: {0}")]
+    UnsupportedScheme(Uri),
+}
+
+/// An error exporting metrics.
+#[derive(Debug, Error)]
+enum ExportError {
+    #[error("failed to build request: {0}")]
+    Request(#[from] http::Error),
+    #[error("failed to send request: {0}")]
+    Send(#[from] hyper::Error),
+    #[error("collector responded with {0}")]
+    Status(StatusCode),
+}
+
+/// Periodically pushes the metrics held by a registry to an OTLP/HTTP endpoint.
+pub(crate) struct OtlpExporter {
+    registry: Registry,
+    endpoint: Uri,
+    interval: Duration,
+    /// Attributes describing the node, attached to all exported metrics.
+    resource_attributes: Vec<(&'static str, String)>,
+}
+
+impl OtlpExporter {
+    /// Creates a new exporter, unless no OTLP endpoint is configured.
+    pub(crate) fn new(
+        config: &Config,
+        registry: Registry,
+        resource_attributes: Vec<(&'static str, String)>,
+    ) -> Result<Option<Self>, OtlpConfigError> {
+        let endpoint = match config.otlp_endpoint {
+            Some(ref endpoint) => endpoint.parse::<Uri>()?,
+            None => return Ok(None),
+        };
+        if endpoint.scheme_str() != Some("http") {
+            return Err(OtlpConfigError::UnsupportedScheme(endpoint));
+        }
+        Ok(Some(OtlpExporter {
+            registry,
+            endpoint,
+            interval: config.otlp_export_interval.into(),
+            resource_attributes,
+        }))
+    }
+
+    /// Exports the metrics every interval, forever.
+    pub(crate) async fn run(self) {
+        info!(endpoint = %self.endpoint, "exporting metrics via OTLP");
+        let client = Client::new();
+        let start_time = unix_nanos(SystemTime::now());
+        let mut interval = time::interval(self.interval);
+        // Only the first of a series of failures is logged as a warning.
+        let mut last_export_failed = false;
+        loop {
+            interval.tick().await;
+            let body = encode(
+                &self.registry.gather(),
+                &self.resource_attributes,
+                start_time,
+                unix_nanos(SystemTime::now()),
+            );
+            match self.export(&client, body.to_string()).await {
+                Ok(()) => {
+                    if last_export_failed {
+                        info!("exporting metrics via OTLP succeeded again");
+                    }
+                    last_export_failed = false;
+                }
+                Err(error) if last_export_failed => {
+                    debug!(%error, "failed to export metrics via OTLP");
+                }
+                Err(error) => {
+                    warn!(%error, "failed to export metrics via OTLP");
+                    last_export_failed = true;
+                }
+            }
+        }
+    }
+
+    async fn export<C>(&self, client: &Client<C>, body: String) -> Result<(), ExportError>
+    where
+        C: hyper::client::connect::Connect + Clone + Send + Sync + 'static,
+    {
+        let request = Request::post(self.endpoint.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(Body::from(body))?;
+        let response = client.request(request).await?;
+        if !response.status().is_success() {
+            return Err(ExportError::Status(response.status()));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the nanoseconds elapsed since the Unix epoch.
+fn unix_nanos(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Encodes the given metric families as an OTLP `ExportMetricsServiceRequest` in JSON.
+///
+/// As in the protocol's JSON encoding, 64 bit integers are encoded as strings.
+fn encode(
+    families: &[MetricFamily],
+    resource_attributes: &[(&'static str, String)],
+    start_time: u64,
+    time: u64,
+) -> Value {
+    let attributes: Vec<Value> = resource_attributes
+        .iter()
+        .map(|(key, value)| attribute(key, value))
+        .collect();
+    let metrics: Vec<Value> = families
+        .iter()
+        .map(|family| encode_family(family, start_time, time))
+        .collect();
+    json!({
+        "resourceMetrics": [{
+            "resource": { "attributes": attributes },
+            "scopeMetrics": [{
+                "scope": { "name": SCOPE_NAME, "version": env!("CARGO_PKG_VERSION") },
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+fn encode_family(family: &MetricFamily, start_time: u64, time: u64) -> Value {
+    let data_points = |to_value: fn(&Metric) -> Value| -> Vec<Value> {
+        family
+            .get_metric()
+            .iter()
+            .map(|metric| {
+                let mut data_point = to_value(metric);
+                data_point["attributes"] = attributes(metric.get_label());
+                data_point["startTimeUnixNano"] = json!(start_time.to_string());
+                data_point["timeUnixNano"] = json!(time.to_string());
+                data_point
+            })
+            .collect()
+    };
+    let mut metric = json!({
+        "name": family.get_name(),
+        "description": family.get_help(),
+    });
+    match family.get_field_type() {
+        MetricType::COUNTER => {
+            metric["sum"] = json!({
+                "dataPoints": data_points(|metric| {
+                    json!({ "asDouble": metric.get_counter().get_value() })
+                }),
+                "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+                "isMonotonic": true,
+            });
+        }
+        MetricType::GAUGE => {
+            metric["gauge"] = json!({
+                "dataPoints": data_points(|metric| {
+                    json!({ "asDouble": metric.get_gauge().get_value() })
+                }),
+            });
+        }
+        MetricType::UNTYPED => {
+            metric["gauge"] = json!({
+                "dataPoints": data_points(|metric| {
+                    json!({ "asDouble": metric.get_untyped().get_value() })
+                }),
+            });
+        }
+        MetricType::HISTOGRAM => {
+            metric["histogram"] = json!({
+                "dataPoints": data_points(encode_histogram),
+                "aggregationTemporality": AGGREGATION_TEMPORALITY_CUMULATIVE,
+            });
+        }
+        MetricType::SUMMARY => {
+            metric["summary"] = json!({
+                "dataPoints": data_points(|metric| {
+                    let summary = metric.get_summary();
+                    let quantile_values: Vec<Value> = summary
+                        .get_quantile()
+                        .iter()
+                        .map(|quantile| {
+                            json!({
+                                "quantile": quantile.get_quantile(),
+                                "value": quantile.get_value(),
+                            })
+                        })
+                        .collect();
+                    json!({
+                        "count": summary.get_sample_count().to_string(),
+                        "sum": summary.get_sample_sum(),
+                        "quantileValues": quantile_values,
+                    })
+                }),
+            });
+        }
+    }
+    metric
+}
+
+/// Encodes a histogram data point.
+///
+/// Prometheus buckets hold cumulative counts, while OTLP buckets hold the count of their own range
+/// only, with an additional last bucket for values above the highest bound.
+fn encode_histogram(metric: &Metric) -> Value {
+    let histogram = metric.get_histogram();
+    let mut explicit_bounds = vec![];
+    let mut bucket_counts = vec![];
+    let mut previous_cumulative_count = 0;
+    for bucket in histogram.get_bucket() {
+        if bucket.get_upper_bound().is_infinite() {
+            break;
+        }
+        explicit_bounds.push(bucket.get_upper_bound());
+        bucket_counts.push(
+            bucket
+                .get_cumulative_count()
+                .saturating_sub(previous_cumulative_count)
+                .to_string(),
+        );
+        previous_cumulative_count = bucket.get_cumulative_count();
+    }
+    bucket_counts.push(
+        histogram
+            .get_sample_count()
+            .saturating_sub(previous_cumulative_count)
+            .to_string(),
+    );
+    json!({
+        "count": histogram.get_sample_count().to_string(),
+        "sum": histogram.get_sample_sum(),
+        "bucketCounts": bucket_counts,
+        "explicitBounds": explicit_bounds,
+    })
+}
+
+fn attributes(labels: &[LabelPair]) -> Value {
+    labels
+        .iter()
+        .map(|label| attribute(label.get_name(), label.get_value()))
+        .collect()
+}
+
+fn attribute(key: &str, value: &str) -> Value {
+    json!({ "key": key, "value": { "stringValue": value } })
+}
+
+#[cfg(test)]
+mod tests {
+    use prometheus::{Histogram, HistogramOpts, IntCounterVec, Opts};
+
+    use super::*;
+
+    #[test]
+    fn should_encode_counters_and_histograms() {
+        let registry = Registry::new();
+        let counter =
+            IntCounterVec::new(Opts::new("requests", "number of requests"), &["kind"]).unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        let histogram =
+            Histogram::with_opts(HistogramOpts::new("latency", "latency").buckets(vec![1.0, 2.0]))
+                .unwrap();
+        registry.register(Box::new(histogram.clone())).unwrap();
+
+        counter.with_label_values(&["get"]).inc_by(3);
+        for value in [0.5, 1.5, 1.75, 5.0] {
+            histogram.observe(value);
+        }
+
+        let encoded = encode(
+            &registry.gather(),
+            &[("service.name", "casper-node".to_string())],
+            10,
+            20,
+        );
+        let resource_metrics = &encoded["resourceMetrics"][0];
+        assert_eq!(
+            resource_metrics["resource"]["attributes"],
+            json!([{ "key": "service.name", "value": { "stringValue": "casper-node" } }])
+        );
+
+        // Metric families are gathered sorted by name.
+        let metrics = &resource_metrics["scopeMetrics"][0]["metrics"];
+        assert_eq!(
+            metrics[0],
+            json!({
+                "name": "latency",
+                "description": "latency",
+                "histogram": {
+                    "dataPoints": [{
+                        "attributes": [],
+                        "startTimeUnixNano": "10",
+                        "timeUnixNano": "20",
+                        "count": "4",
+                        "sum": 8.75,
+                        "bucketCounts": ["1", "2", "1"],
+                        "explicitBounds": [1.0, 2.0],
+                    }],
+                    "aggregationTemporality": 2,
+                },
+            })
+        );
+        assert_eq!(
+            metrics[1],
+            json!({
+                "name": "requests",
+                "description": "number of requests",
+                "sum": {
+                    "dataPoints": [{
+                        "attributes": [{ "key": "kind", "value": { "stringValue": "get" } }],
+                        "startTimeUnixNano": "10",
+                        "timeUnixNano": "20",
+                        "asDouble": 3.0,
+                    }],
+                    "aggregationTemporality": 2,
+                    "isMonotonic": true,
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn should_only_accept_http_endpoints() {
+        let config = |endpoint: &str| Config {
+            otlp_endpoint: Some(endpoint.to_string()),
+            ..Config::default()
+        };
+
+        assert!(
+            OtlpExporter::new(&Config::default(), Registry::new(), vec![])
+                .unwrap()
+                .is_none()
+        );
+        assert!(OtlpExporter::new(
+            &config("http://127.0.0.1:4318/v1/metrics"),
+            Registry::new(),
+            vec![]
+        )
+        .unwrap()
+        .is_some());
+        assert!(matches!(
+            OtlpExporter::new(
+                &config("https://collector/v1/metrics"),
+                Registry::new(),
+                vec![]
+            ),
+            Err(OtlpConfigError::UnsupportedScheme(_))
+        ));
+    }
+}
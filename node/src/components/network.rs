@@ -23,9 +23,11 @@
 //! Nodes gossip their public listening addresses periodically, and will try to establish and
 //! maintain an outgoing connection to any new address learned.
 
+mod address_book;
 mod bincode_format;
 pub(crate) mod blocklist;
 mod chain_info;
+mod compression;
 mod config;
 mod counting_format;
 mod error;
@@ -50,6 +52,7 @@ use std::{
     io,
     net::{SocketAddr, TcpListener},
     sync::{Arc, Weak},
+    thread,
     time::{Duration, Instant},
 };
 
@@ -74,45 +77,48 @@ use tokio_openssl::SslStream;
 use tokio_util::codec::LengthDelimitedCodec;
 use tracing::{debug, error, info, trace, warn, Instrument, Span};
 
-use casper_types::{EraId, PublicKey, SecretKey};
+use casper_types::{EraId, PublicKey, SecretKey, Timestamp};
 
 pub(crate) use self::{
     bincode_format::BincodeFormat,
+    compression::{compress, decompress, DecompressionError},
     config::{Config, IdentityConfig},
     error::Error,
     event::Event,
     gossiped_address::GossipedAddress,
     identity::Identity,
     insights::NetworkInsights,
+    limiter::RateLimits,
     message::{
         generate_largest_serialized_message, EstimatorWeights, FromIncoming, Message, MessageKind,
-        Payload,
+        NetworkMessageEstimator, Payload,
     },
 };
 use self::{
+    address_book::{AddressBook, RejectionReason},
     blocklist::BlocklistJustification,
     chain_info::ChainInfo,
     counting_format::{ConnectionId, CountingFormat, Role},
-    error::{ConnectionError, Result},
+    error::{ConnectionError, Result, SelfTestError},
     event::{IncomingConnection, OutgoingConnection},
     health::{HealthConfig, TaggedTimestamp},
     limiter::Limiter,
     message::NodeKeyPair,
     metrics::Metrics,
-    outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager},
+    outgoing::{DialOutcome, DialRequest, OutgoingConfig, OutgoingManager, OutgoingState},
     symmetry::ConnectionSymmetry,
-    tasks::{MessageQueueItem, NetworkContext},
+    tasks::{CompressionSettings, MessageQueueItem, NetworkContext},
 };
 use crate::{
     components::{gossiper::GossipItem, Component, ComponentState, InitializedComponent},
     effect::{
-        announcements::PeerBehaviorAnnouncement,
+        announcements::{PeerBehaviorAnnouncement, UpcomingValidatorsAnnouncement},
         requests::{BeginGossipRequest, NetworkInfoRequest, NetworkRequest, StorageRequest},
         AutoClosingResponder, EffectBuilder, EffectExt, Effects, GossipTarget,
     },
     reactor::{Finalize, ReactorEvent},
     tls,
-    types::{NodeId, ValidatorMatrix},
+    types::{chainspec::NetworkFeatures, NodeId, ValidatorMatrix},
     utils::{self, display_error, Source},
     NodeRng,
 };
@@ -148,11 +154,22 @@ const PING_TIMEOUT: Duration = Duration::from_secs(6);
 /// How many pings to send before giving up and dropping the connection.
 const PING_RETRIES: u16 = 5;
 
+/// The lifetime of our signed address record, as a multiple of the gossip interval.
+///
+/// Peers discard records once expired, so this must allow for the record to spread before we
+/// gossip a fresh one.
+const ADDRESS_RECORD_LIFETIME_INTERVALS: u64 = 3;
+
 #[derive(Clone, DataSize, Debug)]
 pub(crate) struct OutgoingHandle<P> {
     #[data_size(skip)] // Unfortunately, there is no way to inspect an `UnboundedSender`.
     sender: UnboundedSender<MessageQueueItem<P>>,
+    /// Sender for urgent messages, which take strict priority over those sent via `sender`.
+    #[data_size(skip)]
+    urgent_sender: UnboundedSender<MessageQueueItem<P>>,
     peer_addr: SocketAddr,
+    /// The consensus key the peer proved to hold during the handshake, if any.
+    peer_consensus_public_key: Option<PublicKey>,
 }
 
 impl<P> Display for OutgoingHandle<P> {
@@ -198,11 +215,31 @@ where
 
     /// The era that is considered the active era by the network component.
     active_era: EraId,
+    /// Publishes the network features in use in the active era.
+    #[data_size(skip)]
+    active_network_features: watch::Sender<NetworkFeatures>,
+
+    /// Addresses from the config's known addresses, which are always unforgettable.
+    known_addresses: HashSet<SocketAddr>,
+    /// The verified gossiped listening addresses of other nodes.
+    address_book: AddressBook,
+    /// Validators to whose addresses we keep reconnecting.
+    retained_validators: HashMap<PublicKey, RetainedValidator>,
 
     /// The state of this component.
     state: ComponentState,
 }
 
+/// A validator to whose address we keep reconnecting.
+#[derive(DataSize, Debug)]
+struct RetainedValidator {
+    /// The latest era for which the node is known to be a validator.
+    last_era: EraId,
+    /// The instant at which the validator will be released, set once an era it is not a validator
+    /// in has begun.
+    release_at: Option<Instant>,
+}
+
 #[derive(DataSize)]
 struct ChannelManagement {
     /// Channel signaling a shutdown of the network.
@@ -277,12 +314,18 @@ where
             net_metrics.create_outgoing_metrics(),
         );
 
+        let chain_info: ChainInfo = chain_info_source.into();
+        let active_era = chain_info.activation_era;
+        let (active_network_features, active_network_features_receiver) =
+            watch::channel(chain_info.network_features.active_at(active_era));
+
         let context = Arc::new(NetworkContext::new(
             cfg.clone(),
             our_identity,
             node_key_pair.map(NodeKeyPair::new),
-            chain_info_source.into(),
+            chain_info,
             &net_metrics,
+            active_network_features_receiver,
         ));
 
         let component = Network {
@@ -295,14 +338,40 @@ where
             net_metrics,
             outgoing_limiter,
             incoming_limiter,
-            // We start with an empty set of validators for era 0 and expect to be updated.
-            active_era: EraId::new(0),
+            // We start in the era our protocol version was activated in and expect to be updated.
+            active_era,
+            active_network_features,
+            known_addresses: HashSet::new(),
+            address_book: AddressBook::new(cfg.max_pending_addresses_per_peer as usize),
+            retained_validators: HashMap::new(),
             state: ComponentState::Uninitialized,
         };
 
         Ok(component)
     }
 
+    /// Connects to ourselves over the loopback interface using the same TLS setup as connections
+    /// to and from peers, and exchanges a frame over the connection.
+    ///
+    /// Blocks until done.  The self-test runs on a dedicated runtime in a separate thread, so it
+    /// can be called from within an async context before the component is initialized.
+    pub(crate) fn run_self_test(&self) -> Result<()> {
+        let context = &*self.context;
+        thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .map_err(SelfTestError::Runtime)?
+                        .block_on(tasks::self_test(context))
+                })
+                .join()
+                .expect("network self-test thread panicked")
+        })?;
+        Ok(())
+    }
+
     fn initialize(&mut self, effect_builder: EffectBuilder<REv>) -> Result<Effects<Event<P>>> {
         let mut known_addresses = HashSet::new();
         for address in &self.cfg.known_addresses {
@@ -379,9 +448,10 @@ where
         // Learn all known addresses and mark them as unforgettable.
         let now = Instant::now();
         let dial_requests: Vec<_> = known_addresses
-            .into_iter()
-            .filter_map(|addr| self.outgoing_manager.learn_addr(addr, true, now))
+            .iter()
+            .filter_map(|addr| self.outgoing_manager.learn_addr(*addr, true, now))
             .collect();
+        self.known_addresses = known_addresses;
 
         let mut effects = self.process_dial_requests(dial_requests);
 
@@ -499,11 +569,25 @@ where
                 error!(kind=%msg.classify(), node_id=%dest, "sending unsafe message to syncing node");
             }
 
-            if let Err(msg) = connection.sender.send((msg, opt_responder)) {
+            let kind = msg.classify();
+            let sender = if msg.is_urgent() {
+                &connection.urgent_sender
+            } else {
+                &connection.sender
+            };
+            if let Err(msg) = sender.send((msg, opt_responder, Instant::now())) {
                 // We lost the connection, but that fact has not reached us yet.
                 warn!(our_id=%self.context.our_id(), %dest, ?msg, "dropped outgoing message, lost connection");
+                self.net_metrics
+                    .channel_send_errors
+                    .with_label_values(&[&kind.to_string()])
+                    .inc();
             } else {
                 self.net_metrics.queued_messages.inc();
+                self.net_metrics
+                    .channel_queue_depth
+                    .with_label_values(&[&kind.to_string()])
+                    .inc();
             }
         } else {
             // We are not connected, so the reconnection is likely already in progress.
@@ -669,7 +753,8 @@ where
             | ConnectionError::TlsHandshake(_)
             | ConnectionError::HandshakeSend(_)
             | ConnectionError::HandshakeRecv(_)
-            | ConnectionError::IncompatibleVersion(_) => None,
+            | ConnectionError::IncompatibleVersion(_)
+            | ConnectionError::MissingNetworkFeatures(_) => None,
 
             // These errors are potential bugs on our side.
             ConnectionError::HandshakeSenderCrashed(_)
@@ -739,15 +824,23 @@ where
                         }),
                 );
 
+                if self.address_book.fail(peer_addr) {
+                    self.count_rejected_addresses(RejectionReason::Unreachable, 1);
+                }
+
                 self.process_dial_requests(requests)
             }
             OutgoingConnection::Loopback { peer_addr } => {
                 // Loopback connections are marked, but closed.
                 info!("successful outgoing loopback connection, will be dropped");
-                let request = self
-                    .outgoing_manager
-                    .handle_dial_outcome(DialOutcome::Loopback { addr: peer_addr });
-                self.process_dial_requests(request)
+                let mut requests = Vec::new();
+                requests.extend(
+                    self.outgoing_manager
+                        .handle_dial_outcome(DialOutcome::Loopback { addr: peer_addr }),
+                );
+                // A gossiped address of another node must not lead back to us.
+                requests.extend(self.complete_liveness_check(peer_addr, None, None, now));
+                self.process_dial_requests(requests)
             }
             OutgoingConnection::Established {
                 peer_addr,
@@ -755,22 +848,46 @@ where
                 peer_consensus_public_key,
                 sink,
                 is_syncing,
+                supports_compression,
             } => {
                 info!("new outgoing connection established");
 
                 let (sender, receiver) = mpsc::unbounded_channel();
-                let handle = OutgoingHandle { sender, peer_addr };
+                let (urgent_sender, urgent_receiver) = mpsc::unbounded_channel();
+                let handle = OutgoingHandle {
+                    sender,
+                    urgent_sender,
+                    peer_addr,
+                    peer_consensus_public_key: peer_consensus_public_key.clone(),
+                };
 
-                let request = self
-                    .outgoing_manager
-                    .handle_dial_outcome(DialOutcome::Successful {
+                let mut requests = Vec::new();
+                requests.extend(self.outgoing_manager.handle_dial_outcome(
+                    DialOutcome::Successful {
                         addr: peer_addr,
                         handle,
                         node_id: peer_id,
                         when: now,
-                    });
-
-                let mut effects = self.process_dial_requests(request);
+                    },
+                ));
+                requests.extend(self.complete_liveness_check(
+                    peer_addr,
+                    Some(peer_id),
+                    peer_consensus_public_key.as_ref(),
+                    now,
+                ));
+
+                let mut effects = self.process_dial_requests(requests);
+
+                let compression =
+                    if supports_compression && !self.cfg.compressed_message_kinds.is_empty() {
+                        Some(CompressionSettings {
+                            kinds: self.cfg.compressed_message_kinds.iter().copied().collect(),
+                            threshold: self.cfg.compression_threshold as usize,
+                        })
+                    } else {
+                        None
+                    };
 
                 // Update connection symmetries.
                 if self
@@ -786,10 +903,14 @@ where
                 effects.extend(
                     tasks::message_sender(
                         receiver,
+                        urgent_receiver,
                         sink,
                         self.outgoing_limiter
                             .create_handle(peer_id, peer_consensus_public_key),
                         self.net_metrics.queued_messages.clone(),
+                        compression,
+                        self.active_network_features.subscribe(),
+                        Arc::downgrade(&self.net_metrics),
                     )
                     .instrument(span)
                     .event(move |_| Event::OutgoingDropped {
@@ -879,6 +1000,156 @@ where
         self.process_dial_requests(requests)
     }
 
+    /// Keeps reconnecting to the validators of an upcoming era.
+    ///
+    /// Announcements for eras older than the active one are ignored, as they would otherwise
+    /// retain validators which have left already.
+    fn handle_upcoming_validators(
+        &mut self,
+        announcement: UpcomingValidatorsAnnouncement,
+    ) -> Effects<Event<P>> {
+        let UpcomingValidatorsAnnouncement { era_id, validators } = announcement;
+        if era_id < self.active_era {
+            debug!(
+                %era_id,
+                active_era = %self.active_era,
+                "ignoring validators of past era"
+            );
+            return Effects::new();
+        }
+        let now = Instant::now();
+
+        let our_public_key = self
+            .context
+            .node_key_pair()
+            .map(|key_pair| key_pair.public_key().clone());
+        let mut requests = Vec::new();
+        for public_key in validators {
+            if our_public_key.as_ref() == Some(&public_key) {
+                continue;
+            }
+            if let Some(addr) = self.address_book.get(&public_key) {
+                requests.extend(self.outgoing_manager.set_unforgettable(addr, true, now));
+            }
+            let retained = self.retained_validators.entry(public_key);
+            let retained = retained.or_insert(RetainedValidator {
+                last_era: era_id,
+                release_at: None,
+            });
+            retained.last_era = retained.last_era.max(era_id);
+            retained.release_at = None;
+        }
+        self.update_active_era(era_id, now);
+        debug!(
+            %era_id,
+            retained = self.retained_validators.len(),
+            dialing = requests.len(),
+            "retaining connections to upcoming validators"
+        );
+        self.process_dial_requests(requests)
+    }
+
+    /// Switches to the given era if it is newer than the active one, updating the network features
+    /// in use and starting the grace period of the retained validators which are not validators
+    /// in it.
+    fn update_active_era(&mut self, era_id: EraId, now: Instant) {
+        if era_id <= self.active_era {
+            return;
+        }
+        self.active_era = era_id;
+        let release_at = now + self.cfg.validator_retention_grace_period.into();
+        for retained in self.retained_validators.values_mut() {
+            if retained.last_era < era_id && retained.release_at.is_none() {
+                retained.release_at = Some(release_at);
+            }
+        }
+        let network_features = self.context.chain_info().network_features.active_at(era_id);
+        let previous_network_features = self.active_network_features.send_replace(network_features);
+        if network_features != previous_network_features {
+            info!(%era_id, %network_features, "network features in use changed");
+        }
+    }
+
+    /// Returns a receiver of the network features in use, updated whenever a new era begins.
+    pub(crate) fn network_features(&self) -> watch::Receiver<NetworkFeatures> {
+        self.active_network_features.subscribe()
+    }
+
+    /// Releases validators whose grace period has expired, allowing their addresses to be
+    /// forgotten again, and drops addresses we are no longer interested in.
+    fn release_departed_validators(&mut self, now: Instant) {
+        let released: Vec<PublicKey> = self
+            .retained_validators
+            .iter()
+            .filter(|(_, retained)| {
+                retained
+                    .release_at
+                    .map_or(false, |release_at| release_at <= now)
+            })
+            .map(|(public_key, _)| public_key.clone())
+            .collect();
+        for public_key in released {
+            self.retained_validators.remove(&public_key);
+            if let Some(addr) = self.address_book.get(&public_key) {
+                if !self.known_addresses.contains(&addr) {
+                    debug!(%public_key, %addr, "releasing departed validator");
+                    // Making an address forgettable never requires dialing.
+                    let _ = self.outgoing_manager.set_unforgettable(addr, false, now);
+                }
+            }
+        }
+
+        let retained_validators = &self.retained_validators;
+        let outgoing = &self.outgoing_manager.outgoing;
+        self.address_book.retain(|public_key, addr| {
+            retained_validators.contains_key(public_key) || outgoing.contains_key(&addr)
+        });
+    }
+
+    /// Completes the liveness check of a gossiped address, after an outgoing connection to it
+    /// reached a node holding `peer_public_key`.
+    ///
+    /// Only then is the address recorded in the address book.
+    fn complete_liveness_check(
+        &mut self,
+        addr: SocketAddr,
+        peer_id: Option<NodeId>,
+        peer_public_key: Option<&PublicKey>,
+        now: Instant,
+    ) -> Option<DialRequest<OutgoingHandle<P>>> {
+        match self.address_book.confirm(addr, peer_id, peer_public_key)? {
+            Ok((public_key, previous_addr)) => {
+                debug!(%addr, %public_key, "verified gossiped address");
+                if let Some(previous_addr) = previous_addr {
+                    if !self.known_addresses.contains(&previous_addr) {
+                        // Making an address forgettable never requires dialing.
+                        let _ = self
+                            .outgoing_manager
+                            .set_unforgettable(previous_addr, false, now);
+                    }
+                }
+                if self.retained_validators.contains_key(&public_key) {
+                    self.outgoing_manager.set_unforgettable(addr, true, now)
+                } else {
+                    None
+                }
+            }
+            Err(reason) => {
+                debug!(%addr, reason = reason.as_label(), "rejecting gossiped address");
+                self.count_rejected_addresses(reason, 1);
+                None
+            }
+        }
+    }
+
+    /// Counts gossiped addresses rejected for the given reason.
+    fn count_rejected_addresses(&self, reason: RejectionReason, count: usize) {
+        self.net_metrics
+            .rejected_address_advertisements
+            .with_label_values(&[reason.as_label()])
+            .inc_by(count as u64);
+    }
+
     /// Processes a set of `DialRequest`s, updating the component and emitting needed effects.
     fn process_dial_requests<T>(&mut self, requests: T) -> Effects<Event<P>>
     where
@@ -1004,6 +1275,40 @@ where
         ret
     }
 
+    /// Returns the rate limits currently applied to non-validator peers.
+    pub(crate) fn rate_limits(&self) -> RateLimits {
+        RateLimits {
+            max_outgoing_byte_rate_non_validators: self.outgoing_limiter.resources_per_second(),
+            max_incoming_message_rate_non_validators: self.incoming_limiter.resources_per_second(),
+        }
+    }
+
+    /// Changes the rate limits applied to non-validator peers, returning those in effect
+    /// afterwards.  Limits which are `None` are left unchanged.
+    fn set_rate_limits(
+        &self,
+        max_outgoing_byte_rate_non_validators: Option<u32>,
+        max_incoming_message_rate_non_validators: Option<u32>,
+    ) -> RateLimits {
+        if let Some(byte_rate) = max_outgoing_byte_rate_non_validators {
+            let previous = self.outgoing_limiter.set_resources_per_second(byte_rate);
+            info!(
+                previous,
+                current = byte_rate,
+                "changed outgoing byte rate limit"
+            );
+        }
+        if let Some(message_rate) = max_incoming_message_rate_non_validators {
+            let previous = self.incoming_limiter.set_resources_per_second(message_rate);
+            info!(
+                previous,
+                current = message_rate,
+                "changed incoming message rate limit"
+            );
+        }
+        self.rate_limits()
+    }
+
     pub(crate) fn fully_connected_peers_random(
         &self,
         rng: &mut NodeRng,
@@ -1164,9 +1469,10 @@ where
                 | Event::NetworkRequest { .. }
                 | Event::NetworkInfoRequest { .. }
                 | Event::GossipOurAddress
-                | Event::PeerAddressReceived(_)
+                | Event::PeerAddressReceived { .. }
                 | Event::SweepOutgoing
-                | Event::BlocklistAnnouncement(_) => {
+                | Event::BlocklistAnnouncement(_)
+                | Event::UpcomingValidatorsAnnouncement(_) => {
                     warn!(
                         ?event,
                         name = <Self as Component<REv>>::name(self),
@@ -1215,17 +1521,41 @@ where
                     NetworkInfoRequest::Insight { responder } => responder
                         .respond(NetworkInsights::collect_from_component(self))
                         .ignore(),
+                    NetworkInfoRequest::SetRateLimits {
+                        max_outgoing_byte_rate_non_validators,
+                        max_incoming_message_rate_non_validators,
+                        responder,
+                    } => responder
+                        .respond(self.set_rate_limits(
+                            max_outgoing_byte_rate_non_validators,
+                            max_incoming_message_rate_non_validators,
+                        ))
+                        .ignore(),
                 },
                 Event::GossipOurAddress => {
-                    let our_address = GossipedAddress::new(
-                        self.context
-                            .public_addr()
-                            .expect("component not initialized properly"),
-                    );
-
-                    let mut effects = effect_builder
-                        .begin_gossip(our_address, Source::Ourself, our_address.gossip_target())
-                        .ignore();
+                    let mut effects = match self.context.node_key_pair() {
+                        Some(key_pair) => {
+                            let expiry = Timestamp::now()
+                                + self.cfg.gossip_interval * ADDRESS_RECORD_LIFETIME_INTERVALS;
+                            let our_address = GossipedAddress::new(
+                                self.context
+                                    .public_addr()
+                                    .expect("component not initialized properly"),
+                                self.context.our_id(),
+                                self.context.chain_info().protocol_version,
+                                expiry,
+                                key_pair,
+                            );
+                            let gossip_target = our_address.gossip_target();
+                            effect_builder
+                                .begin_gossip(our_address, Source::Ourself, gossip_target)
+                                .ignore()
+                        }
+                        None => {
+                            debug!("no node key pair to sign our address with, not gossiping it");
+                            Effects::new()
+                        }
+                    };
                     effects.extend(
                         effect_builder
                             .set_timeout(self.cfg.gossip_interval.into())
@@ -1233,16 +1563,91 @@ where
                     );
                     effects
                 }
-                Event::PeerAddressReceived(gossiped_address) => {
-                    let requests = self.outgoing_manager.learn_addr(
-                        gossiped_address.into(),
-                        false,
-                        Instant::now(),
+                Event::PeerAddressReceived {
+                    gossiped_address,
+                    sender,
+                } => {
+                    if let Err(error) = gossiped_address.verify(Timestamp::now()) {
+                        debug!(
+                            %gossiped_address,
+                            %sender,
+                            %error,
+                            "ignoring invalid gossiped address"
+                        );
+                        self.count_rejected_addresses(RejectionReason::Invalid, 1);
+                        return Effects::new();
+                    }
+                    trace!(
+                        address = %gossiped_address.address(),
+                        node_id = %gossiped_address.node_id(),
+                        public_key = %gossiped_address.public_key(),
+                        protocol_version = %gossiped_address.protocol_version(),
+                        %sender,
+                        "learned gossiped address"
                     );
-                    self.process_dial_requests(requests)
+                    let public_key = gossiped_address.public_key().clone();
+                    let addr = gossiped_address.address();
+                    let is_ours = self
+                        .context
+                        .node_key_pair()
+                        .map_or(false, |key_pair| *key_pair.public_key() == public_key);
+                    if is_ours || self.address_book.get(&public_key) == Some(addr) {
+                        // Nothing new to verify.
+                        return Effects::new();
+                    }
+
+                    let now = Instant::now();
+                    if let Err(reason) = self.address_book.add_pending(
+                        public_key,
+                        gossiped_address.node_id(),
+                        addr,
+                        *sender,
+                        now,
+                    ) {
+                        debug!(
+                            %gossiped_address,
+                            %sender,
+                            reason = reason.as_label(),
+                            "rejecting gossiped address"
+                        );
+                        self.count_rejected_addresses(reason, 1);
+                        return Effects::new();
+                    }
+
+                    // The address is verified by connecting to it, unless we are connected already.
+                    let reached_peer = match self
+                        .outgoing_manager
+                        .outgoing
+                        .get(&addr)
+                        .map(|outgoing| &outgoing.state)
+                    {
+                        Some(OutgoingState::Connected {
+                            peer_id, handle, ..
+                        }) => Some((Some(*peer_id), handle.peer_consensus_public_key.clone())),
+                        // We are the node at the address, which thus cannot hold the record's key.
+                        Some(OutgoingState::Loopback) => Some((None, None)),
+                        _ => None,
+                    };
+                    let request = match reached_peer {
+                        Some((peer_id, peer_public_key)) => self.complete_liveness_check(
+                            addr,
+                            peer_id,
+                            peer_public_key.as_ref(),
+                            now,
+                        ),
+                        None => self.outgoing_manager.learn_addr(addr, false, now),
+                    };
+                    self.process_dial_requests(request)
                 }
                 Event::SweepOutgoing => {
                     let now = Instant::now();
+                    self.release_departed_validators(now);
+                    let expired = self
+                        .address_book
+                        .expire_pending(now, self.cfg.max_addr_pending_time.into());
+                    if expired > 0 {
+                        self.count_rejected_addresses(RejectionReason::Unreachable, expired);
+                    }
                     let requests = self.outgoing_manager.perform_housekeeping(rng, now);
 
                     let mut effects = self.process_dial_requests(requests);
@@ -1277,6 +1682,9 @@ where
                         }
                     }
                 },
+                Event::UpcomingValidatorsAnnouncement(announcement) => {
+                    self.handle_upcoming_validators(announcement)
+                }
             },
         }
     }
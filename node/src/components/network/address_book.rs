@@ -0,0 +1,332 @@
+//! The book of the listening addresses of other nodes, learned from gossiped endpoint records.
+//!
+//! An address relayed by a peer only enters the book once an outgoing connection to it reached a
+//! node proving, during the handshake, that it holds the record's key and node ID.  Until then the
+//! address is pending, and each peer may only have a limited number of pending addresses it
+//! relayed, so that a single peer cannot flood the book with addresses of nodes it controls or
+//! which don't exist. Every address is tagged with the peer which relayed it.
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
+
+use datasize::DataSize;
+use serde::Serialize;
+
+use casper_types::PublicKey;
+
+use crate::types::NodeId;
+
+/// An address in the book.
+#[derive(Clone, Copy, DataSize, Debug, Eq, PartialEq, Serialize)]
+pub(super) struct AddressBookEntry {
+    /// The listening address.
+    pub(super) addr: SocketAddr,
+    /// The peer which relayed the endpoint record holding the address.
+    pub(super) origin: NodeId,
+}
+
+/// An address awaiting its liveness check.
+#[derive(Clone, DataSize, Debug)]
+struct PendingAddress {
+    /// The public key of the endpoint record holding the address.
+    public_key: PublicKey,
+    /// The node ID of the endpoint record holding the address.
+    node_id: NodeId,
+    /// The peer which relayed the endpoint record.
+    origin: NodeId,
+    /// The moment the liveness check started.
+    since: Instant,
+}
+
+/// The reason an advertised address was rejected.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(super) enum RejectionReason {
+    /// The endpoint record has expired or its signature is invalid.
+    Invalid,
+    /// The relaying peer already has the maximum number of pending addresses.
+    PeerCapExceeded,
+    /// No connection to the address could be established in time.
+    Unreachable,
+    /// The node reached at the address does not hold the endpoint record's key.
+    KeyMismatch,
+    /// The node reached at the address does not have the endpoint record's node ID.
+    NodeIdMismatch,
+}
+
+impl RejectionReason {
+    /// Returns the label of the reason in the metrics.
+    pub(super) fn as_label(self) -> &'static str {
+        match self {
+            RejectionReason::Invalid => "invalid",
+            RejectionReason::PeerCapExceeded => "peer_cap_exceeded",
+            RejectionReason::Unreachable => "unreachable",
+            RejectionReason::KeyMismatch => "key_mismatch",
+            RejectionReason::NodeIdMismatch => "node_id_mismatch",
+        }
+    }
+}
+
+/// The verified listening addresses of other nodes, and the addresses awaiting verification.
+#[derive(DataSize, Debug)]
+pub(super) struct AddressBook {
+    /// The verified listening address of each node, by its public key.
+    entries: HashMap<PublicKey, AddressBookEntry>,
+    /// The addresses awaiting their liveness check.
+    pending: HashMap<SocketAddr, PendingAddress>,
+    /// The maximum number of pending addresses relayed by a single peer.
+    max_pending_per_peer: usize,
+}
+
+impl AddressBook {
+    /// Creates an empty address book.
+    pub(super) fn new(max_pending_per_peer: usize) -> Self {
+        AddressBook {
+            entries: HashMap::new(),
+            pending: HashMap::new(),
+            max_pending_per_peer,
+        }
+    }
+
+    /// Returns the verified address of the node with the given public key, if any.
+    pub(super) fn get(&self, public_key: &PublicKey) -> Option<SocketAddr> {
+        self.entries.get(public_key).map(|entry| entry.addr)
+    }
+
+    /// Iterates over the verified addresses, by public key.
+    pub(super) fn entries(&self) -> impl Iterator<Item = (&PublicKey, &AddressBookEntry)> {
+        self.entries.iter()
+    }
+
+    /// Returns the number of addresses awaiting their liveness check.
+    pub(super) fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Starts the liveness check of `addr`, advertised for `public_key` and `node_id` and relayed
+    /// by `origin`.
+    ///
+    /// Does nothing if a check of `addr` is pending already.
+    pub(super) fn add_pending(
+        &mut self,
+        public_key: PublicKey,
+        node_id: NodeId,
+        addr: SocketAddr,
+        origin: NodeId,
+        now: Instant,
+    ) -> Result<(), RejectionReason> {
+        if self.pending.contains_key(&addr) {
+            return Ok(());
+        }
+        let pending_from_origin = self
+            .pending
+            .values()
+            .filter(|pending| pending.origin == origin)
+            .count();
+        if pending_from_origin >= self.max_pending_per_peer {
+            return Err(RejectionReason::PeerCapExceeded);
+        }
+        self.pending.insert(
+            addr,
+            PendingAddress {
+                public_key,
+                node_id,
+                origin,
+                since: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Completes the liveness check of `addr`, at which a connection to the node `peer_id` holding
+    /// `peer_public_key` was established.
+    ///
+    /// On success, returns the public key for which `addr` was recorded, along with the different
+    /// address previously recorded for it, if any.  Returns `None` if no check of `addr` is
+    /// pending.
+    pub(super) fn confirm(
+        &mut self,
+        addr: SocketAddr,
+        peer_id: Option<NodeId>,
+        peer_public_key: Option<&PublicKey>,
+    ) -> Option<Result<(PublicKey, Option<SocketAddr>), RejectionReason>> {
+        let pending = self.pending.remove(&addr)?;
+        if peer_id != Some(pending.node_id) {
+            return Some(Err(RejectionReason::NodeIdMismatch));
+        }
+        if peer_public_key != Some(&pending.public_key) {
+            return Some(Err(RejectionReason::KeyMismatch));
+        }
+        let entry = AddressBookEntry {
+            addr,
+            origin: pending.origin,
+        };
+        let previous_addr = self
+            .entries
+            .insert(pending.public_key.clone(), entry)
+            .map(|previous_entry| previous_entry.addr)
+            .filter(|previous_addr| *previous_addr != addr);
+        Some(Ok((pending.public_key, previous_addr)))
+    }
+
+    /// Fails the liveness check of `addr`, returning `true` if one was pending.
+    pub(super) fn fail(&mut self, addr: SocketAddr) -> bool {
+        self.pending.remove(&addr).is_some()
+    }
+
+    /// Fails the liveness checks which didn't complete within `timeout`, returning their number.
+    pub(super) fn expire_pending(&mut self, now: Instant, timeout: Duration) -> usize {
+        let pending_count = self.pending.len();
+        self.pending
+            .retain(|_, pending| now.saturating_duration_since(pending.since) < timeout);
+        pending_count - self.pending.len()
+    }
+
+    /// Removes the verified addresses for which `keep` returns `false`.
+    pub(super) fn retain<F: FnMut(&PublicKey, SocketAddr) -> bool>(&mut self, mut keep: F) {
+        self.entries
+            .retain(|public_key, entry| keep(public_key, entry.addr));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_only_record_addresses_reached_at_their_key() {
+        let mut rng = TestRng::new();
+        let mut book = AddressBook::new(10);
+        let public_key = PublicKey::random(&mut rng);
+        let other_public_key = PublicKey::random(&mut rng);
+        let node_id = NodeId::random(&mut rng);
+        let origin = NodeId::random(&mut rng);
+        let addr: SocketAddr = "127.0.0.1:34553".parse().unwrap();
+        let now = Instant::now();
+
+        book.add_pending(public_key.clone(), node_id, addr, origin, now)
+            .unwrap();
+        assert_eq!(
+            book.confirm(addr, Some(node_id), Some(&other_public_key)),
+            Some(Err(RejectionReason::KeyMismatch))
+        );
+        assert_eq!(book.get(&public_key), None);
+
+        book.add_pending(public_key.clone(), node_id, addr, origin, now)
+            .unwrap();
+        assert_eq!(
+            book.confirm(addr, Some(node_id), Some(&public_key)),
+            Some(Ok((public_key.clone(), None)))
+        );
+        assert_eq!(
+            book.entries.get(&public_key),
+            Some(&AddressBookEntry { addr, origin })
+        );
+        assert_eq!(book.confirm(addr, Some(node_id), Some(&public_key)), None);
+
+        // A newly verified address replaces the previous one.
+        let new_addr: SocketAddr = "127.0.0.1:34554".parse().unwrap();
+        book.add_pending(public_key.clone(), node_id, new_addr, origin, now)
+            .unwrap();
+        assert_eq!(
+            book.confirm(new_addr, Some(node_id), Some(&public_key)),
+            Some(Ok((public_key.clone(), Some(addr))))
+        );
+        assert_eq!(book.get(&public_key), Some(new_addr));
+    }
+
+    #[test]
+    fn should_reject_addresses_reached_at_another_node_id() {
+        let mut rng = TestRng::new();
+        let mut book = AddressBook::new(10);
+        let public_key = PublicKey::random(&mut rng);
+        let node_id = NodeId::random(&mut rng);
+        let origin = NodeId::random(&mut rng);
+        let addr: SocketAddr = "127.0.0.1:34553".parse().unwrap();
+
+        // A record signed with the node's key, but naming another node's ID, is not confirmed by
+        // reaching the node holding the key.
+        book.add_pending(public_key.clone(), node_id, addr, origin, Instant::now())
+            .unwrap();
+        assert_eq!(
+            book.confirm(addr, Some(NodeId::random(&mut rng)), Some(&public_key)),
+            Some(Err(RejectionReason::NodeIdMismatch))
+        );
+        assert_eq!(book.get(&public_key), None);
+    }
+
+    #[test]
+    fn should_cap_pending_addresses_per_peer() {
+        let mut rng = TestRng::new();
+        let mut book = AddressBook::new(2);
+        let origin = NodeId::random(&mut rng);
+        let other_origin = NodeId::random(&mut rng);
+        let now = Instant::now();
+        let addr = |port: u16| SocketAddr::from(([127, 0, 0, 1], port));
+
+        let mut add_pending = |book: &mut AddressBook, port: u16, origin: NodeId| {
+            let public_key = PublicKey::random(&mut rng);
+            let node_id = NodeId::random(&mut rng);
+            book.add_pending(public_key, node_id, addr(port), origin, now)
+        };
+
+        for port in 1..=2 {
+            add_pending(&mut book, port, origin).unwrap();
+        }
+        assert_eq!(
+            add_pending(&mut book, 3, origin),
+            Err(RejectionReason::PeerCapExceeded)
+        );
+        add_pending(&mut book, 3, other_origin).unwrap();
+
+        // Failed checks free up room for the relaying peer.
+        assert!(book.fail(addr(1)));
+        assert!(!book.fail(addr(1)));
+        add_pending(&mut book, 4, origin).unwrap();
+    }
+
+    #[test]
+    fn should_expire_pending_addresses() {
+        let mut rng = TestRng::new();
+        let mut book = AddressBook::new(10);
+        let origin = NodeId::random(&mut rng);
+        let timeout = Duration::from_secs(60);
+        let start = Instant::now();
+        let addr = |port: u16| SocketAddr::from(([127, 0, 0, 1], port));
+
+        book.add_pending(
+            PublicKey::random(&mut rng),
+            NodeId::random(&mut rng),
+            addr(1),
+            origin,
+            start,
+        )
+        .unwrap();
+        book.add_pending(
+            PublicKey::random(&mut rng),
+            NodeId::random(&mut rng),
+            addr(2),
+            origin,
+            start + Duration::from_secs(30),
+        )
+        .unwrap();
+
+        assert_eq!(
+            book.expire_pending(start + Duration::from_secs(59), timeout),
+            0
+        );
+        assert_eq!(
+            book.expire_pending(start + Duration::from_secs(60), timeout),
+            1
+        );
+        assert_eq!(book.confirm(addr(1), None, None), None);
+        assert_eq!(
+            book.confirm(addr(2), None, None),
+            Some(Err(RejectionReason::NodeIdMismatch))
+        );
+    }
+}
@@ -6,7 +6,7 @@
 use std::net::SocketAddr;
 
 use casper_hashing::Digest;
-use casper_types::ProtocolVersion;
+use casper_types::{EraId, ProtocolVersion};
 use datasize::DataSize;
 
 use super::{
@@ -14,7 +14,10 @@ use super::{
     message::{ConsensusCertificate, NodeKeyPair},
     Message,
 };
-use crate::types::Chainspec;
+use crate::types::{
+    chainspec::{NetworkFeatures, NetworkFeaturesConfig},
+    Chainspec,
+};
 
 /// Data retained from the chainspec by the networking component.
 ///
@@ -30,6 +33,10 @@ pub(crate) struct ChainInfo {
     pub(super) protocol_version: ProtocolVersion,
     /// The hash of the chainspec.
     pub(super) chainspec_hash: Digest,
+    /// The era from which the protocol version is active.
+    pub(super) activation_era: EraId,
+    /// The eras from which the network features are used.
+    pub(super) network_features: NetworkFeaturesConfig,
 }
 
 impl ChainInfo {
@@ -42,6 +49,8 @@ impl ChainInfo {
             maximum_net_message_size: 24 * 1024 * 1024, // Hardcoded at 24M.
             protocol_version: ProtocolVersion::V1_0_0,
             chainspec_hash: Digest::hash(format!("{}-chainspec", network_name)),
+            activation_era: EraId::new(0),
+            network_features: NetworkFeaturesConfig::default(),
         }
     }
 
@@ -61,6 +70,9 @@ impl ChainInfo {
                 .map(|key_pair| ConsensusCertificate::create(connection_id, key_pair)),
             is_syncing,
             chainspec_hash: Some(self.chainspec_hash),
+            // Every node running this version is able to decompress payloads.
+            supports_compression: true,
+            network_features: NetworkFeatures::SUPPORTED,
         }
     }
 }
@@ -72,6 +84,8 @@ impl From<&Chainspec> for ChainInfo {
             maximum_net_message_size: chainspec.network_config.maximum_net_message_size,
             protocol_version: chainspec.protocol_version(),
             chainspec_hash: chainspec.hash(),
+            activation_era: chainspec.protocol_config.activation_point.era_id(),
+            network_features: chainspec.network_features_config,
         }
     }
 }
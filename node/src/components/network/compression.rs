@@ -0,0 +1,101 @@
+//! Compression of large payloads sent to peers.
+//!
+//! Payloads are compressed using raw deflate. Since the decompressed size of a payload received
+//! from a peer is not known in advance, decompression is always bounded by a maximum size, so that
+//! a small malicious payload cannot be inflated to exhaust our memory.
+
+use std::io::{self, Read, Write};
+
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use thiserror::Error;
+
+/// Error decompressing a payload received from a peer.
+#[derive(Debug, Error)]
+pub(crate) enum DecompressionError {
+    /// The compressed data is malformed.
+    #[error("malformed compressed data: {0}")]
+    Malformed(#[source] io::Error),
+    /// The decompressed data exceeds the maximum allowed size.
+    #[error("decompressed data exceeds the maximum size of {max_size} bytes")]
+    TooLarge {
+        /// The maximum allowed size.
+        max_size: usize,
+    },
+}
+
+/// Compresses `data`.
+pub(crate) fn compress(data: &[u8]) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::fast());
+    // Writing to and finishing an in-memory encoder cannot fail.
+    encoder
+        .write_all(data)
+        .expect("compressing into a vec should not fail");
+    encoder
+        .finish()
+        .expect("compressing into a vec should not fail")
+}
+
+/// Decompresses `data`, failing if the decompressed data would exceed `max_size` bytes.
+pub(crate) fn decompress(data: &[u8], max_size: usize) -> Result<Vec<u8>, DecompressionError> {
+    let mut decompressed = Vec::new();
+    // Reading one byte beyond the limit lets us distinguish data of exactly `max_size` bytes from
+    // data exceeding it, without ever inflating more than that.
+    DeflateDecoder::new(data)
+        .take(max_size as u64 + 1)
+        .read_to_end(&mut decompressed)
+        .map_err(DecompressionError::Malformed)?;
+
+    if decompressed.len() > max_size {
+        return Err(DecompressionError::TooLarge { max_size });
+    }
+
+    Ok(decompressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::{collection::vec, prelude::any, proptest};
+
+    use super::*;
+
+    #[test]
+    fn roundtrip_compression() {
+        let data: Vec<u8> = (0..100_000u32).map(|i| (i % 7) as u8).collect();
+
+        let compressed = compress(&data);
+        assert!(compressed.len() < data.len());
+        assert_eq!(
+            decompress(&compressed, data.len()).expect("should decompress"),
+            data
+        );
+    }
+
+    #[test]
+    fn rejects_oversized_payloads() {
+        let bomb = compress(&vec![0; 10_000_000]);
+        assert!(bomb.len() < 20_000);
+
+        assert!(matches!(
+            decompress(&bomb, 9_999_999),
+            Err(DecompressionError::TooLarge {
+                max_size: 9_999_999
+            })
+        ));
+        assert!(decompress(&bomb, 10_000_000).is_ok());
+    }
+
+    proptest! {
+        #[test]
+        fn fuzz_decompression(input in vec(any::<u8>(), 0..4096), max_size in 0usize..65536) {
+            if let Ok(decompressed) = decompress(&input, max_size) {
+                assert!(decompressed.len() <= max_size);
+            }
+        }
+
+        #[test]
+        fn fuzz_roundtrip(input in vec(any::<u8>(), 0..4096)) {
+            let compressed = compress(&input);
+            assert_eq!(decompress(&compressed, input.len()).expect("should decompress"), input);
+        }
+    }
+}
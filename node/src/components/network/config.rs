@@ -6,7 +6,7 @@ use casper_types::{ProtocolVersion, TimeDiff};
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
-use super::EstimatorWeights;
+use super::{EstimatorWeights, MessageKind};
 
 /// Default binding address.
 ///
@@ -32,6 +32,15 @@ const DEFAULT_MAX_ADDR_PENDING_TIME: TimeDiff = TimeDiff::from_seconds(60);
 /// Default timeout during which the handshake needs to be completed.
 const DEFAULT_HANDSHAKE_TIMEOUT: TimeDiff = TimeDiff::from_seconds(20);
 
+/// Default time for which connections to validators leaving the validator set are retained.
+const DEFAULT_VALIDATOR_RETENTION_GRACE_PERIOD: TimeDiff = TimeDiff::from_seconds(600);
+
+/// Default maximum number of gossiped addresses relayed by a single peer awaiting verification.
+const DEFAULT_MAX_PENDING_ADDRESSES_PER_PEER: u16 = 64;
+
+/// Default minimum size in bytes of a fetched item for its response to be compressed.
+const DEFAULT_COMPRESSION_THRESHOLD: u32 = 16 * 1024;
+
 impl Default for Config {
     fn default() -> Self {
         Config {
@@ -52,6 +61,10 @@ impl Default for Config {
             tarpit_chance: 0.2,
             max_in_flight_demands: 50,
             blocklist_retain_duration: TimeDiff::from_seconds(600),
+            validator_retention_grace_period: DEFAULT_VALIDATOR_RETENTION_GRACE_PERIOD,
+            compressed_message_kinds: Vec::new(),
+            compression_threshold: DEFAULT_COMPRESSION_THRESHOLD,
+            max_pending_addresses_per_peer: DEFAULT_MAX_PENDING_ADDRESSES_PER_PEER,
             identity: None,
         }
     }
@@ -111,6 +124,18 @@ pub struct Config {
     pub max_in_flight_demands: u32,
     /// Duration peers are kept on the block list, before being redeemed.
     pub blocklist_retain_duration: TimeDiff,
+    /// Duration, counted from the start of the first era they are not a validator in, for which we
+    /// keep reconnecting to former validators before treating them like any other peer.
+    pub validator_retention_grace_period: TimeDiff,
+    /// Kinds of messages whose payloads are compressed when sent to peers supporting it.
+    ///
+    /// Only responses to requests for items have a compressed form.  Empty to never compress.
+    pub compressed_message_kinds: Vec<MessageKind>,
+    /// Minimum size in bytes of a fetched item for its response to be compressed.
+    pub compression_threshold: u32,
+    /// Maximum number of gossiped addresses relayed by a single peer which may await verification
+    /// at the same time.  Further addresses relayed by that peer are rejected.
+    pub max_pending_addresses_per_peer: u16,
     /// Network identity configuration option.
     ///
     /// An identity will be automatically generated when starting up a node if this option is
@@ -6,11 +6,12 @@ use serde::Serialize;
 use thiserror::Error;
 
 use casper_hashing::Digest;
-use casper_types::{crypto, ProtocolVersion};
+use casper_types::{crypto, ProtocolVersion, TimeDiff};
 
 use crate::{
     tls::{LoadCertError, ValidationError},
-    utils::ResolveAddressError,
+    types::{chainspec::NetworkFeatures, NodeId},
+    utils::{display_error, ResolveAddressError},
 };
 
 pub(super) type Result<T> = result::Result<T, Error>;
@@ -71,6 +72,13 @@ pub enum Error {
         #[from]
         LoadCertError,
     ),
+    /// The network self-test failed.
+    #[error("network self-test failed: {0}")]
+    SelfTest(
+        #[serde(skip_serializing)]
+        #[from]
+        SelfTestError,
+    ),
 }
 
 // Manual implementation for `DataSize` - the type contains too many FFI variants that are hard to
@@ -155,6 +163,9 @@ pub enum ConnectionError {
     /// Peer is using a different chainspec.
     #[error("peer is using a different chainspec, hash: {0}")]
     WrongChainspecHash(Digest),
+    /// Peer does not support all network features in use in the current era.
+    #[error("peer does not support network features in use: {0}")]
+    MissingNetworkFeatures(NetworkFeatures),
     /// Peer should have included the chainspec hash in the handshake message,
     /// but didn't.
     #[error("peer did not include chainspec hash in the handshake when it was required")]
@@ -199,6 +210,49 @@ pub enum ConnectionError {
     FailedToReuniteHandshakeSinkAndStream,
 }
 
+/// An error in the network self-test, which connects to ourselves over the loopback interface.
+#[derive(Debug, Error)]
+pub enum SelfTestError {
+    /// Failed to start the runtime running the self-test.
+    #[error("failed to start runtime: {0}")]
+    Runtime(io::Error),
+    /// Failed to bind a listener on the loopback interface.
+    #[error("failed to bind loopback listener: {0}")]
+    Bind(io::Error),
+    /// Failed to accept the loopback connection.
+    #[error("failed to accept loopback connection: {0}")]
+    Accept(io::Error),
+    /// Failed to set up the incoming side of the connection.
+    #[error("failed to set up incoming connection: {}", display_error(.0))]
+    Incoming(ConnectionError),
+    /// Failed to set up the outgoing side of the connection.
+    #[error("failed to set up outgoing connection: {}", display_error(.0))]
+    Outgoing(ConnectionError),
+    /// The connection did not identify us as the peer.
+    #[error("loopback peer identified as {actual} instead of {expected}")]
+    WrongPeerId {
+        /// Our own node ID.
+        expected: NodeId,
+        /// The node ID the connection identified the peer as.
+        actual: NodeId,
+    },
+    /// Failed to send a frame over the connection.
+    #[error("failed to send frame: {0}")]
+    Send(io::Error),
+    /// Failed to receive a frame over the connection.
+    #[error("failed to receive frame: {0}")]
+    Receive(io::Error),
+    /// The connection was closed before a frame was received.
+    #[error("connection closed before a frame was received")]
+    ConnectionClosed,
+    /// The frame received differs from the one sent.
+    #[error("received frame differs from the one sent")]
+    CorruptedFrame,
+    /// The self-test did not complete in time.
+    #[error("timed out after {0}")]
+    Timeout(TimeDiff),
+}
+
 /// IO operation that can time out or close.
 #[derive(Debug, Error)]
 pub enum IoError<E>
@@ -16,7 +16,7 @@ use casper_types::PublicKey;
 use super::{error::ConnectionError, FullTransport, GossipedAddress, Message, NodeId};
 use crate::{
     effect::{
-        announcements::PeerBehaviorAnnouncement,
+        announcements::{PeerBehaviorAnnouncement, UpcomingValidatorsAnnouncement},
         requests::{NetworkInfoRequest, NetworkRequest},
     },
     protocol::Message as ProtocolMessage,
@@ -86,7 +86,10 @@ pub(crate) enum Event<P> {
     GossipOurAddress,
 
     /// We received a peer's public listening address via gossip.
-    PeerAddressReceived(GossipedAddress),
+    PeerAddressReceived {
+        gossiped_address: Box<GossipedAddress>,
+        sender: Box<NodeId>,
+    },
 
     /// Housekeeping for the outgoing manager.
     SweepOutgoing,
@@ -94,6 +97,10 @@ pub(crate) enum Event<P> {
     /// Blocklist announcement.
     #[from]
     BlocklistAnnouncement(PeerBehaviorAnnouncement),
+
+    /// The validators of an upcoming era have become known.
+    #[from]
+    UpcomingValidatorsAnnouncement(UpcomingValidatorsAnnouncement),
 }
 
 impl From<NetworkRequest<ProtocolMessage>> for Event<ProtocolMessage> {
@@ -132,8 +139,15 @@ impl<P: Display> Display for Event<P> {
             Event::NetworkRequest { req } => write!(f, "request: {}", req),
             Event::NetworkInfoRequest { req } => write!(f, "request: {}", req),
             Event::GossipOurAddress => write!(f, "gossip our address"),
-            Event::PeerAddressReceived(gossiped_address) => {
-                write!(f, "received gossiped peer address {}", gossiped_address)
+            Event::PeerAddressReceived {
+                gossiped_address,
+                sender,
+            } => {
+                write!(
+                    f,
+                    "received gossiped peer address {} from {}",
+                    gossiped_address, sender
+                )
             }
             Event::BlocklistAnnouncement(ann) => {
                 write!(f, "handling blocklist announcement: {}", ann)
@@ -141,6 +155,9 @@ impl<P: Display> Display for Event<P> {
             Event::SweepOutgoing => {
                 write!(f, "sweep outgoing connections")
             }
+            Event::UpcomingValidatorsAnnouncement(ann) => {
+                write!(f, "handling upcoming validators announcement: {}", ann)
+            }
         }
     }
 }
@@ -251,6 +268,8 @@ pub(crate) enum OutgoingConnection<P> {
         sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
         /// Holds the information whether the remote node is syncing.
         is_syncing: bool,
+        /// Whether the remote node can decompress compressed payloads.
+        supports_compression: bool,
     },
 }
 
@@ -272,6 +291,7 @@ impl<P> Display for OutgoingConnection<P> {
                 peer_consensus_public_key,
                 sink: _,
                 is_syncing,
+                supports_compression: _,
             } => {
                 write!(
                     f,
@@ -5,27 +5,129 @@ use std::{
 
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
+use casper_types::{crypto, ProtocolVersion, PublicKey, Signature, Timestamp};
+
+use super::message::NodeKeyPair;
 use crate::{
     components::gossiper::{GossipItem, SmallGossipItem},
     effect::GossipTarget,
+    types::NodeId,
 };
 
 /// Used to gossip our public listening address to peers.
-#[derive(
-    Copy, Clone, DataSize, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Debug,
-)]
-pub struct GossipedAddress(SocketAddr);
+///
+/// The address is part of an endpoint record signed with the node's key, which also holds the
+/// node's ID, its protocol version and an expiry time, so that peers can neither spoof other nodes'
+/// endpoints nor replay them indefinitely.
+#[derive(Clone, DataSize, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize, Debug)]
+pub struct GossipedAddress {
+    address: SocketAddr,
+    node_id: NodeId,
+    public_key: Box<PublicKey>,
+    protocol_version: ProtocolVersion,
+    expiry: Timestamp,
+    signature: Box<Signature>,
+}
+
+/// An error verifying a gossiped endpoint record.
+#[derive(Debug, Error)]
+pub(crate) enum InvalidGossipedAddress {
+    /// The record has expired.
+    #[error("endpoint record expired at {expiry}")]
+    Expired { expiry: Timestamp },
+    /// The record's signature is invalid.
+    #[error("invalid endpoint record signature: {0}")]
+    Signature(#[source] crypto::Error),
+}
 
 impl GossipedAddress {
-    pub(super) fn new(address: SocketAddr) -> Self {
-        GossipedAddress(address)
+    /// Creates a new endpoint record for `address` of the node with ID `node_id`, signed with the
+    /// given key pair.
+    pub(super) fn new(
+        address: SocketAddr,
+        node_id: NodeId,
+        protocol_version: ProtocolVersion,
+        expiry: Timestamp,
+        key_pair: &NodeKeyPair,
+    ) -> Self {
+        let public_key = key_pair.public_key().clone();
+        let signature = key_pair.sign(signed_bytes(
+            address,
+            node_id,
+            &public_key,
+            protocol_version,
+            expiry,
+        ));
+        GossipedAddress {
+            address,
+            node_id,
+            public_key: Box::new(public_key),
+            protocol_version,
+            expiry,
+            signature: Box::new(signature),
+        }
     }
+
+    /// The gossiped listening address.
+    pub(crate) fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The ID of the node listening on the address.
+    pub(crate) fn node_id(&self) -> NodeId {
+        self.node_id
+    }
+
+    /// The public key of the node which signed the record.
+    pub(crate) fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// The protocol version of the node which signed the record.
+    pub(crate) fn protocol_version(&self) -> ProtocolVersion {
+        self.protocol_version
+    }
+
+    /// Checks that the record hasn't expired by `now` and is signed by the included public key.
+    pub(crate) fn verify(&self, now: Timestamp) -> Result<(), InvalidGossipedAddress> {
+        if self.expiry < now {
+            return Err(InvalidGossipedAddress::Expired {
+                expiry: self.expiry,
+            });
+        }
+        let bytes = signed_bytes(
+            self.address,
+            self.node_id,
+            &self.public_key,
+            self.protocol_version,
+            self.expiry,
+        );
+        crypto::verify(bytes, &self.signature, &self.public_key)
+            .map_err(InvalidGossipedAddress::Signature)
+    }
+}
+
+/// Returns the bytes covered by an endpoint record's signature.
+fn signed_bytes(
+    address: SocketAddr,
+    node_id: NodeId,
+    public_key: &PublicKey,
+    protocol_version: ProtocolVersion,
+    expiry: Timestamp,
+) -> Vec<u8> {
+    bincode::serialize(&(address, node_id, public_key, protocol_version, expiry))
+        .expect("serializing endpoint record fields should not fail")
 }
 
 impl Display for GossipedAddress {
     fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
-        write!(formatter, "gossiped-address {}", self.0)
+        write!(
+            formatter,
+            "gossiped-address {} of {} {} (protocol version {}, expires {})",
+            self.address, self.node_id, self.public_key, self.protocol_version, self.expiry
+        )
     }
 }
 
@@ -36,7 +138,7 @@ impl GossipItem for GossipedAddress {
     type Id = GossipedAddress;
 
     fn gossip_id(&self) -> Self::Id {
-        *self
+        self.clone()
     }
 
     fn gossip_target(&self) -> GossipTarget {
@@ -48,22 +150,130 @@ impl SmallGossipItem for GossipedAddress {
     fn id_as_item(id: &Self::Id) -> &Self {
         id
     }
+
+    fn is_valid(&self, now: Timestamp) -> bool {
+        self.verify(now).is_ok()
+    }
 }
 
 impl From<GossipedAddress> for SocketAddr {
     fn from(gossiped_address: GossipedAddress) -> Self {
-        gossiped_address.0
+        gossiped_address.address
     }
 }
 
 mod specimen_support {
-    use crate::utils::specimen::{Cache, LargestSpecimen, SizeEstimator};
+    use crate::{
+        tls::KeyFingerprint,
+        types::NodeId,
+        utils::specimen::{Cache, LargestSpecimen, SizeEstimator},
+    };
 
     use super::GossipedAddress;
 
     impl LargestSpecimen for GossipedAddress {
         fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
-            GossipedAddress::new(LargestSpecimen::largest_specimen(estimator, cache))
+            GossipedAddress {
+                address: LargestSpecimen::largest_specimen(estimator, cache),
+                // Node IDs are of fixed size.
+                node_id: NodeId::from([u8::MAX; KeyFingerprint::LENGTH]),
+                public_key: LargestSpecimen::largest_specimen(estimator, cache),
+                protocol_version: LargestSpecimen::largest_specimen(estimator, cache),
+                expiry: LargestSpecimen::largest_specimen(estimator, cache),
+                signature: LargestSpecimen::largest_specimen(estimator, cache),
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use casper_types::{testing::TestRng, SecretKey, TimeDiff};
+
+    use super::*;
+
+    fn key_pair(rng: &mut TestRng) -> NodeKeyPair {
+        let secret_key = SecretKey::random(rng);
+        let public_key = PublicKey::from(&secret_key);
+        NodeKeyPair::new((Arc::new(secret_key), public_key))
+    }
+
+    #[test]
+    fn should_verify_signed_record() {
+        let mut rng = TestRng::new();
+        let now = Timestamp::now();
+        let expiry = now + TimeDiff::from_seconds(60);
+        let record = GossipedAddress::new(
+            "127.0.0.1:34553".parse().unwrap(),
+            NodeId::random(&mut rng),
+            ProtocolVersion::V1_0_0,
+            expiry,
+            &key_pair(&mut rng),
+        );
+        assert!(record.verify(now).is_ok());
+        assert!(matches!(
+            record.verify(expiry + TimeDiff::from_millis(1)),
+            Err(InvalidGossipedAddress::Expired { .. })
+        ));
+    }
+
+    #[test]
+    fn should_reject_spoofed_record() {
+        let mut rng = TestRng::new();
+        let now = Timestamp::now();
+        let mut record = GossipedAddress::new(
+            "127.0.0.1:34553".parse().unwrap(),
+            NodeId::random(&mut rng),
+            ProtocolVersion::V1_0_0,
+            now + TimeDiff::from_seconds(60),
+            &key_pair(&mut rng),
+        );
+
+        // Claiming someone else's key invalidates the signature.
+        record.public_key = key_pair(&mut rng).public_key().clone();
+        assert!(matches!(
+            record.verify(now),
+            Err(InvalidGossipedAddress::Signature(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_tampered_address() {
+        let mut rng = TestRng::new();
+        let now = Timestamp::now();
+        let mut record = GossipedAddress::new(
+            "127.0.0.1:34553".parse().unwrap(),
+            NodeId::random(&mut rng),
+            ProtocolVersion::V1_0_0,
+            now + TimeDiff::from_seconds(60),
+            &key_pair(&mut rng),
+        );
+
+        record.address = "10.0.0.1:34553".parse().unwrap();
+        assert!(matches!(
+            record.verify(now),
+            Err(InvalidGossipedAddress::Signature(_))
+        ));
+    }
+
+    #[test]
+    fn should_reject_tampered_node_id() {
+        let mut rng = TestRng::new();
+        let now = Timestamp::now();
+        let mut record = GossipedAddress::new(
+            "127.0.0.1:34553".parse().unwrap(),
+            NodeId::random(&mut rng),
+            ProtocolVersion::V1_0_0,
+            now + TimeDiff::from_seconds(60),
+            &key_pair(&mut rng),
+        );
+
+        record.node_id = NodeId::random(&mut rng);
+        assert!(matches!(
+            record.verify(now),
+            Err(InvalidGossipedAddress::Signature(_))
+        ));
+    }
+}
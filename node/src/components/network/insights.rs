@@ -22,8 +22,8 @@ use crate::{
 };
 
 use super::{
-    error::ConnectionError, outgoing::OutgoingState, symmetry::ConnectionSymmetry, Network,
-    OutgoingHandle, Payload,
+    address_book::AddressBookEntry, error::ConnectionError, limiter::RateLimits,
+    outgoing::OutgoingState, symmetry::ConnectionSymmetry, Network, OutgoingHandle, Payload,
 };
 
 /// A collection of insights into the active networking component.
@@ -45,10 +45,16 @@ pub(crate) struct NetworkInsights {
     privileged_upcoming_outgoing_nodes: Option<HashSet<PublicKey>>,
     /// The amount of bandwidth allowance currently buffered, ready to be spent.
     unspent_bandwidth_allowance_bytes: Option<i64>,
+    /// The rate limits currently applied to non-validator peers.
+    rate_limits: RateLimits,
     /// Map of outgoing connections, along with their current state.
     outgoing_connections: Vec<(SocketAddr, OutgoingInsight)>,
     /// Map of incoming connections.
     connection_symmetries: Vec<(NodeId, ConnectionSymmetryInsight)>,
+    /// The verified addresses of other nodes, along with the peers which relayed them.
+    address_book: Vec<(PublicKey, AddressBookEntry)>,
+    /// The number of gossiped addresses awaiting their liveness check.
+    pending_addresses: usize,
 }
 
 /// Insight into an outgoing connection.
@@ -317,8 +323,15 @@ impl NetworkInsights {
             unspent_bandwidth_allowance_bytes: net
                 .outgoing_limiter
                 .debug_inspect_unspent_allowance(),
+            rate_limits: net.rate_limits(),
             outgoing_connections,
             connection_symmetries,
+            address_book: net
+                .address_book
+                .entries()
+                .map(|(public_key, entry)| (public_key.clone(), *entry))
+                .collect(),
+            pending_addresses: net.address_book.pending_count(),
         }
     }
 }
@@ -343,6 +356,7 @@ impl Display for NetworkInsights {
             self.net_active_era,
             OptDisplay::new(self.unspent_bandwidth_allowance_bytes, "inactive"),
         )?;
+        writeln!(f, "rate limits: {}", self.rate_limits)?;
         let active = self
             .privileged_active_outgoing_nodes
             .as_ref()
@@ -380,6 +394,13 @@ impl Display for NetworkInsights {
             f.write_str("\n")?;
         }
 
+        f.write_str("address book:\n")?;
+        writeln!(f, "address                  origin          public key")?;
+        for (public_key, entry) in &self.address_book {
+            writeln!(f, "{:23}  {:10}  {}", entry.addr, entry.origin, public_key)?;
+        }
+        writeln!(f, "pending addresses: {}", self.pending_addresses)?;
+
         Ok(())
     }
 }
@@ -5,11 +5,16 @@
 
 use std::{
     collections::{HashMap, HashSet},
-    sync::{Arc, RwLock},
+    fmt::{self, Display, Formatter},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, RwLock,
+    },
     time::{Duration, Instant},
 };
 
 use prometheus::Counter;
+use serde::Serialize;
 use tokio::{runtime::Handle, sync::Mutex, task};
 use tracing::{error, trace, warn};
 
@@ -20,6 +25,25 @@ use crate::types::{NodeId, ValidatorMatrix};
 /// Amount of resource allowed to buffer in `Limiter`.
 const STORED_BUFFER_SECS: Duration = Duration::from_secs(2);
 
+/// The rate limits applied to non-validator peers, 0 meaning unlimited.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub(crate) struct RateLimits {
+    /// Maximum number of bytes per second sent to non-validating peers.
+    pub(crate) max_outgoing_byte_rate_non_validators: u32,
+    /// Maximum number of messages per second received from non-validating peers.
+    pub(crate) max_incoming_message_rate_non_validators: u32,
+}
+
+impl Display for RateLimits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "max_outgoing_byte_rate_non_validators: {}, max_incoming_message_rate_non_validators: {}",
+            self.max_outgoing_byte_rate_non_validators, self.max_incoming_message_rate_non_validators
+        )
+    }
+}
+
 /// A limiter dividing resources into two classes based on their validator status.
 ///
 /// Any consumer of a specific resource is expected to call `create_handle` for every peer and use
@@ -77,6 +101,22 @@ impl Limiter {
         }
     }
 
+    /// Returns the number of resource units allowed for non-validators per second.
+    pub(super) fn resources_per_second(&self) -> u32 {
+        self.data.resources_per_second.load(Ordering::Relaxed)
+    }
+
+    /// Changes the number of resource units allowed for non-validators per second, returning the
+    /// previous value.
+    ///
+    /// Takes effect for all existing handles on their next request.  A value of 0 disables the
+    /// limit.
+    pub(super) fn set_resources_per_second(&self, resources_per_second: u32) -> u32 {
+        self.data
+            .resources_per_second
+            .swap(resources_per_second, Ordering::Relaxed)
+    }
+
     pub(super) fn remove_connected_validator(&self, peer_id: &NodeId) {
         match self.data.connected_validators.write() {
             Ok(mut connected_validators) => {
@@ -139,7 +179,9 @@ impl Limiter {
 #[derive(Debug)]
 struct LimiterData {
     /// Number of resource units to allow for non-validators per second.
-    resources_per_second: u32,
+    ///
+    /// May be changed at runtime.
+    resources_per_second: AtomicU32,
     /// A mapping from node IDs to public keys of validators to which we have an outgoing
     /// connection.
     connected_validators: RwLock<HashMap<NodeId, PublicKey>>,
@@ -166,7 +208,7 @@ impl LimiterData {
     /// Initial resources will be initialized to 0, with the last refill set to the current time.
     fn new(resources_per_second: u32, wait_time_sec: Counter) -> Self {
         LimiterData {
-            resources_per_second,
+            resources_per_second: AtomicU32::new(resources_per_second),
             connected_validators: Default::default(),
             resources: Mutex::new(ResourceData {
                 available: 0,
@@ -227,13 +269,15 @@ impl LimiterHandle {
                 // No limit imposed on validators.
             }
             PeerClass::NonValidator => {
-                if self.data.resources_per_second == 0 {
+                // Read once, so that a concurrent change of the limit cannot lead to a division by
+                // zero below.
+                let resources_per_second = self.data.resources_per_second.load(Ordering::Relaxed);
+                if resources_per_second == 0 {
                     return;
                 }
 
-                let max_stored_resource = ((self.data.resources_per_second as f64)
-                    * STORED_BUFFER_SECS.as_secs_f64())
-                    as u32;
+                let max_stored_resource =
+                    ((resources_per_second as f64) * STORED_BUFFER_SECS.as_secs_f64()) as u32;
 
                 // We are a low-priority sender. Obtain a lock on the resources and wait an
                 // appropriate amount of time to fill them up.
@@ -249,16 +293,14 @@ impl LimiterHandle {
                         // Add appropriate amount of resources, capped at `max_stored_bytes`. We
                         // are still maintaining the lock here to avoid issues with other
                         // low-priority requestors.
-                        resources.available += ((elapsed.as_nanos()
-                            * self.data.resources_per_second as u128)
+                        resources.available += ((elapsed.as_nanos() * resources_per_second as u128)
                             / 1_000_000_000) as i64;
                         resources.available = resources.available.min(max_stored_resource as i64);
 
                         // If we do not have enough resources available, sleep until we do.
                         if resources.available < 0 {
                             let estimated_time_remaining = Duration::from_millis(
-                                (-resources.available) as u64 * 1000
-                                    / self.data.resources_per_second as u64,
+                                (-resources.available) as u64 * 1000 / resources_per_second as u64,
                             );
 
                             // Note: This sleep call is the reason we are using a tokio mutex
@@ -335,6 +377,28 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn limit_can_be_changed_at_runtime() {
+        let mut rng = crate::new_rng();
+
+        // We insert one unrelated active validator to avoid triggering the automatic disabling of
+        // the limiter in case there are no active validators.
+        let validator_matrix =
+            ValidatorMatrix::new_with_validator(Arc::new(SecretKey::random(&mut rng)));
+        let limiter = Limiter::new(1, new_wait_time_sec(), validator_matrix);
+        let handle = limiter.create_handle(NodeId::random(&mut rng), None);
+
+        // The first request puts the limiter into a deficit which would take ~1000 seconds to
+        // recover from at the initial rate.
+        let start = Instant::now();
+        handle.request_allowance(1000).await;
+
+        assert_eq!(limiter.set_resources_per_second(0), 1);
+        assert_eq!(limiter.resources_per_second(), 0);
+        handle.request_allowance(1000).await;
+        assert!(start.elapsed() < SHORT_TIME);
+    }
+
     #[tokio::test]
     async fn active_validator_is_unlimited() {
         let mut rng = crate::new_rng();
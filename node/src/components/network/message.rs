@@ -17,11 +17,13 @@ use casper_hashing::Digest;
 use casper_types::testing::TestRng;
 use casper_types::{crypto, AsymmetricType, ProtocolVersion, PublicKey, SecretKey, Signature};
 
-use super::{counting_format::ConnectionId, health::Nonce, BincodeFormat};
+use super::{
+    compression::DecompressionError, counting_format::ConnectionId, health::Nonce, BincodeFormat,
+};
 use crate::{
     effect::EffectBuilder,
     protocol,
-    types::{Chainspec, NodeId},
+    types::{chainspec::NetworkFeatures, Chainspec, NodeId},
     utils::{
         opt_display::OptDisplay,
         specimen::{Cache, LargestSpecimen, SizeEstimator},
@@ -55,6 +57,12 @@ pub(crate) enum Message<P> {
         /// Hash of the chainspec the node is running.
         #[serde(default)]
         chainspec_hash: Option<Digest>,
+        /// True if the node can decompress compressed payloads.
+        #[serde(default)]
+        supports_compression: bool,
+        /// The network features the node supports.
+        #[serde(default)]
+        network_features: NetworkFeatures,
     },
     /// A ping request.
     Ping {
@@ -90,6 +98,15 @@ impl<P: Payload> Message<P> {
         }
     }
 
+    /// Determines whether or not a message is urgent.
+    #[inline]
+    pub(super) fn is_urgent(&self) -> bool {
+        match self {
+            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => false,
+            Message::Payload(payload) => payload.is_urgent(),
+        }
+    }
+
     /// Returns the incoming resource estimate of the payload.
     #[inline]
     pub(super) fn payload_incoming_resource_estimate(&self, weights: &EstimatorWeights) -> u32 {
@@ -112,6 +129,32 @@ impl<P: Payload> Message<P> {
         }
     }
 
+    /// Returns a copy of the message with its payload compressed, if the payload is worth
+    /// compressing.
+    pub(super) fn compressed(&self, threshold: usize) -> Option<Self> {
+        match self {
+            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => None,
+            Message::Payload(payload) => payload.compressed(threshold).map(Message::Payload),
+        }
+    }
+
+    /// Returns whether or not the payload is compressed.
+    #[inline]
+    pub(super) fn payload_is_compressed(&self) -> bool {
+        match self {
+            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => false,
+            Message::Payload(payload) => payload.is_compressed(),
+        }
+    }
+
+    /// Decompresses the payload, failing if it would exceed `max_size` bytes.
+    pub(super) fn decompressed(self, max_size: usize) -> Result<Self, DecompressionError> {
+        match self {
+            Message::Handshake { .. } | Message::Ping { .. } | Message::Pong { .. } => Ok(self),
+            Message::Payload(payload) => payload.decompressed(max_size).map(Message::Payload),
+        }
+    }
+
     /// Attempts to create a demand-event from this message.
     ///
     /// Succeeds if the outer message contains a payload that can be converted into a demand.
@@ -152,9 +195,14 @@ impl NodeKeyPair {
     }
 
     /// Sign a value using this keypair.
-    fn sign<T: AsRef<[u8]>>(&self, value: T) -> Signature {
+    pub(super) fn sign<T: AsRef<[u8]>>(&self, value: T) -> Signature {
         crypto::sign(value, &self.secret_key, &self.public_key)
     }
+
+    /// The public key of this keypair.
+    pub(super) fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
 }
 
 /// Certificate used to indicate that the peer is a validator using the specified public key.
@@ -293,16 +341,20 @@ impl<P: Display> Display for Message<P> {
                 consensus_certificate,
                 is_syncing,
                 chainspec_hash,
+                supports_compression,
+                network_features,
             } => {
                 write!(
                     f,
-                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}",
+                    "handshake: {}, public addr: {}, protocol_version: {}, consensus_certificate: {}, is_syncing: {}, chainspec_hash: {}, supports_compression: {}, network_features: {}",
                     network_name,
                     public_addr,
                     protocol_version,
                     OptDisplay::new(consensus_certificate.as_ref(), "none"),
                     is_syncing,
-                    OptDisplay::new(chainspec_hash.as_ref(), "none")
+                    OptDisplay::new(chainspec_hash.as_ref(), "none"),
+                    supports_compression,
+                    network_features
                 )
             }
             Message::Ping { nonce } => write!(f, "ping({})", nonce),
@@ -313,12 +365,17 @@ impl<P: Display> Display for Message<P> {
 }
 
 /// A classification system for networking messages.
-#[derive(Copy, Clone, Debug)]
-pub(crate) enum MessageKind {
+///
+/// Also used to configure the handling of each kind of message, such as which kinds are compressed.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, DataSize, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageKind {
     /// Non-payload messages, like handshakes.
     Protocol,
     /// Messages directly related to consensus.
     Consensus,
+    /// Time-critical consensus messages, sent ahead of all other queued messages.
+    UrgentConsensus,
     /// Deploys being gossiped.
     DeployGossip,
     /// Blocks being gossiped.
@@ -342,6 +399,7 @@ impl Display for MessageKind {
         match self {
             MessageKind::Protocol => f.write_str("protocol"),
             MessageKind::Consensus => f.write_str("consensus"),
+            MessageKind::UrgentConsensus => f.write_str("urgent_consensus"),
             MessageKind::DeployGossip => f.write_str("deploy_gossip"),
             MessageKind::BlockGossip => f.write_str("block_gossip"),
             MessageKind::FinalitySignatureGossip => f.write_str("finality_signature_gossip"),
@@ -372,10 +430,34 @@ pub(crate) trait Payload:
         false
     }
 
+    /// Determines if the payload is time-critical, in which case it is sent ahead of all other
+    /// messages queued for the same peer.
+    fn is_urgent(&self) -> bool {
+        false
+    }
+
     /// Indicates a message is not safe to send to a syncing node.
     ///
     /// This functionality should be removed once multiplexed networking lands.
     fn is_unsafe_for_syncing_peers(&self) -> bool;
+
+    /// Returns a compressed version of the payload, if it is of a kind worth compressing, at least
+    /// `threshold` bytes large and compression actually reduces its size.
+    fn compressed(&self, _threshold: usize) -> Option<Self> {
+        None
+    }
+
+    /// Returns whether the payload was compressed by [`Payload::compressed`].
+    fn is_compressed(&self) -> bool {
+        false
+    }
+
+    /// Reverses [`Payload::compressed`], leaving uncompressed payloads untouched.
+    ///
+    /// Fails if the payload is malformed or would decompress to more than `max_size` bytes.
+    fn decompressed(self, _max_size: usize) -> Result<Self, DecompressionError> {
+        Ok(self)
+    }
 }
 
 /// Network message conversion support.
@@ -464,6 +546,8 @@ mod specimen_support {
                         consensus_certificate: LargestSpecimen::largest_specimen(estimator, cache),
                         is_syncing: LargestSpecimen::largest_specimen(estimator, cache),
                         chainspec_hash: LargestSpecimen::largest_specimen(estimator, cache),
+                        supports_compression: LargestSpecimen::largest_specimen(estimator, cache),
+                        network_features: LargestSpecimen::largest_specimen(estimator, cache),
                     },
                     MessageDiscriminants::Ping => Message::Ping {
                         nonce: LargestSpecimen::largest_specimen(estimator, cache),
@@ -612,11 +696,18 @@ mod tests {
 
     use assert_matches::assert_matches;
     use bytes::BytesMut;
-    use casper_types::ProtocolVersion;
+    use casper_types::{EraId, ProtocolVersion};
     use serde::{de::DeserializeOwned, Deserialize, Serialize};
     use tokio_serde::{Deserializer, Serializer};
 
-    use crate::{components::network::message_pack_format::MessagePackFormat, protocol};
+    use crate::{
+        components::{
+            consensus::ConsensusMessage, fetcher::Tag,
+            network::message_pack_format::MessagePackFormat,
+        },
+        protocol,
+        types::{BlockHash, FinalitySignature},
+    };
 
     use super::*;
 
@@ -768,6 +859,8 @@ mod tests {
             consensus_certificate: Some(ConsensusCertificate::random(&mut rng)),
             is_syncing: false,
             chainspec_hash: Some(Digest::hash("example-chainspec")),
+            supports_compression: true,
+            network_features: NetworkFeatures::SUPPORTED,
         };
 
         let legacy_handshake: V1_0_0_Message = roundtrip_message(&modern_handshake);
@@ -802,6 +895,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
+            network_features,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -809,7 +904,9 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
+            assert!(network_features.is_empty());
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -826,6 +923,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
+            network_features,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -834,7 +933,9 @@ mod tests {
             assert_eq!(protocol_version, ProtocolVersion::V1_0_0);
             assert!(consensus_certificate.is_none());
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
+            assert!(network_features.is_empty());
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -851,6 +952,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
+            network_features,
         } = modern_handshake
         {
             assert_eq!(network_name, "example-handshake");
@@ -878,7 +981,9 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
+            assert!(network_features.is_empty());
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -895,6 +1000,8 @@ mod tests {
             consensus_certificate,
             is_syncing,
             chainspec_hash,
+            supports_compression,
+            network_features,
         } = modern_handshake
         {
             assert!(!is_syncing);
@@ -922,7 +1029,9 @@ mod tests {
                 .unwrap()
             );
             assert!(!is_syncing);
-            assert!(chainspec_hash.is_none())
+            assert!(chainspec_hash.is_none());
+            assert!(!supports_compression);
+            assert!(network_features.is_empty());
         } else {
             panic!("did not expect modern handshake to deserialize to anything but")
         }
@@ -952,6 +1061,36 @@ mod tests {
         roundtrip_certificate(false)
     }
 
+    #[test]
+    fn finality_signatures_and_evidence_requests_are_urgent() {
+        let mut rng = TestRng::new();
+        let secret_key = SecretKey::random(&mut rng);
+        let public_key = PublicKey::from(&secret_key);
+        let finality_signature = FinalitySignature::create(
+            BlockHash::random(&mut rng),
+            EraId::new(1),
+            &secret_key,
+            public_key.clone(),
+        );
+        let urgent_payloads = [
+            protocol::Message::from(Box::new(finality_signature)),
+            protocol::Message::Consensus(ConsensusMessage::EvidenceRequest {
+                era_id: EraId::new(1),
+                pub_key: public_key,
+            }),
+        ];
+        for payload in urgent_payloads {
+            assert_matches!(payload.message_kind(), MessageKind::UrgentConsensus);
+            assert!(Message::Payload(payload).is_urgent());
+        }
+
+        let request = protocol::Message::GetRequest {
+            tag: Tag::Block,
+            serialized_id: vec![],
+        };
+        assert!(!Message::Payload(request).is_urgent());
+    }
+
     #[test]
     fn assert_the_largest_specimen_type_and_size() {
         let (chainspec, _) = crate::utils::Loadable::from_resources("production");
@@ -1,6 +1,9 @@
-use std::sync::Weak;
+use std::{sync::Weak, time::Duration};
 
-use prometheus::{Counter, IntCounter, IntGauge, Registry};
+use prometheus::{
+    Counter, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry,
+};
 use tracing::debug;
 
 use super::{outgoing::OutgoingMetrics, MessageKind};
@@ -22,6 +25,8 @@ pub(super) struct Metrics {
     pub(super) out_count_protocol: IntCounter,
     /// Count of outgoing messages with consensus payload.
     pub(super) out_count_consensus: IntCounter,
+    /// Count of outgoing messages with urgent consensus payload.
+    pub(super) out_count_urgent_consensus: IntCounter,
     /// Count of outgoing messages with deploy gossiper payload.
     pub(super) out_count_deploy_gossip: IntCounter,
     pub(super) out_count_block_gossip: IntCounter,
@@ -41,6 +46,8 @@ pub(super) struct Metrics {
     pub(super) out_bytes_protocol: IntCounter,
     /// Volume in bytes of outgoing messages with consensus payload.
     pub(super) out_bytes_consensus: IntCounter,
+    /// Volume in bytes of outgoing messages with urgent consensus payload.
+    pub(super) out_bytes_urgent_consensus: IntCounter,
     /// Volume in bytes of outgoing messages with deploy gossiper payload.
     pub(super) out_bytes_deploy_gossip: IntCounter,
     pub(super) out_bytes_block_gossip: IntCounter,
@@ -71,6 +78,8 @@ pub(super) struct Metrics {
     pub(super) in_bytes_protocol: IntCounter,
     /// Volume in bytes of incoming messages with consensus payload.
     pub(super) in_bytes_consensus: IntCounter,
+    /// Volume in bytes of incoming messages with urgent consensus payload.
+    pub(super) in_bytes_urgent_consensus: IntCounter,
     /// Volume in bytes of incoming messages with deploy gossiper payload.
     pub(super) in_bytes_deploy_gossip: IntCounter,
     pub(super) in_bytes_block_gossip: IntCounter,
@@ -90,6 +99,8 @@ pub(super) struct Metrics {
     pub(super) in_count_protocol: IntCounter,
     /// Count of incoming messages with consensus payload.
     pub(super) in_count_consensus: IntCounter,
+    /// Count of incoming messages with urgent consensus payload.
+    pub(super) in_count_urgent_consensus: IntCounter,
     /// Count of incoming messages with deploy gossiper payload.
     pub(super) in_count_deploy_gossip: IntCounter,
     pub(super) in_count_block_gossip: IntCounter,
@@ -115,6 +126,31 @@ pub(super) struct Metrics {
     /// Total time spent delaying incoming traffic from non-validators due to limiter, in seconds.
     pub(super) accumulated_incoming_limiter_delay: Counter,
 
+    /// Number of outgoing payloads sent compressed.
+    pub(super) compressed_payloads: IntCounter,
+    /// Total number of bytes saved by compressing outgoing payloads.
+    pub(super) compression_saved_bytes: IntCounter,
+    /// Total number of bytes of outgoing payloads before compression, per channel.
+    compression_uncompressed_bytes: IntCounterVec,
+    /// Total number of bytes of outgoing payloads after compression, per channel.
+    compression_compressed_bytes: IntCounterVec,
+    /// Total time spent compressing outgoing payloads, in seconds.
+    pub(super) compression_time: Counter,
+    /// Total time spent decompressing incoming payloads, in seconds.
+    pub(super) decompression_time: Counter,
+
+    /// Number of outgoing messages waiting to be sent, per channel.
+    pub(super) channel_queue_depth: IntGaugeVec,
+    /// Total number of bytes sent, per channel.
+    channel_sent_bytes: IntCounterVec,
+    /// Number of outgoing messages which could not be sent, per channel.
+    pub(super) channel_send_errors: IntCounterVec,
+    /// Time between queueing and sending outgoing messages, per channel.
+    channel_send_latency: HistogramVec,
+
+    /// Number of gossiped peer addresses rejected, per reason.
+    pub(super) rejected_address_advertisements: IntCounterVec,
+
     /// Registry instance.
     registry: Registry,
 }
@@ -142,6 +178,10 @@ impl Metrics {
             "net_out_count_consensus",
             "count of outgoing messages with consensus payload",
         )?;
+        let out_count_urgent_consensus = IntCounter::new(
+            "net_out_count_urgent_consensus",
+            "count of outgoing messages with urgent consensus payload",
+        )?;
         let out_count_deploy_gossip = IntCounter::new(
             "net_out_count_deploy_gossip",
             "count of outgoing messages with deploy gossiper payload",
@@ -183,6 +223,10 @@ impl Metrics {
             "net_out_bytes_consensus",
             "volume in bytes of outgoing messages with consensus payload",
         )?;
+        let out_bytes_urgent_consensus = IntCounter::new(
+            "net_out_bytes_urgent_consensus",
+            "volume in bytes of outgoing messages with urgent consensus payload",
+        )?;
         let out_bytes_deploy_gossip = IntCounter::new(
             "net_out_bytes_deploy_gossip",
             "volume in bytes of outgoing messages with deploy gossiper payload",
@@ -245,6 +289,10 @@ impl Metrics {
             "net_in_count_consensus",
             "count of incoming messages with consensus payload",
         )?;
+        let in_count_urgent_consensus = IntCounter::new(
+            "net_in_count_urgent_consensus",
+            "count of incoming messages with urgent consensus payload",
+        )?;
         let in_count_deploy_gossip = IntCounter::new(
             "net_in_count_deploy_gossip",
             "count of incoming messages with deploy gossiper payload",
@@ -286,6 +334,10 @@ impl Metrics {
             "net_in_bytes_consensus",
             "volume in bytes of incoming messages with consensus payload",
         )?;
+        let in_bytes_urgent_consensus = IntCounter::new(
+            "net_in_bytes_urgent_consensus",
+            "volume in bytes of incoming messages with urgent consensus payload",
+        )?;
         let in_bytes_deploy_gossip = IntCounter::new(
             "net_in_bytes_deploy_gossip",
             "volume in bytes of incoming messages with deploy gossiper payload",
@@ -337,6 +389,75 @@ impl Metrics {
             "seconds spent delaying incoming traffic from non-validators due to limiter, in seconds."
         )?;
 
+        let compressed_payloads = IntCounter::new(
+            "net_compressed_payloads",
+            "number of outgoing payloads sent compressed",
+        )?;
+        let compression_saved_bytes = IntCounter::new(
+            "net_compression_saved_bytes",
+            "volume in bytes saved by compressing outgoing payloads",
+        )?;
+        let compression_uncompressed_bytes = IntCounterVec::new(
+            Opts::new(
+                "net_compression_uncompressed_bytes",
+                "volume in bytes of compressed outgoing payloads before compression, per channel",
+            ),
+            &["channel"],
+        )?;
+        let compression_compressed_bytes = IntCounterVec::new(
+            Opts::new(
+                "net_compression_compressed_bytes",
+                "volume in bytes of compressed outgoing payloads after compression, per channel",
+            ),
+            &["channel"],
+        )?;
+        let compression_time = Counter::new(
+            "net_compression_time",
+            "seconds spent compressing outgoing payloads",
+        )?;
+        let decompression_time = Counter::new(
+            "net_decompression_time",
+            "seconds spent decompressing incoming payloads",
+        )?;
+
+        let channel_queue_depth = IntGaugeVec::new(
+            Opts::new(
+                "net_channel_queue_depth",
+                "number of outgoing messages waiting to be sent, per channel",
+            ),
+            &["channel"],
+        )?;
+        let channel_sent_bytes = IntCounterVec::new(
+            Opts::new(
+                "net_channel_sent_bytes",
+                "number of bytes sent, per channel",
+            ),
+            &["channel"],
+        )?;
+        let channel_send_errors = IntCounterVec::new(
+            Opts::new(
+                "net_channel_send_errors",
+                "number of outgoing messages which could not be sent, per channel",
+            ),
+            &["channel"],
+        )?;
+        let channel_send_latency = HistogramVec::new(
+            HistogramOpts::new(
+                "net_channel_send_latency_seconds",
+                "time between queueing and sending outgoing messages in seconds, per channel",
+            )
+            .buckets(prometheus::exponential_buckets(0.001, 2.0, 14)?),
+            &["channel"],
+        )?;
+
+        let rejected_address_advertisements = IntCounterVec::new(
+            Opts::new(
+                "net_rejected_address_advertisements",
+                "number of gossiped peer addresses rejected, per reason",
+            ),
+            &["reason"],
+        )?;
+
         registry.register(Box::new(broadcast_requests.clone()))?;
         registry.register(Box::new(direct_message_requests.clone()))?;
         registry.register(Box::new(queued_messages.clone()))?;
@@ -344,6 +465,7 @@ impl Metrics {
 
         registry.register(Box::new(out_count_protocol.clone()))?;
         registry.register(Box::new(out_count_consensus.clone()))?;
+        registry.register(Box::new(out_count_urgent_consensus.clone()))?;
         registry.register(Box::new(out_count_deploy_gossip.clone()))?;
         registry.register(Box::new(out_count_block_gossip.clone()))?;
         registry.register(Box::new(out_count_finality_signature_gossip.clone()))?;
@@ -355,6 +477,7 @@ impl Metrics {
 
         registry.register(Box::new(out_bytes_protocol.clone()))?;
         registry.register(Box::new(out_bytes_consensus.clone()))?;
+        registry.register(Box::new(out_bytes_urgent_consensus.clone()))?;
         registry.register(Box::new(out_bytes_deploy_gossip.clone()))?;
         registry.register(Box::new(out_bytes_block_gossip.clone()))?;
         registry.register(Box::new(out_bytes_finality_signature_gossip.clone()))?;
@@ -372,6 +495,7 @@ impl Metrics {
 
         registry.register(Box::new(in_count_protocol.clone()))?;
         registry.register(Box::new(in_count_consensus.clone()))?;
+        registry.register(Box::new(in_count_urgent_consensus.clone()))?;
         registry.register(Box::new(in_count_deploy_gossip.clone()))?;
         registry.register(Box::new(in_count_block_gossip.clone()))?;
         registry.register(Box::new(in_count_finality_signature_gossip.clone()))?;
@@ -383,6 +507,7 @@ impl Metrics {
 
         registry.register(Box::new(in_bytes_protocol.clone()))?;
         registry.register(Box::new(in_bytes_consensus.clone()))?;
+        registry.register(Box::new(in_bytes_urgent_consensus.clone()))?;
         registry.register(Box::new(in_bytes_deploy_gossip.clone()))?;
         registry.register(Box::new(in_bytes_block_gossip.clone()))?;
         registry.register(Box::new(in_bytes_finality_signature_gossip.clone()))?;
@@ -398,6 +523,18 @@ impl Metrics {
         registry.register(Box::new(accumulated_outgoing_limiter_delay.clone()))?;
         registry.register(Box::new(accumulated_incoming_limiter_delay.clone()))?;
 
+        registry.register(Box::new(compressed_payloads.clone()))?;
+        registry.register(Box::new(compression_saved_bytes.clone()))?;
+        registry.register(Box::new(compression_uncompressed_bytes.clone()))?;
+        registry.register(Box::new(compression_compressed_bytes.clone()))?;
+        registry.register(Box::new(compression_time.clone()))?;
+        registry.register(Box::new(decompression_time.clone()))?;
+        registry.register(Box::new(channel_queue_depth.clone()))?;
+        registry.register(Box::new(channel_sent_bytes.clone()))?;
+        registry.register(Box::new(channel_send_errors.clone()))?;
+        registry.register(Box::new(channel_send_latency.clone()))?;
+        registry.register(Box::new(rejected_address_advertisements.clone()))?;
+
         Ok(Metrics {
             broadcast_requests,
             direct_message_requests,
@@ -405,6 +542,7 @@ impl Metrics {
             peers,
             out_count_protocol,
             out_count_consensus,
+            out_count_urgent_consensus,
             out_count_deploy_gossip,
             out_count_block_gossip,
             out_count_finality_signature_gossip,
@@ -415,6 +553,7 @@ impl Metrics {
             out_count_other,
             out_bytes_protocol,
             out_bytes_consensus,
+            out_bytes_urgent_consensus,
             out_bytes_deploy_gossip,
             out_bytes_block_gossip,
             out_bytes_finality_signature_gossip,
@@ -430,6 +569,7 @@ impl Metrics {
             out_state_loopback,
             in_count_protocol,
             in_count_consensus,
+            in_count_urgent_consensus,
             in_count_deploy_gossip,
             in_count_block_gossip,
             in_count_finality_signature_gossip,
@@ -440,6 +580,7 @@ impl Metrics {
             in_count_other,
             in_bytes_protocol,
             in_bytes_consensus,
+            in_bytes_urgent_consensus,
             in_bytes_deploy_gossip,
             in_bytes_block_gossip,
             in_bytes_finality_signature_gossip,
@@ -452,6 +593,17 @@ impl Metrics {
             requests_for_trie_finished,
             accumulated_outgoing_limiter_delay,
             accumulated_incoming_limiter_delay,
+            compressed_payloads,
+            compression_saved_bytes,
+            compression_uncompressed_bytes,
+            compression_compressed_bytes,
+            compression_time,
+            decompression_time,
+            channel_queue_depth,
+            channel_sent_bytes,
+            channel_send_errors,
+            channel_send_latency,
+            rejected_address_advertisements,
             registry: registry.clone(),
         })
     }
@@ -468,6 +620,10 @@ impl Metrics {
                     metrics.out_bytes_consensus.inc_by(size);
                     metrics.out_count_consensus.inc();
                 }
+                MessageKind::UrgentConsensus => {
+                    metrics.out_bytes_urgent_consensus.inc_by(size);
+                    metrics.out_count_urgent_consensus.inc();
+                }
                 MessageKind::DeployGossip => {
                     metrics.out_bytes_deploy_gossip.inc_by(size);
                     metrics.out_count_deploy_gossip.inc();
@@ -501,6 +657,10 @@ impl Metrics {
                     metrics.out_count_other.inc();
                 }
             }
+            metrics
+                .channel_sent_bytes
+                .with_label_values(&[&kind.to_string()])
+                .inc_by(size);
         } else {
             debug!("not recording metrics, component already shut down");
         }
@@ -518,6 +678,10 @@ impl Metrics {
                     metrics.in_bytes_consensus.inc_by(size);
                     metrics.in_count_consensus.inc();
                 }
+                MessageKind::UrgentConsensus => {
+                    metrics.in_bytes_urgent_consensus.inc_by(size);
+                    metrics.in_count_urgent_consensus.inc();
+                }
                 MessageKind::DeployGossip => {
                     metrics.in_bytes_deploy_gossip.inc_by(size);
                     metrics.in_count_deploy_gossip.inc();
@@ -584,6 +748,83 @@ impl Metrics {
             debug!("not recording metrics, component already shut down");
         }
     }
+
+    /// Records the compression of an outgoing payload of the given kind.
+    ///
+    /// `sizes` holds the serialized sizes of the message before and after compression, and is
+    /// `None` if the payload was not worth compressing.
+    pub(super) fn record_compression(
+        this: &Weak<Self>,
+        kind: MessageKind,
+        elapsed: Duration,
+        sizes: Option<(u64, u64)>,
+    ) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.compression_time.inc_by(elapsed.as_secs_f64());
+            if let Some((uncompressed_size, compressed_size)) = sizes {
+                let label = kind.to_string();
+                metrics.compressed_payloads.inc();
+                metrics
+                    .compression_saved_bytes
+                    .inc_by(uncompressed_size.saturating_sub(compressed_size));
+                metrics
+                    .compression_uncompressed_bytes
+                    .with_label_values(&[&label])
+                    .inc_by(uncompressed_size);
+                metrics
+                    .compression_compressed_bytes
+                    .with_label_values(&[&label])
+                    .inc_by(compressed_size);
+            }
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records that an outgoing message has been taken off its queue.
+    pub(super) fn record_channel_dequeued(this: &Weak<Self>, kind: MessageKind) {
+        if let Some(metrics) = this.upgrade() {
+            metrics
+                .channel_queue_depth
+                .with_label_values(&[&kind.to_string()])
+                .dec();
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records that an outgoing message has been sent, `latency` after it was queued.
+    pub(super) fn record_channel_sent(this: &Weak<Self>, kind: MessageKind, latency: Duration) {
+        if let Some(metrics) = this.upgrade() {
+            metrics
+                .channel_send_latency
+                .with_label_values(&[&kind.to_string()])
+                .observe(latency.as_secs_f64());
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records that an outgoing message could not be sent.
+    pub(super) fn record_channel_send_error(this: &Weak<Self>, kind: MessageKind) {
+        if let Some(metrics) = this.upgrade() {
+            metrics
+                .channel_send_errors
+                .with_label_values(&[&kind.to_string()])
+                .inc();
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
+
+    /// Records the decompression of an incoming payload.
+    pub(super) fn record_decompression(this: &Weak<Self>, elapsed: Duration) {
+        if let Some(metrics) = this.upgrade() {
+            metrics.decompression_time.inc_by(elapsed.as_secs_f64());
+        } else {
+            debug!("not recording metrics, component already shut down");
+        }
+    }
 }
 
 impl Drop for Metrics {
@@ -595,6 +836,7 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.out_count_protocol);
         unregister_metric!(self.registry, self.out_count_consensus);
+        unregister_metric!(self.registry, self.out_count_urgent_consensus);
         unregister_metric!(self.registry, self.out_count_deploy_gossip);
         unregister_metric!(self.registry, self.out_count_block_gossip);
         unregister_metric!(self.registry, self.out_count_finality_signature_gossip);
@@ -606,6 +848,7 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.out_bytes_protocol);
         unregister_metric!(self.registry, self.out_bytes_consensus);
+        unregister_metric!(self.registry, self.out_bytes_urgent_consensus);
         unregister_metric!(self.registry, self.out_bytes_deploy_gossip);
         unregister_metric!(self.registry, self.out_bytes_block_gossip);
         unregister_metric!(self.registry, self.out_bytes_finality_signature_gossip);
@@ -623,6 +866,7 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.in_count_protocol);
         unregister_metric!(self.registry, self.in_count_consensus);
+        unregister_metric!(self.registry, self.in_count_urgent_consensus);
         unregister_metric!(self.registry, self.in_count_deploy_gossip);
         unregister_metric!(self.registry, self.in_count_block_gossip);
         unregister_metric!(self.registry, self.in_count_finality_signature_gossip);
@@ -634,6 +878,7 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.in_bytes_protocol);
         unregister_metric!(self.registry, self.in_bytes_consensus);
+        unregister_metric!(self.registry, self.in_bytes_urgent_consensus);
         unregister_metric!(self.registry, self.in_bytes_deploy_gossip);
         unregister_metric!(self.registry, self.in_bytes_block_gossip);
         unregister_metric!(self.registry, self.in_bytes_finality_signature_gossip);
@@ -648,5 +893,18 @@ impl Drop for Metrics {
 
         unregister_metric!(self.registry, self.accumulated_outgoing_limiter_delay);
         unregister_metric!(self.registry, self.accumulated_incoming_limiter_delay);
+
+        unregister_metric!(self.registry, self.compressed_payloads);
+        unregister_metric!(self.registry, self.compression_saved_bytes);
+        unregister_metric!(self.registry, self.compression_uncompressed_bytes);
+        unregister_metric!(self.registry, self.compression_compressed_bytes);
+        unregister_metric!(self.registry, self.compression_time);
+        unregister_metric!(self.registry, self.decompression_time);
+
+        unregister_metric!(self.registry, self.channel_queue_depth);
+        unregister_metric!(self.registry, self.channel_sent_bytes);
+        unregister_metric!(self.registry, self.channel_send_errors);
+        unregister_metric!(self.registry, self.channel_send_latency);
+        unregister_metric!(self.registry, self.rejected_address_advertisements);
     }
 }
@@ -682,6 +682,49 @@ where
             })
     }
 
+    /// Changes whether an address is unforgettable, learning it if it was not known before.
+    ///
+    /// An address made unforgettable while waiting to reconnect after failures is dialed
+    /// immediately, rather than after its backoff expires.
+    pub(crate) fn set_unforgettable(
+        &mut self,
+        addr: SocketAddr,
+        unforgettable: bool,
+        now: Instant,
+    ) -> Option<DialRequest<H>> {
+        if !self.outgoing.contains_key(&addr) {
+            return if unforgettable {
+                self.learn_addr(addr, true, now)
+            } else {
+                None
+            };
+        }
+
+        let span = make_span(addr, self.outgoing.get(&addr));
+        let _span_guard = span.clone().entered();
+        let outgoing = self
+            .outgoing
+            .get_mut(&addr)
+            .expect("presence checked above");
+        if outgoing.is_unforgettable != unforgettable {
+            outgoing.is_unforgettable = unforgettable;
+            debug!(unforgettable, "marked");
+        }
+
+        if unforgettable && matches!(outgoing.state, OutgoingState::Waiting { .. }) {
+            info!("redialing address made unforgettable");
+            self.change_outgoing_state(
+                addr,
+                OutgoingState::Connecting {
+                    failures_so_far: 0,
+                    since: now,
+                },
+            );
+            return Some(DialRequest::Dial { addr, span });
+        }
+        None
+    }
+
     /// Records a pong being received.
     pub(super) fn record_pong(&mut self, peer_id: NodeId, pong: TaggedTimestamp) -> bool {
         let addr = if let Some(addr) = self.routes.get(&peer_id) {
@@ -1183,6 +1226,50 @@ mod tests {
             .is_empty());
     }
 
+    #[test]
+    fn making_address_unforgettable_redials_immediately() {
+        init_logging();
+
+        let clock = TestClock::new();
+
+        let addr_a: SocketAddr = "1.2.3.4:1234".parse().unwrap();
+        let addr_b: SocketAddr = "5.6.7.8:5678".parse().unwrap();
+
+        let mut manager = OutgoingManager::<u32, TestDialerError>::new(test_config());
+
+        // Unknown addresses are learned and dialed when made unforgettable, but ignored otherwise.
+        assert!(dials(
+            addr_a,
+            &manager.set_unforgettable(addr_a, true, clock.now())
+        ));
+        assert!(manager
+            .set_unforgettable(addr_b, false, clock.now())
+            .is_none());
+        assert!(manager.learn_addr(addr_b, false, clock.now()).is_some());
+
+        // A failed address is only redialed right away if it becomes unforgettable.
+        assert!(manager
+            .handle_dial_outcome(DialOutcome::Failed {
+                addr: addr_b,
+                error: TestDialerError { id: 1 },
+                when: clock.now(),
+            })
+            .is_none());
+        assert!(manager
+            .set_unforgettable(addr_b, false, clock.now())
+            .is_none());
+        assert!(dials(
+            addr_b,
+            &manager.set_unforgettable(addr_b, true, clock.now())
+        ));
+
+        // Releasing an address which is connecting does not disturb it.
+        assert!(manager
+            .set_unforgettable(addr_a, false, clock.now())
+            .is_none());
+        assert!(!manager.outgoing[&addr_a].is_unforgettable);
+    }
+
     #[test]
     fn connections_forgotten_after_too_many_tries() {
         init_logging();
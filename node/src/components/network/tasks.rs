@@ -1,19 +1,21 @@
 //! Tasks run by the component.
 
 use std::{
+    collections::HashSet,
     error::Error as StdError,
     fmt::Display,
     io,
-    net::SocketAddr,
+    net::{Ipv4Addr, SocketAddr},
     pin::Pin,
     sync::{
         atomic::{AtomicBool, Ordering},
         Arc, Weak,
     },
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 use bincode::Options;
+use bytes::Bytes;
 use futures::{
     future::{self, Either},
     stream::{SplitSink, SplitStream},
@@ -28,7 +30,7 @@ use prometheus::IntGauge;
 use rand::Rng;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use tokio::{
-    net::TcpStream,
+    net::{TcpListener, TcpStream},
     sync::{mpsc::UnboundedReceiver, watch, Semaphore},
 };
 use tokio_openssl::SslStream;
@@ -44,14 +46,14 @@ use casper_types::{ProtocolVersion, PublicKey, TimeDiff};
 use super::{
     chain_info::ChainInfo,
     counting_format::{ConnectionId, Role},
-    error::{ConnectionError, IoError},
+    error::{ConnectionError, IoError, SelfTestError},
     event::{IncomingConnection, OutgoingConnection},
     full_transport,
     limiter::LimiterHandle,
     message::NodeKeyPair,
     message_pack_format::MessagePackFormat,
-    EstimatorWeights, Event, FramedTransport, FullTransport, Identity, Message, Metrics, Payload,
-    Transport,
+    EstimatorWeights, Event, FramedTransport, FullTransport, Identity, Message, MessageKind,
+    Metrics, Payload, Transport,
 };
 use crate::{
     components::network::{framed_transport, BincodeFormat, Config, FromIncoming},
@@ -61,15 +63,18 @@ use crate::{
     },
     reactor::{EventQueueHandle, QueueKind},
     tls::{self, TlsCert, ValidationError},
-    types::NodeId,
+    types::{
+        chainspec::{NetworkFeature, NetworkFeatures},
+        NodeId,
+    },
     utils::display_error,
 };
 
 /// An item on the internal outgoing message queue.
 ///
-/// Contains a reference counted message and an optional responder to call once the message has been
-/// successfully handed over to the kernel for sending.
-pub(super) type MessageQueueItem<P> = (Arc<Message<P>>, Option<AutoClosingResponder<()>>);
+/// Contains a reference counted message, an optional responder to call once the message has been
+/// successfully handed over to the kernel for sending and the time the message was queued at.
+pub(super) type MessageQueueItem<P> = (Arc<Message<P>>, Option<AutoClosingResponder<()>>, Instant);
 
 /// The outcome of the handshake process.
 struct HandshakeOutcome {
@@ -81,6 +86,8 @@ struct HandshakeOutcome {
     peer_consensus_public_key: Option<PublicKey>,
     /// Holds the information whether the remote node is syncing.
     is_peer_syncing: bool,
+    /// Whether the remote node can decompress compressed payloads.
+    peer_supports_compression: bool,
 }
 
 /// Low-level TLS connection function.
@@ -163,6 +170,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_supports_compression: supports_compression,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", &field::display(public_key));
@@ -188,6 +196,7 @@ where
                 peer_consensus_public_key,
                 sink,
                 is_syncing,
+                supports_compression,
             }
         }
         Err(error) => OutgoingConnection::Failed {
@@ -236,6 +245,8 @@ where
     max_in_flight_demands: usize,
     /// Flag indicating whether this node is syncing.
     is_syncing: AtomicBool,
+    /// The network features in use in the current era.
+    active_network_features: watch::Receiver<NetworkFeatures>,
 }
 
 impl<REv> NetworkContext<REv> {
@@ -245,6 +256,7 @@ impl<REv> NetworkContext<REv> {
         node_key_pair: Option<NodeKeyPair>,
         chain_info: ChainInfo,
         net_metrics: &Arc<Metrics>,
+        active_network_features: watch::Receiver<NetworkFeatures>,
     ) -> Self {
         // Set the demand max from configuration, regarding `0` as "unlimited".
         let max_in_flight_demands = if cfg.max_in_flight_demands == 0 {
@@ -277,6 +289,7 @@ impl<REv> NetworkContext<REv> {
             tarpit_chance: cfg.tarpit_chance,
             max_in_flight_demands,
             is_syncing: AtomicBool::new(false),
+            active_network_features,
         }
     }
 
@@ -299,6 +312,11 @@ impl<REv> NetworkContext<REv> {
         self.public_addr
     }
 
+    /// Our signing keys, if any.
+    pub(super) fn node_key_pair(&self) -> Option<&NodeKeyPair> {
+        self.node_key_pair.as_ref()
+    }
+
     /// Chain info extract from chainspec.
     pub(super) fn chain_info(&self) -> &ChainInfo {
         &self.chain_info
@@ -318,6 +336,11 @@ impl<REv> NetworkContext<REv> {
     pub(crate) fn is_syncing(&self) -> &AtomicBool {
         &self.is_syncing
     }
+
+    /// The network features in use in the current era.
+    pub(super) fn active_network_features(&self) -> NetworkFeatures {
+        *self.active_network_features.borrow()
+    }
 }
 
 /// Handles an incoming connection.
@@ -362,6 +385,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: _,
+            peer_supports_compression: _,
         }) => {
             if let Some(ref public_key) = peer_consensus_public_key {
                 Span::current().record("consensus_key", &field::display(public_key));
@@ -428,6 +452,70 @@ pub(super) async fn server_setup_tls<REv>(
     ))
 }
 
+/// Payload of the frame exchanged during the network self-test.
+const SELF_TEST_PAYLOAD: &[u8] = b"casper-node network self-test";
+
+/// Connects to ourselves over the loopback interface and sends a single frame over the connection.
+///
+/// Both sides of the connection go through the same TLS setup and certificate validation as
+/// connections to and from peers, and the frame is sent using the same framing.
+pub(super) async fn self_test<REv>(context: &NetworkContext<REv>) -> Result<(), SelfTestError>
+where
+    REv: 'static,
+{
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .await
+        .map_err(SelfTestError::Bind)?;
+    let addr = listener.local_addr().map_err(SelfTestError::Bind)?;
+    let maximum_net_message_size = context.chain_info.maximum_net_message_size;
+
+    let incoming = async {
+        let (stream, _) = listener.accept().await.map_err(SelfTestError::Accept)?;
+        let (peer_id, transport) = server_setup_tls(context, stream)
+            .await
+            .map_err(SelfTestError::Incoming)?;
+        let mut framed = framed_transport(transport, maximum_net_message_size);
+        let frame = framed
+            .next()
+            .await
+            .ok_or(SelfTestError::ConnectionClosed)?
+            .map_err(SelfTestError::Receive)?;
+        Ok::<_, SelfTestError>((peer_id, frame))
+    };
+    let outgoing = async {
+        let (peer_id, transport) = tls_connect(context, addr)
+            .await
+            .map_err(SelfTestError::Outgoing)?;
+        let mut framed = framed_transport(transport, maximum_net_message_size);
+        framed
+            .send(Bytes::from_static(SELF_TEST_PAYLOAD))
+            .await
+            .map_err(SelfTestError::Send)?;
+        // Keep the connection open until the frame has been received on the other end.
+        Ok::<_, SelfTestError>((peer_id, framed))
+    };
+
+    let ((incoming_peer_id, frame), (outgoing_peer_id, _framed)) = tokio::time::timeout(
+        context.handshake_timeout.into(),
+        future::try_join(incoming, outgoing),
+    )
+    .await
+    .map_err(|_| SelfTestError::Timeout(context.handshake_timeout))??;
+
+    for peer_id in [incoming_peer_id, outgoing_peer_id] {
+        if peer_id != context.our_id {
+            return Err(SelfTestError::WrongPeerId {
+                expected: context.our_id,
+                actual: peer_id,
+            });
+        }
+    }
+    if frame != SELF_TEST_PAYLOAD {
+        return Err(SelfTestError::CorruptedFrame);
+    }
+    Ok(())
+}
+
 /// Performs an IO-operation that can time out.
 async fn io_timeout<F, T, E>(duration: Duration, future: F) -> Result<T, IoError<E>>
 where
@@ -513,6 +601,8 @@ where
         consensus_certificate,
         is_syncing,
         chainspec_hash,
+        supports_compression,
+        network_features,
     } = remote_message
     {
         debug!(%protocol_version, "handshake received");
@@ -555,6 +645,16 @@ where
             return Err(ConnectionError::WrongChainspecHash(peer_chainspec_hash));
         }
 
+        // Peers unable to handle the network features in use would fail to decode our messages.
+        let missing_network_features = context
+            .active_network_features()
+            .difference(network_features);
+        if !missing_network_features.is_empty() {
+            return Err(ConnectionError::MissingNetworkFeatures(
+                missing_network_features,
+            ));
+        }
+
         let peer_consensus_public_key = consensus_certificate
             .map(|cert| {
                 cert.validate(connection_id)
@@ -571,6 +671,7 @@ where
             public_addr,
             peer_consensus_public_key,
             is_peer_syncing: is_syncing,
+            peer_supports_compression: supports_compression,
         })
     } else {
         // Received a non-handshake, this is an error.
@@ -679,6 +780,26 @@ where
                 Ok(msg) => {
                     trace!(%msg, "message received");
 
+                    let msg = if msg.payload_is_compressed() {
+                        let start = Instant::now();
+                        let outcome =
+                            msg.decompressed(context.chain_info.maximum_net_message_size as usize);
+                        Metrics::record_decompression(&context.net_metrics, start.elapsed());
+
+                        match outcome {
+                            Ok(msg) => msg,
+                            Err(err) => {
+                                warn!(
+                                    err = display_error(&err),
+                                    "decompressing message failed, closing connection"
+                                );
+                                return Err(io::Error::new(io::ErrorKind::InvalidData, err));
+                            }
+                        }
+                    } else {
+                        msg
+                    };
+
                     let effect_builder = EffectBuilder::new(event_queue);
 
                     match msg.try_into_demand(effect_builder, peer_id) {
@@ -791,19 +912,67 @@ where
     Ok(())
 }
 
+/// Settings for compressing the payloads sent to a peer.
+#[derive(Debug)]
+pub(super) struct CompressionSettings {
+    /// Kinds of messages whose payloads are compressed.
+    pub(super) kinds: HashSet<MessageKind>,
+    /// Minimum size in bytes of a fetched item for its payload to be compressed.
+    pub(super) threshold: usize,
+}
+
 /// Network message sender.
 ///
 /// Reads from a channel and sends all messages, until the stream is closed or an error occurs.
+/// Messages read from `urgent_queue` are always sent before those waiting in `queue`.
+///
+/// If `compression` is set, payloads of the kinds it lists are compressed if worthwhile, as long as
+/// compression is among the `network_features` in use.
+#[allow(clippy::too_many_arguments)]
 pub(super) async fn message_sender<P>(
     mut queue: UnboundedReceiver<MessageQueueItem<P>>,
+    mut urgent_queue: UnboundedReceiver<MessageQueueItem<P>>,
     mut sink: SplitSink<FullTransport<P>, Arc<Message<P>>>,
     limiter: LimiterHandle,
     counter: IntGauge,
+    compression: Option<CompressionSettings>,
+    network_features: watch::Receiver<NetworkFeatures>,
+    net_metrics: Weak<Metrics>,
 ) where
     P: Payload,
 {
-    while let Some((message, opt_responder)) = queue.recv().await {
+    loop {
+        // Urgent messages take strict priority over all others.
+        let (mut message, opt_responder, queued_at) = tokio::select! {
+            biased;
+            Some(item) = urgent_queue.recv() => item,
+            Some(item) = queue.recv() => item,
+            else => break,
+        };
         counter.dec();
+        let kind = message.classify();
+        Metrics::record_channel_dequeued(&net_metrics, kind);
+
+        let compression = compression.as_ref().filter(|compression| {
+            compression.kinds.contains(&kind)
+                && network_features
+                    .borrow()
+                    .contains(NetworkFeature::Compression)
+        });
+        if let Some(compression) = compression {
+            let start = Instant::now();
+            let compressed = message.compressed(compression.threshold);
+            let sizes = compressed.as_ref().and_then(|compressed| {
+                let serialized_size =
+                    |msg: &Message<P>| BincodeFormat::default().0.serialized_size(msg).ok();
+                Some((serialized_size(&message)?, serialized_size(compressed)?))
+            });
+            Metrics::record_compression(&net_metrics, kind, start.elapsed(), sizes);
+
+            if let Some(compressed) = compressed {
+                message = Arc::new(compressed);
+            }
+        }
 
         let estimated_wire_size = match BincodeFormat::default().0.serialized_size(&*message) {
             Ok(size) => size as u32,
@@ -833,13 +1002,21 @@ pub(super) async fn message_sender<P>(
                 "message send failed, closing outgoing connection"
             );
 
-            // To ensure, metrics are up to date, we close the queue and drain it.
-            queue.close();
-            while queue.recv().await.is_some() {
-                counter.dec();
+            // To ensure, metrics are up to date, we close the queues and drain them.
+            Metrics::record_channel_send_error(&net_metrics, kind);
+            for queue in [&mut urgent_queue, &mut queue] {
+                queue.close();
+                while let Some((message, _, _)) = queue.recv().await {
+                    counter.dec();
+                    let kind = message.classify();
+                    Metrics::record_channel_dequeued(&net_metrics, kind);
+                    Metrics::record_channel_send_error(&net_metrics, kind);
+                }
             }
 
             break;
         };
+
+        Metrics::record_channel_sent(&net_metrics, kind, queued_at.elapsed());
     }
 }
@@ -18,7 +18,7 @@ use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 use tracing::{debug, info};
 
-use casper_types::SecretKey;
+use casper_types::{PublicKey, SecretKey};
 
 use super::{
     chain_info::ChainInfo, Config, Event as NetworkEvent, FromIncoming, GossipedAddress, Identity,
@@ -191,19 +191,21 @@ impl Reactor for TestReactor {
         _event_queue: EventQueueHandle<Self::Event>,
         rng: &mut NodeRng,
     ) -> anyhow::Result<(Self, Effects<Self::Event>)> {
-        let secret_key = SecretKey::random(rng);
+        let secret_key = Arc::new(SecretKey::random(rng));
+        let public_key = PublicKey::from(secret_key.as_ref());
         let mut net = Network::new(
             cfg,
             our_identity,
-            None,
+            Some((secret_key.clone(), public_key)),
             registry,
             ChainInfo::create_for_testing(),
-            ValidatorMatrix::new_with_validator(Arc::new(secret_key)),
+            ValidatorMatrix::new_with_validator(secret_key),
         )?;
         let gossiper_config = gossiper::Config::new_with_small_timeouts();
         let address_gossiper = Gossiper::<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, _>::new(
             "address_gossiper",
             gossiper_config,
+            net.network_features(),
             registry,
         )?;
 
@@ -241,14 +243,18 @@ impl Reactor for TestReactor {
             Event::ControlAnnouncement(ctrl_ann) => {
                 unreachable!("unhandled control announcement: {}", ctrl_ann)
             }
-            Event::AddressGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem(
-                gossiped_address,
-            )) => reactor::wrap_effects(
+            Event::AddressGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem {
+                item_id: gossiped_address,
+                sender,
+            }) => reactor::wrap_effects(
                 Event::Net,
                 self.net.handle_event(
                     effect_builder,
                     rng,
-                    NetworkEvent::PeerAddressReceived(gossiped_address),
+                    NetworkEvent::PeerAddressReceived {
+                        gossiped_address: Box::new(gossiped_address),
+                        sender: Box::new(sender),
+                    },
                 ),
             ),
 
@@ -535,3 +541,24 @@ async fn ensure_peers_metric_is_correct() {
         net.finalize().await;
     }
 }
+
+#[tokio::test]
+async fn self_test_should_connect_to_ourselves() {
+    init_logging();
+
+    let mut rng = crate::new_rng();
+    let secret_key = Arc::new(SecretKey::random(&mut rng));
+    let public_key = PublicKey::from(secret_key.as_ref());
+    let net = Network::<Event, Message>::new(
+        Config::default_local_net_first_node(testing::unused_port_on_localhost()),
+        Identity::with_generated_certs().expect("should generate identity"),
+        Some((secret_key.clone(), public_key)),
+        &Registry::new(),
+        ChainInfo::create_for_testing(),
+        ValidatorMatrix::new_with_validator(secret_key),
+    )
+    .expect("should create network component");
+
+    // Runs from within the async test, as it does when the reactor is constructed.
+    net.run_self_test().expect("self-test should pass");
+}
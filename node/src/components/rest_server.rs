@@ -22,6 +22,8 @@ mod config;
 mod event;
 mod filters;
 mod http_server;
+mod performance;
+mod rewards;
 
 use std::{fmt::Debug, time::Instant};
 
@@ -41,11 +43,11 @@ use crate::{
     },
     effect::{
         requests::{
-            BlockSynchronizerRequest, ChainspecRawBytesRequest, ConsensusRequest, MetricsRequest,
-            NetworkInfoRequest, ReactorStatusRequest, RestRequest, StorageRequest,
-            UpgradeWatcherRequest,
+            BlockSynchronizerRequest, ChainspecRawBytesRequest, ConsensusRequest,
+            ContractRuntimeRequest, MetricsRequest, NetworkInfoRequest, ReactorStatusRequest,
+            RestRequest, StorageRequest, UpgradeWatcherRequest,
         },
-        EffectBuilder, EffectExt, Effects,
+        ComponentUnavailable, EffectBuilder, EffectExt, Effects,
     },
     reactor::{main_reactor::MainEvent, Finalize},
     types::{ChainspecInfo, StatusFeed},
@@ -69,6 +71,7 @@ pub(crate) trait ReactorEventT:
     + From<MetricsRequest>
     + From<ReactorStatusRequest>
     + From<BlockSynchronizerRequest>
+    + From<ContractRuntimeRequest>
     + Send
 {
 }
@@ -84,6 +87,7 @@ impl<REv> ReactorEventT for REv where
         + From<MetricsRequest>
         + From<ReactorStatusRequest>
         + From<BlockSynchronizerRequest>
+        + From<ContractRuntimeRequest>
         + Send
         + 'static
 {
@@ -153,7 +157,7 @@ where
                     name = <Self as Component<MainEvent>>::name(self),
                     "should not handle this event when this component has fatal error"
                 );
-                Effects::new()
+                respond_unavailable(event)
             }
             ComponentState::Uninitialized => {
                 warn!(
@@ -161,7 +165,7 @@ where
                     name = <Self as Component<MainEvent>>::name(self),
                     "should not handle this event when component is uninitialized"
                 );
-                Effects::new()
+                respond_unavailable(event)
             }
             ComponentState::Initializing => match event {
                 Event::Initialize => {
@@ -175,7 +179,7 @@ where
                         name = <Self as Component<MainEvent>>::name(self),
                         "should not handle this event when component is pending initialization"
                     );
-                    Effects::new()
+                    respond_unavailable(event)
                 }
             },
             ComponentState::Initialized => match event {
@@ -228,7 +232,7 @@ where
                             block_sync,
                             starting_state_root_hash,
                         );
-                        responder.respond(status_feed).await;
+                        responder.respond(Ok(status_feed)).await;
                     }
                 }
                 .ignore(),
@@ -240,12 +244,12 @@ where
                     }),
                 Event::RestRequest(RestRequest::RpcSchema { responder }) => {
                     let schema = OPEN_RPC_SCHEMA.clone();
-                    responder.respond(schema).ignore()
+                    responder.respond(Ok(schema)).ignore()
                 }
                 Event::GetMetricsResult {
                     text,
                     main_responder,
-                } => main_responder.respond(text).ignore(),
+                } => main_responder.respond(Ok(text)).ignore(),
             },
         }
     }
@@ -255,6 +259,19 @@ where
     }
 }
 
+/// Answers any request carried by an event the component cannot currently serve, so that the
+/// HTTP handler awaiting it fails instead of hanging.
+fn respond_unavailable(event: Event) -> Effects<Event> {
+    let error = ComponentUnavailable::new(COMPONENT_NAME);
+    match event {
+        Event::Initialize => Effects::new(),
+        Event::RestRequest(request) => request.respond_unavailable(error).ignore(),
+        Event::GetMetricsResult { main_responder, .. } => {
+            main_responder.respond(Err(error)).ignore()
+        }
+    }
+}
+
 impl<REv> InitializedComponent<REv> for RestServer
 where
     REv: ReactorEventT,
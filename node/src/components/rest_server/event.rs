@@ -6,7 +6,7 @@ use std::{
 use derive_more::From;
 use static_assertions::const_assert;
 
-use crate::effect::{requests::RestRequest, Responder};
+use crate::effect::{requests::RestRequest, ComponentUnavailable, Responder};
 
 const _REST_EVENT_SIZE: usize = mem::size_of::<Event>();
 const_assert!(_REST_EVENT_SIZE < 89);
@@ -18,7 +18,7 @@ pub(crate) enum Event {
     RestRequest(RestRequest),
     GetMetricsResult {
         text: Option<String>,
-        main_responder: Responder<Option<String>>,
+        main_responder: Responder<Result<Option<String>, ComponentUnavailable>>,
     },
 }
 
@@ -1,6 +1,7 @@
 use futures::FutureExt;
-use http::Response;
+use http::{header, Response};
 use hyper::Body;
+use serde::{Deserialize, Serialize};
 use tracing::warn;
 use warp::{
     filters::BoxedFilter,
@@ -10,11 +11,17 @@ use warp::{
     Filter,
 };
 
-use casper_types::ProtocolVersion;
+use casper_execution_engine::core::engine_state::{QueryRequest, QueryResult};
+use casper_hashing::Digest;
+use casper_types::{
+    bytesrepr::Bytes, system::auction::EraInfo, EraId, Key, ProtocolVersion, PublicKey, StoredValue,
+};
 
-use super::ReactorEventT;
+use super::{
+    performance::GetValidatorPerformanceResult, rewards::GetEraRewardsResult, ReactorEventT,
+};
 use crate::{
-    effect::{requests::RestRequest, EffectBuilder},
+    effect::{requests::RestRequest, ComponentUnavailable, EffectBuilder},
     reactor::QueueKind,
     rpcs::info::{GetChainspecResult, GetValidatorChangesResult},
     types::GetStatusResult,
@@ -35,6 +42,36 @@ pub const VALIDATOR_CHANGES_API_PATH: &str = "validator-changes";
 /// The chainspec file URL path.
 pub const CHAINSPEC_API_PATH: &str = "chainspec";
 
+/// The URL path segment, under the chainspec path, of the staged upgrade chainspec.
+pub const STAGED_CHAINSPEC_API_PATH: &str = "upgrade";
+
+/// The URL path segment, under a chainspec path, serving the chainspec parsed into JSON.
+pub const JSON_API_PATH: &str = "json";
+
+/// The era rewards URL path.
+pub const REWARDS_API_PATH: &str = "rewards";
+
+/// The validators URL path.
+pub const VALIDATORS_API_PATH: &str = "validators";
+
+/// The URL path segment, under a validator's path, serving the validator's performance.
+pub const PERFORMANCE_API_PATH: &str = "performance";
+
+/// The number of seconds after which clients are advised to retry a request which failed because
+/// the REST server was not available.
+const RETRY_AFTER_SECONDS: u32 = 5;
+
+/// Returns a `503 Service Unavailable` reply advising the client to retry later.
+fn component_unavailable_reply(error: ComponentUnavailable) -> Response<Body> {
+    warn!(%error, "failed to serve REST request");
+    reply::with_header(
+        reply::with_status(error.to_string(), StatusCode::SERVICE_UNAVAILABLE),
+        header::RETRY_AFTER,
+        RETRY_AFTER_SECONDS,
+    )
+    .into_response()
+}
+
 pub(super) fn create_status_filter<REv: ReactorEventT>(
     effect_builder: EffectBuilder<REv>,
     api_version: ProtocolVersion,
@@ -47,9 +84,12 @@ pub(super) fn create_status_filter<REv: ReactorEventT>(
                     |responder| RestRequest::Status { responder },
                     QueueKind::Api,
                 )
-                .map(move |status_feed| {
-                    let body = GetStatusResult::new(status_feed, api_version);
-                    Ok::<_, Rejection>(reply::json(&body).into_response())
+                .map(move |result| match result {
+                    Ok(status_feed) => {
+                        let body = GetStatusResult::new(status_feed, api_version);
+                        Ok::<_, Rejection>(reply::json(&body).into_response())
+                    }
+                    Err(error) => Ok(component_unavailable_reply(error)),
                 })
         })
         .boxed()
@@ -66,11 +106,12 @@ pub(super) fn create_metrics_filter<REv: ReactorEventT>(
                     |responder| RestRequest::Metrics { responder },
                     QueueKind::Api,
                 )
-                .map(|maybe_metrics| match maybe_metrics {
-                    Some(metrics) => Ok::<_, Rejection>(
+                .map(|result| match result {
+                    Ok(Some(metrics)) => Ok::<_, Rejection>(
                         reply::with_status(metrics, StatusCode::OK).into_response(),
                     ),
-                    None => {
+                    Err(error) => Ok(component_unavailable_reply(error)),
+                    Ok(None) => {
                         warn!("metrics not available");
                         Ok(reply::with_status(
                             "metrics not available",
@@ -94,8 +135,11 @@ pub(super) fn create_rpc_schema_filter<REv: ReactorEventT>(
                     |responder| RestRequest::RpcSchema { responder },
                     QueueKind::Api,
                 )
-                .map(move |open_rpc_schema| {
-                    Ok::<_, Rejection>(reply::json(&open_rpc_schema).into_response())
+                .map(move |result| match result {
+                    Ok(open_rpc_schema) => {
+                        Ok::<_, Rejection>(reply::json(&open_rpc_schema).into_response())
+                    }
+                    Err(error) => Ok(component_unavailable_reply(error)),
                 })
         })
         .boxed()
@@ -124,6 +168,7 @@ pub(super) fn create_chainspec_filter<REv: ReactorEventT>(
 ) -> BoxedFilter<(Response<Body>,)> {
     warp::get()
         .and(warp::path(CHAINSPEC_API_PATH))
+        .and(warp::path::end())
         .and_then(move || {
             effect_builder
                 .get_chainspec_raw_bytes()
@@ -134,3 +179,272 @@ pub(super) fn create_chainspec_filter<REv: ReactorEventT>(
         })
         .boxed()
 }
+
+pub(super) fn create_chainspec_json_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(CHAINSPEC_API_PATH))
+        .and(warp::path(JSON_API_PATH))
+        .and(warp::path::end())
+        .and_then(move || {
+            effect_builder
+                .get_chainspec_raw_bytes()
+                .map(move |chainspec_bytes| {
+                    Ok::<_, Rejection>(parsed_chainspec_reply(
+                        api_version,
+                        chainspec_bytes.chainspec_bytes(),
+                    ))
+                })
+        })
+        .boxed()
+}
+
+pub(super) fn create_staged_chainspec_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(CHAINSPEC_API_PATH))
+        .and(warp::path(STAGED_CHAINSPEC_API_PATH))
+        .and(warp::path::end())
+        .and_then(move || {
+            effect_builder
+                .get_staged_chainspec_bytes()
+                .map(move |maybe_chainspec_bytes| match maybe_chainspec_bytes {
+                    Some(chainspec_bytes) => {
+                        let result = GetStagedChainspecResult {
+                            api_version,
+                            chainspec_bytes,
+                        };
+                        Ok::<_, Rejection>(reply::json(&result).into_response())
+                    }
+                    None => Ok(no_staged_chainspec_reply()),
+                })
+        })
+        .boxed()
+}
+
+pub(super) fn create_staged_chainspec_json_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(CHAINSPEC_API_PATH))
+        .and(warp::path(STAGED_CHAINSPEC_API_PATH))
+        .and(warp::path(JSON_API_PATH))
+        .and(warp::path::end())
+        .and_then(move || {
+            effect_builder
+                .get_staged_chainspec_bytes()
+                .map(move |maybe_chainspec_bytes| match maybe_chainspec_bytes {
+                    Some(chainspec_bytes) => Ok::<_, Rejection>(parsed_chainspec_reply(
+                        api_version,
+                        chainspec_bytes.as_slice(),
+                    )),
+                    None => Ok(no_staged_chainspec_reply()),
+                })
+        })
+        .boxed()
+}
+
+/// Query parameters of the era rewards endpoint.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RewardsQuery {
+    /// Whether to include the rewards of each delegator.
+    #[serde(default)]
+    delegators: bool,
+}
+
+pub(super) fn create_rewards_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(REWARDS_API_PATH))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(warp::query::<RewardsQuery>())
+        .and_then(move |era_id: u64, query: RewardsQuery| {
+            era_rewards_reply(
+                effect_builder,
+                api_version,
+                EraId::new(era_id),
+                query.delegators,
+            )
+            .map(Ok::<_, Rejection>)
+        })
+        .boxed()
+}
+
+/// Replies with the rewards paid out at the end of the given era, read from the era summary
+/// stored in global state as of the era's switch block.
+async fn era_rewards_reply<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+    era_id: EraId,
+    include_delegators: bool,
+) -> Response<Body> {
+    let switch_block_header = match effect_builder
+        .get_switch_block_header_by_era_from_storage(era_id)
+        .await
+    {
+        Some(switch_block_header) => switch_block_header,
+        None => {
+            return reply::with_status(
+                format!("no switch block stored for era {}", era_id),
+                StatusCode::NOT_FOUND,
+            )
+            .into_response()
+        }
+    };
+    match get_era_info(
+        effect_builder,
+        *switch_block_header.state_root_hash(),
+        era_id,
+    )
+    .await
+    {
+        Ok(Some(era_info)) => {
+            let result = GetEraRewardsResult::new(
+                api_version,
+                era_id,
+                switch_block_header.block_hash(),
+                &era_info,
+                include_delegators,
+            );
+            reply::json(&result).into_response()
+        }
+        Ok(None) => reply::with_status(
+            format!("no era summary stored for era {}", era_id),
+            StatusCode::NOT_FOUND,
+        )
+        .into_response(),
+        Err(error) => {
+            warn!(%era_id, %error, "failed to read era summary");
+            reply::with_status(
+                "failed to read era summary",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
+pub(super) fn create_validator_performance_filter<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+) -> BoxedFilter<(Response<Body>,)> {
+    warp::get()
+        .and(warp::path(VALIDATORS_API_PATH))
+        .and(warp::path::param::<String>())
+        .and(warp::path(PERFORMANCE_API_PATH))
+        .and(warp::path::end())
+        .and_then(move |public_key: String| {
+            validator_performance_reply(effect_builder, api_version, public_key)
+                .map(Ok::<_, Rejection>)
+        })
+        .boxed()
+}
+
+/// Replies with the performance of the given validator in each era recorded in storage.
+async fn validator_performance_reply<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+    public_key: String,
+) -> Response<Body> {
+    let public_key = match PublicKey::from_hex(&public_key) {
+        Ok(public_key) => public_key,
+        Err(error) => {
+            return reply::with_status(
+                format!("invalid public key: {}", error),
+                StatusCode::BAD_REQUEST,
+            )
+            .into_response()
+        }
+    };
+    let performance = effect_builder
+        .get_validator_performance_from_storage(public_key.clone())
+        .await;
+    let result = GetValidatorPerformanceResult::new(api_version, public_key, performance);
+    reply::json(&result).into_response()
+}
+
+/// Reads the era summary from global state.
+///
+/// As with the `chain_get_era_summary` JSON-RPC method, the summary is looked up under
+/// `Key::EraSummary` first, falling back to `Key::EraInfo`, under which it was stored on networks
+/// running versions prior to 1.4.15.
+async fn get_era_info<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+    state_root_hash: Digest,
+    era_id: EraId,
+) -> Result<Option<EraInfo>, String> {
+    for key in [Key::EraSummary, Key::EraInfo(era_id)] {
+        let query_request = QueryRequest::new(state_root_hash, key, vec![]);
+        match effect_builder.query_global_state(query_request).await {
+            Ok(QueryResult::Success { value, .. }) => {
+                return match *value {
+                    StoredValue::EraInfo(era_info) => Ok(Some(era_info)),
+                    other => Err(format!(
+                        "expected era info under {}, got {}",
+                        key,
+                        other.type_name()
+                    )),
+                };
+            }
+            Ok(QueryResult::ValueNotFound(_)) => continue,
+            Ok(query_result) => return Err(format!("{:?}", query_result)),
+            Err(error) => return Err(error.to_string()),
+        }
+    }
+    Ok(None)
+}
+
+/// Result for the staged upgrade chainspec in raw form.
+#[derive(Serialize)]
+struct GetStagedChainspecResult {
+    /// The REST API version.
+    api_version: ProtocolVersion,
+    /// The staged chainspec file bytes.
+    chainspec_bytes: Bytes,
+}
+
+/// Result for a chainspec parsed into JSON.
+#[derive(Serialize)]
+struct GetParsedChainspecResult {
+    /// The REST API version.
+    api_version: ProtocolVersion,
+    /// The chainspec file contents, converted from TOML to JSON.
+    chainspec: toml::Value,
+}
+
+/// Parses the given chainspec file bytes and replies with their contents as JSON.
+///
+/// The chainspec is deliberately parsed as a generic TOML document rather than a `Chainspec`, as a
+/// staged upgrade chainspec may be in a format this node version doesn't understand.
+fn parsed_chainspec_reply(api_version: ProtocolVersion, chainspec_bytes: &[u8]) -> Response<Body> {
+    match toml::from_slice(chainspec_bytes) {
+        Ok(chainspec) => {
+            let result = GetParsedChainspecResult {
+                api_version,
+                chainspec,
+            };
+            reply::json(&result).into_response()
+        }
+        Err(error) => {
+            warn!(%error, "failed to parse chainspec");
+            reply::with_status(
+                "failed to parse chainspec",
+                StatusCode::INTERNAL_SERVER_ERROR,
+            )
+            .into_response()
+        }
+    }
+}
+
+fn no_staged_chainspec_reply() -> Response<Body> {
+    reply::with_status("no upgrade chainspec staged", StatusCode::NOT_FOUND).into_response()
+}
@@ -30,13 +30,27 @@ pub(super) async fn run<REv: ReactorEventT>(
     let rest_validator_changes =
         filters::create_validator_changes_filter(effect_builder, api_version);
     let rest_chainspec_filter = filters::create_chainspec_filter(effect_builder, api_version);
+    let rest_chainspec_json_filter =
+        filters::create_chainspec_json_filter(effect_builder, api_version);
+    let rest_staged_chainspec_filter =
+        filters::create_staged_chainspec_filter(effect_builder, api_version);
+    let rest_staged_chainspec_json_filter =
+        filters::create_staged_chainspec_json_filter(effect_builder, api_version);
+    let rest_rewards_filter = filters::create_rewards_filter(effect_builder, api_version);
+    let rest_validator_performance_filter =
+        filters::create_validator_performance_filter(effect_builder, api_version);
 
     let service = warp::service(
         rest_status
             .or(rest_metrics)
             .or(rest_open_rpc)
             .or(rest_validator_changes)
-            .or(rest_chainspec_filter),
+            .or(rest_chainspec_filter)
+            .or(rest_chainspec_json_filter)
+            .or(rest_staged_chainspec_filter)
+            .or(rest_staged_chainspec_json_filter)
+            .or(rest_rewards_filter)
+            .or(rest_validator_performance_filter),
     );
 
     // Start the server, passing a oneshot receiver to allow the server to be shut down gracefully.
@@ -79,6 +93,15 @@ pub(super) async fn run_with_cors<REv: ReactorEventT>(
     let rest_validator_changes =
         filters::create_validator_changes_filter(effect_builder, api_version);
     let rest_chainspec_filter = filters::create_chainspec_filter(effect_builder, api_version);
+    let rest_chainspec_json_filter =
+        filters::create_chainspec_json_filter(effect_builder, api_version);
+    let rest_staged_chainspec_filter =
+        filters::create_staged_chainspec_filter(effect_builder, api_version);
+    let rest_staged_chainspec_json_filter =
+        filters::create_staged_chainspec_json_filter(effect_builder, api_version);
+    let rest_rewards_filter = filters::create_rewards_filter(effect_builder, api_version);
+    let rest_validator_performance_filter =
+        filters::create_validator_performance_filter(effect_builder, api_version);
 
     let service = warp::service(
         rest_status
@@ -86,6 +109,11 @@ pub(super) async fn run_with_cors<REv: ReactorEventT>(
             .or(rest_open_rpc)
             .or(rest_validator_changes)
             .or(rest_chainspec_filter)
+            .or(rest_chainspec_json_filter)
+            .or(rest_staged_chainspec_filter)
+            .or(rest_staged_chainspec_json_filter)
+            .or(rest_rewards_filter)
+            .or(rest_validator_performance_filter)
             .with(match cors_origin {
                 CorsOrigin::Any => warp::cors().allow_any_origin(),
                 CorsOrigin::Specified(origin) => warp::cors().allow_origin(origin.as_str()),
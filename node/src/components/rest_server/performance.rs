@@ -0,0 +1,60 @@
+//! The performance of a validator in each era, as recorded by the validator performance index of
+//! storage.
+
+use serde::Serialize;
+
+use casper_types::{EraId, ProtocolVersion, PublicKey};
+
+use crate::components::storage::ValidatorEraPerformance;
+
+/// Result for the performance of a validator.
+#[derive(Serialize)]
+pub(super) struct GetValidatorPerformanceResult {
+    /// The REST API version.
+    api_version: ProtocolVersion,
+    /// The validator's public key.
+    public_key: PublicKey,
+    /// The performance of the validator in each era recorded in storage, ordered by era.
+    eras: Vec<EraPerformance>,
+}
+
+impl GetValidatorPerformanceResult {
+    pub(super) fn new(
+        api_version: ProtocolVersion,
+        public_key: PublicKey,
+        performance: Vec<ValidatorEraPerformance>,
+    ) -> Self {
+        GetValidatorPerformanceResult {
+            api_version,
+            public_key,
+            eras: performance.into_iter().map(EraPerformance::from).collect(),
+        }
+    }
+}
+
+/// The performance of a validator in a single era.
+#[derive(PartialEq, Eq, Debug, Serialize)]
+struct EraPerformance {
+    /// The era.
+    era_id: EraId,
+    /// The number of blocks proposed by the validator.
+    blocks_proposed: u64,
+    /// The number of blocks for which a finality signature of the validator is stored.
+    finality_signatures: u64,
+    /// Whether the validator was reported as an equivocator in the era's switch block.
+    equivocated: bool,
+    /// Whether the validator was reported as inactive in the era's switch block.
+    inactive: bool,
+}
+
+impl From<ValidatorEraPerformance> for EraPerformance {
+    fn from(performance: ValidatorEraPerformance) -> Self {
+        EraPerformance {
+            era_id: performance.era_id,
+            blocks_proposed: performance.proposed_blocks.len() as u64,
+            finality_signatures: performance.finality_signatures,
+            equivocated: performance.equivocated,
+            inactive: performance.inactive,
+        }
+    }
+}
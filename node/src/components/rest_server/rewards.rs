@@ -0,0 +1,176 @@
+//! The rewards paid out at the end of an era, as recorded by the auction in the era summary.
+
+use std::collections::BTreeMap;
+
+use serde::Serialize;
+
+use casper_types::{
+    system::auction::{EraInfo, SeigniorageAllocation},
+    EraId, ProtocolVersion, PublicKey, U512,
+};
+
+use crate::types::BlockHash;
+
+/// Result for the rewards paid out at the end of an era.
+#[derive(Serialize)]
+pub(super) struct GetEraRewardsResult {
+    /// The REST API version.
+    api_version: ProtocolVersion,
+    /// The era the rewards were paid out for.
+    era_id: EraId,
+    /// The hash of the switch block concluding the era.
+    switch_block_hash: BlockHash,
+    /// The rewards of each validator, ordered by public key.
+    validators: Vec<ValidatorRewards>,
+}
+
+impl GetEraRewardsResult {
+    /// Collects the rewards of each validator from the seigniorage allocations of the era,
+    /// including the rewards of each of their delegators if `include_delegators` is set.
+    pub(super) fn new(
+        api_version: ProtocolVersion,
+        era_id: EraId,
+        switch_block_hash: BlockHash,
+        era_info: &EraInfo,
+        include_delegators: bool,
+    ) -> Self {
+        let mut validators: BTreeMap<PublicKey, ValidatorRewards> = BTreeMap::new();
+        for allocation in era_info.seigniorage_allocations() {
+            match allocation {
+                SeigniorageAllocation::Validator {
+                    validator_public_key,
+                    amount,
+                } => {
+                    let rewards = validators
+                        .entry(validator_public_key.clone())
+                        .or_insert_with(|| ValidatorRewards::new(validator_public_key));
+                    rewards.amount += *amount;
+                }
+                SeigniorageAllocation::Delegator {
+                    delegator_public_key,
+                    validator_public_key,
+                    amount,
+                } => {
+                    let rewards = validators
+                        .entry(validator_public_key.clone())
+                        .or_insert_with(|| ValidatorRewards::new(validator_public_key));
+                    rewards.delegators_amount += *amount;
+                    if include_delegators {
+                        rewards
+                            .delegators
+                            .get_or_insert_with(Vec::new)
+                            .push(DelegatorReward {
+                                public_key: delegator_public_key.clone(),
+                                amount: *amount,
+                            });
+                    }
+                }
+            }
+        }
+        if include_delegators {
+            for rewards in validators.values_mut() {
+                rewards
+                    .delegators
+                    .get_or_insert_with(Vec::new)
+                    .sort_by(|reward1, reward2| reward1.public_key.cmp(&reward2.public_key));
+            }
+        }
+        GetEraRewardsResult {
+            api_version,
+            era_id,
+            switch_block_hash,
+            validators: validators.into_values().collect(),
+        }
+    }
+}
+
+/// The rewards of a validator for an era.
+#[derive(PartialEq, Eq, Debug, Serialize)]
+struct ValidatorRewards {
+    /// The validator's public key.
+    public_key: PublicKey,
+    /// The amount paid to the validator itself, in motes.
+    amount: U512,
+    /// The total amount paid to the validator's delegators, in motes.
+    delegators_amount: U512,
+    /// The amount paid to each of the validator's delegators, ordered by public key, if requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    delegators: Option<Vec<DelegatorReward>>,
+}
+
+impl ValidatorRewards {
+    fn new(public_key: &PublicKey) -> Self {
+        ValidatorRewards {
+            public_key: public_key.clone(),
+            amount: U512::zero(),
+            delegators_amount: U512::zero(),
+            delegators: None,
+        }
+    }
+}
+
+/// The rewards of a delegator for an era.
+#[derive(PartialEq, Eq, Debug, Serialize)]
+struct DelegatorReward {
+    /// The delegator's public key.
+    public_key: PublicKey,
+    /// The amount paid to the delegator, in motes.
+    amount: U512,
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_collect_rewards_per_validator() {
+        let mut rng = TestRng::new();
+        let validator = PublicKey::random(&mut rng);
+        let delegator1 = PublicKey::random(&mut rng);
+        let delegator2 = PublicKey::random(&mut rng);
+
+        let mut era_info = EraInfo::new();
+        era_info.seigniorage_allocations_mut().extend([
+            SeigniorageAllocation::delegator(delegator1.clone(), validator.clone(), 20.into()),
+            SeigniorageAllocation::validator(validator.clone(), 100.into()),
+            SeigniorageAllocation::delegator(delegator2.clone(), validator.clone(), 30.into()),
+        ]);
+        let block_hash = BlockHash::random(&mut rng);
+
+        let result = GetEraRewardsResult::new(
+            ProtocolVersion::V1_0_0,
+            EraId::new(3),
+            block_hash,
+            &era_info,
+            false,
+        );
+        assert_eq!(result.validators.len(), 1);
+        let rewards = &result.validators[0];
+        assert_eq!(rewards.public_key, validator);
+        assert_eq!(rewards.amount, U512::from(100));
+        assert_eq!(rewards.delegators_amount, U512::from(50));
+        assert!(rewards.delegators.is_none());
+
+        let result = GetEraRewardsResult::new(
+            ProtocolVersion::V1_0_0,
+            EraId::new(3),
+            block_hash,
+            &era_info,
+            true,
+        );
+        let mut expected_delegators = vec![
+            DelegatorReward {
+                public_key: delegator1,
+                amount: 20.into(),
+            },
+            DelegatorReward {
+                public_key: delegator2,
+                amount: 30.into(),
+            },
+        ];
+        expected_delegators.sort_by(|reward1, reward2| reward1.public_key.cmp(&reward2.public_key));
+        assert_eq!(result.validators[0].delegators, Some(expected_delegators));
+    }
+}
@@ -22,7 +22,7 @@ use std::{fmt::Debug, time::Instant};
 
 use datasize::DataSize;
 use futures::join;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::core::engine_state::{
     self, BalanceRequest, BalanceResult, GetBidsRequest, GetEraValidatorsError, QueryRequest,
@@ -42,15 +42,16 @@ use crate::{
             AcceptDeployRequest, BlockSynchronizerRequest, ChainspecRawBytesRequest,
             ConsensusRequest, ContractRuntimeRequest, MetricsRequest, NetworkInfoRequest,
             ReactorStatusRequest, RpcRequest, StorageRequest, UpgradeWatcherRequest,
+            WatchDeployRequest,
         },
         EffectBuilder, EffectExt, Effects, Responder,
     },
     reactor::main_reactor::MainEvent,
-    types::{ChainspecInfo, StatusFeed},
+    types::{BlockWithMetadata, ChainspecInfo, StatusFeed, ValidatorMatrix},
     utils::{self, ListeningError},
     NodeRng,
 };
-pub use config::Config;
+pub use config::{Config, MinBlockSignatureWeight};
 pub(crate) use event::Event;
 pub use speculative_exec_config::Config as SpeculativeExecConfig;
 
@@ -70,6 +71,7 @@ pub(crate) trait ReactorEventT:
     + From<StorageRequest>
     + From<ReactorStatusRequest>
     + From<BlockSynchronizerRequest>
+    + From<WatchDeployRequest>
     + Send
 {
 }
@@ -87,6 +89,7 @@ impl<REv> ReactorEventT for REv where
         + From<StorageRequest>
         + From<ReactorStatusRequest>
         + From<BlockSynchronizerRequest>
+        + From<WatchDeployRequest>
         + Send
         + 'static
 {
@@ -112,6 +115,11 @@ pub(crate) struct RpcServer {
     /// no fields and no methods because all that is needed to operate it is the
     /// spawned tokio task, so a unit struct will suffice here.
     speculative_exec: Option<()>,
+    /// The validators of each era, used to compute the signature weight of blocks.
+    validator_matrix: ValidatorMatrix,
+    /// The height of the highest block which has collected the signature weight required by the
+    /// config, if any has yet.
+    highest_exposed_block_height: Option<u64>,
 }
 
 impl RpcServer {
@@ -121,6 +129,7 @@ impl RpcServer {
         api_version: ProtocolVersion,
         network_name: String,
         node_startup_instant: Instant,
+        validator_matrix: ValidatorMatrix,
     ) -> Self {
         RpcServer {
             state: ComponentState::Uninitialized,
@@ -130,6 +139,8 @@ impl RpcServer {
             network_name,
             node_startup_instant,
             speculative_exec: None,
+            validator_matrix,
+            highest_exposed_block_height: None,
         }
     }
 }
@@ -183,6 +194,47 @@ impl RpcServer {
                 main_responder: responder,
             })
     }
+
+    /// Returns the height of the highest block which may be exposed, if any.
+    fn highest_exposed_block_height(&self) -> Option<u64> {
+        if self.config.min_block_signature_weight == MinBlockSignatureWeight::Any {
+            return Some(u64::MAX);
+        }
+        self.highest_exposed_block_height
+    }
+
+    /// Exposes the given block and all blocks below it if it has collected the signature weight
+    /// required by the config.
+    fn register_block_signatures(&mut self, block_with_metadata: &BlockWithMetadata) {
+        let block_height = block_with_metadata.block.height();
+        if self.highest_exposed_block_height >= Some(block_height) {
+            return;
+        }
+        let block_signatures = &block_with_metadata.block_signatures;
+        let signature_weight = match self
+            .validator_matrix
+            .validator_weights(block_signatures.era_id)
+        {
+            Some(validator_weights) => {
+                validator_weights.signature_weight(block_signatures.proofs.keys())
+            }
+            None => {
+                debug!(
+                    era_id = %block_signatures.era_id,
+                    "RpcServer: no validator weights to compute signature weight of block"
+                );
+                return;
+            }
+        };
+        if self
+            .config
+            .min_block_signature_weight
+            .is_met_by(signature_weight)
+        {
+            debug!(block_height, "RpcServer: exposing blocks up to height");
+            self.highest_exposed_block_height = Some(block_height);
+        }
+    }
 }
 
 impl<REv> Component<REv> for RpcServer
@@ -217,8 +269,21 @@ where
             }
             ComponentState::Initializing => match event {
                 Event::Initialize => {
-                    let (effects, state) = self.bind(self.config.enable_server, effect_builder);
+                    let (mut effects, state) = self.bind(self.config.enable_server, effect_builder);
                     <Self as InitializedComponent<MainEvent>>::set_state(self, state);
+                    if self.config.min_block_signature_weight != MinBlockSignatureWeight::Any {
+                        // Blocks stored before a restart are only announced again if they collect
+                        // further signatures, so check the highest one.
+                        effects.extend(
+                            effect_builder
+                                .get_highest_block_with_metadata_from_storage(true)
+                                .event(|maybe_block_with_metadata| {
+                                    Event::GetBlockWithMetadataResult {
+                                        maybe_block_with_metadata,
+                                    }
+                                }),
+                        );
+                    }
                     effects
                 }
                 Event::RpcRequest(_)
@@ -228,7 +293,9 @@ where
                 | Event::GetBidsResult { .. }
                 | Event::GetDeployResult { .. }
                 | Event::GetPeersResult { .. }
-                | Event::GetBalanceResult { .. } => {
+                | Event::GetBalanceResult { .. }
+                | Event::FinalitySignatureAccepted(_)
+                | Event::GetBlockWithMetadataResult { .. } => {
                     warn!(
                         ?event,
                         name = <Self as Component<MainEvent>>::name(self),
@@ -372,6 +439,31 @@ where
                         .await
                 }
                 .ignore(),
+                Event::RpcRequest(RpcRequest::GetHighestExposedBlockHeight { responder }) => {
+                    responder
+                        .respond(self.highest_exposed_block_height())
+                        .ignore()
+                }
+                Event::FinalitySignatureAccepted(block_hash) => {
+                    if self.config.min_block_signature_weight == MinBlockSignatureWeight::Any {
+                        return Effects::new();
+                    }
+                    effect_builder
+                        .get_block_with_metadata_from_storage(block_hash, true)
+                        .event(
+                            |maybe_block_with_metadata| Event::GetBlockWithMetadataResult {
+                                maybe_block_with_metadata,
+                            },
+                        )
+                }
+                Event::GetBlockWithMetadataResult {
+                    maybe_block_with_metadata,
+                } => {
+                    if let Some(block_with_metadata) = maybe_block_with_metadata {
+                        self.register_block_signatures(&block_with_metadata);
+                    }
+                    Effects::new()
+                }
                 Event::GetBlockTransfersResult {
                     block_hash: _,
                     result,
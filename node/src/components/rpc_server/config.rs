@@ -1,6 +1,8 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use crate::types::SignatureWeight;
+
 /// Default binding address for the JSON-RPC HTTP server.
 ///
 /// Uses a fixed port per node, but binds on any interface.
@@ -28,6 +30,9 @@ pub struct Config {
     pub max_body_bytes: u32,
     /// CORS origin.
     pub cors_origin: String,
+    /// The signature weight a block needs to have collected before it is exposed.
+    #[serde(default)]
+    pub min_block_signature_weight: MinBlockSignatureWeight,
 }
 
 impl Config {
@@ -39,6 +44,7 @@ impl Config {
             qps_limit: DEFAULT_QPS_LIMIT,
             max_body_bytes: DEFAULT_MAX_BODY_BYTES,
             cors_origin: DEFAULT_CORS_ORIGIN.to_string(),
+            min_block_signature_weight: MinBlockSignatureWeight::default(),
         }
     }
 }
@@ -48,3 +54,57 @@ impl Default for Config {
         Config::new()
     }
 }
+
+/// The signature weight a block needs to have collected before the JSON-RPC server exposes it.
+///
+/// Blocks above the highest block with the required weight are treated as if they were not
+/// stored yet, so that the latest block reported by the server never goes backwards.
+#[derive(Copy, Clone, DataSize, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MinBlockSignatureWeight {
+    /// Blocks are exposed as soon as they are stored.
+    #[default]
+    Any,
+    /// Blocks are exposed once at least one honest validator has signed them.
+    Weak,
+    /// Blocks are exposed once they have strict finality, i.e. no block on another fork can
+    /// collect as many signatures.
+    Strict,
+}
+
+impl MinBlockSignatureWeight {
+    /// Returns whether a block with the given signature weight may be exposed.
+    pub(crate) fn is_met_by(self, signature_weight: SignatureWeight) -> bool {
+        match self {
+            MinBlockSignatureWeight::Any => true,
+            MinBlockSignatureWeight::Weak => signature_weight != SignatureWeight::Insufficient,
+            MinBlockSignatureWeight::Strict => signature_weight == SignatureWeight::Strict,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_require_configured_signature_weight() {
+        let weights = [
+            SignatureWeight::Insufficient,
+            SignatureWeight::Weak,
+            SignatureWeight::Strict,
+        ];
+        let met_by = |min_weight: MinBlockSignatureWeight| {
+            weights
+                .iter()
+                .map(|weight| min_weight.is_met_by(*weight))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(met_by(MinBlockSignatureWeight::Any), [true, true, true]);
+        assert_eq!(met_by(MinBlockSignatureWeight::Weak), [false, true, true]);
+        assert_eq!(
+            met_by(MinBlockSignatureWeight::Strict),
+            [false, false, true]
+        );
+    }
+}
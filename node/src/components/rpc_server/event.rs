@@ -12,7 +12,7 @@ use casper_types::{system::auction::EraValidators, Transfer};
 
 use crate::{
     effect::{requests::RpcRequest, Responder},
-    types::{BlockHash, Deploy, DeployHash, DeployMetadataExt, NodeId},
+    types::{BlockHash, BlockWithMetadata, Deploy, DeployHash, DeployMetadataExt, NodeId},
 };
 
 #[derive(Debug, From)]
@@ -50,6 +50,11 @@ pub(crate) enum Event {
         result: Result<BalanceResult, engine_state::Error>,
         main_responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    /// A new finality signature for the given block has been accepted and stored.
+    FinalitySignatureAccepted(BlockHash),
+    GetBlockWithMetadataResult {
+        maybe_block_with_metadata: Option<BlockWithMetadata>,
+    },
 }
 
 impl Display for Event {
@@ -80,6 +85,19 @@ impl Display for Event {
                 write!(formatter, "get deploy result for {}: {:?}", hash, result)
             }
             Event::GetPeersResult { peers, .. } => write!(formatter, "get peers: {}", peers.len()),
+            Event::FinalitySignatureAccepted(block_hash) => {
+                write!(formatter, "finality signature accepted for {}", block_hash)
+            }
+            Event::GetBlockWithMetadataResult {
+                maybe_block_with_metadata,
+            } => match maybe_block_with_metadata {
+                Some(block_with_metadata) => write!(
+                    formatter,
+                    "get block with metadata result: {}",
+                    block_with_metadata.block.hash()
+                ),
+                None => write!(formatter, "get block with metadata result: none"),
+            },
         }
     }
 }
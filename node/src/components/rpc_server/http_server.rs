@@ -4,19 +4,7 @@ use casper_json_rpc::{CorsOrigin, RequestHandlersBuilder};
 use casper_types::ProtocolVersion;
 
 use super::{
-    rpcs::{
-        account::PutDeploy,
-        chain::{
-            GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
-        },
-        docs::ListRpcs,
-        info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
-        state::{
-            GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, GetTrie,
-            QueryBalance, QueryGlobalState,
-        },
-        RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
-    },
+    rpcs::{self, RpcVisitor, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams},
     ReactorEventT,
 };
 use crate::effect::EffectBuilder;
@@ -26,6 +14,27 @@ pub const RPC_API_PATH: &str = "rpc";
 
 pub const RPC_API_SERVER_NAME: &str = "JSON RPC";
 
+/// Registers the handlers of the visited RPCs.
+struct HandlerRegistrar<REv: 'static> {
+    effect_builder: EffectBuilder<REv>,
+    api_version: ProtocolVersion,
+    handlers: RequestHandlersBuilder,
+}
+
+impl<REv: ReactorEventT> RpcVisitor for HandlerRegistrar<REv> {
+    fn visit_with_params<T: RpcWithParams>(&mut self, _summary: Option<&str>) {
+        T::register_as_handler(self.effect_builder, self.api_version, &mut self.handlers);
+    }
+
+    fn visit_without_params<T: RpcWithoutParams>(&mut self, _summary: Option<&str>) {
+        T::register_as_handler(self.effect_builder, self.api_version, &mut self.handlers);
+    }
+
+    fn visit_with_optional_params<T: RpcWithOptionalParams>(&mut self, _summary: Option<&str>) {
+        T::register_as_handler(self.effect_builder, self.api_version, &mut self.handlers);
+    }
+}
+
 /// Run the JSON-RPC server.
 pub(super) async fn run<REv: ReactorEventT>(
     builder: Builder<AddrIncoming>,
@@ -35,28 +44,13 @@ pub(super) async fn run<REv: ReactorEventT>(
     max_body_bytes: u32,
     cors_origin: String,
 ) {
-    let mut handlers = RequestHandlersBuilder::new();
-    PutDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetBlock::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetBlockTransfers::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetStateRootHash::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetItem::register_as_handler(effect_builder, api_version, &mut handlers);
-    QueryGlobalState::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetBalance::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetAccountInfo::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetDeploy::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetPeers::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetStatus::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetEraInfoBySwitchBlock::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetEraSummary::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetAuctionInfo::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetTrie::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetValidatorChanges::register_as_handler(effect_builder, api_version, &mut handlers);
-    ListRpcs::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetDictionaryItem::register_as_handler(effect_builder, api_version, &mut handlers);
-    GetChainspec::register_as_handler(effect_builder, api_version, &mut handlers);
-    QueryBalance::register_as_handler(effect_builder, api_version, &mut handlers);
-    let handlers = handlers.build();
+    let mut registrar = HandlerRegistrar {
+        effect_builder,
+        api_version,
+        handlers: RequestHandlersBuilder::new(),
+    };
+    rpcs::visit_rpcs(&mut registrar);
+    let handlers = registrar.handlers.build();
 
     match cors_origin.as_str() {
         "" => {
@@ -31,9 +31,22 @@ use casper_types::ProtocolVersion;
 
 use super::{ReactorEventT, RpcRequest};
 use crate::effect::EffectBuilder;
+use account::{PutDeploy, PutDeployAndWatch};
+use chain::{
+    GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
+    GetTransfersByMemo,
+};
 pub use common::ErrorData;
-use docs::DocExample;
+use docs::{DocExample, ListRpcs};
 pub use error_code::ErrorCode;
+use info::{
+    GetBlockUtilization, GetChainspec, GetDeploy, GetDeploysByBodyHash, GetLeaderSchedule,
+    GetPeers, GetStatus, GetValidatorChanges,
+};
+use state::{
+    GetAccountInfo, GetAuctionInfo, GetAuctionSnapshot, GetBalance, GetDictionaryItem, GetEntity,
+    GetItem, GetTrie, GetUnbondingPurses, QueryBalance, QueryBalances, QueryGlobalState,
+};
 
 /// This setting causes the server to ignore extra fields in JSON-RPC requests other than the
 /// standard 'id', 'jsonrpc', 'method', and 'params' fields.
@@ -254,6 +267,104 @@ pub(super) trait RpcWithOptionalParams {
     ) -> Result<Self::ResponseResult, Error>;
 }
 
+/// A visitor of the RPCs served by the JSON-RPC server.
+///
+/// Both the registration of the RPC handlers and the OpenRPC schema returned by "rpc.discover" are
+/// driven by [`visit_rpcs`], so that the schema always describes the RPCs actually served.
+pub(super) trait RpcVisitor {
+    /// Visits an RPC requiring the "params" field, with its summary if it is documented.
+    fn visit_with_params<T: RpcWithParams>(&mut self, summary: Option<&str>);
+
+    /// Visits an RPC without a "params" field, with its summary if it is documented.
+    fn visit_without_params<T: RpcWithoutParams>(&mut self, summary: Option<&str>);
+
+    /// Visits an RPC with an optional "params" field, with its summary if it is documented.
+    fn visit_with_optional_params<T: RpcWithOptionalParams>(&mut self, summary: Option<&str>);
+}
+
+/// Visits every RPC served by the JSON-RPC server, in the order they are documented.
+///
+/// RPCs without a summary are served, but left out of the OpenRPC schema.
+pub(super) fn visit_rpcs<V: RpcVisitor>(visitor: &mut V) {
+    visitor.visit_with_params::<PutDeploy>(Some("receives a Deploy to be executed by the network"));
+    visitor.visit_with_params::<PutDeployAndWatch>(Some(
+        "receives a Deploy to be executed by the network and publishes its milestones on the event \
+        stream",
+    ));
+    visitor.visit_with_params::<GetDeploy>(Some("returns a Deploy from the network"));
+    visitor.visit_with_params::<GetDeploysByBodyHash>(Some(
+        "returns the hashes of all deploys stored with a given body hash, regardless of their \
+        approvals",
+    ));
+    visitor.visit_with_params::<GetAccountInfo>(Some("returns an Account from the network"));
+    visitor.visit_with_params::<GetDictionaryItem>(Some("returns an item from a Dictionary"));
+    visitor.visit_with_params::<QueryGlobalState>(Some(
+        "a query to global state using either a Block hash or state root hash",
+    ));
+    visitor.visit_with_params::<QueryBalance>(Some(
+        "query for a balance using a purse identifier and a state identifier",
+    ));
+    visitor.visit_with_params::<QueryBalances>(Some(
+        "query for the balances of several purses using purse identifiers and a state identifier",
+    ));
+    visitor.visit_with_params::<GetUnbondingPurses>(Some(
+        "returns the unbonding purses of a validator or delegator using a public key and a state \
+        identifier",
+    ));
+    visitor.visit_with_params::<GetAuctionSnapshot>(Some(
+        "returns the validator weights and delegation totals recorded by the auction for an era",
+    ));
+    visitor.visit_with_params::<GetEntity>(Some(
+        "returns an account or a contract with its named keys, and either its associated keys or \
+        its entry points, using an entity identifier and a state identifier",
+    ));
+    visitor.visit_without_params::<GetPeers>(Some("returns a list of peers connected to the node"));
+    visitor.visit_without_params::<GetStatus>(Some("returns the current status of the node"));
+    visitor.visit_without_params::<GetValidatorChanges>(Some(
+        "returns status changes of active validators",
+    ));
+    visitor.visit_without_params::<GetChainspec>(Some(
+        "returns the raw bytes of the chainspec.toml, genesis accounts.toml, and \
+        global_state.toml files",
+    ));
+    visitor.visit_without_params::<GetBlockUtilization>(Some(
+        "returns the utilization of the gas, size, deploy and transfer limits of the most recently \
+        executed blocks and of the last era",
+    ));
+    visitor.visit_without_params::<GetLeaderSchedule>(Some(
+        "returns the proposers of the current and upcoming rounds of the current era",
+    ));
+    visitor.visit_with_optional_params::<GetBlock>(Some("returns a Block from the network"));
+    visitor.visit_with_optional_params::<GetBlockTransfers>(Some(
+        "returns all transfers for a Block from the network",
+    ));
+    visitor.visit_with_params::<GetTransfersByMemo>(Some(
+        "returns the addresses of all transfers recorded with a given memo",
+    ));
+    visitor.visit_with_optional_params::<GetStateRootHash>(Some(
+        "returns a state root hash at a given Block",
+    ));
+    visitor.visit_with_params::<GetItem>(Some(
+        "returns a stored value from the network. This RPC is deprecated, use \
+        `query_global_state` instead.",
+    ));
+    visitor.visit_with_params::<GetTrie>(None);
+    visitor.visit_with_params::<GetBalance>(Some("returns a purse's balance from the network"));
+    visitor.visit_with_optional_params::<GetEraInfoBySwitchBlock>(Some(
+        "returns an EraInfo from the network",
+    ));
+    visitor.visit_with_optional_params::<GetAuctionInfo>(Some(
+        "returns the bids and validators as of either a specific block (by height or hash), or \
+        the most recently added block",
+    ));
+    visitor.visit_with_optional_params::<GetEraSummary>(Some(
+        "returns the era summary at either a specific block (by height or hash), or the most \
+        recently added block",
+    ));
+
+    visitor.visit_without_params::<ListRpcs>(None);
+}
+
 /// Start JSON RPC server with CORS enabled in a background.
 pub(super) async fn run_with_cors(
     builder: Builder<AddrIncoming>,
@@ -30,6 +30,12 @@ static PUT_DEPLOY_RESULT: Lazy<PutDeployResult> = Lazy::new(|| PutDeployResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     deploy_hash: *Deploy::doc_example().hash(),
 });
+static PUT_DEPLOY_AND_WATCH_RESULT: Lazy<PutDeployAndWatchResult> =
+    Lazy::new(|| PutDeployAndWatchResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        deploy_hash: *Deploy::doc_example().hash(),
+        watch_id: Some(1024),
+    });
 
 /// Params for "account_put_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -102,3 +108,68 @@ impl RpcWithParams for PutDeploy {
         }
     }
 }
+
+/// Result for "account_put_deploy_and_watch" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct PutDeployAndWatchResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The deploy hash.
+    pub deploy_hash: DeployHash,
+    /// The ID of the event stream event announcing the deploy's acceptance.  Subscribing to
+    /// `/events/main?start_from=<watch_id>` yields every `DeployMilestone` event of the deploy.
+    /// `None` if the event stream server is disabled.
+    pub watch_id: Option<u32>,
+}
+
+impl DocExample for PutDeployAndWatchResult {
+    fn doc_example() -> &'static Self {
+        &PUT_DEPLOY_AND_WATCH_RESULT
+    }
+}
+
+/// "account_put_deploy_and_watch" RPC
+pub struct PutDeployAndWatch {}
+
+#[async_trait]
+impl RpcWithParams for PutDeployAndWatch {
+    const METHOD: &'static str = "account_put_deploy_and_watch";
+    type RequestParams = PutDeployParams;
+    type ResponseResult = PutDeployAndWatchResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let deploy_hash = *params.deploy.hash();
+        let expires = params.deploy.header().expires();
+
+        let accept_deploy_result = effect_builder
+            .try_accept_deploy(Arc::new(params.deploy), None)
+            .await;
+
+        match accept_deploy_result {
+            Ok(_) => {
+                debug!(%deploy_hash, "deploy was stored");
+                let watch_id = effect_builder.watch_deploy(deploy_hash, expires).await;
+                let result = Self::ResponseResult {
+                    api_version,
+                    deploy_hash,
+                    watch_id,
+                };
+                Ok(result)
+            }
+            Err(error) => {
+                debug!(
+                    %deploy_hash,
+                    %error,
+                    "the deploy submitted by the client was invalid",
+                );
+                Err(Error::new(ErrorCode::InvalidDeploy, error.to_string()))
+            }
+        }
+    }
+}
@@ -14,11 +14,12 @@ use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::core::engine_state::{self, QueryResult};
 use casper_hashing::Digest;
-use casper_types::{Key, ProtocolVersion, Transfer};
+use casper_types::{Key, ProtocolVersion, Transfer, TransferAddr, TransferMemo};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, ReservedErrorCode, RpcRequest, RpcWithOptionalParams,
+    RpcWithParams,
 };
 use crate::{
     effect::EffectBuilder,
@@ -46,6 +47,15 @@ static GET_BLOCK_TRANSFERS_RESULT: Lazy<GetBlockTransfersResult> =
         block_hash: Some(*Block::doc_example().hash()),
         transfers: Some(vec![Transfer::default()]),
     });
+static GET_TRANSFERS_BY_MEMO_PARAMS: Lazy<GetTransfersByMemoParams> =
+    Lazy::new(|| GetTransfersByMemoParams {
+        memo: TransferMemo::new([42; 32]),
+    });
+static GET_TRANSFERS_BY_MEMO_RESULT: Lazy<GetTransfersByMemoResult> =
+    Lazy::new(|| GetTransfersByMemoResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        transfer_addrs: vec![TransferAddr::new([7; 32])],
+    });
 static GET_STATE_ROOT_HASH_PARAMS: Lazy<GetStateRootHashParams> =
     Lazy::new(|| GetStateRootHashParams {
         block_identifier: BlockIdentifier::Height(Block::doc_example().header().height()),
@@ -276,6 +286,62 @@ impl RpcWithOptionalParams for GetBlockTransfers {
     }
 }
 
+/// Params for "chain_get_transfers_by_memo" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetTransfersByMemoParams {
+    /// The memo the transfers were recorded with.
+    pub memo: TransferMemo,
+}
+
+impl DocExample for GetTransfersByMemoParams {
+    fn doc_example() -> &'static Self {
+        &GET_TRANSFERS_BY_MEMO_PARAMS
+    }
+}
+
+/// Result for "chain_get_transfers_by_memo" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetTransfersByMemoResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The addresses of the transfers recorded with the memo, in the order they were stored.
+    pub transfer_addrs: Vec<TransferAddr>,
+}
+
+impl DocExample for GetTransfersByMemoResult {
+    fn doc_example() -> &'static Self {
+        &GET_TRANSFERS_BY_MEMO_RESULT
+    }
+}
+
+/// "chain_get_transfers_by_memo" RPC.
+pub struct GetTransfersByMemo {}
+
+#[async_trait]
+impl RpcWithParams for GetTransfersByMemo {
+    const METHOD: &'static str = "chain_get_transfers_by_memo";
+    type RequestParams = GetTransfersByMemoParams;
+    type ResponseResult = GetTransfersByMemoResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let transfer_addrs = effect_builder
+            .get_transfers_by_memo_from_storage(params.memo)
+            .await;
+
+        Ok(Self::ResponseResult {
+            api_version,
+            transfer_addrs,
+        })
+    }
+}
+
 /// Params for "chain_get_state_root_hash" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -496,6 +562,27 @@ pub(super) async fn get_block_with_metadata<REv: ReactorEventT>(
         }
     };
 
+    // Blocks which have not collected the signature weight required by the config yet are treated
+    // as missing, and the highest block is the highest one which has.
+    let highest_exposed_block_height =
+        common::get_highest_exposed_block_height(effect_builder).await;
+    let maybe_result = match (maybe_result, highest_exposed_block_height) {
+        (Some(block_with_metadata), Some(max_height))
+            if block_with_metadata.block.height() <= max_height =>
+        {
+            Some(block_with_metadata)
+        }
+        (Some(_), Some(max_height)) if maybe_id.is_none() => {
+            effect_builder
+                .get_block_at_height_with_metadata_from_storage(
+                    max_height,
+                    only_from_available_block_range,
+                )
+                .await
+        }
+        _ => None,
+    };
+
     if let Some(block_with_metadata) = maybe_result {
         return Ok(block_with_metadata);
     }
@@ -15,7 +15,7 @@ use super::{
 use crate::{
     effect::EffectBuilder,
     reactor::QueueKind,
-    types::{json_compatibility::StoredValue, AvailableBlockRange, Block},
+    types::{json_compatibility::StoredValue, AvailableBlockRange, Block, BlockHeader},
 };
 
 pub(super) static MERKLE_PROOF: Lazy<String> = Lazy::new(|| {
@@ -124,6 +124,35 @@ pub(super) async fn missing_block_or_state_root_error<REv: ReactorEventT, E: Err
     Error::new(error_code, error_data)
 }
 
+/// Returns the height of the highest block which may be exposed, or `None` if no block has
+/// collected the signature weight required by the config yet.
+pub(super) async fn get_highest_exposed_block_height<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+) -> Option<u64> {
+    effect_builder
+        .make_request(
+            |responder| RpcRequest::GetHighestExposedBlockHeight { responder },
+            QueueKind::Api,
+        )
+        .await
+}
+
+/// Returns the header of the highest complete block which may be exposed.
+pub(super) async fn get_highest_exposed_block_header<REv: ReactorEventT>(
+    effect_builder: EffectBuilder<REv>,
+) -> Option<BlockHeader> {
+    let block_header = effect_builder
+        .get_highest_complete_block_header_from_storage()
+        .await?;
+    let highest_exposed_block_height = get_highest_exposed_block_height(effect_builder).await?;
+    if block_header.height() <= highest_exposed_block_height {
+        return Some(block_header);
+    }
+    effect_builder
+        .get_block_header_at_height_from_storage(highest_exposed_block_height, true)
+        .await
+}
+
 pub(super) async fn get_block<REv: ReactorEventT>(
     maybe_id: Option<BlockIdentifier>,
     only_from_available_block_range: bool,
@@ -16,16 +16,8 @@ use serde_json::{json, Value};
 use casper_types::ProtocolVersion;
 
 use super::{
-    account::PutDeploy,
-    chain::{
-        GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetEraSummary, GetStateRootHash,
-    },
-    info::{GetChainspec, GetDeploy, GetPeers, GetStatus, GetValidatorChanges},
-    state::{
-        GetAccountInfo, GetAuctionInfo, GetBalance, GetDictionaryItem, GetItem, QueryBalance,
-        QueryGlobalState,
-    },
-    Error, ReactorEventT, RpcWithOptionalParams, RpcWithParams, RpcWithoutParams,
+    visit_rpcs, Error, ReactorEventT, RpcVisitor, RpcWithOptionalParams, RpcWithParams,
+    RpcWithoutParams,
 };
 use crate::effect::EffectBuilder;
 
@@ -68,47 +60,7 @@ pub(crate) static OPEN_RPC_SCHEMA: Lazy<OpenRpcSchema> = Lazy::new(|| {
         },
     };
 
-    schema.push_with_params::<PutDeploy>("receives a Deploy to be executed by the network");
-    schema.push_with_params::<GetDeploy>("returns a Deploy from the network");
-    schema.push_with_params::<GetAccountInfo>("returns an Account from the network");
-    schema.push_with_params::<GetDictionaryItem>("returns an item from a Dictionary");
-    schema.push_with_params::<QueryGlobalState>(
-        "a query to global state using either a Block hash or state root hash",
-    );
-    schema.push_with_params::<QueryBalance>(
-        "query for a balance using a purse identifier and a state identifier",
-    );
-    schema.push_without_params::<GetPeers>("returns a list of peers connected to the node");
-    schema.push_without_params::<GetStatus>("returns the current status of the node");
-    schema
-        .push_without_params::<GetValidatorChanges>("returns status changes of active validators");
-    schema.push_without_params::<GetChainspec>(
-        "returns the raw bytes of the chainspec.toml, genesis accounts.toml, and \
-        global_state.toml files",
-    );
-    schema.push_with_optional_params::<GetBlock>("returns a Block from the network");
-    schema.push_with_optional_params::<GetBlockTransfers>(
-        "returns all transfers for a Block from the network",
-    );
-    schema.push_with_optional_params::<GetStateRootHash>(
-        "returns a state root hash at a given Block",
-    );
-    schema.push_with_params::<GetItem>(
-        "returns a stored value from the network. This RPC is deprecated, use \
-        `query_global_state` instead.",
-    );
-    schema.push_with_params::<GetBalance>("returns a purse's balance from the network");
-    schema.push_with_optional_params::<GetEraInfoBySwitchBlock>(
-        "returns an EraInfo from the network",
-    );
-    schema.push_with_optional_params::<GetAuctionInfo>(
-        "returns the bids and validators as of either a specific block (by height or hash), or \
-        the most recently added block",
-    );
-    schema.push_with_optional_params::<GetEraSummary>(
-        "returns the era summary at either a specific block (by height or hash), or the most \
-        recently added block",
-    );
+    visit_rpcs(&mut schema);
 
     schema
 });
@@ -297,6 +249,26 @@ impl OpenRpcSchema {
     }
 }
 
+impl RpcVisitor for OpenRpcSchema {
+    fn visit_with_params<T: RpcWithParams>(&mut self, summary: Option<&str>) {
+        if let Some(summary) = summary {
+            self.push_with_params::<T>(summary);
+        }
+    }
+
+    fn visit_without_params<T: RpcWithoutParams>(&mut self, summary: Option<&str>) {
+        if let Some(summary) = summary {
+            self.push_without_params::<T>(summary);
+        }
+    }
+
+    fn visit_with_optional_params<T: RpcWithOptionalParams>(&mut self, summary: Option<&str>) {
+        if let Some(summary) = summary {
+            self.push_with_optional_params::<T>(summary);
+        }
+    }
+}
+
 #[derive(Clone, PartialEq, Serialize, Deserialize, Debug, JsonSchema)]
 struct OpenRpcInfoField {
     version: String,
@@ -481,12 +453,20 @@ mod doc_example_impls {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashSet;
+
     use crate::{
         types::{Chainspec, ChainspecRawBytes},
         utils::Loadable,
     };
 
-    use super::*;
+    use super::{
+        super::{
+            chain::{GetBlock, GetBlockTransfers, GetEraInfoBySwitchBlock, GetStateRootHash},
+            state::GetAuctionInfo,
+        },
+        *,
+    };
 
     #[test]
     fn check_docs_example_version() {
@@ -573,4 +553,47 @@ mod tests {
         let incorrect_optional_params = check_optional_params_fields::<GetAuctionInfo>();
         assert!(incorrect_optional_params.is_empty())
     }
+
+    /// Records the method names of the visited RPCs, along with whether they are documented.
+    #[derive(Default)]
+    struct MethodCollector(Vec<(&'static str, bool)>);
+
+    impl RpcVisitor for MethodCollector {
+        fn visit_with_params<T: RpcWithParams>(&mut self, summary: Option<&str>) {
+            self.0.push((T::METHOD, summary.is_some()));
+        }
+
+        fn visit_without_params<T: RpcWithoutParams>(&mut self, summary: Option<&str>) {
+            self.0.push((T::METHOD, summary.is_some()));
+        }
+
+        fn visit_with_optional_params<T: RpcWithOptionalParams>(&mut self, summary: Option<&str>) {
+            self.0.push((T::METHOD, summary.is_some()));
+        }
+    }
+
+    #[test]
+    fn schema_should_document_served_rpcs() {
+        let mut collector = MethodCollector::default();
+        visit_rpcs(&mut collector);
+
+        let mut served_methods = HashSet::new();
+        for (method, _) in &collector.0 {
+            assert!(served_methods.insert(*method), "{} visited twice", method);
+        }
+        assert!(served_methods.contains(ListRpcs::METHOD));
+
+        let documented_methods = collector
+            .0
+            .iter()
+            .filter(|(_, documented)| *documented)
+            .map(|(method, _)| method.to_string())
+            .collect::<Vec<_>>();
+        let schema_methods = OPEN_RPC_SCHEMA
+            .methods
+            .iter()
+            .map(|method| method.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(documented_methods, schema_methods);
+    }
 }
@@ -35,6 +35,12 @@ pub enum ErrorCode {
     FailedToGetTrie = -32011,
     /// The requested state root hash was not found.
     NoSuchStateRoot = -32012,
+    /// Too many purse identifiers were passed in a single balances query.
+    TooManyPurseIdentifiers = -32013,
+    /// No auction snapshot has been recorded for the requested era.
+    NoSuchAuctionSnapshot = -32014,
+    /// No account or contract was found for the requested entity identifier.
+    NoSuchEntity = -32015,
 }
 
 impl From<ErrorCode> for (i64, &'static str) {
@@ -59,6 +65,9 @@ impl From<ErrorCode> for (i64, &'static str) {
             }
             ErrorCode::FailedToGetTrie => (error_code as i64, "Failed to get trie"),
             ErrorCode::NoSuchStateRoot => (error_code as i64, "No such state root"),
+            ErrorCode::TooManyPurseIdentifiers => (error_code as i64, "Too many purse identifiers"),
+            ErrorCode::NoSuchAuctionSnapshot => (error_code as i64, "No such auction snapshot"),
+            ErrorCode::NoSuchEntity => (error_code as i64, "No such entity"),
         }
     }
 }
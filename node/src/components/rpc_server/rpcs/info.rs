@@ -11,19 +11,23 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use tracing::info;
 
-use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey};
+use casper_hashing::Digest;
+use casper_types::{EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp};
 
 use super::{
     docs::{DocExample, DOCS_EXAMPLE_PROTOCOL_VERSION},
     Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithParams, RpcWithoutParams,
 };
 use crate::{
-    components::consensus::ValidatorChange,
+    components::{
+        consensus::{LeaderSchedule, ProposerSlot, ValidatorChange},
+        contract_runtime::{EraUtilization, ResourceUtilization, UtilizationSummary},
+    },
     effect::EffectBuilder,
     reactor::QueueKind,
     types::{
-        Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes, Deploy, DeployHash,
-        DeployMetadataExt, GetStatusResult, PeersMap,
+        Block, BlockHash, BlockHashAndHeight, ChainspecRawBytes, Deploy, DeployExpiryReason,
+        DeployHash, DeployMetadataExt, GetStatusResult, PeersMap,
     },
 };
 
@@ -39,7 +43,17 @@ static GET_DEPLOY_RESULT: Lazy<GetDeployResult> = Lazy::new(|| GetDeployResult {
         result: ExecutionResult::example().clone(),
     }],
     block_hash_and_height: None,
+    expiry_reason: None,
 });
+static GET_DEPLOYS_BY_BODY_HASH_PARAMS: Lazy<GetDeploysByBodyHashParams> =
+    Lazy::new(|| GetDeploysByBodyHashParams {
+        body_hash: *Deploy::doc_example().header().body_hash(),
+    });
+static GET_DEPLOYS_BY_BODY_HASH_RESULT: Lazy<GetDeploysByBodyHashResult> =
+    Lazy::new(|| GetDeploysByBodyHashResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        deploy_hashes: vec![*Deploy::doc_example().hash()],
+    });
 static GET_PEERS_RESULT: Lazy<GetPeersResult> = Lazy::new(|| GetPeersResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     peers: GetStatusResult::doc_example().peers.clone(),
@@ -57,6 +71,55 @@ static GET_CHAINSPEC_RESULT: Lazy<GetChainspecResult> = Lazy::new(|| GetChainspe
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     chainspec_bytes: ChainspecRawBytes::new(vec![42, 42].into(), None, None),
 });
+static GET_LEADER_SCHEDULE_RESULT: Lazy<GetLeaderScheduleResult> = Lazy::new(|| {
+    let start = Timestamp::from(1_605_573_564_032);
+    let slot = ProposerSlot {
+        round_id: start.millis(),
+        start: Some(start),
+        proposer: PublicKey::doc_example().clone(),
+    };
+    GetLeaderScheduleResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        leader_schedule: Some(LeaderSchedule {
+            era_id: EraId::new(1),
+            slots: vec![slot],
+        }),
+    }
+});
+static GET_BLOCK_UTILIZATION_RESULT: Lazy<GetBlockUtilizationResult> = Lazy::new(|| {
+    let summary = UtilizationSummary {
+        block_count: 2,
+        gas: ResourceUtilization {
+            used: 5_000_000_000,
+            limit: 20_000_000_000,
+            peak_percent: 40,
+        },
+        size: ResourceUtilization {
+            used: 2_048,
+            limit: 20_971_520,
+            peak_percent: 0,
+        },
+        deploy_slots: ResourceUtilization {
+            used: 3,
+            limit: 100,
+            peak_percent: 2,
+        },
+        transfer_slots: ResourceUtilization {
+            used: 10,
+            limit: 2_000,
+            peak_percent: 1,
+        },
+    };
+    GetBlockUtilizationResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        recent_blocks: summary,
+        last_era: Some(EraUtilization {
+            era_id: EraId::new(1),
+            switch_block_hash: *Block::doc_example().hash(),
+            summary,
+        }),
+    }
+});
 
 /// Params for "info_get_deploy" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -106,6 +169,10 @@ pub struct GetDeployResult {
     /// only provided if the full execution results are not know on this node.
     #[serde(skip_serializing_if = "Option::is_none", flatten)]
     pub block_hash_and_height: Option<BlockHashAndHeight>,
+    /// The reason this deploy was dropped by this node without having been executed, only
+    /// provided if it was.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expiry_reason: Option<DeployExpiryReason>,
 }
 
 impl DocExample for GetDeployResult {
@@ -152,7 +219,7 @@ impl RpcWithParams for GetDeploy {
             }
         };
 
-        let (execution_results, block_hash_and_height) = match metadata_ext {
+        let (execution_results, block_hash_and_height, expiry_reason) = match metadata_ext {
             DeployMetadataExt::Metadata(metadata) => (
                 metadata
                     .execution_results
@@ -160,11 +227,13 @@ impl RpcWithParams for GetDeploy {
                     .map(|(block_hash, result)| JsonExecutionResult { block_hash, result })
                     .collect(),
                 None,
+                None,
             ),
             DeployMetadataExt::BlockInfo(block_hash_and_height) => {
-                (Vec::new(), Some(block_hash_and_height))
+                (Vec::new(), Some(block_hash_and_height), None)
             }
-            DeployMetadataExt::Empty => (Vec::new(), None),
+            DeployMetadataExt::Expired(reason) => (Vec::new(), None, Some(reason)),
+            DeployMetadataExt::Empty => (Vec::new(), None, None),
         };
 
         let result = Self::ResponseResult {
@@ -172,11 +241,68 @@ impl RpcWithParams for GetDeploy {
             deploy,
             execution_results,
             block_hash_and_height,
+            expiry_reason,
         };
         Ok(result)
     }
 }
 
+/// Params for "info_get_deploys_by_body_hash" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDeploysByBodyHashParams {
+    /// The body hash of the deploys.
+    pub body_hash: Digest,
+}
+
+impl DocExample for GetDeploysByBodyHashParams {
+    fn doc_example() -> &'static Self {
+        &GET_DEPLOYS_BY_BODY_HASH_PARAMS
+    }
+}
+
+/// Result for "info_get_deploys_by_body_hash" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetDeploysByBodyHashResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The hashes of the deploys stored with the body hash, in the order they were stored.
+    pub deploy_hashes: Vec<DeployHash>,
+}
+
+impl DocExample for GetDeploysByBodyHashResult {
+    fn doc_example() -> &'static Self {
+        &GET_DEPLOYS_BY_BODY_HASH_RESULT
+    }
+}
+
+/// "info_get_deploys_by_body_hash" RPC.
+pub struct GetDeploysByBodyHash {}
+
+#[async_trait]
+impl RpcWithParams for GetDeploysByBodyHash {
+    const METHOD: &'static str = "info_get_deploys_by_body_hash";
+    type RequestParams = GetDeploysByBodyHashParams;
+    type ResponseResult = GetDeploysByBodyHashResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let deploy_hashes = effect_builder
+            .get_deploy_hashes_by_body_hash_from_storage(params.body_hash)
+            .await;
+
+        Ok(Self::ResponseResult {
+            api_version,
+            deploy_hashes,
+        })
+    }
+}
+
 /// Result for "info_get_peers" RPC response.
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
 #[serde(deny_unknown_fields)]
@@ -390,3 +516,87 @@ impl RpcWithoutParams for GetChainspec {
         Ok(result)
     }
 }
+
+/// Result for the "info_get_block_utilization" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetBlockUtilizationResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The utilization of the capacity of the most recently executed blocks.
+    pub recent_blocks: UtilizationSummary,
+    /// The utilization of the capacity of the blocks of the last era concluded by a switch block
+    /// executed by this node, if any.
+    pub last_era: Option<EraUtilization>,
+}
+
+impl DocExample for GetBlockUtilizationResult {
+    fn doc_example() -> &'static Self {
+        &GET_BLOCK_UTILIZATION_RESULT
+    }
+}
+
+/// "info_get_block_utilization" RPC.
+pub struct GetBlockUtilization {}
+
+#[async_trait]
+impl RpcWithoutParams for GetBlockUtilization {
+    const METHOD: &'static str = "info_get_block_utilization";
+    type ResponseResult = GetBlockUtilizationResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+    ) -> Result<Self::ResponseResult, Error> {
+        let block_utilization = effect_builder.get_block_utilization().await;
+        Ok(Self::ResponseResult {
+            api_version,
+            recent_blocks: block_utilization.recent_blocks,
+            last_era: block_utilization.last_era,
+        })
+    }
+}
+
+/// The number of upcoming rounds returned by the "info_get_leader_schedule" RPC.
+const LEADER_SCHEDULE_ROUNDS: usize = 100;
+
+/// Result for the "info_get_leader_schedule" RPC.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetLeaderScheduleResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The proposers of the current and upcoming rounds of the current era, if consensus is
+    /// running.
+    pub leader_schedule: Option<LeaderSchedule>,
+}
+
+impl DocExample for GetLeaderScheduleResult {
+    fn doc_example() -> &'static Self {
+        &GET_LEADER_SCHEDULE_RESULT
+    }
+}
+
+/// "info_get_leader_schedule" RPC.
+pub struct GetLeaderSchedule {}
+
+#[async_trait]
+impl RpcWithoutParams for GetLeaderSchedule {
+    const METHOD: &'static str = "info_get_leader_schedule";
+    type ResponseResult = GetLeaderScheduleResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+    ) -> Result<Self::ResponseResult, Error> {
+        let leader_schedule = effect_builder
+            .get_consensus_leader_schedule(LEADER_SCHEDULE_ROUNDS)
+            .await;
+        Ok(Self::ResponseResult {
+            api_version,
+            leader_schedule,
+        })
+    }
+}
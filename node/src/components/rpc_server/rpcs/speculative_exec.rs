@@ -12,7 +12,7 @@ use serde::{Deserialize, Serialize};
 
 use casper_execution_engine::core::engine_state::Error as EngineStateError;
 use casper_json_rpc::ReservedErrorCode;
-use casper_types::{ExecutionResult, ProtocolVersion};
+use casper_types::{ExecutionResult, ProtocolVersion, U512};
 
 use super::{
     chain::BlockIdentifier,
@@ -29,11 +29,13 @@ use crate::{
 static SPECULATIVE_EXEC_PARAMS: Lazy<SpeculativeExecParams> = Lazy::new(|| SpeculativeExecParams {
     block_identifier: Some(BlockIdentifier::Hash(*Block::doc_example().hash())),
     deploy: Deploy::doc_example().clone(),
+    gas_profile: false,
 });
 static SPECULATIVE_EXEC_RESULT: Lazy<SpeculativeExecResult> = Lazy::new(|| SpeculativeExecResult {
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     block_hash: *Block::doc_example().hash(),
     execution_result: ExecutionResult::example().clone(),
+    gas_profile: None,
 });
 
 /// Params for "speculative_exec" RPC request.
@@ -44,6 +46,15 @@ pub struct SpeculativeExecParams {
     pub block_identifier: Option<BlockIdentifier>,
     /// Deploy to execute.
     pub deploy: Deploy,
+    /// Whether to report the gas consumed per host function. If `false` or omitted, no gas
+    /// profile is included in the result.
+    #[serde(default = "gas_profile_default")]
+    pub gas_profile: bool,
+}
+
+/// The default for `SpeculativeExecParams::gas_profile`.
+fn gas_profile_default() -> bool {
+    false
 }
 
 impl DocExample for SpeculativeExecParams {
@@ -63,6 +74,23 @@ pub struct SpeculativeExecResult {
     pub block_hash: BlockHash,
     /// Result of the execution.
     pub execution_result: ExecutionResult,
+    /// Gas consumed per host function, only provided if requested.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub gas_profile: Option<Vec<GasProfileEntry>>,
+}
+
+/// The gas consumed by a single host function or opcode group during speculative execution.
+///
+/// Gas charged for executing Wasm instructions is reported under the `wasm_opcodes` group, while
+/// gas charged outside of any host function (e.g. for system contract calls made on behalf of the
+/// deploy) is reported under the `other` group.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GasProfileEntry {
+    /// The name of the host function or opcode group.
+    pub group: String,
+    /// The gas consumed.
+    pub cost: U512,
 }
 
 impl DocExample for SpeculativeExecResult {
@@ -88,6 +116,7 @@ impl RpcWithParams for SpeculativeExec {
         let SpeculativeExecParams {
             block_identifier: maybe_block_id,
             deploy,
+            gas_profile,
         } = params;
         let deploy = Arc::new(deploy);
         let only_from_available_block_range = true;
@@ -114,15 +143,25 @@ impl RpcWithParams for SpeculativeExec {
         }
 
         let result = effect_builder
-            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy))
+            .speculative_execute_deploy(execution_prestate, Arc::clone(&deploy), gas_profile)
             .await;
 
         match result {
-            Ok(Some(execution_result)) => {
+            Ok(Some((execution_result, maybe_gas_profile))) => {
+                let gas_profile = maybe_gas_profile.map(|gas_profile| {
+                    gas_profile
+                        .into_iter()
+                        .map(|(group, gas)| GasProfileEntry {
+                            group,
+                            cost: gas.value(),
+                        })
+                        .collect()
+                });
                 let result = Self::ResponseResult {
                     api_version,
                     block_hash,
                     execution_result,
+                    gas_profile,
                 };
                 Ok(result)
             }
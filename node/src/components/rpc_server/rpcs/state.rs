@@ -12,7 +12,13 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, error, info, warn};
 
 use casper_execution_engine::{
-    core::engine_state::{self, BalanceResult, GetBidsResult, QueryResult},
+    core::engine_state::{
+        self, BalanceIdentifier, BalanceResult, BalancesRequest, BalancesResult,
+        Entity as EngineEntity, EntityIdentifier as EngineEntityIdentifier, GetBidsResult,
+        GetEntityRequest, GetEntityResult as EngineGetEntityResult, GetUnbondingPursesRequest,
+        GetUnbondingPursesResult as EngineGetUnbondingPursesResult, QueryResult,
+        UnbondingPurseKind,
+    },
     storage::trie::merkle_proof::TrieMerkleProof,
 };
 use casper_hashing::Digest;
@@ -20,8 +26,9 @@ use casper_json_rpc::ReservedErrorCode;
 use casper_types::{
     account::AccountHash,
     bytesrepr::{Bytes, ToBytes},
-    CLValue, Key, ProtocolVersion, PublicKey, SecretKey, StoredValue as DomainStoredValue, URef,
-    U512,
+    system::auction::{AuctionSnapshot, UnbondingPurse, ValidatorSnapshot},
+    AccessRights, CLValue, ContractHash, EraId, Key, ProtocolVersion, PublicKey, SecretKey,
+    StoredValue as DomainStoredValue, URef, U512,
 };
 
 use crate::{
@@ -34,7 +41,9 @@ use crate::{
         Error, ErrorCode, ReactorEventT, RpcRequest, RpcWithOptionalParams, RpcWithParams,
     },
     types::{
-        json_compatibility::{Account as JsonAccount, AuctionState, StoredValue},
+        json_compatibility::{
+            Account as JsonAccount, AuctionState, Contract as JsonContract, StoredValue,
+        },
         Block, BlockHash, JsonBlockHeader,
     },
 };
@@ -129,6 +138,98 @@ static QUERY_BALANCE_RESULT: Lazy<QueryBalanceResult> = Lazy::new(|| QueryBalanc
     api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
     balance: U512::from(123_456),
 });
+static QUERY_BALANCES_PARAMS: Lazy<QueryBalancesParams> = Lazy::new(|| QueryBalancesParams {
+    state_identifier: Some(GlobalStateIdentifier::BlockHash(
+        *Block::doc_example().hash(),
+    )),
+    purse_identifiers: vec![
+        PurseIdentifier::MainPurseUnderAccountHash(AccountHash::new([9u8; 32])),
+        PurseIdentifier::MainPurseUnderAccountHash(AccountHash::new([10u8; 32])),
+    ],
+});
+static QUERY_BALANCES_RESULT: Lazy<QueryBalancesResult> = Lazy::new(|| QueryBalancesResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    balances: vec![
+        PurseBalance {
+            purse_identifier: PurseIdentifier::MainPurseUnderAccountHash(AccountHash::new(
+                [9u8; 32],
+            )),
+            balance: Some(U512::from(123_456)),
+        },
+        PurseBalance {
+            purse_identifier: PurseIdentifier::MainPurseUnderAccountHash(AccountHash::new(
+                [10u8; 32],
+            )),
+            balance: None,
+        },
+    ],
+});
+
+static GET_UNBONDING_PURSES_PARAMS: Lazy<GetUnbondingPursesParams> =
+    Lazy::new(|| GetUnbondingPursesParams {
+        state_identifier: Some(GlobalStateIdentifier::BlockHash(
+            *Block::doc_example().hash(),
+        )),
+        public_key: PublicKey::from(&SecretKey::ed25519_from_bytes([0; 32]).unwrap()),
+    });
+static GET_UNBONDING_PURSES_RESULT: Lazy<GetUnbondingPursesResult> = Lazy::new(|| {
+    let unbonder_public_key = PublicKey::from(&SecretKey::ed25519_from_bytes([0; 32]).unwrap());
+    let validator_public_key = PublicKey::from(&SecretKey::ed25519_from_bytes([42; 32]).unwrap());
+    let unbonding_purse = UnbondingPurse::new(
+        URef::new([250; 32], AccessRights::READ_ADD_WRITE),
+        validator_public_key,
+        unbonder_public_key,
+        EraId::new(10),
+        U512::from(1_000),
+        None,
+    );
+    GetUnbondingPursesResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        unbonding_purses: vec![UnbondingPurseInfo {
+            legacy_withdraw: false,
+            release_era: EraId::new(17),
+            unbonding_purse,
+        }],
+    }
+});
+
+static GET_AUCTION_SNAPSHOT_PARAMS: Lazy<GetAuctionSnapshotParams> =
+    Lazy::new(|| GetAuctionSnapshotParams {
+        state_identifier: Some(GlobalStateIdentifier::BlockHash(
+            *Block::doc_example().hash(),
+        )),
+        era_id: EraId::new(42),
+    });
+static GET_AUCTION_SNAPSHOT_RESULT: Lazy<GetAuctionSnapshotResult> = Lazy::new(|| {
+    let validator_public_key = PublicKey::from(&SecretKey::ed25519_from_bytes([42; 32]).unwrap());
+    GetAuctionSnapshotResult {
+        api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+        era_id: EraId::new(42),
+        auction_snapshot: AuctionSnapshot::new(vec![ValidatorSnapshot::new(
+            validator_public_key,
+            U512::from(1_500),
+            U512::from(500),
+            2,
+            10,
+        )]),
+        merkle_proof: MERKLE_PROOF.clone(),
+    }
+});
+static GET_ENTITY_PARAMS: Lazy<GetEntityParams> = Lazy::new(|| GetEntityParams {
+    state_identifier: Some(GlobalStateIdentifier::BlockHash(
+        *Block::doc_example().hash(),
+    )),
+    entity_identifier: EntityIdentifier::PublicKey(PublicKey::from(
+        &SecretKey::ed25519_from_bytes([0; 32]).unwrap(),
+    )),
+});
+static GET_ENTITY_RESULT: Lazy<GetEntityResult> = Lazy::new(|| GetEntityResult {
+    api_version: DOCS_EXAMPLE_PROTOCOL_VERSION,
+    entity: JsonEntity::Account(JsonAccount::doc_example().clone()),
+});
+
+/// The maximum number of purses whose balances can be queried in a single "query_balances" RPC.
+pub const MAX_QUERY_BALANCES_PURSES: usize = 100;
 
 /// Params for "state_get_item" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -399,7 +500,7 @@ impl RpcWithOptionalParams for GetAuctionInfo {
             .await;
 
         let bids = match get_bids_result {
-            Ok(GetBidsResult::Success { bids }) => bids,
+            Ok(GetBidsResult::Success { bids, .. }) => bids,
             Ok(GetBidsResult::RootNotFound) => {
                 error!(
                     block_hash=?block.hash(),
@@ -841,10 +942,7 @@ impl RpcWithParams for QueryGlobalState {
         params: Self::RequestParams,
     ) -> Result<Self::ResponseResult, Error> {
         let (state_root_hash, maybe_block_header) = match params.state_identifier {
-            None => match effect_builder
-                .get_highest_complete_block_header_from_storage()
-                .await
-            {
+            None => match common::get_highest_exposed_block_header(effect_builder).await {
                 None => {
                     return Err(Error::new(
                         ErrorCode::NoSuchBlock,
@@ -889,7 +987,7 @@ impl RpcWithParams for QueryGlobalState {
 }
 
 /// Identifier of a purse.
-#[derive(Serialize, Deserialize, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, JsonSchema)]
 #[serde(deny_unknown_fields, rename_all = "snake_case")]
 pub enum PurseIdentifier {
     /// The main purse of the account identified by this public key.
@@ -947,10 +1045,7 @@ impl RpcWithParams for QueryBalance {
         params: Self::RequestParams,
     ) -> Result<Self::ResponseResult, Error> {
         let state_root_hash = match params.state_identifier {
-            None => match effect_builder
-                .get_highest_complete_block_header_from_storage()
-                .await
-            {
+            None => match common::get_highest_exposed_block_header(effect_builder).await {
                 None => {
                     return Err(Error::new(
                         ErrorCode::NoSuchBlock,
@@ -1029,6 +1124,521 @@ impl RpcWithParams for QueryBalance {
     }
 }
 
+/// Params for "query_balances" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+pub struct QueryBalancesParams {
+    /// The state identifier used for the query, if none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// The identifiers of the purses whose balances are queried.
+    pub purse_identifiers: Vec<PurseIdentifier>,
+}
+
+impl DocExample for QueryBalancesParams {
+    fn doc_example() -> &'static Self {
+        &QUERY_BALANCES_PARAMS
+    }
+}
+
+/// The balance of a single purse in a "query_balances" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct PurseBalance {
+    /// The identifier of the purse, as passed in the request.
+    pub purse_identifier: PurseIdentifier,
+    /// The balance represented in motes, or `None` if the account or purse doesn't exist.
+    pub balance: Option<U512>,
+}
+
+/// Result for "query_balances" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct QueryBalancesResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The balances, in the order of the requested purse identifiers.
+    pub balances: Vec<PurseBalance>,
+}
+
+impl DocExample for QueryBalancesResult {
+    fn doc_example() -> &'static Self {
+        &QUERY_BALANCES_RESULT
+    }
+}
+
+/// "query_balances" RPC.
+pub struct QueryBalances {}
+
+#[async_trait]
+impl RpcWithParams for QueryBalances {
+    const METHOD: &'static str = "query_balances";
+    type RequestParams = QueryBalancesParams;
+    type ResponseResult = QueryBalancesResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        if params.purse_identifiers.len() > MAX_QUERY_BALANCES_PURSES {
+            return Err(Error::new(
+                ErrorCode::TooManyPurseIdentifiers,
+                format!(
+                    "query-balances accepts at most {} purse identifiers, got {}",
+                    MAX_QUERY_BALANCES_PURSES,
+                    params.purse_identifiers.len()
+                ),
+            ));
+        }
+
+        let state_root_hash = match params.state_identifier {
+            None => match common::get_highest_exposed_block_header(effect_builder).await {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "query-balances failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => *block_header.state_root_hash(),
+            },
+            Some(state_identifier) => {
+                let (state_root_hash, _) =
+                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
+                        .await?;
+                state_root_hash
+            }
+        };
+
+        let identifiers = params
+            .purse_identifiers
+            .iter()
+            .map(|purse_identifier| match purse_identifier {
+                PurseIdentifier::MainPurseUnderPublicKey(public_key) => {
+                    BalanceIdentifier::Account(public_key.to_account_hash())
+                }
+                PurseIdentifier::MainPurseUnderAccountHash(account_hash) => {
+                    BalanceIdentifier::Account(*account_hash)
+                }
+                PurseIdentifier::PurseUref(purse_uref) => BalanceIdentifier::Purse(*purse_uref),
+            })
+            .collect();
+
+        let balances = match effect_builder
+            .get_balances(BalancesRequest::new(state_root_hash, identifiers))
+            .await
+        {
+            Ok(BalancesResult::Success(balances)) => balances,
+            Ok(BalancesResult::RootNotFound) => {
+                info!(%state_root_hash, "query-balances failed: root not found");
+                return Err(Error::new(
+                    ErrorCode::FailedToGetBalance,
+                    format!("root hash {} not found", state_root_hash),
+                ));
+            }
+            Err(error) => {
+                info!("query-balances failed to execute: {}", error);
+                return Err(Error::new(
+                    ErrorCode::GetBalanceFailedToExecute,
+                    error.to_string(),
+                ));
+            }
+        };
+
+        let balances = params
+            .purse_identifiers
+            .into_iter()
+            .zip(balances)
+            .map(|(purse_identifier, (_, balance))| PurseBalance {
+                purse_identifier,
+                balance,
+            })
+            .collect();
+
+        let result = Self::ResponseResult {
+            api_version,
+            balances,
+        };
+        Ok(result)
+    }
+}
+
+/// Params for "state_get_unbonding_purses" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetUnbondingPursesParams {
+    /// The state identifier used for the query, if none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// The public key of the validator or delegator whose unbonding purses are queried.
+    pub public_key: PublicKey,
+}
+
+impl DocExample for GetUnbondingPursesParams {
+    fn doc_example() -> &'static Self {
+        &GET_UNBONDING_PURSES_PARAMS
+    }
+}
+
+/// A single unbonding purse in a "state_get_unbonding_purses" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct UnbondingPurseInfo {
+    /// Whether the purse is a legacy withdraw purse, created before unbonds were introduced.
+    pub legacy_withdraw: bool,
+    /// The era in which the unbonded amount is paid out to the unbonder.
+    pub release_era: EraId,
+    /// The unbonding purse.
+    pub unbonding_purse: UnbondingPurse,
+}
+
+/// Result for "state_get_unbonding_purses" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetUnbondingPursesResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The unbonding purses of the given public key, both as validator and as delegator.
+    pub unbonding_purses: Vec<UnbondingPurseInfo>,
+}
+
+impl DocExample for GetUnbondingPursesResult {
+    fn doc_example() -> &'static Self {
+        &GET_UNBONDING_PURSES_RESULT
+    }
+}
+
+/// "state_get_unbonding_purses" RPC.
+pub struct GetUnbondingPurses {}
+
+#[async_trait]
+impl RpcWithParams for GetUnbondingPurses {
+    const METHOD: &'static str = "state_get_unbonding_purses";
+    type RequestParams = GetUnbondingPursesParams;
+    type ResponseResult = GetUnbondingPursesResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let state_root_hash = match params.state_identifier {
+            None => match common::get_highest_exposed_block_header(effect_builder).await {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "get-unbonding-purses failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => *block_header.state_root_hash(),
+            },
+            Some(state_identifier) => {
+                let (state_root_hash, _) =
+                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
+                        .await?;
+                state_root_hash
+            }
+        };
+
+        let request = GetUnbondingPursesRequest::new(state_root_hash, params.public_key);
+        let entries = match effect_builder.get_unbonding_purses(request).await {
+            Ok(EngineGetUnbondingPursesResult::Success { unbonding_purses }) => unbonding_purses,
+            Ok(EngineGetUnbondingPursesResult::RootNotFound) => {
+                info!(%state_root_hash, "get-unbonding-purses failed: root not found");
+                return Err(Error::new(
+                    ErrorCode::NoSuchStateRoot,
+                    format!("root hash {} not found", state_root_hash),
+                ));
+            }
+            Err(error) => {
+                info!("get-unbonding-purses failed to execute: {}", error);
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    error.to_string(),
+                ));
+            }
+        };
+
+        let unbonding_purses = entries
+            .into_iter()
+            .map(|entry| UnbondingPurseInfo {
+                legacy_withdraw: entry.kind() == UnbondingPurseKind::Withdraw,
+                release_era: entry.release_era(),
+                unbonding_purse: entry.unbonding_purse().clone(),
+            })
+            .collect();
+
+        let result = Self::ResponseResult {
+            api_version,
+            unbonding_purses,
+        };
+        Ok(result)
+    }
+}
+
+/// Params for "state_get_auction_snapshot" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetAuctionSnapshotParams {
+    /// The state identifier used for the query, if none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// The era whose auction snapshot is queried.
+    pub era_id: EraId,
+}
+
+impl DocExample for GetAuctionSnapshotParams {
+    fn doc_example() -> &'static Self {
+        &GET_AUCTION_SNAPSHOT_PARAMS
+    }
+}
+
+/// Result for "state_get_auction_snapshot" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+pub struct GetAuctionSnapshotResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The era of the auction snapshot.
+    pub era_id: EraId,
+    /// The validator weights and delegation totals of the era.
+    pub auction_snapshot: AuctionSnapshot,
+    /// The Merkle proof.
+    pub merkle_proof: String,
+}
+
+impl DocExample for GetAuctionSnapshotResult {
+    fn doc_example() -> &'static Self {
+        &GET_AUCTION_SNAPSHOT_RESULT
+    }
+}
+
+/// "state_get_auction_snapshot" RPC.
+pub struct GetAuctionSnapshot {}
+
+#[async_trait]
+impl RpcWithParams for GetAuctionSnapshot {
+    const METHOD: &'static str = "state_get_auction_snapshot";
+    type RequestParams = GetAuctionSnapshotParams;
+    type ResponseResult = GetAuctionSnapshotResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let state_root_hash = match params.state_identifier {
+            None => match common::get_highest_exposed_block_header(effect_builder).await {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "get-auction-snapshot failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => *block_header.state_root_hash(),
+            },
+            Some(state_identifier) => {
+                let (state_root_hash, _) =
+                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
+                        .await?;
+                state_root_hash
+            }
+        };
+
+        let era_id = params.era_id;
+        let query_result = effect_builder
+            .make_request(
+                |responder| RpcRequest::QueryGlobalState {
+                    state_root_hash,
+                    base_key: Key::AuctionSnapshot(era_id),
+                    path: vec![],
+                    responder,
+                },
+                QueueKind::Api,
+            )
+            .await;
+        if let Ok(QueryResult::ValueNotFound(_)) = query_result {
+            info!(%era_id, "get-auction-snapshot failed: no snapshot recorded");
+            return Err(Error::new(
+                ErrorCode::NoSuchAuctionSnapshot,
+                format!("no auction snapshot recorded for {}", era_id),
+            ));
+        }
+
+        let (stored_value, proofs) =
+            handle_query_result(effect_builder, state_root_hash, query_result).await?;
+        let (stored_value, merkle_proof) = common::encode_query_success(stored_value, proofs)?;
+        let auction_snapshot = match stored_value {
+            StoredValue::AuctionSnapshot(auction_snapshot) => auction_snapshot,
+            _ => {
+                let error_msg = format!("failed to get auction snapshot for {}", era_id);
+                info!(?stored_value, "{}", error_msg);
+                return Err(Error::new(ErrorCode::QueryFailed, error_msg));
+            }
+        };
+
+        let result = Self::ResponseResult {
+            api_version,
+            era_id,
+            auction_snapshot,
+            merkle_proof,
+        };
+        Ok(result)
+    }
+}
+
+/// Identifier of an account or a contract.
+#[derive(Serialize, Deserialize, Debug, JsonSchema, Clone)]
+#[serde(deny_unknown_fields)]
+pub enum EntityIdentifier {
+    /// The account identified by the account hash of this public key.
+    PublicKey(PublicKey),
+    /// The account identified by this account hash.
+    AccountHash(AccountHash),
+    /// The contract identified by this contract hash.
+    ContractHash(ContractHash),
+}
+
+impl From<EntityIdentifier> for EngineEntityIdentifier {
+    fn from(entity_identifier: EntityIdentifier) -> Self {
+        match entity_identifier {
+            EntityIdentifier::PublicKey(public_key) => {
+                EngineEntityIdentifier::PublicKey(public_key)
+            }
+            EntityIdentifier::AccountHash(account_hash) => {
+                EngineEntityIdentifier::AccountHash(account_hash)
+            }
+            EntityIdentifier::ContractHash(contract_hash) => {
+                EngineEntityIdentifier::ContractHash(contract_hash)
+            }
+        }
+    }
+}
+
+/// Params for "state_get_entity" RPC request.
+#[derive(Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEntityParams {
+    /// The state identifier used for the query, if none is passed
+    /// the tip of the chain will be used.
+    pub state_identifier: Option<GlobalStateIdentifier>,
+    /// The identifier of the account or contract.
+    pub entity_identifier: EntityIdentifier,
+}
+
+impl DocExample for GetEntityParams {
+    fn doc_example() -> &'static Self {
+        &GET_ENTITY_PARAMS
+    }
+}
+
+/// An account or a contract in a "state_get_entity" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+pub enum JsonEntity {
+    /// An account, with its named keys and associated keys.
+    Account(JsonAccount),
+    /// A contract, with its named keys and entry points.
+    Contract {
+        /// The hash of the contract.
+        contract_hash: ContractHash,
+        /// The contract.
+        contract: JsonContract,
+    },
+}
+
+/// Result for "state_get_entity" RPC response.
+#[derive(PartialEq, Eq, Serialize, Deserialize, Debug, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct GetEntityResult {
+    /// The RPC API version.
+    #[schemars(with = "String")]
+    pub api_version: ProtocolVersion,
+    /// The account or contract.
+    pub entity: JsonEntity,
+}
+
+impl DocExample for GetEntityResult {
+    fn doc_example() -> &'static Self {
+        &GET_ENTITY_RESULT
+    }
+}
+
+/// "state_get_entity" RPC.
+pub struct GetEntity {}
+
+#[async_trait]
+impl RpcWithParams for GetEntity {
+    const METHOD: &'static str = "state_get_entity";
+    type RequestParams = GetEntityParams;
+    type ResponseResult = GetEntityResult;
+
+    async fn do_handle_request<REv: ReactorEventT>(
+        effect_builder: EffectBuilder<REv>,
+        api_version: ProtocolVersion,
+        params: Self::RequestParams,
+    ) -> Result<Self::ResponseResult, Error> {
+        let state_root_hash = match params.state_identifier {
+            None => match common::get_highest_exposed_block_header(effect_builder).await {
+                None => {
+                    return Err(Error::new(
+                        ErrorCode::NoSuchBlock,
+                        "get-entity failed to retrieve highest block header",
+                    ))
+                }
+                Some(block_header) => *block_header.state_root_hash(),
+            },
+            Some(state_identifier) => {
+                let (state_root_hash, _) =
+                    get_state_root_hash_and_optional_header(effect_builder, state_identifier)
+                        .await?;
+                state_root_hash
+            }
+        };
+
+        let entity_identifier = EngineEntityIdentifier::from(params.entity_identifier);
+        let entity_key = entity_identifier.key();
+        let request = GetEntityRequest::new(state_root_hash, entity_identifier);
+        let entity = match effect_builder.get_entity(request).await {
+            Ok(EngineGetEntityResult::Success { entity }) => entity,
+            Ok(EngineGetEntityResult::RootNotFound) => {
+                info!(%state_root_hash, "get-entity failed: root not found");
+                return Err(Error::new(
+                    ErrorCode::NoSuchStateRoot,
+                    format!("root hash {} not found", state_root_hash),
+                ));
+            }
+            Ok(EngineGetEntityResult::EntityNotFound) => {
+                info!(%entity_key, "get-entity failed: entity not found");
+                return Err(Error::new(
+                    ErrorCode::NoSuchEntity,
+                    format!("no account or contract found under {}", entity_key),
+                ));
+            }
+            Err(error) => {
+                info!("get-entity failed to execute: {}", error);
+                return Err(Error::new(
+                    ErrorCode::QueryFailedToExecute,
+                    error.to_string(),
+                ));
+            }
+        };
+
+        let entity = match entity {
+            EngineEntity::Account(account) => JsonEntity::Account(JsonAccount::from(&account)),
+            EngineEntity::Contract {
+                contract_hash,
+                contract,
+            } => JsonEntity::Contract {
+                contract_hash,
+                contract: JsonContract::from(&contract),
+            },
+        };
+
+        let result = Self::ResponseResult {
+            api_version,
+            entity,
+        };
+        Ok(result)
+    }
+}
+
 /// Parameters for "state_get_trie" RPC request.
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
 pub struct GetTrieParams {
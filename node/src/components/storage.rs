@@ -30,13 +30,18 @@
 //! The storage component itself is panic free and in general reports three classes of errors:
 //! Corruption, temporary resource exhaustion and potential bugs.
 
+mod cold_storage;
+mod compaction;
 pub(crate) mod disjoint_sequences;
 mod error;
 mod lmdb_ext;
 mod metrics;
 mod object_pool;
+mod proposer_index;
+mod pruning;
 #[cfg(test)]
 mod tests;
+mod validator_performance;
 
 #[cfg(test)]
 use std::collections::BTreeSet;
@@ -48,9 +53,10 @@ use std::{
     fs::{self, OpenOptions},
     io::ErrorKind,
     mem,
+    ops::Range,
     path::{Path, PathBuf},
-    rc::Rc,
     sync::Arc,
+    time::Duration,
 };
 
 use datasize::DataSize;
@@ -61,17 +67,19 @@ use lmdb::{
     WriteFlags,
 };
 use prometheus::Registry;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use smallvec::SmallVec;
 use static_assertions::const_assert;
 #[cfg(test)]
 use tempfile::TempDir;
+use tokio::task;
 use tracing::{debug, error, info, trace, warn};
 
 use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::{FromBytes, ToBytes},
-    EraId, ExecutionResult, ProtocolVersion, PublicKey, Timestamp, Transfer, Transform,
+    EraId, ExecutionResult, Key, ProtocolVersion, PublicKey, TimeDiff, Timestamp, Transfer,
+    TransferAddr, TransferMemo, Transform,
 };
 
 use crate::{
@@ -93,25 +101,31 @@ use crate::{
         ApprovalsHash, ApprovalsHashes, AvailableBlockRange, Block, BlockAndDeploys, BlockBody,
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash,
         BlockHashAndHeight, BlockHashHeightAndEra, BlockHeader, BlockHeaderWithMetadata,
-        BlockSignatures, BlockWithMetadata, Deploy, DeployHash, DeployHeader, DeployId,
-        DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalizedApprovals, FinalizedBlock, LegacyDeploy, MaxTtl, NodeId, SyncLeap,
-        SyncLeapIdentifier, ValueOrChunk,
+        BlockSignatures, BlockWithMetadata, Deploy, DeployExpiryReason, DeployHash, DeployHeader,
+        DeployId, DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals,
+        FinalitySignature, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MaxTtl, NodeId,
+        SyncLeap, SyncLeapIdentifier, ValueOrChunk,
     },
     utils::{display_error, WithDir},
     NodeRng,
 };
+use cold_storage::ColdStorage;
+use compaction::{CompactedDatabase, CompactionJob};
 use disjoint_sequences::{DisjointSequences, Sequence};
 pub use error::FatalStorageError;
 use error::GetRequestError;
 use lmdb_ext::{BytesreprError, LmdbExtError, TransactionExt, WriteTransactionExt};
 use metrics::Metrics;
 use object_pool::ObjectPool;
+pub(crate) use validator_performance::ValidatorEraPerformance;
 
 const COMPONENT_NAME: &str = "storage";
 
 /// Filename for the LMDB database created by the Storage component.
-const STORAGE_DB_FILENAME: &str = "storage.lmdb";
+pub(crate) const STORAGE_DB_FILENAME: &str = "storage.lmdb";
+/// Filename for the compacted copy of the LMDB database created by storage, before it replaces the
+/// original one.
+const COMPACTED_STORAGE_DB_FILENAME: &str = "storage.lmdb.compacted";
 
 /// We can set this very low, as there is only a single reader/writer accessing the component at any
 /// one time.
@@ -128,12 +142,33 @@ const DEFAULT_MAX_DEPLOY_STORE_SIZE: usize = 300 * GIB;
 const DEFAULT_MAX_DEPLOY_METADATA_STORE_SIZE: usize = 300 * GIB;
 /// Default max state store size.
 const DEFAULT_MAX_STATE_STORE_SIZE: usize = 10 * GIB;
+/// Default interval between checks whether the database should be compacted.
+const DEFAULT_COMPACTION_INTERVAL: &str = "1day";
+/// Default minimum share of free pages in the database file, as a percentage, for it to be
+/// compacted.
+const DEFAULT_COMPACTION_MIN_FREE_PAGES_PERCENT: u8 = 50;
+/// Default number of most recent eras whose blocks' bodies and deploys are kept out of cold
+/// storage.
+const DEFAULT_COLD_STORAGE_AFTER_ERAS: u64 = 100;
+/// Interval between passes of the pruning of execution results, if enabled.
+const EXECUTION_RESULTS_PRUNING_INTERVAL: Duration = Duration::from_secs(60);
+/// Interval between passes of the migration of old blocks to cold storage, if enabled.
+const COLD_STORAGE_MIGRATION_INTERVAL: Duration = Duration::from_secs(60);
 /// Maximum number of allowed dbs.
-const MAX_DB_COUNT: u32 = 9;
+pub(crate) const MAX_DB_COUNT: u32 = 14;
 /// Key under which completed blocks are to be stored.
-const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+pub(crate) const COMPLETED_BLOCKS_STORAGE_KEY: &[u8] = b"completed_blocks_disjoint_sequences";
+/// Key under which the storage self-test temporarily writes its probe value.
+const SELF_TEST_STORAGE_KEY: &[u8] = b"self_test_probe";
+/// Key under which the lowest and highest heights of the blocks whose execution results were
+/// pruned are stored.
+const PRUNED_EXECUTION_RESULTS_STORAGE_KEY: &[u8] = b"pruned_execution_results_heights";
+/// Key under which the lowest and highest heights of the blocks moved to cold storage are stored.
+const COLD_STORAGE_MOVED_BLOCKS_STORAGE_KEY: &[u8] = b"cold_storage_moved_blocks_heights";
 /// Name of the file created when initializing a force resync.
 const FORCE_RESYNC_FILE_NAME: &str = "force_resync";
+/// Key under which the name of the network to which the storage belongs is stored.
+const NETWORK_NAME_STORAGE_KEY: &[u8] = b"network_name";
 
 /// OS-specific lmdb flags.
 #[cfg(not(target_os = "macos"))]
@@ -164,7 +199,7 @@ pub struct Storage {
     root: PathBuf,
     /// Environment holding LMDB databases.
     #[data_size(skip)]
-    env: Rc<Environment>,
+    env: Arc<Environment>,
     /// The block header database.
     #[data_size(skip)]
     block_header_db: Database,
@@ -180,12 +215,30 @@ pub struct Storage {
     /// The deploy database.
     #[data_size(skip)]
     deploy_db: Database,
+    /// The deploy body hash index database, mapping a body hash to the deploys with that body.
+    #[data_size(skip)]
+    deploy_body_hash_db: Database,
     /// The deploy metadata database.
     #[data_size(skip)]
     deploy_metadata_db: Database,
+    /// The expired deploy database, mapping a deploy dropped without having been executed to the
+    /// reason it was dropped.
+    #[data_size(skip)]
+    expired_deploy_db: Database,
     /// The transfer database.
     #[data_size(skip)]
     transfer_db: Database,
+    /// The transfer memo index database, mapping a memo to the transfers recorded with it.
+    #[data_size(skip)]
+    transfer_memo_db: Database,
+    /// The proposer index database, mapping a proposer and a block height to the hash of the block
+    /// proposed at that height.
+    #[data_size(skip)]
+    proposer_db: Database,
+    /// The validator performance index database, mapping a validator and an era to the
+    /// validator's performance in that era.
+    #[data_size(skip)]
+    validator_performance_db: Database,
     /// The state storage database.
     #[data_size(skip)]
     state_store_db: Database,
@@ -196,6 +249,8 @@ pub struct Storage {
     block_height_index: BTreeMap<u64, BlockHash>,
     /// A map of era ID to switch block ID.
     switch_block_era_id_index: BTreeMap<EraId, BlockHash>,
+    /// A map of era ID to the lowest and highest heights of the blocks of that era.
+    era_height_index: BTreeMap<EraId, (u64, u64)>,
     /// A map of deploy hashes to hashes, heights and era IDs of blocks containing them.
     deploy_hash_index: BTreeMap<DeployHash, BlockHashHeightAndEra>,
     /// Runs of completed blocks known in storage.
@@ -217,6 +272,28 @@ pub struct Storage {
     metrics: Option<Metrics>,
     /// The maximum TTL of a deploy.
     max_ttl: MaxTtl,
+    /// The interval between checks whether the database should be compacted, if enabled.
+    compaction_interval: Option<TimeDiff>,
+    /// The minimum share of free pages in the database file, as a percentage, for it to be
+    /// compacted.
+    compaction_min_free_pages_percent: u8,
+    /// Events which may write to the database, held back while a compacted copy of it is being
+    /// made, or `None` if no compaction is in progress.
+    #[data_size(skip)]
+    deferred_events: Option<Vec<Event>>,
+    /// The number of most recent eras whose blocks' execution results are kept, if pruning is
+    /// enabled.
+    execution_results_retention_eras: Option<u64>,
+    /// The lowest and highest heights of the blocks whose execution results were pruned, if any.
+    pruned_execution_results: Option<(u64, u64)>,
+    /// The cold storage database holding the bodies and deploys of old blocks, if enabled.
+    #[data_size(skip)]
+    cold_storage: Option<ColdStorage>,
+    /// The number of most recent eras whose blocks' bodies and deploys are kept out of cold
+    /// storage.
+    cold_storage_after_eras: u64,
+    /// The lowest and highest heights of the blocks moved to cold storage, if any.
+    moved_to_cold_storage: Option<(u64, u64)>,
 }
 
 /// A storage component event.
@@ -234,6 +311,14 @@ pub(crate) enum Event {
     /// Make block executable request.
     #[from]
     MakeBlockExecutableRequest(Box<MakeBlockExecutableRequest>),
+    /// Check whether the database should be compacted, and compact it if so.
+    CompactDatabase,
+    /// A compacted copy of the database was made, or making it failed.
+    DatabaseCompacted(#[serde(skip_serializing)] Box<Result<CompactedDatabase, lmdb::Error>>),
+    /// Prune the execution results of blocks older than the retention window.
+    PruneExecutionResults,
+    /// Move the bodies and deploys of old blocks to cold storage.
+    MoveToColdStorage,
 }
 
 impl Display for Event {
@@ -243,6 +328,37 @@ impl Display for Event {
             Event::NetRequestIncoming(incoming) => incoming.fmt(f),
             Event::MarkBlockCompletedRequest(req) => req.fmt(f),
             Event::MakeBlockExecutableRequest(req) => req.fmt(f),
+            Event::CompactDatabase => write!(f, "compact database"),
+            Event::DatabaseCompacted(_) => write!(f, "database compacted"),
+            Event::PruneExecutionResults => write!(f, "prune execution results"),
+            Event::MoveToColdStorage => write!(f, "move to cold storage"),
+        }
+    }
+}
+
+impl Event {
+    /// Returns whether handling the event may write to the database.
+    fn may_write(&self) -> bool {
+        match self {
+            Event::StorageRequest(request) => matches!(
+                **request,
+                StorageRequest::PutBlock { .. }
+                    | StorageRequest::PutApprovalsHashes { .. }
+                    | StorageRequest::PutExecutedBlock { .. }
+                    | StorageRequest::PutDeploy { .. }
+                    | StorageRequest::PutExecutionResults { .. }
+                    | StorageRequest::PutExpiredDeploys { .. }
+                    | StorageRequest::PutBlockSignatures { .. }
+                    | StorageRequest::PutFinalitySignature { .. }
+                    | StorageRequest::PutBlockHeader { .. }
+                    | StorageRequest::StoreFinalizedApprovals { .. }
+            ),
+            Event::NetRequestIncoming(_) | Event::DatabaseCompacted(_) => false,
+            Event::MarkBlockCompletedRequest(_)
+            | Event::MakeBlockExecutableRequest(_)
+            | Event::CompactDatabase
+            | Event::PruneExecutionResults
+            | Event::MoveToColdStorage => true,
         }
     }
 }
@@ -306,9 +422,15 @@ where
     fn handle_event(
         &mut self,
         effect_builder: EffectBuilder<REv>,
-        _rng: &mut NodeRng,
+        rng: &mut NodeRng,
         event: Self::Event,
     ) -> Effects<Self::Event> {
+        if let Some(deferred_events) = self.deferred_events.as_mut() {
+            if event.may_write() {
+                deferred_events.push(event);
+                return Effects::new();
+            }
+        }
         let result = match event {
             Event::StorageRequest(req) => self.handle_storage_request(*req),
             Event::NetRequestIncoming(ref incoming) => {
@@ -336,6 +458,31 @@ where
                     Err(err) => Err(err),
                 }
             }
+            Event::CompactDatabase => self.prepare_compaction().map(|maybe_job| match maybe_job {
+                Some(job) => {
+                    self.deferred_events = Some(vec![]);
+                    async move {
+                        task::spawn_blocking(move || job.run())
+                            .await
+                            .expect("compaction task panicked")
+                    }
+                    .event(|outcome| Event::DatabaseCompacted(Box::new(outcome)))
+                }
+                None => self.schedule_compaction(effect_builder),
+            }),
+            Event::DatabaseCompacted(outcome) => self.finish_compaction(*outcome).map(|()| {
+                let mut effects = self.schedule_compaction(effect_builder);
+                for event in self.deferred_events.take().unwrap_or_default() {
+                    effects.extend(self.handle_event(effect_builder, rng, event));
+                }
+                effects
+            }),
+            Event::PruneExecutionResults => self
+                .prune_execution_results()
+                .map(|()| self.schedule_pruning(effect_builder)),
+            Event::MoveToColdStorage => self
+                .move_to_cold_storage()
+                .map(|()| self.schedule_cold_storage_migration(effect_builder)),
         };
 
         // Any error is turned into a fatal effect, the component itself does not panic. Note that
@@ -368,50 +515,65 @@ impl Storage {
     ) -> Result<Self, FatalStorageError> {
         let config = cfg.value();
 
-        // Create the database directory.
-        let mut root = cfg.with_dir(config.path.clone());
-        let network_subdir = root.join(network_name);
-
-        if !network_subdir.exists() {
-            fs::create_dir_all(&network_subdir).map_err(|err| {
-                FatalStorageError::CreateDatabaseDirectory(network_subdir.clone(), err)
-            })?;
-        }
-
-        if should_move_storage_files_to_network_subdir(&root, &STORAGE_FILES)? {
-            move_storage_files_to_network_subdir(&root, &network_subdir, &STORAGE_FILES)?;
-        }
-
-        root = network_subdir;
-
         // Calculate the upper bound for the memory map that is potentially used.
         let total_size = config
             .max_block_store_size
             .saturating_add(config.max_deploy_store_size)
             .saturating_add(config.max_deploy_metadata_store_size);
 
+        // Create the database directory.
+        let storage_root = cfg.with_dir(config.path.clone());
+        let root = storage_dir(&storage_root, network_name, protocol_version);
+
+        if !root.exists() {
+            fs::create_dir_all(&root)
+                .map_err(|err| FatalStorageError::CreateDatabaseDirectory(root.clone(), err))?;
+        }
+
+        migrate_storage_files(&storage_root, network_name, protocol_version, total_size)?;
+
+        // Remove the leftovers of a compaction interrupted by a shutdown or crash, if any.
+        let compacted_db_path = root.join(COMPACTED_STORAGE_DB_FILENAME);
+        compaction::remove_database_files(&compacted_db_path).map_err(|error| {
+            FatalStorageError::RemoveCompactedDatabase(compacted_db_path, error)
+        })?;
+
         // Creates the environment and databases.
-        let env = Environment::new()
-            .set_flags(
-                OS_FLAGS
-                // We manage our own directory.
-                | EnvironmentFlags::NO_SUB_DIR
-                // Disable thread local storage, strongly suggested for operation with tokio.
-                | EnvironmentFlags::NO_TLS
-                // Disable read-ahead. Our data is not stored/read in sequence that would benefit from the read-ahead.
-                | EnvironmentFlags::NO_READAHEAD,
-            )
-            .set_max_readers(MAX_TRANSACTIONS)
-            .set_max_dbs(MAX_DB_COUNT)
-            .set_map_size(total_size)
-            .open(&root.join(STORAGE_DB_FILENAME))?;
+        let env = open_environment(&root.join(STORAGE_DB_FILENAME), total_size)?;
+
+        let cold_storage = match &config.cold_storage_path {
+            Some(cold_storage_path) => {
+                let cold_storage_dir = cfg.with_dir(cold_storage_path.clone()).join(network_name);
+                if !cold_storage_dir.exists() {
+                    fs::create_dir_all(&cold_storage_dir).map_err(|err| {
+                        FatalStorageError::CreateDatabaseDirectory(cold_storage_dir.clone(), err)
+                    })?;
+                }
+                let cold_storage_size = config
+                    .max_block_store_size
+                    .saturating_add(config.max_deploy_store_size);
+                Some(ColdStorage::new(
+                    &cold_storage_dir.join(STORAGE_DB_FILENAME),
+                    cold_storage_size,
+                )?)
+            }
+            None => None,
+        };
 
         let block_header_db = env.create_db(Some("block_header"), DatabaseFlags::empty())?;
         let block_metadata_db = env.create_db(Some("block_metadata"), DatabaseFlags::empty())?;
         let deploy_db = env.create_db(Some("deploys"), DatabaseFlags::empty())?;
+        let deploy_body_hash_db =
+            env.create_db(Some("deploy_body_hash"), DatabaseFlags::empty())?;
         let deploy_metadata_db = env.create_db(Some("deploy_metadata"), DatabaseFlags::empty())?;
+        let expired_deploy_db = env.create_db(Some("expired_deploys"), DatabaseFlags::empty())?;
         let transfer_db = env.create_db(Some("transfer"), DatabaseFlags::empty())?;
+        let transfer_memo_db = env.create_db(Some("transfer_memo"), DatabaseFlags::empty())?;
+        let proposer_db = env.create_db(Some("proposer"), DatabaseFlags::empty())?;
+        let validator_performance_db =
+            env.create_db(Some("validator_performance"), DatabaseFlags::empty())?;
         let state_store_db = env.create_db(Some("state_store"), DatabaseFlags::empty())?;
+        check_network_name(&env, state_store_db, &root, network_name)?;
         let finalized_approvals_db =
             env.create_db(Some("finalized_approvals"), DatabaseFlags::empty())?;
         let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
@@ -422,6 +584,7 @@ impl Storage {
         info!("indexing block store");
         let mut block_height_index = BTreeMap::new();
         let mut switch_block_era_id_index = BTreeMap::new();
+        let mut era_height_index = BTreeMap::new();
         let mut deploy_hash_index = BTreeMap::new();
         let mut block_txn = env.begin_rw_txn()?;
         let mut cursor = block_txn.open_rw_cursor(block_header_db)?;
@@ -436,8 +599,12 @@ impl Storage {
             let (_, raw_val) = row?;
             let mut body_txn = env.begin_ro_txn()?;
             let block_header: BlockHeader = lmdb_ext::deserialize(raw_val)?;
-            let maybe_block_body =
-                get_body_for_block_header(&mut body_txn, block_header.body_hash(), block_body_db);
+            let maybe_block_body = get_body_for_block_header(
+                &mut body_txn,
+                block_header.body_hash(),
+                block_body_db,
+                cold_storage.as_ref(),
+            );
             if let Some(invalid_era) = hard_reset_to_start_of_era {
                 // Remove blocks that are in to-be-upgraded eras, but have obsolete protocol
                 // versions - they were most likely created before the upgrade and should be
@@ -462,6 +629,7 @@ impl Storage {
             insert_to_block_header_indices(
                 &mut block_height_index,
                 &mut switch_block_era_id_index,
+                &mut era_height_index,
                 &block_header,
             )?;
 
@@ -492,24 +660,34 @@ impl Storage {
         )?;
 
         initialize_block_metadata_db(&env, &block_metadata_db, &deleted_block_hashes_raw)?;
+        initialize_proposer_db(&env, &proposer_db, &deleted_block_hashes)?;
         initialize_deploy_metadata_db(&env, &deploy_metadata_db, &deleted_deploy_hashes)?;
+        let maybe_invalid_era =
+            hard_reset_to_start_of_era.filter(|_| !deleted_block_hashes.is_empty());
+        initialize_validator_performance_db(&env, &validator_performance_db, maybe_invalid_era)?;
 
         let metrics = registry.map(Metrics::new).transpose()?;
 
         let mut component = Self {
             root,
-            env: Rc::new(env),
+            env: Arc::new(env),
             block_header_db,
             block_body_db,
             block_metadata_db,
             approvals_hashes_db,
             deploy_db,
+            deploy_body_hash_db,
             deploy_metadata_db,
+            expired_deploy_db,
             transfer_db,
+            transfer_memo_db,
+            proposer_db,
+            validator_performance_db,
             state_store_db,
             finalized_approvals_db,
             block_height_index,
             switch_block_era_id_index,
+            era_height_index,
             deploy_hash_index,
             completed_blocks: Default::default(),
             activation_era,
@@ -519,6 +697,16 @@ impl Storage {
             recent_era_count,
             max_ttl,
             metrics,
+            compaction_interval: config
+                .enable_compaction
+                .then_some(config.compaction_interval),
+            compaction_min_free_pages_percent: config.compaction_min_free_pages_percent,
+            deferred_events: None,
+            execution_results_retention_eras: config.execution_results_retention_eras,
+            pruned_execution_results: None,
+            cold_storage,
+            cold_storage_after_eras: config.cold_storage_after_eras,
+            moved_to_cold_storage: None,
         };
 
         if force_resync {
@@ -555,11 +743,21 @@ impl Storage {
             }
         }
 
-        match component.read_state_store(&Cow::Borrowed(COMPLETED_BLOCKS_STORAGE_KEY))? {
-            Some(raw) => {
-                let (mut sequences, _) = DisjointSequences::from_vec(raw)
-                    .map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
-
+        let maybe_completed_blocks = match component
+            .read_state_store(&Cow::Borrowed(COMPLETED_BLOCKS_STORAGE_KEY))?
+        {
+            Some(raw) => match DisjointSequences::from_vec(raw) {
+                Ok((sequences, _)) => Some(sequences),
+                Err(error) => {
+                    // The record can be rebuilt by syncing the blocks again, so treat it as lost.
+                    warn!(%error, "discarding undecodable record of completed blocks");
+                    None
+                }
+            },
+            None => None,
+        };
+        match maybe_completed_blocks {
+            Some(mut sequences) => {
                 // Truncate the sequences in case we removed blocks via a hard reset.
                 if let Some(&highest_block_height) = component.block_height_index.keys().last() {
                     sequences.truncate(highest_block_height);
@@ -598,6 +796,22 @@ impl Storage {
             }
         }
 
+        if let Some(raw) =
+            component.read_state_store(&Cow::Borrowed(PRUNED_EXECUTION_RESULTS_STORAGE_KEY))?
+        {
+            let (pruned_execution_results, _) = <(u64, u64)>::from_vec(raw)
+                .map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
+            component.pruned_execution_results = Some(pruned_execution_results);
+        }
+
+        if let Some(raw) =
+            component.read_state_store(&Cow::Borrowed(COLD_STORAGE_MOVED_BLOCKS_STORAGE_KEY))?
+        {
+            let (moved_to_cold_storage, _) = <(u64, u64)>::from_vec(raw)
+                .map_err(FatalStorageError::UnexpectedDeserializationFailure)?;
+            component.moved_to_cold_storage = Some(moved_to_cold_storage);
+        }
+
         Ok(component)
     }
 
@@ -636,11 +850,382 @@ impl Storage {
         Ok(())
     }
 
+    /// Deletes a key from the state storage database, if present.
+    fn delete_state_store(&self, key: Cow<'static, [u8]>) -> Result<(), FatalStorageError> {
+        let mut txn = self.env.begin_rw_txn()?;
+        match txn.del(self.state_store_db, &key, None) {
+            Ok(()) | Err(lmdb::Error::NotFound) => (),
+            Err(err) => return Err(err.into()),
+        }
+        txn.commit()?;
+
+        Ok(())
+    }
+
+    /// Performs a write, read and delete round trip on the state storage database.
+    ///
+    /// Fails if the value read back differs from the one written, or if it is still present after
+    /// having been deleted.
+    pub(crate) fn run_self_test(&self) -> Result<(), FatalStorageError> {
+        let probe = Timestamp::now().millis().to_le_bytes().to_vec();
+        self.write_state_store(Cow::Borrowed(SELF_TEST_STORAGE_KEY), &probe)?;
+        let read_back = self.read_state_store(&Cow::Borrowed(SELF_TEST_STORAGE_KEY))?;
+        if read_back.as_ref() != Some(&probe) {
+            return Err(FatalStorageError::SelfTest(
+                "value read back differs from the one written",
+            ));
+        }
+        self.delete_state_store(Cow::Borrowed(SELF_TEST_STORAGE_KEY))?;
+        if self
+            .read_state_store(&Cow::Borrowed(SELF_TEST_STORAGE_KEY))?
+            .is_some()
+        {
+            return Err(FatalStorageError::SelfTest(
+                "value still present after having been deleted",
+            ));
+        }
+        Ok(())
+    }
+
     /// Returns the path to the storage folder.
     pub(crate) fn root_path(&self) -> &Path {
         &self.root
     }
 
+    /// Schedules the next check whether the database should be compacted, if compaction is
+    /// enabled.
+    pub(crate) fn schedule_compaction<REv: Send>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event> {
+        match self.compaction_interval {
+            Some(interval) => effect_builder
+                .set_timeout(interval.into())
+                .event(|_| Event::CompactDatabase),
+            None => Effects::new(),
+        }
+    }
+
+    /// Schedules the next pass of the pruning of execution results, if pruning is enabled.
+    pub(crate) fn schedule_pruning<REv: Send>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event> {
+        if self.execution_results_retention_eras.is_none() {
+            return Effects::new();
+        }
+        effect_builder
+            .set_timeout(EXECUTION_RESULTS_PRUNING_INTERVAL)
+            .event(|_| Event::PruneExecutionResults)
+    }
+
+    /// Prunes the execution results and approvals hashes of the next batch of blocks older than
+    /// the retention window, keeping their headers, bodies and finality signatures.
+    ///
+    /// The retention window covers the era of the highest complete block and the
+    /// `execution_results_retention_eras` eras preceding it.  Blocks outside the available block
+    /// range are never pruned.
+    fn prune_execution_results(&mut self) -> Result<(), FatalStorageError> {
+        let retention_eras = match self.execution_results_retention_eras {
+            Some(retention_eras) => retention_eras,
+            None => return Ok(()),
+        };
+        let retention_low = match self.lowest_height_of_recent_eras(retention_eras) {
+            Some(retention_low) => retention_low,
+            None => {
+                debug!("storage: not pruning, retention era not indexed");
+                return Ok(());
+            }
+        };
+        let (heights, pruned_execution_results) = match pruning::next_pruning_range(
+            self.get_available_block_range().low(),
+            retention_low,
+            self.pruned_execution_results,
+        ) {
+            Some(next_pruning_range) => next_pruning_range,
+            None => return Ok(()),
+        };
+
+        let mut txn = self.env.begin_rw_txn()?;
+        for height in heights.clone() {
+            let block_hash = match self.block_height_index.get(&height) {
+                Some(block_hash) => *block_hash,
+                None => continue,
+            };
+            let block_header = match self.get_single_block_header(&mut txn, &block_hash)? {
+                Some(block_header) => block_header,
+                None => continue,
+            };
+            if let Some(block_body) = get_body_for_block_header(
+                &mut txn,
+                block_header.body_hash(),
+                self.block_body_db,
+                self.cold_storage.as_ref(),
+            )? {
+                for deploy_hash in block_body.deploy_and_transfer_hashes() {
+                    let mut metadata = match self.get_deploy_metadata(&mut txn, deploy_hash)? {
+                        Some(metadata) => metadata,
+                        None => continue,
+                    };
+                    if metadata.execution_results.remove(&block_hash).is_none() {
+                        continue;
+                    }
+                    if metadata.execution_results.is_empty() {
+                        txn.del(self.deploy_metadata_db, deploy_hash, None)?;
+                    } else {
+                        let _ =
+                            txn.put_value(self.deploy_metadata_db, deploy_hash, &metadata, true)?;
+                    }
+                }
+            }
+            match txn.del(self.approvals_hashes_db, &block_hash, None) {
+                Ok(()) | Err(lmdb::Error::NotFound) => (),
+                Err(err) => return Err(err.into()),
+            }
+        }
+        txn.commit()?;
+
+        self.pruned_execution_results = Some(pruned_execution_results);
+        let serialized = pruned_execution_results
+            .to_bytes()
+            .map_err(FatalStorageError::UnexpectedSerializationFailure)?;
+        self.write_state_store(
+            Cow::Borrowed(PRUNED_EXECUTION_RESULTS_STORAGE_KEY),
+            &serialized,
+        )?;
+        info!(
+            lowest_height = heights.start,
+            highest_height = heights.end - 1,
+            "storage: pruned execution results"
+        );
+        Ok(())
+    }
+
+    /// Returns the height of the lowest block of the era `era_count` eras before the era of the
+    /// highest complete block, or `None` if that era is not indexed.
+    fn lowest_height_of_recent_eras(&self, era_count: u64) -> Option<u64> {
+        let available_block_range = self.get_available_block_range();
+        let (highest_era_id, _) = self
+            .era_height_index
+            .iter()
+            .rev()
+            .find(|(_, (lowest, _))| *lowest <= available_block_range.high())?;
+        let lowest_era_id = highest_era_id.saturating_sub(era_count);
+        self.era_height_index
+            .get(&lowest_era_id)
+            .map(|(lowest, _)| *lowest)
+    }
+
+    /// Schedules the next pass of the migration of old blocks to cold storage, if cold storage is
+    /// enabled.
+    pub(crate) fn schedule_cold_storage_migration<REv: Send>(
+        &self,
+        effect_builder: EffectBuilder<REv>,
+    ) -> Effects<Event> {
+        if self.cold_storage.is_none() {
+            return Effects::new();
+        }
+        effect_builder
+            .set_timeout(COLD_STORAGE_MIGRATION_INTERVAL)
+            .event(|_| Event::MoveToColdStorage)
+    }
+
+    /// Moves the bodies and deploys of the next batch of blocks older than
+    /// `cold_storage_after_eras` eras to cold storage, keeping everything else in the main
+    /// database.
+    ///
+    /// Blocks are selected in batches the same way as for pruning, so blocks outside the available
+    /// block range are never moved.
+    fn move_to_cold_storage(&mut self) -> Result<(), FatalStorageError> {
+        let cold_storage = match &self.cold_storage {
+            Some(cold_storage) => cold_storage,
+            None => return Ok(()),
+        };
+        let cold_storage_high =
+            match self.lowest_height_of_recent_eras(self.cold_storage_after_eras) {
+                Some(cold_storage_high) => cold_storage_high,
+                None => {
+                    debug!("storage: not moving blocks to cold storage, era not indexed");
+                    return Ok(());
+                }
+            };
+        let (heights, moved_to_cold_storage) = match pruning::next_pruning_range(
+            self.get_available_block_range().low(),
+            cold_storage_high,
+            self.moved_to_cold_storage,
+        ) {
+            Some(next_range) => next_range,
+            None => return Ok(()),
+        };
+
+        let mut txn = self.env.begin_rw_txn()?;
+        let mut cold_txn = cold_storage.begin_rw_txn()?;
+        for height in heights.clone() {
+            let block_hash = match self.block_height_index.get(&height) {
+                Some(block_hash) => *block_hash,
+                None => continue,
+            };
+            let block_header = match self.get_single_block_header(&mut txn, &block_hash)? {
+                Some(block_header) => block_header,
+                None => continue,
+            };
+            // The body is missing if it is shared with a block moved already.
+            let block_body: BlockBody =
+                match txn.get_value(self.block_body_db, block_header.body_hash())? {
+                    Some(block_body) => block_body,
+                    None => continue,
+                };
+            for deploy_hash in block_body.deploy_and_transfer_hashes() {
+                let _ = cold_storage.move_deploy(
+                    &mut txn,
+                    self.deploy_db,
+                    &mut cold_txn,
+                    deploy_hash,
+                )?;
+            }
+            let _ = cold_storage.move_block_body(
+                &mut txn,
+                self.block_body_db,
+                &mut cold_txn,
+                block_header.body_hash(),
+            )?;
+        }
+        // Commit to cold storage first: if the node stops in between, the moved values are kept in
+        // both databases rather than lost.
+        cold_txn.commit()?;
+        txn.commit()?;
+
+        self.moved_to_cold_storage = Some(moved_to_cold_storage);
+        let serialized = moved_to_cold_storage
+            .to_bytes()
+            .map_err(FatalStorageError::UnexpectedSerializationFailure)?;
+        self.write_state_store(
+            Cow::Borrowed(COLD_STORAGE_MOVED_BLOCKS_STORAGE_KEY),
+            &serialized,
+        )?;
+        info!(
+            lowest_height = heights.start,
+            highest_height = heights.end - 1,
+            "storage: moved blocks to cold storage"
+        );
+        Ok(())
+    }
+
+    /// Returns the name of each database in the environment, along with a mutable reference to
+    /// its handle.
+    ///
+    /// Must list every database created in `Storage::new`.
+    fn named_databases_mut(&mut self) -> [(&'static str, &mut Database); MAX_DB_COUNT as usize] {
+        [
+            ("block_header", &mut self.block_header_db),
+            ("block_metadata", &mut self.block_metadata_db),
+            ("deploys", &mut self.deploy_db),
+            ("deploy_body_hash", &mut self.deploy_body_hash_db),
+            ("deploy_metadata", &mut self.deploy_metadata_db),
+            ("expired_deploys", &mut self.expired_deploy_db),
+            ("transfer", &mut self.transfer_db),
+            ("transfer_memo", &mut self.transfer_memo_db),
+            ("proposer", &mut self.proposer_db),
+            ("validator_performance", &mut self.validator_performance_db),
+            ("state_store", &mut self.state_store_db),
+            ("finalized_approvals", &mut self.finalized_approvals_db),
+            ("block_body", &mut self.block_body_db),
+            ("approvals_hashes", &mut self.approvals_hashes_db),
+        ]
+    }
+
+    /// Returns the job compacting the database, if enough of the pages of its file are free and
+    /// there is enough free disk space for a compacted copy.
+    fn prepare_compaction(&mut self) -> Result<Option<CompactionJob>, FatalStorageError> {
+        let free_pages_percent = compaction::free_pages_percent(&self.env)?;
+        if free_pages_percent < u64::from(self.compaction_min_free_pages_percent) {
+            debug!(free_pages_percent, "storage: not compacting database");
+            return Ok(None);
+        }
+        let required_bytes = compaction::used_bytes(&self.env)?;
+        match fs2::available_space(&self.root) {
+            Ok(available_bytes) if available_bytes < required_bytes => {
+                warn!(
+                    available_bytes,
+                    required_bytes, "storage: not enough free disk space to compact database"
+                );
+                return Ok(None);
+            }
+            Ok(_) => (),
+            Err(error) => {
+                warn!(%error, "storage: failed to check free disk space, not compacting database");
+                return Ok(None);
+            }
+        }
+
+        info!(free_pages_percent, "storage: compacting database");
+        let databases = self
+            .named_databases_mut()
+            .into_iter()
+            .map(|(name, database)| (name, *database))
+            .collect();
+        let compacted_db_path = self.root.join(COMPACTED_STORAGE_DB_FILENAME);
+        // The lock file of a previous compaction may still be in use by the current environment,
+        // so it must be unlinked rather than shared with the new copy.
+        compaction::remove_database_files(&compacted_db_path).map_err(|error| {
+            FatalStorageError::RemoveCompactedDatabase(compacted_db_path.clone(), error)
+        })?;
+        Ok(Some(CompactionJob {
+            env: Arc::clone(&self.env),
+            databases,
+            path: compacted_db_path,
+        }))
+    }
+
+    /// Replaces the database with the compacted copy made by a `CompactionJob`.
+    ///
+    /// The copy is discarded if it failed, or if the database was written to since it was copied,
+    /// in which case compaction is retried at the next check.
+    fn finish_compaction(
+        &mut self,
+        outcome: Result<CompactedDatabase, lmdb::Error>,
+    ) -> Result<(), FatalStorageError> {
+        let compacted_db_path = self.root.join(COMPACTED_STORAGE_DB_FILENAME);
+        let compacted = match outcome {
+            Ok(compacted) => compacted,
+            Err(error) => {
+                warn!(%error, "storage: failed to compact database");
+                let _ = compaction::remove_database_files(&compacted_db_path);
+                return Ok(());
+            }
+        };
+        if self.env.info()?.last_txnid() != compacted.last_txnid {
+            info!("storage: database was written to while compacting, discarding compacted copy");
+            drop(compacted);
+            let _ = compaction::remove_database_files(&compacted_db_path);
+            return Ok(());
+        }
+
+        // The compacted environment keeps using the lock file it was created with, so renaming its
+        // database file does not affect it.
+        let db_path = self.root.join(STORAGE_DB_FILENAME);
+        if let Err(error) = fs::rename(&compacted_db_path, &db_path) {
+            warn!(%error, "storage: failed to replace database with compacted copy");
+            drop(compacted);
+            let _ = compaction::remove_database_files(&compacted_db_path);
+            return Ok(());
+        }
+        self.env = Arc::new(compacted.env);
+        for ((_, database), compacted_database) in self
+            .named_databases_mut()
+            .into_iter()
+            .zip(compacted.databases)
+        {
+            *database = compacted_database;
+        }
+
+        info!(
+            free_pages_percent = compaction::free_pages_percent(&self.env)?,
+            "storage: finished compacting database"
+        );
+        Ok(())
+    }
+
     fn handle_net_request_incoming<REv>(
         &mut self,
         effect_builder: EffectBuilder<REv>,
@@ -799,7 +1384,7 @@ impl Storage {
                 approvals_hashes,
                 responder,
             } => {
-                let env = Rc::clone(&self.env);
+                let env = Arc::clone(&self.env);
                 let mut txn = env.begin_rw_txn()?;
                 let result = self.write_approvals_hashes(&mut txn, &approvals_hashes)?;
                 txn.commit()?;
@@ -828,6 +1413,21 @@ impl Storage {
                     .respond(self.get_highest_complete_block_header(&mut txn)?)
                     .ignore()
             }
+            StorageRequest::GetSwitchBlockHeaderByEra { era_id, responder } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                responder
+                    .respond(self.get_switch_block_header_by_era_id(&mut txn, era_id)?)
+                    .ignore()
+            }
+            StorageRequest::GetStateRootHashesFromHeight {
+                lowest_height,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                responder
+                    .respond(self.get_state_root_hashes_from_height(&mut txn, lowest_height)?)
+                    .ignore()
+            }
             StorageRequest::GetDeploysEraIds {
                 deploy_hashes,
                 responder,
@@ -855,6 +1455,30 @@ impl Storage {
                 let maybe_transfers = self.get_transfers(&block_hash)?;
                 responder.respond(maybe_transfers).ignore()
             }
+            StorageRequest::GetTransfersByMemo { memo, responder } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                responder
+                    .respond(self.get_transfers_by_memo(&mut txn, &memo)?)
+                    .ignore()
+            }
+            StorageRequest::GetDeployHashesByBodyHash {
+                body_hash,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                responder
+                    .respond(self.get_deploy_hashes_by_body_hash(&mut txn, &body_hash)?)
+                    .ignore()
+            }
+            StorageRequest::GetValidatorPerformance {
+                public_key,
+                responder,
+            } => {
+                let mut txn = self.env.begin_ro_txn()?;
+                responder
+                    .respond(self.get_validator_performance(&mut txn, &public_key)?)
+                    .ignore()
+            }
             StorageRequest::PutDeploy { deploy, responder } => {
                 responder.respond(self.put_deploy(&deploy)?).ignore()
             }
@@ -905,7 +1529,7 @@ impl Storage {
                 responder,
             } => {
                 let mut txn = self.env.begin_ro_txn()?;
-                let has_deploy = txn.value_exists(self.deploy_db, deploy_id.deploy_hash())?;
+                let has_deploy = self.deploy_exists(&mut txn, deploy_id.deploy_hash())?;
                 responder.respond(has_deploy).ignore()
             }
             StorageRequest::GetExecutionResults {
@@ -922,12 +1546,29 @@ impl Storage {
                 execution_results,
                 responder,
             } => {
-                let env = Rc::clone(&self.env);
+                let env = Arc::clone(&self.env);
                 let mut txn = env.begin_rw_txn()?;
                 self.write_execution_results(&mut txn, &block_hash, execution_results)?;
                 txn.commit()?;
                 responder.respond(()).ignore()
             }
+            StorageRequest::PutExpiredDeploys {
+                expired_deploys,
+                responder,
+            } => {
+                let env = Arc::clone(&self.env);
+                let mut txn = env.begin_rw_txn()?;
+                for (deploy_hash, reason) in expired_deploys {
+                    let was_written =
+                        txn.put_value(self.expired_deploy_db, &deploy_hash, &reason, true)?;
+                    if !was_written {
+                        error!(%deploy_hash, "failed to write expired deploy");
+                        debug_assert!(was_written);
+                    }
+                }
+                txn.commit()?;
+                responder.respond(()).ignore()
+            }
             StorageRequest::GetDeployAndMetadata {
                 deploy_hash,
                 responder,
@@ -953,6 +1594,10 @@ impl Storage {
                         self.get_block_hash_and_height_by_deploy_hash(deploy_hash)?
                     {
                         block_hash_and_height.into()
+                    } else if let Some(reason) =
+                        self.get_deploy_expiry_reason(&mut txn, &deploy_hash)?
+                    {
+                        DeployMetadataExt::Expired(reason)
                     } else {
                         DeployMetadataExt::Empty
                     };
@@ -1094,6 +1739,19 @@ impl Storage {
                 let mut txn = self.env.begin_rw_txn()?;
                 let old_data: Option<BlockSignatures> =
                     txn.get_value(self.block_metadata_db, &signatures.block_hash)?;
+                for public_key in signatures.proofs.keys() {
+                    let is_new = old_data
+                        .as_ref()
+                        .map_or(true, |data| !data.proofs.contains_key(public_key));
+                    if is_new {
+                        self.update_validator_performance(
+                            &mut txn,
+                            public_key,
+                            signatures.era_id,
+                            |performance| performance.finality_signatures += 1,
+                        )?;
+                    }
+                }
                 let new_data = match old_data {
                     None => signatures,
                     Some(mut data) => {
@@ -1195,6 +1853,14 @@ impl Storage {
         let mut block_signatures = txn
             .get_value(self.block_metadata_db, &signature.block_hash)?
             .unwrap_or_else(|| BlockSignatures::new(signature.block_hash, signature.era_id));
+        if !block_signatures.proofs.contains_key(&signature.public_key) {
+            self.update_validator_performance(
+                &mut txn,
+                &signature.public_key,
+                signature.era_id,
+                |performance| performance.finality_signatures += 1,
+            )?;
+        }
         block_signatures.insert_proof(signature.public_key, signature.signature);
         let outcome = txn.put_value(
             self.block_metadata_db,
@@ -1250,11 +1916,18 @@ impl Storage {
 
     /// Put a single deploy into storage.
     pub fn put_deploy(&self, deploy: &Deploy) -> Result<bool, FatalStorageError> {
-        let mut txn = self.env.begin_rw_txn()?;
         let deploy_hash = deploy.hash();
+        if let Some(cold_storage) = &self.cold_storage {
+            if cold_storage.deploy_exists(deploy_hash)? {
+                debug!(%deploy_hash, "Storage: attempt to store deploy moved to cold storage");
+                return Ok(false);
+            }
+        }
+        let mut txn = self.env.begin_rw_txn()?;
         let outcome = txn.put_value(self.deploy_db, deploy_hash, deploy, false)?;
         if outcome {
             debug!(%deploy_hash, "Storage: new deploy stored");
+            self.index_deploy_body_hash(&mut txn, *deploy.header().body_hash(), *deploy_hash)?;
         } else {
             debug!(%deploy_hash, "Storage: attempt to store existing deploy");
         }
@@ -1268,7 +1941,7 @@ impl Storage {
         approvals_hashes: &ApprovalsHashes,
         execution_results: HashMap<DeployHash, ExecutionResult>,
     ) -> Result<bool, FatalStorageError> {
-        let env = Rc::clone(&self.env);
+        let env = Arc::clone(&self.env);
         let mut txn = env.begin_rw_txn()?;
         let wrote = self.write_validated_block(&mut txn, block)?;
         if !wrote {
@@ -1287,6 +1960,23 @@ impl Storage {
         self.get_single_block(&mut self.env.begin_ro_txn()?, block_hash)
     }
 
+    /// Retrieves the blocks proposed by `proposer` at heights in `heights`, ordered by height.
+    pub fn read_blocks_by_proposer(
+        &self,
+        proposer: &PublicKey,
+        heights: Range<u64>,
+    ) -> Result<Vec<Block>, FatalStorageError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        let block_hashes = self.get_block_hashes_by_proposer(&mut txn, proposer, heights)?;
+        let mut blocks = Vec::with_capacity(block_hashes.len());
+        for block_hash in block_hashes {
+            if let Some(block) = self.get_single_block(&mut txn, &block_hash)? {
+                blocks.push(block);
+            }
+        }
+        Ok(blocks)
+    }
+
     /// Returns `true` if the given block's header and body are stored.
     fn block_exists(&self, block_hash: &BlockHash) -> Result<bool, FatalStorageError> {
         let mut txn = self.env.begin_ro_txn()?;
@@ -1296,7 +1986,13 @@ impl Storage {
                 return Ok(false);
             }
         };
-        Ok(txn.value_exists(self.block_body_db, block_header.body_hash())?)
+        if txn.value_exists(self.block_body_db, block_header.body_hash())? {
+            return Ok(true);
+        }
+        match &self.cold_storage {
+            Some(cold_storage) => Ok(cold_storage.block_body_exists(block_header.body_hash())?),
+            None => Ok(false),
+        }
     }
 
     /// Retrieves a approvals hashes by block hash.
@@ -1435,7 +2131,7 @@ impl Storage {
     pub fn write_block(&mut self, block: &Block) -> Result<bool, FatalStorageError> {
         // Validate the block prior to inserting it into the database
         block.verify()?;
-        let env = Rc::clone(&self.env);
+        let env = Arc::clone(&self.env);
         let mut txn = env.begin_rw_txn()?;
         let wrote = self.write_validated_block(&mut txn, block)?;
         if wrote {
@@ -1453,7 +2149,7 @@ impl Storage {
     pub fn write_complete_block(&mut self, block: &Block) -> Result<bool, FatalStorageError> {
         // Validate the block prior to inserting it into the database
         block.verify()?;
-        let env = Rc::clone(&self.env);
+        let env = Arc::clone(&self.env);
         let mut txn = env.begin_rw_txn()?;
         let wrote = self.write_validated_block(&mut txn, block)?;
         if wrote {
@@ -1473,6 +2169,9 @@ impl Storage {
         let mut transfers: Vec<Transfer> = vec![];
         for (deploy_hash, execution_result) in execution_results {
             transfers.extend(successful_transfers(&execution_result));
+            for (memo, transfer_addr) in successful_transfer_memos(&execution_result) {
+                self.index_transfer_memo(txn, memo, transfer_addr)?;
+            }
 
             let mut metadata = self
                 .get_deploy_metadata(txn, &deploy_hash)?
@@ -1507,6 +2206,183 @@ impl Storage {
         Ok(was_written)
     }
 
+    /// Adds the given transfer to the list of transfers recorded with `memo`.
+    fn index_transfer_memo(
+        &self,
+        txn: &mut RwTransaction,
+        memo: TransferMemo,
+        transfer_addr: TransferAddr,
+    ) -> Result<(), FatalStorageError> {
+        let mut transfer_addrs = self.get_transfers_by_memo(txn, &memo)?;
+        if transfer_addrs.contains(&transfer_addr) {
+            return Ok(());
+        }
+        transfer_addrs.push(transfer_addr);
+        let was_written = txn.put_value(self.transfer_memo_db, &memo, &transfer_addrs, true)?;
+        if !was_written {
+            error!(%memo, "failed to write transfer memo index");
+            debug_assert!(was_written);
+        }
+        Ok(())
+    }
+
+    /// Adds the given deploy to the list of deploys stored with the body hash `body_hash`.
+    fn index_deploy_body_hash(
+        &self,
+        txn: &mut RwTransaction,
+        body_hash: Digest,
+        deploy_hash: DeployHash,
+    ) -> Result<(), FatalStorageError> {
+        let mut deploy_hashes = self.get_deploy_hashes_by_body_hash(txn, &body_hash)?;
+        if deploy_hashes.contains(&deploy_hash) {
+            return Ok(());
+        }
+        deploy_hashes.push(deploy_hash);
+        let was_written =
+            txn.put_value(self.deploy_body_hash_db, &body_hash, &deploy_hashes, true)?;
+        if !was_written {
+            error!(%body_hash, "failed to write deploy body hash index");
+            debug_assert!(was_written);
+        }
+        Ok(())
+    }
+
+    /// Retrieves the hashes of all stored deploys with the given body hash.
+    fn get_deploy_hashes_by_body_hash<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        body_hash: &Digest,
+    ) -> Result<Vec<DeployHash>, FatalStorageError> {
+        Ok(txn
+            .get_value(self.deploy_body_hash_db, body_hash)?
+            .unwrap_or_default())
+    }
+
+    /// Retrieves the addresses of all stored transfers recorded with the given memo.
+    fn get_transfers_by_memo<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        memo: &TransferMemo,
+    ) -> Result<Vec<TransferAddr>, FatalStorageError> {
+        Ok(txn
+            .get_value(self.transfer_memo_db, memo)?
+            .unwrap_or_default())
+    }
+
+    /// Records the given block in the proposer index.
+    fn index_proposer(
+        &self,
+        txn: &mut RwTransaction,
+        block: &Block,
+    ) -> Result<(), FatalStorageError> {
+        let key = proposer_index::index_key(block.body().proposer(), block.height());
+        let was_written = txn.put_value(self.proposer_db, &key, block.hash(), true)?;
+        if !was_written {
+            error!(block_hash = %block.hash(), "failed to write proposer index");
+            debug_assert!(was_written);
+        }
+        Ok(())
+    }
+
+    /// Retrieves the hashes of the blocks proposed by `proposer` at heights in `heights`, ordered
+    /// by height.
+    fn get_block_hashes_by_proposer<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        proposer: &PublicKey,
+        heights: Range<u64>,
+    ) -> Result<Vec<BlockHash>, FatalStorageError> {
+        if heights.is_empty() {
+            return Ok(vec![]);
+        }
+        let key_range = proposer_index::index_key_range(proposer, heights);
+        let mut cursor = txn.open_ro_cursor(self.proposer_db)?;
+        let mut block_hashes = vec![];
+        for row in cursor.iter_from(key_range.start) {
+            let (raw_key, raw_val) = row?;
+            if *raw_key >= key_range.end[..] {
+                break;
+            }
+            block_hashes.push(lmdb_ext::deserialize(raw_val)?);
+        }
+        Ok(block_hashes)
+    }
+
+    /// Records the proposer of the given block and, if it is a switch block, the validators it
+    /// reports as equivocators or as inactive in the validator performance index.
+    fn index_validator_performance(
+        &self,
+        txn: &mut RwTransaction,
+        block: &Block,
+    ) -> Result<(), FatalStorageError> {
+        let era_id = block.header().era_id();
+        let height = block.height();
+        self.update_validator_performance(txn, block.body().proposer(), era_id, |performance| {
+            let _ = performance.proposed_blocks.insert(height);
+        })?;
+        if let Some(era_end) = block.header().era_end() {
+            let era_report = era_end.era_report();
+            for equivocator in &era_report.equivocators {
+                self.update_validator_performance(txn, equivocator, era_id, |performance| {
+                    performance.equivocated = true
+                })?;
+            }
+            for inactive_validator in &era_report.inactive_validators {
+                self.update_validator_performance(
+                    txn,
+                    inactive_validator,
+                    era_id,
+                    |performance| performance.inactive = true,
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applies `update` to the performance of the given validator in the given era.
+    fn update_validator_performance<F>(
+        &self,
+        txn: &mut RwTransaction,
+        public_key: &PublicKey,
+        era_id: EraId,
+        update: F,
+    ) -> Result<(), FatalStorageError>
+    where
+        F: FnOnce(&mut ValidatorEraPerformance),
+    {
+        let key = validator_performance::index_key(public_key, era_id);
+        let mut performance = txn
+            .get_value(self.validator_performance_db, &key)?
+            .unwrap_or_else(|| ValidatorEraPerformance::new(era_id));
+        update(&mut performance);
+        let was_written = txn.put_value(self.validator_performance_db, &key, &performance, true)?;
+        if !was_written {
+            error!(%public_key, %era_id, "failed to write validator performance index");
+            debug_assert!(was_written);
+        }
+        Ok(())
+    }
+
+    /// Retrieves the performance of the given validator in every era in which it is recorded,
+    /// ordered by era.
+    fn get_validator_performance<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        public_key: &PublicKey,
+    ) -> Result<Vec<ValidatorEraPerformance>, FatalStorageError> {
+        let prefix = validator_performance::index_key_prefix(public_key);
+        let mut cursor = txn.open_ro_cursor(self.validator_performance_db)?;
+        let mut performance = vec![];
+        for row in cursor.iter_from(prefix) {
+            let (raw_key, raw_val) = row?;
+            if !raw_key.starts_with(&prefix) {
+                break;
+            }
+            performance.push(lmdb_ext::deserialize(raw_val)?);
+        }
+        Ok(performance)
+    }
+
     /// Writes approvals hashes to storage.
     fn write_approvals_hashes(
         &mut self,
@@ -1573,10 +2449,14 @@ impl Storage {
             return Ok(false);
         }
 
+        self.index_proposer(txn, block)?;
+        self.index_validator_performance(txn, block)?;
+
         {
             insert_to_block_header_indices(
                 &mut self.block_height_index,
                 &mut self.switch_block_era_id_index,
+                &mut self.era_height_index,
                 block.header(),
             )?;
             insert_to_deploy_index(
@@ -1679,6 +2559,28 @@ impl Storage {
         res
     }
 
+    /// Retrieves all stored block headers of the given era, sorted by height.
+    ///
+    /// The headers are looked up in a single transaction, using the range of heights of the era
+    /// known to the index.
+    pub fn read_block_headers_by_era(
+        &self,
+        era_id: EraId,
+    ) -> Result<Vec<BlockHeader>, FatalStorageError> {
+        let (lowest, highest) = match self.era_height_index.get(&era_id) {
+            Some(&heights) => heights,
+            None => return Ok(vec![]),
+        };
+        let mut txn = self.env.begin_ro_txn()?;
+        self.block_height_index
+            .range(lowest..=highest)
+            .filter_map(|(_, block_hash)| {
+                self.get_single_block_header(&mut txn, block_hash)
+                    .transpose()
+            })
+            .collect()
+    }
+
     /// Retrieves a single block header by hash.
     pub fn read_block_header(
         &self,
@@ -1784,6 +2686,25 @@ impl Storage {
         ret
     }
 
+    /// Retrieves the state root hashes of the stored blocks from `lowest_height` on.
+    fn get_state_root_hashes_from_height<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        lowest_height: u64,
+    ) -> Result<Vec<Digest>, FatalStorageError> {
+        let mut state_root_hashes = Vec::new();
+        for block_hash in self
+            .block_height_index
+            .range(lowest_height..)
+            .map(|(_, hash)| hash)
+        {
+            if let Some(block_header) = self.get_single_block_header(txn, block_hash)? {
+                state_root_hashes.push(*block_header.state_root_hash());
+            }
+        }
+        Ok(state_root_hashes)
+    }
+
     /// Returns the era IDs of the blocks in which the given deploys were executed.  If none of the
     /// deploys have been executed yet, an empty set will be returned.
     fn get_deploys_era_ids(&self, deploy_hashes: HashSet<DeployHash>) -> HashSet<EraId> {
@@ -2097,6 +3018,7 @@ impl Storage {
             insert_to_block_header_indices(
                 &mut self.block_height_index,
                 &mut self.switch_block_era_id_index,
+                &mut self.era_height_index,
                 block_header,
             )?;
         }
@@ -2141,8 +3063,12 @@ impl Storage {
                 return Ok(None);
             }
         };
-        let maybe_block_body =
-            get_body_for_block_header(txn, block_header.body_hash(), self.block_body_db);
+        let maybe_block_body = get_body_for_block_header(
+            txn,
+            block_header.body_hash(),
+            self.block_body_db,
+            self.cold_storage.as_ref(),
+        );
         let block_body = match maybe_block_body? {
             Some(block_body) => block_body,
             None => {
@@ -2176,7 +3102,7 @@ impl Storage {
         txn: &mut Tx,
         deploy_hash: &DeployHash,
     ) -> Result<Option<DeployWithFinalizedApprovals>, LmdbExtError> {
-        let maybe_original_deploy = txn.get_value(self.deploy_db, deploy_hash)?;
+        let maybe_original_deploy = self.read_deploy(txn, deploy_hash)?;
         if let Some(deploy) = maybe_original_deploy {
             let maybe_finalized_approvals =
                 txn.get_value(self.finalized_approvals_db, deploy_hash)?;
@@ -2189,6 +3115,36 @@ impl Storage {
         }
     }
 
+    /// Retrieves a deploy from the deploy store, falling back to cold storage if it is not there.
+    fn read_deploy<Tx: Transaction, V: 'static + DeserializeOwned>(
+        &self,
+        txn: &mut Tx,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<V>, LmdbExtError> {
+        match txn.get_value(self.deploy_db, deploy_hash)? {
+            Some(deploy) => Ok(Some(deploy)),
+            None => match &self.cold_storage {
+                Some(cold_storage) => cold_storage.read_deploy(deploy_hash),
+                None => Ok(None),
+            },
+        }
+    }
+
+    /// Returns `true` if the deploy is in the deploy store or in cold storage.
+    fn deploy_exists<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        deploy_hash: &DeployHash,
+    ) -> Result<bool, LmdbExtError> {
+        if txn.value_exists(self.deploy_db, deploy_hash)? {
+            return Ok(true);
+        }
+        match &self.cold_storage {
+            Some(cold_storage) => cold_storage.deploy_exists(deploy_hash),
+            None => Ok(false),
+        }
+    }
+
     /// Retrieves deploy metadata associated with deploy.
     ///
     /// If no deploy metadata is stored for the specific deploy, an empty metadata instance will be
@@ -2201,6 +3157,15 @@ impl Storage {
         Ok(txn.get_value(self.deploy_metadata_db, deploy_hash)?)
     }
 
+    /// Retrieves the reason a deploy was dropped without having been executed, if it was.
+    fn get_deploy_expiry_reason<Tx: Transaction>(
+        &self,
+        txn: &mut Tx,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<DeployExpiryReason>, FatalStorageError> {
+        Ok(txn.get_value(self.expired_deploy_db, deploy_hash)?)
+    }
+
     /// Retrieves successful transfers associated with block.
     ///
     /// If there is no record of successful transfers for this block, then the list will be built
@@ -2284,7 +3249,7 @@ impl Storage {
         deploy_hash: &DeployHash,
     ) -> Result<Option<Deploy>, FatalStorageError> {
         let mut txn = self.env.begin_ro_txn()?;
-        Ok(txn.get_value(self.deploy_db, &deploy_hash)?)
+        Ok(self.read_deploy(&mut txn, deploy_hash)?)
     }
 
     /// Stores a set of finalized approvals if they are different to the approvals in the original
@@ -2297,7 +3262,7 @@ impl Storage {
         finalized_approvals: &FinalizedApprovals,
     ) -> Result<bool, FatalStorageError> {
         let mut txn = self.env.begin_rw_txn()?;
-        let maybe_original_deploy: Option<Deploy> = txn.get_value(self.deploy_db, &deploy_hash)?;
+        let maybe_original_deploy: Option<Deploy> = self.read_deploy(&mut txn, deploy_hash)?;
         let original_deploy =
             maybe_original_deploy.ok_or(FatalStorageError::UnexpectedFinalizedApprovals {
                 deploy_hash: *deploy_hash,
@@ -2330,14 +3295,14 @@ impl Storage {
         self.env
             .begin_ro_txn()
             .map_err(Into::into)
-            .and_then(|mut txn| txn.get_value(self.deploy_db, &deploy_hash))
+            .and_then(|mut txn| self.read_deploy(&mut txn, &deploy_hash))
     }
 
     /// Retrieves a deploy from the deploy store by deploy ID.
     fn get_deploy(&self, deploy_id: DeployId) -> Result<Option<Deploy>, LmdbExtError> {
         let mut txn = self.env.begin_ro_txn()?;
 
-        let deploy = match txn.get_value::<_, Deploy>(self.deploy_db, deploy_id.deploy_hash())? {
+        let deploy = match self.read_deploy::<_, Deploy>(&mut txn, deploy_id.deploy_hash())? {
             None => return Ok(None),
             Some(deploy) if deploy.fetch_id() == deploy_id => return Ok(Some(deploy)),
             Some(deploy) => deploy,
@@ -2511,6 +3476,12 @@ impl Storage {
         }
     }
 
+    /// Returns whether the execution results of the block at `height` were pruned.
+    fn are_execution_results_pruned(&self, height: u64) -> bool {
+        self.pruned_execution_results
+            .map_or(false, |(low, high)| (low..=high).contains(&height))
+    }
+
     fn get_execution_results<Tx: Transaction>(
         &self,
         txn: &mut Tx,
@@ -2528,8 +3499,16 @@ impl Storage {
             Some(block_header) => block_header,
             None => return Ok(None),
         };
-        let maybe_block_body =
-            get_body_for_block_header(txn, block_header.body_hash(), self.block_body_db);
+        if self.are_execution_results_pruned(block_header.height()) {
+            trace!(%block_hash, "execution results of block were pruned");
+            return Ok(None);
+        }
+        let maybe_block_body = get_body_for_block_header(
+            txn,
+            block_header.body_hash(),
+            self.block_body_db,
+            self.cold_storage.as_ref(),
+        );
         let block_body = match maybe_block_body? {
             Some(block_body) => block_body,
             None => {
@@ -2589,7 +3568,7 @@ impl Storage {
 
         let mut ret = Vec::with_capacity(execution_results.len());
         for (deploy_hash, execution_result) in execution_results {
-            match txn.get_value::<_, Deploy>(self.deploy_db, &deploy_hash)? {
+            match self.read_deploy::<_, Deploy>(&mut txn, &deploy_hash)? {
                 None => {
                     error!(
                         %block_hash,
@@ -2660,6 +3639,7 @@ where
 fn insert_to_block_header_indices(
     block_height_index: &mut BTreeMap<u64, BlockHash>,
     switch_block_era_id_index: &mut BTreeMap<EraId, BlockHash>,
+    era_height_index: &mut BTreeMap<EraId, (u64, u64)>,
     block_header: &BlockHeader,
 ) -> Result<(), FatalStorageError> {
     let block_hash = block_header.block_hash();
@@ -2690,7 +3670,14 @@ fn insert_to_block_header_indices(
         }
     }
 
-    let _ = block_height_index.insert(block_header.height(), block_hash);
+    let height = block_header.height();
+    let (lowest, highest) = era_height_index
+        .entry(block_header.era_id())
+        .or_insert((height, height));
+    *lowest = (*lowest).min(height);
+    *highest = (*highest).max(height);
+
+    let _ = block_height_index.insert(height, block_hash);
     Ok(())
 }
 
@@ -2785,6 +3772,239 @@ fn move_storage_files_to_network_subdir(
     Ok(())
 }
 
+/// Returns the directory holding the files of `network_name` at `protocol_version` under the
+/// storage root directory.
+pub(crate) fn storage_dir(
+    storage_root: &Path,
+    network_name: &str,
+    protocol_version: ProtocolVersion,
+) -> PathBuf {
+    storage_root
+        .join(network_name)
+        .join(protocol_version.to_string())
+}
+
+/// Moves the storage files of previous layouts into the storage directory of `protocol_version`,
+/// unless it holds a database already.
+///
+/// The files are taken from the first location holding a database out of: the directory of the
+/// most recent earlier protocol version of the network, the network directory itself (the layout
+/// before protocol versions were kept apart) and the storage root directory (the layout before
+/// networks were kept apart).  Files in the storage root directory are only moved if they belong
+/// to `network_name`, as they don't record the network they belong to otherwise.
+fn migrate_storage_files(
+    storage_root: &Path,
+    network_name: &str,
+    protocol_version: ProtocolVersion,
+    map_size: usize,
+) -> Result<(), FatalStorageError> {
+    let network_dir = storage_root.join(network_name);
+    let dest_dir = storage_dir(storage_root, network_name, protocol_version);
+    if dest_dir.join(STORAGE_DB_FILENAME).exists() {
+        return Ok(());
+    }
+
+    let mut versions = vec![];
+    for entry in fs::read_dir(&network_dir)
+        .map_err(|error| FatalStorageError::ReadStorageDirectory(network_dir.clone(), error))?
+    {
+        let entry = entry
+            .map_err(|error| FatalStorageError::ReadStorageDirectory(network_dir.clone(), error))?;
+        if let Some(version) = dir_protocol_version(&entry.path()) {
+            if entry.path().join(STORAGE_DB_FILENAME).exists() {
+                versions.push(version);
+            }
+        }
+    }
+    let newer_version = versions
+        .iter()
+        .filter(|version| **version > protocol_version)
+        .min()
+        .copied();
+    let previous_version = versions
+        .into_iter()
+        .filter(|version| *version < protocol_version)
+        .max();
+
+    match (previous_version, newer_version) {
+        (Some(previous_version), newer_version) => {
+            if let Some(newer_version) = newer_version {
+                warn!(
+                    %newer_version,
+                    %protocol_version,
+                    "found storage of a newer protocol version, which will not be used"
+                );
+            }
+            let source_dir = network_dir.join(previous_version.to_string());
+            return move_dir_entries(&source_dir, &dest_dir);
+        }
+        // Starting with empty storage would resync the chain, while the node most likely was
+        // downgraded by mistake.
+        (None, Some(newer_version)) => {
+            return Err(FatalStorageError::NewerStorageVersion {
+                path: network_dir.join(newer_version.to_string()),
+                expected: protocol_version,
+                found: newer_version,
+            });
+        }
+        (None, None) => {}
+    }
+
+    if network_dir.join(STORAGE_DB_FILENAME).exists() {
+        return move_dir_entries(&network_dir, &dest_dir);
+    }
+
+    if should_move_storage_files_to_network_subdir(storage_root, &STORAGE_FILES)? {
+        match legacy_storage_network_name(storage_root, map_size)? {
+            Some(legacy_network_name) if legacy_network_name == network_name => {
+                move_storage_files_to_network_subdir(storage_root, &dest_dir, &STORAGE_FILES)?;
+            }
+            Some(legacy_network_name) => {
+                info!(
+                    path = %storage_root.display(),
+                    %legacy_network_name,
+                    "leaving storage files of another network in place"
+                );
+            }
+            None => {
+                warn!(
+                    path = %storage_root.display(),
+                    "leaving storage files of unknown network in place; move them to {} if \
+                    they belong to network {}",
+                    dest_dir.display(),
+                    network_name
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the protocol version named by the directory at `path`, if it is one.
+fn dir_protocol_version(path: &Path) -> Option<ProtocolVersion> {
+    if !path.is_dir() {
+        return None;
+    }
+    path.file_name()?.to_str()?.parse().ok()
+}
+
+/// Moves all entries of `source_dir` other than protocol version directories into `dest_dir`.
+fn move_dir_entries(source_dir: &Path, dest_dir: &Path) -> Result<(), FatalStorageError> {
+    let entries = fs::read_dir(source_dir).map_err(|error| {
+        FatalStorageError::ReadStorageDirectory(source_dir.to_path_buf(), error)
+    })?;
+    for entry in entries {
+        let source_path = entry
+            .map_err(|error| {
+                FatalStorageError::ReadStorageDirectory(source_dir.to_path_buf(), error)
+            })?
+            .path();
+        if dir_protocol_version(&source_path).is_some() {
+            continue;
+        }
+        let dest_path = match source_path.file_name() {
+            Some(file_name) => dest_dir.join(file_name),
+            None => continue,
+        };
+        fs::rename(&source_path, &dest_path).map_err(|original_error| {
+            FatalStorageError::UnableToMoveFile {
+                source_path: source_path.clone(),
+                dest_path: dest_path.clone(),
+                original_error,
+            }
+        })?;
+    }
+    info!(from = %source_dir.display(), to = %dest_dir.display(), "moved storage files");
+    Ok(())
+}
+
+/// Returns the name of the network to which the legacy storage database under `storage_root`
+/// belongs, if it can be told.
+///
+/// Storage databases of previous versions of the node don't record their network, so it is taken
+/// from the chain name of any of the stored deploys.
+fn legacy_storage_network_name(
+    storage_root: &Path,
+    map_size: usize,
+) -> Result<Option<String>, FatalStorageError> {
+    let env = open_environment(&storage_root.join(STORAGE_DB_FILENAME), map_size)?;
+    let txn = env.begin_ro_txn()?;
+    if let Ok(state_store_db) = env.open_db(Some("state_store")) {
+        match txn.get(state_store_db, &NETWORK_NAME_STORAGE_KEY) {
+            Ok(raw) => return Ok(Some(String::from_utf8_lossy(raw).into_owned())),
+            Err(lmdb::Error::NotFound) => (),
+            Err(error) => return Err(error.into()),
+        }
+    }
+    let deploy_db = match env.open_db(Some("deploys")) {
+        Ok(deploy_db) => deploy_db,
+        Err(lmdb::Error::NotFound) => return Ok(None),
+        Err(error) => return Err(error.into()),
+    };
+    let mut cursor = txn.open_ro_cursor(deploy_db)?;
+    let maybe_network_name = match cursor.iter().next() {
+        Some(row) => {
+            let (_, raw_deploy) = row?;
+            let deploy: Deploy = lmdb_ext::deserialize(raw_deploy)?;
+            Some(deploy.header().chain_name().to_string())
+        }
+        None => None,
+    };
+    Ok(maybe_network_name)
+}
+
+/// Ensures the storage database belongs to `network_name`.
+///
+/// Databases which don't yet record the network they belong to, i.e. new ones or ones created by
+/// previous versions of the node, are marked as belonging to `network_name`.
+fn check_network_name(
+    env: &Environment,
+    state_store_db: Database,
+    storage_dir: &Path,
+    network_name: &str,
+) -> Result<(), FatalStorageError> {
+    let mut txn = env.begin_rw_txn()?;
+    match txn.get(state_store_db, &NETWORK_NAME_STORAGE_KEY) {
+        Ok(raw) if raw == network_name.as_bytes() => return Ok(()),
+        Ok(raw) => {
+            return Err(FatalStorageError::NetworkNameMismatch {
+                path: storage_dir.to_path_buf(),
+                expected: network_name.to_string(),
+                found: String::from_utf8_lossy(raw).into_owned(),
+            })
+        }
+        Err(lmdb::Error::NotFound) => (),
+        Err(error) => return Err(error.into()),
+    }
+    txn.put(
+        state_store_db,
+        &NETWORK_NAME_STORAGE_KEY,
+        &network_name,
+        WriteFlags::default(),
+    )?;
+    txn.commit()?;
+    Ok(())
+}
+
+/// Opens the LMDB environment holding the storage databases at `path`.
+fn open_environment(path: &Path, map_size: usize) -> Result<Environment, lmdb::Error> {
+    Environment::new()
+        .set_flags(
+            OS_FLAGS
+            // We manage our own directory.
+            | EnvironmentFlags::NO_SUB_DIR
+            // Disable thread local storage, strongly suggested for operation with tokio.
+            | EnvironmentFlags::NO_TLS
+            // Disable read-ahead. Our data is not stored/read in sequence that would benefit from the read-ahead.
+            | EnvironmentFlags::NO_READAHEAD,
+        )
+        .set_max_readers(MAX_TRANSACTIONS)
+        .set_max_dbs(MAX_DB_COUNT)
+        .set_map_size(map_size)
+        .open(path)
+}
+
 /// On-disk storage configuration.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -2813,6 +4033,26 @@ pub struct Config {
     pub enable_mem_deduplication: bool,
     /// How many loads before memory duplication checks for dead references.
     pub mem_pool_prune_interval: u16,
+    /// Whether or not the database is compacted while the node is running.
+    pub enable_compaction: bool,
+    /// The interval between checks whether the database should be compacted.
+    pub compaction_interval: TimeDiff,
+    /// The minimum share of free pages in the database file, as a percentage, for it to be
+    /// compacted.
+    pub compaction_min_free_pages_percent: u8,
+    /// The number of most recent eras, preceding the era of the highest complete block, whose
+    /// blocks' execution results and approvals hashes are kept.
+    ///
+    /// If `None`, they are kept indefinitely.
+    pub execution_results_retention_eras: Option<u64>,
+    /// The path to the folder holding the cold storage database, to which the bodies and deploys
+    /// of old blocks are moved.
+    ///
+    /// If `None`, cold storage is disabled.
+    pub cold_storage_path: Option<PathBuf>,
+    /// The number of most recent eras, preceding the era of the highest complete block, whose
+    /// blocks' bodies and deploys are kept out of cold storage.
+    pub cold_storage_after_eras: u64,
 }
 
 impl Default for Config {
@@ -2826,6 +4066,12 @@ impl Default for Config {
             max_state_store_size: DEFAULT_MAX_STATE_STORE_SIZE,
             enable_mem_deduplication: true,
             mem_pool_prune_interval: 4096,
+            enable_compaction: false,
+            compaction_interval: DEFAULT_COMPACTION_INTERVAL.parse().unwrap(),
+            compaction_min_free_pages_percent: DEFAULT_COMPACTION_MIN_FREE_PAGES_PERCENT,
+            execution_results_retention_eras: None,
+            cold_storage_path: None,
+            cold_storage_after_eras: DEFAULT_COLD_STORAGE_AFTER_ERAS,
         }
     }
 }
@@ -2860,7 +4106,7 @@ impl Storage {
             .env
             .begin_ro_txn()
             .expect("could not create RO transaction");
-        txn.get_value(self.deploy_db, &deploy_hash)
+        self.read_deploy(&mut txn, &deploy_hash)
             .expect("could not retrieve value from storage")
     }
 
@@ -2993,13 +4239,21 @@ fn initialize_block_body_db(
     Ok(())
 }
 
-/// Retrieves the block body for the given block header.
+/// Retrieves the block body for the given block header, falling back to cold storage if it is not
+/// in `block_body_db`.
 fn get_body_for_block_header<Tx: Transaction>(
     txn: &mut Tx,
     block_body_hash: &Digest,
     block_body_db: Database,
+    cold_storage: Option<&ColdStorage>,
 ) -> Result<Option<BlockBody>, LmdbExtError> {
-    txn.get_value(block_body_db, block_body_hash)
+    match txn.get_value(block_body_db, block_body_hash)? {
+        Some(block_body) => Ok(Some(block_body)),
+        None => match cold_storage {
+            Some(cold_storage) => cold_storage.read_block_body(block_body_hash),
+            None => Ok(None),
+        },
+    }
 }
 
 /// Purges stale entries from the block metadata database.
@@ -3038,6 +4292,62 @@ fn initialize_block_metadata_db(
     Ok(())
 }
 
+/// Purges the entries of deleted blocks from the proposer database.
+fn initialize_proposer_db(
+    env: &Environment,
+    proposer_db: &Database,
+    deleted_block_hashes: &HashSet<BlockHash>,
+) -> Result<(), FatalStorageError> {
+    info!("initializing proposer database");
+
+    if !deleted_block_hashes.is_empty() {
+        let mut txn = env.begin_rw_txn()?;
+        let mut cursor = txn.open_rw_cursor(*proposer_db)?;
+
+        for row in cursor.iter() {
+            let (_, raw_val) = row?;
+            let block_hash: BlockHash = lmdb_ext::deserialize(raw_val)?;
+            if deleted_block_hashes.contains(&block_hash) {
+                cursor.del(WriteFlags::empty())?;
+            }
+        }
+        drop(cursor);
+        txn.commit()?;
+    }
+
+    info!("proposer database initialized");
+    Ok(())
+}
+
+/// Purges the entries of eras at or after `maybe_invalid_era` from the validator performance
+/// database, as the blocks of these eras have been removed by a hard reset.
+fn initialize_validator_performance_db(
+    env: &Environment,
+    validator_performance_db: &Database,
+    maybe_invalid_era: Option<EraId>,
+) -> Result<(), FatalStorageError> {
+    info!("initializing validator performance database");
+
+    if let Some(invalid_era) = maybe_invalid_era {
+        let mut txn = env.begin_rw_txn()?;
+        let mut cursor = txn.open_rw_cursor(*validator_performance_db)?;
+
+        for row in cursor.iter() {
+            let (raw_key, _) = row?;
+            let is_stale = validator_performance::era_id_from_index_key(raw_key)
+                .map_or(true, |era_id| era_id >= invalid_era);
+            if is_stale {
+                cursor.del(WriteFlags::empty())?;
+            }
+        }
+        drop(cursor);
+        txn.commit()?;
+    }
+
+    info!("validator performance database initialized");
+    Ok(())
+}
+
 /// Purges stale entries from the deploy metadata database.
 fn initialize_deploy_metadata_db(
     env: &Environment,
@@ -3063,22 +4373,55 @@ fn initialize_deploy_metadata_db(
     Ok(())
 }
 
-/// Returns all `Transform::WriteTransfer`s from the execution effects if this is an
-/// `ExecutionResult::Success`, or an empty `Vec` if `ExecutionResult::Failure`.
+/// Returns all `Transform::WriteTransfer`s and `Transform::WriteTransferV2`s from the execution
+/// effects if this is a successful `ExecutionResult`, or an empty `Vec` if it is a failure.
 pub fn successful_transfers(execution_result: &ExecutionResult) -> Vec<Transfer> {
     let effects = match execution_result {
         ExecutionResult::Success { effect, .. } => effect,
-        ExecutionResult::Failure { .. } => return vec![],
+        ExecutionResult::V2(execution_result) if execution_result.error_message.is_none() => {
+            &execution_result.effect
+        }
+        ExecutionResult::Failure { .. } | ExecutionResult::V2(_) => return vec![],
+    };
+
+    effects
+        .transforms
+        .iter()
+        .filter_map(|transform_entry| match &transform_entry.transform {
+            Transform::WriteTransfer(transfer) => Some(*transfer),
+            Transform::WriteTransferV2(transfer_v2) => Some(transfer_v2.transfer),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Returns the memo and address of every `Transform::WriteTransferV2` from the execution effects
+/// if this is a successful `ExecutionResult`, or an empty `Vec` if it is a failure.
+fn successful_transfer_memos(
+    execution_result: &ExecutionResult,
+) -> Vec<(TransferMemo, TransferAddr)> {
+    let effects = match execution_result {
+        ExecutionResult::Success { effect, .. } => effect,
+        ExecutionResult::V2(execution_result) if execution_result.error_message.is_none() => {
+            &execution_result.effect
+        }
+        ExecutionResult::Failure { .. } | ExecutionResult::V2(_) => return vec![],
     };
 
     effects
         .transforms
         .iter()
         .filter_map(|transform_entry| {
-            if let Transform::WriteTransfer(transfer) = transform_entry.transform {
-                Some(transfer)
-            } else {
-                None
+            let memo = match &transform_entry.transform {
+                Transform::WriteTransferV2(transfer_v2) => transfer_v2.memo,
+                _ => return None,
+            };
+            match Key::from_formatted_str(&transform_entry.key) {
+                Ok(Key::Transfer(transfer_addr)) => Some((memo, transfer_addr)),
+                _ => {
+                    error!(key = %transform_entry.key, "transfer written under a non-transfer key");
+                    None
+                }
             }
         })
         .collect()
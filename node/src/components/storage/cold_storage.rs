@@ -0,0 +1,139 @@
+//! A second storage tier holding the bodies and deploys of old blocks.
+//!
+//! Once blocks are older than the configured number of eras, their bodies and deploys are moved
+//! from the main database into the cold storage database, which may live on a different path,
+//! e.g. on cheaper and slower disks.  Reads of block bodies and deploys fall back to cold storage
+//! if they are missing from the main database.
+//!
+//! Block headers, which are indexed on startup, and all other data stay in the main database.
+
+use std::path::Path;
+
+use lmdb::{Database, DatabaseFlags, Environment, RwTransaction, Transaction, WriteFlags};
+use serde::de::DeserializeOwned;
+
+use casper_hashing::Digest;
+
+use super::{
+    lmdb_ext::{LmdbExtError, TransactionExt},
+    open_environment,
+};
+use crate::types::{BlockBody, DeployHash};
+
+/// The cold storage database and its databases.
+#[derive(Debug)]
+pub(super) struct ColdStorage {
+    /// Environment holding LMDB databases.
+    env: Environment,
+    /// The block body database.
+    block_body_db: Database,
+    /// The deploy database.
+    deploy_db: Database,
+}
+
+impl ColdStorage {
+    /// Opens the cold storage database at `path`, creating it if it doesn't exist.
+    pub(super) fn new(path: &Path, map_size: usize) -> Result<Self, lmdb::Error> {
+        let env = open_environment(path, map_size)?;
+        let block_body_db = env.create_db(Some("block_body"), DatabaseFlags::empty())?;
+        let deploy_db = env.create_db(Some("deploys"), DatabaseFlags::empty())?;
+        Ok(ColdStorage {
+            env,
+            block_body_db,
+            deploy_db,
+        })
+    }
+
+    /// Reads a block body.
+    pub(super) fn read_block_body(
+        &self,
+        block_body_hash: &Digest,
+    ) -> Result<Option<BlockBody>, LmdbExtError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        txn.get_value(self.block_body_db, block_body_hash)
+    }
+
+    /// Returns `true` if the given block body is stored.
+    pub(super) fn block_body_exists(&self, block_body_hash: &Digest) -> Result<bool, LmdbExtError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        txn.value_exists(self.block_body_db, block_body_hash)
+    }
+
+    /// Reads a deploy, deserialized as `V`.
+    pub(super) fn read_deploy<V: 'static + DeserializeOwned>(
+        &self,
+        deploy_hash: &DeployHash,
+    ) -> Result<Option<V>, LmdbExtError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        txn.get_value(self.deploy_db, deploy_hash)
+    }
+
+    /// Returns `true` if the given deploy is stored.
+    pub(super) fn deploy_exists(&self, deploy_hash: &DeployHash) -> Result<bool, LmdbExtError> {
+        let mut txn = self.env.begin_ro_txn()?;
+        txn.value_exists(self.deploy_db, deploy_hash)
+    }
+
+    /// Begins a write transaction on the cold storage database.
+    pub(super) fn begin_rw_txn(&self) -> Result<RwTransaction, lmdb::Error> {
+        self.env.begin_rw_txn()
+    }
+
+    /// Moves a block body from `hot_block_body_db` into cold storage.
+    ///
+    /// Returns `false` if the block body is not in `hot_block_body_db`.
+    pub(super) fn move_block_body(
+        &self,
+        hot_txn: &mut RwTransaction,
+        hot_block_body_db: Database,
+        cold_txn: &mut RwTransaction,
+        block_body_hash: &Digest,
+    ) -> Result<bool, lmdb::Error> {
+        move_value(
+            hot_txn,
+            hot_block_body_db,
+            cold_txn,
+            self.block_body_db,
+            block_body_hash,
+        )
+    }
+
+    /// Moves a deploy from `hot_deploy_db` into cold storage.
+    ///
+    /// Returns `false` if the deploy is not in `hot_deploy_db`.
+    pub(super) fn move_deploy(
+        &self,
+        hot_txn: &mut RwTransaction,
+        hot_deploy_db: Database,
+        cold_txn: &mut RwTransaction,
+        deploy_hash: &DeployHash,
+    ) -> Result<bool, lmdb::Error> {
+        move_value(
+            hot_txn,
+            hot_deploy_db,
+            cold_txn,
+            self.deploy_db,
+            deploy_hash,
+        )
+    }
+}
+
+/// Moves the raw value under `key` from `hot_db` to `cold_db`.
+///
+/// Returns `false` if there is no value under `key` in `hot_db`.
+fn move_value<K: AsRef<[u8]>>(
+    hot_txn: &mut RwTransaction,
+    hot_db: Database,
+    cold_txn: &mut RwTransaction,
+    cold_db: Database,
+    key: &K,
+) -> Result<bool, lmdb::Error> {
+    let raw_value = match hot_txn.get(hot_db, key) {
+        Ok(raw_value) => raw_value.to_vec(),
+        Err(lmdb::Error::NotFound) => return Ok(false),
+        Err(err) => return Err(err),
+    };
+    cold_txn.put(cold_db, key, &raw_value, WriteFlags::empty())?;
+    hot_txn.del(hot_db, key, None)?;
+    Ok(true)
+}
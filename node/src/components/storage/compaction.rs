@@ -0,0 +1,131 @@
+//! Compaction of the storage database.
+//!
+//! LMDB never shrinks its database file: pages freed by deletions and overwrites are reused, but a
+//! file which once grew large stays large.  Compaction copies every database into a fresh file,
+//! which then replaces the original one.
+//!
+//! The copy is made on a blocking thread from a snapshot of the database, and only replaces the
+//! original database if nothing was written to the latter in the meantime.
+
+use std::{
+    fs,
+    io::{self, ErrorKind},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use lmdb::{Cursor, Database, DatabaseFlags, Environment, Transaction, WriteFlags};
+
+use super::open_environment;
+
+/// The number of entries copied in a single write transaction.
+///
+/// LMDB limits the number of pages a single write transaction may dirty, so large databases must
+/// be copied in batches.
+const COPY_BATCH_SIZE: usize = 10_000;
+
+/// Returns the share of the pages of the database file which are free, as a percentage.
+pub(super) fn free_pages_percent(env: &Environment) -> Result<u64, lmdb::Error> {
+    // Page numbers are zero based.
+    let total_pages = env.info()?.last_pgno() as u64 + 1;
+    let free_pages = env.freelist()? as u64;
+    Ok(free_pages.saturating_mul(100) / total_pages)
+}
+
+/// Returns the number of bytes of the database file taken by pages in use, i.e. roughly the size
+/// of a compacted copy.
+pub(super) fn used_bytes(env: &Environment) -> Result<u64, lmdb::Error> {
+    let total_pages = env.info()?.last_pgno() as u64 + 1;
+    let free_pages = env.freelist()? as u64;
+    let page_size = u64::from(env.stat()?.page_size());
+    Ok(total_pages
+        .saturating_sub(free_pages)
+        .saturating_mul(page_size))
+}
+
+/// A compaction of the storage database, to be run on a blocking thread.
+pub(super) struct CompactionJob {
+    pub(super) env: Arc<Environment>,
+    pub(super) databases: Vec<(&'static str, Database)>,
+    pub(super) path: PathBuf,
+}
+
+impl CompactionJob {
+    /// Copies the databases into a new environment created at the job's path.
+    pub(super) fn run(self) -> Result<CompactedDatabase, lmdb::Error> {
+        // Taken before the snapshot, so that a write committed in between is seen as a change.
+        let last_txnid = self.env.info()?.last_txnid();
+        let (env, databases) = copy_compacted(&self.env, &self.databases, &self.path)?;
+        Ok(CompactedDatabase {
+            env,
+            databases,
+            last_txnid,
+        })
+    }
+}
+
+/// A compacted copy of the storage database.
+#[derive(Debug)]
+pub(crate) struct CompactedDatabase {
+    pub(super) env: Environment,
+    /// The copied databases, in the order they were given to the job.
+    pub(super) databases: Vec<Database>,
+    /// The ID of the last transaction committed to the original database before it was copied.
+    pub(super) last_txnid: usize,
+}
+
+/// Copies the given databases of `env` into a new environment created at `path`, and returns it
+/// along with the copied databases, in the same order.
+///
+/// Entries are appended to the copies in key order, so that the new database file holds no free
+/// pages.
+fn copy_compacted(
+    env: &Environment,
+    databases: &[(&'static str, Database)],
+    path: &Path,
+) -> Result<(Environment, Vec<Database>), lmdb::Error> {
+    let compacted_env = open_environment(path, env.info()?.map_size())?;
+    // Databases must be created before opening a write transaction, as creating one opens a write
+    // transaction itself.
+    let compacted_databases = databases
+        .iter()
+        .map(|(name, _)| compacted_env.create_db(Some(name), DatabaseFlags::empty()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let txn = env.begin_ro_txn()?;
+    for ((_, database), compacted_database) in databases.iter().zip(&compacted_databases) {
+        let mut cursor = txn.open_ro_cursor(*database)?;
+        let mut compacted_txn = compacted_env.begin_rw_txn()?;
+        for (index, row) in cursor.iter().enumerate() {
+            let (key, value) = row?;
+            if index > 0 && index % COPY_BATCH_SIZE == 0 {
+                compacted_txn.commit()?;
+                compacted_txn = compacted_env.begin_rw_txn()?;
+            }
+            compacted_txn.put(*compacted_database, &key, &value, WriteFlags::APPEND)?;
+        }
+        compacted_txn.commit()?;
+    }
+    txn.commit()?;
+
+    compacted_env.sync(true)?;
+    Ok((compacted_env, compacted_databases))
+}
+
+/// Returns the path of the lock file LMDB creates alongside the database file at `path`.
+fn lock_file_path(path: &Path) -> PathBuf {
+    let mut lock_file_path = path.as_os_str().to_owned();
+    lock_file_path.push("-lock");
+    PathBuf::from(lock_file_path)
+}
+
+/// Removes the database file at `path` and its lock file, if they exist.
+pub(super) fn remove_database_files(path: &Path) -> io::Result<()> {
+    for file_path in [path.to_path_buf(), lock_file_path(path)] {
+        match fs::remove_file(&file_path) {
+            Err(error) if error.kind() != ErrorKind::NotFound => return Err(error),
+            _ => (),
+        }
+    }
+    Ok(())
+}
@@ -4,7 +4,7 @@ use thiserror::Error;
 use tracing::error;
 
 use casper_hashing::Digest;
-use casper_types::{bytesrepr, crypto, EraId};
+use casper_types::{bytesrepr, crypto, EraId, ProtocolVersion};
 
 use super::lmdb_ext::LmdbExtError;
 use crate::types::{
@@ -67,6 +67,39 @@ pub enum FatalStorageError {
         /// The original `io::Error` from `fs::rename`.
         original_error: io::Error,
     },
+    /// Filesystem error while removing the leftovers of a database compaction.
+    #[error("unable to remove compacted database `{}`: {}", .0.display(), .1)]
+    RemoveCompactedDatabase(PathBuf, io::Error),
+    /// Filesystem error while listing the contents of a storage directory.
+    #[error("unable to read storage directory `{}`: {}", .0.display(), .1)]
+    ReadStorageDirectory(PathBuf, io::Error),
+    /// The storage database was created for a different network.
+    #[error(
+        "storage in `{}` belongs to network `{found}`, not `{expected}`",
+        path.display()
+    )]
+    NetworkNameMismatch {
+        /// The storage directory.
+        path: PathBuf,
+        /// The network name from the chainspec.
+        expected: String,
+        /// The network name recorded in the storage database.
+        found: String,
+    },
+    /// Only storage of a newer protocol version than the node's was found.
+    #[error(
+        "found storage of protocol version {found} in `{}`, but none of protocol version \
+         {expected} or older",
+        path.display()
+    )]
+    NewerStorageVersion {
+        /// The directory holding the storage of the newer protocol version.
+        path: PathBuf,
+        /// The protocol version of the node.
+        expected: ProtocolVersion,
+        /// The oldest protocol version of the storage found.
+        found: ProtocolVersion,
+    },
     /// Mix of missing and found storage files.
     #[error("expected files to exist: {missing_files:?}.")]
     MissingStorageFiles {
@@ -165,6 +198,9 @@ pub enum FatalStorageError {
     /// Error initializing metrics.
     #[error("failed to initialize metrics for storage: {0}")]
     Prometheus(#[from] prometheus::Error),
+    /// The storage self-test did not read back what it had written or deleted.
+    #[error("storage self-test failed: {0}")]
+    SelfTest(&'static str),
 }
 
 // We wholesale wrap lmdb errors and treat them as internal errors here.
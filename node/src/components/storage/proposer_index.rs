@@ -0,0 +1,53 @@
+//! An index of the blocks proposed by each validator, built from the blocks written to storage.
+
+use std::ops::Range;
+
+use casper_types::PublicKey;
+
+/// The length of the prefix of an index key identifying the proposer.
+const KEY_PREFIX_LENGTH: usize = 32;
+
+/// The length of a key of the proposer index.
+const KEY_LENGTH: usize = KEY_PREFIX_LENGTH + 8;
+
+/// Returns the key of the entry of the block proposed by `proposer` at the given height in the
+/// proposer index.
+///
+/// The key is the proposer's account hash followed by the big-endian block height, so that all
+/// the entries of a proposer are adjacent and ordered by height.
+pub(super) fn index_key(proposer: &PublicKey, height: u64) -> [u8; KEY_LENGTH] {
+    let mut key = [0; KEY_LENGTH];
+    key[..KEY_PREFIX_LENGTH].copy_from_slice(&proposer.to_account_hash().value());
+    key[KEY_PREFIX_LENGTH..].copy_from_slice(&height.to_be_bytes());
+    key
+}
+
+/// Returns the range of keys of the entries of the blocks proposed by `proposer` at heights in
+/// `heights`.
+pub(super) fn index_key_range(
+    proposer: &PublicKey,
+    heights: Range<u64>,
+) -> Range<[u8; KEY_LENGTH]> {
+    index_key(proposer, heights.start)..index_key(proposer, heights.end)
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_only_cover_keys_of_proposer_within_heights() {
+        let mut rng = TestRng::new();
+        let proposer = PublicKey::random(&mut rng);
+        let other_proposer = PublicKey::random(&mut rng);
+
+        let key_range = index_key_range(&proposer, 10..300);
+        assert!(!key_range.contains(&index_key(&proposer, 9)));
+        assert!(key_range.contains(&index_key(&proposer, 10)));
+        assert!(key_range.contains(&index_key(&proposer, 256)));
+        assert!(!key_range.contains(&index_key(&proposer, 300)));
+        assert!(!key_range.contains(&index_key(&other_proposer, 10)));
+    }
+}
@@ -0,0 +1,94 @@
+//! Pruning of the execution results and approvals hashes of blocks older than the retention window.
+//!
+//! Block headers, bodies and finality signatures of pruned blocks are kept.
+
+use std::ops::Range;
+
+/// The maximum number of blocks pruned in a single pass.
+const PRUNING_BATCH_SIZE: u64 = 1_000;
+
+/// Returns the heights of the blocks to prune in the next pass, along with the lowest and highest
+/// heights of the pruned blocks once they are, or `None` if there is nothing to prune.
+///
+/// Only blocks in the available block range, from `available_low` on, and below `retention_low`
+/// are pruned.  Blocks below the available block range may still be in the process of being
+/// acquired by historical sync.  `pruned` holds the lowest and highest heights already pruned.
+pub(super) fn next_pruning_range(
+    available_low: u64,
+    retention_low: u64,
+    pruned: Option<(u64, u64)>,
+) -> Option<(Range<u64>, (u64, u64))> {
+    let mut start = available_low;
+    let mut end = retention_low;
+    let mut joined_pruned_high = None;
+    if let Some((pruned_low, pruned_high)) = pruned {
+        if pruned_low <= start && start <= pruned_high.saturating_add(1) {
+            // Continue where the previous pass stopped.
+            start = pruned_high.saturating_add(1);
+        } else if start < pruned_low && pruned_low < end {
+            // Blocks below the pruned ones were acquired since, prune them up to the pruned ones.
+            end = pruned_low;
+            joined_pruned_high = Some(pruned_high);
+        }
+    }
+
+    let batch_end = end.min(start.saturating_add(PRUNING_BATCH_SIZE));
+    if start >= batch_end {
+        return None;
+    }
+    let pruned_high = match joined_pruned_high {
+        Some(pruned_high) if batch_end == end => pruned_high,
+        _ => batch_end - 1,
+    };
+    Some((start..batch_end, (available_low, pruned_high)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_not_prune_outside_available_range_or_within_retention_window() {
+        assert_eq!(next_pruning_range(10, 10, None), None);
+        assert_eq!(next_pruning_range(10, 5, None), None);
+        assert_eq!(next_pruning_range(10, 15, None), Some((10..15, (10, 14))));
+        assert_eq!(next_pruning_range(10, 15, Some((10, 14))), None);
+    }
+
+    #[test]
+    fn should_prune_in_batches() {
+        let retention_low = 10 + 2 * PRUNING_BATCH_SIZE + 5;
+        let (heights, pruned) = next_pruning_range(10, retention_low, None).unwrap();
+        assert_eq!(heights, 10..10 + PRUNING_BATCH_SIZE);
+        assert_eq!(pruned, (10, 9 + PRUNING_BATCH_SIZE));
+
+        let (heights, pruned) = next_pruning_range(10, retention_low, Some(pruned)).unwrap();
+        assert_eq!(
+            heights,
+            10 + PRUNING_BATCH_SIZE..10 + 2 * PRUNING_BATCH_SIZE
+        );
+        assert_eq!(pruned, (10, 9 + 2 * PRUNING_BATCH_SIZE));
+
+        let (heights, pruned) = next_pruning_range(10, retention_low, Some(pruned)).unwrap();
+        assert_eq!(heights, 10 + 2 * PRUNING_BATCH_SIZE..retention_low);
+        assert_eq!(pruned, (10, retention_low - 1));
+    }
+
+    #[test]
+    fn should_prune_blocks_acquired_below_pruned_ones() {
+        // Historical sync lowered the available block range.
+        assert_eq!(
+            next_pruning_range(5, 30, Some((10, 19))),
+            Some((5..10, (5, 19)))
+        );
+        // The available block range was raised.
+        assert_eq!(
+            next_pruning_range(25, 30, Some((10, 19))),
+            Some((25..30, (25, 29)))
+        );
+        assert_eq!(
+            next_pruning_range(15, 30, Some((10, 19))),
+            Some((20..30, (15, 29)))
+        );
+    }
+}
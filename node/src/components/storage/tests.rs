@@ -1,6 +1,7 @@
 //! Unit tests for the storage component.
 
 use std::{
+    borrow::Cow,
     collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fs::{self, File},
     iter::{self, FromIterator},
@@ -13,17 +14,20 @@ use rand::{prelude::SliceRandom, Rng};
 use serde::{Deserialize, Serialize};
 use smallvec::smallvec;
 
+use casper_hashing::Digest;
 use casper_types::{
     generate_ed25519_keypair, system::auction::UnbondingPurse, testing::TestRng, AccessRights,
     EraId, ExecutionEffect, ExecutionResult, Key, ProtocolVersion, PublicKey, SecretKey, TimeDiff,
-    Transfer, Transform, TransformEntry, URef, U512,
+    Timestamp, Transfer, TransferAddr, TransferMemo, TransferV2, Transform, TransformEntry, URef,
+    U512,
 };
 
 use super::{
     initialize_block_metadata_db,
     lmdb_ext::{deserialize_internal, serialize_internal, TransactionExt, WriteTransactionExt},
-    move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, Config,
-    Storage, FORCE_RESYNC_FILE_NAME,
+    move_storage_files_to_network_subdir, should_move_storage_files_to_network_subdir, storage_dir,
+    Config, FatalStorageError, Storage, FORCE_RESYNC_FILE_NAME, SELF_TEST_STORAGE_KEY,
+    STORAGE_DB_FILENAME, STORAGE_FILES,
 };
 use crate::{
     components::fetcher::{FetchItem, FetchResponse},
@@ -35,9 +39,9 @@ use crate::{
     types::{
         sync_leap_validation_metadata::SyncLeapValidationMetaData, AvailableBlockRange, Block,
         BlockHash, BlockHashAndHeight, BlockHashHeightAndEra, BlockHeader, BlockHeaderWithMetadata,
-        BlockSignatures, Chainspec, ChainspecRawBytes, Deploy, DeployHash, DeployMetadata,
-        DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature, LegacyDeploy,
-        SyncLeapIdentifier, TestBlockBuilder,
+        BlockSignatures, Chainspec, ChainspecRawBytes, Deploy, DeployExpiryReason, DeployHash,
+        DeployMetadata, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
+        LegacyDeploy, SyncLeapIdentifier, TestBlockBuilder,
     },
     utils::{Loadable, WithDir},
 };
@@ -57,6 +61,7 @@ fn new_config(harness: &ComponentHarness<UnitTestEvent>) -> Config {
         max_state_store_size: 50 * MIB,
         enable_mem_deduplication: true,
         mem_pool_prune_interval: 4,
+        ..Default::default()
     }
 }
 
@@ -448,6 +453,23 @@ fn put_block_signatures(
     response
 }
 
+/// Stores a finality signature in a storage component.
+fn put_finality_signature(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    signature: Box<FinalitySignature>,
+) -> bool {
+    let response = harness.send_request(storage, move |responder| {
+        StorageRequest::PutFinalitySignature {
+            signature,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+    response
+}
+
 /// Stores a deploy in a storage component.
 fn put_deploy(
     harness: &mut ComponentHarness<UnitTestEvent>,
@@ -490,6 +512,22 @@ fn put_execution_results(
     assert!(harness.is_idle());
 }
 
+/// Stores the reasons deploys were dropped without having been executed in a storage component.
+fn put_expired_deploys(
+    harness: &mut ComponentHarness<UnitTestEvent>,
+    storage: &mut Storage,
+    expired_deploys: Vec<(DeployHash, DeployExpiryReason)>,
+) {
+    harness.send_request(storage, move |responder| {
+        StorageRequest::PutExpiredDeploys {
+            expired_deploys,
+            responder,
+        }
+        .into()
+    });
+    assert!(harness.is_idle());
+}
+
 #[test]
 fn get_block_of_non_existing_block_returns_none() {
     let mut harness = ComponentHarness::default();
@@ -808,7 +846,7 @@ fn can_retrieve_store_and_load_deploys() {
                 recv_block_hash_and_height
             )
         }
-        DeployMetadataExt::Empty => panic!(
+        DeployMetadataExt::Expired(_) | DeployMetadataExt::Empty => panic!(
             "We stored block info in the deploy hash index \
                                             but we received nothing in the response."
         ),
@@ -840,10 +878,50 @@ fn can_retrieve_store_and_load_deploys() {
                 "We didn't store any block info in the index but we received it in the response."
             )
         }
+        DeployMetadataExt::Expired(_) => {
+            panic!("We didn't store any expiry but we received it in the response.")
+        }
         DeployMetadataExt::Empty => { /* We didn't store execution results or block info */ }
     }
 }
 
+#[test]
+fn can_retrieve_expiry_reason_of_deploy() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy = Arc::new(Deploy::random(&mut harness.rng));
+    assert!(put_deploy(&mut harness, &mut storage, Arc::clone(&deploy)));
+    put_expired_deploys(
+        &mut harness,
+        &mut storage,
+        vec![(*deploy.hash(), DeployExpiryReason::InvalidAtProposal)],
+    );
+
+    let (_, metadata_response) =
+        get_naive_deploy_and_metadata(&mut harness, &mut storage, *deploy.hash())
+            .expect("no deploy with metadata returned");
+    assert_eq!(
+        metadata_response,
+        DeployMetadataExt::Expired(DeployExpiryReason::InvalidAtProposal)
+    );
+
+    // Knowing the block containing the deploy takes precedence over its expiry.
+    let block_hash_height_and_era = BlockHashHeightAndEra::random(&mut harness.rng);
+    assert!(insert_to_deploy_index(
+        &mut storage,
+        deploy.hash(),
+        block_hash_height_and_era
+    ));
+    let (_, metadata_response) =
+        get_naive_deploy_and_metadata(&mut harness, &mut storage, *deploy.hash())
+            .expect("no deploy with metadata returned");
+    assert_eq!(
+        metadata_response,
+        BlockHashAndHeight::from(&block_hash_height_and_era)
+    );
+}
+
 #[test]
 fn should_retrieve_deploys_era_ids() {
     let mut harness = ComponentHarness::default();
@@ -1340,6 +1418,329 @@ fn should_provide_transfers_after_emptied() {
     assert_eq!(Some(vec![transfer]), maybe_transfers);
 }
 
+#[test]
+fn should_index_transfers_by_memo() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy = Deploy::random_valid_native_transfer(&mut harness.rng);
+    let deploy_hash = *deploy.hash();
+    let block = Block::random_with_deploys(&mut harness.rng, Some(&deploy));
+    storage.write_block(&block).unwrap();
+    let block_hash = *block.hash();
+
+    let (_, transfer) = prepare_exec_result_with_transfer(&mut harness.rng, &deploy_hash);
+    let memo: TransferMemo = harness.rng.gen();
+    let transfer_addr: TransferAddr = harness.rng.gen();
+    let transform = TransformEntry {
+        key: Key::Transfer(transfer_addr).to_formatted_string(),
+        transform: Transform::WriteTransferV2(TransferV2::new(transfer, memo)),
+    };
+    let exec_result = ExecutionResult::Success {
+        effect: ExecutionEffect::new(vec![transform]),
+        transfers: vec![transfer_addr],
+        cost: harness.rng.gen(),
+    };
+    let mut exec_results = HashMap::new();
+    exec_results.insert(deploy_hash, exec_result);
+
+    // Storing the same execution results twice should not index the transfer twice.
+    put_execution_results(&mut harness, &mut storage, block_hash, exec_results.clone());
+    put_execution_results(&mut harness, &mut storage, block_hash, exec_results);
+
+    let mut txn = storage.env.begin_ro_txn().unwrap();
+    assert_eq!(
+        storage.get_transfers_by_memo(&mut txn, &memo).unwrap(),
+        vec![transfer_addr]
+    );
+    assert!(storage
+        .get_transfers_by_memo(&mut txn, &harness.rng.gen())
+        .unwrap()
+        .is_empty());
+    drop(txn);
+
+    // The transfer is still reported among the block's transfers.
+    let retrieved_transfers = storage
+        .get_transfers(&block_hash)
+        .expect("should execute get")
+        .expect("should return Some");
+    assert_eq!(retrieved_transfers, vec![transfer]);
+}
+
+#[test]
+fn should_index_deploys_by_body_hash() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let deploy = Deploy::random(&mut harness.rng);
+    let body_hash = *deploy.header().body_hash();
+    // The same body, signed by another account at another time.
+    let resubmitted_deploy = Deploy::new(
+        Timestamp::random(&mut harness.rng),
+        deploy.header().ttl(),
+        deploy.header().gas_price(),
+        vec![],
+        deploy.header().chain_name().to_string(),
+        deploy.payment().clone(),
+        deploy.session().clone(),
+        &SecretKey::random(&mut harness.rng),
+        None,
+    );
+    assert_eq!(*resubmitted_deploy.header().body_hash(), body_hash);
+    assert_ne!(resubmitted_deploy.hash(), deploy.hash());
+
+    // Storing the same deploy twice should not index it twice.
+    assert!(put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::new(deploy.clone())
+    ));
+    assert!(!put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::new(deploy.clone())
+    ));
+    assert!(put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::new(resubmitted_deploy.clone())
+    ));
+    let other_deploy = Deploy::random(&mut harness.rng);
+    assert!(put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::new(other_deploy)
+    ));
+
+    let mut txn = storage.env.begin_ro_txn().unwrap();
+    assert_eq!(
+        storage
+            .get_deploy_hashes_by_body_hash(&mut txn, &body_hash)
+            .unwrap(),
+        vec![*deploy.hash(), *resubmitted_deploy.hash()]
+    );
+    assert!(storage
+        .get_deploy_hashes_by_body_hash(&mut txn, &Digest::hash([42]))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn should_read_blocks_by_proposer() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let proposer = PublicKey::random(&mut harness.rng);
+    let proposed_blocks: Vec<_> = [1, 3, 256, 300]
+        .into_iter()
+        .map(|height| {
+            TestBlockBuilder::new()
+                .height(height)
+                .proposer(proposer.clone())
+                .build(&mut harness.rng)
+        })
+        .collect();
+    for block in &proposed_blocks {
+        assert!(storage.write_block(block).unwrap());
+    }
+    let other_block = TestBlockBuilder::new().height(2).build(&mut harness.rng);
+    assert!(storage.write_block(&other_block).unwrap());
+
+    assert_eq!(
+        storage
+            .read_blocks_by_proposer(&proposer, 0..u64::MAX)
+            .unwrap(),
+        proposed_blocks
+    );
+    assert_eq!(
+        storage.read_blocks_by_proposer(&proposer, 2..300).unwrap(),
+        proposed_blocks[1..3].to_vec()
+    );
+    assert!(storage
+        .read_blocks_by_proposer(&proposer, 4..256)
+        .unwrap()
+        .is_empty());
+    assert_eq!(
+        storage
+            .read_blocks_by_proposer(other_block.body().proposer(), 0..u64::MAX)
+            .unwrap(),
+        vec![other_block]
+    );
+}
+
+#[test]
+fn should_compact_database() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let blocks: Vec<_> = (0..10).map(|_| Block::random(&mut harness.rng)).collect();
+    for block in &blocks {
+        assert!(storage.write_block(block).unwrap());
+    }
+
+    storage.compaction_min_free_pages_percent = 0;
+    let job = storage
+        .prepare_compaction()
+        .unwrap()
+        .expect("should compact database");
+    storage.finish_compaction(job.run()).unwrap();
+    assert!(!storage.root.join(COMPACTED_STORAGE_DB_FILENAME).exists());
+
+    // All data should be readable from, and new data writable to, the compacted database.
+    for block in &blocks {
+        assert_eq!(
+            storage.read_block(block.hash()).unwrap().as_ref(),
+            Some(block)
+        );
+    }
+    let block = Block::random(&mut harness.rng);
+    assert!(storage.write_block(&block).unwrap());
+    assert_eq!(storage.read_block(block.hash()).unwrap(), Some(block));
+}
+
+#[test]
+fn should_discard_compacted_copy_if_database_was_written_to() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let block = Block::random(&mut harness.rng);
+    assert!(storage.write_block(&block).unwrap());
+
+    storage.compaction_min_free_pages_percent = 0;
+    let job = storage
+        .prepare_compaction()
+        .unwrap()
+        .expect("should compact database");
+    let outcome = job.run();
+
+    // A block written after the copy was made must not be lost.
+    let late_block = Block::random(&mut harness.rng);
+    assert!(storage.write_block(&late_block).unwrap());
+    storage.finish_compaction(outcome).unwrap();
+    assert!(!storage.root.join(COMPACTED_STORAGE_DB_FILENAME).exists());
+
+    assert_eq!(storage.read_block(block.hash()).unwrap(), Some(block));
+    assert_eq!(
+        storage.read_block(late_block.hash()).unwrap(),
+        Some(late_block)
+    );
+}
+
+#[test]
+fn should_read_block_headers_by_era() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let era_blocks: Vec<_> = (30..34)
+        .map(|height| {
+            TestBlockBuilder::new()
+                .era(3)
+                .height(height)
+                .switch_block(height == 33)
+                .build(&mut harness.rng)
+        })
+        .collect();
+    let next_era_block = TestBlockBuilder::new()
+        .era(4)
+        .height(34)
+        .switch_block(false)
+        .build(&mut harness.rng);
+    // Blocks are not necessarily stored in order of height, e.g. while syncing.
+    for block in era_blocks.iter().rev().chain(iter::once(&next_era_block)) {
+        assert!(storage.write_block(block).unwrap());
+    }
+
+    let expected_headers: Vec<_> = era_blocks
+        .iter()
+        .map(|block| block.header().clone())
+        .collect();
+    assert_eq!(
+        storage.read_block_headers_by_era(EraId::new(3)).unwrap(),
+        expected_headers
+    );
+    assert_eq!(
+        storage.read_block_headers_by_era(EraId::new(4)).unwrap(),
+        vec![next_era_block.header().clone()]
+    );
+    assert!(storage
+        .read_block_headers_by_era(EraId::new(5))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn should_index_validator_performance() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    let block = Block::random_switch_block(&mut harness.rng);
+    let era_id = block.header().era_id();
+    let proposer = block.body().proposer().clone();
+    // Storing the same block twice should not count its proposal twice.
+    assert!(storage.write_block(&block).unwrap());
+    assert!(storage.write_block(&block).unwrap());
+
+    let signature_1 = FinalitySignature::random_for_block(*block.hash(), era_id.value());
+    let signature_2 = FinalitySignature::random_for_block(*block.hash(), era_id.value());
+    let mut block_signatures = BlockSignatures::new(*block.hash(), era_id);
+    block_signatures.insert_proof(signature_1.public_key.clone(), signature_1.signature);
+    assert!(put_block_signatures(
+        &mut harness,
+        &mut storage,
+        block_signatures.clone()
+    ));
+    // Storing the same signature again, individually or along with a new one, should not count
+    // it twice.
+    assert!(put_finality_signature(
+        &mut harness,
+        &mut storage,
+        Box::new(signature_1.clone())
+    ));
+    block_signatures.insert_proof(signature_2.public_key.clone(), signature_2.signature);
+    assert!(put_block_signatures(
+        &mut harness,
+        &mut storage,
+        block_signatures
+    ));
+
+    let mut txn = storage.env.begin_ro_txn().unwrap();
+    let proposer_performance = storage
+        .get_validator_performance(&mut txn, &proposer)
+        .unwrap();
+    assert_eq!(proposer_performance.len(), 1);
+    assert_eq!(proposer_performance[0].era_id, era_id);
+    assert_eq!(
+        proposer_performance[0].proposed_blocks,
+        iter::once(block.height()).collect::<BTreeSet<_>>()
+    );
+    for signature in [&signature_1, &signature_2] {
+        let performance = storage
+            .get_validator_performance(&mut txn, &signature.public_key)
+            .unwrap();
+        assert_eq!(performance.len(), 1);
+        assert_eq!(performance[0].finality_signatures, 1);
+        assert!(performance[0].proposed_blocks.is_empty());
+    }
+
+    let era_report = block.header().era_end().unwrap().era_report();
+    for equivocator in &era_report.equivocators {
+        let performance = storage
+            .get_validator_performance(&mut txn, equivocator)
+            .unwrap();
+        assert!(performance[0].equivocated);
+    }
+    for inactive_validator in &era_report.inactive_validators {
+        let performance = storage
+            .get_validator_performance(&mut txn, inactive_validator)
+            .unwrap();
+        assert!(performance[0].inactive);
+    }
+    assert!(storage
+        .get_validator_performance(&mut txn, &PublicKey::random(&mut harness.rng))
+        .unwrap()
+        .is_empty());
+}
+
 /// Example state used in storage.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 struct StateData {
@@ -1533,7 +1934,9 @@ fn should_hard_reset() {
                 DeployMetadataExt::BlockInfo(_block_hash_and_height) => {
                     assert!(!should_have_exec_results)
                 }
-                DeployMetadataExt::Empty => assert!(!should_have_exec_results),
+                DeployMetadataExt::Expired(_) | DeployMetadataExt::Empty => {
+                    assert!(!should_have_exec_results)
+                }
             };
         }
     };
@@ -1565,7 +1968,7 @@ fn should_create_subdir_named_after_network() {
     )
     .unwrap();
 
-    let expected_path = cfg.path.join(network_name);
+    let expected_path = cfg.path.join(network_name).join("1.0.0");
 
     assert!(expected_path.exists());
     assert_eq!(expected_path, storage.root_path());
@@ -1659,6 +2062,124 @@ fn should_actually_move_specified_files() {
     assert!(dest_path3.exists());
 }
 
+#[test]
+fn should_reject_storage_of_other_network() {
+    let harness = ComponentHarness::default();
+    let cfg = new_config(&harness);
+    let protocol_version = ProtocolVersion::V1_0_0;
+    let storage = storage_fixture_from_parts(&harness, None, None, Some("casper-test"), None, None);
+    let testnet_dir = storage.root_path().to_path_buf();
+    drop(storage);
+
+    // Copy the database of one network into the directory of another.
+    let mainnet_dir = storage_dir(&cfg.path, "casper", protocol_version);
+    fs::create_dir_all(&mainnet_dir).unwrap();
+    fs::copy(
+        testnet_dir.join(STORAGE_DB_FILENAME),
+        mainnet_dir.join(STORAGE_DB_FILENAME),
+    )
+    .unwrap();
+
+    let result = Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        protocol_version,
+        EraId::default(),
+        "casper",
+        MAX_TTL.into(),
+        RECENT_ERA_COUNT,
+        None,
+        false,
+    );
+    assert!(matches!(
+        result,
+        Err(FatalStorageError::NetworkNameMismatch { ref expected, ref found, .. })
+            if expected == "casper" && found == "casper-test"
+    ));
+}
+
+#[test]
+fn should_move_storage_of_previous_protocol_version() {
+    let mut harness = ComponentHarness::default();
+    let deploy = Deploy::random(&mut harness.rng);
+
+    let storage = storage_fixture_from_parts(&harness, None, None, None, None, None);
+    let previous_dir = storage.root_path().to_path_buf();
+    storage.put_deploy(&deploy).unwrap();
+    drop(storage);
+
+    let storage = storage_fixture_from_parts(
+        &harness,
+        None,
+        Some(ProtocolVersion::from_parts(1, 1, 0)),
+        None,
+        None,
+        None,
+    );
+    assert_ne!(previous_dir, storage.root_path());
+    assert!(!previous_dir.join(STORAGE_DB_FILENAME).exists());
+    assert_eq!(storage.get_deploy_by_hash(*deploy.hash()), Some(deploy));
+}
+
+#[test]
+fn should_reject_storage_of_newer_protocol_version_only() {
+    let harness = ComponentHarness::default();
+    let cfg = new_config(&harness);
+    let newer_version = ProtocolVersion::from_parts(1, 1, 0);
+
+    let storage = storage_fixture_from_parts(&harness, None, Some(newer_version), None, None, None);
+    let newer_dir = storage.root_path().to_path_buf();
+    drop(storage);
+
+    let result = Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::V1_0_0,
+        EraId::default(),
+        "test",
+        MAX_TTL.into(),
+        RECENT_ERA_COUNT,
+        None,
+        false,
+    );
+    assert!(matches!(
+        result,
+        Err(FatalStorageError::NewerStorageVersion { ref path, expected, found })
+            if *path == newer_dir
+                && expected == ProtocolVersion::V1_0_0
+                && found == newer_version
+    ));
+    assert!(newer_dir.join(STORAGE_DB_FILENAME).exists());
+}
+
+#[test]
+fn should_only_move_legacy_storage_files_of_same_network() {
+    let harness = ComponentHarness::default();
+    let cfg = new_config(&harness);
+    let storage = storage_fixture_from_parts(&harness, None, None, Some("casper-test"), None, None);
+    let testnet_dir = storage.root_path().to_path_buf();
+    drop(storage);
+
+    // Lay the storage files out as previous versions of the node did, directly in the root.
+    for file_name in STORAGE_FILES {
+        let source_path = testnet_dir.join(file_name);
+        let dest_path = cfg.path.join(file_name);
+        if source_path.exists() {
+            fs::rename(source_path, dest_path).unwrap();
+        } else {
+            File::create(dest_path).unwrap();
+        }
+    }
+
+    let storage = storage_fixture_from_parts(&harness, None, None, Some("casper"), None, None);
+    assert!(cfg.path.join(STORAGE_DB_FILENAME).exists());
+    drop(storage);
+
+    let storage = storage_fixture_from_parts(&harness, None, None, Some("casper-test"), None, None);
+    assert!(!cfg.path.join(STORAGE_DB_FILENAME).exists());
+    assert!(storage.root_path().join(STORAGE_DB_FILENAME).exists());
+}
+
 #[test]
 fn can_put_and_get_block() {
     let mut harness = ComponentHarness::default();
@@ -2235,3 +2756,166 @@ fn should_initialize_block_metadata_db() {
     assert_signatures(&storage, *block_3.hash(), vec![]);
     assert_signatures(&storage, *block_4.hash(), vec![]);
 }
+
+#[test]
+fn should_pass_self_test_and_leave_no_probe_behind() {
+    let harness = ComponentHarness::default();
+    let storage = storage_fixture(&harness);
+
+    storage.run_self_test().expect("self-test should pass");
+    assert!(storage
+        .read_state_store(&Cow::Borrowed(SELF_TEST_STORAGE_KEY))
+        .expect("should read state store")
+        .is_none());
+
+    // Running it again must not trip over the previous run.
+    storage
+        .run_self_test()
+        .expect("self-test should pass again");
+}
+
+#[test]
+fn should_prune_execution_results_outside_retention_window() {
+    let mut harness = ComponentHarness::default();
+    let mut storage = storage_fixture(&harness);
+
+    // Two blocks in each of eras 0 to 2.
+    let mut blocks = vec![];
+    for height in 0..6 {
+        let deploy = Deploy::random_valid_native_transfer(&mut harness.rng);
+        let block = TestBlockBuilder::new()
+            .era(height / 2)
+            .height(height)
+            .switch_block(height % 2 == 1)
+            .deploys(Some(&deploy))
+            .build(&mut harness.rng);
+        assert!(storage.write_block(&block).unwrap());
+        let (exec_result, _) = prepare_exec_result_with_transfer(&mut harness.rng, deploy.hash());
+        let exec_results = iter::once((*deploy.hash(), exec_result)).collect();
+        put_execution_results(&mut harness, &mut storage, *block.hash(), exec_results);
+        // The block at height 0 is outside the available block range.
+        if height > 0 {
+            storage.mark_block_complete(height).unwrap();
+        }
+        blocks.push(block);
+    }
+
+    storage.execution_results_retention_eras = Some(1);
+    storage.prune_execution_results().unwrap();
+    assert_eq!(storage.pruned_execution_results, Some((1, 1)));
+
+    let has_execution_results = |storage: &Storage, block: &Block| {
+        let mut txn = storage.env.begin_ro_txn().unwrap();
+        storage
+            .get_execution_results(&mut txn, block.hash())
+            .unwrap()
+            .is_some()
+    };
+    // Only the execution results of the complete blocks of era 0 are pruned.
+    assert!(has_execution_results(&storage, &blocks[0]));
+    assert!(!has_execution_results(&storage, &blocks[1]));
+    for block in &blocks[2..] {
+        assert!(has_execution_results(&storage, block));
+    }
+    // Pruned blocks are still readable.
+    assert_eq!(
+        storage.read_block(blocks[1].hash()).unwrap().as_ref(),
+        Some(&blocks[1])
+    );
+
+    // Pruning again has no effect until blocks leave the retention window.
+    storage.prune_execution_results().unwrap();
+    assert_eq!(storage.pruned_execution_results, Some((1, 1)));
+}
+
+#[test]
+fn should_read_blocks_and_deploys_moved_to_cold_storage() {
+    let mut harness = ComponentHarness::default();
+    let cfg = Config {
+        cold_storage_path: Some(harness.tmp.path().join("cold_storage")),
+        cold_storage_after_eras: 1,
+        ..new_config(&harness)
+    };
+    let mut storage = Storage::new(
+        &WithDir::new(harness.tmp.path(), cfg),
+        None,
+        ProtocolVersion::from_parts(1, 0, 0),
+        EraId::default(),
+        "test",
+        MAX_TTL.into(),
+        RECENT_ERA_COUNT,
+        None,
+        false,
+    )
+    .expect("could not create storage component with cold storage");
+
+    // Two blocks in each of eras 0 to 2.
+    let mut blocks = vec![];
+    let mut deploys = vec![];
+    for height in 0..6 {
+        let deploy = Deploy::random_valid_native_transfer(&mut harness.rng);
+        let block = TestBlockBuilder::new()
+            .era(height / 2)
+            .height(height)
+            .switch_block(height % 2 == 1)
+            .deploys(Some(&deploy))
+            .build(&mut harness.rng);
+        assert!(put_deploy(
+            &mut harness,
+            &mut storage,
+            Arc::new(deploy.clone())
+        ));
+        assert!(storage.write_block(&block).unwrap());
+        // The block at height 0 is outside the available block range.
+        if height > 0 {
+            storage.mark_block_complete(height).unwrap();
+        }
+        blocks.push(block);
+        deploys.push(deploy);
+    }
+
+    storage.move_to_cold_storage().unwrap();
+    assert_eq!(storage.moved_to_cold_storage, Some((1, 1)));
+
+    let is_in_main_database = |storage: &Storage, block: &Block, deploy: &Deploy| {
+        let mut txn = storage.env.begin_ro_txn().unwrap();
+        let has_body = txn
+            .value_exists(storage.block_body_db, block.header().body_hash())
+            .unwrap();
+        let has_deploy = txn.value_exists(storage.deploy_db, deploy.hash()).unwrap();
+        assert_eq!(has_body, has_deploy);
+        has_body
+    };
+    // Only the complete blocks of era 0 are moved.
+    assert!(is_in_main_database(&storage, &blocks[0], &deploys[0]));
+    assert!(!is_in_main_database(&storage, &blocks[1], &deploys[1]));
+    for (block, deploy) in blocks.iter().zip(&deploys).skip(2) {
+        assert!(is_in_main_database(&storage, block, deploy));
+    }
+
+    // Moved blocks and deploys are still readable.
+    assert_eq!(
+        storage.read_block(blocks[1].hash()).unwrap().as_ref(),
+        Some(&blocks[1])
+    );
+    assert_eq!(
+        storage.get_deploy_by_hash(*deploys[1].hash()).as_ref(),
+        Some(&deploys[1])
+    );
+    assert_eq!(
+        get_naive_deploys(&mut harness, &mut storage, smallvec![*deploys[1].hash()]),
+        vec![Some(deploys[1].clone())]
+    );
+
+    // Storing a moved deploy again doesn't bring it back into the main database.
+    assert!(!put_deploy(
+        &mut harness,
+        &mut storage,
+        Arc::new(deploys[1].clone())
+    ));
+    assert!(!is_in_main_database(&storage, &blocks[1], &deploys[1]));
+
+    // Moving again has no effect until blocks leave the recent eras.
+    storage.move_to_cold_storage().unwrap();
+    assert_eq!(storage.moved_to_cold_storage, Some((1, 1)));
+}
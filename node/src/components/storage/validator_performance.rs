@@ -0,0 +1,88 @@
+//! An index of the performance of each validator in each era, built from the blocks and finality
+//! signatures written to storage.
+
+use std::{collections::BTreeSet, convert::TryInto};
+
+use datasize::DataSize;
+use serde::{Deserialize, Serialize};
+
+use casper_types::{EraId, PublicKey};
+
+/// The length of the prefix of an index key identifying the validator.
+const KEY_PREFIX_LENGTH: usize = 32;
+
+/// The length of a key of the validator performance index.
+const KEY_LENGTH: usize = KEY_PREFIX_LENGTH + 8;
+
+/// Returns the key of the entry of the given validator and era in the validator performance index.
+///
+/// The key is the validator's account hash followed by the big-endian era ID, so that all the
+/// entries of a validator are adjacent and ordered by era.
+pub(super) fn index_key(public_key: &PublicKey, era_id: EraId) -> [u8; KEY_LENGTH] {
+    let mut key = [0; KEY_LENGTH];
+    key[..KEY_PREFIX_LENGTH].copy_from_slice(&index_key_prefix(public_key));
+    key[KEY_PREFIX_LENGTH..].copy_from_slice(&era_id.value().to_be_bytes());
+    key
+}
+
+/// Returns the prefix shared by the keys of all entries of the given validator.
+pub(super) fn index_key_prefix(public_key: &PublicKey) -> [u8; KEY_PREFIX_LENGTH] {
+    public_key.to_account_hash().value()
+}
+
+/// Returns the era of the entry with the given key, or `None` if the key is malformed.
+pub(super) fn era_id_from_index_key(raw_key: &[u8]) -> Option<EraId> {
+    let era_id_bytes = raw_key.get(KEY_PREFIX_LENGTH..KEY_LENGTH)?;
+    Some(EraId::new(u64::from_be_bytes(
+        era_id_bytes.try_into().ok()?,
+    )))
+}
+
+/// The performance of a validator in a single era.
+#[derive(Clone, DataSize, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ValidatorEraPerformance {
+    /// The era.
+    pub(crate) era_id: EraId,
+    /// The heights of the blocks proposed by the validator.
+    pub(crate) proposed_blocks: BTreeSet<u64>,
+    /// The number of blocks for which a finality signature of the validator is stored.
+    pub(crate) finality_signatures: u64,
+    /// Whether the validator was reported as an equivocator in the era's switch block.
+    pub(crate) equivocated: bool,
+    /// Whether the validator was reported as inactive in the era's switch block.
+    pub(crate) inactive: bool,
+}
+
+impl ValidatorEraPerformance {
+    pub(super) fn new(era_id: EraId) -> Self {
+        ValidatorEraPerformance {
+            era_id,
+            proposed_blocks: BTreeSet::new(),
+            finality_signatures: 0,
+            equivocated: false,
+            inactive: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_order_index_keys_by_validator_then_era() {
+        let mut rng = TestRng::new();
+        let validator = PublicKey::random(&mut rng);
+        let other_validator = PublicKey::random(&mut rng);
+
+        let key = index_key(&validator, EraId::new(1));
+        assert!(key.starts_with(&index_key_prefix(&validator)));
+        assert!(!key.starts_with(&index_key_prefix(&other_validator)));
+        assert_eq!(era_id_from_index_key(&key), Some(EraId::new(1)));
+
+        assert!(key < index_key(&validator, EraId::new(256)));
+        assert!(index_key(&validator, EraId::new(256)) < index_key(&validator, EraId::new(257)));
+    }
+}
@@ -23,6 +23,7 @@ use tokio::task;
 use tracing::{debug, error, info, trace, warn};
 
 use casper_types::{
+    bytesrepr::Bytes,
     file_utils::{self, ReadFileError},
     EraId, ProtocolVersion, TimeDiff,
 };
@@ -31,7 +32,7 @@ use crate::{
     components::{Component, ComponentState, InitializedComponent},
     effect::{
         announcements::UpgradeWatcherAnnouncement, requests::UpgradeWatcherRequest, EffectBuilder,
-        EffectExt, Effects,
+        EffectExt, Effects, Responder,
     },
     reactor::main_reactor::MainEvent,
     types::{
@@ -203,6 +204,11 @@ impl UpgradeWatcher {
         Ok(upgrade_watcher)
     }
 
+    /// Returns the next upgrade, if one has been staged.
+    pub(crate) fn next_upgrade(&self) -> Option<&NextUpgrade> {
+        self.next_upgrade.as_ref()
+    }
+
     pub(crate) fn should_upgrade_after(&self, era_id: EraId) -> bool {
         self.next_upgrade.as_ref().map_or(false, |upgrade| {
             upgrade.activation_point.should_upgrade(&era_id)
@@ -255,6 +261,34 @@ impl UpgradeWatcher {
         effects
     }
 
+    /// Reads the chainspec file of the next scheduled upgrade, if any, from the versioned subdir.
+    fn get_staged_chainspec_bytes(&self, responder: Responder<Option<Bytes>>) -> Effects<Event> {
+        let subdir = match self.next_upgrade.as_ref() {
+            Some(next_upgrade) => self
+                .root_dir
+                .join(dir_name_from_version(&next_upgrade.protocol_version)),
+            None => return responder.respond(None).ignore(),
+        };
+        async move {
+            let maybe_bytes = task::spawn_blocking(move || {
+                match file_utils::read_file(subdir.join(CHAINSPEC_FILENAME)) {
+                    Ok(bytes) => Some(Bytes::from(bytes)),
+                    Err(error) => {
+                        warn!(%error, "failed to read staged chainspec");
+                        None
+                    }
+                }
+            })
+            .await
+            .unwrap_or_else(|error| {
+                warn!(%error, "failed to join tokio task");
+                None
+            });
+            responder.respond(maybe_bytes).await
+        }
+        .ignore()
+    }
+
     fn handle_got_next_upgrade(&mut self, next_upgrade: NextUpgrade) -> Effects<Event> {
         debug!("got {}", next_upgrade);
         if let Some(ref current_point) = self.next_upgrade {
@@ -321,7 +355,12 @@ where
                     );
                     Effects::new()
                 }
-                Event::Request(request) => request.0.respond(self.next_upgrade.clone()).ignore(),
+                Event::Request(UpgradeWatcherRequest::GetNextUpgrade(responder)) => {
+                    responder.respond(self.next_upgrade.clone()).ignore()
+                }
+                Event::Request(UpgradeWatcherRequest::GetStagedChainspecBytes(responder)) => {
+                    self.get_staged_chainspec_bytes(responder)
+                }
                 Event::CheckForNextUpgrade => self.check_for_next_upgrade(effect_builder),
                 Event::GotNextUpgrade(next_upgrade) => self.handle_got_next_upgrade(next_upgrade),
             },
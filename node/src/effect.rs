@@ -98,7 +98,7 @@ pub(crate) mod requests;
 use std::{
     any::type_name,
     borrow::Cow,
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
     fmt::{self, Debug, Display, Formatter},
     future::Future,
     mem,
@@ -111,22 +111,25 @@ use futures::{channel::oneshot, future::BoxFuture, FutureExt};
 use once_cell::sync::Lazy;
 use serde::{Serialize, Serializer};
 use smallvec::{smallvec, SmallVec};
+use thiserror::Error;
 use tokio::{sync::Semaphore, time};
 use tracing::{debug, error, warn};
 
 use casper_execution_engine::{
     core::engine_state::{
-        self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult, GetBidsRequest,
-        GetBidsResult, QueryRequest, QueryResult,
+        self, era_validators::GetEraValidatorsError, BalanceRequest, BalanceResult,
+        BalancesRequest, BalancesResult, GetBidsRequest, GetBidsResult, GetEntityRequest,
+        GetEntityResult, GetUnbondingPursesRequest, GetUnbondingPursesResult, QueryRequest,
+        QueryResult,
     },
-    shared::execution_journal::ExecutionJournal,
+    shared::{execution_journal::ExecutionJournal, gas_profile::GasProfile},
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
     account::Account, bytesrepr::Bytes, system::auction::EraValidators, Contract, ContractPackage,
-    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp, Transfer, URef,
-    U512,
+    EraId, ExecutionEffect, ExecutionResult, Key, PublicKey, TimeDiff, Timestamp, Transfer,
+    TransferAddr, TransferMemo, URef, U512,
 };
 
 use crate::{
@@ -135,25 +138,29 @@ use crate::{
             BlockSynchronizerStatus, GlobalStateSynchronizerError, GlobalStateSynchronizerResponse,
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
-        consensus::{ClContext, EraDump, ProposedBlock, ValidatorChange},
-        contract_runtime::{ContractRuntimeError, EraValidatorsRequest},
+        block_validator::BlockValidationError,
+        consensus::{ClContext, EraDump, LeaderSchedule, ProposedBlock, ValidatorChange},
+        contract_runtime::{
+            BlockUtilization, ContractRuntimeError, EraValidatorsRequest, StepOutcome,
+        },
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
-        network::{blocklist::BlocklistJustification, FromIncoming, NetworkInsights},
+        network::{blocklist::BlocklistJustification, FromIncoming, NetworkInsights, RateLimits},
+        storage::ValidatorEraPerformance,
         upgrade_watcher::NextUpgrade,
     },
     contract_runtime::SpeculativeExecutionState,
     failpoints::FailpointActivation,
-    reactor::{main_reactor::ReactorState, EventQueueHandle, QueueKind},
+    reactor::{event_trace::EventTrace, main_reactor::ReactorState, EventQueueHandle, QueueKind},
     types::{
         appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
-        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlock,
-        MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
+        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployExpiryReason,
+        DeployHash, DeployHeader, DeployId, DeployMetadataExt, DeployWithFinalizedApprovals,
+        FinalitySignature, FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy,
+        MetaBlock, MetaBlockState, NodeId, TrieOrChunk, TrieOrChunkId,
     },
     utils::{fmt_limit::FmtLimit, SharedFlag, Source},
 };
@@ -162,7 +169,7 @@ use announcements::{
     ControlAnnouncement, DeployAcceptorAnnouncement, DeployBufferAnnouncement, FatalAnnouncement,
     FetchedNewBlockAnnouncement, FetchedNewFinalitySignatureAnnouncement, GossiperAnnouncement,
     MetaBlockAnnouncement, PeerBehaviorAnnouncement, QueueDumpFormat, UnexecutedBlockAnnouncement,
-    UpgradeWatcherAnnouncement,
+    UpcomingValidatorsAnnouncement, UpgradeWatcherAnnouncement,
 };
 use diagnostics_port::DumpConsensusStateRequest;
 use requests::{
@@ -171,6 +178,7 @@ use requests::{
     DeployBufferRequest, FetcherRequest, MakeBlockExecutableRequest, MarkBlockCompletedRequest,
     MetricsRequest, NetworkInfoRequest, NetworkRequest, ReactorStatusRequest, SetNodeStopRequest,
     StorageRequest, SyncGlobalStateRequest, TrieAccumulatorRequest, UpgradeWatcherRequest,
+    WatchDeployRequest,
 };
 
 /// A resource that will never be available, thus trying to acquire it will wait forever.
@@ -218,6 +226,24 @@ pub(crate) struct Responder<T> {
     is_shutting_down: SharedFlag,
 }
 
+/// Error responded to a request addressed to an optional component which cannot currently serve
+/// it, e.g. because it is disabled, has not finished initializing or has failed.
+///
+/// Unlike dropping the responder, which leaves the requester waiting forever, responding with this
+/// error lets the requester fail gracefully or retry later.
+#[derive(Clone, Copy, Debug, Error, PartialEq, Eq)]
+#[error("{component} is unavailable")]
+pub(crate) struct ComponentUnavailable {
+    component: &'static str,
+}
+
+impl ComponentUnavailable {
+    /// Creates a new `ComponentUnavailable` error for the named component.
+    pub(crate) fn new(component: &'static str) -> Self {
+        ComponentUnavailable { component }
+    }
+}
+
 /// A responder that will automatically send a `None` on drop.
 #[must_use]
 #[derive(DataSize, Debug)]
@@ -772,6 +798,29 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Changes the rate limits applied to non-validator peers, returning those in effect
+    /// afterwards.
+    ///
+    /// Limits which are `None` are left unchanged.
+    pub(crate) async fn set_network_rate_limits(
+        self,
+        max_outgoing_byte_rate_non_validators: Option<u32>,
+        max_incoming_message_rate_non_validators: Option<u32>,
+    ) -> RateLimits
+    where
+        REv: From<NetworkInfoRequest>,
+    {
+        self.make_request(
+            |responder| NetworkInfoRequest::SetRateLimits {
+                max_outgoing_byte_rate_non_validators,
+                max_incoming_message_rate_non_validators,
+                responder,
+            },
+            QueueKind::Control,
+        )
+        .await
+    }
+
     /// Gets a map of the current network peers to their socket addresses.
     pub(crate) async fn network_peers(self) -> BTreeMap<NodeId, String>
     where
@@ -796,14 +845,16 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
-    /// Announces which deploys have expired.
-    pub(crate) async fn announce_expired_deploys(self, hashes: Vec<DeployHash>)
-    where
+    /// Announces which deploys have expired without being executed, and why.
+    pub(crate) async fn announce_expired_deploys(
+        self,
+        expired: Vec<(DeployHash, DeployExpiryReason)>,
+    ) where
         REv: From<DeployBufferAnnouncement>,
     {
         self.event_queue
             .schedule(
-                DeployBufferAnnouncement::DeploysExpired(hashes),
+                DeployBufferAnnouncement::DeploysExpired(expired),
                 QueueKind::Validation,
             )
             .await;
@@ -823,8 +874,11 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Announces that a gossiper has received a new item, where the item's ID is the complete item.
-    pub(crate) async fn announce_complete_item_received_via_gossip<T: GossipItem>(self, item: T::Id)
-    where
+    pub(crate) async fn announce_complete_item_received_via_gossip<T: GossipItem>(
+        self,
+        item: T::Id,
+        sender: NodeId,
+    ) where
         REv: From<GossiperAnnouncement<T>>,
     {
         assert!(
@@ -834,7 +888,10 @@ impl<REv> EffectBuilder<REv> {
         );
         self.event_queue
             .schedule(
-                GossiperAnnouncement::NewCompleteItem(item),
+                GossiperAnnouncement::NewCompleteItem {
+                    item_id: item,
+                    sender,
+                },
                 QueueKind::Gossip,
             )
             .await;
@@ -933,6 +990,29 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Starts publishing the milestones of the given accepted deploy on the event stream.
+    ///
+    /// Returns the ID of the first event published for the deploy, or `None` if the event stream
+    /// server is disabled.
+    pub(crate) async fn watch_deploy(
+        self,
+        deploy_hash: DeployHash,
+        expires: Timestamp,
+    ) -> Option<u32>
+    where
+        REv: From<WatchDeployRequest>,
+    {
+        self.make_request(
+            |responder| WatchDeployRequest {
+                deploy_hash,
+                expires,
+                responder,
+            },
+            QueueKind::Api,
+        )
+        .await
+    }
+
     /// Announces that a deploy not previously stored has now been accepted and stored.
     pub(crate) fn announce_new_deploy_accepted(
         self,
@@ -1024,6 +1104,19 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces the outcome of a step committed at the end of an era.
+    pub(crate) async fn announce_step_outcome(self, step_outcome: StepOutcome)
+    where
+        REv: From<ContractRuntimeAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                ContractRuntimeAnnouncement::StepOutcome(Box::new(step_outcome)),
+                QueueKind::ContractRuntime,
+            )
+            .await
+    }
+
     /// Announces validators for upcoming era.
     pub(crate) async fn announce_upcoming_era_validators(
         self,
@@ -1304,6 +1397,57 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Gets the addresses of all transfers recorded with the given memo from storage.
+    pub(crate) async fn get_transfers_by_memo_from_storage(
+        self,
+        memo: TransferMemo,
+    ) -> Vec<TransferAddr>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetTransfersByMemo { memo, responder },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Gets the hashes of all deploys with the given body hash from storage.
+    pub(crate) async fn get_deploy_hashes_by_body_hash_from_storage(
+        self,
+        body_hash: Digest,
+    ) -> Vec<DeployHash>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetDeployHashesByBodyHash {
+                body_hash,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Gets the performance of the given validator in every era recorded in storage.
+    pub(crate) async fn get_validator_performance_from_storage(
+        self,
+        public_key: PublicKey,
+    ) -> Vec<ValidatorEraPerformance>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetValidatorPerformance {
+                public_key: Box::new(public_key),
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Returns the era IDs of the blocks in which the given deploys were executed.  If none of the
     /// deploys have been executed yet, an empty set will be returned.
     pub(crate) async fn get_deploys_era_ids(
@@ -1347,6 +1491,36 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the header of the switch block concluding the given era.
+    pub(crate) async fn get_switch_block_header_by_era_from_storage(
+        self,
+        era_id: EraId,
+    ) -> Option<BlockHeader>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetSwitchBlockHeaderByEra { era_id, responder },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
+    /// Requests the state root hashes of the stored blocks from the given height on.
+    pub(crate) async fn get_state_root_hashes_from_storage(self, lowest_height: u64) -> Vec<Digest>
+    where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::GetStateRootHashesFromHeight {
+                lowest_height,
+                responder,
+            },
+            QueueKind::FromStorage,
+        )
+        .await
+    }
+
     /// Requests the height range of fully available blocks (not just block headers).
     pub(crate) async fn get_available_block_range_from_storage(self) -> AvailableBlockRange
     where
@@ -1416,6 +1590,19 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the hashes of the tries of the global state being synced which may already have
+    /// been written.
+    pub(crate) async fn get_tries_being_synced(self) -> Vec<Digest>
+    where
+        REv: From<BlockSynchronizerRequest>,
+    {
+        self.make_request(
+            |responder| BlockSynchronizerRequest::TriesBeingSynced { responder },
+            QueueKind::Regular,
+        )
+        .await
+    }
+
     /// Get a trie by its hash key.
     pub(crate) async fn get_trie_full(
         self,
@@ -1560,6 +1747,23 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Stores the reasons the given deploys were dropped without having been executed.
+    pub(crate) async fn put_expired_deploys_to_storage(
+        self,
+        expired_deploys: Vec<(DeployHash, DeployExpiryReason)>,
+    ) where
+        REv: From<StorageRequest>,
+    {
+        self.make_request(
+            |responder| StorageRequest::PutExpiredDeploys {
+                expired_deploys,
+                responder,
+            },
+            QueueKind::ToStorage,
+        )
+        .await
+    }
+
     /// Gets the requested deploys from the deploy store.
     pub(crate) async fn get_deploy_and_metadata_from_storage(
         self,
@@ -1755,12 +1959,12 @@ impl<REv> EffectBuilder<REv> {
     }
 
     /// Checks whether the deploys included in the block exist on the network and the block is
-    /// valid.
+    /// valid, returning the reason it was rejected otherwise.
     pub(crate) async fn validate_block(
         self,
         sender: NodeId,
         block: ProposedBlock<ClContext>,
-    ) -> bool
+    ) -> Result<(), BlockValidationError>
     where
         REv: From<BlockValidationRequest>,
     {
@@ -1868,15 +2072,43 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Announces the validator set of an upcoming era.
+    pub(crate) async fn announce_upcoming_validators(
+        self,
+        era_id: EraId,
+        validators: BTreeSet<PublicKey>,
+    ) where
+        REv: From<UpcomingValidatorsAnnouncement>,
+    {
+        self.event_queue
+            .schedule(
+                UpcomingValidatorsAnnouncement { era_id, validators },
+                QueueKind::NetworkInfo,
+            )
+            .await
+    }
+
     /// Gets the next scheduled upgrade, if any.
     pub(crate) async fn get_next_upgrade(self) -> Option<NextUpgrade>
     where
         REv: From<UpgradeWatcherRequest> + Send,
     {
-        self.make_request(UpgradeWatcherRequest, QueueKind::Control)
+        self.make_request(UpgradeWatcherRequest::GetNextUpgrade, QueueKind::Control)
             .await
     }
 
+    /// Gets the raw bytes of the chainspec file staged for the next scheduled upgrade, if any.
+    pub(crate) async fn get_staged_chainspec_bytes(self) -> Option<Bytes>
+    where
+        REv: From<UpgradeWatcherRequest> + Send,
+    {
+        self.make_request(
+            UpgradeWatcherRequest::GetStagedChainspecBytes,
+            QueueKind::Control,
+        )
+        .await
+    }
+
     /// Requests a query be executed on the Contract Runtime component.
     pub(crate) async fn query_global_state(
         self,
@@ -1989,6 +2221,24 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the balances of several purses from the Contract Runtime component.
+    pub(crate) async fn get_balances(
+        self,
+        balances_request: BalancesRequest,
+    ) -> Result<BalancesResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetBalances {
+                balances_request,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Returns a map of validators weights for all eras as known from `root_hash`.
     ///
     /// This operation is read only.
@@ -2024,6 +2274,42 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the unbonding purses of a public key from the Contract Runtime component.
+    pub(crate) async fn get_unbonding_purses(
+        self,
+        get_unbonding_purses_request: GetUnbondingPursesRequest,
+    ) -> Result<GetUnbondingPursesResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetUnbondingPurses {
+                get_unbonding_purses_request,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
+    /// Requests an account or a contract from the Contract Runtime component.
+    pub(crate) async fn get_entity(
+        self,
+        get_entity_request: GetEntityRequest,
+    ) -> Result<GetEntityResult, engine_state::Error>
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetEntity {
+                get_entity_request,
+                responder,
+            },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     pub(crate) async fn get_execution_results_checksum(
@@ -2063,6 +2349,19 @@ impl<REv> EffectBuilder<REv> {
             .await
     }
 
+    /// Returns the proposers of the upcoming `rounds` rounds of the current era, starting with the
+    /// current round.
+    pub(crate) async fn get_consensus_leader_schedule(self, rounds: usize) -> Option<LeaderSchedule>
+    where
+        REv: From<ConsensusRequest>,
+    {
+        self.make_request(
+            |responder| ConsensusRequest::LeaderSchedule { rounds, responder },
+            QueueKind::Consensus,
+        )
+        .await
+    }
+
     /// Dump consensus state for a specific era, using the supplied function to serialize the
     /// output.
     pub(crate) async fn diagnostics_port_dump_consensus_state(
@@ -2099,6 +2398,36 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Starts recording dispatched events for the diagnostics port.
+    ///
+    /// Returns `false` if a recording is already in progress.
+    pub(crate) async fn diagnostics_port_start_event_trace(self, max_events: usize) -> bool
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::StartEventTrace {
+                max_events,
+                started: responder,
+            },
+            QueueKind::Control,
+        )
+        .await
+    }
+
+    /// Stops recording dispatched events for the diagnostics port, returning the recording if one
+    /// was in progress.
+    pub(crate) async fn diagnostics_port_stop_event_trace(self) -> Option<EventTrace>
+    where
+        REv: From<ControlAnnouncement>,
+    {
+        self.make_request(
+            |responder| ControlAnnouncement::StopEventTrace { trace: responder },
+            QueueKind::Control,
+        )
+        .await
+    }
+
     /// Activates/deactivates a failpoint from a given activation.
     pub(crate) async fn activate_failpoint(self, activation: FailpointActivation)
     where
@@ -2200,7 +2529,8 @@ impl<REv> EffectBuilder<REv> {
         self,
         execution_prestate: SpeculativeExecutionState,
         deploy: Arc<Deploy>,
-    ) -> Result<Option<ExecutionResult>, engine_state::Error>
+        collect_gas_profile: bool,
+    ) -> Result<Option<(ExecutionResult, Option<GasProfile>)>, engine_state::Error>
     where
         REv: From<ContractRuntimeRequest>,
     {
@@ -2208,6 +2538,7 @@ impl<REv> EffectBuilder<REv> {
             |responder| ContractRuntimeRequest::SpeculativeDeployExecution {
                 execution_prestate,
                 deploy,
+                collect_gas_profile,
                 responder,
             },
             QueueKind::ContractRuntime,
@@ -2215,6 +2546,18 @@ impl<REv> EffectBuilder<REv> {
         .await
     }
 
+    /// Requests the utilization of the capacity of the most recently executed blocks.
+    pub(crate) async fn get_block_utilization(self) -> BlockUtilization
+    where
+        REv: From<ContractRuntimeRequest>,
+    {
+        self.make_request(
+            |responder| ContractRuntimeRequest::GetBlockUtilization { responder },
+            QueueKind::ContractRuntime,
+        )
+        .await
+    }
+
     /// Reads block execution results (or chunk) from Storage component.
     pub(crate) async fn get_block_execution_results_or_chunk_from_storage(
         self,
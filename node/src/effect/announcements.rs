@@ -4,7 +4,7 @@
 //! module documentation for details.
 
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     fmt::{self, Debug, Display, Formatter},
     fs::File,
     sync::Arc,
@@ -19,6 +19,7 @@ use casper_types::{EraId, ExecutionEffect, PublicKey, Timestamp, U512};
 use crate::{
     components::{
         consensus::{ClContext, ProposedBlock},
+        contract_runtime::StepOutcome,
         diagnostics_port::FileSerializer,
         fetcher::FetchItem,
         gossiper::GossipItem,
@@ -27,7 +28,11 @@ use crate::{
     },
     effect::Responder,
     failpoints::FailpointActivation,
-    types::{Block, Deploy, DeployHash, FinalitySignature, FinalizedBlock, MetaBlock, NodeId},
+    reactor::event_trace::EventTrace,
+    types::{
+        Block, Deploy, DeployExpiryReason, DeployHash, FinalitySignature, FinalizedBlock,
+        MetaBlock, NodeId,
+    },
     utils::Source,
 };
 
@@ -69,6 +74,18 @@ pub(crate) enum ControlAnnouncement {
         /// The failpoint activation to process.
         activation: FailpointActivation,
     },
+    /// Starts recording dispatched events.
+    StartEventTrace {
+        /// The maximum number of events to record.
+        max_events: usize,
+        /// Responder called with `false` if a recording was already in progress.
+        started: Responder<bool>,
+    },
+    /// Stops recording dispatched events.
+    StopEventTrace {
+        /// Responder called with the recording, if one was in progress.
+        trace: Responder<Option<EventTrace>>,
+    },
 }
 
 impl Debug for ControlAnnouncement {
@@ -89,6 +106,13 @@ impl Debug for ControlAnnouncement {
                 .debug_struct("ActivateFailpoint")
                 .field("activation", activation)
                 .finish(),
+            ControlAnnouncement::StartEventTrace { max_events, .. } => f
+                .debug_struct("StartEventTrace")
+                .field("max_events", max_events)
+                .finish_non_exhaustive(),
+            ControlAnnouncement::StopEventTrace { .. } => {
+                f.debug_struct("StopEventTrace").finish_non_exhaustive()
+            }
         }
     }
 }
@@ -109,6 +133,12 @@ impl Display for ControlAnnouncement {
             ControlAnnouncement::ActivateFailpoint { activation } => {
                 write!(f, "failpoint activation: {}", activation)
             }
+            ControlAnnouncement::StartEventTrace { max_events, .. } => {
+                write!(f, "start event trace of up to {} events", max_events)
+            }
+            ControlAnnouncement::StopEventTrace { .. } => {
+                write!(f, "stop event trace")
+            }
         }
     }
 }
@@ -221,15 +251,23 @@ impl Display for DeployAcceptorAnnouncement {
 
 #[derive(Debug, Serialize)]
 pub(crate) enum DeployBufferAnnouncement {
-    /// Hashes of the deploys that expired.
-    DeploysExpired(Vec<DeployHash>),
+    /// Hashes of the deploys that expired without being executed, with the reason each was
+    /// dropped.
+    DeploysExpired(Vec<(DeployHash, DeployExpiryReason)>),
 }
 
 impl Display for DeployBufferAnnouncement {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
-            DeployBufferAnnouncement::DeploysExpired(hashes) => {
-                write!(f, "pruned hashes: {}", hashes.iter().join(", "))
+            DeployBufferAnnouncement::DeploysExpired(expired) => {
+                write!(
+                    f,
+                    "pruned hashes: {}",
+                    expired
+                        .iter()
+                        .map(|(deploy_hash, reason)| format!("{} ({})", deploy_hash, reason))
+                        .join(", ")
+                )
             }
         }
     }
@@ -300,6 +338,26 @@ impl Display for PeerBehaviorAnnouncement {
     }
 }
 
+/// The validator set of an upcoming era has become known.
+#[derive(Debug, Serialize)]
+pub(crate) struct UpcomingValidatorsAnnouncement {
+    /// The upcoming era.
+    pub(crate) era_id: EraId,
+    /// The validators of the upcoming era.
+    pub(crate) validators: BTreeSet<PublicKey>,
+}
+
+impl Display for UpcomingValidatorsAnnouncement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} validators known for upcoming {}",
+            self.validators.len(),
+            self.era_id
+        )
+    }
+}
+
 /// A Gossiper announcement.
 #[derive(Debug)]
 pub(crate) enum GossiperAnnouncement<T: GossipItem> {
@@ -307,7 +365,7 @@ pub(crate) enum GossiperAnnouncement<T: GossipItem> {
     GossipReceived { item_id: T::Id, sender: NodeId },
 
     /// A new item has been received, where the item's ID is the complete item.
-    NewCompleteItem(T::Id),
+    NewCompleteItem { item_id: T::Id, sender: NodeId },
 
     /// A new item has been received where the item's ID is NOT the complete item.
     NewItemBody { item: Box<T>, sender: NodeId },
@@ -322,7 +380,9 @@ impl<T: GossipItem> Display for GossiperAnnouncement<T> {
             GossiperAnnouncement::GossipReceived { item_id, sender } => {
                 write!(f, "new gossiped item {} from sender {}", item_id, sender)
             }
-            GossiperAnnouncement::NewCompleteItem(item) => write!(f, "new complete item {}", item),
+            GossiperAnnouncement::NewCompleteItem { item_id, sender } => {
+                write!(f, "new complete item {} from sender {}", item_id, sender)
+            }
             GossiperAnnouncement::NewItemBody { item, sender } => {
                 write!(f, "new item body {} from {}", item.gossip_id(), sender)
             }
@@ -367,6 +427,8 @@ pub(crate) enum ContractRuntimeAnnouncement {
         /// The validators for the eras after the `era_that_is_ending` era.
         upcoming_era_validators: BTreeMap<EraId, BTreeMap<PublicKey, U512>>,
     },
+    /// The outcome of a step committed at the end of an era.
+    StepOutcome(Box<StepOutcome>),
 }
 
 impl Display for ContractRuntimeAnnouncement {
@@ -384,6 +446,9 @@ impl Display for ContractRuntimeAnnouncement {
                     era_that_is_ending,
                 )
             }
+            ContractRuntimeAnnouncement::StepOutcome(step_outcome) => {
+                write!(f, "step outcome for {}", step_outcome.era_id)
+            }
         }
     }
 }
@@ -18,17 +18,20 @@ use static_assertions::const_assert;
 use casper_execution_engine::{
     core::engine_state::{
         self,
-        balance::{BalanceRequest, BalanceResult},
+        balance::{BalanceRequest, BalanceResult, BalancesRequest, BalancesResult},
         era_validators::GetEraValidatorsError,
         get_bids::{GetBidsRequest, GetBidsResult},
+        get_entity::{GetEntityRequest, GetEntityResult},
+        get_unbonding_purses::{GetUnbondingPursesRequest, GetUnbondingPursesResult},
         query::{QueryRequest, QueryResult},
     },
+    shared::gas_profile::GasProfile,
     storage::trie::TrieRaw,
 };
 use casper_hashing::Digest;
 use casper_types::{
     bytesrepr::Bytes, system::auction::EraValidators, EraId, ExecutionResult, Key, ProtocolVersion,
-    PublicKey, TimeDiff, Timestamp, Transfer, URef,
+    PublicKey, TimeDiff, Timestamp, Transfer, TransferAddr, TransferMemo, URef,
 };
 
 use crate::{
@@ -37,13 +40,15 @@ use crate::{
             BlockSynchronizerStatus, GlobalStateSynchronizerError, GlobalStateSynchronizerResponse,
             TrieAccumulatorError, TrieAccumulatorResponse,
         },
-        consensus::{ClContext, ProposedBlock, ValidatorChange},
-        contract_runtime::EraValidatorsRequest,
+        block_validator::BlockValidationError,
+        consensus::{ClContext, LeaderSchedule, ProposedBlock, ValidatorChange},
+        contract_runtime::{BlockUtilization, EraValidatorsRequest},
         deploy_acceptor,
         diagnostics_port::StopAtSpec,
         fetcher::{FetchItem, FetchResult},
         gossiper::GossipItem,
-        network::NetworkInsights,
+        network::{NetworkInsights, RateLimits},
+        storage::ValidatorEraPerformance,
         upgrade_watcher::NextUpgrade,
     },
     contract_runtime::{ContractRuntimeError, SpeculativeExecutionState},
@@ -53,15 +58,15 @@ use crate::{
     types::{
         appendable_block::AppendableBlock, ApprovalsHashes, AvailableBlockRange, Block,
         BlockExecutionResultsOrChunk, BlockExecutionResultsOrChunkId, BlockHash, BlockHeader,
-        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployHash, DeployHeader,
-        DeployId, DeployMetadataExt, DeployWithFinalizedApprovals, FinalitySignature,
-        FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy, MetaBlockState,
-        NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
+        BlockSignatures, BlockWithMetadata, ChainspecRawBytes, Deploy, DeployExpiryReason,
+        DeployHash, DeployHeader, DeployId, DeployMetadataExt, DeployWithFinalizedApprovals,
+        FinalitySignature, FinalitySignatureId, FinalizedApprovals, FinalizedBlock, LegacyDeploy,
+        MetaBlockState, NodeId, StatusFeed, TrieOrChunk, TrieOrChunkId,
     },
     utils::{DisplayIter, Source},
 };
 
-use super::GossipTarget;
+use super::{ComponentUnavailable, GossipTarget};
 
 const _STORAGE_REQUEST_SIZE: usize = mem::size_of::<StorageRequest>();
 const_assert!(_STORAGE_REQUEST_SIZE < 89);
@@ -213,6 +218,14 @@ pub(crate) enum NetworkInfoRequest {
     Insight {
         responder: Responder<NetworkInsights>,
     },
+    /// Change the rate limits applied to non-validator peers, leaving those which are `None`
+    /// unchanged.
+    SetRateLimits {
+        max_outgoing_byte_rate_non_validators: Option<u32>,
+        max_incoming_message_rate_non_validators: Option<u32>,
+        /// Responder to be called with the rate limits in effect afterwards.
+        responder: Responder<RateLimits>,
+    },
 }
 
 impl Display for NetworkInfoRequest {
@@ -230,6 +243,15 @@ impl Display for NetworkInfoRequest {
             NetworkInfoRequest::Insight { responder: _ } => {
                 formatter.write_str("get networking insights")
             }
+            NetworkInfoRequest::SetRateLimits {
+                max_outgoing_byte_rate_non_validators,
+                max_incoming_message_rate_non_validators,
+                responder: _,
+            } => write!(
+                formatter,
+                "set rate limits, outgoing bytes: {:?}, incoming messages: {:?}",
+                max_outgoing_byte_rate_non_validators, max_incoming_message_rate_non_validators
+            ),
         }
     }
 }
@@ -318,6 +340,20 @@ pub(crate) enum StorageRequest {
         /// Responder.
         responder: Responder<Option<BlockHeader>>,
     },
+    /// Retrieve the header of the switch block concluding the given era.
+    GetSwitchBlockHeaderByEra {
+        /// The era concluded by the switch block.
+        era_id: EraId,
+        /// Responder.
+        responder: Responder<Option<BlockHeader>>,
+    },
+    /// Retrieve the state root hashes of the stored blocks from the given height on.
+    GetStateRootHashesFromHeight {
+        /// The height of the lowest block whose state root hash is retrieved.
+        lowest_height: u64,
+        /// Responder.
+        responder: Responder<Vec<Digest>>,
+    },
     /// Retrieve the era IDs of the blocks in which the given deploys were executed.
     GetDeploysEraIds {
         deploy_hashes: HashSet<DeployHash>,
@@ -352,6 +388,30 @@ pub(crate) enum StorageRequest {
         /// local storage under the block_hash provided.
         responder: Responder<Option<Vec<Transfer>>>,
     },
+    /// Retrieve the addresses of all transfers recorded with the given memo.
+    GetTransfersByMemo {
+        /// Memo of the transfers to get.
+        memo: TransferMemo,
+        /// Responder to call with the result.  Returns an empty `Vec` if no transfer with the
+        /// given memo exists in local storage.
+        responder: Responder<Vec<TransferAddr>>,
+    },
+    /// Retrieve the hashes of all deploys with the given body hash.
+    GetDeployHashesByBodyHash {
+        /// Body hash of the deploys to get.
+        body_hash: Digest,
+        /// Responder to call with the result.  Returns an empty `Vec` if no deploy with the given
+        /// body hash exists in local storage.
+        responder: Responder<Vec<DeployHash>>,
+    },
+    /// Retrieve the performance of the given validator in every era recorded in local storage.
+    GetValidatorPerformance {
+        /// Public key of the validator.
+        public_key: Box<PublicKey>,
+        /// Responder to call with the result, ordered by era.  Returns an empty `Vec` if no
+        /// activity of the validator is recorded in local storage.
+        responder: Responder<Vec<ValidatorEraPerformance>>,
+    },
     /// Store given deploy.
     PutDeploy {
         /// Deploy to store.
@@ -396,6 +456,13 @@ pub(crate) enum StorageRequest {
         /// Responder to call when done storing.
         responder: Responder<()>,
     },
+    /// Store the reasons the given deploys were dropped without having been executed.
+    PutExpiredDeploys {
+        /// Hashes of the deploys, with the reason each was dropped.
+        expired_deploys: Vec<(DeployHash, DeployExpiryReason)>,
+        /// Responder to call when done storing.
+        responder: Responder<()>,
+    },
     GetExecutionResults {
         block_hash: BlockHash,
         responder: Responder<Option<Vec<(DeployHash, DeployHeader, ExecutionResult)>>>,
@@ -524,6 +591,16 @@ impl Display for StorageRequest {
             StorageRequest::GetHighestCompleteBlockHeader { .. } => {
                 write!(formatter, "get highest complete block header")
             }
+            StorageRequest::GetSwitchBlockHeaderByEra { era_id, .. } => {
+                write!(formatter, "get switch block header for era {}", era_id)
+            }
+            StorageRequest::GetStateRootHashesFromHeight { lowest_height, .. } => {
+                write!(
+                    formatter,
+                    "get state root hashes from height {}",
+                    lowest_height
+                )
+            }
             StorageRequest::GetDeploysEraIds { deploy_hashes, .. } => {
                 write!(formatter, "get era ids for {} deploys", deploy_hashes.len())
             }
@@ -536,6 +613,15 @@ impl Display for StorageRequest {
             StorageRequest::GetBlockTransfers { block_hash, .. } => {
                 write!(formatter, "get transfers for {}", block_hash)
             }
+            StorageRequest::GetTransfersByMemo { memo, .. } => {
+                write!(formatter, "get transfers with memo {}", memo)
+            }
+            StorageRequest::GetDeployHashesByBodyHash { body_hash, .. } => {
+                write!(formatter, "get deploys with body hash {}", body_hash)
+            }
+            StorageRequest::GetValidatorPerformance { public_key, .. } => {
+                write!(formatter, "get performance of validator {}", public_key)
+            }
             StorageRequest::PutDeploy { deploy, .. } => write!(formatter, "put {}", deploy),
             StorageRequest::GetDeploys { deploy_hashes, .. } => {
                 write!(formatter, "get {}", DisplayIter::new(deploy_hashes.iter()))
@@ -552,6 +638,11 @@ impl Display for StorageRequest {
             StorageRequest::PutExecutionResults { block_hash, .. } => {
                 write!(formatter, "put execution results for {}", block_hash)
             }
+            StorageRequest::PutExpiredDeploys {
+                expired_deploys, ..
+            } => {
+                write!(formatter, "put {} expired deploys", expired_deploys.len())
+            }
             StorageRequest::GetExecutionResults { block_hash, .. } => {
                 write!(formatter, "get execution results for {}", block_hash)
             }
@@ -755,6 +846,14 @@ pub(crate) enum RpcRequest {
         /// Responder to call with the result.
         responder: Responder<AvailableBlockRange>,
     },
+    /// Return the height of the highest block which has collected the signature weight required
+    /// for blocks to be exposed, or `None` if no block has yet.
+    ///
+    /// If no signature weight is required, `u64::MAX` is returned.
+    GetHighestExposedBlockHeight {
+        /// Responder to call with the result.
+        responder: Responder<Option<u64>>,
+    },
 }
 
 impl Display for RpcRequest {
@@ -805,6 +904,9 @@ impl Display for RpcRequest {
             RpcRequest::GetAvailableBlockRange { .. } => {
                 write!(formatter, "get available block range")
             }
+            RpcRequest::GetHighestExposedBlockHeight { .. } => {
+                write!(formatter, "get highest exposed block height")
+            }
         }
     }
 }
@@ -819,20 +921,31 @@ pub(crate) enum RestRequest {
     /// Return string formatted status or `None` if an error occurred.
     Status {
         /// Responder to call with the result.
-        responder: Responder<StatusFeed>,
+        responder: Responder<Result<StatusFeed, ComponentUnavailable>>,
     },
     /// Return string formatted, prometheus compatible metrics or `None` if an error occurred.
     Metrics {
         /// Responder to call with the result.
-        responder: Responder<Option<String>>,
+        responder: Responder<Result<Option<String>, ComponentUnavailable>>,
     },
     /// Returns schema of client-facing JSON-RPCs in OpenRPC format.
     RpcSchema {
         /// Responder to call with the result
-        responder: Responder<OpenRpcSchema>,
+        responder: Responder<Result<OpenRpcSchema, ComponentUnavailable>>,
     },
 }
 
+impl RestRequest {
+    /// Responds to the request with the given error, rather than leaving the requester hanging.
+    pub(crate) async fn respond_unavailable(self, error: ComponentUnavailable) {
+        match self {
+            RestRequest::Status { responder } => responder.respond(Err(error)).await,
+            RestRequest::Metrics { responder } => responder.respond(Err(error)).await,
+            RestRequest::RpcSchema { responder } => responder.respond(Err(error)).await,
+        }
+    }
+}
+
 impl Display for RestRequest {
     fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -873,6 +986,14 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the balance result.
         responder: Responder<Result<BalanceResult, engine_state::Error>>,
     },
+    /// A request for the balances of several purses.
+    GetBalances {
+        /// Balances request.
+        #[serde(skip_serializing)]
+        balances_request: BalancesRequest,
+        /// Responder to call with the balances result.
+        responder: Responder<Result<BalancesResult, engine_state::Error>>,
+    },
     /// Returns validator weights.
     GetEraValidators {
         /// Get validators weights request.
@@ -889,6 +1010,22 @@ pub(crate) enum ContractRuntimeRequest {
         /// Responder to call with the result.
         responder: Responder<Result<GetBidsResult, engine_state::Error>>,
     },
+    /// Return the unbonding purses of a public key at a given state root hash
+    GetUnbondingPurses {
+        /// Get unbonding purses request.
+        #[serde(skip_serializing)]
+        get_unbonding_purses_request: GetUnbondingPursesRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetUnbondingPursesResult, engine_state::Error>>,
+    },
+    /// Return an account or a contract at a given state root hash
+    GetEntity {
+        /// Get entity request.
+        #[serde(skip_serializing)]
+        get_entity_request: GetEntityRequest,
+        /// Responder to call with the result.
+        responder: Responder<Result<GetEntityResult, engine_state::Error>>,
+    },
     /// Returns the value of the execution results checksum stored in the ChecksumRegistry for the
     /// given state root hash.
     GetExecutionResultsChecksum {
@@ -922,8 +1059,16 @@ pub(crate) enum ContractRuntimeRequest {
         execution_prestate: SpeculativeExecutionState,
         /// Deploy to execute.
         deploy: Arc<Deploy>,
+        /// Whether to aggregate the gas consumed per host function.
+        collect_gas_profile: bool,
         /// Results
-        responder: Responder<Result<Option<ExecutionResult>, engine_state::Error>>,
+        responder:
+            Responder<Result<Option<(ExecutionResult, Option<GasProfile>)>, engine_state::Error>>,
+    },
+    /// Returns the utilization of the capacity of the most recently executed blocks.
+    GetBlockUtilization {
+        /// Responder to call with the result.
+        responder: Responder<BlockUtilization>,
     },
 }
 
@@ -941,6 +1086,9 @@ impl Display for ContractRuntimeRequest {
             ContractRuntimeRequest::GetBalance {
                 balance_request, ..
             } => write!(formatter, "balance request: {:?}", balance_request),
+            ContractRuntimeRequest::GetBalances {
+                balances_request, ..
+            } => write!(formatter, "balances request: {:?}", balances_request),
             ContractRuntimeRequest::GetEraValidators { request, .. } => {
                 write!(formatter, "get era validators: {:?}", request)
             }
@@ -949,6 +1097,21 @@ impl Display for ContractRuntimeRequest {
             } => {
                 write!(formatter, "get bids request: {:?}", get_bids_request)
             }
+            ContractRuntimeRequest::GetUnbondingPurses {
+                get_unbonding_purses_request,
+                ..
+            } => {
+                write!(
+                    formatter,
+                    "get unbonding purses request: {:?}",
+                    get_unbonding_purses_request
+                )
+            }
+            ContractRuntimeRequest::GetEntity {
+                get_entity_request, ..
+            } => {
+                write!(formatter, "get entity request: {:?}", get_entity_request)
+            }
             ContractRuntimeRequest::GetExecutionResultsChecksum {
                 state_root_hash, ..
             } => write!(
@@ -979,6 +1142,9 @@ impl Display for ContractRuntimeRequest {
                     execution_prestate.state_root_hash
                 )
             }
+            ContractRuntimeRequest::GetBlockUtilization { .. } => {
+                write!(formatter, "get block utilization")
+            }
         }
     }
 }
@@ -1050,8 +1216,8 @@ pub(crate) struct BlockValidationRequest {
     pub(crate) sender: NodeId,
     /// Responder to call with the result.
     ///
-    /// Indicates whether or not validation was successful.
-    pub(crate) responder: Responder<bool>,
+    /// Indicates whether or not validation was successful, and if not, why the block is invalid.
+    pub(crate) responder: Responder<Result<(), BlockValidationError>>,
 }
 
 impl Display for BlockValidationRequest {
@@ -1071,6 +1237,12 @@ pub(crate) enum ConsensusRequest {
     Status(Responder<Option<(PublicKey, Option<TimeDiff>)>>),
     /// Request for a list of validator status changes, by public key.
     ValidatorChanges(Responder<BTreeMap<PublicKey, Vec<(EraId, ValidatorChange)>>>),
+    /// Request for the proposers of the upcoming `rounds` rounds of the current era, starting
+    /// with the current round.
+    LeaderSchedule {
+        rounds: usize,
+        responder: Responder<Option<LeaderSchedule>>,
+    },
 }
 
 /// ChainspecLoader component requests.
@@ -1091,13 +1263,24 @@ impl Display for ChainspecRawBytesRequest {
     }
 }
 
-/// UpgradeWatcher component request to get the next scheduled upgrade, if any.
+/// UpgradeWatcher component request.
 #[derive(Debug, Serialize)]
-pub(crate) struct UpgradeWatcherRequest(pub(crate) Responder<Option<NextUpgrade>>);
+pub(crate) enum UpgradeWatcherRequest {
+    /// Request for the next scheduled upgrade, if any.
+    GetNextUpgrade(Responder<Option<NextUpgrade>>),
+    /// Request for the raw bytes of the chainspec file staged for the next scheduled upgrade, if
+    /// any.
+    GetStagedChainspecBytes(Responder<Option<Bytes>>),
+}
 
 impl Display for UpgradeWatcherRequest {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "get next upgrade")
+        match self {
+            UpgradeWatcherRequest::GetNextUpgrade(_) => write!(f, "get next upgrade"),
+            UpgradeWatcherRequest::GetStagedChainspecBytes(_) => {
+                write!(f, "get staged chainspec bytes")
+            }
+        }
     }
 }
 
@@ -1137,6 +1320,9 @@ pub(crate) enum BlockSynchronizerRequest {
     Status {
         responder: Responder<BlockSynchronizerStatus>,
     },
+    TriesBeingSynced {
+        responder: Responder<Vec<Digest>>,
+    },
 }
 
 impl Display for BlockSynchronizerRequest {
@@ -1151,6 +1337,9 @@ impl Display for BlockSynchronizerRequest {
             BlockSynchronizerRequest::Status { .. } => {
                 write!(f, "block synchronizer request: status")
             }
+            BlockSynchronizerRequest::TriesBeingSynced { .. } => {
+                write!(f, "block synchronizer request: tries being synced")
+            }
             BlockSynchronizerRequest::SyncGlobalStates(_) => {
                 write!(f, "request to sync global states")
             }
@@ -1199,3 +1388,20 @@ impl Display for AcceptDeployRequest {
         }
     }
 }
+
+/// A request to publish the milestones of an accepted deploy on the event stream.
+#[derive(DataSize, Debug, Serialize)]
+pub(crate) struct WatchDeployRequest {
+    pub(crate) deploy_hash: DeployHash,
+    /// The time after which the deploy is no longer watched.
+    pub(crate) expires: Timestamp,
+    /// Responds with the ID of the first event published for the deploy, or `None` if the event
+    /// stream server is disabled.
+    pub(crate) responder: Responder<Option<u32>>,
+}
+
+impl Display for WatchDeployRequest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "watch deploy {}", self.deploy_hash)
+    }
+}
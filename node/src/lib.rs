@@ -63,6 +63,7 @@ pub(crate) use components::{
     event_stream_server::Config as EventStreamServerConfig,
     fetcher::Config as FetcherConfig,
     gossiper::Config as GossipConfig,
+    metrics::Config as MetricsConfig,
     network::Config as NetworkConfig,
     rest_server::Config as RestServerConfig,
     rpc_server::{Config as RpcServerConfig, SpeculativeExecConfig},
@@ -1,11 +1,16 @@
 //! Logging via the tracing crate.
 
-use std::{env, fmt, io};
+use std::{
+    collections::VecDeque,
+    env, fmt,
+    io::{self, Write},
+    sync::Mutex,
+};
 
 use ansi_term::{Color, Style};
 use anyhow::anyhow;
 use datasize::DataSize;
-use once_cell::sync::OnceCell;
+use once_cell::sync::{Lazy, OnceCell};
 use serde::{Deserialize, Serialize};
 use smallvec::SmallVec;
 use tracing::{
@@ -37,6 +42,13 @@ const LOG_FIELD_LINE: &str = "log.line";
 /// We use a static variable for the reload handle since our logger instance is also global.
 static RELOAD_HANDLE: OnceCell<ReloadHandle> = OnceCell::new();
 
+/// The maximum number of log lines retained in memory.
+const RECENT_LOG_LINES_CAPACITY: usize = 10_000;
+
+/// The most recently logged lines, oldest first, e.g. for inclusion in support bundles.
+static RECENT_LOG_LINES: Lazy<Mutex<VecDeque<String>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(RECENT_LOG_LINES_CAPACITY)));
+
 /// Logging configuration.
 #[derive(Clone, DataSize, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -267,9 +279,22 @@ pub fn init() -> anyhow::Result<()> {
 #[allow(clippy::type_complexity)] // Cannot be helped, unfortunately.
 pub enum ReloadHandle {
     /// Text-logger reload handle.
-    Text(Handle<EnvFilter, Layered<Layer<Registry, FieldFn<FormatDebugFn>, FmtEvent>, Registry>>),
+    Text(
+        Handle<
+            EnvFilter,
+            Layered<
+                Layer<Registry, FieldFn<FormatDebugFn>, FmtEvent, fn() -> RecordingStdout>,
+                Registry,
+            >,
+        >,
+    ),
     /// JSON-logger reload handle.
-    Json(Handle<EnvFilter, Layered<Layer<Registry, JsonFields, Format<Json>>, Registry>>),
+    Json(
+        Handle<
+            EnvFilter,
+            Layered<Layer<Registry, JsonFields, Format<Json>, fn() -> RecordingStdout>, Registry>,
+        >,
+    ),
 }
 
 impl ReloadHandle {
@@ -310,6 +335,58 @@ pub fn display_global_env_filter() -> anyhow::Result<String> {
     Ok(formatted)
 }
 
+/// Returns up to `count` of the most recently logged lines, oldest first.
+pub fn recent_log_lines(count: usize) -> Vec<String> {
+    let recent_lines = RECENT_LOG_LINES
+        .lock()
+        .expect("recent log lines lock should not be poisoned");
+    let skip = recent_lines.len().saturating_sub(count);
+    recent_lines.iter().skip(skip).cloned().collect()
+}
+
+/// Writer printing log output to `stdout`, while retaining the most recent lines in memory.
+pub struct RecordingStdout(io::Stdout);
+
+impl RecordingStdout {
+    fn new() -> Self {
+        RecordingStdout(io::stdout())
+    }
+
+    /// Adds the lines of `output` to the recently logged lines, evicting the oldest ones.
+    fn record(output: &[u8]) {
+        let mut recent_lines = match RECENT_LOG_LINES.lock() {
+            Ok(recent_lines) => recent_lines,
+            // Logging must never panic, losing the recent lines is acceptable.
+            Err(_) => return,
+        };
+        for line in String::from_utf8_lossy(output).lines() {
+            if recent_lines.len() == RECENT_LOG_LINES_CAPACITY {
+                recent_lines.pop_front();
+            }
+            recent_lines.push_back(line.to_string());
+        }
+    }
+}
+
+impl Write for RecordingStdout {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.0.write(buf)?;
+        Self::record(&buf[..written]);
+        Ok(written)
+    }
+
+    // Events are written in one go, overriding `write_all` ensures their lines are never split.
+    fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+        self.0.write_all(buf)?;
+        Self::record(buf);
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
 /// Type alias for the formatting function used.
 pub type FormatDebugFn = fn(&mut Writer, &Field, &dyn std::fmt::Debug) -> fmt::Result;
 
@@ -331,7 +408,7 @@ fn format_into_debug_writer(
 /// this outside of the application or testing code, the installed logger is global.
 ///
 /// See the `README.md` for hints on how to configure logging at runtime.
-// The `io::stdout as fn()...` casts are necessary, as is the `FormatDebugFn` cast.
+// The `RecordingStdout::new as fn()...` casts are necessary, as is the `FormatDebugFn` cast.
 #[allow(trivial_casts)]
 pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
     let formatter = format::debug_fn(format_into_debug_writer as FormatDebugFn);
@@ -346,7 +423,7 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
         // Setup a new tracing-subscriber writing to `stdout` for logging.
         LoggingFormat::Text => {
             let builder = tracing_subscriber::fmt()
-                .with_writer(io::stdout as fn() -> std::io::Stdout)
+                .with_writer(RecordingStdout::new as fn() -> RecordingStdout)
                 .with_env_filter(filter)
                 .fmt_fields(formatter)
                 .event_format(FmtEvent::new(config.color, config.abbreviate_modules))
@@ -360,7 +437,7 @@ pub fn init_with_config(config: &LoggingConfig) -> anyhow::Result<()> {
         // JSON logging writes to `stdout` as well but uses the JSON format.
         LoggingFormat::Json => {
             let builder = tracing_subscriber::fmt()
-                .with_writer(io::stdout as fn() -> std::io::Stdout)
+                .with_writer(RecordingStdout::new as fn() -> RecordingStdout)
                 .with_env_filter(filter)
                 .json()
                 .with_filter_reloading();
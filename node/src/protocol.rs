@@ -1,5 +1,7 @@
 //! A network message type used for communication between nodes
 
+pub(crate) mod schema;
+
 use std::{
     fmt::{self, Display, Formatter},
     sync::Arc,
@@ -17,7 +19,10 @@ use crate::{
         consensus,
         fetcher::{FetchItem, FetchResponse, Tag},
         gossiper,
-        network::{EstimatorWeights, FromIncoming, GossipedAddress, MessageKind, Payload},
+        network::{
+            compress, decompress, DecompressionError, EstimatorWeights, FromIncoming,
+            GossipedAddress, MessageKind, Payload,
+        },
     },
     effect::{
         incoming::{
@@ -68,18 +73,33 @@ pub(crate) enum Message {
     /// Finality signature.
     #[from]
     FinalitySignature(Box<FinalitySignature>),
+    /// Response to a `GetRequest`, with the item compressed.
+    ///
+    /// Only sent to peers which announced support for compression during the handshake, and
+    /// decompressed into a `GetResponse` by the networking component on receipt.
+    CompressedGetResponse {
+        /// The type tag of the contained item.
+        tag: Tag,
+        /// The compressed serialized item.
+        compressed_item: Arc<[u8]>,
+    },
 }
 
 impl Payload for Message {
     #[inline]
     fn message_kind(&self) -> MessageKind {
         match self {
+            Message::Consensus(consensus::ConsensusMessage::EvidenceRequest { .. }) => {
+                MessageKind::UrgentConsensus
+            }
             Message::Consensus(_) => MessageKind::Consensus,
             Message::ConsensusRequest(_) => MessageKind::Consensus,
             Message::BlockGossiper(_) => MessageKind::BlockGossip,
             Message::DeployGossiper(_) => MessageKind::DeployGossip,
             Message::AddressGossiper(_) => MessageKind::AddressGossip,
-            Message::GetRequest { tag, .. } | Message::GetResponse { tag, .. } => match tag {
+            Message::GetRequest { tag, .. }
+            | Message::GetResponse { tag, .. }
+            | Message::CompressedGetResponse { tag, .. } => match tag {
                 Tag::Deploy | Tag::LegacyDeploy => MessageKind::DeployTransfer,
                 Tag::Block => MessageKind::BlockTransfer,
                 Tag::BlockHeader => MessageKind::BlockTransfer,
@@ -89,11 +109,17 @@ impl Payload for Message {
                 Tag::ApprovalsHashes => MessageKind::BlockTransfer,
                 Tag::BlockExecutionResults => MessageKind::BlockTransfer,
             },
-            Message::FinalitySignature(_) => MessageKind::Consensus,
+            Message::FinalitySignature(_) => MessageKind::UrgentConsensus,
             Message::FinalitySignatureGossiper(_) => MessageKind::FinalitySignatureGossip,
         }
     }
 
+    fn is_urgent(&self) -> bool {
+        // Finality signatures and requests for evidence of equivocation are small, but delaying
+        // them behind large proposals or responses holds up finality and fault detection.
+        matches!(self.message_kind(), MessageKind::UrgentConsensus)
+    }
+
     fn is_low_priority(&self) -> bool {
         // We only deprioritize requested trie nodes, as they are the most commonly requested item
         // during fast sync.
@@ -108,6 +134,7 @@ impl Payload for Message {
             Message::GetRequest { .. } => false,
             Message::GetResponse { .. } => false,
             Message::FinalitySignature(_) => false,
+            Message::CompressedGetResponse { .. } => false,
         }
     }
 
@@ -131,21 +158,60 @@ impl Payload for Message {
                 Tag::ApprovalsHashes => weights.approvals_hashes_requests,
                 Tag::BlockExecutionResults => weights.execution_results_requests,
             },
-            Message::GetResponse { tag, .. } => match tag {
-                Tag::Deploy => weights.deploy_responses,
-                Tag::LegacyDeploy => weights.legacy_deploy_responses,
-                Tag::Block => weights.block_responses,
-                Tag::BlockHeader => weights.block_header_responses,
-                Tag::TrieOrChunk => weights.trie_responses,
-                Tag::FinalitySignature => weights.finality_signature_responses,
-                Tag::SyncLeap => weights.sync_leap_responses,
-                Tag::ApprovalsHashes => weights.approvals_hashes_responses,
-                Tag::BlockExecutionResults => weights.execution_results_responses,
-            },
+            Message::GetResponse { tag, .. } | Message::CompressedGetResponse { tag, .. } => {
+                match tag {
+                    Tag::Deploy => weights.deploy_responses,
+                    Tag::LegacyDeploy => weights.legacy_deploy_responses,
+                    Tag::Block => weights.block_responses,
+                    Tag::BlockHeader => weights.block_header_responses,
+                    Tag::TrieOrChunk => weights.trie_responses,
+                    Tag::FinalitySignature => weights.finality_signature_responses,
+                    Tag::SyncLeap => weights.sync_leap_responses,
+                    Tag::ApprovalsHashes => weights.approvals_hashes_responses,
+                    Tag::BlockExecutionResults => weights.execution_results_responses,
+                }
+            }
             Message::FinalitySignature(_) => weights.finality_signature_broadcasts,
         }
     }
 
+    fn compressed(&self, threshold: usize) -> Option<Self> {
+        match self {
+            // Which kinds of responses are worth the CPU time spent compressing them is configured
+            // by the caller.
+            Message::GetResponse {
+                tag,
+                serialized_item,
+            } if serialized_item.len() >= threshold => {
+                let compressed_item = compress(serialized_item);
+                (compressed_item.len() < serialized_item.len()).then(|| {
+                    Message::CompressedGetResponse {
+                        tag: *tag,
+                        compressed_item: compressed_item.into(),
+                    }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn is_compressed(&self) -> bool {
+        matches!(self, Message::CompressedGetResponse { .. })
+    }
+
+    fn decompressed(self, max_size: usize) -> Result<Self, DecompressionError> {
+        match self {
+            Message::CompressedGetResponse {
+                tag,
+                compressed_item,
+            } => Ok(Message::GetResponse {
+                tag,
+                serialized_item: decompress(&compressed_item, max_size)?.into(),
+            }),
+            other => Ok(other),
+        }
+    }
+
     fn is_unsafe_for_syncing_peers(&self) -> bool {
         match self {
             Message::Consensus(_) => false,
@@ -159,6 +225,7 @@ impl Payload for Message {
             Message::GetRequest { .. } => false,
             Message::GetResponse { .. } => false,
             Message::FinalitySignature(_) => false,
+            Message::CompressedGetResponse { .. } => false,
         }
     }
 }
@@ -220,49 +287,69 @@ impl Debug for Message {
             Message::FinalitySignature(fs) => {
                 f.debug_tuple("FinalitySignature").field(&fs).finish()
             }
+            Message::CompressedGetResponse {
+                tag,
+                compressed_item,
+            } => f
+                .debug_struct("CompressedGetResponse")
+                .field("tag", tag)
+                .field(
+                    "compressed_item",
+                    &format!("{} bytes", compressed_item.len()),
+                )
+                .finish(),
         }
     }
 }
 mod specimen_support {
     use crate::utils::specimen::{
-        largest_get_request, largest_get_response, largest_variant, Cache, LargestSpecimen,
-        SizeEstimator,
+        largest_compressed_get_response, largest_get_request, largest_get_response,
+        largest_variant, Cache, LargestSpecimen, SizeEstimator,
     };
 
     use super::{Message, MessageDiscriminants};
 
     impl LargestSpecimen for Message {
         fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
-            largest_variant::<Self, MessageDiscriminants, _, _>(
-                estimator,
-                |variant| match variant {
-                    MessageDiscriminants::Consensus => {
-                        Message::Consensus(LargestSpecimen::largest_specimen(estimator, cache))
-                    }
-                    MessageDiscriminants::ConsensusRequest => Message::ConsensusRequest(
-                        LargestSpecimen::largest_specimen(estimator, cache),
-                    ),
-                    MessageDiscriminants::BlockGossiper => {
-                        Message::BlockGossiper(LargestSpecimen::largest_specimen(estimator, cache))
-                    }
-                    MessageDiscriminants::DeployGossiper => {
-                        Message::DeployGossiper(LargestSpecimen::largest_specimen(estimator, cache))
-                    }
-                    MessageDiscriminants::FinalitySignatureGossiper => {
-                        Message::FinalitySignatureGossiper(LargestSpecimen::largest_specimen(
-                            estimator, cache,
-                        ))
-                    }
-                    MessageDiscriminants::AddressGossiper => Message::AddressGossiper(
-                        LargestSpecimen::largest_specimen(estimator, cache),
-                    ),
-                    MessageDiscriminants::GetRequest => largest_get_request(estimator, cache),
-                    MessageDiscriminants::GetResponse => largest_get_response(estimator, cache),
-                    MessageDiscriminants::FinalitySignature => Message::FinalitySignature(
-                        LargestSpecimen::largest_specimen(estimator, cache),
-                    ),
-                },
-            )
+            largest_variant::<Self, MessageDiscriminants, _, _>(estimator, |variant| {
+                largest_variant_specimen(variant, estimator, cache)
+            })
+        }
+    }
+
+    /// Returns the largest specimen of the given variant of [`Message`].
+    pub(super) fn largest_variant_specimen<E: SizeEstimator>(
+        variant: MessageDiscriminants,
+        estimator: &E,
+        cache: &mut Cache,
+    ) -> Message {
+        match variant {
+            MessageDiscriminants::Consensus => {
+                Message::Consensus(LargestSpecimen::largest_specimen(estimator, cache))
+            }
+            MessageDiscriminants::ConsensusRequest => {
+                Message::ConsensusRequest(LargestSpecimen::largest_specimen(estimator, cache))
+            }
+            MessageDiscriminants::BlockGossiper => {
+                Message::BlockGossiper(LargestSpecimen::largest_specimen(estimator, cache))
+            }
+            MessageDiscriminants::DeployGossiper => {
+                Message::DeployGossiper(LargestSpecimen::largest_specimen(estimator, cache))
+            }
+            MessageDiscriminants::FinalitySignatureGossiper => Message::FinalitySignatureGossiper(
+                LargestSpecimen::largest_specimen(estimator, cache),
+            ),
+            MessageDiscriminants::AddressGossiper => {
+                Message::AddressGossiper(LargestSpecimen::largest_specimen(estimator, cache))
+            }
+            MessageDiscriminants::GetRequest => largest_get_request(estimator, cache),
+            MessageDiscriminants::GetResponse => largest_get_response(estimator, cache),
+            MessageDiscriminants::FinalitySignature => {
+                Message::FinalitySignature(LargestSpecimen::largest_specimen(estimator, cache))
+            }
+            MessageDiscriminants::CompressedGetResponse => {
+                largest_compressed_get_response(estimator, cache)
+            }
         }
     }
 }
@@ -290,6 +377,15 @@ impl Display for Message {
             Message::FinalitySignature(fs) => {
                 write!(f, "FinalitySignature::({})", fs)
             }
+            Message::CompressedGetResponse {
+                tag,
+                compressed_item,
+            } => write!(
+                f,
+                "CompressedGetResponse({}-{:10})",
+                tag,
+                HexFmt(compressed_item)
+            ),
         }
     }
 }
@@ -440,6 +536,10 @@ where
             Message::FinalitySignature(message) => {
                 FinalitySignatureIncoming { sender, message }.into()
             }
+            Message::CompressedGetResponse { .. } => {
+                // Compressed responses are decompressed by the networking component on receipt.
+                unreachable!("called from_incoming with a compressed get response")
+            }
         }
     }
 
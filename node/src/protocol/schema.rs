@@ -0,0 +1,106 @@
+//! Machine-readable description of the variants of [`Message`].
+//!
+//! Used by the `dump-message-schema` command, so that tooling can compare the network messages of
+//! two releases and flag incompatible changes.  Each variant's layout is fingerprinted by hashing
+//! the bincode encoding of its largest specimen, so that adding, removing, reordering or retyping
+//! any field contained in the variant changes its hash.
+
+use std::collections::BTreeSet;
+
+use casper_hashing::Digest;
+use serde::Serialize;
+use strum::IntoEnumIterator;
+
+use super::{specimen_support::largest_variant_specimen, Message, MessageDiscriminants};
+use crate::{
+    components::{
+        fetcher::Tag,
+        network::{BincodeFormat, NetworkMessageEstimator, Payload},
+    },
+    types::Chainspec,
+    utils::specimen::Cache,
+};
+
+/// Description of a single variant of [`Message`].
+#[derive(Debug, Serialize)]
+pub(crate) struct MessageVariantSchema {
+    /// Name of the variant.
+    name: String,
+    /// Index of the variant, as used to tag it in the bincode encoding.
+    tag: u32,
+    /// Kinds of network traffic messages of this variant are classified as.
+    kinds: BTreeSet<String>,
+    /// Hash of the bincode encoding of the variant's largest specimen.
+    layout_hash: Digest,
+}
+
+/// Returns descriptions of all variants of [`Message`], in the order of their tags.
+///
+/// Specimens are sized according to `chainspec`, so schemas should be compared using the same
+/// chainspec.
+pub(crate) fn message_schema(chainspec: &Chainspec) -> Vec<MessageVariantSchema> {
+    let estimator = NetworkMessageEstimator::new(chainspec);
+    let cache = &mut Cache::default();
+
+    MessageDiscriminants::iter()
+        .enumerate()
+        .map(|(index, variant)| {
+            let specimen = largest_variant_specimen(variant, &estimator, cache);
+            let kinds = match variant {
+                // Requests and responses are classified by the type of item they carry.
+                MessageDiscriminants::GetRequest
+                | MessageDiscriminants::GetResponse
+                | MessageDiscriminants::CompressedGetResponse => Tag::iter()
+                    .map(|tag| {
+                        Message::GetRequest {
+                            tag,
+                            serialized_id: vec![],
+                        }
+                        .message_kind()
+                        .to_string()
+                    })
+                    .collect(),
+                _ => BTreeSet::from([specimen.message_kind().to_string()]),
+            };
+            let encoded = BincodeFormat::default()
+                .serialize_arbitrary(&specimen)
+                .expect("did not expect serialization to fail");
+
+            MessageVariantSchema {
+                name: format!("{:?}", variant),
+                tag: u32::try_from(index).expect("too many message variants"),
+                kinds,
+                layout_hash: Digest::hash(encoded),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::ChainspecRawBytes, utils::Loadable};
+
+    #[test]
+    fn should_describe_every_variant_deterministically() {
+        let (chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+
+        let schema = message_schema(&chainspec);
+        assert_eq!(schema.len(), MessageDiscriminants::iter().count());
+        for (index, variant) in schema.iter().enumerate() {
+            assert_eq!(variant.tag as usize, index);
+            assert!(!variant.kinds.is_empty());
+        }
+
+        let layout_hashes = |schema: &[MessageVariantSchema]| {
+            schema
+                .iter()
+                .map(|variant| variant.layout_hash)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            layout_hashes(&schema),
+            layout_hashes(&message_schema(&chainspec))
+        );
+    }
+}
@@ -30,6 +30,7 @@
 //! manner using [`Runner::crank`] or indefinitely using [`Runner::run`].
 
 mod event_queue_metrics;
+pub(crate) mod event_trace;
 pub(crate) mod main_reactor;
 mod queue_kind;
 
@@ -89,6 +90,7 @@ use crate::{
     utils::{self, SharedFlag, WeightedRoundRobin},
     NodeRng, TERMINATION_REQUESTED,
 };
+use event_trace::EventTrace;
 pub(crate) use queue_kind::QueueKind;
 
 /// Default threshold for when an event is considered slow.  Can be overridden by setting the env
@@ -377,6 +379,9 @@ where
 
     /// Flag indicating the reactor is being shut down.
     is_shutting_down: SharedFlag,
+
+    /// Recording of dispatched events, if one was requested via the diagnostics port.
+    event_trace: Option<EventTrace>,
 }
 
 /// Metric data for the Runner
@@ -541,6 +546,7 @@ where
             event_metrics_threshold: 1000,
             clock: Clock::new(),
             is_shutting_down,
+            event_trace: None,
         })
     }
 
@@ -579,7 +585,9 @@ where
             }
         }
 
-        let ((ancestor, event), queue_kind) = self.scheduler.pop().await;
+        let ((ancestor, event), queue_kind, queue_time) =
+            self.scheduler.pop_with_queue_time().await;
+        let popped_from = queue_kind;
         trace!(%event, %queue_kind, "current");
         let event_desc = event.description();
 
@@ -592,6 +600,7 @@ where
         }
 
         // Dispatch the event, then execute the resulting effect.
+        let dispatch_start = std::time::Instant::now();
         let start = self.clock.start();
 
         let (effects, maybe_exit_code, queue_kind) = if event.is_control() {
@@ -672,6 +681,26 @@ where
                     // No other effects, calling the method is all we had to do.
                     (Effects::new(), None, QueueKind::Control)
                 }
+                Some(ControlAnnouncement::StartEventTrace {
+                    max_events,
+                    started,
+                }) => {
+                    let is_new = self.event_trace.is_none();
+                    if is_new {
+                        info!(%max_events, "starting event trace");
+                        self.event_trace = Some(EventTrace::new(max_events));
+                    }
+                    started.respond(is_new).await;
+                    (Effects::new(), None, QueueKind::Control)
+                }
+                Some(ControlAnnouncement::StopEventTrace { trace }) => {
+                    let event_trace = self.event_trace.take();
+                    if let Some(ref event_trace) = event_trace {
+                        info!(recorded = event_trace.len(), "stopped event trace");
+                    }
+                    trace.respond(event_trace).await;
+                    (Effects::new(), None, QueueKind::Control)
+                }
             }
         } else {
             (
@@ -692,6 +721,18 @@ where
             .event_dispatch_duration
             .observe(delta.into_nanos() as f64);
 
+        if let Some(ref mut event_trace) = self.event_trace {
+            event_trace.record(
+                self.current_event_id,
+                ancestor,
+                event_desc,
+                popped_from,
+                queue_time,
+                dispatch_start,
+                Duration::from_nanos(delta.into_nanos()),
+            );
+        }
+
         // Run effects, with the current event ID as the ancestor for resulting set of events.
         process_effects(
             NonZeroU64::new(self.current_event_id),
@@ -0,0 +1,255 @@
+//! Recording of dispatched reactor events, exported in the Chrome trace event format.
+//!
+//! A trace can be loaded into `chrome://tracing` or [Perfetto](https://ui.perfetto.dev) to
+//! visualize where events spent their time.  Each event's dispatch is shown as a slice on the track
+//! of the component or request type it was dispatched to (as given by
+//! [`ReactorEvent::description`](super::ReactorEvent::description)), and the time it spent in its
+//! queue beforehand as an asynchronous slice in the `queue` category.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug, Formatter},
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::json;
+
+use super::QueueKind;
+
+/// Process ID used for all trace events.
+const PID: u32 = 1;
+
+/// A single dispatched event.
+struct TraceEntry {
+    /// The reactor's ID for the event.
+    event_id: u64,
+    /// The ID of the event whose effects created this event, if known.
+    ancestor: Option<NonZeroU64>,
+    /// The component or request type the event was dispatched to.
+    description: &'static str,
+    /// The queue the event was popped from.
+    queue_kind: QueueKind,
+    /// Time the event spent queued before being dispatched.
+    queue_time: Duration,
+    /// Instant at which dispatching the event started.
+    dispatch_start: Instant,
+    /// Time it took to dispatch the event.
+    dispatch_time: Duration,
+}
+
+/// An in-progress recording of dispatched reactor events.
+pub(crate) struct EventTrace {
+    /// Instant at which recording started, used as the origin of the trace's timestamps.
+    started_at: Instant,
+    /// Maximum number of events to record.
+    max_events: usize,
+    /// The recorded events.
+    entries: Vec<TraceEntry>,
+    /// Number of events which were not recorded because `max_events` was reached.
+    skipped: u64,
+}
+
+impl EventTrace {
+    /// Starts a new recording which will hold at most `max_events` events.
+    pub(crate) fn new(max_events: usize) -> Self {
+        EventTrace {
+            started_at: Instant::now(),
+            max_events,
+            entries: Vec::new(),
+            skipped: 0,
+        }
+    }
+
+    /// Records a dispatched event.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn record(
+        &mut self,
+        event_id: u64,
+        ancestor: Option<NonZeroU64>,
+        description: &'static str,
+        queue_kind: QueueKind,
+        queue_time: Duration,
+        dispatch_start: Instant,
+        dispatch_time: Duration,
+    ) {
+        if self.entries.len() >= self.max_events {
+            self.skipped += 1;
+            return;
+        }
+        self.entries.push(TraceEntry {
+            event_id,
+            ancestor,
+            description,
+            queue_kind,
+            queue_time,
+            dispatch_start,
+            dispatch_time,
+        });
+    }
+
+    /// Returns the number of recorded events.
+    pub(crate) fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the offset of `instant` from the start of the recording in microseconds, clamped to
+    /// zero.
+    fn timestamp(&self, instant: Instant) -> f64 {
+        micros(instant.saturating_duration_since(self.started_at))
+    }
+
+    /// Serializes the recording as a Chrome trace JSON document.
+    pub(crate) fn to_chrome_trace(&self) -> Result<Vec<u8>, serde_json::Error> {
+        let mut tids: HashMap<&'static str, usize> = HashMap::new();
+        let mut trace_events = Vec::with_capacity(self.entries.len() * 3);
+
+        for entry in &self.entries {
+            let next_tid = tids.len() + 1;
+            let tid = *tids.entry(entry.description).or_insert_with(|| {
+                trace_events.push(json!({
+                    "name": "thread_name",
+                    "ph": "M",
+                    "pid": PID,
+                    "tid": next_tid,
+                    "args": { "name": entry.description },
+                }));
+                next_tid
+            });
+
+            let dispatch_ts = self.timestamp(entry.dispatch_start);
+            let queued_ts = self.timestamp(
+                entry
+                    .dispatch_start
+                    .checked_sub(entry.queue_time)
+                    .unwrap_or(self.started_at),
+            );
+            let queue = entry.queue_kind.to_string();
+
+            trace_events.push(json!({
+                "name": entry.description,
+                "cat": "queue",
+                "ph": "b",
+                "id": entry.event_id,
+                "pid": PID,
+                "tid": tid,
+                "ts": queued_ts,
+                "args": { "queue": queue },
+            }));
+            trace_events.push(json!({
+                "name": entry.description,
+                "cat": "queue",
+                "ph": "e",
+                "id": entry.event_id,
+                "pid": PID,
+                "tid": tid,
+                "ts": dispatch_ts,
+            }));
+            trace_events.push(json!({
+                "name": entry.description,
+                "cat": "dispatch",
+                "ph": "X",
+                "pid": PID,
+                "tid": tid,
+                "ts": dispatch_ts,
+                "dur": micros(entry.dispatch_time),
+                "args": {
+                    "event_id": entry.event_id,
+                    "ancestor": entry.ancestor,
+                    "queue": queue,
+                    "queue_time_us": micros(entry.queue_time),
+                },
+            }));
+        }
+
+        serde_json::to_vec(&ChromeTrace {
+            trace_events,
+            display_time_unit: "ms",
+            other_data: json!({ "skipped_events": self.skipped }),
+        })
+    }
+}
+
+impl Debug for EventTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventTrace")
+            .field("max_events", &self.max_events)
+            .field("recorded", &self.entries.len())
+            .field("skipped", &self.skipped)
+            .finish()
+    }
+}
+
+/// Top-level object of the Chrome trace JSON format.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChromeTrace {
+    trace_events: Vec<serde_json::Value>,
+    display_time_unit: &'static str,
+    other_data: serde_json::Value,
+}
+
+fn micros(duration: Duration) -> f64 {
+    duration.as_nanos() as f64 / 1_000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_export_chrome_trace() {
+        let mut trace = EventTrace::new(2);
+        let start = trace.started_at + Duration::from_micros(100);
+        trace.record(
+            1,
+            None,
+            "Storage",
+            QueueKind::FromStorage,
+            Duration::from_micros(40),
+            start,
+            Duration::from_micros(10),
+        );
+        trace.record(
+            2,
+            NonZeroU64::new(1),
+            "Consensus",
+            QueueKind::Consensus,
+            Duration::from_micros(5),
+            start + Duration::from_micros(10),
+            Duration::from_micros(20),
+        );
+        trace.record(
+            3,
+            None,
+            "Storage",
+            QueueKind::FromStorage,
+            Duration::ZERO,
+            start + Duration::from_micros(30),
+            Duration::from_micros(1),
+        );
+        assert_eq!(trace.len(), 2);
+
+        let exported: serde_json::Value =
+            serde_json::from_slice(&trace.to_chrome_trace().expect("should export"))
+                .expect("should be valid JSON");
+        assert_eq!(exported["otherData"]["skipped_events"], 1);
+
+        let events = exported["traceEvents"].as_array().expect("should be array");
+        let dispatches: Vec<_> = events.iter().filter(|event| event["ph"] == "X").collect();
+        assert_eq!(dispatches.len(), 2);
+        assert_eq!(dispatches[0]["name"], "Storage");
+        assert_eq!(dispatches[0]["ts"], 100.0);
+        assert_eq!(dispatches[0]["dur"], 10.0);
+        assert_eq!(dispatches[0]["args"]["queue_time_us"], 40.0);
+        assert_eq!(dispatches[1]["args"]["ancestor"], 1);
+        assert_ne!(dispatches[0]["tid"], dispatches[1]["tid"]);
+
+        let queue_start = events
+            .iter()
+            .find(|event| event["ph"] == "b" && event["id"] == 1)
+            .expect("should have queue slice");
+        assert_eq!(queue_start["ts"], 60.0);
+    }
+}
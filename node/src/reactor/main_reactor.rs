@@ -12,6 +12,7 @@ mod catch_up;
 mod genesis_instruction;
 mod keep_up;
 mod reactor_state;
+mod self_test;
 #[cfg(test)]
 mod tests;
 mod upgrade_shutdown;
@@ -23,9 +24,9 @@ use std::{collections::BTreeMap, sync::Arc, time::Instant};
 use datasize::DataSize;
 use memory_metrics::MemoryMetrics;
 use prometheus::Registry;
-use tracing::{debug, error, info, warn};
+use tracing::{debug, error, info, trace, warn};
 
-use casper_types::{EraId, PublicKey, TimeDiff, Timestamp, U512};
+use casper_types::{AsymmetricType, EraId, PublicKey, TimeDiff, Timestamp, U512};
 
 #[cfg(test)]
 use crate::testing::network::NetworkedReactor;
@@ -38,13 +39,13 @@ use crate::{
         contract_runtime::ContractRuntime,
         deploy_acceptor::{self, DeployAcceptor},
         deploy_buffer::{self, DeployBuffer},
-        diagnostics_port::DiagnosticsPort,
-        event_stream_server::{self, EventStreamServer},
+        diagnostics_port::{self, DiagnosticsPort},
+        event_stream_server::{self, EventStreamServer, NodeMilestone},
         gossiper::{self, GossipItem, Gossiper},
-        metrics::Metrics,
+        metrics::{Metrics, OtlpExporter},
         network::{self, GossipedAddress, Identity as NetworkIdentity, Network},
         rest_server::RestServer,
-        rpc_server::RpcServer,
+        rpc_server::{self, RpcServer},
         shutdown_trigger::{self, ShutdownTrigger},
         storage::Storage,
         sync_leaper::SyncLeaper,
@@ -73,8 +74,8 @@ use crate::{
         EventQueueHandle, QueueKind,
     },
     types::{
-        Block, BlockHash, Chainspec, ChainspecRawBytes, Deploy, FinalitySignature, MetaBlock,
-        MetaBlockState, SyncHandling, TrieOrChunk, ValidatorMatrix,
+        Block, BlockHash, Chainspec, ChainspecRawBytes, Deploy, FinalitySignature, FootprintCache,
+        MetaBlock, MetaBlockState, SyncHandling, TrieOrChunk, ValidatorMatrix,
     },
     utils::{Source, WithDir},
     NodeRng,
@@ -191,6 +192,7 @@ pub(crate) struct MainReactor {
     upgrade_timeout: TimeDiff,
     sync_handling: SyncHandling,
     signature_gossip_tracker: SignatureGossipTracker,
+    archive_mode: bool,
 }
 
 impl reactor::Reactor for MainReactor {
@@ -204,6 +206,10 @@ impl reactor::Reactor for MainReactor {
         rng: &mut NodeRng,
         event: MainEvent,
     ) -> Effects<MainEvent> {
+        if self.archive_mode && event.is_participation_message() {
+            trace!(%event, "archive mode: ignoring participation message");
+            return Effects::new();
+        }
         match event {
             MainEvent::ControlAnnouncement(ctrl_ann) => {
                 error!("unhandled control announcement: {}", ctrl_ann);
@@ -235,6 +241,8 @@ impl reactor::Reactor for MainReactor {
 
             // PRIMARY REACTOR STATE CONTROL LOGIC
             MainEvent::ReactorCrank => self.crank(effect_builder, rng),
+            #[cfg(test)]
+            MainEvent::Delayed(event) => self.dispatch_event(effect_builder, rng, *event),
 
             MainEvent::MainReactorRequest(req) => {
                 req.0.respond((self.state, self.last_progress)).ignore()
@@ -296,14 +304,25 @@ impl reactor::Reactor for MainReactor {
                 // register activation point of upgrade w/ block accumulator
                 self.block_accumulator
                     .register_activation_point(next_upgrade.activation_point());
-                reactor::wrap_effects(
+                // the upgrade watcher re-reads the upgrade periodically; only publish changes
+                let mut effects = if self.upgrade_watcher.next_upgrade() != Some(&next_upgrade) {
+                    let reactor_event =
+                        MainEvent::EventStreamServer(event_stream_server::Event::NodeMilestone(
+                            NodeMilestone::UpgradeStaged(next_upgrade.clone()),
+                        ));
+                    self.dispatch_event(effect_builder, rng, reactor_event)
+                } else {
+                    Effects::new()
+                };
+                effects.extend(reactor::wrap_effects(
                     MainEvent::UpgradeWatcher,
                     self.upgrade_watcher.handle_event(
                         effect_builder,
                         rng,
                         upgrade_watcher::Event::GotNextUpgrade(next_upgrade),
                     ),
-                )
+                ));
+                effects
             }
             MainEvent::RpcServer(event) => reactor::wrap_effects(
                 MainEvent::RpcServer,
@@ -373,6 +392,10 @@ impl reactor::Reactor for MainReactor {
                 ));
                 effects
             }
+            MainEvent::UpcomingValidatorsAnnouncement(ann) => {
+                let event = MainEvent::Network(ann.into());
+                self.dispatch_event(effect_builder, rng, event)
+            }
             MainEvent::NetworkPeerRequestingData(incoming) => reactor::wrap_effects(
                 MainEvent::Storage,
                 self.storage
@@ -400,9 +423,14 @@ impl reactor::Reactor for MainReactor {
                 GossiperAnnouncement::GossipReceived { .. }
                 | GossiperAnnouncement::NewItemBody { .. }
                 | GossiperAnnouncement::FinishedGossiping(_) => Effects::new(),
-                GossiperAnnouncement::NewCompleteItem(gossiped_address) => {
-                    let reactor_event =
-                        MainEvent::Network(network::Event::PeerAddressReceived(gossiped_address));
+                GossiperAnnouncement::NewCompleteItem {
+                    item_id: gossiped_address,
+                    sender,
+                } => {
+                    let reactor_event = MainEvent::Network(network::Event::PeerAddressReceived {
+                        gossiped_address: Box::new(gossiped_address),
+                        sender: Box::new(sender),
+                    });
                     self.dispatch_event(effect_builder, rng, reactor_event)
                 }
             },
@@ -427,14 +455,39 @@ impl reactor::Reactor for MainReactor {
             MainEvent::ConsensusAnnouncement(consensus_announcement) => {
                 match consensus_announcement {
                     ConsensusAnnouncement::Proposed(block) => {
+                        let event_stream_event = event_stream_server::Event::BlockProposed {
+                            timestamp: block.context().timestamp(),
+                            deploy_hashes: block
+                                .value()
+                                .deploy_and_transfer_hashes()
+                                .copied()
+                                .collect(),
+                        };
                         let reactor_event =
                             MainEvent::DeployBuffer(deploy_buffer::Event::BlockProposed(block));
-                        self.dispatch_event(effect_builder, rng, reactor_event)
+                        let mut effects = self.dispatch_event(effect_builder, rng, reactor_event);
+                        effects.extend(self.dispatch_event(
+                            effect_builder,
+                            rng,
+                            MainEvent::EventStreamServer(event_stream_event),
+                        ));
+                        effects
                     }
                     ConsensusAnnouncement::Finalized(block) => {
+                        let event_stream_event = event_stream_server::Event::BlockFinalized {
+                            era_id: block.era_id(),
+                            height: block.height(),
+                            deploy_hashes: block.deploy_and_transfer_hashes().copied().collect(),
+                        };
                         let reactor_event =
                             MainEvent::DeployBuffer(deploy_buffer::Event::BlockFinalized(block));
-                        self.dispatch_event(effect_builder, rng, reactor_event)
+                        let mut effects = self.dispatch_event(effect_builder, rng, reactor_event);
+                        effects.extend(self.dispatch_event(
+                            effect_builder,
+                            rng,
+                            MainEvent::EventStreamServer(event_stream_event),
+                        ));
+                        effects
                     }
                     ConsensusAnnouncement::Fault {
                         era_id,
@@ -503,6 +556,15 @@ impl reactor::Reactor for MainReactor {
                     ),
                 );
 
+                effects.extend(reactor::wrap_effects(
+                    MainEvent::RpcServer,
+                    self.rpc_server.handle_event(
+                        effect_builder,
+                        rng,
+                        rpc_server::Event::FinalitySignatureAccepted(finality_signature.block_hash),
+                    ),
+                ));
+
                 effects.extend(reactor::wrap_effects(
                     MainEvent::EventStreamServer,
                     self.event_stream_server.handle_event(
@@ -538,9 +600,10 @@ impl reactor::Reactor for MainReactor {
                     },
                 ),
             ),
-            MainEvent::BlockGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem(
-                gossiped_block_id,
-            )) => {
+            MainEvent::BlockGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem {
+                item_id: gossiped_block_id,
+                ..
+            }) => {
                 error!(%gossiped_block_id, "gossiper should not announce new block");
                 Effects::new()
             }
@@ -627,7 +690,10 @@ impl reactor::Reactor for MainReactor {
                 ),
             ),
             MainEvent::FinalitySignatureGossiperAnnouncement(
-                GossiperAnnouncement::NewCompleteItem(gossiped_finality_signature_id),
+                GossiperAnnouncement::NewCompleteItem {
+                    item_id: gossiped_finality_signature_id,
+                    ..
+                },
             ) => {
                 error!(%gossiped_finality_signature_id, "gossiper should not announce new finality signature");
                 Effects::new()
@@ -680,6 +746,11 @@ impl reactor::Reactor for MainReactor {
                 speculative_exec_at_block,
                 responder,
             }) => {
+                if self.archive_mode && speculative_exec_at_block.is_none() {
+                    return responder
+                        .respond(Err(deploy_acceptor::Error::ArchiveMode))
+                        .ignore();
+                }
                 let source = if let Some(block) = speculative_exec_at_block {
                     Source::SpeculativeExec(block)
                 } else {
@@ -763,9 +834,10 @@ impl reactor::Reactor for MainReactor {
                 // Ignore the announcement.
                 Effects::new()
             }
-            MainEvent::DeployGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem(
-                gossiped_deploy_id,
-            )) => {
+            MainEvent::DeployGossiperAnnouncement(GossiperAnnouncement::NewCompleteItem {
+                item_id: gossiped_deploy_id,
+                ..
+            }) => {
                 error!(%gossiped_deploy_id, "gossiper should not announce new deploy");
                 Effects::new()
             }
@@ -800,12 +872,16 @@ impl reactor::Reactor for MainReactor {
                 self.dispatch_event(effect_builder, rng, MainEvent::DeployBuffer(req.into()))
             }
             MainEvent::DeployBufferAnnouncement(DeployBufferAnnouncement::DeploysExpired(
-                hashes,
+                expired,
             )) => {
+                let mut effects = effect_builder
+                    .put_expired_deploys_to_storage(expired.clone())
+                    .ignore();
                 let reactor_event = MainEvent::EventStreamServer(
-                    event_stream_server::Event::DeploysExpired(hashes),
+                    event_stream_server::Event::DeploysExpired(expired),
                 );
-                self.dispatch_event(effect_builder, rng, reactor_event)
+                effects.extend(self.dispatch_event(effect_builder, rng, reactor_event));
+                effects
             }
 
             // CONTRACT RUNTIME & GLOBAL STATE
@@ -843,7 +919,20 @@ impl reactor::Reactor for MainReactor {
                     era_that_is_ending
                 );
                 self.validator_matrix.register_eras(upcoming_era_validators);
-                Effects::new()
+                let reactor_event = MainEvent::EventStreamServer(
+                    event_stream_server::Event::NodeMilestone(NodeMilestone::EraTransition {
+                        era_id: era_that_is_ending.successor(),
+                    }),
+                );
+                self.dispatch_event(effect_builder, rng, reactor_event)
+            }
+            MainEvent::ContractRuntimeAnnouncement(ContractRuntimeAnnouncement::StepOutcome(
+                step_outcome,
+            )) => {
+                let reactor_event = MainEvent::EventStreamServer(
+                    event_stream_server::Event::StepOutcome(step_outcome),
+                );
+                self.dispatch_event(effect_builder, rng, reactor_event)
             }
 
             MainEvent::TrieRequestIncoming(req) => reactor::wrap_effects(
@@ -1004,7 +1093,19 @@ impl reactor::Reactor for MainReactor {
 
         let trusted_hash = config.value().node.trusted_hash;
         let (root_dir, config) = config.into_parts();
+        let diagnostics_node_info = diagnostics_port::NodeInfo::new(&config, &chainspec);
         let (our_secret_key, our_public_key) = config.consensus.load_keys(&root_dir)?;
+        let otlp_exporter = OtlpExporter::new(
+            &config.metrics,
+            registry.clone(),
+            vec![
+                ("service.name", "casper-node".to_string()),
+                ("service.version", crate::VERSION_STRING.clone()),
+                ("service.instance.id", our_public_key.to_hex()),
+                ("casper.network", chainspec.network_config.name.clone()),
+                ("casper.protocol_version", protocol_version.to_string()),
+            ],
+        )?;
         let validator_matrix = ValidatorMatrix::new(
             chainspec.core_config.finality_threshold_fraction,
             chainspec
@@ -1048,9 +1149,15 @@ impl reactor::Reactor for MainReactor {
             chainspec.system_costs_config,
             chainspec.core_config.max_associated_keys,
             chainspec.core_config.max_runtime_call_stack_height,
+            chainspec.core_config.max_cl_value_size,
+            chainspec.core_config.max_named_keys,
             chainspec.core_config.minimum_delegation_amount,
+            chainspec.core_config.minimum_bid_amount,
+            chainspec.core_config.record_auction_snapshots,
             chainspec.protocol_config.activation_point,
             chainspec.core_config.prune_batch_size,
+            chainspec.core_config.execution_results_v2_activation_point,
+            chainspec.core_config.unbonding_delay,
             chainspec.core_config.strict_argument_checking,
             chainspec.core_config.vesting_schedule_period.millis(),
             max_delegators_per_validator,
@@ -1060,6 +1167,10 @@ impl reactor::Reactor for MainReactor {
             chainspec.core_config.allow_unrestricted_transfers,
             chainspec.core_config.refund_handling,
             chainspec.core_config.fee_handling,
+            chainspec.core_config.storage_rent,
+            chainspec.core_config.delegation_rate_change_limit,
+            chainspec.core_config.account_creation_policy,
+            &chainspec.deploy_config,
         )?;
 
         let network = Network::new(
@@ -1071,9 +1182,14 @@ impl reactor::Reactor for MainReactor {
             validator_matrix.clone(),
         )?;
 
+        if config.node.run_self_test {
+            self_test::run(protocol_version, &contract_runtime, &storage, &network)?;
+        }
+
         let address_gossiper = Gossiper::<{ GossipedAddress::ID_IS_COMPLETE_ITEM }, _>::new(
             "address_gossiper",
             config.gossip,
+            network.network_features(),
             registry,
         )?;
 
@@ -1083,6 +1199,7 @@ impl reactor::Reactor for MainReactor {
             protocol_version,
             chainspec.network_config.name.clone(),
             node_startup_instant,
+            validator_matrix.clone(),
         );
         let rest_server = RestServer::new(
             config.rest_server.clone(),
@@ -1095,8 +1212,10 @@ impl reactor::Reactor for MainReactor {
             storage.root_path().to_path_buf(),
             protocol_version,
         );
-        let diagnostics_port =
-            DiagnosticsPort::new(WithDir::new(&root_dir, config.diagnostics_port));
+        let diagnostics_port = DiagnosticsPort::new(
+            WithDir::new(&root_dir, config.diagnostics_port),
+            diagnostics_node_info,
+        );
         let shutdown_trigger = ShutdownTrigger::new();
 
         // local / remote data management
@@ -1107,17 +1226,20 @@ impl reactor::Reactor for MainReactor {
         let block_gossiper = Gossiper::<{ Block::ID_IS_COMPLETE_ITEM }, _>::new(
             "block_gossiper",
             config.gossip,
+            network.network_features(),
             registry,
         )?;
         let deploy_gossiper = Gossiper::<{ Deploy::ID_IS_COMPLETE_ITEM }, _>::new(
             "deploy_gossiper",
             config.gossip,
+            network.network_features(),
             registry,
         )?;
         let finality_signature_gossiper =
             Gossiper::<{ FinalitySignature::ID_IS_COMPLETE_ITEM }, _>::new(
                 "finality_signature_gossiper",
                 config.gossip,
+                network.network_features(),
                 registry,
             )?;
 
@@ -1148,13 +1270,29 @@ impl reactor::Reactor for MainReactor {
             validator_matrix.clone(),
             registry,
         )?;
-        let block_validator = BlockValidator::new(Arc::clone(&chainspec), config.block_validator);
+        // Deploy footprints are shared between block validation and block proposal.
+        let footprint_cache = FootprintCache::default();
+        let block_validator = BlockValidator::new(
+            Arc::clone(&chainspec),
+            config.block_validator,
+            footprint_cache.clone(),
+        );
         let upgrade_watcher =
             UpgradeWatcher::new(chainspec.as_ref(), config.upgrade_watcher, &root_dir)?;
-        let deploy_acceptor =
-            DeployAcceptor::new(config.deploy_acceptor, chainspec.as_ref(), registry)?;
-        let deploy_buffer =
-            DeployBuffer::new(chainspec.deploy_config, config.deploy_buffer, registry)?;
+        let deploy_acceptor = DeployAcceptor::new(
+            config.deploy_acceptor,
+            chainspec.as_ref(),
+            validator_matrix.clone(),
+            registry,
+        )?;
+        let proposal_policy = deploy_buffer::configured_proposal_policy(&config.deploy_buffer);
+        let mut deploy_buffer = DeployBuffer::new(
+            chainspec.deploy_config,
+            config.deploy_buffer,
+            footprint_cache,
+            registry,
+        )?;
+        deploy_buffer.set_proposal_policy(proposal_policy);
 
         let reactor = MainReactor {
             chainspec,
@@ -1200,11 +1338,29 @@ impl reactor::Reactor for MainReactor {
             shutdown_for_upgrade_timeout: config.node.shutdown_for_upgrade_timeout,
             switched_to_shutdown_for_upgrade: Timestamp::from(0),
             upgrade_timeout: config.node.upgrade_timeout,
+            archive_mode: config.node.archive_mode,
         };
         info!("MainReactor: instantiated");
-        let effects = effect_builder
+        let mut effects = effect_builder
             .immediately()
             .event(|()| MainEvent::ReactorCrank);
+        effects.extend(reactor::wrap_effects(
+            MainEvent::Storage,
+            reactor.storage.schedule_compaction(effect_builder),
+        ));
+        effects.extend(reactor::wrap_effects(
+            MainEvent::Storage,
+            reactor.storage.schedule_pruning(effect_builder),
+        ));
+        effects.extend(reactor::wrap_effects(
+            MainEvent::Storage,
+            reactor
+                .storage
+                .schedule_cold_storage_migration(effect_builder),
+        ));
+        if let Some(otlp_exporter) = otlp_exporter {
+            effects.extend(otlp_exporter.run().ignore());
+        }
         Ok((reactor, effects))
     }
 
@@ -1232,15 +1388,19 @@ impl MainReactor {
         era_id: EraId,
         validator_weights: BTreeMap<PublicKey, U512>,
     ) -> Effects<MainEvent> {
+        let validators = validator_weights.keys().cloned().collect();
         self.validator_matrix
             .register_validator_weights(era_id, validator_weights);
         info!(%era_id, "validator_matrix updated");
         // notify validator bound components
-        let mut effects = reactor::wrap_effects(
+        let mut effects = effect_builder
+            .announce_upcoming_validators(era_id, validators)
+            .ignore();
+        effects.extend(reactor::wrap_effects(
             MainEvent::BlockAccumulator,
             self.block_accumulator
                 .handle_validators(effect_builder, rng),
-        );
+        ));
         effects.extend(reactor::wrap_effects(
             MainEvent::BlockSynchronizer,
             self.block_synchronizer
@@ -1582,6 +1742,14 @@ impl MainReactor {
     pub(crate) fn contract_runtime(&self) -> &ContractRuntime {
         &self.contract_runtime
     }
+
+    pub(crate) fn contract_runtime_mut(&mut self) -> &mut ContractRuntime {
+        &mut self.contract_runtime
+    }
+
+    pub(crate) fn event_stream_server(&self) -> &EventStreamServer {
+        &self.event_stream_server
+    }
 }
 
 #[cfg(test)]
@@ -8,13 +8,14 @@ use crate::{
     components::{
         block_accumulator::{SyncIdentifier, SyncInstruction},
         block_synchronizer::BlockSynchronizerProgress,
+        event_stream_server::NodeMilestone,
         sync_leaper,
         sync_leaper::{LeapActivityError, LeapState},
         ValidatorBoundComponent,
     },
     effect::{requests::BlockSynchronizerRequest, EffectBuilder, EffectExt, Effects},
     reactor::{
-        main_reactor::{MainEvent, MainReactor},
+        main_reactor::{utils, MainEvent, MainReactor},
         wrap_effects,
     },
     types::{ActivationPoint, BlockHash, NodeId, SyncLeap, SyncLeapIdentifier},
@@ -386,6 +387,14 @@ impl MainReactor {
         self.block_synchronizer
             .register_sync_leap(&sync_leap, from_peers, true);
 
+        effects.extend(utils::announce_milestone(
+            effect_builder,
+            NodeMilestone::SyncLeaped {
+                block_hash,
+                block_height,
+            },
+        ));
+
         CatchUpInstruction::Do(self.control_logic_default_delay.into(), effects)
     }
 
@@ -7,8 +7,8 @@ use crate::{
     types::{Chainspec, NodeConfig},
     BlockAccumulatorConfig, BlockSynchronizerConfig, BlockValidatorConfig, ConsensusConfig,
     ContractRuntimeConfig, DeployAcceptorConfig, DeployBufferConfig, DiagnosticsPortConfig,
-    EventStreamServerConfig, FetcherConfig, GossipConfig, NetworkConfig, RestServerConfig,
-    RpcServerConfig, SpeculativeExecConfig, StorageConfig, UpgradeWatcherConfig,
+    EventStreamServerConfig, FetcherConfig, GossipConfig, MetricsConfig, NetworkConfig,
+    RestServerConfig, RpcServerConfig, SpeculativeExecConfig, StorageConfig, UpgradeWatcherConfig,
 };
 
 /// Root configuration.
@@ -54,6 +54,8 @@ pub struct Config {
     pub block_validator: BlockValidatorConfig,
     /// Config values for the upgrade watcher.
     pub upgrade_watcher: UpgradeWatcherConfig,
+    /// Config values for metrics.
+    pub metrics: MetricsConfig,
 }
 
 impl Config {
@@ -6,9 +6,13 @@ use casper_types::{EraId, PublicKey, Timestamp};
 
 use crate::{
     components::{
-        block_synchronizer, block_synchronizer::BlockSynchronizerProgress, consensus::EraReport,
-        contract_runtime::ExecutionPreState, diagnostics_port, event_stream_server, network,
-        rest_server, rpc_server, upgrade_watcher,
+        block_synchronizer,
+        block_synchronizer::BlockSynchronizerProgress,
+        consensus::EraReport,
+        contract_runtime::ExecutionPreState,
+        diagnostics_port,
+        event_stream_server::{self, NodeMilestone},
+        network, rest_server, rpc_server, upgrade_watcher,
     },
     effect::{EffectBuilder, EffectExt, Effects},
     fatal,
@@ -60,6 +64,17 @@ impl MainReactor {
                 match self.initialize_next_component(effect_builder) {
                     Some(effects) => (initialization_logic_default_delay.into(), effects),
                     None => {
+                        if self.archive_mode {
+                            if let Err(msg) = self.refresh_contract_runtime() {
+                                return (
+                                    Duration::ZERO,
+                                    fatal!(effect_builder, "{}", msg).ignore(),
+                                );
+                            }
+                            info!("Initialize: switch to Archive");
+                            self.state = ReactorState::Archive;
+                            return (Duration::ZERO, Effects::new());
+                        }
                         if false == self.net.has_sufficient_fully_connected_peers() {
                             info!("Initialize: awaiting sufficient fully-connected peers");
                             return (initialization_logic_default_delay.into(), Effects::new());
@@ -69,7 +84,10 @@ impl MainReactor {
                         }
                         info!("Initialize: switch to CatchUp");
                         self.state = ReactorState::CatchUp;
-                        (Duration::ZERO, Effects::new())
+                        (
+                            Duration::ZERO,
+                            utils::announce_milestone(effect_builder, NodeMilestone::SyncStarted),
+                        )
                     }
                 }
             }
@@ -81,7 +99,10 @@ impl MainReactor {
                 UpgradingInstruction::CatchUp => {
                     info!("Upgrading: switch to CatchUp");
                     self.state = ReactorState::CatchUp;
-                    (Duration::ZERO, Effects::new())
+                    (
+                        Duration::ZERO,
+                        utils::announce_milestone(effect_builder, NodeMilestone::SyncStarted),
+                    )
                 }
             },
             ReactorState::CatchUp => match self.catch_up_instruction(effect_builder, rng) {
@@ -94,10 +115,16 @@ impl MainReactor {
                     (Duration::ZERO, Effects::new())
                 }
                 CatchUpInstruction::CommitGenesis => match self.commit_genesis(effect_builder) {
-                    GenesisInstruction::Validator(duration, effects) => {
+                    GenesisInstruction::Validator(duration, mut effects) => {
                         info!("CatchUp: switch to Validate at genesis");
                         self.block_synchronizer.purge();
                         self.state = ReactorState::Validate;
+                        effects.extend(utils::announce_milestone(
+                            effect_builder,
+                            NodeMilestone::StartedValidating {
+                                era_id: EraId::from(0),
+                            },
+                        ));
                         (duration, effects)
                     }
                     GenesisInstruction::NonValidator(duration, effects) => {
@@ -111,12 +138,18 @@ impl MainReactor {
                     ),
                 },
                 CatchUpInstruction::CommitUpgrade => match self.commit_upgrade(effect_builder) {
-                    Ok(effects) => {
+                    Ok(mut effects) => {
                         info!("CatchUp: switch to Upgrading");
                         self.block_synchronizer.purge();
                         self.state = ReactorState::Upgrading;
                         self.last_progress = Timestamp::now();
                         self.attempts = 0;
+                        effects.extend(utils::announce_milestone(
+                            effect_builder,
+                            NodeMilestone::UpgradeActivated {
+                                protocol_version: self.chainspec.protocol_version(),
+                            },
+                        ));
                         (Duration::ZERO, effects)
                     }
                     Err(msg) => (
@@ -140,7 +173,10 @@ impl MainReactor {
                     info!("CatchUp: switch to KeepUp");
                     self.block_synchronizer.purge();
                     self.state = ReactorState::KeepUp;
-                    (Duration::ZERO, Effects::new())
+                    (
+                        Duration::ZERO,
+                        utils::announce_milestone(effect_builder, NodeMilestone::CaughtUp),
+                    )
                 }
             },
             ReactorState::KeepUp => match self.keep_up_instruction(effect_builder, rng) {
@@ -165,13 +201,22 @@ impl MainReactor {
                     self.sync_leaper.purge();
                     info!("KeepUp: switch to CatchUp");
                     self.state = ReactorState::CatchUp;
-                    (Duration::ZERO, Effects::new())
+                    (
+                        Duration::ZERO,
+                        utils::announce_milestone(effect_builder, NodeMilestone::SyncStarted),
+                    )
                 }
-                KeepUpInstruction::Validate(effects) => {
+                KeepUpInstruction::Validate(mut effects) => {
                     info!("KeepUp: switch to Validate");
                     // purge to avoid polluting the status endpoints w/ stale state
                     self.block_synchronizer.purge();
                     self.state = ReactorState::Validate;
+                    if let Some(era_id) = self.consensus.current_era() {
+                        effects.extend(utils::announce_milestone(
+                            effect_builder,
+                            NodeMilestone::StartedValidating { era_id },
+                        ));
+                    }
                     (Duration::ZERO, effects)
                 }
             },
@@ -196,7 +241,15 @@ impl MainReactor {
                     Ok(_) => {
                         info!("Validate: switch to CatchUp");
                         self.state = ReactorState::CatchUp;
-                        (Duration::ZERO, Effects::new())
+                        let mut effects = utils::announce_milestone(
+                            effect_builder,
+                            NodeMilestone::StoppedValidating,
+                        );
+                        effects.extend(utils::announce_milestone(
+                            effect_builder,
+                            NodeMilestone::SyncStarted,
+                        ));
+                        (Duration::ZERO, effects)
                     }
                     Err(msg) => (Duration::ZERO, fatal!(effect_builder, "{}", msg).ignore()),
                 },
@@ -204,11 +257,18 @@ impl MainReactor {
                     Ok(_) => {
                         info!("Validate: switch to KeepUp");
                         self.state = ReactorState::KeepUp;
-                        (Duration::ZERO, Effects::new())
+                        (
+                            Duration::ZERO,
+                            utils::announce_milestone(
+                                effect_builder,
+                                NodeMilestone::StoppedValidating,
+                            ),
+                        )
                     }
                     Err(msg) => (Duration::ZERO, fatal!(effect_builder, "{}", msg).ignore()),
                 },
             },
+            ReactorState::Archive => (self.control_logic_default_delay.into(), Effects::new()),
             ReactorState::ShutdownForUpgrade => {
                 match self.upgrade_shutdown_instruction(effect_builder) {
                     UpgradeShutdownInstruction::Fatal(msg) => (
@@ -429,7 +489,10 @@ impl MainReactor {
             chainspec.protocol_config.activation_point.era_id(),
             chainspec_raw,
         ) {
-            Ok(cfg) => match self.contract_runtime.commit_upgrade(cfg) {
+            Ok(cfg) => match self
+                .contract_runtime
+                .commit_upgrade(cfg, chainspec.protocol_config.compact_auction_state)
+            {
                 Ok(success) => {
                     let post_state_hash = success.post_state_hash;
                     info!(
@@ -5,9 +5,10 @@ use casper_types::{bytesrepr, crypto::ErrorExt as CryptoError};
 
 use crate::{
     components::{
-        contract_runtime, contract_runtime::BlockExecutionError, diagnostics_port, network,
-        storage, upgrade_watcher,
+        contract_runtime, contract_runtime::BlockExecutionError, diagnostics_port, metrics,
+        network, storage, upgrade_watcher,
     },
+    reactor::main_reactor::self_test::SelfTestError,
     utils::{ListeningError, LoadError},
 };
 
@@ -22,6 +23,10 @@ pub(crate) enum Error {
     #[error("prometheus (metrics) error: {0}")]
     Metrics(#[from] prometheus::Error),
 
+    /// Invalid OTLP metrics exporter configuration.
+    #[error("OTLP metrics exporter error: {0}")]
+    OtlpExporter(#[from] metrics::OtlpConfigError),
+
     /// `Network` component error.
     #[error("network error: {0}")]
     Network(#[from] network::Error),
@@ -61,6 +66,10 @@ pub(crate) enum Error {
     /// Error while loading the signing key pair.
     #[error("signing key pair load error: {0}")]
     LoadSigningKeyPair(#[from] LoadError<CryptoError>),
+
+    /// The startup self-test failed.
+    #[error("startup self-test failed: {0}")]
+    SelfTest(#[from] SelfTestError),
 }
 
 impl From<bytesrepr::Error> for Error {
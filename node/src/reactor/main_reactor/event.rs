@@ -24,7 +24,8 @@ use crate::{
             ControlAnnouncement, DeployAcceptorAnnouncement, DeployBufferAnnouncement,
             FatalAnnouncement, FetchedNewBlockAnnouncement,
             FetchedNewFinalitySignatureAnnouncement, GossiperAnnouncement, MetaBlockAnnouncement,
-            PeerBehaviorAnnouncement, UnexecutedBlockAnnouncement, UpgradeWatcherAnnouncement,
+            PeerBehaviorAnnouncement, UnexecutedBlockAnnouncement, UpcomingValidatorsAnnouncement,
+            UpgradeWatcherAnnouncement,
         },
         diagnostics_port::DumpConsensusStateRequest,
         incoming::{
@@ -39,7 +40,7 @@ use crate::{
             MakeBlockExecutableRequest, MarkBlockCompletedRequest, MetricsRequest,
             NetworkInfoRequest, NetworkRequest, ReactorStatusRequest, RestRequest, RpcRequest,
             SetNodeStopRequest, StorageRequest, SyncGlobalStateRequest, TrieAccumulatorRequest,
-            UpgradeWatcherRequest,
+            UpgradeWatcherRequest, WatchDeployRequest,
         },
     },
     protocol::Message,
@@ -68,6 +69,11 @@ pub(crate) enum MainEvent {
     /// Check the status of the reactor, should only be raised by the reactor itself
     ReactorCrank,
 
+    /// An event held back by the event filter of a test, to be dispatched without being filtered
+    /// again.
+    #[cfg(test)]
+    Delayed(Box<MainEvent>),
+
     #[from]
     UpgradeWatcher(#[serde(skip_serializing)] upgrade_watcher::Event),
     #[from]
@@ -99,6 +105,8 @@ pub(crate) enum MainEvent {
     #[from]
     NetworkPeerBehaviorAnnouncement(PeerBehaviorAnnouncement),
     #[from]
+    UpcomingValidatorsAnnouncement(UpcomingValidatorsAnnouncement),
+    #[from]
     NetworkPeerRequestingData(NetRequestIncoming),
     #[from]
     NetworkPeerProvidingData(NetResponseIncoming),
@@ -249,6 +257,22 @@ pub(crate) enum MainEvent {
     GotBlockAfterUpgradeEraValidators(EraId, EraValidators, EraValidators),
 }
 
+impl MainEvent {
+    /// Returns `true` if the event is a message by which a peer has the node take part in
+    /// consensus or gossiping of deploys, blocks or finality signatures.
+    pub(super) fn is_participation_message(&self) -> bool {
+        matches!(
+            self,
+            MainEvent::ConsensusMessageIncoming(_)
+                | MainEvent::ConsensusDemand(_)
+                | MainEvent::DeployGossiperIncoming(_)
+                | MainEvent::BlockGossiperIncoming(_)
+                | MainEvent::FinalitySignatureGossiperIncoming(_)
+                | MainEvent::FinalitySignatureIncoming(_)
+        )
+    }
+}
+
 impl ReactorEvent for MainEvent {
     fn is_control(&self) -> bool {
         matches!(self, MainEvent::ControlAnnouncement(_))
@@ -266,6 +290,8 @@ impl ReactorEvent for MainEvent {
     fn description(&self) -> &'static str {
         match self {
             MainEvent::ReactorCrank => "ReactorCrank",
+            #[cfg(test)]
+            MainEvent::Delayed(_) => "Delayed",
             MainEvent::Network(_) => "Network",
             MainEvent::SyncLeaper(_) => "SyncLeaper",
             MainEvent::DeployBuffer(_) => "DeployBuffer",
@@ -323,6 +349,7 @@ impl ReactorEvent for MainEvent {
             MainEvent::AddressGossiperAnnouncement(_) => "AddressGossiperAnnouncement",
             MainEvent::UpgradeWatcherAnnouncement(_) => "UpgradeWatcherAnnouncement",
             MainEvent::NetworkPeerBehaviorAnnouncement(_) => "BlocklistAnnouncement",
+            MainEvent::UpcomingValidatorsAnnouncement(_) => "UpcomingValidatorsAnnouncement",
             MainEvent::DeployBufferAnnouncement(_) => "DeployBufferAnnouncement",
             MainEvent::FinalitySignatureFetcherAnnouncement(_) => {
                 "FinalitySignatureFetcherAnnouncement"
@@ -370,6 +397,8 @@ impl Display for MainEvent {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             MainEvent::ReactorCrank => write!(f, "reactor crank"),
+            #[cfg(test)]
+            MainEvent::Delayed(event) => write!(f, "delayed: {}", event),
             MainEvent::Storage(event) => write!(f, "storage: {}", event),
             MainEvent::Network(event) => write!(f, "network: {}", event),
             MainEvent::SyncLeaper(event) => write!(f, "sync leaper: {}", event),
@@ -513,6 +542,9 @@ impl Display for MainEvent {
             MainEvent::NetworkPeerBehaviorAnnouncement(ann) => {
                 write!(f, "blocklist announcement: {}", ann)
             }
+            MainEvent::UpcomingValidatorsAnnouncement(ann) => {
+                write!(f, "upcoming validators announcement: {}", ann)
+            }
             MainEvent::FinalitySignatureFetcherAnnouncement(ann) => {
                 write!(f, "finality signature fetcher announcement: {}", ann)
             }
@@ -592,6 +624,12 @@ impl From<RestRequest> for MainEvent {
     }
 }
 
+impl From<WatchDeployRequest> for MainEvent {
+    fn from(request: WatchDeployRequest) -> Self {
+        MainEvent::EventStreamServer(event_stream_server::Event::WatchDeploy(request))
+    }
+}
+
 impl From<NetworkRequest<consensus::ConsensusMessage>> for MainEvent {
     fn from(request: NetworkRequest<consensus::ConsensusMessage>) -> Self {
         MainEvent::NetworkRequest(request.map_payload(Message::from))
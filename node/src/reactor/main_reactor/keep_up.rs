@@ -252,6 +252,18 @@ impl MainReactor {
             }
             SyncInstruction::BlockSync { block_hash } => {
                 debug!("KeepUp: BlockSync: {:?}", block_hash);
+                if self.contract_runtime.is_exec_queue_full() {
+                    // execution is lagging behind syncing; hold off acquiring further blocks
+                    // until the backlog has been worked off rather than piling them up in memory.
+                    info!(
+                        queue_depth = self.contract_runtime.queue_depth(),
+                        "KeepUp: BlockSync: execution queue full; pausing forward sync"
+                    );
+                    return Some(KeepUpInstruction::CheckLater(
+                        "execution queue full".to_string(),
+                        self.control_logic_default_delay.into(),
+                    ));
+                }
                 if self
                     .block_synchronizer
                     .register_block_by_hash(block_hash, false)
@@ -13,6 +13,7 @@ use serde::{Deserialize, Serialize};
 ///     
 ///     Start --> Initialize
 ///     Initialize --> CatchUp
+///     Initialize -->|archive mode| Archive
 ///     CatchUp --> KeepUp
 ///     KeepUp --> CatchUp
 ///     KeepUp --> Validate
@@ -70,4 +71,6 @@ pub enum ReactorState {
     Validate,
     /// Node should be shut down for upgrade.
     ShutdownForUpgrade,
+    /// Node only serves historical reads from storage.
+    Archive,
 }
@@ -0,0 +1,62 @@
+//! Startup self-test of the node's Wasm execution, storage and networking stacks.
+//!
+//! Run on construction of the reactor if enabled in the config, so that a node whose environment
+//! is broken in one of these areas refuses to start with a targeted error, rather than failing in
+//! less obvious ways once it is running.
+
+use std::time::Instant;
+
+use thiserror::Error;
+use tracing::info;
+
+use casper_execution_engine::core::engine_state;
+use casper_types::ProtocolVersion;
+
+use crate::{
+    components::{
+        contract_runtime::ContractRuntime,
+        network::{self, Network},
+        storage::{FatalStorageError, Storage},
+    },
+    protocol::Message,
+    reactor::main_reactor::MainEvent,
+};
+
+/// An error in the startup self-test.
+#[derive(Debug, Error)]
+pub(crate) enum SelfTestError {
+    /// Executing the built-in Wasm module failed.
+    #[error("wasm execution: {0}")]
+    Wasm(engine_state::Error),
+    /// The storage round trip failed.
+    #[error("storage: {0}")]
+    Storage(FatalStorageError),
+    /// The loopback network connection failed.
+    #[error("networking: {0}")]
+    Network(network::Error),
+}
+
+/// Runs the self-test of each stack in turn, stopping at the first failure.
+pub(super) fn run(
+    protocol_version: ProtocolVersion,
+    contract_runtime: &ContractRuntime,
+    storage: &Storage,
+    network: &Network<MainEvent, Message>,
+) -> Result<(), SelfTestError> {
+    let start = Instant::now();
+
+    contract_runtime
+        .engine_state()
+        .run_wasm_self_test(protocol_version)
+        .map_err(SelfTestError::Wasm)?;
+    info!("self-test: wasm execution passed");
+
+    storage.run_self_test().map_err(SelfTestError::Storage)?;
+    info!("self-test: storage passed");
+
+    network.run_self_test().map_err(SelfTestError::Network)?;
+    info!("self-test: networking passed");
+
+    info!(elapsed = ?start.elapsed(), "self-test passed");
+    Ok(())
+}
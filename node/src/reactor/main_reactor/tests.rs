@@ -1,18 +1,42 @@
-use std::{collections::BTreeMap, iter, net::SocketAddr, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env, fs, io, iter,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    str,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
+};
 
 use either::Either;
+use futures::StreamExt;
+use lmdb::{Cursor, Environment, EnvironmentFlags, Transaction, WriteFlags};
 use num::Zero;
 use num_rational::Ratio;
-use rand::Rng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
 use tempfile::TempDir;
-use tokio::time::{self, error::Elapsed};
+use tokio::{
+    task::JoinHandle,
+    time::{self, error::Elapsed},
+};
 use tracing::{error, info};
 
-use casper_execution_engine::core::engine_state::GetBidsRequest;
+use casper_execution_engine::{
+    core::engine_state::GetBidsRequest,
+    shared::{additive_map::AdditiveMap, newtypes::CorrelationId, transform::Transform},
+};
 use casper_types::{
     system::auction::{Bids, DelegationRate},
     testing::TestRng,
-    EraId, Motes, ProtocolVersion, PublicKey, SecretKey, TimeDiff, Timestamp, U512,
+    CLValue, EraId, Key, Motes, ProtocolVersion, PublicKey, SecretKey, StoredValue, TimeDiff,
+    Timestamp, U512,
 };
 
 use crate::{
@@ -20,13 +44,15 @@ use crate::{
         consensus::{
             self, ClContext, ConsensusMessage, HighwayMessage, HighwayVertex, NewBlockPayload,
         },
+        contract_runtime::ExecutionPreState,
+        event_stream_server::{self, SseData},
         gossiper, network, storage,
         upgrade_watcher::NextUpgrade,
     },
     effect::{
         incoming::ConsensusMessageIncoming,
         requests::{ContractRuntimeRequest, NetworkRequest},
-        EffectExt,
+        EffectExt, Effects,
     },
     protocol::Message,
     reactor::{
@@ -60,12 +86,14 @@ impl Runner<ConditionCheckReactor<FilterReactor<MainReactor>>> {
     }
 }
 
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 enum InitialStakes {
     FromVec(Vec<u128>),
     Random { count: usize },
     AllEqual { count: usize, stake: u128 },
 }
 
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 struct ChainspecOverride {
     minimum_block_time: TimeDiff,
     minimum_era_height: u64,
@@ -80,6 +108,135 @@ impl Default for ChainspecOverride {
     }
 }
 
+/// A way of damaging the storage of a stopped node before restarting it.
+enum CorruptionKind {
+    /// Deletes all records of the named database except the first `retain` ones in key order.
+    Truncate {
+        db_name: &'static str,
+        retain: usize,
+    },
+    /// Flips every bit of the value stored under `key` in the named database and drops its last
+    /// byte, so that decoding the value fails.
+    Garble {
+        db_name: &'static str,
+        key: &'static [u8],
+    },
+}
+
+impl CorruptionKind {
+    fn db_name(&self) -> &'static str {
+        match self {
+            CorruptionKind::Truncate { db_name, .. } | CorruptionKind::Garble { db_name, .. } => {
+                db_name
+            }
+        }
+    }
+}
+
+/// The conditions applied to the messages sent from one node to another.
+#[derive(Clone, Copy, Default, Debug)]
+struct LinkConditions {
+    /// The minimum delay of each message.
+    latency: Duration,
+    /// The maximum random delay added to the latency of each message.
+    jitter: Duration,
+    /// The probability of a message being dropped, between 0 and 1.
+    loss: f64,
+}
+
+impl LinkConditions {
+    /// Conditions dropping all messages.
+    const DISCONNECTED: LinkConditions = LinkConditions {
+        latency: Duration::ZERO,
+        jitter: Duration::ZERO,
+        loss: 1.0,
+    };
+
+    fn is_perfect(&self) -> bool {
+        self.latency.is_zero() && self.jitter.is_zero() && self.loss == 0.0
+    }
+}
+
+/// The conditions of all links between the nodes of a `TestFixture`, identified by node index.
+///
+/// Links are directed: the conditions of messages from `a` to `b` are independent of those from
+/// `b` to `a`.
+#[derive(Default, Debug)]
+struct LinkConditioner {
+    /// The conditions of all links not explicitly configured.
+    default: LinkConditions,
+    links: HashMap<(usize, usize), LinkConditions>,
+}
+
+impl LinkConditioner {
+    /// Creates a conditioner applying `default` to all links.
+    fn new(default: LinkConditions) -> Self {
+        LinkConditioner {
+            default,
+            links: HashMap::new(),
+        }
+    }
+
+    /// Sets the conditions of messages sent from node `from` to node `to`.
+    fn with_link(mut self, from: usize, to: usize, conditions: LinkConditions) -> Self {
+        self.links.insert((from, to), conditions);
+        self
+    }
+
+    /// Sets the conditions of messages sent between nodes `a` and `b`, in both directions.
+    fn with_symmetric_link(self, a: usize, b: usize, conditions: LinkConditions) -> Self {
+        self.with_link(a, b, conditions).with_link(b, a, conditions)
+    }
+
+    fn conditions(&self, from: usize, to: usize) -> LinkConditions {
+        self.links.get(&(from, to)).copied().unwrap_or(self.default)
+    }
+}
+
+/// The events emitted on the main event stream of a node, collected in the background.
+struct SseEvents {
+    received: Arc<Mutex<Vec<SseData>>>,
+    collector: JoinHandle<()>,
+}
+
+impl SseEvents {
+    /// Returns all events received so far, in the order they were emitted.
+    fn received(&self) -> Vec<SseData> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Waits until an event matching `predicate` has been received, and returns it.
+    ///
+    /// Panics if no such event is received within `within`.
+    async fn expect_event<F>(&self, description: &str, predicate: F, within: Duration) -> SseData
+    where
+        F: Fn(&SseData) -> bool,
+    {
+        let find_event = async {
+            loop {
+                if let Some(event) = self.received().into_iter().find(&predicate) {
+                    return event;
+                }
+                time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        time::timeout(within, find_event).await.unwrap_or_else(|_| {
+            panic!(
+                "should receive {} event within {} seconds, received {:?}",
+                description,
+                within.as_secs_f64(),
+                self.received()
+            )
+        })
+    }
+}
+
+impl Drop for SseEvents {
+    fn drop(&mut self) {
+        self.collector.abort();
+    }
+}
+
 struct NodeContext {
     id: NodeId,
     secret_key: Arc<SecretKey>,
@@ -87,12 +244,57 @@ struct NodeContext {
     storage_dir: TempDir,
 }
 
+/// The configuration a `TestFixture` was set up with.
+///
+/// It is written to a file under the target directory when a test using the fixture fails, so that
+/// the test can be rerun with exactly the same configuration via `TestFixture::from_recorded`.
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct FixtureRecording {
+    /// The hex-encoded seed of the fixture's `TestRng`.
+    seed: String,
+    /// The stakes of the nodes, or `None` if the fixture was set up from explicitly given keys.
+    initial_stakes: Option<InitialStakes>,
+    /// The overrides applied to the `local` chainspec.
+    spec_override: ChainspecOverride,
+}
+
+impl FixtureRecording {
+    /// Returns the path the recording of the test running on the current thread is written to.
+    fn path_for_current_test() -> PathBuf {
+        let test_name = thread::current()
+            .name()
+            .unwrap_or("unnamed")
+            .replace("::", "-");
+        let target_dir = env::var_os("CARGO_TARGET_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../target"));
+        target_dir
+            .join("reactor-test-recordings")
+            .join(format!("{}.json", test_name))
+    }
+
+    fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)
+    }
+
+    fn read(path: &Path) -> Self {
+        let contents = fs::read(path)
+            .unwrap_or_else(|error| panic!("could not read {}: {}", path.display(), error));
+        serde_json::from_slice(&contents)
+            .unwrap_or_else(|error| panic!("could not parse {}: {}", path.display(), error))
+    }
+}
+
 struct TestFixture {
     rng: TestRng,
     node_contexts: Vec<NodeContext>,
     network: TestingNetwork<FilterReactor<MainReactor>>,
     chainspec: Arc<Chainspec>,
     chainspec_raw_bytes: Arc<ChainspecRawBytes>,
+    recording: FixtureRecording,
 }
 
 impl TestFixture {
@@ -104,7 +306,41 @@ impl TestFixture {
     /// Runs the network until all nodes are initialized (i.e. none of their reactor states are
     /// still `ReactorState::Initialize`).
     async fn new(initial_stakes: InitialStakes, spec_override: Option<ChainspecOverride>) -> Self {
-        let mut rng = TestRng::new();
+        Self::new_with_rng(TestRng::new(), initial_stakes, spec_override).await
+    }
+
+    /// Sets up a new fixture with the configuration recorded in `seed_file` when a test failed.
+    ///
+    /// Panics if the recorded fixture was set up from explicitly given keys, in which case the
+    /// test itself should be rerun with the recorded seed set as `CL_TEST_SEED`.
+    async fn from_recorded<P: AsRef<Path>>(seed_file: P) -> Self {
+        let seed_file = seed_file.as_ref();
+        let recording = FixtureRecording::read(seed_file);
+        let initial_stakes = recording.initial_stakes.unwrap_or_else(|| {
+            panic!(
+                "{} was recorded from a fixture with explicitly given keys, rerun the test with \
+                CL_TEST_SEED={} instead",
+                seed_file.display(),
+                recording.seed
+            )
+        });
+        let mut seed = <TestRng as SeedableRng>::Seed::default();
+        base16::decode_slice(&recording.seed, &mut seed)
+            .unwrap_or_else(|error| panic!("invalid seed in {}: {}", seed_file.display(), error));
+        Self::new_with_rng(
+            TestRng::from_seed(seed),
+            initial_stakes,
+            Some(recording.spec_override),
+        )
+        .await
+    }
+
+    async fn new_with_rng(
+        mut rng: TestRng,
+        initial_stakes: InitialStakes,
+        spec_override: Option<ChainspecOverride>,
+    ) -> Self {
+        let recorded_stakes = initial_stakes.clone();
         let stake_values = match initial_stakes {
             InitialStakes::FromVec(stakes) => {
                 stakes.into_iter().map(|stake| stake.into()).collect()
@@ -129,14 +365,31 @@ impl TestFixture {
             .zip(stake_values)
             .map(|(secret_key, stake)| (PublicKey::from(secret_key.as_ref()), stake))
             .collect();
-        Self::new_with_keys(rng, secret_keys, stakes, spec_override).await
+        Self::set_up(
+            rng,
+            secret_keys,
+            stakes,
+            spec_override,
+            Some(recorded_stakes),
+        )
+        .await
     }
 
     async fn new_with_keys(
+        rng: TestRng,
+        secret_keys: Vec<Arc<SecretKey>>,
+        stakes: BTreeMap<PublicKey, U512>,
+        spec_override: Option<ChainspecOverride>,
+    ) -> Self {
+        Self::set_up(rng, secret_keys, stakes, spec_override, None).await
+    }
+
+    async fn set_up(
         mut rng: TestRng,
         secret_keys: Vec<Arc<SecretKey>>,
         stakes: BTreeMap<PublicKey, U512>,
         spec_override: Option<ChainspecOverride>,
+        initial_stakes: Option<InitialStakes>,
     ) -> Self {
         testing::init_logging();
 
@@ -180,12 +433,18 @@ impl TestFixture {
         chainspec.highway_config.maximum_round_length =
             chainspec.core_config.minimum_block_time * 2;
 
+        let recording = FixtureRecording {
+            seed: base16::encode_lower(&rng.seed()),
+            initial_stakes,
+            spec_override,
+        };
         let mut fixture = TestFixture {
             rng,
             node_contexts: vec![],
             network: TestingNetwork::new(),
             chainspec: Arc::new(chainspec),
             chainspec_raw_bytes: Arc::new(chainspec_raw_bytes),
+            recording,
         };
 
         for secret_key in secret_keys {
@@ -298,6 +557,11 @@ impl TestFixture {
         id
     }
 
+    /// Cranks the nodes from then on in an order determined by the fixture's seed.
+    fn crank_deterministically(&mut self) {
+        self.network.crank_deterministically(&mut self.rng);
+    }
+
     fn remove_and_stop_node(&mut self, index: usize) -> NodeContext {
         let node_context = self.node_contexts.remove(index);
         let runner = self.network.remove_node(&node_context.id).unwrap();
@@ -306,6 +570,187 @@ impl TestFixture {
         node_context
     }
 
+    /// Stops the node at `index`, corrupts its storage as per `corruption` and restarts it.
+    ///
+    /// The restarted node is appended to the end of `node_contexts`, and its new ID is returned.
+    async fn corrupt_storage(&mut self, index: usize, corruption: CorruptionKind) -> NodeId {
+        let node_context = self.remove_and_stop_node(index);
+        let db_path = storage::storage_dir(
+            &node_context.config.storage.path,
+            &self.chainspec.network_config.name,
+            self.chainspec.protocol_version(),
+        )
+        .join(storage::STORAGE_DB_FILENAME);
+
+        {
+            let env = Environment::new()
+                .set_flags(EnvironmentFlags::NO_SUB_DIR)
+                .set_max_dbs(storage::MAX_DB_COUNT)
+                .open(&db_path)
+                .expect("should open storage of stopped node");
+            let db = env
+                .open_db(Some(corruption.db_name()))
+                .expect("should open database to corrupt");
+            let mut txn = env.begin_rw_txn().expect("should begin transaction");
+            let records: Vec<(Vec<u8>, Vec<u8>)> = txn
+                .open_ro_cursor(db)
+                .expect("should open cursor")
+                .iter()
+                .map(|row| {
+                    let (key, value) = row.expect("should read record");
+                    (key.to_vec(), value.to_vec())
+                })
+                .collect();
+            match corruption {
+                CorruptionKind::Truncate { retain, .. } => {
+                    for (key, _) in records.iter().skip(retain) {
+                        txn.del(db, key, None).expect("should delete record");
+                    }
+                }
+                CorruptionKind::Garble { key, .. } => {
+                    let (_, mut value) = records
+                        .into_iter()
+                        .find(|(record_key, _)| record_key.as_slice() == key)
+                        .expect("should have record to garble");
+                    value.pop();
+                    value.iter_mut().for_each(|byte| *byte = !*byte);
+                    txn.put(db, &key, &value, WriteFlags::empty())
+                        .expect("should overwrite record");
+                }
+            }
+            txn.commit().expect("should commit corruption");
+        }
+        info!(
+            "corrupted {} database of node {} with id {}",
+            corruption.db_name(),
+            index,
+            node_context.id
+        );
+
+        self.add_node(
+            node_context.secret_key,
+            node_context.config,
+            node_context.storage_dir,
+        )
+        .await
+    }
+
+    /// Applies the link conditions to all messages received by the nodes from then on.
+    ///
+    /// Delayed messages from the same peer can be reordered if the link has jitter.  Replaces any
+    /// event filters previously set on the nodes.
+    fn condition_links(&mut self, conditioner: &LinkConditioner) {
+        for (to, node_context) in self.node_contexts.iter().enumerate() {
+            let incoming_links: HashMap<NodeId, LinkConditions> = self
+                .node_contexts
+                .iter()
+                .enumerate()
+                .filter(|(from, _)| *from != to)
+                .map(|(from, peer_context)| (peer_context.id, conditioner.conditions(from, to)))
+                .filter(|(_, conditions)| !conditions.is_perfect())
+                .collect();
+            let mut rng = ChaCha8Rng::seed_from_u64(self.rng.gen());
+            let runner = self.network.nodes_mut().get_mut(&node_context.id).unwrap();
+            runner.reactor_mut().inner_mut().set_filter(move |event| {
+                let peer_id = match &event {
+                    MainEvent::Network(network::Event::IncomingMessage { peer_id, .. }) => peer_id,
+                    _ => return Either::Right(event),
+                };
+                let conditions = match incoming_links.get(peer_id) {
+                    Some(conditions) => conditions,
+                    None => return Either::Right(event),
+                };
+                if rng.gen_bool(conditions.loss) {
+                    return Either::Left(Effects::new());
+                }
+                let delay = conditions.latency + rng.gen_range(Duration::ZERO..=conditions.jitter);
+                if delay.is_zero() {
+                    return Either::Right(event);
+                }
+                // Once delayed, the message is marked so as to pass through the filter unchanged.
+                Either::Left(time::sleep(delay).event(move |_| MainEvent::Delayed(Box::new(event))))
+            });
+        }
+        info!("conditioning network links: {:?}", conditioner);
+    }
+
+    /// Partitions the network into the given groups of node indices, by dropping all messages
+    /// received by a node from nodes outside its group.
+    ///
+    /// Nodes not contained in any group are cut off from all other nodes.  Replaces any event
+    /// filters previously set on the nodes.
+    fn partition(&mut self, groups: Vec<Vec<usize>>) {
+        let node_count = self.node_contexts.len();
+        let group_of = |index: usize| groups.iter().position(|group| group.contains(&index));
+        let mut conditioner = LinkConditioner::default();
+        for from in 0..node_count {
+            for to in 0..node_count {
+                if from != to && (group_of(from).is_none() || group_of(from) != group_of(to)) {
+                    conditioner = conditioner.with_link(from, to, LinkConditions::DISCONNECTED);
+                }
+            }
+        }
+        self.condition_links(&conditioner);
+        info!("partitioned network into {:?}", groups);
+    }
+
+    /// Heals a partition created by `partition` and removes the conditions set by
+    /// `condition_links`, letting all nodes exchange messages unhindered again.
+    fn heal_partition(&mut self) {
+        for runner in self.network.runners_mut() {
+            runner.reactor_mut().inner_mut().set_filter(Either::Right);
+        }
+        info!("healed network partition");
+    }
+
+    /// Subscribes to the main event stream of the node at the given index, collecting all events
+    /// it emits from then on.
+    ///
+    /// The subscription ends if the node is stopped.
+    async fn subscribe_to_events(&self, index: usize) -> SseEvents {
+        let node_id = self.node_contexts[index].id;
+        let address = self
+            .network
+            .nodes()
+            .get(&node_id)
+            .expect("should have node")
+            .main_reactor()
+            .event_stream_server()
+            .listening_address()
+            .expect("event stream server should be running");
+        let url = format!(
+            "http://{}/{}/{}",
+            address,
+            event_stream_server::SSE_API_ROOT_PATH,
+            event_stream_server::SSE_API_MAIN_PATH
+        );
+        let response = reqwest::get(&url)
+            .await
+            .unwrap_or_else(|error| panic!("should subscribe to {}: {}", url, error));
+
+        let received = Arc::new(Mutex::new(vec![]));
+        let received_clone = Arc::clone(&received);
+        let collector = tokio::spawn(async move {
+            // Events can be split across chunks, so only complete lines are parsed.
+            let mut pending = String::new();
+            let mut stream = response.bytes_stream();
+            while let Some(Ok(bytes)) = stream.next().await {
+                pending.push_str(str::from_utf8(bytes.as_ref()).expect("should be utf-8"));
+                while let Some(line_end) = pending.find('\n') {
+                    let line: String = pending.drain(..=line_end).collect();
+                    if let Some(data) = line.trim_end().strip_prefix("data:") {
+                        let event = serde_json::from_str(data).expect("should parse SSE data");
+                        received_clone.lock().unwrap().push(event);
+                    }
+                }
+            }
+        });
+        SseEvents {
+            received,
+            collector,
+        }
+    }
+
     /// Runs the network until `condition` is true.
     ///
     /// Returns an error if the condition isn't met in time.
@@ -438,6 +883,27 @@ impl TestFixture {
     }
 }
 
+impl Drop for TestFixture {
+    fn drop(&mut self) {
+        if !thread::panicking() {
+            return;
+        }
+        let path = FixtureRecording::path_for_current_test();
+        // The seed itself is already printed by the `TestRng`.
+        match self.recording.write(&path) {
+            Ok(()) => info!(
+                "recorded test fixture to {}, rerun via `TestFixture::from_recorded`",
+                path.display()
+            ),
+            Err(error) => error!(
+                "failed to record test fixture to {}: {}",
+                path.display(),
+                error
+            ),
+        }
+    }
+}
+
 /// Given a block height and a node id, returns a predicate to check if the lowest available block
 /// for the specified node is at or below the specified height.
 fn node_has_lowest_available_block_at_or_below_height(
@@ -679,6 +1145,91 @@ async fn should_not_historical_sync_no_sync_node() {
         .unwrap_err();
 }
 
+#[tokio::test]
+async fn archive_node_should_not_sync() {
+    let initial_stakes = InitialStakes::Random { count: 5 };
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+
+    // Wait for all nodes to complete block 1.
+    fixture.run_until_block_height(1, ONE_MIN).await;
+
+    // Create an archive node.
+    let secret_key = SecretKey::random(&mut fixture.rng);
+    let (mut config, storage_dir) = fixture.create_node_config(&secret_key, None);
+    config.node.archive_mode = true;
+    let archive_id = fixture
+        .add_node(Arc::new(secret_key), config, storage_dir)
+        .await;
+
+    let archive_runner = |nodes: &Nodes| {
+        nodes
+            .get(&archive_id)
+            .expect("should have archive node")
+            .main_reactor()
+    };
+
+    fixture
+        .try_run_until(
+            |nodes: &Nodes| archive_runner(nodes).state == ReactorState::Archive,
+            ONE_MIN,
+        )
+        .await
+        .expect("timed out waiting for archive node to switch to archive mode");
+
+    // The rest of the network keeps making progress, while the archive node neither syncs nor
+    // leaves archive mode.
+    let target_height = fixture.highest_complete_block().height() + 2;
+    fixture
+        .try_run_until(
+            |nodes: &Nodes| {
+                nodes.values().any(|runner| {
+                    runner
+                        .main_reactor()
+                        .storage()
+                        .read_highest_complete_block()
+                        .expect("should not error reading db")
+                        .map_or(false, |block| block.height() >= target_height)
+                })
+            },
+            ONE_MIN,
+        )
+        .await
+        .unwrap_or_else(|_| panic!("should reach block {} within a minute", target_height));
+    let archive_reactor = archive_runner(fixture.network.nodes());
+    assert_eq!(archive_reactor.state, ReactorState::Archive);
+    assert!(archive_reactor
+        .storage()
+        .read_highest_complete_block()
+        .expect("should read from storage")
+        .is_none());
+}
+
+#[tokio::test]
+async fn node_should_join_after_passing_self_test() {
+    let initial_stakes = InitialStakes::Random { count: 5 };
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+
+    // Wait for all nodes to complete block 1.
+    fixture.run_until_block_height(1, ONE_MIN).await;
+
+    // Create a node running the startup self-test; adding it fails if the self-test does.
+    let trusted_hash = *fixture.highest_complete_block().hash();
+    let secret_key = SecretKey::random(&mut fixture.rng);
+    let (mut config, storage_dir) = fixture.create_node_config(&secret_key, Some(trusted_hash));
+    config.node.run_self_test = true;
+    let joiner_id = fixture
+        .add_node(Arc::new(secret_key), config, storage_dir)
+        .await;
+
+    // The node then syncs like any other.
+    fixture
+        .run_until(
+            node_has_lowest_available_block_at_or_below_height(1, joiner_id),
+            ONE_MIN,
+        )
+        .await;
+}
+
 #[tokio::test]
 async fn run_equivocator_network() {
     let mut rng = crate::new_rng();
@@ -710,6 +1261,8 @@ async fn run_equivocator_network() {
 
     let mut fixture =
         TestFixture::new_with_keys(rng, secret_keys, stakes.clone(), Some(spec_override)).await;
+    // Whether Alice manages to equivocate depends on the interleaving of the nodes' events.
+    fixture.crank_deterministically();
 
     let min_round_len = fixture.chainspec.core_config.minimum_block_time;
     let mut maybe_first_message_time = None;
@@ -922,6 +1475,157 @@ async fn dont_upgrade_without_switch_block() {
     }
 }
 
+#[tokio::test]
+async fn should_not_execute_block_against_wrong_pre_state() {
+    // Bob's dominant stake lets the network make progress even if Alice stops executing blocks.
+    let initial_stakes = InitialStakes::FromVec(vec![255, u128::MAX]);
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+    fixture.run_until_consensus_in_era(ERA_ONE, ONE_MIN).await;
+
+    let alice_id = fixture.node_contexts[0].id;
+    let bob_id = fixture.node_contexts[1].id;
+    let target_height = fixture.highest_complete_block().height() + 2;
+
+    // We artificially delay Alice's execution of the block at the target height, so that her
+    // execution pre-state can be tampered with after executing its parent.
+    let delayed = Arc::new(AtomicBool::new(false));
+    let released = Arc::new(AtomicBool::new(false));
+    {
+        let delayed = Arc::clone(&delayed);
+        let released = Arc::clone(&released);
+        let runner = fixture.network.nodes_mut().get_mut(&alice_id).unwrap();
+        runner.reactor_mut().inner_mut().set_filter(move |event| {
+            if let MainEvent::ContractRuntimeRequest(
+                ContractRuntimeRequest::EnqueueBlockForExecution {
+                    finalized_block, ..
+                },
+            ) = &event
+            {
+                if finalized_block.height() == target_height && !delayed.load(Ordering::SeqCst) {
+                    info!("delaying {}", finalized_block);
+                    delayed.store(true, Ordering::SeqCst);
+                    let released = Arc::clone(&released);
+                    return Either::Left(time::sleep(Duration::from_secs(10)).event(move |_| {
+                        released.store(true, Ordering::SeqCst);
+                        event
+                    }));
+                }
+            }
+            Either::Right(event)
+        });
+    }
+
+    // Run until Alice has executed the parent of the delayed block.
+    {
+        let delayed = Arc::clone(&delayed);
+        fixture
+            .run_until(
+                move |nodes: &Nodes| {
+                    let main_reactor = nodes.get(&alice_id).unwrap().main_reactor();
+                    let maybe_parent = main_reactor
+                        .storage()
+                        .read_block_by_height(target_height - 1)
+                        .unwrap();
+                    delayed.load(Ordering::SeqCst)
+                        && maybe_parent
+                            .map(|parent| ExecutionPreState::from_block_header(parent.header()))
+                            == Some(main_reactor.contract_runtime().execution_pre_state())
+                },
+                ONE_MIN,
+            )
+            .await;
+    }
+
+    // Point Alice's pre-state to a global state which exists, but isn't the parent's post-state.
+    let runner = fixture.network.nodes_mut().get_mut(&alice_id).unwrap();
+    let refused_block_count = runner
+        .main_reactor()
+        .contract_runtime()
+        .refused_block_count();
+    let parent_header = runner
+        .main_reactor()
+        .storage()
+        .read_block_by_height(target_height - 1)
+        .unwrap()
+        .unwrap()
+        .take_header();
+    let mut effects = AdditiveMap::new();
+    effects.insert(
+        Key::Hash([u8::MAX; 32]),
+        Transform::Write(StoredValue::CLValue(CLValue::from_t(1u64).unwrap())),
+    );
+    let wrong_state_root_hash = runner
+        .main_reactor()
+        .contract_runtime()
+        .engine_state()
+        .apply_effect(
+            CorrelationId::new(),
+            *parent_header.state_root_hash(),
+            effects,
+        )
+        .unwrap();
+    runner
+        .reactor_mut()
+        .inner_mut()
+        .inner_mut()
+        .contract_runtime_mut()
+        .set_initial_state(ExecutionPreState::new(
+            target_height,
+            wrong_state_root_hash,
+            parent_header.block_hash(),
+            parent_header.accumulated_seed(),
+        ));
+
+    // Run until Bob has moved past the delayed block after its release.
+    fixture
+        .run_until(
+            move |nodes: &Nodes| {
+                released.load(Ordering::SeqCst)
+                    && nodes
+                        .get(&bob_id)
+                        .unwrap()
+                        .main_reactor()
+                        .storage()
+                        .read_block_by_height(target_height + 2)
+                        .unwrap()
+                        .is_some()
+            },
+            ONE_MIN,
+        )
+        .await;
+
+    // Alice must have refused to execute the block, rather than produce one diverging from Bob's.
+    assert!(fixture
+        .network
+        .nodes()
+        .get(&bob_id)
+        .unwrap()
+        .main_reactor()
+        .storage()
+        .read_block_by_height(target_height)
+        .unwrap()
+        .is_some());
+    let alice = fixture
+        .network
+        .nodes()
+        .get(&alice_id)
+        .unwrap()
+        .main_reactor();
+    assert!(
+        alice.contract_runtime().refused_block_count() > refused_block_count,
+        "Alice should have refused to execute the block"
+    );
+    assert!(
+        alice
+            .storage()
+            .read_block_by_height(target_height)
+            .unwrap()
+            .is_none(),
+        "Alice should not have stored a block at height {}",
+        target_height
+    );
+}
+
 #[tokio::test]
 async fn should_store_finalized_approvals() {
     // Set up a network with two nodes where node 0 (Alice) is effectively guaranteed to be the
@@ -1171,3 +1875,161 @@ async fn network_should_recover_from_stall() {
     // Ensure all nodes progress until block 3 is marked complete.
     fixture.run_until_block_height(3, TEN_SECS).await;
 }
+
+async fn assert_node_recovers_from_corrupted_storage(corruption: CorruptionKind) {
+    // Set up a network with three nodes.
+    let initial_stakes = InitialStakes::AllEqual {
+        count: 3,
+        stake: 100,
+    };
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+
+    // Let all nodes progress until block 2 is marked complete.
+    fixture.run_until_block_height(2, ONE_MIN).await;
+
+    // Restart node 0 with corrupted storage.
+    fixture.corrupt_storage(0, corruption).await;
+
+    // Ensure all nodes, including the restarted one, progress until block 4 is marked complete.
+    fixture.run_until_block_height(4, ONE_MIN).await;
+}
+
+#[tokio::test]
+async fn node_should_recover_from_truncated_state_store() {
+    // Losing the record of completed blocks should only cause the node to sync them again.
+    assert_node_recovers_from_corrupted_storage(CorruptionKind::Truncate {
+        db_name: "state_store",
+        retain: 0,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn node_should_recover_from_garbled_completed_blocks_index() {
+    // An undecodable record of completed blocks should be discarded, as if it were lost.
+    assert_node_recovers_from_corrupted_storage(CorruptionKind::Garble {
+        db_name: "state_store",
+        key: storage::COMPLETED_BLOCKS_STORAGE_KEY,
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn network_should_recover_from_partition() {
+    // Set up a network with four nodes.
+    let initial_stakes = InitialStakes::AllEqual {
+        count: 4,
+        stake: 100,
+    };
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+
+    // Let all nodes progress until block 2 is marked complete.
+    fixture.run_until_block_height(2, ONE_MIN).await;
+
+    // Split the network in two halves, neither of which has enough weight to finalize blocks.
+    fixture.partition(vec![vec![0, 1], vec![2, 3]]);
+    fixture
+        .try_run_until_block_height(3, TEN_SECS)
+        .await
+        .expect_err("should time out");
+
+    // Ensure all nodes progress until block 3 is marked complete once the partition is healed.
+    fixture.heal_partition();
+    fixture.run_until_block_height(3, ONE_MIN).await;
+}
+
+#[tokio::test]
+async fn should_emit_block_added_events() {
+    let initial_stakes = InitialStakes::AllEqual {
+        count: 2,
+        stake: 100,
+    };
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+    let events = fixture.subscribe_to_events(0).await;
+
+    fixture.run_until_block_height(2, ONE_MIN).await;
+    let block = fixture.highest_complete_block();
+
+    let event = events
+        .expect_event(
+            "BlockAdded",
+            |event| match event {
+                SseData::BlockAdded { block_hash, .. } => block_hash == block.hash(),
+                _ => false,
+            },
+            TEN_SECS,
+        )
+        .await;
+    match event {
+        SseData::BlockAdded {
+            block: json_block, ..
+        } => assert_eq!(json_block.header.height, block.height()),
+        _ => unreachable!(),
+    }
+    assert!(matches!(
+        events.received().first(),
+        Some(SseData::ApiVersion(_))
+    ));
+}
+
+#[tokio::test]
+async fn network_should_make_progress_over_slow_and_lossy_links() {
+    // Set up a network with four nodes.
+    let initial_stakes = InitialStakes::AllEqual {
+        count: 4,
+        stake: 100,
+    };
+    let mut fixture = TestFixture::new(initial_stakes, None).await;
+    fixture.run_until_block_height(1, ONE_MIN).await;
+
+    // Slow down all links and drop some messages, and connect node 3 over a particularly slow
+    // link to node 0.
+    let lossy = LinkConditions {
+        latency: Duration::from_millis(50),
+        jitter: Duration::from_millis(100),
+        loss: 0.02,
+    };
+    let slow = LinkConditions {
+        latency: Duration::from_millis(500),
+        jitter: Duration::from_millis(250),
+        loss: 0.0,
+    };
+    let conditioner = LinkConditioner::new(lossy).with_symmetric_link(0, 3, slow);
+    fixture.condition_links(&conditioner);
+
+    // Ensure all nodes still progress until block 4 is marked complete.
+    fixture.run_until_block_height(4, ONE_MIN * 2).await;
+}
+
+#[tokio::test]
+async fn should_rerun_recorded_fixture() {
+    let initial_stakes = InitialStakes::AllEqual {
+        count: 2,
+        stake: 100,
+    };
+    let spec_override = ChainspecOverride {
+        minimum_era_height: 3,
+        ..Default::default()
+    };
+    let fixture = TestFixture::new(initial_stakes.clone(), Some(spec_override.clone())).await;
+    let recording = &fixture.recording;
+    assert_eq!(recording.seed, base16::encode_lower(&fixture.rng.seed()));
+    assert_eq!(recording.initial_stakes, Some(initial_stakes));
+    assert_eq!(recording.spec_override, spec_override);
+
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("recording.json");
+    recording.write(&path).unwrap();
+    drop(fixture);
+
+    // Only one `TestRng` can be created per thread, so rerun the fixture on a fresh one.
+    thread::spawn(move || {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime.block_on(async {
+            let rerun_fixture = TestFixture::from_recorded(&path).await;
+            assert_eq!(rerun_fixture.recording, FixtureRecording::read(&path));
+        });
+    })
+    .join()
+    .expect("rerun fixture should match recording");
+}
@@ -3,7 +3,10 @@ use smallvec::smallvec;
 use tracing::info;
 
 use crate::{
-    components::InitializedComponent,
+    components::{
+        event_stream_server::{self, NodeMilestone},
+        InitializedComponent,
+    },
     effect::{EffectBuilder, EffectExt, Effects},
     fatal,
     reactor::main_reactor::MainEvent,
@@ -24,3 +27,13 @@ pub(super) fn initialize_component(
     }
     None
 }
+
+/// Returns an effect publishing `milestone` on the event stream.
+pub(super) fn announce_milestone(
+    effect_builder: EffectBuilder<MainEvent>,
+    milestone: NodeMilestone,
+) -> Effects<MainEvent> {
+    effect_builder.immediately().event(move |_| {
+        MainEvent::EventStreamServer(event_stream_server::Event::NodeMilestone(milestone))
+    })
+}
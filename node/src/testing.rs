@@ -6,6 +6,7 @@
 mod condition_check_reactor;
 mod fake_deploy_acceptor;
 pub(crate) mod filter_reactor;
+pub(crate) mod mock_contract_runtime;
 pub(crate) mod network;
 pub(crate) mod test_clock;
 
@@ -295,6 +296,10 @@ impl<REv: 'static> ComponentHarness<REv> {
                         panic!("currently no failpoint activations implemented in test harness")
                         // TODO: forward to component instead
                     },
+                    ControlAnnouncement::StartEventTrace { .. }
+                    | ControlAnnouncement::StopEventTrace { .. } => {
+                        panic!("event traces are not supported in the test harness")
+                    }
                 }
             }
 
@@ -0,0 +1,293 @@
+//! The `MockContractRuntime` answers `ContractRuntimeRequest`s with responses programmed by the
+//! test, so that components depending on the contract runtime can be tested without an engine
+//! state or global state on disk.
+//!
+//! Requests for data which has not been programmed are answered as if the data was missing from
+//! global state.  Requests which only make sense against a real global state, such as reading or
+//! writing tries, cause a panic.
+
+use std::collections::{HashMap, VecDeque};
+
+use casper_execution_engine::{
+    core::engine_state::{BalanceResult, GetEraValidatorsError, QueryResult},
+    storage::trie::merkle_proof::TrieMerkleProof,
+};
+use casper_hashing::Digest;
+use casper_types::{
+    system::auction::EraValidators, CLValue, ExecutionResult, Key, StoredValue, URef, U512,
+};
+
+use crate::{
+    effect::{requests::ContractRuntimeRequest, EffectExt, Effects},
+    types::FinalizedBlock,
+};
+
+/// A stand-in for the `ContractRuntime` component with programmable responses.
+#[derive(Debug, Default)]
+pub(crate) struct MockContractRuntime {
+    /// The values returned by queries, by queried key and path.
+    stored_values: HashMap<(Key, Vec<String>), StoredValue>,
+    /// The balances of purses.
+    balances: HashMap<URef, U512>,
+    /// The era validators, by state root hash.
+    era_validators: HashMap<Digest, EraValidators>,
+    /// The execution results checksums, by state root hash.
+    execution_results_checksums: HashMap<Digest, Digest>,
+    /// The result of every speculative execution.
+    speculative_execution_result: Option<ExecutionResult>,
+    /// The blocks enqueued for execution, in the order they were received.
+    enqueued_blocks: Vec<FinalizedBlock>,
+}
+
+impl MockContractRuntime {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the value returned by queries of `key` with the given `path`.
+    pub(crate) fn set_stored_value(&mut self, key: Key, path: Vec<String>, value: StoredValue) {
+        self.stored_values.insert((key, path), value);
+    }
+
+    /// Sets the balance of the given purse.
+    pub(crate) fn set_balance(&mut self, purse: URef, motes: U512) {
+        self.balances.insert(purse, motes);
+    }
+
+    /// Sets the era validators at the given state root hash.
+    pub(crate) fn set_era_validators(
+        &mut self,
+        state_root_hash: Digest,
+        era_validators: EraValidators,
+    ) {
+        self.era_validators.insert(state_root_hash, era_validators);
+    }
+
+    /// Sets the execution results checksum at the given state root hash.
+    pub(crate) fn set_execution_results_checksum(
+        &mut self,
+        state_root_hash: Digest,
+        checksum: Digest,
+    ) {
+        self.execution_results_checksums
+            .insert(state_root_hash, checksum);
+    }
+
+    /// Sets the result of every speculative execution.
+    pub(crate) fn set_speculative_execution_result(&mut self, execution_result: ExecutionResult) {
+        self.speculative_execution_result = Some(execution_result);
+    }
+
+    /// Returns the blocks enqueued for execution so far, in the order they were received.
+    ///
+    /// Enqueued blocks are never executed.
+    pub(crate) fn enqueued_blocks(&self) -> &[FinalizedBlock] {
+        &self.enqueued_blocks
+    }
+
+    /// Answers the request with the programmed response.
+    ///
+    /// Panics if the request is not supported.
+    pub(crate) fn handle_request<REv>(&mut self, request: ContractRuntimeRequest) -> Effects<REv> {
+        match request {
+            ContractRuntimeRequest::EnqueueBlockForExecution {
+                finalized_block, ..
+            } => {
+                self.enqueued_blocks.push(finalized_block);
+                Effects::new()
+            }
+            ContractRuntimeRequest::Query {
+                query_request,
+                responder,
+            } => {
+                let key = (query_request.key(), query_request.path().to_vec());
+                let query_result = match self.stored_values.get(&key) {
+                    Some(value) => QueryResult::Success {
+                        value: Box::new(value.clone()),
+                        proofs: vec![],
+                    },
+                    None => QueryResult::ValueNotFound(format!("no value under {:?}", key)),
+                };
+                responder.respond(Ok(query_result)).ignore()
+            }
+            ContractRuntimeRequest::GetBalance {
+                balance_request,
+                responder,
+            } => {
+                let purse = balance_request.purse_uref();
+                let balance_result = match self.balances.get(&purse) {
+                    Some(motes) => BalanceResult::Success {
+                        motes: *motes,
+                        proof: Box::new(TrieMerkleProof::new(
+                            purse.into(),
+                            StoredValue::CLValue(CLValue::from_t(*motes).unwrap()),
+                            VecDeque::new(),
+                        )),
+                    },
+                    None => BalanceResult::RootNotFound,
+                };
+                responder.respond(Ok(balance_result)).ignore()
+            }
+            ContractRuntimeRequest::GetEraValidators { request, responder } => {
+                let result = self
+                    .era_validators
+                    .get(&request.state_hash())
+                    .cloned()
+                    .ok_or(GetEraValidatorsError::RootNotFound);
+                responder.respond(result).ignore()
+            }
+            ContractRuntimeRequest::GetExecutionResultsChecksum {
+                state_root_hash,
+                responder,
+            } => {
+                let checksum = self
+                    .execution_results_checksums
+                    .get(&state_root_hash)
+                    .copied();
+                responder.respond(Ok(checksum)).ignore()
+            }
+            ContractRuntimeRequest::SpeculativeDeployExecution { responder, .. } => {
+                let result = self
+                    .speculative_execution_result
+                    .clone()
+                    .map(|execution_result| (execution_result, None));
+                responder.respond(Ok(result)).ignore()
+            }
+            request => panic!("mock contract runtime does not support {}", request),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use futures::channel::oneshot;
+    use rand::Rng;
+
+    use casper_execution_engine::core::engine_state::{BalanceRequest, QueryRequest};
+    use casper_types::{
+        testing::TestRng, AccessRights, EraId, ProtocolVersion, PublicKey, Timestamp,
+    };
+
+    use super::*;
+    use crate::{
+        components::contract_runtime::{EraValidatorsRequest, SpeculativeExecutionState},
+        effect::Responder,
+        types::{Deploy, MetaBlockState},
+    };
+
+    /// Handles the request created by `create_request` and returns the response.
+    async fn respond<T, F>(mock: &mut MockContractRuntime, create_request: F) -> T
+    where
+        T: Send + 'static,
+        F: FnOnce(Responder<T>) -> ContractRuntimeRequest,
+    {
+        let (sender, receiver) = oneshot::channel();
+        let request = create_request(Responder::without_shutdown(sender));
+        for effect in mock.handle_request::<()>(request) {
+            effect.await;
+        }
+        receiver.await.expect("should respond")
+    }
+
+    #[tokio::test]
+    async fn should_answer_with_programmed_responses() {
+        let mut rng = TestRng::new();
+        let mut mock = MockContractRuntime::new();
+        let state_root_hash = Digest::hash([1]);
+        let unknown_state_root_hash = Digest::hash([2]);
+
+        // Queries.
+        let key = Key::Hash([3; 32]);
+        let value = StoredValue::CLValue(CLValue::from_t(7_u64).unwrap());
+        mock.set_stored_value(key, vec![], value.clone());
+        let query_result = respond(&mut mock, |responder| ContractRuntimeRequest::Query {
+            query_request: QueryRequest::new(state_root_hash, key, vec![]),
+            responder,
+        })
+        .await;
+        assert!(matches!(
+            query_result,
+            Ok(QueryResult::Success { value: stored, .. }) if *stored == value
+        ));
+        let query_result = respond(&mut mock, |responder| ContractRuntimeRequest::Query {
+            query_request: QueryRequest::new(state_root_hash, key, vec!["missing".to_string()]),
+            responder,
+        })
+        .await;
+        assert!(matches!(query_result, Ok(QueryResult::ValueNotFound(_))));
+
+        // Balances.
+        let purse = URef::new([4; 32], AccessRights::READ_ADD_WRITE);
+        mock.set_balance(purse, U512::from(100));
+        let balance_result = respond(&mut mock, |responder| ContractRuntimeRequest::GetBalance {
+            balance_request: BalanceRequest::new(state_root_hash, purse),
+            responder,
+        })
+        .await
+        .unwrap();
+        assert_eq!(balance_result.motes(), Some(&U512::from(100)));
+
+        // Era validators.
+        let mut validator_weights = BTreeMap::new();
+        validator_weights.insert(PublicKey::random(&mut rng), U512::from(10));
+        let mut era_validators = EraValidators::new();
+        era_validators.insert(EraId::new(1), validator_weights);
+        mock.set_era_validators(state_root_hash, era_validators.clone());
+        let get_era_validators = |state_root_hash| {
+            move |responder| ContractRuntimeRequest::GetEraValidators {
+                request: EraValidatorsRequest::new(state_root_hash, ProtocolVersion::V1_0_0),
+                responder,
+            }
+        };
+        let result = respond(&mut mock, get_era_validators(state_root_hash)).await;
+        assert_eq!(result.unwrap(), era_validators);
+        let result = respond(&mut mock, get_era_validators(unknown_state_root_hash)).await;
+        assert!(matches!(result, Err(GetEraValidatorsError::RootNotFound)));
+
+        // Execution results checksums.
+        let checksum = Digest::hash([5]);
+        mock.set_execution_results_checksum(state_root_hash, checksum);
+        let get_checksum = |state_root_hash| {
+            move |responder| ContractRuntimeRequest::GetExecutionResultsChecksum {
+                state_root_hash,
+                responder,
+            }
+        };
+        let result = respond(&mut mock, get_checksum(state_root_hash)).await;
+        assert_eq!(result.unwrap(), Some(checksum));
+        let result = respond(&mut mock, get_checksum(unknown_state_root_hash)).await;
+        assert_eq!(result.unwrap(), None);
+
+        // Speculative execution.
+        let execution_result: ExecutionResult = rng.gen();
+        mock.set_speculative_execution_result(execution_result.clone());
+        let result = respond(&mut mock, |responder| {
+            ContractRuntimeRequest::SpeculativeDeployExecution {
+                execution_prestate: SpeculativeExecutionState {
+                    state_root_hash,
+                    block_time: Timestamp::zero(),
+                    protocol_version: ProtocolVersion::V1_0_0,
+                },
+                deploy: Arc::new(Deploy::random(&mut rng)),
+                collect_gas_profile: false,
+                responder,
+            }
+        })
+        .await;
+        let (speculative_result, _) = result.unwrap().expect("should have result");
+        assert_eq!(speculative_result, execution_result);
+
+        // Block execution.
+        let finalized_block = FinalizedBlock::random(&mut rng);
+        let effects = mock.handle_request::<()>(ContractRuntimeRequest::EnqueueBlockForExecution {
+            finalized_block: finalized_block.clone(),
+            deploys: vec![],
+            key_block_height_for_activation_point: 0,
+            meta_block_state: MetaBlockState::new_already_stored(),
+        });
+        assert!(effects.is_empty());
+        assert_eq!(mock.enqueued_blocks(), &[finalized_block]);
+    }
+}
@@ -10,6 +10,8 @@ use std::{
 
 use fake_instant::FakeClock as Instant;
 use futures::future::{BoxFuture, FutureExt};
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use serde::Serialize;
 use tokio::time::{self, error::Elapsed};
 use tracing::{debug, error_span};
@@ -61,6 +63,8 @@ const POLL_INTERVAL: Duration = Duration::from_millis(10);
 pub(crate) struct TestingNetwork<R: Reactor + NetworkedReactor> {
     /// Current network.
     nodes: HashMap<NodeId, Runner<ConditionCheckReactor<R>>>,
+    /// The RNG determining the order in which nodes are cranked, if it is deterministic.
+    crank_order_rng: Option<ChaCha8Rng>,
 }
 
 impl<R> TestingNetwork<R>
@@ -105,9 +109,30 @@ where
     pub(crate) fn new() -> Self {
         TestingNetwork {
             nodes: HashMap::new(),
+            crank_order_rng: None,
         }
     }
 
+    /// Cranks the nodes from then on in an order drawn from an RNG seeded by `rng`, rather than in
+    /// the iteration order of a `HashMap`, which differs between runs.
+    ///
+    /// Given the same seed, the nodes take turns processing their events in the same order.  This
+    /// is not a simulation: events caused by timers, wall-clock time or network I/O can still be
+    /// enqueued at different times, so it only narrows down the interleavings of a flaky test.
+    pub(crate) fn crank_deterministically(&mut self, rng: &mut TestRng) {
+        self.crank_order_rng = Some(ChaCha8Rng::seed_from_u64(rng.gen()));
+    }
+
+    /// Returns the IDs of all nodes in the order in which they should be cranked next.
+    fn crank_order(&mut self) -> Vec<NodeId> {
+        let mut node_ids: Vec<NodeId> = self.nodes.keys().copied().collect();
+        if let Some(crank_order_rng) = self.crank_order_rng.as_mut() {
+            node_ids.sort();
+            node_ids.shuffle(crank_order_rng);
+        }
+        node_ids
+    }
+
     /// Creates a new networking node on the network.
     ///
     /// # Panics
@@ -202,8 +227,8 @@ where
     /// Panics if any node returns an exit code.
     async fn crank_all(&mut self, rng: &mut TestRng) -> usize {
         let mut event_count = 0;
-        for node in self.nodes.values_mut() {
-            let node_id = node.reactor().node_id();
+        for node_id in self.crank_order() {
+            let node = self.nodes.get_mut(&node_id).expect("should find node");
             match node
                 .try_crank(rng)
                 .instrument(error_span!("crank", node_id = %node_id))
@@ -254,8 +279,8 @@ where
     ) {
         loop {
             let mut no_events = true;
-            for node in self.nodes.values_mut() {
-                let node_id = node.reactor().node_id();
+            for node_id in self.crank_order() {
+                let node = self.nodes.get_mut(&node_id).expect("should find node");
                 match node
                     .try_crank(rng)
                     .instrument(error_span!("crank", node_id = %node_id))
@@ -403,8 +428,8 @@ where
             }
 
             let mut event_count = 0;
-            for node in self.nodes.values_mut() {
-                let node_id = node.reactor().node_id();
+            for node_id in self.crank_order() {
+                let node = self.nodes.get_mut(&node_id).expect("should find node");
                 match node
                     .try_crank(rng)
                     .instrument(error_span!("crank", node_id = %node_id))
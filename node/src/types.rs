@@ -43,12 +43,13 @@ pub(crate) use chainspec::{ActivationPoint, ChainspecRawBytes};
 pub use chunkable::Chunkable;
 pub use datasize::DataSize;
 pub use deploy::{
-    Approval, ApprovalsHash, Deploy, DeployConfigurationFailure, DeployError, DeployHash,
-    DeployHeader, DeployOrTransferHash, ExcessiveSizeError as ExcessiveSizeDeployError,
+    Approval, ApprovalsHash, Deploy, DeployConfigurationFailure, DeployError, DeployExpiryReason,
+    DeployHash, DeployHeader, DeployOrTransferHash, ExcessiveSizeError as ExcessiveSizeDeployError,
 };
 pub(crate) use deploy::{
-    DeployFootprint, DeployHashWithApprovals, DeployId, DeployMetadata, DeployMetadataExt,
-    DeployWithFinalizedApprovals, FinalizedApprovals, LegacyDeploy,
+    DeployCategory, DeployFootprint, DeployHashWithApprovals, DeployId, DeployMetadata,
+    DeployMetadataExt, DeployWithFinalizedApprovals, FinalizedApprovals, FootprintCache,
+    LegacyDeploy,
 };
 pub use error::BlockValidationError;
 pub use exit_code::ExitCode;
@@ -56,7 +57,7 @@ pub(crate) use max_ttl::MaxTtl;
 pub use node_config::{NodeConfig, SyncHandling};
 pub(crate) use node_id::NodeId;
 pub use peers_map::PeersMap;
-pub use status_feed::{ChainspecInfo, GetStatusResult, StatusFeed};
+pub use status_feed::{ChainspecInfo, GetStatusResult, MinimalBlockInfo, StatusFeed};
 pub(crate) use sync_leap::{GlobalStatesMetadata, SyncLeap, SyncLeapIdentifier};
 pub(crate) use validator_matrix::{EraValidatorWeights, SignatureWeight, ValidatorMatrix};
 pub use value_or_chunk::{
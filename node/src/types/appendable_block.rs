@@ -9,8 +9,9 @@ use num_traits::Zero;
 use thiserror::Error;
 
 use crate::types::{
-    chainspec::DeployConfig, deploy::DeployFootprint, BlockPayload, DeployHash,
-    DeployHashWithApprovals,
+    chainspec::DeployConfig,
+    deploy::{DeployConfigurationFailure, DeployFootprint},
+    BlockPayload, DeployHash, DeployHashWithApprovals,
 };
 
 const NO_LEEWAY: TimeDiff = TimeDiff::from_millis(0);
@@ -23,16 +24,31 @@ pub(crate) enum AddError {
     DeployCount,
     #[error("would exceed maximum approval count per block")]
     ApprovalCount,
-    #[error("would exceed maximum gas per block")]
-    GasLimit,
+    #[error(
+        "gas estimate of {gas_estimate} would exceed the block gas limit of {block_gas_limit}, \
+        with {total_gas} already used"
+    )]
+    GasLimit {
+        /// The gas estimate of the deploy which could not be added.
+        gas_estimate: Gas,
+        /// The gas already used by the deploys in the block.
+        total_gas: Gas,
+        /// The configured block gas limit.
+        block_gas_limit: u64,
+    },
     #[error("would exceed maximum block size")]
     BlockSize,
     #[error("duplicate deploy")]
     Duplicate,
-    #[error("deploy has expired")]
-    Expired,
-    #[error("deploy is not valid in this context")]
-    InvalidDeploy,
+    #[error("deploy expired at {expiry}, before the block timestamp {block_timestamp}")]
+    Expired {
+        /// The timestamp at which the deploy expired.
+        expiry: Timestamp,
+        /// The timestamp of the block the deploy was to be included in.
+        block_timestamp: Timestamp,
+    },
+    #[error("deploy is not valid in the context of the block: {0}")]
+    InvalidDeploy(#[source] DeployConfigurationFailure),
 }
 
 /// A block that is still being added to. It keeps track of and enforces block limits.
@@ -70,7 +86,7 @@ impl AppendableBlock {
         deploy_hash_with_approvals: DeployHashWithApprovals,
         footprint: &DeployFootprint,
     ) -> Result<(), AddError> {
-        if footprint.is_transfer {
+        if footprint.category.is_transfer() {
             self.add_transfer(deploy_hash_with_approvals, footprint)
         } else {
             self.add_deploy(deploy_hash_with_approvals, footprint)
@@ -93,21 +109,7 @@ impl AppendableBlock {
         {
             return Err(AddError::Duplicate);
         }
-        if footprint.header.expired(self.timestamp) {
-            return Err(AddError::Expired);
-        }
-        if footprint
-            .header
-            .is_valid(
-                &self.deploy_config,
-                NO_LEEWAY,
-                self.timestamp,
-                transfer.deploy_hash(),
-            )
-            .is_err()
-        {
-            return Err(AddError::InvalidDeploy);
-        }
+        self.check_header(footprint, transfer.deploy_hash())?;
         if self.has_max_transfer_count() {
             return Err(AddError::TransferCount);
         }
@@ -133,21 +135,7 @@ impl AppendableBlock {
         if self.deploy_and_transfer_set.contains(deploy.deploy_hash()) {
             return Err(AddError::Duplicate);
         }
-        if footprint.header.expired(self.timestamp) {
-            return Err(AddError::Expired);
-        }
-        if footprint
-            .header
-            .is_valid(
-                &self.deploy_config,
-                NO_LEEWAY,
-                self.timestamp,
-                deploy.deploy_hash(),
-            )
-            .is_err()
-        {
-            return Err(AddError::InvalidDeploy);
-        }
+        self.check_header(footprint, deploy.deploy_hash())?;
         if self.has_max_deploy_count() {
             return Err(AddError::DeployCount);
         }
@@ -161,12 +149,17 @@ impl AppendableBlock {
             .filter(|size| *size <= self.deploy_config.max_block_size as usize)
             .ok_or(AddError::BlockSize)?;
         let gas_estimate = footprint.gas_estimate;
+        let gas_limit_error = || AddError::GasLimit {
+            gas_estimate,
+            total_gas: self.total_gas,
+            block_gas_limit: self.deploy_config.block_gas_limit,
+        };
         let new_total_gas = self
             .total_gas
             .checked_add(gas_estimate)
-            .ok_or(AddError::GasLimit)?;
+            .ok_or_else(gas_limit_error)?;
         if new_total_gas > Gas::from(self.deploy_config.block_gas_limit) {
-            return Err(AddError::GasLimit);
+            return Err(gas_limit_error());
         }
         self.total_gas = new_total_gas;
         self.total_size = new_total_size;
@@ -193,6 +186,32 @@ impl AppendableBlock {
         self.timestamp
     }
 
+    /// Returns the sum of the gas estimates of all deploys added so far.
+    ///
+    /// Transfers don't count towards the block gas limit.
+    pub(crate) fn total_gas(&self) -> Gas {
+        self.total_gas
+    }
+
+    /// Checks that the deploy is valid at the block's timestamp, i.e. it hasn't expired and it
+    /// isn't future-dated, and that its header satisfies the configured limits.
+    fn check_header(
+        &self,
+        footprint: &DeployFootprint,
+        deploy_hash: &DeployHash,
+    ) -> Result<(), AddError> {
+        if footprint.header.expired(self.timestamp) {
+            return Err(AddError::Expired {
+                expiry: footprint.header.expires(),
+                block_timestamp: self.timestamp,
+            });
+        }
+        footprint
+            .header
+            .is_valid(&self.deploy_config, NO_LEEWAY, self.timestamp, deploy_hash)
+            .map_err(AddError::InvalidDeploy)
+    }
+
     /// Returns `true` if the number of transfers is already the maximum allowed count, i.e. no
     /// more transfers can be added to this block.
     fn has_max_transfer_count(&self) -> bool {
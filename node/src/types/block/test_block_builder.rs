@@ -21,6 +21,7 @@ pub(crate) struct TestBlockBuilder {
     protocol_version: ProtocolVersion,
     deploys: Vec<Deploy>,
     is_switch: Option<bool>,
+    proposer: Option<PublicKey>,
 }
 
 impl TestBlockBuilder {
@@ -35,6 +36,7 @@ impl TestBlockBuilder {
             state_root_hash: None,
             parent_hash: None,
             timestamp: None,
+            proposer: None,
         }
     }
 
@@ -68,6 +70,12 @@ impl TestBlockBuilder {
         self
     }
 
+    #[allow(unused)]
+    pub(crate) fn proposer(mut self, proposer: PublicKey) -> Self {
+        self.proposer = Some(proposer);
+        self
+    }
+
     #[allow(unused)]
     pub(crate) fn deploys<'a, I: IntoIterator<Item = &'a Deploy>>(
         mut self,
@@ -174,9 +182,13 @@ impl TestBlockBuilder {
             } else {
                 None
             };
-            let secret_key: SecretKey =
-                SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap();
-            let public_key = PublicKey::from(&secret_key);
+            let public_key = if let Some(proposer) = self.proposer {
+                proposer
+            } else {
+                let secret_key: SecretKey =
+                    SecretKey::ed25519_from_bytes(rng.gen::<[u8; 32]>()).unwrap();
+                PublicKey::from(&secret_key)
+            };
 
             FinalizedBlock::new(
                 block_payload,
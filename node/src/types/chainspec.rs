@@ -10,6 +10,7 @@ mod error;
 mod global_state_update;
 mod highway_config;
 mod network_config;
+mod network_features;
 mod parse_toml;
 mod protocol_config;
 
@@ -46,6 +47,7 @@ pub use self::{
     global_state_update::GlobalStateUpdate,
     highway_config::{HighwayConfig, PerformanceMeterConfig},
     network_config::NetworkConfig,
+    network_features::{NetworkFeature, NetworkFeatures, NetworkFeaturesConfig},
     protocol_config::ProtocolConfig,
 };
 use crate::{components::network::generate_largest_serialized_message, utils::Loadable};
@@ -68,6 +70,10 @@ pub struct Chainspec {
     #[serde(rename = "network")]
     pub network_config: NetworkConfig,
 
+    /// Network features config.
+    #[serde(rename = "network_features")]
+    pub network_features_config: NetworkFeaturesConfig,
+
     /// Core config.
     #[serde(rename = "core")]
     pub core_config: CoreConfig,
@@ -137,6 +143,11 @@ impl Chainspec {
             }
         }
 
+        if let Err(error) = self.wasm_config.host_function_costs().validate() {
+            error!(%error, "host function costs must not let host functions be called for free");
+            return false;
+        }
+
         self.protocol_config.is_valid()
             && self.core_config.is_valid()
             && self.deploy_config.is_valid()
@@ -192,6 +203,7 @@ impl Chainspec {
             Some(self.core_config.locked_funds_period.millis()),
             Some(self.core_config.round_seigniorage_rate),
             Some(self.core_config.unbonding_delay),
+            Some(self.core_config.mint_paused),
             global_state_update,
             chainspec_registry,
         ))
@@ -204,6 +216,7 @@ impl Chainspec {
     pub fn random(rng: &mut TestRng) -> Self {
         let protocol_config = ProtocolConfig::random(rng);
         let network_config = NetworkConfig::random(rng);
+        let network_features_config = NetworkFeaturesConfig::random(rng);
         let core_config = CoreConfig::random(rng);
         let highway_config = HighwayConfig::random(rng);
         let deploy_config = DeployConfig::random(rng);
@@ -213,6 +226,7 @@ impl Chainspec {
         Chainspec {
             protocol_config,
             network_config,
+            network_features_config,
             core_config,
             highway_config,
             deploy_config,
@@ -227,6 +241,7 @@ impl ToBytes for Chainspec {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
         buffer.extend(self.protocol_config.to_bytes()?);
         buffer.extend(self.network_config.to_bytes()?);
+        buffer.extend(self.network_features_config.to_bytes()?);
         buffer.extend(self.core_config.to_bytes()?);
         buffer.extend(self.highway_config.to_bytes()?);
         buffer.extend(self.deploy_config.to_bytes()?);
@@ -238,6 +253,7 @@ impl ToBytes for Chainspec {
     fn serialized_length(&self) -> usize {
         self.protocol_config.serialized_length()
             + self.network_config.serialized_length()
+            + self.network_features_config.serialized_length()
             + self.core_config.serialized_length()
             + self.highway_config.serialized_length()
             + self.deploy_config.serialized_length()
@@ -250,6 +266,7 @@ impl FromBytes for Chainspec {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (protocol_config, remainder) = ProtocolConfig::from_bytes(bytes)?;
         let (network_config, remainder) = NetworkConfig::from_bytes(remainder)?;
+        let (network_features_config, remainder) = NetworkFeaturesConfig::from_bytes(remainder)?;
         let (core_config, remainder) = CoreConfig::from_bytes(remainder)?;
         let (highway_config, remainder) = HighwayConfig::from_bytes(remainder)?;
         let (deploy_config, remainder) = DeployConfig::from_bytes(remainder)?;
@@ -258,6 +275,7 @@ impl FromBytes for Chainspec {
         let chainspec = Chainspec {
             protocol_config,
             network_config,
+            network_features_config,
             core_config,
             highway_config,
             deploy_config,
@@ -295,6 +313,7 @@ impl From<&Chainspec> for ExecConfig {
             .with_genesis_timestamp_millis(genesis_timestamp_millis)
             .with_refund_handling(chainspec.core_config.refund_handling)
             .with_fee_handling(chainspec.core_config.fee_handling)
+            .with_mint_paused(chainspec.core_config.mint_paused)
             .build()
     }
 }
@@ -368,6 +387,9 @@ mod tests {
         WasmConfig::new(
             17, // initial_memory
             19, // max_stack_height
+            20, // max_table_size
+            21, // max_table_elements
+            22, // max_indirect_call_signatures
             EXPECTED_GENESIS_COSTS,
             EXPECTED_GENESIS_STORAGE_COSTS,
             *EXPECTED_GENESIS_HOST_FUNCTION_COSTS,
@@ -423,6 +445,13 @@ mod tests {
                 Some(Timestamp::from(1600454700000))
             );
             assert_eq!(spec.network_config.accounts_config.accounts().len(), 4);
+            assert_eq!(
+                spec.network_features_config,
+                NetworkFeaturesConfig {
+                    compression: Some(EraId::new(0)),
+                    gossip_batching: None,
+                }
+            );
 
             let accounts: Vec<_> = {
                 let mut accounts = spec.network_config.accounts_config.accounts().to_vec();
@@ -449,6 +478,13 @@ mod tests {
                 EraId::from(1)
             );
             assert!(spec.network_config.accounts_config.accounts().is_empty());
+            assert_eq!(
+                spec.network_features_config,
+                NetworkFeaturesConfig {
+                    compression: Some(EraId::new(1)),
+                    gossip_batching: Some(EraId::new(2)),
+                }
+            );
             assert!(spec.protocol_config.global_state_update.is_some());
             assert!(spec
                 .protocol_config
@@ -532,6 +568,28 @@ mod tests {
         assert!(chainspec.is_valid());
     }
 
+    #[test]
+    fn should_reject_zero_cost_host_functions() {
+        let (mut chainspec, _) = <(Chainspec, ChainspecRawBytes)>::from_resources("local");
+        assert!(chainspec.is_valid());
+
+        let wasm_config = chainspec.wasm_config;
+        chainspec.wasm_config = WasmConfig::new(
+            wasm_config.max_memory,
+            wasm_config.max_stack_height,
+            wasm_config.max_table_size,
+            wasm_config.max_table_elements,
+            wasm_config.max_indirect_call_signatures,
+            wasm_config.opcode_costs(),
+            wasm_config.storage_costs(),
+            HostFunctionCosts {
+                blake2b: HostFunction::fixed(0),
+                ..*wasm_config.host_function_costs()
+            },
+        );
+        assert!(!chainspec.is_valid());
+    }
+
     #[ignore = "We probably need to reconsider our approach here"]
     #[test]
     fn should_have_deterministic_chainspec_hash() {
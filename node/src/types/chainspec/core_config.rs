@@ -2,12 +2,14 @@ use std::collections::BTreeSet;
 
 use tracing::{error, warn};
 
-use casper_execution_engine::core::engine_state::engine_config::{FeeHandling, RefundHandling};
+use casper_execution_engine::core::engine_state::engine_config::{
+    AccountCreationPolicy, DelegationRateChangeLimit, FeeHandling, RefundHandling, StorageRent,
+};
 #[cfg(test)]
 use casper_types::testing::TestRng;
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
-    PublicKey,
+    EraId, PublicKey,
 };
 
 use datasize::DataSize;
@@ -78,9 +80,30 @@ pub struct CoreConfig {
     /// Maximum height of contract runtime call stack.
     pub max_runtime_call_stack_height: u32,
 
+    /// Maximum size in bytes of a single `CLValue` written to global state.
+    pub max_cl_value_size: u32,
+
+    /// Maximum number of named keys of a single account or contract.
+    pub max_named_keys: u32,
+
     /// The minimum bound of motes that can be delegated to a validator.
     pub minimum_delegation_amount: u64,
 
+    /// The minimum bound of motes a validator's stake must hold to be eligible as a proposer, and
+    /// to be left staked by `add_bid` and partial `withdraw_bid` calls.  Zero disables the check.
+    #[serde(default)]
+    pub minimum_bid_amount: u64,
+
+    /// Whether the auction records the validator set of each era under `Key::AuctionSnapshot`.
+    #[serde(default)]
+    pub record_auction_snapshots: bool,
+
+    /// The era from which execution results are reported as V2 execution results, giving the gas
+    /// limit, gas consumed, gas price, paid amount and refund separately.  Until then, or if not
+    /// set, they are reported as V1 execution results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_results_v2_activation_point: Option<EraId>,
+
     /// Global state prune batch size (0 means the feature is off in the current protocol version).
     pub prune_batch_size: u64,
 
@@ -102,6 +125,8 @@ pub struct CoreConfig {
     pub(crate) allow_auction_bids: bool,
     /// Allows unrestricted transfers between users.
     pub(crate) allow_unrestricted_transfers: bool,
+    /// Pauses mint, transfer, burn and total supply reduction by the mint.
+    pub(crate) mint_paused: bool,
     /// If set to false then consensus doesn't compute rewards and always uses 0.
     pub(crate) compute_rewards: bool,
     /// Administrative accounts are valid option for a private chain only.
@@ -112,6 +137,12 @@ pub struct CoreConfig {
     pub(crate) refund_handling: RefundHandling,
     /// Fee handling.
     pub(crate) fee_handling: FeeHandling,
+    /// Storage rent.
+    pub(crate) storage_rent: StorageRent,
+    /// Limit on how often and by how much validators can raise their delegation rate.
+    pub(crate) delegation_rate_change_limit: DelegationRateChangeLimit,
+    /// Whether transfers to accounts which do not exist yet create them.
+    pub(crate) account_creation_policy: AccountCreationPolicy,
 }
 
 impl CoreConfig {
@@ -187,13 +218,23 @@ impl CoreConfig {
         );
         let max_associated_keys = rng.gen();
         let max_runtime_call_stack_height = rng.gen();
+        let max_cl_value_size = rng.gen();
+        let max_named_keys = rng.gen();
         let minimum_delegation_amount = rng.gen::<u32>() as u64;
+        let minimum_bid_amount = rng.gen::<u32>() as u64;
+        let record_auction_snapshots = rng.gen();
+        let execution_results_v2_activation_point = if rng.gen() {
+            Some(EraId::new(rng.gen()))
+        } else {
+            None
+        };
         let prune_batch_size = rng.gen_range(0..100);
         let strict_argument_checking = rng.gen();
         let simultaneous_peer_requests = rng.gen_range(3..100);
         let consensus_protocol = rng.gen();
         let allow_auction_bids = rng.gen();
         let allow_unrestricted_transfers = rng.gen();
+        let mint_paused = rng.gen();
         let compute_rewards = rng.gen();
         let administrators = (0..rng.gen_range(0..=10u32))
             .map(|_| PublicKey::random(rng))
@@ -210,6 +251,32 @@ impl CoreConfig {
             FeeHandling::Accumulate
         };
 
+        let storage_rent = if rng.gen() {
+            StorageRent::Disabled
+        } else {
+            StorageRent::Lease {
+                lease_eras: rng.gen(),
+                grace_eras: rng.gen(),
+            }
+        };
+
+        let delegation_rate_change_limit = if rng.gen() {
+            DelegationRateChangeLimit::Unlimited
+        } else {
+            DelegationRateChangeLimit::Limited {
+                min_eras_between_increases: rng.gen(),
+                max_increase: rng.gen(),
+            }
+        };
+
+        let account_creation_policy = match rng.gen_range(0..3) {
+            0 => AccountCreationPolicy::Allowed,
+            1 => AccountCreationPolicy::Disallowed,
+            _ => AccountCreationPolicy::AllowedAboveMinimum {
+                minimum_amount: rng.gen(),
+            },
+        };
+
         CoreConfig {
             era_duration,
             minimum_era_height,
@@ -225,7 +292,12 @@ impl CoreConfig {
             round_seigniorage_rate,
             max_associated_keys,
             max_runtime_call_stack_height,
+            max_cl_value_size,
+            max_named_keys,
             minimum_delegation_amount,
+            minimum_bid_amount,
+            record_auction_snapshots,
+            execution_results_v2_activation_point,
             prune_batch_size,
             strict_argument_checking,
             simultaneous_peer_requests,
@@ -234,9 +306,13 @@ impl CoreConfig {
             allow_auction_bids,
             administrators,
             allow_unrestricted_transfers,
+            mint_paused,
             compute_rewards,
             refund_handling,
             fee_handling,
+            storage_rent,
+            delegation_rate_change_limit,
+            account_creation_policy,
         }
     }
 }
@@ -261,7 +337,12 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.round_seigniorage_rate.to_bytes()?);
         buffer.extend(self.max_associated_keys.to_bytes()?);
         buffer.extend(self.max_runtime_call_stack_height.to_bytes()?);
+        buffer.extend(self.max_cl_value_size.to_bytes()?);
+        buffer.extend(self.max_named_keys.to_bytes()?);
         buffer.extend(self.minimum_delegation_amount.to_bytes()?);
+        buffer.extend(self.minimum_bid_amount.to_bytes()?);
+        buffer.extend(self.record_auction_snapshots.to_bytes()?);
+        buffer.extend(self.execution_results_v2_activation_point.to_bytes()?);
         buffer.extend(self.prune_batch_size.to_bytes()?);
         buffer.extend(self.strict_argument_checking.to_bytes()?);
         buffer.extend(self.simultaneous_peer_requests.to_bytes()?);
@@ -269,10 +350,14 @@ impl ToBytes for CoreConfig {
         buffer.extend(self.max_delegators_per_validator.to_bytes()?);
         buffer.extend(self.allow_auction_bids.to_bytes()?);
         buffer.extend(self.allow_unrestricted_transfers.to_bytes()?);
+        buffer.extend(self.mint_paused.to_bytes()?);
         buffer.extend(self.compute_rewards.to_bytes()?);
         buffer.extend(self.administrators.to_bytes()?);
         buffer.extend(self.refund_handling.to_bytes()?);
         buffer.extend(self.fee_handling.to_bytes()?);
+        buffer.extend(self.storage_rent.to_bytes()?);
+        buffer.extend(self.delegation_rate_change_limit.to_bytes()?);
+        buffer.extend(self.account_creation_policy.to_bytes()?);
         Ok(buffer)
     }
 
@@ -293,7 +378,14 @@ impl ToBytes for CoreConfig {
             + self.round_seigniorage_rate.serialized_length()
             + self.max_associated_keys.serialized_length()
             + self.max_runtime_call_stack_height.serialized_length()
+            + self.max_cl_value_size.serialized_length()
+            + self.max_named_keys.serialized_length()
             + self.minimum_delegation_amount.serialized_length()
+            + self.minimum_bid_amount.serialized_length()
+            + self.record_auction_snapshots.serialized_length()
+            + self
+                .execution_results_v2_activation_point
+                .serialized_length()
             + self.prune_batch_size.serialized_length()
             + self.strict_argument_checking.serialized_length()
             + self.simultaneous_peer_requests.serialized_length()
@@ -301,10 +393,14 @@ impl ToBytes for CoreConfig {
             + self.max_delegators_per_validator.serialized_length()
             + self.allow_auction_bids.serialized_length()
             + self.allow_unrestricted_transfers.serialized_length()
+            + self.mint_paused.serialized_length()
             + self.compute_rewards.serialized_length()
             + self.administrators.serialized_length()
             + self.refund_handling.serialized_length()
             + self.fee_handling.serialized_length()
+            + self.storage_rent.serialized_length()
+            + self.delegation_rate_change_limit.serialized_length()
+            + self.account_creation_policy.serialized_length()
     }
 }
 
@@ -325,7 +421,13 @@ impl FromBytes for CoreConfig {
         let (round_seigniorage_rate, remainder) = Ratio::<u64>::from_bytes(remainder)?;
         let (max_associated_keys, remainder) = u32::from_bytes(remainder)?;
         let (max_runtime_call_stack_height, remainder) = u32::from_bytes(remainder)?;
+        let (max_cl_value_size, remainder) = u32::from_bytes(remainder)?;
+        let (max_named_keys, remainder) = u32::from_bytes(remainder)?;
         let (minimum_delegation_amount, remainder) = u64::from_bytes(remainder)?;
+        let (minimum_bid_amount, remainder) = u64::from_bytes(remainder)?;
+        let (record_auction_snapshots, remainder) = bool::from_bytes(remainder)?;
+        let (execution_results_v2_activation_point, remainder) =
+            Option::<EraId>::from_bytes(remainder)?;
         let (prune_batch_size, remainder) = u64::from_bytes(remainder)?;
         let (strict_argument_checking, remainder) = bool::from_bytes(remainder)?;
         let (simultaneous_peer_requests, remainder) = u8::from_bytes(remainder)?;
@@ -333,10 +435,14 @@ impl FromBytes for CoreConfig {
         let (max_delegators_per_validator, remainder) = FromBytes::from_bytes(remainder)?;
         let (allow_auction_bids, remainder) = FromBytes::from_bytes(remainder)?;
         let (allow_unrestricted_transfers, remainder) = FromBytes::from_bytes(remainder)?;
+        let (mint_paused, remainder) = bool::from_bytes(remainder)?;
         let (compute_rewards, remainder) = bool::from_bytes(remainder)?;
         let (administrative_accounts, remainder) = FromBytes::from_bytes(remainder)?;
         let (refund_handling, remainder) = FromBytes::from_bytes(remainder)?;
         let (fee_handling, remainder) = FromBytes::from_bytes(remainder)?;
+        let (storage_rent, remainder) = FromBytes::from_bytes(remainder)?;
+        let (delegation_rate_change_limit, remainder) = FromBytes::from_bytes(remainder)?;
+        let (account_creation_policy, remainder) = FromBytes::from_bytes(remainder)?;
         let config = CoreConfig {
             era_duration,
             minimum_era_height,
@@ -352,7 +458,12 @@ impl FromBytes for CoreConfig {
             round_seigniorage_rate,
             max_associated_keys,
             max_runtime_call_stack_height,
+            max_cl_value_size,
+            max_named_keys,
             minimum_delegation_amount,
+            minimum_bid_amount,
+            record_auction_snapshots,
+            execution_results_v2_activation_point,
             prune_batch_size,
             strict_argument_checking,
             simultaneous_peer_requests,
@@ -360,10 +471,14 @@ impl FromBytes for CoreConfig {
             max_delegators_per_validator,
             allow_auction_bids,
             allow_unrestricted_transfers,
+            mint_paused,
             compute_rewards,
             administrators: administrative_accounts,
             refund_handling,
             fee_handling,
+            storage_rent,
+            delegation_rate_change_limit,
+            account_creation_policy,
         };
         Ok((config, remainder))
     }
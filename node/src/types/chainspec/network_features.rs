@@ -0,0 +1,220 @@
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+#[cfg(test)]
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[cfg(test)]
+use casper_types::testing::TestRng;
+use casper_types::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    EraId,
+};
+
+/// A capability of the networking protocol changing what is sent over the wire.
+///
+/// A feature is only used once the era from which it is activated in the chainspec has been
+/// reached, so that all nodes start using it at the same era boundary.
+#[derive(Copy, Clone, DataSize, PartialEq, Eq, Debug)]
+pub enum NetworkFeature {
+    /// Compression of large outgoing payloads.
+    Compression,
+    /// Batching of gossip messages sharing a destination into a single message.
+    GossipBatching,
+}
+
+impl NetworkFeature {
+    /// All features known to this version of the node.
+    pub const ALL: [NetworkFeature; 2] =
+        [NetworkFeature::Compression, NetworkFeature::GossipBatching];
+
+    /// Returns the bit representing the feature in a [`NetworkFeatures`] set.
+    const fn bit(self) -> u32 {
+        match self {
+            NetworkFeature::Compression => 1,
+            NetworkFeature::GossipBatching => 1 << 1,
+        }
+    }
+}
+
+impl Display for NetworkFeature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            NetworkFeature::Compression => f.write_str("compression"),
+            NetworkFeature::GossipBatching => f.write_str("gossip_batching"),
+        }
+    }
+}
+
+/// A set of network features, represented as a bitset.
+///
+/// Unknown bits received from peers running a newer version are retained, but never reported as
+/// contained features.
+#[derive(Copy, Clone, DataSize, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+#[serde(transparent)]
+pub struct NetworkFeatures(u32);
+
+impl NetworkFeatures {
+    /// The features supported by this version of the node.
+    pub const SUPPORTED: NetworkFeatures =
+        NetworkFeatures(NetworkFeature::Compression.bit() | NetworkFeature::GossipBatching.bit());
+
+    /// Creates a set from its raw bits, including those of features unknown to this version.
+    pub(crate) const fn from_bits(bits: u32) -> Self {
+        NetworkFeatures(bits)
+    }
+
+    /// Returns whether the given feature is part of the set.
+    pub fn contains(self, feature: NetworkFeature) -> bool {
+        self.0 & feature.bit() != 0
+    }
+
+    /// Adds the given feature to the set.
+    pub fn insert(&mut self, feature: NetworkFeature) {
+        self.0 |= feature.bit();
+    }
+
+    /// Returns the features contained in `self`, but not in `other`.
+    pub fn difference(self, other: NetworkFeatures) -> NetworkFeatures {
+        NetworkFeatures(self.0 & !other.0)
+    }
+
+    /// Returns whether the set holds no features.
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl Display for NetworkFeatures {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut features = NetworkFeature::ALL
+            .iter()
+            .filter(|feature| self.contains(**feature))
+            .peekable();
+        if features.peek().is_none() {
+            return f.write_str("none");
+        }
+        for (index, feature) in features.enumerate() {
+            if index > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", feature)?;
+        }
+        Ok(())
+    }
+}
+
+/// The eras from which the network features are used.
+///
+/// Features without an activation era are never used.
+#[derive(Copy, Clone, DataSize, Default, PartialEq, Eq, Serialize, Deserialize, Debug)]
+// Disallow unknown fields to ensure config files and command-line overrides contain valid keys.
+#[serde(deny_unknown_fields)]
+pub struct NetworkFeaturesConfig {
+    /// The era from which large outgoing payloads are compressed.
+    #[serde(default)]
+    pub compression: Option<EraId>,
+    /// The era from which gossip messages are batched.
+    #[serde(default)]
+    pub gossip_batching: Option<EraId>,
+}
+
+impl NetworkFeaturesConfig {
+    /// Returns the era from which the given feature is used, if any.
+    pub fn activation_era(&self, feature: NetworkFeature) -> Option<EraId> {
+        match feature {
+            NetworkFeature::Compression => self.compression,
+            NetworkFeature::GossipBatching => self.gossip_batching,
+        }
+    }
+
+    /// Returns the features in use in the given era.
+    pub fn active_at(&self, era_id: EraId) -> NetworkFeatures {
+        let mut features = NetworkFeatures::default();
+        for feature in NetworkFeature::ALL {
+            if self
+                .activation_era(feature)
+                .map_or(false, |activation_era| activation_era <= era_id)
+            {
+                features.insert(feature);
+            }
+        }
+        features
+    }
+}
+
+#[cfg(test)]
+impl NetworkFeaturesConfig {
+    /// Generates a random instance using a `TestRng`.
+    pub fn random(rng: &mut TestRng) -> Self {
+        let mut random_era = || rng.gen::<bool>().then(|| EraId::new(rng.gen_range(0..100)));
+        NetworkFeaturesConfig {
+            compression: random_era(),
+            gossip_batching: random_era(),
+        }
+    }
+}
+
+impl ToBytes for NetworkFeaturesConfig {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.compression.to_bytes()?);
+        buffer.extend(self.gossip_batching.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.compression.serialized_length() + self.gossip_batching.serialized_length()
+    }
+}
+
+impl FromBytes for NetworkFeaturesConfig {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (compression, remainder) = FromBytes::from_bytes(bytes)?;
+        let (gossip_batching, remainder) = FromBytes::from_bytes(remainder)?;
+        let config = NetworkFeaturesConfig {
+            compression,
+            gossip_batching,
+        };
+        Ok((config, remainder))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut rng = crate::new_rng();
+        let config = NetworkFeaturesConfig::random(&mut rng);
+        bytesrepr::test_serialization_roundtrip(&config);
+    }
+
+    #[test]
+    fn should_activate_features_at_their_era() {
+        let config = NetworkFeaturesConfig {
+            compression: Some(EraId::new(0)),
+            gossip_batching: Some(EraId::new(10)),
+        };
+        let features = config.active_at(EraId::new(9));
+        assert!(features.contains(NetworkFeature::Compression));
+        assert!(!features.contains(NetworkFeature::GossipBatching));
+        assert_eq!(config.active_at(EraId::new(10)), NetworkFeatures::SUPPORTED);
+        assert!(NetworkFeaturesConfig::default()
+            .active_at(EraId::new(u64::MAX))
+            .is_empty());
+    }
+
+    #[test]
+    fn should_report_missing_features() {
+        let mut peer_features = NetworkFeatures::default();
+        peer_features.insert(NetworkFeature::GossipBatching);
+        let missing = NetworkFeatures::SUPPORTED.difference(peer_features);
+        assert!(missing.contains(NetworkFeature::Compression));
+        assert!(!missing.contains(NetworkFeature::GossipBatching));
+        assert_eq!(missing.to_string(), "compression");
+        assert_eq!(NetworkFeatures::default().to_string(), "none");
+    }
+}
@@ -16,7 +16,7 @@ use casper_types::{bytesrepr::Bytes, file_utils, ProtocolVersion};
 use super::{
     accounts_config::AccountsConfig, global_state_update::GlobalStateUpdateConfig, ActivationPoint,
     Chainspec, ChainspecRawBytes, CoreConfig, DeployConfig, Error, GlobalStateUpdate,
-    HighwayConfig, NetworkConfig, ProtocolConfig,
+    HighwayConfig, NetworkConfig, NetworkFeaturesConfig, ProtocolConfig,
 };
 
 #[derive(PartialEq, Eq, Serialize, Deserialize, Debug)]
@@ -33,6 +33,7 @@ struct TomlNetwork {
 struct TomlProtocol {
     version: ProtocolVersion,
     hard_reset: bool,
+    compact_auction_state: bool,
     activation_point: ActivationPoint,
 }
 
@@ -43,6 +44,7 @@ struct TomlProtocol {
 pub(super) struct TomlChainspec {
     protocol: TomlProtocol,
     network: TomlNetwork,
+    network_features: NetworkFeaturesConfig,
     core: CoreConfig,
     deploys: DeployConfig,
     highway: HighwayConfig,
@@ -55,12 +57,14 @@ impl From<&Chainspec> for TomlChainspec {
         let protocol = TomlProtocol {
             version: chainspec.protocol_config.version,
             hard_reset: chainspec.protocol_config.hard_reset,
+            compact_auction_state: chainspec.protocol_config.compact_auction_state,
             activation_point: chainspec.protocol_config.activation_point,
         };
         let network = TomlNetwork {
             name: chainspec.network_config.name.clone(),
             maximum_net_message_size: chainspec.network_config.maximum_net_message_size,
         };
+        let network_features = chainspec.network_features_config;
 
         let core = chainspec.core_config.clone();
         let deploys = chainspec.deploy_config;
@@ -71,6 +75,7 @@ impl From<&Chainspec> for TomlChainspec {
         TomlChainspec {
             protocol,
             network,
+            network_features,
             core,
             deploys,
             highway,
@@ -111,6 +116,7 @@ pub(super) fn parse_toml<P: AsRef<Path>>(
     let protocol_config = ProtocolConfig {
         version: toml_chainspec.protocol.version,
         hard_reset: toml_chainspec.protocol.hard_reset,
+        compact_auction_state: toml_chainspec.protocol.compact_auction_state,
         activation_point: toml_chainspec.protocol.activation_point,
         global_state_update,
     };
@@ -118,6 +124,7 @@ pub(super) fn parse_toml<P: AsRef<Path>>(
     let chainspec = Chainspec {
         protocol_config,
         network_config,
+        network_features_config: toml_chainspec.network_features,
         core_config: toml_chainspec.core,
         deploy_config: toml_chainspec.deploys,
         highway_config: toml_chainspec.highway,
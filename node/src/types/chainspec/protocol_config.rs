@@ -27,6 +27,8 @@ pub struct ProtocolConfig {
     /// Whether we need to clear latest blocks back to the switch block just before the activation
     /// point or not.
     pub hard_reset: bool,
+    /// Whether the auction's stale records should be deleted from global state at the upgrade.
+    pub compact_auction_state: bool,
     /// This protocol config applies starting at the era specified in the activation point.
     pub activation_point: ActivationPoint,
     /// Any arbitrary updates we might want to make to the global state at the start of the era
@@ -78,6 +80,7 @@ impl ProtocolConfig {
         ProtocolConfig {
             version: protocol_version,
             hard_reset: rng.gen(),
+            compact_auction_state: rng.gen(),
             activation_point,
             global_state_update: None,
         }
@@ -89,6 +92,7 @@ impl ToBytes for ProtocolConfig {
         let mut buffer = bytesrepr::allocate_buffer(self)?;
         buffer.extend(self.version.to_string().to_bytes()?);
         buffer.extend(self.hard_reset.to_bytes()?);
+        buffer.extend(self.compact_auction_state.to_bytes()?);
         buffer.extend(self.activation_point.to_bytes()?);
         buffer.extend(self.global_state_update.to_bytes()?);
         Ok(buffer)
@@ -97,6 +101,7 @@ impl ToBytes for ProtocolConfig {
     fn serialized_length(&self) -> usize {
         self.version.to_string().serialized_length()
             + self.hard_reset.serialized_length()
+            + self.compact_auction_state.serialized_length()
             + self.activation_point.serialized_length()
             + self.global_state_update.serialized_length()
     }
@@ -108,11 +113,13 @@ impl FromBytes for ProtocolConfig {
         let version = ProtocolVersion::from_str(&protocol_version_string)
             .map_err(|_| bytesrepr::Error::Formatting)?;
         let (hard_reset, remainder) = bool::from_bytes(remainder)?;
+        let (compact_auction_state, remainder) = bool::from_bytes(remainder)?;
         let (activation_point, remainder) = ActivationPoint::from_bytes(remainder)?;
         let (global_state_update, remainder) = Option::<GlobalStateUpdate>::from_bytes(remainder)?;
         let protocol_config = ProtocolConfig {
             version,
             hard_reset,
+            compact_auction_state,
             activation_point,
             global_state_update,
         };
@@ -186,6 +193,7 @@ mod tests {
         let protocol_config = ProtocolConfig {
             version: current_version,
             hard_reset: false,
+            compact_auction_state: false,
             activation_point: ActivationPoint::EraId(upgrade_era),
             global_state_update: None,
         };
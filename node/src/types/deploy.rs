@@ -3,14 +3,17 @@
 
 mod approval;
 mod approvals_hash;
+mod category;
 mod deploy_hash;
 mod deploy_hash_with_approvals;
 mod deploy_header;
 mod deploy_or_transfer_hash;
 mod deploy_with_finalized_approvals;
 mod error;
+mod expiry_reason;
 mod finalized_approvals;
 mod footprint;
+mod footprint_cache;
 mod id;
 mod legacy_deploy;
 mod metadata;
@@ -37,15 +40,21 @@ use casper_execution_engine::core::engine_state::{
     executable_deploy_item::ExecutableDeployItem, DeployItem,
 };
 use casper_hashing::Digest;
-#[cfg(test)]
-use casper_types::bytesrepr::Bytes;
 #[cfg(any(feature = "testing", test))]
-use casper_types::testing::TestRng;
+use std::collections::BTreeMap;
+
+#[cfg(any(feature = "testing", test))]
+use casper_types::{
+    bytesrepr::Bytes,
+    system::auction::{ARG_DELEGATIONS, ARG_DELEGATOR, ARG_REDELEGATIONS, METHOD_BULK_DELEGATE},
+    testing::TestRng,
+    ContractHash,
+};
 use casper_types::{
     bytesrepr::{self, FromBytes, ToBytes},
     crypto, runtime_args,
     system::standard_payment::ARG_AMOUNT,
-    PublicKey, RuntimeArgs, SecretKey, TimeDiff, Timestamp, U512,
+    PublicKey, RuntimeArgs, SecretKey, Signature, TimeDiff, Timestamp, U512,
 };
 
 use crate::{
@@ -60,14 +69,17 @@ use crate::{
 };
 pub use approval::Approval;
 pub use approvals_hash::ApprovalsHash;
+pub(crate) use category::Category as DeployCategory;
 pub use deploy_hash::DeployHash;
 pub(crate) use deploy_hash_with_approvals::DeployHashWithApprovals;
 pub use deploy_header::DeployHeader;
 pub use deploy_or_transfer_hash::DeployOrTransferHash;
 pub(crate) use deploy_with_finalized_approvals::DeployWithFinalizedApprovals;
 pub use error::{DeployConfigurationFailure, Error as DeployError, ExcessiveSizeError};
+pub use expiry_reason::ExpiryReason as DeployExpiryReason;
 pub(crate) use finalized_approvals::FinalizedApprovals;
 pub(crate) use footprint::Footprint as DeployFootprint;
+pub(crate) use footprint_cache::FootprintCache;
 pub use id::Id as DeployId;
 pub(crate) use legacy_deploy::LegacyDeploy;
 pub(crate) use metadata::{Metadata as DeployMetadata, MetadataExt as DeployMetadataExt};
@@ -181,6 +193,20 @@ impl Deploy {
         self.approvals.insert(approval);
     }
 
+    /// Adds a signature of this deploy's hash made by `signer` to its approvals.
+    ///
+    /// This allows the approvals required by a multisig account to be collected from signers
+    /// holding their secret keys elsewhere.  The signature is verified before being added.
+    pub fn add_signature(
+        &mut self,
+        signer: PublicKey,
+        signature: Signature,
+    ) -> Result<(), crypto::Error> {
+        crypto::verify(self.hash, &signature, &signer)?;
+        self.approvals.insert(Approval::new(signer, signature));
+        Ok(())
+    }
+
     /// Returns the `DeployHash` identifying this `Deploy`.
     pub fn hash(&self) -> &DeployHash {
         &self.hash
@@ -221,13 +247,14 @@ impl Deploy {
         self.approvals = approvals;
     }
 
+    /// Returns the category of this deploy.
+    pub(crate) fn category(&self) -> DeployCategory {
+        DeployCategory::of_session(&self.session)
+    }
+
     /// Returns the hash of this deploy wrapped in `DeployOrTransferHash`.
     pub fn deploy_or_transfer_hash(&self) -> DeployOrTransferHash {
-        if self.session.is_transfer() {
-            DeployOrTransferHash::Transfer(self.hash)
-        } else {
-            DeployOrTransferHash::Deploy(self.hash)
-        }
+        self.category().deploy_or_transfer_hash(self.hash)
     }
 
     pub(crate) fn with_approvals(mut self, approvals: BTreeSet<Approval>) -> Self {
@@ -245,12 +272,12 @@ impl Deploy {
             }
         };
         let size_estimate = self.serialized_length();
-        let is_transfer = self.session.is_transfer();
         Ok(DeployFootprint {
             header,
             gas_estimate,
             size_estimate,
-            is_transfer,
+            category: self.category(),
+            session: self.session().identifier(),
         })
     }
 
@@ -336,10 +363,12 @@ impl Deploy {
             });
         }
 
+        let category = self.category();
+
         // Transfers have a fixed cost and won't blow the block gas limit.
         // Other deploys can, therefore, statically check the payment amount
         // associated with the deploy.
-        if !self.session().is_transfer() {
+        if !category.is_transfer() {
             let value = self
                 .payment()
                 .args()
@@ -388,7 +417,7 @@ impl Deploy {
             });
         }
 
-        if self.session().is_transfer() {
+        if category.is_transfer() {
             let item = self.session().clone();
             let attempted = item
                 .args()
@@ -701,6 +730,47 @@ impl Deploy {
             None,
         )
     }
+
+    /// Returns a deploy calling the `bulk_delegate` entry point of the auction contract, which
+    /// makes all the given delegations and redelegations of the signer in one go.
+    ///
+    /// `redelegations` lists the validator to redelegate from, the validator to redelegate to and
+    /// the amount.
+    pub fn bulk_delegate(
+        chain_name: String,
+        auction_contract_hash: ContractHash,
+        delegator_secret_key: &SecretKey,
+        delegations: BTreeMap<PublicKey, U512>,
+        redelegations: Vec<(PublicKey, PublicKey, U512)>,
+        payment_amount: U512,
+    ) -> Self {
+        let session = ExecutableDeployItem::StoredContractByHash {
+            hash: auction_contract_hash,
+            entry_point: METHOD_BULK_DELEGATE.to_string(),
+            args: runtime_args! {
+                ARG_DELEGATOR => PublicKey::from(delegator_secret_key),
+                ARG_DELEGATIONS => delegations,
+                ARG_REDELEGATIONS => redelegations,
+            },
+        };
+        let payment = ExecutableDeployItem::ModuleBytes {
+            module_bytes: Bytes::new(),
+            args: runtime_args! {
+                ARG_AMOUNT => payment_amount,
+            },
+        };
+        Deploy::new(
+            Timestamp::now(),
+            TimeDiff::from_seconds(3_600),
+            1,
+            vec![],
+            chain_name,
+            payment,
+            session,
+            delegator_secret_key,
+            None,
+        )
+    }
 }
 
 #[cfg(test)]
@@ -993,6 +1063,15 @@ impl Deploy {
         Self::random_transfer_with_session(rng, session)
     }
 
+    /// Returns a random invalid deploy with the given session bytes.
+    pub(crate) fn random_with_session_module_bytes(rng: &mut TestRng, module_bytes: Bytes) -> Self {
+        let session = ExecutableDeployItem::ModuleBytes {
+            module_bytes,
+            args: Default::default(),
+        };
+        Self::random_transfer_with_session(rng, session)
+    }
+
     /// Returns a random invalid deploy with an expired TTL.
     pub(crate) fn random_expired_deploy(rng: &mut TestRng) -> Self {
         let deploy = Self::random_valid_native_transfer(rng);
@@ -1148,6 +1227,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn should_add_signatures_made_elsewhere() {
+        let mut rng = crate::new_rng();
+        let mut deploy = create_deploy(&mut rng, DeployConfig::default().max_ttl, 0, "net-1");
+
+        let cosigner_secret_key = SecretKey::random(&mut rng);
+        let cosigner = PublicKey::from(&cosigner_secret_key);
+        let signature = crypto::sign(deploy.hash(), &cosigner_secret_key, &cosigner);
+
+        // A signature attributed to the wrong signer is rejected.
+        let other_signer = PublicKey::random(&mut rng);
+        assert!(deploy.add_signature(other_signer, signature).is_err());
+        assert_eq!(deploy.approvals().len(), 1);
+
+        deploy
+            .add_signature(cosigner.clone(), signature)
+            .expect("should add signature");
+        assert_eq!(deploy.approvals().len(), 2);
+        assert!(deploy
+            .approvals()
+            .iter()
+            .any(|approval| *approval.signer() == cosigner));
+        deploy.is_valid().expect("should be valid");
+    }
+
     fn check_is_not_valid(invalid_deploy: Deploy, expected_error: DeployConfigurationFailure) {
         assert!(
             invalid_deploy.is_valid.get().is_none(),
@@ -1772,4 +1876,40 @@ mod tests {
             )
         )
     }
+
+    #[test]
+    fn bulk_delegate_deploy_should_call_auction_entry_point() {
+        let mut rng = crate::new_rng();
+        let secret_key = SecretKey::random(&mut rng);
+        let auction_contract_hash = ContractHash::new([7; 32]);
+        let validator_1 = PublicKey::random(&mut rng);
+        let validator_2 = PublicKey::random(&mut rng);
+        let mut delegations = BTreeMap::new();
+        delegations.insert(validator_1.clone(), U512::from(1_000));
+        let redelegations = vec![(validator_1, validator_2, U512::from(500))];
+
+        let deploy = Deploy::bulk_delegate(
+            "net-1".to_string(),
+            auction_contract_hash,
+            &secret_key,
+            delegations.clone(),
+            redelegations.clone(),
+            U512::from(10),
+        );
+        assert!(deploy.is_valid().is_ok());
+        assert_eq!(deploy.header().account(), &PublicKey::from(&secret_key));
+
+        let expected_args = runtime_args! {
+            ARG_DELEGATOR => PublicKey::from(&secret_key),
+            ARG_DELEGATIONS => delegations,
+            ARG_REDELEGATIONS => redelegations,
+        };
+        assert!(matches!(
+            deploy.session(),
+            ExecutableDeployItem::StoredContractByHash { hash, entry_point, args }
+                if *hash == auction_contract_hash
+                    && entry_point == METHOD_BULK_DELEGATE
+                    && *args == expected_args
+        ));
+    }
 }
@@ -31,6 +31,12 @@ impl Approval {
         Self { signer, signature }
     }
 
+    /// Creates an approval from a signature of a deploy hash made by `signer`, e.g. on another
+    /// machine.
+    pub fn new(signer: PublicKey, signature: Signature) -> Self {
+        Self { signer, signature }
+    }
+
     /// Returns the public key of the approval's signer.
     pub fn signer(&self) -> &PublicKey {
         &self.signer
@@ -0,0 +1,98 @@
+use datasize::DataSize;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItem;
+
+use super::{DeployHash, DeployOrTransferHash};
+
+/// The category of a deploy, which determines the lane of a block the deploy is included in.
+///
+/// It is derived from the deploy's session code once, as part of the deploy's footprint, so that
+/// the components handling deploys don't need to inspect the session code themselves.
+#[derive(Copy, Clone, DataSize, Eq, PartialEq, Hash, Serialize, Deserialize, Debug, Display)]
+pub(crate) enum Category {
+    /// A native transfer, included in the transfer lane.
+    #[display(fmt = "transfer")]
+    Transfer,
+    /// Session code supplied as Wasm, which may install or upgrade contracts.
+    #[display(fmt = "install")]
+    Install,
+    /// A call to a stored contract.
+    #[display(fmt = "standard")]
+    Standard,
+}
+
+impl Category {
+    /// Returns the category of a deploy with the given session code.
+    pub(crate) fn of_session(session: &ExecutableDeployItem) -> Self {
+        match session {
+            ExecutableDeployItem::Transfer { .. } => Category::Transfer,
+            ExecutableDeployItem::ModuleBytes { .. } => Category::Install,
+            ExecutableDeployItem::StoredContractByHash { .. }
+            | ExecutableDeployItem::StoredContractByName { .. }
+            | ExecutableDeployItem::StoredVersionedContractByHash { .. }
+            | ExecutableDeployItem::StoredVersionedContractByName { .. } => Category::Standard,
+        }
+    }
+
+    /// Returns `true` if deploys of this category are included in the transfer lane of blocks
+    /// rather than in the deploy lane.
+    pub(crate) fn is_transfer(self) -> bool {
+        self == Category::Transfer
+    }
+
+    /// Returns the hash of a deploy of this category, tagged with the lane it is included in.
+    pub(crate) fn deploy_or_transfer_hash(self, deploy_hash: DeployHash) -> DeployOrTransferHash {
+        if self.is_transfer() {
+            DeployOrTransferHash::Transfer(deploy_hash)
+        } else {
+            DeployOrTransferHash::Deploy(deploy_hash)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_hashing::Digest;
+    use casper_types::{bytesrepr::Bytes, RuntimeArgs};
+
+    use super::*;
+
+    #[test]
+    fn should_only_assign_transfers_to_transfer_lane() {
+        let deploy_hash = DeployHash::new(Digest::hash([1u8]));
+        let sessions = [
+            (
+                ExecutableDeployItem::Transfer {
+                    args: RuntimeArgs::new(),
+                },
+                Category::Transfer,
+            ),
+            (
+                ExecutableDeployItem::ModuleBytes {
+                    module_bytes: Bytes::from(vec![0]),
+                    args: RuntimeArgs::new(),
+                },
+                Category::Install,
+            ),
+            (
+                ExecutableDeployItem::StoredContractByName {
+                    name: String::from("contract"),
+                    entry_point: String::from("entry_point"),
+                    args: RuntimeArgs::new(),
+                },
+                Category::Standard,
+            ),
+        ];
+
+        for (session, expected_category) in sessions {
+            let category = Category::of_session(&session);
+            assert_eq!(category, expected_category);
+            assert_eq!(
+                category.deploy_or_transfer_hash(deploy_hash).is_transfer(),
+                category == Category::Transfer
+            );
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use std::fmt::{self, Display, Formatter};
+
+use datasize::DataSize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// The reason a deploy held by this node was dropped without having been executed.
+#[derive(
+    Copy, Clone, DataSize, Default, PartialEq, Eq, Hash, Serialize, Deserialize, Debug, JsonSchema,
+)]
+pub enum ExpiryReason {
+    /// The deploy's time to live elapsed before it was included in a block.
+    #[default]
+    Expired,
+    /// The deploy was found to be invalid when proposing a block, and its time to live elapsed
+    /// before it was included in a block by another validator.
+    InvalidAtProposal,
+}
+
+impl Display for ExpiryReason {
+    fn fmt(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ExpiryReason::Expired => write!(formatter, "expired"),
+            ExpiryReason::InvalidAtProposal => write!(formatter, "invalid at proposal"),
+        }
+    }
+}
@@ -1,9 +1,10 @@
 use datasize::DataSize;
 use serde::{Deserialize, Serialize};
 
+use casper_execution_engine::core::engine_state::executable_deploy_item::ExecutableDeployItemIdentifier;
 use casper_types::Gas;
 
-use super::DeployHeader;
+use super::{DeployCategory, DeployHeader};
 
 /// Information about how much block limit a deploy will consume.
 #[derive(Clone, DataSize, Debug, Deserialize, Serialize)]
@@ -11,5 +12,7 @@ pub(crate) struct Footprint {
     pub(crate) header: DeployHeader,
     pub(crate) gas_estimate: Gas,
     pub(crate) size_estimate: usize,
-    pub(crate) is_transfer: bool,
+    pub(crate) category: DeployCategory,
+    /// Identifies the session code, e.g. the stored contract it calls.
+    pub(crate) session: ExecutableDeployItemIdentifier,
 }
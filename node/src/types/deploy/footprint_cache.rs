@@ -0,0 +1,123 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use super::{Deploy, DeployError, DeployFootprint, DeployId};
+use crate::components::fetcher::FetchItem;
+
+/// The default maximum number of footprints held by a [`FootprintCache`].
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// A cache of deploy footprints, shared between the components which need them.
+///
+/// Footprints are keyed by [`DeployId`], as the size estimate depends on the approvals as well as
+/// on the deploy itself.  Once the cache is full, the oldest footprints are evicted first.
+#[derive(Clone, Debug)]
+pub(crate) struct FootprintCache {
+    inner: Arc<Mutex<Inner>>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    capacity: usize,
+    footprints: HashMap<DeployId, DeployFootprint>,
+    /// The cached IDs, oldest first.
+    insertion_order: VecDeque<DeployId>,
+}
+
+impl FootprintCache {
+    /// Returns a new cache holding at most `capacity` footprints.
+    pub(crate) fn new(capacity: usize) -> Self {
+        FootprintCache {
+            inner: Arc::new(Mutex::new(Inner {
+                capacity,
+                footprints: HashMap::new(),
+                insertion_order: VecDeque::new(),
+            })),
+        }
+    }
+
+    /// Returns the cached footprint of the deploy with the given ID, if any.
+    pub(crate) fn get(&self, deploy_id: &DeployId) -> Option<DeployFootprint> {
+        self.lock().footprints.get(deploy_id).cloned()
+    }
+
+    /// Returns the footprint of `deploy`, computing and caching it unless already cached.
+    pub(crate) fn footprint(&self, deploy: &Deploy) -> Result<DeployFootprint, DeployError> {
+        let deploy_id = deploy.fetch_id();
+        if let Some(footprint) = self.get(&deploy_id) {
+            return Ok(footprint);
+        }
+        let footprint = deploy.footprint()?;
+        self.lock().insert(deploy_id, footprint.clone());
+        Ok(footprint)
+    }
+
+    /// Returns the number of cached footprints.
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.lock().footprints.len()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Inner> {
+        self.inner
+            .lock()
+            .expect("footprint cache lock should not be poisoned")
+    }
+}
+
+impl Default for FootprintCache {
+    fn default() -> Self {
+        FootprintCache::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl Inner {
+    fn insert(&mut self, deploy_id: DeployId, footprint: DeployFootprint) {
+        if self.capacity == 0 || self.footprints.insert(deploy_id, footprint).is_some() {
+            return;
+        }
+        self.insertion_order.push_back(deploy_id);
+        while self.insertion_order.len() > self.capacity {
+            if let Some(evicted) = self.insertion_order.pop_front() {
+                self.footprints.remove(&evicted);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use casper_types::testing::TestRng;
+
+    use super::*;
+
+    #[test]
+    fn should_cache_footprints() {
+        let mut rng = TestRng::new();
+        let cache = FootprintCache::new(2);
+        let deploys: Vec<_> = (0..3).map(|_| Deploy::random(&mut rng)).collect();
+
+        assert!(cache.get(&deploys[0].fetch_id()).is_none());
+        let footprint = cache.footprint(&deploys[0]).unwrap();
+        let cached = cache.get(&deploys[0].fetch_id()).unwrap();
+        assert_eq!(cached.header, footprint.header);
+        assert_eq!(
+            cached.size_estimate,
+            deploys[0].footprint().unwrap().size_estimate
+        );
+
+        // Caching the same deploy again doesn't add an entry.
+        cache.footprint(&deploys[0]).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        // The oldest footprint is evicted once the capacity is exceeded.
+        cache.footprint(&deploys[1]).unwrap();
+        cache.footprint(&deploys[2]).unwrap();
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&deploys[0].fetch_id()).is_none());
+        assert!(cache.get(&deploys[1].fetch_id()).is_some());
+        assert!(cache.get(&deploys[2].fetch_id()).is_some());
+    }
+}
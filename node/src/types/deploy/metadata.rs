@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 
 use casper_types::ExecutionResult;
 
-use crate::types::{BlockHash, BlockHashAndHeight};
+use crate::types::{BlockHash, BlockHashAndHeight, DeployExpiryReason};
 
 /// The deploy mutable metadata.
 ///
@@ -24,6 +24,8 @@ pub(crate) enum MetadataExt {
     Metadata(Metadata),
     /// Holds the hash and height of the block this deploy was included in.
     BlockInfo(BlockHashAndHeight),
+    /// Holds the reason the deploy was dropped by this node without having been executed.
+    Expired(DeployExpiryReason),
     /// No execution results or block information available.
     Empty,
 }
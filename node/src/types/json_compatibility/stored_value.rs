@@ -10,8 +10,8 @@ use serde::{Deserialize, Serialize};
 
 use casper_types::{
     bytesrepr::{self, ToBytes},
-    system::auction::{Bid, EraInfo, UnbondingPurse, WithdrawPurse},
-    CLValue, DeployInfo, StoredValue as ExecutionEngineStoredValue, Transfer,
+    system::auction::{AuctionSnapshot, Bid, EraInfo, UnbondingPurse, WithdrawPurse},
+    Burn, CLValue, DeployInfo, StoredValue as ExecutionEngineStoredValue, Transfer, TransferV2,
 };
 
 use super::{Account, Contract, ContractPackage};
@@ -45,6 +45,12 @@ pub enum StoredValue {
     Withdraw(Vec<WithdrawPurse>),
     /// A collection of unbonding purses
     Unbonding(Vec<UnbondingPurse>),
+    /// The validator weights and delegation totals of an era
+    AuctionSnapshot(AuctionSnapshot),
+    /// A record of a transfer tagged with a memo
+    TransferV2(TransferV2),
+    /// A record of a burn of tokens
+    Burn(Burn),
 }
 
 impl TryFrom<ExecutionEngineStoredValue> for StoredValue {
@@ -75,6 +81,13 @@ impl TryFrom<ExecutionEngineStoredValue> for StoredValue {
             ExecutionEngineStoredValue::Unbonding(unbonding_purses) => {
                 StoredValue::Unbonding(unbonding_purses)
             }
+            ExecutionEngineStoredValue::AuctionSnapshot(auction_snapshot) => {
+                StoredValue::AuctionSnapshot(auction_snapshot)
+            }
+            ExecutionEngineStoredValue::TransferV2(transfer_v2) => {
+                StoredValue::TransferV2(transfer_v2)
+            }
+            ExecutionEngineStoredValue::Burn(burn) => StoredValue::Burn(burn),
         };
 
         Ok(stored_value)
@@ -74,6 +74,14 @@ pub struct NodeConfig {
 
     /// Maximum time a node will wait for an upgrade to commit.
     pub upgrade_timeout: TimeDiff,
+
+    /// Flag which makes the node check its Wasm execution, storage and networking stacks on
+    /// startup, refusing to start if any of them fails.
+    pub run_self_test: bool,
+
+    /// Flag which makes the node serve historical reads from its storage only, without syncing,
+    /// executing blocks, participating in consensus or gossiping, and without accepting deploys.
+    pub archive_mode: bool,
 }
 
 impl Default for NodeConfig {
@@ -87,6 +95,8 @@ impl Default for NodeConfig {
             force_resync: false,
             shutdown_for_upgrade_timeout: DEFAULT_SHUTDOWN_FOR_UPGRADE_TIMEOUT.parse().unwrap(),
             upgrade_timeout: DEFAULT_UPGRADE_TIMEOUT.parse().unwrap(),
+            run_self_test: false,
+            archive_mode: false,
         }
     }
 }
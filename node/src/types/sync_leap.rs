@@ -1697,6 +1697,7 @@ mod tests {
             global_state_update: None,
             activation_point: ActivationPoint::EraId(rng.gen()),
             hard_reset: rng.gen(),
+            compact_auction_state: false,
         };
 
         let result: Vec<_> = sync_leap
@@ -1888,6 +1889,7 @@ mod tests {
             version,
             global_state_update: None,
             hard_reset: false,
+            compact_auction_state: false,
             activation_point: ActivationPoint::EraId(rng.gen()),
         };
 
@@ -1945,6 +1947,7 @@ mod tests {
                 entries: BTreeMap::new(),
             }),
             hard_reset: false,
+            compact_auction_state: false,
             activation_point: ActivationPoint::EraId(rng.gen()),
         };
 
@@ -1965,6 +1968,7 @@ mod tests {
             version: version_2,
             global_state_update: None,
             hard_reset: rng.gen(),
+            compact_auction_state: false,
             activation_point: ActivationPoint::EraId(rng.gen()),
         };
 
@@ -2021,6 +2025,7 @@ mod tests {
             version,
             global_state_update: None,
             hard_reset: false,
+            compact_auction_state: false,
             activation_point: ActivationPoint::EraId(rng.gen()),
         };
 
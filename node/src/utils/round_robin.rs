@@ -10,6 +10,7 @@ use std::{
     hash::Hash,
     num::NonZeroUsize,
     sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
 };
 
 use enum_iterator::IntoEnumIterator;
@@ -56,7 +57,8 @@ struct QueueState<I> {
     ///
     /// Do not modify this unless you are holding the `queue` lock.
     event_count: AtomicUsize,
-    queue: Mutex<VecDeque<I>>,
+    /// The queued items, along with the instant at which each was pushed.
+    queue: Mutex<VecDeque<(I, Instant)>>,
 }
 
 impl<I> QueueState<I> {
@@ -71,14 +73,14 @@ impl<I> QueueState<I> {
     #[cfg(test)]
     async fn drain(&self) -> Vec<I> {
         let mut guard = self.queue.lock().await;
-        let events: Vec<I> = guard.drain(..).collect();
+        let events: Vec<I> = guard.drain(..).map(|(item, _)| item).collect();
         self.event_count.fetch_sub(events.len(), Ordering::SeqCst);
         events
     }
 
     #[inline]
     async fn push_back(&self, element: I) {
-        self.queue.lock().await.push_back(element);
+        self.queue.lock().await.push_back((element, Instant::now()));
         self.event_count.fetch_add(1, Ordering::SeqCst);
     }
 
@@ -128,7 +130,7 @@ where
     ///
     /// A `BTreeMap` is used to make the ordering constant, it will be in the natural order defined
     /// by `Ord` on `K`.
-    queues: BTreeMap<K, &'a VecDeque<I>>,
+    queues: BTreeMap<K, Vec<&'a I>>,
 }
 
 impl<I, K> WeightedRoundRobin<I, K>
@@ -180,7 +182,7 @@ where
         let locks = self.lock_queues().await;
         let mut queues = BTreeMap::new();
         for (kind, guard) in &locks {
-            let queue = &**guard;
+            let queue = guard.iter().map(|(item, _)| item).collect();
             queues.insert(*kind, queue);
         }
 
@@ -189,7 +191,7 @@ where
     }
 
     /// Lock all queues in a well-defined order to avoid deadlocks conditions.
-    async fn lock_queues(&self) -> Vec<(K, MutexGuard<'_, VecDeque<I>>)> {
+    async fn lock_queues(&self) -> Vec<(K, MutexGuard<'_, VecDeque<(I, Instant)>>)> {
         let mut locks = Vec::new();
         for kind in K::into_enum_iter() {
             let queue_guard = self
@@ -257,6 +259,14 @@ where
     ///
     /// Asynchronously waits until a queue is non-empty or panics if an internal error occurred.
     pub(crate) async fn pop(&self) -> (I, K) {
+        let (item, queue, _) = self.pop_with_queue_time().await;
+        (item, queue)
+    }
+
+    /// Returns the next item from queue, along with the time it spent queued.
+    ///
+    /// Asynchronously waits until a queue is non-empty or panics if an internal error occurred.
+    pub(crate) async fn pop_with_queue_time(&self) -> (I, K, Duration) {
         // Safe to `expect` here as the only way for acquiring a permit to fail would be if the
         // `self.total` semaphore were closed.
         self.total.acquire().await.expect("should acquire").forget();
@@ -283,12 +293,12 @@ where
             // We have hit a queue that is not empty. Decrease tickets and pop.
             inner.active_slot.tickets -= 1;
 
-            let item = current_queue
+            let (item, pushed_at) = current_queue
                 .pop_front()
                 // We hold the queue's lock and checked `is_empty` earlier.
                 .expect("item disappeared. this should not happen");
             queue_state.dec_count();
-            break (item, inner.active_slot.key);
+            break (item, inner.active_slot.key, pushed_at.elapsed());
         }
     }
 
@@ -31,9 +31,10 @@ use crate::{
     },
     protocol::Message,
     types::{
-        ApprovalsHash, ApprovalsHashes, Block, BlockExecutionResultsOrChunk, BlockHash,
-        BlockHeader, BlockPayload, Deploy, DeployHashWithApprovals, DeployId, FinalitySignature,
-        FinalitySignatureId, FinalizedBlock, LegacyDeploy, SyncLeap, TrieOrChunk,
+        chainspec::NetworkFeatures, ApprovalsHash, ApprovalsHashes, Block,
+        BlockExecutionResultsOrChunk, BlockHash, BlockHeader, BlockPayload, Deploy,
+        DeployHashWithApprovals, DeployId, FinalitySignature, FinalitySignatureId, FinalizedBlock,
+        LegacyDeploy, SyncLeap, TrieOrChunk,
     },
 };
 
@@ -418,9 +419,9 @@ where
                 //
                 // 1. The required seed bytes for Ed25519 and Secp256k1 are both the same length of
                 //    32 bytes.
-                // 2. While Secp256k1 does not allow the most trivial seed bytes of 0x00..0001, a
-                //    a hash function output seems to satisfy it, and our current hashing scheme
-                //    also output 32 bytes.
+                // 2. While Secp256k1 does not allow the most trivial seed bytes of 0x00..0001, a a
+                //    hash function output seems to satisfy it, and our current hashing scheme also
+                //    output 32 bytes.
                 let seed_bytes = Digest::hash(seed.to_be_bytes()).value();
 
                 match variant {
@@ -506,6 +507,12 @@ impl LargestSpecimen for EraId {
     }
 }
 
+impl LargestSpecimen for NetworkFeatures {
+    fn largest_specimen<E: SizeEstimator>(estimator: &E, cache: &mut Cache) -> Self {
+        NetworkFeatures::from_bits(LargestSpecimen::largest_specimen(estimator, cache))
+    }
+}
+
 impl LargestSpecimen for Timestamp {
     fn largest_specimen<E: SizeEstimator>(_estimator: &E, _cache: &mut Cache) -> Self {
         const MAX_TIMESTAMP_HUMAN_READABLE: u64 = 253_402_300_799;
@@ -814,6 +821,26 @@ pub(crate) fn largest_get_response<E: SizeEstimator>(estimator: &E, cache: &mut
     })
 }
 
+/// Returns the largest `Message::CompressedGetResponse`.
+///
+/// Responses are only sent compressed if compression reduces their size, so the largest compressed
+/// response is bounded by the largest uncompressed one.
+pub(crate) fn largest_compressed_get_response<E: SizeEstimator>(
+    estimator: &E,
+    cache: &mut Cache,
+) -> Message {
+    match largest_get_response(estimator, cache) {
+        Message::GetResponse {
+            tag,
+            serialized_item,
+        } => Message::CompressedGetResponse {
+            tag,
+            compressed_item: serialized_item,
+        },
+        _ => unreachable!("largest_get_response returned a different message variant"),
+    }
+}
+
 /// Returns the largest string allowed for a chain name.
 fn largest_chain_name<E: SizeEstimator>(estimator: &E) -> String {
     string_max_characters(estimator.parameter("network_name_limit"))
@@ -0,0 +1,121 @@
+//! Records of burns of tokens.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    account::AccountHash,
+    bytesrepr::{self, FromBytes, ToBytes},
+    DeployHash, URef, U512,
+};
+
+/// The record of a burn of tokens made by a deploy, stored under [`Key::Burn`](crate::Key::Burn).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct Burn {
+    /// The deploy which burned the tokens.
+    deploy_hash: DeployHash,
+    /// The account which made the deploy.
+    from: AccountHash,
+    /// The purse the tokens were burned from.
+    purse: URef,
+    /// The amount of burned tokens.
+    amount: U512,
+}
+
+impl Burn {
+    /// Creates a new record of `amount` tokens burned from `purse` by the given deploy.
+    pub fn new(deploy_hash: DeployHash, from: AccountHash, purse: URef, amount: U512) -> Self {
+        Burn {
+            deploy_hash,
+            from,
+            purse,
+            amount,
+        }
+    }
+
+    /// Returns the hash of the deploy which burned the tokens.
+    pub fn deploy_hash(&self) -> &DeployHash {
+        &self.deploy_hash
+    }
+
+    /// Returns the account which made the deploy.
+    pub fn from(&self) -> &AccountHash {
+        &self.from
+    }
+
+    /// Returns the purse the tokens were burned from.
+    pub fn purse(&self) -> &URef {
+        &self.purse
+    }
+
+    /// Returns the amount of burned tokens.
+    pub fn amount(&self) -> &U512 {
+        &self.amount
+    }
+}
+
+impl ToBytes for Burn {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.deploy_hash.serialized_length()
+            + self.from.serialized_length()
+            + self.purse.serialized_length()
+            + self.amount.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.deploy_hash.write_bytes(writer)?;
+        self.from.write_bytes(writer)?;
+        self.purse.write_bytes(writer)?;
+        self.amount.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for Burn {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (deploy_hash, rem) = DeployHash::from_bytes(bytes)?;
+        let (from, rem) = AccountHash::from_bytes(rem)?;
+        let (purse, rem) = URef::from_bytes(rem)?;
+        let (amount, rem) = U512::from_bytes(rem)?;
+        Ok((
+            Burn {
+                deploy_hash,
+                from,
+                purse,
+                amount,
+            },
+            rem,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AccessRights;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let burn = Burn::new(
+            DeployHash::new([42; 32]),
+            AccountHash::new([43; 32]),
+            URef::new([44; 32], AccessRights::READ_ADD_WRITE),
+            U512::from(1_000_000),
+        );
+        bytesrepr::test_serialization_roundtrip(&burn);
+    }
+}
@@ -61,8 +61,8 @@ impl EraId {
     /// For `u64::MAX`, this returns `u64::MAX` again: We want to make sure this doesn't panic, and
     /// that era number will never be reached in practice.
     #[must_use]
-    pub fn successor(self) -> EraId {
-        EraId::from(self.0.saturating_add(1))
+    pub const fn successor(self) -> EraId {
+        EraId(self.0.saturating_add(1))
     }
 
     /// Returns the predecessor to current era, or `None` if genesis.
@@ -83,36 +83,36 @@ impl EraId {
 
     /// Returns the current era minus `x`, or `0` if that would be less than `0`.
     #[must_use]
-    pub fn saturating_sub(&self, x: u64) -> EraId {
-        EraId::from(self.0.saturating_sub(x))
+    pub const fn saturating_sub(&self, x: u64) -> EraId {
+        EraId(self.0.saturating_sub(x))
     }
 
     /// Returns the current era plus `x`, or [`EraId::MAX`] if overflow would occur.
     #[must_use]
-    pub fn saturating_add(self, rhs: u64) -> EraId {
+    pub const fn saturating_add(self, rhs: u64) -> EraId {
         EraId(self.0.saturating_add(rhs))
     }
 
     /// Returns the current era times `x`, or [`EraId::MAX`] if overflow would occur.
     #[must_use]
-    pub fn saturating_mul(&self, x: u64) -> EraId {
-        EraId::from(self.0.saturating_mul(x))
+    pub const fn saturating_mul(&self, x: u64) -> EraId {
+        EraId(self.0.saturating_mul(x))
     }
 
     /// Returns whether this is era 0.
-    pub fn is_genesis(&self) -> bool {
+    pub const fn is_genesis(&self) -> bool {
         self.0 == 0
     }
 
     /// Returns little endian bytes.
-    pub fn to_le_bytes(self) -> [u8; 8] {
+    pub const fn to_le_bytes(self) -> [u8; 8] {
         self.0.to_le_bytes()
     }
 
     /// Returns a raw value held by this [`EraId`] instance.
     ///
     /// You should prefer [`From`] trait implementations over this method where possible.
-    pub fn value(self) -> u64 {
+    pub const fn value(self) -> u64 {
         self.0
     }
 }
@@ -37,8 +37,8 @@ use crate::KEY_HASH_LENGTH;
 use crate::{
     account::AccountHash,
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
-    system::auction::{Bid, EraInfo, UnbondingPurse, WithdrawPurse},
-    CLValue, DeployInfo, NamedKey, Transfer, TransferAddr, U128, U256, U512,
+    system::auction::{AuctionSnapshot, Bid, EraInfo, UnbondingPurse, WithdrawPurse},
+    Burn, CLValue, DeployInfo, NamedKey, Transfer, TransferAddr, TransferV2, U128, U256, U512,
 };
 
 #[derive(FromPrimitive, ToPrimitive, Debug)]
@@ -46,6 +46,7 @@ use crate::{
 enum ExecutionResultTag {
     Failure = 0,
     Success = 1,
+    V2 = 2,
 }
 
 impl TryFrom<u8> for ExecutionResultTag {
@@ -95,6 +96,9 @@ enum TransformTag {
     AddKeys = 16,
     Failure = 17,
     WriteUnbonding = 18,
+    WriteAuctionSnapshot = 19,
+    WriteTransferV2 = 20,
+    WriteBurn = 21,
 }
 
 impl TryFrom<u8> for TransformTag {
@@ -176,6 +180,9 @@ pub enum ExecutionResult {
         /// The cost of executing the deploy.
         cost: U512,
     },
+    /// The result of an execution, with its cost broken down into the gas consumed and the amounts
+    /// paid and refunded.
+    V2(ExecutionResultV2),
 }
 
 impl ExecutionResult {
@@ -199,6 +206,7 @@ impl ExecutionResult {
                 transfers: _,
                 cost: _,
             } => ExecutionResultTag::Success,
+            ExecutionResult::V2(_) => ExecutionResultTag::V2,
         }
     }
 }
@@ -234,19 +242,30 @@ impl Distribution<ExecutionResult> for Standard {
             transfers.push(TransferAddr::new(rng.gen()))
         }
 
-        if rng.gen() {
-            ExecutionResult::Failure {
+        match rng.gen_range(0..3) {
+            0 => ExecutionResult::Failure {
                 effect: execution_effect,
                 transfers,
                 cost: rng.gen::<u64>().into(),
                 error_message: format!("Error message {}", rng.gen::<u64>()),
-            }
-        } else {
-            ExecutionResult::Success {
+            },
+            1 => ExecutionResult::Success {
                 effect: execution_effect,
                 transfers,
                 cost: rng.gen::<u64>().into(),
-            }
+            },
+            _ => ExecutionResult::V2(ExecutionResultV2 {
+                effect: execution_effect,
+                transfers,
+                limit: rng.gen::<u64>().into(),
+                consumed: rng.gen::<u64>().into(),
+                gas_price: rng.gen(),
+                paid_amount: rng.gen::<u64>().into(),
+                refund: rng.gen::<u64>().into(),
+                error_message: rng
+                    .gen::<bool>()
+                    .then(|| format!("Error message {}", rng.gen::<u64>())),
+            }),
         }
     }
 }
@@ -278,6 +297,9 @@ impl ToBytes for ExecutionResult {
                 buffer.extend(transfers.to_bytes()?);
                 buffer.extend(cost.to_bytes()?);
             }
+            ExecutionResult::V2(execution_result) => {
+                buffer.extend(execution_result.to_bytes()?);
+            }
         }
         Ok(buffer)
     }
@@ -305,6 +327,7 @@ impl ToBytes for ExecutionResult {
                         + transfers.serialized_length()
                         + cost.serialized_length()
                 }
+                ExecutionResult::V2(execution_result) => execution_result.serialized_length(),
             }
     }
 }
@@ -337,10 +360,92 @@ impl FromBytes for ExecutionResult {
                 };
                 Ok((execution_result, remainder))
             }
+            ExecutionResultTag::V2 => {
+                let (execution_result, remainder) = ExecutionResultV2::from_bytes(remainder)?;
+                Ok((ExecutionResult::V2(execution_result), remainder))
+            }
         }
     }
 }
 
+/// The result of executing a single deploy, reporting separately the gas consumed and the amounts
+/// paid and refunded.
+///
+/// The amount the payer was charged is `paid_amount - refund`, which differs from
+/// `consumed * gas_price` whenever only part of the unspent payment is refunded.
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ExecutionResultV2 {
+    /// The effect of executing the deploy.
+    pub effect: ExecutionEffect,
+    /// A record of Transfers performed while executing the deploy.
+    pub transfers: Vec<TransferAddr>,
+    /// The maximum amount of gas the deploy could consume, as bought by the paid amount.
+    pub limit: U512,
+    /// The amount of gas consumed executing the deploy.
+    pub consumed: U512,
+    /// The price of one unit of gas in motes.
+    pub gas_price: u64,
+    /// The amount in motes paid for the execution of the deploy.
+    pub paid_amount: U512,
+    /// The part of the paid amount refunded to the payer after execution, in motes.
+    pub refund: U512,
+    /// The error message associated with executing the deploy, if it failed.
+    pub error_message: Option<String>,
+}
+
+impl ToBytes for ExecutionResultV2 {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        buffer.extend(self.effect.to_bytes()?);
+        buffer.extend(self.transfers.to_bytes()?);
+        buffer.extend(self.limit.to_bytes()?);
+        buffer.extend(self.consumed.to_bytes()?);
+        buffer.extend(self.gas_price.to_bytes()?);
+        buffer.extend(self.paid_amount.to_bytes()?);
+        buffer.extend(self.refund.to_bytes()?);
+        buffer.extend(self.error_message.to_bytes()?);
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.effect.serialized_length()
+            + self.transfers.serialized_length()
+            + self.limit.serialized_length()
+            + self.consumed.serialized_length()
+            + self.gas_price.serialized_length()
+            + self.paid_amount.serialized_length()
+            + self.refund.serialized_length()
+            + self.error_message.serialized_length()
+    }
+}
+
+impl FromBytes for ExecutionResultV2 {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (effect, remainder) = ExecutionEffect::from_bytes(bytes)?;
+        let (transfers, remainder) = Vec::<TransferAddr>::from_bytes(remainder)?;
+        let (limit, remainder) = U512::from_bytes(remainder)?;
+        let (consumed, remainder) = U512::from_bytes(remainder)?;
+        let (gas_price, remainder) = u64::from_bytes(remainder)?;
+        let (paid_amount, remainder) = U512::from_bytes(remainder)?;
+        let (refund, remainder) = U512::from_bytes(remainder)?;
+        let (error_message, remainder) = Option::<String>::from_bytes(remainder)?;
+        let execution_result = ExecutionResultV2 {
+            effect,
+            transfers,
+            limit,
+            consumed,
+            gas_price,
+            paid_amount,
+            refund,
+            error_message,
+        };
+        Ok((execution_result, remainder))
+    }
+}
+
 /// The journal of execution transforms from a single deploy.
 #[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Default, Debug)]
 #[cfg_attr(feature = "datasize", derive(DataSize))]
@@ -554,6 +659,12 @@ pub enum Transform {
     Failure(String),
     /// Writes the given Unbonding to global state.
     WriteUnbonding(Vec<UnbondingPurse>),
+    /// Writes the given AuctionSnapshot to global state.
+    WriteAuctionSnapshot(AuctionSnapshot),
+    /// Writes the given TransferV2 to global state.
+    WriteTransferV2(TransferV2),
+    /// Writes the given Burn to global state.
+    WriteBurn(Burn),
 }
 
 impl Transform {
@@ -578,6 +689,9 @@ impl Transform {
             Transform::AddKeys(_) => TransformTag::AddKeys,
             Transform::Failure(_) => TransformTag::Failure,
             Transform::WriteUnbonding(_) => TransformTag::WriteUnbonding,
+            Transform::WriteAuctionSnapshot(_) => TransformTag::WriteAuctionSnapshot,
+            Transform::WriteTransferV2(_) => TransformTag::WriteTransferV2,
+            Transform::WriteBurn(_) => TransformTag::WriteBurn,
         }
     }
 }
@@ -638,6 +752,15 @@ impl ToBytes for Transform {
             Transform::WriteUnbonding(value) => {
                 buffer.extend(value.to_bytes()?);
             }
+            Transform::WriteAuctionSnapshot(value) => {
+                buffer.extend(value.to_bytes()?);
+            }
+            Transform::WriteTransferV2(value) => {
+                buffer.extend(value.to_bytes()?);
+            }
+            Transform::WriteBurn(value) => {
+                buffer.extend(value.to_bytes()?);
+            }
         }
         Ok(buffer)
     }
@@ -663,6 +786,9 @@ impl ToBytes for Transform {
             Transform::WriteBid(value) => value.serialized_length(),
             Transform::WriteWithdraw(value) => value.serialized_length(),
             Transform::WriteUnbonding(value) => value.serialized_length(),
+            Transform::WriteAuctionSnapshot(value) => value.serialized_length(),
+            Transform::WriteTransferV2(value) => value.serialized_length(),
+            Transform::WriteBurn(value) => value.serialized_length(),
         };
         U8_SERIALIZED_LENGTH + body_len
     }
@@ -738,6 +864,18 @@ impl FromBytes for Transform {
                     <Vec<UnbondingPurse> as FromBytes>::from_bytes(remainder)?;
                 Ok((Transform::WriteUnbonding(unbonding_purses), remainder))
             }
+            TransformTag::WriteAuctionSnapshot => {
+                let (auction_snapshot, remainder) = AuctionSnapshot::from_bytes(remainder)?;
+                Ok((Transform::WriteAuctionSnapshot(auction_snapshot), remainder))
+            }
+            TransformTag::WriteTransferV2 => {
+                let (transfer_v2, remainder) = TransferV2::from_bytes(remainder)?;
+                Ok((Transform::WriteTransferV2(transfer_v2), remainder))
+            }
+            TransformTag::WriteBurn => {
+                let (burn, remainder) = Burn::from_bytes(remainder)?;
+                Ok((Transform::WriteBurn(burn), remainder))
+            }
         }
     }
 }
@@ -0,0 +1,141 @@
+//! Chained checked arithmetic on numbers such as [`U512`] and [`Ratio<U512>`].
+//!
+//! Wrapping operands in a [`MaybeNum`] allows a computation to be written using ordinary
+//! arithmetic operators, while any overflow, underflow or division by zero is carried through to
+//! the result instead of panicking, to be checked once at the end.
+
+use core::ops::{Add, Div, Mul, Sub};
+
+use num_integer::Integer;
+use num_rational::Ratio;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Zero};
+
+use crate::U512;
+
+/// A number, or `None` if a checked operation producing it failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaybeNum<T>(Option<T>);
+
+impl<T> MaybeNum<T> {
+    /// Wraps `value`.
+    pub const fn new(value: T) -> Self {
+        MaybeNum(Some(value))
+    }
+
+    /// Wraps the result of a checked operation performed outside of `MaybeNum`.
+    pub const fn from_option(value: Option<T>) -> Self {
+        MaybeNum(value)
+    }
+
+    /// Returns the number, or `None` if any operation producing it failed.
+    pub fn into_option(self) -> Option<T> {
+        self.0
+    }
+
+    /// Returns the number, or `error` if any operation producing it failed.
+    pub fn ok_or<E>(self, error: E) -> Result<T, E> {
+        self.0.ok_or(error)
+    }
+}
+
+impl<T: Clone + Integer> MaybeNum<Ratio<T>> {
+    /// Creates the ratio `numer / denom`, or `None` if `denom` is zero.
+    pub fn ratio(numer: T, denom: T) -> Self {
+        if denom.is_zero() {
+            return MaybeNum(None);
+        }
+        MaybeNum::new(Ratio::new(numer, denom))
+    }
+
+    /// Creates a ratio equal to the integer `value`.
+    pub fn from_integer(value: T) -> Self {
+        MaybeNum::new(Ratio::from_integer(value))
+    }
+
+    /// Rounds the ratio towards zero to the nearest integer.
+    pub fn to_integer(self) -> MaybeNum<T> {
+        MaybeNum(self.0.map(|ratio| ratio.to_integer()))
+    }
+}
+
+impl MaybeNum<U512> {
+    /// Converts the number into a ratio.
+    pub fn into_ratio(self) -> MaybeNum<Ratio<U512>> {
+        MaybeNum(self.0.map(Ratio::from_integer))
+    }
+}
+
+impl<T> From<T> for MaybeNum<T> {
+    fn from(value: T) -> Self {
+        MaybeNum::new(value)
+    }
+}
+
+macro_rules! impl_checked_op {
+    ($op_trait:ident, $op_fn:ident, $checked_trait:ident, $checked_fn:ident) => {
+        impl<T: $checked_trait> $op_trait for MaybeNum<T> {
+            type Output = MaybeNum<T>;
+
+            fn $op_fn(self, rhs: MaybeNum<T>) -> MaybeNum<T> {
+                match (self.0, rhs.0) {
+                    (Some(lhs), Some(rhs)) => MaybeNum(lhs.$checked_fn(&rhs)),
+                    _ => MaybeNum(None),
+                }
+            }
+        }
+
+        impl<T: $checked_trait> $op_trait<T> for MaybeNum<T> {
+            type Output = MaybeNum<T>;
+
+            fn $op_fn(self, rhs: T) -> MaybeNum<T> {
+                MaybeNum(self.0.and_then(|lhs| lhs.$checked_fn(&rhs)))
+            }
+        }
+    };
+}
+
+impl_checked_op!(Add, add, CheckedAdd, checked_add);
+impl_checked_op!(Sub, sub, CheckedSub, checked_sub);
+impl_checked_op!(Mul, mul, CheckedMul, checked_mul);
+impl_checked_op!(Div, div, CheckedDiv, checked_div);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_chain_checked_operations() {
+        let result =
+            (MaybeNum::new(U512::from(10)) * U512::from(3) - U512::from(5)) / U512::from(5);
+        assert_eq!(result.into_option(), Some(U512::from(5)));
+
+        let reward = MaybeNum::<Ratio<U512>>::ratio(U512::from(2), U512::from(3))
+            * MaybeNum::new(U512::from(100)).into_ratio();
+        assert_eq!(reward.to_integer().into_option(), Some(U512::from(66)));
+    }
+
+    #[test]
+    fn should_propagate_failures() {
+        assert_eq!(
+            (MaybeNum::new(U512::MAX) + U512::one() - U512::MAX).into_option(),
+            None
+        );
+        assert_eq!(
+            (MaybeNum::new(U512::zero()) - U512::one() + U512::MAX).into_option(),
+            None
+        );
+        assert_eq!(
+            (MaybeNum::new(U512::one()) / U512::zero()).into_option(),
+            None
+        );
+        assert_eq!(
+            MaybeNum::<Ratio<U512>>::ratio(U512::one(), U512::zero()).into_option(),
+            None
+        );
+        assert_eq!(
+            (MaybeNum::<Ratio<U512>>::from_integer(U512::MAX) * Ratio::from_integer(U512::from(2)))
+                .into_option(),
+            None
+        );
+    }
+}
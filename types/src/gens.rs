@@ -19,17 +19,21 @@ use crate::{
     },
     crypto::gens::public_key_arb_no_system,
     system::auction::{
-        gens::era_info_arb, Bid, DelegationRate, Delegator, UnbondingPurse, WithdrawPurse,
-        DELEGATION_RATE_DENOMINATOR,
+        gens::{auction_snapshot_arb, era_info_arb},
+        Bid, DelegationRate, Delegator, UnbondingPurse, WithdrawPurse, DELEGATION_RATE_DENOMINATOR,
     },
     transfer::TransferAddr,
-    AccessRights, CLType, CLValue, Contract, ContractHash, ContractPackage, ContractVersionKey,
-    ContractWasm, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, EraId, Group, Key,
-    NamedArg, Parameter, Phase, ProtocolVersion, SemVer, StoredValue, URef, U128, U256, U512,
+    AccessRights, Burn, CLType, CLValue, Contract, ContractHash, ContractPackage,
+    ContractVersionKey, ContractWasm, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints,
+    EraId, Group, Key, NamedArg, Parameter, Phase, ProtocolVersion, SemVer, StoredValue, URef,
+    U128, U256, U512,
 };
 
 use crate::deploy_info::gens::{deploy_hash_arb, transfer_addr_arb};
-pub use crate::{deploy_info::gens::deploy_info_arb, transfer::gens::transfer_arb};
+pub use crate::{
+    deploy_info::gens::deploy_info_arb,
+    transfer::gens::{transfer_arb, transfer_v2_arb},
+};
 
 pub fn u8_slice_32() -> impl Strategy<Value = [u8; 32]> {
     collection::vec(any::<u8>(), 32).prop_map(|b| {
@@ -95,6 +99,14 @@ pub fn key_arb() -> impl Strategy<Value = Key> {
         account_hash_arb().prop_map(Key::Withdraw),
         u8_slice_32().prop_map(Key::Dictionary),
         Just(Key::EraSummary),
+        era_id_arb().prop_map(Key::AuctionSnapshot),
+        u8_slice_32().prop_map(Key::StorageLease),
+        u8_slice_32().prop_map(Key::Burn),
+        (era_id_arb(), u8_slice_32()).prop_map(|(era_id, bytes)| {
+            let mut id = [0; 24];
+            id.copy_from_slice(&bytes[..24]);
+            Key::StorageLeaseExpiry(era_id, id)
+        }),
     ]
 }
 
@@ -498,6 +510,16 @@ fn unbondings_arb(size: impl Into<SizeRange>) -> impl Strategy<Value = Vec<Unbon
     collection::vec(unbonding_arb(), size)
 }
 
+pub fn burn_arb() -> impl Strategy<Value = Burn> {
+    (
+        deploy_hash_arb(),
+        account_hash_arb(),
+        uref_arb(),
+        u512_arb(),
+    )
+        .prop_map(|(deploy_hash, from, purse, amount)| Burn::new(deploy_hash, from, purse, amount))
+}
+
 pub fn stored_value_arb() -> impl Strategy<Value = StoredValue> {
     prop_oneof![
         cl_value_arb().prop_map(StoredValue::CLValue),
@@ -510,7 +532,10 @@ pub fn stored_value_arb() -> impl Strategy<Value = StoredValue> {
         era_info_arb(1..10).prop_map(StoredValue::EraInfo),
         bid_arb(0..100).prop_map(|bid| StoredValue::Bid(Box::new(bid))),
         withdraws_arb(1..50).prop_map(StoredValue::Withdraw),
-        unbondings_arb(1..50).prop_map(StoredValue::Unbonding)
+        unbondings_arb(1..50).prop_map(StoredValue::Unbonding),
+        auction_snapshot_arb(1..10).prop_map(StoredValue::AuctionSnapshot),
+        transfer_v2_arb().prop_map(StoredValue::TransferV2),
+        burn_arb().prop_map(StoredValue::Burn)
     ]
     .prop_map(|stored_value|
         // The following match statement is here only to make sure
@@ -527,5 +552,8 @@ pub fn stored_value_arb() -> impl Strategy<Value = StoredValue> {
             StoredValue::Bid(_) => stored_value,
             StoredValue::Withdraw(_) => stored_value,
             StoredValue::Unbonding(_) => stored_value,
+            StoredValue::AuctionSnapshot(_) => stored_value,
+            StoredValue::TransferV2(_) => stored_value,
+            StoredValue::Burn(_) => stored_value,
         })
 }
@@ -47,6 +47,10 @@ const SYSTEM_CONTRACT_REGISTRY_PREFIX: &str = "system-contract-registry-";
 const ERA_SUMMARY_PREFIX: &str = "era-summary-";
 const CHAINSPEC_REGISTRY_PREFIX: &str = "chainspec-registry-";
 const CHECKSUM_REGISTRY_PREFIX: &str = "checksum-registry-";
+const AUCTION_SNAPSHOT_PREFIX: &str = "auction-snapshot-";
+const STORAGE_LEASE_PREFIX: &str = "storage-lease-";
+const BURN_PREFIX: &str = "burn-";
+const STORAGE_LEASE_EXPIRY_PREFIX: &str = "storage-lease-expiry-";
 
 /// The number of bytes in a Blake2b hash
 pub const BLAKE2B_DIGEST_LENGTH: usize = 32;
@@ -58,6 +62,8 @@ pub const KEY_TRANSFER_LENGTH: usize = TRANSFER_ADDR_LENGTH;
 pub const KEY_DEPLOY_INFO_LENGTH: usize = DEPLOY_HASH_LENGTH;
 /// The number of bytes in a [`Key::Dictionary`].
 pub const KEY_DICTIONARY_LENGTH: usize = 32;
+/// The number of bytes identifying a storage lease in a [`Key::StorageLeaseExpiry`].
+pub const KEY_STORAGE_LEASE_EXPIRY_ID_LENGTH: usize = 24;
 /// The maximum length for a `dictionary_item_key`.
 pub const DICTIONARY_ITEM_KEY_MAX_LENGTH: usize = 128;
 const PADDING_BYTES: [u8; 32] = [0u8; 32];
@@ -80,6 +86,12 @@ const KEY_CHAINSPEC_REGISTRY_SERIALIZED_LENGTH: usize =
     KEY_ID_SERIALIZED_LENGTH + PADDING_BYTES.len();
 const KEY_CHECKSUM_REGISTRY_SERIALIZED_LENGTH: usize =
     KEY_ID_SERIALIZED_LENGTH + PADDING_BYTES.len();
+const KEY_AUCTION_SNAPSHOT_SERIALIZED_LENGTH: usize =
+    KEY_ID_SERIALIZED_LENGTH + U64_SERIALIZED_LENGTH;
+const KEY_STORAGE_LEASE_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LENGTH;
+const KEY_BURN_SERIALIZED_LENGTH: usize = KEY_ID_SERIALIZED_LENGTH + KEY_HASH_LENGTH;
+const KEY_STORAGE_LEASE_EXPIRY_SERIALIZED_LENGTH: usize =
+    KEY_ID_SERIALIZED_LENGTH + U64_SERIALIZED_LENGTH + KEY_STORAGE_LEASE_EXPIRY_ID_LENGTH;
 
 /// An alias for [`Key`]s hash variant.
 pub type HashAddr = [u8; KEY_HASH_LENGTH];
@@ -87,6 +99,9 @@ pub type HashAddr = [u8; KEY_HASH_LENGTH];
 /// An alias for [`Key`]s dictionary variant.
 pub type DictionaryAddr = [u8; KEY_DICTIONARY_LENGTH];
 
+/// An alias for the identifier of a storage lease in [`Key`]s storage lease expiry variant.
+pub type StorageLeaseExpiryId = [u8; KEY_STORAGE_LEASE_EXPIRY_ID_LENGTH];
+
 #[allow(missing_docs)]
 #[derive(Debug, Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Hash)]
 #[repr(u8)]
@@ -106,6 +121,10 @@ pub enum KeyTag {
     Unbond = 12,
     ChainspecRegistry = 13,
     ChecksumRegistry = 14,
+    AuctionSnapshot = 15,
+    StorageLease = 16,
+    Burn = 17,
+    StorageLeaseExpiry = 18,
 }
 
 /// The type under which data (e.g. [`CLValue`](crate::CLValue)s, smart contracts, user accounts)
@@ -145,6 +164,15 @@ pub enum Key {
     ChainspecRegistry,
     /// A `Key` variant under which we store a registry of checksums.
     ChecksumRegistry,
+    /// A `Key` under which we store the auction snapshot of an era.
+    AuctionSnapshot(EraId),
+    /// A `Key` under which we store the storage lease of another key, derived by hashing that key.
+    StorageLease(HashAddr),
+    /// A `Key` under which we store the record of a burn of tokens.
+    Burn(HashAddr),
+    /// A `Key` indexing a storage lease under the era in which it is due to expire, so that the
+    /// leases due in an era can be found by prefix.
+    StorageLeaseExpiry(EraId, StorageLeaseExpiryId),
 }
 
 /// Errors produced when converting a `String` into a `Key`.
@@ -181,6 +209,14 @@ pub enum FromStrError {
     ChainspecRegistry(String),
     /// Checksum registry error.
     ChecksumRegistry(String),
+    /// Auction snapshot parse error.
+    AuctionSnapshot(String),
+    /// Storage lease parse error.
+    StorageLease(String),
+    /// Burn parse error.
+    Burn(String),
+    /// Storage lease expiry parse error.
+    StorageLeaseExpiry(String),
     /// Unknown prefix.
     UnknownPrefix,
 }
@@ -239,6 +275,16 @@ impl Display for FromStrError {
             FromStrError::ChecksumRegistry(error) => {
                 write!(f, "checksum-registry-key from string error: {}", error)
             }
+            FromStrError::AuctionSnapshot(error) => {
+                write!(f, "auction-snapshot-key from string error: {}", error)
+            }
+            FromStrError::StorageLease(error) => {
+                write!(f, "storage-lease-key from string error: {}", error)
+            }
+            FromStrError::Burn(error) => write!(f, "burn-key from string error: {}", error),
+            FromStrError::StorageLeaseExpiry(error) => {
+                write!(f, "storage-lease-expiry-key from string error: {}", error)
+            }
             FromStrError::UnknownPrefix => write!(f, "unknown prefix for key"),
         }
     }
@@ -264,6 +310,10 @@ impl Key {
             Key::Unbond(_) => String::from("Key::Unbond"),
             Key::ChainspecRegistry => String::from("Key::ChainspecRegistry"),
             Key::ChecksumRegistry => String::from("Key::ChecksumRegistry"),
+            Key::AuctionSnapshot(_) => String::from("Key::AuctionSnapshot"),
+            Key::StorageLease(_) => String::from("Key::StorageLease"),
+            Key::Burn(_) => String::from("Key::Burn"),
+            Key::StorageLeaseExpiry(..) => String::from("Key::StorageLeaseExpiry"),
         }
     }
 
@@ -347,6 +397,23 @@ impl Key {
                     base16::encode_lower(&PADDING_BYTES)
                 )
             }
+            Key::AuctionSnapshot(era_id) => {
+                format!("{}{}", AUCTION_SNAPSHOT_PREFIX, era_id.value())
+            }
+            Key::StorageLease(addr) => {
+                format!("{}{}", STORAGE_LEASE_PREFIX, base16::encode_lower(&addr))
+            }
+            Key::Burn(addr) => {
+                format!("{}{}", BURN_PREFIX, base16::encode_lower(&addr))
+            }
+            Key::StorageLeaseExpiry(era_id, id) => {
+                format!(
+                    "{}{}-{}",
+                    STORAGE_LEASE_EXPIRY_PREFIX,
+                    era_id.value(),
+                    base16::encode_lower(&id)
+                )
+            }
         }
     }
 
@@ -474,6 +541,42 @@ impl Key {
             return Ok(Key::ChecksumRegistry);
         }
 
+        if let Some(era_id_str) = input.strip_prefix(AUCTION_SNAPSHOT_PREFIX) {
+            let era_id = EraId::from_str(era_id_str)
+                .map_err(|error| FromStrError::AuctionSnapshot(error.to_string()))?;
+            return Ok(Key::AuctionSnapshot(era_id));
+        }
+
+        // Checked before `STORAGE_LEASE_PREFIX`, which is a prefix of it.
+        if let Some(era_id_and_hex) = input.strip_prefix(STORAGE_LEASE_EXPIRY_PREFIX) {
+            let (era_id_str, hex) = era_id_and_hex.split_once('-').ok_or_else(|| {
+                FromStrError::StorageLeaseExpiry("missing storage lease id".to_string())
+            })?;
+            let era_id = EraId::from_str(era_id_str)
+                .map_err(|error| FromStrError::StorageLeaseExpiry(error.to_string()))?;
+            let id = checksummed_hex::decode(hex)
+                .map_err(|error| FromStrError::StorageLeaseExpiry(error.to_string()))?;
+            let id = StorageLeaseExpiryId::try_from(id.as_ref())
+                .map_err(|error| FromStrError::StorageLeaseExpiry(error.to_string()))?;
+            return Ok(Key::StorageLeaseExpiry(era_id, id));
+        }
+
+        if let Some(hex) = input.strip_prefix(STORAGE_LEASE_PREFIX) {
+            let addr = checksummed_hex::decode(hex)
+                .map_err(|error| FromStrError::StorageLease(error.to_string()))?;
+            let addr = HashAddr::try_from(addr.as_ref())
+                .map_err(|error| FromStrError::StorageLease(error.to_string()))?;
+            return Ok(Key::StorageLease(addr));
+        }
+
+        if let Some(hex) = input.strip_prefix(BURN_PREFIX) {
+            let addr = checksummed_hex::decode(hex)
+                .map_err(|error| FromStrError::Burn(error.to_string()))?;
+            let addr = HashAddr::try_from(addr.as_ref())
+                .map_err(|error| FromStrError::Burn(error.to_string()))?;
+            return Ok(Key::Burn(addr));
+        }
+
         Err(FromStrError::UnknownPrefix)
     }
 
@@ -575,6 +678,42 @@ impl Key {
         }
         false
     }
+
+    /// Creates a new [`Key::StorageLease`] variant under which the storage lease of `leased_key`
+    /// is stored.
+    pub fn storage_lease(leased_key: &Key) -> Key {
+        Key::StorageLease(storage_lease_addr(leased_key))
+    }
+
+    /// Creates a new [`Key::StorageLeaseExpiry`] variant indexing the storage lease of
+    /// `leased_key` as due to expire in `era_id`.
+    pub fn storage_lease_expiry(era_id: EraId, leased_key: &Key) -> Key {
+        let mut id = StorageLeaseExpiryId::default();
+        id.copy_from_slice(&storage_lease_addr(leased_key)[..KEY_STORAGE_LEASE_EXPIRY_ID_LENGTH]);
+        Key::StorageLeaseExpiry(era_id, id)
+    }
+
+    /// Returns the prefix shared by the serialized [`Key::StorageLeaseExpiry`]s of all the storage
+    /// leases due to expire in `era_id`.
+    pub fn storage_lease_expiry_prefix(era_id: EraId) -> Vec<u8> {
+        let mut prefix = Vec::with_capacity(KEY_ID_SERIALIZED_LENGTH + U64_SERIALIZED_LENGTH);
+        prefix.push(KeyTag::StorageLeaseExpiry as u8);
+        prefix.extend_from_slice(&era_id.value().to_le_bytes());
+        prefix
+    }
+}
+
+/// Returns the address of the [`Key::StorageLease`] under which the storage lease of `leased_key`
+/// is stored.
+fn storage_lease_addr(leased_key: &Key) -> HashAddr {
+    // NOTE: Expect below is safe because the length passed is supported.
+    let mut hasher = VarBlake2b::new(BLAKE2B_DIGEST_LENGTH).expect("should create hasher");
+    // NOTE: Serializing a key cannot fail.
+    hasher.update(leased_key.to_bytes().expect("should serialize key"));
+    // NOTE: Assumed safe as size of `HashAddr` equals to the output provided by hasher.
+    let mut addr = HashAddr::default();
+    hasher.finalize_variable(|hash| addr.clone_from_slice(hash));
+    addr
 }
 
 impl Display for Key {
@@ -621,6 +760,17 @@ impl Display for Key {
                     base16::encode_lower(&PADDING_BYTES)
                 )
             }
+            Key::AuctionSnapshot(era_id) => write!(f, "Key::AuctionSnapshot({})", era_id),
+            Key::StorageLease(addr) => {
+                write!(f, "Key::StorageLease({})", base16::encode_lower(addr))
+            }
+            Key::Burn(addr) => write!(f, "Key::Burn({})", base16::encode_lower(addr)),
+            Key::StorageLeaseExpiry(era_id, id) => write!(
+                f,
+                "Key::StorageLeaseExpiry({}, {})",
+                era_id,
+                base16::encode_lower(id)
+            ),
         }
     }
 }
@@ -649,6 +799,10 @@ impl Tagged<KeyTag> for Key {
             Key::Unbond(_) => KeyTag::Unbond,
             Key::ChainspecRegistry => KeyTag::ChainspecRegistry,
             Key::ChecksumRegistry => KeyTag::ChecksumRegistry,
+            Key::AuctionSnapshot(_) => KeyTag::AuctionSnapshot,
+            Key::StorageLease(_) => KeyTag::StorageLease,
+            Key::Burn(_) => KeyTag::Burn,
+            Key::StorageLeaseExpiry(..) => KeyTag::StorageLeaseExpiry,
         }
     }
 }
@@ -722,6 +876,10 @@ impl ToBytes for Key {
             Key::Unbond(_) => KEY_UNBOND_SERIALIZED_LENGTH,
             Key::ChainspecRegistry => KEY_CHAINSPEC_REGISTRY_SERIALIZED_LENGTH,
             Key::ChecksumRegistry => KEY_CHECKSUM_REGISTRY_SERIALIZED_LENGTH,
+            Key::AuctionSnapshot(_) => KEY_AUCTION_SNAPSHOT_SERIALIZED_LENGTH,
+            Key::StorageLease(_) => KEY_STORAGE_LEASE_SERIALIZED_LENGTH,
+            Key::Burn(_) => KEY_BURN_SERIALIZED_LENGTH,
+            Key::StorageLeaseExpiry(..) => KEY_STORAGE_LEASE_EXPIRY_SERIALIZED_LENGTH,
         }
     }
 
@@ -739,6 +897,13 @@ impl ToBytes for Key {
             Key::Withdraw(account_hash) => account_hash.write_bytes(writer),
             Key::Dictionary(addr) => addr.write_bytes(writer),
             Key::Unbond(account_hash) => account_hash.write_bytes(writer),
+            Key::AuctionSnapshot(era_id) => era_id.write_bytes(writer),
+            Key::StorageLease(addr) => addr.write_bytes(writer),
+            Key::Burn(addr) => addr.write_bytes(writer),
+            Key::StorageLeaseExpiry(era_id, id) => {
+                era_id.write_bytes(writer)?;
+                id.write_bytes(writer)
+            }
             Key::SystemContractRegistry
             | Key::EraSummary
             | Key::ChainspecRegistry
@@ -811,6 +976,23 @@ impl FromBytes for Key {
                 let (_, rem) = <[u8; 32]>::from_bytes(remainder)?;
                 Ok((Key::ChecksumRegistry, rem))
             }
+            tag if tag == KeyTag::AuctionSnapshot as u8 => {
+                let (era_id, rem) = EraId::from_bytes(remainder)?;
+                Ok((Key::AuctionSnapshot(era_id), rem))
+            }
+            tag if tag == KeyTag::StorageLease as u8 => {
+                let (addr, rem) = HashAddr::from_bytes(remainder)?;
+                Ok((Key::StorageLease(addr), rem))
+            }
+            tag if tag == KeyTag::Burn as u8 => {
+                let (addr, rem) = HashAddr::from_bytes(remainder)?;
+                Ok((Key::Burn(addr), rem))
+            }
+            tag if tag == KeyTag::StorageLeaseExpiry as u8 => {
+                let (era_id, rem) = EraId::from_bytes(remainder)?;
+                let (id, rem) = StorageLeaseExpiryId::from_bytes(rem)?;
+                Ok((Key::StorageLeaseExpiry(era_id, id), rem))
+            }
             _ => Err(Error::Formatting),
         }
     }
@@ -836,12 +1018,16 @@ fn please_add_to_distribution_impl(key: Key) {
         Key::Unbond(_) => unimplemented!(),
         Key::ChainspecRegistry => unimplemented!(),
         Key::ChecksumRegistry => unimplemented!(),
+        Key::AuctionSnapshot(_) => unimplemented!(),
+        Key::StorageLease(_) => unimplemented!(),
+        Key::Burn(_) => unimplemented!(),
+        Key::StorageLeaseExpiry(..) => unimplemented!(),
     }
 }
 
 impl Distribution<Key> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Key {
-        match rng.gen_range(0..=14) {
+        match rng.gen_range(0..=18) {
             0 => Key::Account(rng.gen()),
             1 => Key::Hash(rng.gen()),
             2 => Key::URef(rng.gen()),
@@ -857,6 +1043,10 @@ impl Distribution<Key> for Standard {
             12 => Key::Unbond(rng.gen()),
             13 => Key::ChainspecRegistry,
             14 => Key::ChecksumRegistry,
+            15 => Key::AuctionSnapshot(rng.gen()),
+            16 => Key::StorageLease(rng.gen()),
+            17 => Key::Burn(rng.gen()),
+            18 => Key::StorageLeaseExpiry(rng.gen(), rng.gen()),
             _ => unreachable!(),
         }
     }
@@ -882,6 +1072,10 @@ mod serde_helpers {
         Unbond(String),
         ChainspecRegistry(String),
         ChecksumRegistry(String),
+        AuctionSnapshot(String),
+        StorageLease(String),
+        Burn(String),
+        StorageLeaseExpiry(String),
     }
 
     impl From<&Key> for HumanReadable {
@@ -905,6 +1099,10 @@ mod serde_helpers {
                 Key::Unbond(_) => HumanReadable::Unbond(formatted_string),
                 Key::ChainspecRegistry => HumanReadable::ChainspecRegistry(formatted_string),
                 Key::ChecksumRegistry => HumanReadable::ChecksumRegistry(formatted_string),
+                Key::AuctionSnapshot(_) => HumanReadable::AuctionSnapshot(formatted_string),
+                Key::StorageLease(_) => HumanReadable::StorageLease(formatted_string),
+                Key::Burn(_) => HumanReadable::Burn(formatted_string),
+                Key::StorageLeaseExpiry(..) => HumanReadable::StorageLeaseExpiry(formatted_string),
             }
         }
     }
@@ -928,7 +1126,11 @@ mod serde_helpers {
                 | HumanReadable::EraSummary(formatted_string)
                 | HumanReadable::Unbond(formatted_string)
                 | HumanReadable::ChainspecRegistry(formatted_string)
-                | HumanReadable::ChecksumRegistry(formatted_string) => {
+                | HumanReadable::ChecksumRegistry(formatted_string)
+                | HumanReadable::AuctionSnapshot(formatted_string)
+                | HumanReadable::StorageLease(formatted_string)
+                | HumanReadable::Burn(formatted_string)
+                | HumanReadable::StorageLeaseExpiry(formatted_string) => {
                     Key::from_formatted_str(&formatted_string)
                 }
             }
@@ -952,6 +1154,10 @@ mod serde_helpers {
         Unbond(&'a AccountHash),
         ChainspecRegistry,
         ChecksumRegistry,
+        AuctionSnapshot(&'a EraId),
+        StorageLease(&'a HashAddr),
+        Burn(&'a HashAddr),
+        StorageLeaseExpiry(&'a EraId, &'a StorageLeaseExpiryId),
     }
 
     impl<'a> From<&'a Key> for BinarySerHelper<'a> {
@@ -972,6 +1178,12 @@ mod serde_helpers {
                 Key::Unbond(account_hash) => BinarySerHelper::Unbond(account_hash),
                 Key::ChainspecRegistry => BinarySerHelper::ChainspecRegistry,
                 Key::ChecksumRegistry => BinarySerHelper::ChecksumRegistry,
+                Key::AuctionSnapshot(era_id) => BinarySerHelper::AuctionSnapshot(era_id),
+                Key::StorageLease(addr) => BinarySerHelper::StorageLease(addr),
+                Key::Burn(addr) => BinarySerHelper::Burn(addr),
+                Key::StorageLeaseExpiry(era_id, id) => {
+                    BinarySerHelper::StorageLeaseExpiry(era_id, id)
+                }
             }
         }
     }
@@ -993,6 +1205,10 @@ mod serde_helpers {
         Unbond(AccountHash),
         ChainspecRegistry,
         ChecksumRegistry,
+        AuctionSnapshot(EraId),
+        StorageLease(HashAddr),
+        Burn(HashAddr),
+        StorageLeaseExpiry(EraId, StorageLeaseExpiryId),
     }
 
     impl From<BinaryDeserHelper> for Key {
@@ -1013,6 +1229,12 @@ mod serde_helpers {
                 BinaryDeserHelper::Unbond(account_hash) => Key::Unbond(account_hash),
                 BinaryDeserHelper::ChainspecRegistry => Key::ChainspecRegistry,
                 BinaryDeserHelper::ChecksumRegistry => Key::ChecksumRegistry,
+                BinaryDeserHelper::AuctionSnapshot(era_id) => Key::AuctionSnapshot(era_id),
+                BinaryDeserHelper::StorageLease(addr) => Key::StorageLease(addr),
+                BinaryDeserHelper::Burn(addr) => Key::Burn(addr),
+                BinaryDeserHelper::StorageLeaseExpiry(era_id, id) => {
+                    Key::StorageLeaseExpiry(era_id, id)
+                }
             }
         }
     }
@@ -1070,6 +1292,10 @@ mod tests {
     const UNBOND_KEY: Key = Key::Unbond(AccountHash::new([42; 32]));
     const CHAINSPEC_REGISTRY_KEY: Key = Key::ChainspecRegistry;
     const CHECKSUM_REGISTRY_KEY: Key = Key::ChecksumRegistry;
+    const AUCTION_SNAPSHOT_KEY: Key = Key::AuctionSnapshot(EraId::new(42));
+    const STORAGE_LEASE_KEY: Key = Key::StorageLease([42; 32]);
+    const BURN_KEY: Key = Key::Burn([42; 32]);
+    const STORAGE_LEASE_EXPIRY_KEY: Key = Key::StorageLeaseExpiry(EraId::new(42), [42; 24]);
     const KEYS: &[Key] = &[
         ACCOUNT_KEY,
         HASH_KEY,
@@ -1086,6 +1312,10 @@ mod tests {
         UNBOND_KEY,
         CHAINSPEC_REGISTRY_KEY,
         CHECKSUM_REGISTRY_KEY,
+        AUCTION_SNAPSHOT_KEY,
+        STORAGE_LEASE_KEY,
+        BURN_KEY,
+        STORAGE_LEASE_EXPIRY_KEY,
     ];
     const HEX_STRING: &str = "2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a2a";
 
@@ -1202,6 +1432,22 @@ mod tests {
                 base16::encode_lower(&PADDING_BYTES),
             )
         );
+        assert_eq!(
+            format!("{}", AUCTION_SNAPSHOT_KEY),
+            "Key::AuctionSnapshot(era 42)".to_string()
+        );
+        assert_eq!(
+            format!("{}", STORAGE_LEASE_KEY),
+            format!("Key::StorageLease({})", HEX_STRING)
+        );
+        assert_eq!(
+            format!("{}", BURN_KEY),
+            format!("Key::Burn({})", HEX_STRING)
+        );
+        assert_eq!(
+            format!("{}", STORAGE_LEASE_EXPIRY_KEY),
+            format!("Key::StorageLeaseExpiry(era 42, {})", &HEX_STRING[..48])
+        );
     }
 
     #[test]
@@ -1334,6 +1580,22 @@ mod tests {
             .unwrap_err()
             .to_string()
             .starts_with("checksum-registry-key from string error: "));
+        assert!(Key::from_formatted_str(AUCTION_SNAPSHOT_PREFIX)
+            .unwrap_err()
+            .to_string()
+            .starts_with("auction-snapshot-key from string error: "));
+        assert!(Key::from_formatted_str(STORAGE_LEASE_PREFIX)
+            .unwrap_err()
+            .to_string()
+            .starts_with("storage-lease-key from string error: "));
+        assert!(Key::from_formatted_str(BURN_PREFIX)
+            .unwrap_err()
+            .to_string()
+            .starts_with("burn-key from string error: "));
+        assert!(Key::from_formatted_str(STORAGE_LEASE_EXPIRY_PREFIX)
+            .unwrap_err()
+            .to_string()
+            .starts_with("storage-lease-expiry-key from string error: "));
         let invalid_prefix = "a-0000000000000000000000000000000000000000000000000000000000000000";
         assert_eq!(
             Key::from_formatted_str(invalid_prefix)
@@ -1393,6 +1655,12 @@ mod tests {
                 "ChecksumRegistry":
                     format!("checksum-registry-{}", base16::encode_lower(&PADDING_BYTES))
             }),
+            json!({ "AuctionSnapshot": "auction-snapshot-42" }),
+            json!({ "StorageLease": format!("storage-lease-{}", HEX_STRING) }),
+            json!({ "Burn": format!("burn-{}", HEX_STRING) }),
+            json!({
+                "StorageLeaseExpiry": format!("storage-lease-expiry-42-{}", &HEX_STRING[..48])
+            }),
         ];
 
         assert_eq!(
@@ -1444,5 +1712,9 @@ mod tests {
         round_trip(&Key::Unbond(AccountHash::new(zeros)));
         round_trip(&Key::ChainspecRegistry);
         round_trip(&Key::ChecksumRegistry);
+        round_trip(&Key::AuctionSnapshot(EraId::from(0)));
+        round_trip(&Key::StorageLease(zeros));
+        round_trip(&Key::Burn(zeros));
+        round_trip(&Key::StorageLeaseExpiry(EraId::from(0), [0; 24]));
     }
 }
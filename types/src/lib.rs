@@ -25,6 +25,7 @@ mod access_rights;
 pub mod account;
 pub mod api_error;
 mod block_time;
+mod burn;
 pub mod bytesrepr;
 pub mod checksummed_hex;
 mod cl_type;
@@ -35,6 +36,7 @@ pub mod crypto;
 mod deploy_info;
 mod era_id;
 mod execution_result;
+pub mod fallible_num;
 #[cfg(any(feature = "std", test))]
 pub mod file_utils;
 mod gas;
@@ -48,6 +50,7 @@ mod phase;
 mod protocol_version;
 pub mod runtime_args;
 mod semver;
+mod storage_lease;
 mod stored_value;
 pub mod system;
 mod tagged;
@@ -65,6 +68,7 @@ pub use access_rights::{
 #[doc(inline)]
 pub use api_error::ApiError;
 pub use block_time::{BlockTime, BLOCKTIME_SERIALIZED_LENGTH};
+pub use burn::Burn;
 pub use cl_type::{named_key_type, CLType, CLTyped};
 pub use cl_value::{CLTypeMismatch, CLValue, CLValueError};
 pub use contract_wasm::{ContractWasm, ContractWasmHash};
@@ -77,7 +81,8 @@ pub use contracts::{
 pub use crypto::*;
 pub use deploy_info::DeployInfo;
 pub use execution_result::{
-    ExecutionEffect, ExecutionResult, OpKind, Operation, Transform, TransformEntry,
+    ExecutionEffect, ExecutionResult, ExecutionResultV2, OpKind, Operation, Transform,
+    TransformEntry,
 };
 pub use gas::Gas;
 pub use json_pretty_printer::json_pretty_print;
@@ -93,14 +98,15 @@ pub use protocol_version::{ProtocolVersion, VersionCheckResult};
 #[doc(inline)]
 pub use runtime_args::{NamedArg, RuntimeArgs};
 pub use semver::{ParseSemVerError, SemVer, SEM_VER_SERIALIZED_LENGTH};
+pub use storage_lease::StorageLease;
 pub use stored_value::{StoredValue, TypeMismatch as StoredValueTypeMismatch};
 pub use tagged::Tagged;
 #[cfg(any(feature = "std", test))]
 pub use timestamp::serde_option_time_diff;
 pub use timestamp::{TimeDiff, Timestamp};
 pub use transfer::{
-    DeployHash, FromStrError as TransferFromStrError, Transfer, TransferAddr, DEPLOY_HASH_LENGTH,
-    TRANSFER_ADDR_LENGTH,
+    DeployHash, FromStrError as TransferFromStrError, Transfer, TransferAddr, TransferMemo,
+    TransferV2, DEPLOY_HASH_LENGTH, TRANSFER_ADDR_LENGTH, TRANSFER_MEMO_LENGTH,
 };
 pub use transfer_result::{TransferResult, TransferredTo};
 pub use uref::{
@@ -0,0 +1,136 @@
+//! Storage leases of values written to global state.
+
+use alloc::vec::Vec;
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    CLType, CLTyped, EraId, Key,
+};
+
+/// The storage lease of a value written to global state, stored under [`Key::StorageLease`].
+///
+/// A lease is paid until a given era.  Once it and a subsequent grace period have passed without
+/// the value being written again, the lease is marked as expired and the value can no longer be
+/// read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct StorageLease {
+    /// The key the leased value is stored under.
+    key: Key,
+    /// The last era the lease is paid for.
+    paid_until: EraId,
+    /// Whether the lease has expired.
+    expired: bool,
+}
+
+impl StorageLease {
+    /// Creates a new, unexpired lease of the value under `key`, paid until `paid_until`.
+    pub fn new(key: Key, paid_until: EraId) -> Self {
+        StorageLease {
+            key,
+            paid_until,
+            expired: false,
+        }
+    }
+
+    /// Returns the key the leased value is stored under.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Returns the last era the lease is paid for.
+    pub fn paid_until(&self) -> EraId {
+        self.paid_until
+    }
+
+    /// Returns `true` if the lease has expired.
+    pub fn is_expired(&self) -> bool {
+        self.expired
+    }
+
+    /// Returns `true` if the lease is due to expire in `era_id`, i.e. if both the paid period and
+    /// the `grace_eras` following it have passed.
+    pub fn is_due(&self, era_id: EraId, grace_eras: u64) -> bool {
+        !self.expired && self.paid_until.saturating_add(grace_eras) < era_id
+    }
+
+    /// Marks the lease as expired.
+    pub fn expire(&mut self) {
+        self.expired = true;
+    }
+}
+
+impl CLTyped for StorageLease {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+impl ToBytes for StorageLease {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.key.serialized_length()
+            + self.paid_until.serialized_length()
+            + self.expired.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.key.write_bytes(writer)?;
+        self.paid_until.write_bytes(writer)?;
+        self.expired.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for StorageLease {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (key, rem) = Key::from_bytes(bytes)?;
+        let (paid_until, rem) = EraId::from_bytes(rem)?;
+        let (expired, rem) = bool::from_bytes(rem)?;
+        Ok((
+            StorageLease {
+                key,
+                paid_until,
+                expired,
+            },
+            rem,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytesrepr_roundtrip() {
+        let mut lease = StorageLease::new(Key::Hash([42; 32]), EraId::new(7));
+        bytesrepr::test_serialization_roundtrip(&lease);
+        lease.expire();
+        bytesrepr::test_serialization_roundtrip(&lease);
+    }
+
+    #[test]
+    fn should_be_due_after_grace_period() {
+        let mut lease = StorageLease::new(Key::Hash([42; 32]), EraId::new(7));
+        assert!(!lease.is_due(EraId::new(7), 2));
+        assert!(!lease.is_due(EraId::new(9), 2));
+        assert!(lease.is_due(EraId::new(10), 2));
+
+        lease.expire();
+        assert!(!lease.is_due(EraId::new(10), 2));
+    }
+}
@@ -16,8 +16,8 @@ use crate::{
     account::Account,
     bytesrepr::{self, FromBytes, ToBytes, U8_SERIALIZED_LENGTH},
     contracts::ContractPackage,
-    system::auction::{Bid, EraInfo, UnbondingPurse, WithdrawPurse},
-    CLValue, Contract, ContractWasm, DeployInfo, Transfer,
+    system::auction::{AuctionSnapshot, Bid, EraInfo, UnbondingPurse, WithdrawPurse},
+    Burn, CLValue, Contract, ContractWasm, DeployInfo, Transfer, TransferV2,
 };
 pub use type_mismatch::TypeMismatch;
 
@@ -35,6 +35,9 @@ enum Tag {
     Bid = 8,
     Withdraw = 9,
     Unbonding = 10,
+    AuctionSnapshot = 11,
+    TransferV2 = 12,
+    Burn = 13,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -64,6 +67,12 @@ pub enum StoredValue {
     Withdraw(Vec<WithdrawPurse>),
     /// Variant that stores unbonding information.
     Unbonding(Vec<UnbondingPurse>),
+    /// Variant that stores [`AuctionSnapshot`].
+    AuctionSnapshot(AuctionSnapshot),
+    /// Variant that stores [`TransferV2`].
+    TransferV2(TransferV2),
+    /// Variant that stores [`Burn`].
+    Burn(Burn),
 }
 
 impl StoredValue {
@@ -147,6 +156,14 @@ impl StoredValue {
         }
     }
 
+    /// Returns a wrapped [`AuctionSnapshot`] if this is an `AuctionSnapshot` variant.
+    pub fn as_auction_snapshot(&self) -> Option<&AuctionSnapshot> {
+        match self {
+            StoredValue::AuctionSnapshot(auction_snapshot) => Some(auction_snapshot),
+            _ => None,
+        }
+    }
+
     /// Returns the type name of the [`StoredValue`] enum variant.
     ///
     /// For [`CLValue`] variants it will return the name of the [`CLType`](crate::cl_type::CLType)
@@ -163,6 +180,9 @@ impl StoredValue {
             StoredValue::Bid(_) => "Bid".to_string(),
             StoredValue::Withdraw(_) => "Withdraw".to_string(),
             StoredValue::Unbonding(_) => "Unbonding".to_string(),
+            StoredValue::AuctionSnapshot(_) => "AuctionSnapshot".to_string(),
+            StoredValue::TransferV2(_) => "TransferV2".to_string(),
+            StoredValue::Burn(_) => "Burn".to_string(),
         }
     }
 
@@ -179,6 +199,9 @@ impl StoredValue {
             StoredValue::Bid(_) => Tag::Bid,
             StoredValue::Withdraw(_) => Tag::Withdraw,
             StoredValue::Unbonding(_) => Tag::Unbonding,
+            StoredValue::AuctionSnapshot(_) => Tag::AuctionSnapshot,
+            StoredValue::TransferV2(_) => Tag::TransferV2,
+            StoredValue::Burn(_) => Tag::Burn,
         }
     }
 }
@@ -295,6 +318,31 @@ impl TryFrom<StoredValue> for Transfer {
     }
 }
 
+impl TryFrom<StoredValue> for TransferV2 {
+    type Error = TypeMismatch;
+
+    fn try_from(value: StoredValue) -> Result<Self, Self::Error> {
+        match value {
+            StoredValue::TransferV2(transfer_v2) => Ok(transfer_v2),
+            _ => Err(TypeMismatch::new(
+                "TransferV2".to_string(),
+                value.type_name(),
+            )),
+        }
+    }
+}
+
+impl TryFrom<StoredValue> for Burn {
+    type Error = TypeMismatch;
+
+    fn try_from(value: StoredValue) -> Result<Self, Self::Error> {
+        match value {
+            StoredValue::Burn(burn) => Ok(burn),
+            _ => Err(TypeMismatch::new("Burn".to_string(), value.type_name())),
+        }
+    }
+}
+
 impl TryFrom<StoredValue> for DeployInfo {
     type Error = TypeMismatch;
 
@@ -320,6 +368,20 @@ impl TryFrom<StoredValue> for EraInfo {
     }
 }
 
+impl TryFrom<StoredValue> for AuctionSnapshot {
+    type Error = TypeMismatch;
+
+    fn try_from(value: StoredValue) -> Result<Self, Self::Error> {
+        match value {
+            StoredValue::AuctionSnapshot(auction_snapshot) => Ok(auction_snapshot),
+            _ => Err(TypeMismatch::new(
+                "AuctionSnapshot".to_string(),
+                value.type_name(),
+            )),
+        }
+    }
+}
+
 impl ToBytes for StoredValue {
     fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
         let mut result = bytesrepr::allocate_buffer(self)?;
@@ -341,6 +403,11 @@ impl ToBytes for StoredValue {
             StoredValue::Unbonding(unbonding_purses) => {
                 (Tag::Unbonding, unbonding_purses.to_bytes()?)
             }
+            StoredValue::AuctionSnapshot(auction_snapshot) => {
+                (Tag::AuctionSnapshot, auction_snapshot.to_bytes()?)
+            }
+            StoredValue::TransferV2(transfer_v2) => (Tag::TransferV2, transfer_v2.to_bytes()?),
+            StoredValue::Burn(burn) => (Tag::Burn, burn.to_bytes()?),
         };
         result.push(tag as u8);
         result.append(&mut serialized_data);
@@ -363,6 +430,11 @@ impl ToBytes for StoredValue {
                 StoredValue::Bid(bid) => bid.serialized_length(),
                 StoredValue::Withdraw(withdraw_purses) => withdraw_purses.serialized_length(),
                 StoredValue::Unbonding(unbonding_purses) => unbonding_purses.serialized_length(),
+                StoredValue::AuctionSnapshot(auction_snapshot) => {
+                    auction_snapshot.serialized_length()
+                }
+                StoredValue::TransferV2(transfer_v2) => transfer_v2.serialized_length(),
+                StoredValue::Burn(burn) => burn.serialized_length(),
             }
     }
 
@@ -382,6 +454,11 @@ impl ToBytes for StoredValue {
             StoredValue::Bid(bid) => bid.write_bytes(writer)?,
             StoredValue::Withdraw(unbonding_purses) => unbonding_purses.write_bytes(writer)?,
             StoredValue::Unbonding(unbonding_purses) => unbonding_purses.write_bytes(writer)?,
+            StoredValue::AuctionSnapshot(auction_snapshot) => {
+                auction_snapshot.write_bytes(writer)?
+            }
+            StoredValue::TransferV2(transfer_v2) => transfer_v2.write_bytes(writer)?,
+            StoredValue::Burn(burn) => burn.write_bytes(writer)?,
         };
         Ok(())
     }
@@ -425,6 +502,15 @@ impl FromBytes for StoredValue {
                     (StoredValue::Unbonding(unbonding_purses), remainder)
                 })
             }
+            tag if tag == Tag::AuctionSnapshot as u8 => {
+                AuctionSnapshot::from_bytes(remainder).map(|(auction_snapshot, remainder)| {
+                    (StoredValue::AuctionSnapshot(auction_snapshot), remainder)
+                })
+            }
+            tag if tag == Tag::TransferV2 as u8 => TransferV2::from_bytes(remainder)
+                .map(|(transfer_v2, remainder)| (StoredValue::TransferV2(transfer_v2), remainder)),
+            tag if tag == Tag::Burn as u8 => Burn::from_bytes(remainder)
+                .map(|(burn, remainder)| (StoredValue::Burn(burn), remainder)),
             _ => Err(bytesrepr::Error::Formatting),
         }
     }
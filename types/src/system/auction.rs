@@ -1,4 +1,5 @@
 //! Contains implementation of a Auction contract functionality.
+mod auction_snapshot;
 mod bid;
 mod constants;
 mod delegator;
@@ -11,6 +12,7 @@ mod withdraw_purse;
 
 use alloc::{collections::BTreeMap, vec::Vec};
 
+pub use auction_snapshot::{AuctionSnapshot, ValidatorSnapshot};
 pub use bid::{Bid, VESTING_SCHEDULE_LENGTH_MILLIS};
 pub use constants::*;
 pub use delegator::Delegator;
@@ -23,7 +25,7 @@ pub use withdraw_purse::WithdrawPurse;
 
 #[cfg(any(feature = "testing", test))]
 pub(crate) mod gens {
-    pub use super::era_info::gens::*;
+    pub use super::{auction_snapshot::gens::*, era_info::gens::*};
 }
 
 use crate::{account::AccountHash, EraId, PublicKey, U512};
@@ -0,0 +1,322 @@
+// TODO - remove once schemars stops causing warning.
+#![allow(clippy::field_reassign_with_default)]
+
+use alloc::{boxed::Box, vec::Vec};
+
+#[cfg(feature = "datasize")]
+use datasize::DataSize;
+#[cfg(feature = "json-schema")]
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    bytesrepr::{self, FromBytes, ToBytes},
+    system::auction::{DelegationRate, SeigniorageRecipient, SeigniorageRecipients},
+    CLType, CLTyped, PublicKey, U512,
+};
+
+/// The weight and delegations of a single validator, as recorded in an [`AuctionSnapshot`].
+#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct ValidatorSnapshot {
+    /// Validator's public key.
+    validator_public_key: PublicKey,
+    /// Total stake of the validator, including the stake delegated to it.
+    weight: U512,
+    /// Total stake delegated to the validator.
+    delegated_amount: U512,
+    /// Number of delegators of the validator.
+    delegator_count: u32,
+    /// Delegation rate of the validator.
+    delegation_rate: DelegationRate,
+}
+
+impl ValidatorSnapshot {
+    /// Constructs a [`ValidatorSnapshot`].
+    pub fn new(
+        validator_public_key: PublicKey,
+        weight: U512,
+        delegated_amount: U512,
+        delegator_count: u32,
+        delegation_rate: DelegationRate,
+    ) -> Self {
+        ValidatorSnapshot {
+            validator_public_key,
+            weight,
+            delegated_amount,
+            delegator_count,
+            delegation_rate,
+        }
+    }
+
+    /// Constructs a [`ValidatorSnapshot`] from a seigniorage recipient, returning `None` if its
+    /// total stake overflows.
+    pub fn from_seigniorage_recipient(
+        validator_public_key: PublicKey,
+        seigniorage_recipient: &SeigniorageRecipient,
+    ) -> Option<Self> {
+        let delegated_amount = seigniorage_recipient.delegator_total_stake()?;
+        let weight = seigniorage_recipient
+            .stake()
+            .checked_add(delegated_amount)?;
+        let delegator_count =
+            u32::try_from(seigniorage_recipient.delegator_stake().len()).unwrap_or(u32::MAX);
+        Some(ValidatorSnapshot::new(
+            validator_public_key,
+            weight,
+            delegated_amount,
+            delegator_count,
+            *seigniorage_recipient.delegation_rate(),
+        ))
+    }
+
+    /// Returns the validator's public key.
+    pub fn validator_public_key(&self) -> &PublicKey {
+        &self.validator_public_key
+    }
+
+    /// Returns the total stake of the validator, including the stake delegated to it.
+    pub fn weight(&self) -> &U512 {
+        &self.weight
+    }
+
+    /// Returns the total stake delegated to the validator.
+    pub fn delegated_amount(&self) -> &U512 {
+        &self.delegated_amount
+    }
+
+    /// Returns the number of delegators of the validator.
+    pub fn delegator_count(&self) -> u32 {
+        self.delegator_count
+    }
+
+    /// Returns the delegation rate of the validator.
+    pub fn delegation_rate(&self) -> DelegationRate {
+        self.delegation_rate
+    }
+}
+
+impl ToBytes for ValidatorSnapshot {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut buffer = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut buffer)?;
+        Ok(buffer)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validator_public_key.serialized_length()
+            + self.weight.serialized_length()
+            + self.delegated_amount.serialized_length()
+            + self.delegator_count.serialized_length()
+            + self.delegation_rate.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.validator_public_key.write_bytes(writer)?;
+        self.weight.write_bytes(writer)?;
+        self.delegated_amount.write_bytes(writer)?;
+        self.delegator_count.write_bytes(writer)?;
+        self.delegation_rate.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for ValidatorSnapshot {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validator_public_key, rem) = PublicKey::from_bytes(bytes)?;
+        let (weight, rem) = U512::from_bytes(rem)?;
+        let (delegated_amount, rem) = U512::from_bytes(rem)?;
+        let (delegator_count, rem) = u32::from_bytes(rem)?;
+        let (delegation_rate, rem) = DelegationRate::from_bytes(rem)?;
+        Ok((
+            ValidatorSnapshot::new(
+                validator_public_key,
+                weight,
+                delegated_amount,
+                delegator_count,
+                delegation_rate,
+            ),
+            rem,
+        ))
+    }
+}
+
+impl CLTyped for ValidatorSnapshot {
+    fn cl_type() -> CLType {
+        CLType::Any
+    }
+}
+
+/// Compact record of the validator set of an era, as determined by the auction.  Intended to be
+/// recorded under [`Key::AuctionSnapshot`](crate::Key::AuctionSnapshot) for each era.
+#[derive(Debug, Default, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct AuctionSnapshot {
+    validators: Vec<ValidatorSnapshot>,
+}
+
+impl AuctionSnapshot {
+    /// Constructs an [`AuctionSnapshot`].
+    pub fn new(validators: Vec<ValidatorSnapshot>) -> Self {
+        AuctionSnapshot { validators }
+    }
+
+    /// Constructs an [`AuctionSnapshot`] from the seigniorage recipients of an era, returning
+    /// `None` if the total stake of any of them overflows.
+    pub fn from_seigniorage_recipients(
+        seigniorage_recipients: &SeigniorageRecipients,
+    ) -> Option<Self> {
+        let validators = seigniorage_recipients
+            .iter()
+            .map(|(validator_public_key, seigniorage_recipient)| {
+                ValidatorSnapshot::from_seigniorage_recipient(
+                    validator_public_key.clone(),
+                    seigniorage_recipient,
+                )
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(AuctionSnapshot::new(validators))
+    }
+
+    /// Returns the validators of the era, ordered by public key.
+    pub fn validators(&self) -> &Vec<ValidatorSnapshot> {
+        &self.validators
+    }
+
+    /// Returns the snapshot of the given validator, if it is part of the era's validator set.
+    pub fn validator(&self, validator_public_key: &PublicKey) -> Option<&ValidatorSnapshot> {
+        self.validators
+            .iter()
+            .find(|validator| validator.validator_public_key == *validator_public_key)
+    }
+}
+
+impl ToBytes for AuctionSnapshot {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.validators.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.validators.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.validators.write_bytes(writer)
+    }
+}
+
+impl FromBytes for AuctionSnapshot {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (validators, rem) = Vec::<ValidatorSnapshot>::from_bytes(bytes)?;
+        Ok((AuctionSnapshot { validators }, rem))
+    }
+}
+
+impl CLTyped for AuctionSnapshot {
+    fn cl_type() -> CLType {
+        CLType::List(Box::new(ValidatorSnapshot::cl_type()))
+    }
+}
+
+/// Generators for [`ValidatorSnapshot`] and [`AuctionSnapshot`]
+#[cfg(any(feature = "testing", feature = "gens", test))]
+pub mod gens {
+    use proptest::{
+        collection::{self, SizeRange},
+        prelude::{any, Strategy},
+    };
+
+    use crate::{
+        crypto::gens::public_key_arb,
+        gens::u512_arb,
+        system::auction::{AuctionSnapshot, ValidatorSnapshot},
+    };
+
+    /// Creates an arbitrary [`ValidatorSnapshot`]
+    pub fn validator_snapshot_arb() -> impl Strategy<Value = ValidatorSnapshot> {
+        (
+            public_key_arb(),
+            u512_arb(),
+            u512_arb(),
+            any::<u32>(),
+            any::<u8>(),
+        )
+            .prop_map(
+                |(
+                    validator_public_key,
+                    weight,
+                    delegated_amount,
+                    delegator_count,
+                    delegation_rate,
+                )| {
+                    ValidatorSnapshot::new(
+                        validator_public_key,
+                        weight,
+                        delegated_amount,
+                        delegator_count,
+                        delegation_rate,
+                    )
+                },
+            )
+    }
+
+    /// Creates an arbitrary [`AuctionSnapshot`]
+    pub fn auction_snapshot_arb(
+        size: impl Into<SizeRange>,
+    ) -> impl Strategy<Value = AuctionSnapshot> {
+        collection::vec(validator_snapshot_arb(), size).prop_map(AuctionSnapshot::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::collections::BTreeMap;
+
+    use proptest::prelude::*;
+
+    use crate::{bytesrepr, SecretKey};
+
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn test_serialization_roundtrip(auction_snapshot in gens::auction_snapshot_arb(0..32)) {
+            bytesrepr::test_serialization_roundtrip(&auction_snapshot)
+        }
+    }
+
+    #[test]
+    fn should_summarize_seigniorage_recipients() {
+        let validator = PublicKey::from(&SecretKey::ed25519_from_bytes([1; 32]).unwrap());
+        let delegator_1 = PublicKey::from(&SecretKey::ed25519_from_bytes([2; 32]).unwrap());
+        let delegator_2 = PublicKey::from(&SecretKey::ed25519_from_bytes([3; 32]).unwrap());
+
+        let mut delegator_stake = BTreeMap::new();
+        delegator_stake.insert(delegator_1, U512::from(20));
+        delegator_stake.insert(delegator_2, U512::from(30));
+        let mut seigniorage_recipients = SeigniorageRecipients::new();
+        seigniorage_recipients.insert(
+            validator.clone(),
+            SeigniorageRecipient::new(U512::from(100), 10, delegator_stake),
+        );
+
+        let snapshot = AuctionSnapshot::from_seigniorage_recipients(&seigniorage_recipients)
+            .expect("should not overflow");
+        assert_eq!(
+            snapshot.validator(&validator),
+            Some(&ValidatorSnapshot::new(
+                validator,
+                U512::from(150),
+                U512::from(50),
+                2,
+                10
+            ))
+        );
+    }
+}
@@ -34,6 +34,12 @@ pub const ARG_VALIDATOR_KEYS: &str = "validator_keys";
 pub const ARG_VALIDATOR_PUBLIC_KEYS: &str = "validator_public_keys";
 /// Named constant for `new_validator`.
 pub const ARG_NEW_VALIDATOR: &str = "new_validator";
+/// Named constant for `delegations`.
+pub const ARG_DELEGATIONS: &str = "delegations";
+/// Named constant for `redelegations`.
+pub const ARG_REDELEGATIONS: &str = "redelegations";
+/// Named constant for `reward_purse`.
+pub const ARG_REWARD_PURSE: &str = "reward_purse";
 /// Named constant for `era_id`.
 pub const ARG_ERA_ID: &str = "era_id";
 /// Named constant for `reward_factors`.
@@ -71,6 +77,10 @@ pub const METHOD_DELEGATE: &str = "delegate";
 pub const METHOD_UNDELEGATE: &str = "undelegate";
 /// Named constant for method `redelegate`.
 pub const METHOD_REDELEGATE: &str = "redelegate";
+/// Named constant for method `bulk_delegate`.
+pub const METHOD_BULK_DELEGATE: &str = "bulk_delegate";
+/// Named constant for method `set_delegator_reward_purse`.
+pub const METHOD_SET_DELEGATOR_REWARD_PURSE: &str = "set_delegator_reward_purse";
 /// Named constant for method `run_auction`.
 pub const METHOD_RUN_AUCTION: &str = "run_auction";
 /// Named constant for method `slash`.
@@ -96,3 +106,7 @@ pub const AUCTION_DELAY_KEY: &str = "auction_delay";
 pub const LOCKED_FUNDS_PERIOD_KEY: &str = "locked_funds_period";
 /// Unbonding delay expressed in eras.
 pub const UNBONDING_DELAY_KEY: &str = "unbonding_delay";
+/// Storage for the eras in which validators last raised their delegation rate.
+pub const DELEGATION_RATE_INCREASES_KEY: &str = "delegation_rate_increases";
+/// Storage for the purses receiving the rewards of delegators, by validator and delegator.
+pub const DELEGATOR_REWARD_PURSES_KEY: &str = "delegator_reward_purses";
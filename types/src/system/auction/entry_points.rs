@@ -1,16 +1,17 @@
-use alloc::boxed::Box;
+use alloc::{boxed::Box, collections::BTreeMap, vec::Vec};
 
 use crate::{
     system::auction::{
-        DelegationRate, ValidatorWeights, ARG_AMOUNT, ARG_DELEGATION_RATE, ARG_DELEGATOR,
-        ARG_ERA_END_TIMESTAMP_MILLIS, ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY, ARG_REWARD_FACTORS,
-        ARG_VALIDATOR, ARG_VALIDATOR_PUBLIC_KEY, METHOD_ACTIVATE_BID, METHOD_ADD_BID,
+        DelegationRate, ValidatorWeights, ARG_AMOUNT, ARG_DELEGATIONS, ARG_DELEGATION_RATE,
+        ARG_DELEGATOR, ARG_ERA_END_TIMESTAMP_MILLIS, ARG_NEW_VALIDATOR, ARG_PUBLIC_KEY,
+        ARG_REDELEGATIONS, ARG_REWARD_FACTORS, ARG_REWARD_PURSE, ARG_VALIDATOR,
+        ARG_VALIDATOR_PUBLIC_KEY, METHOD_ACTIVATE_BID, METHOD_ADD_BID, METHOD_BULK_DELEGATE,
         METHOD_DELEGATE, METHOD_DISTRIBUTE, METHOD_GET_ERA_VALIDATORS, METHOD_READ_ERA_ID,
-        METHOD_REDELEGATE, METHOD_RUN_AUCTION, METHOD_SLASH, METHOD_UNDELEGATE,
-        METHOD_WITHDRAW_BID,
+        METHOD_REDELEGATE, METHOD_RUN_AUCTION, METHOD_SET_DELEGATOR_REWARD_PURSE, METHOD_SLASH,
+        METHOD_UNDELEGATE, METHOD_WITHDRAW_BID,
     },
     CLType, CLTyped, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
-    PublicKey, U512,
+    PublicKey, URef, U512,
 };
 
 /// Creates auction contract entry points.
@@ -91,6 +92,35 @@ pub fn auction_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_BULK_DELEGATE,
+        vec![
+            Parameter::new(ARG_DELEGATOR, PublicKey::cl_type()),
+            Parameter::new(ARG_DELEGATIONS, BTreeMap::<PublicKey, U512>::cl_type()),
+            Parameter::new(
+                ARG_REDELEGATIONS,
+                Vec::<(PublicKey, PublicKey, U512)>::cl_type(),
+            ),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SET_DELEGATOR_REWARD_PURSE,
+        vec![
+            Parameter::new(ARG_DELEGATOR, PublicKey::cl_type()),
+            Parameter::new(ARG_VALIDATOR, PublicKey::cl_type()),
+            Parameter::new(ARG_REWARD_PURSE, Option::<URef>::cl_type()),
+        ],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     let entry_point = EntryPoint::new(
         METHOD_RUN_AUCTION,
         vec![Parameter::new(ARG_ERA_END_TIMESTAMP_MILLIS, u64::cl_type())],
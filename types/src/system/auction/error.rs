@@ -327,6 +327,26 @@ pub enum Error {
     /// assert_eq!(49, Error::TransferToAdministrator as u8);
     /// ```
     TransferToAdministrator = 49,
+    /// Raising the delegation rate is not allowed by the delegation rate change limit, either
+    /// because the increase is too large or because the last one was too recent.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(50, Error::DelegationRateChangeLimited as u8);
+    /// ```
+    DelegationRateChangeLimited = 50,
+    /// The reward purse of a delegator is not an existing purse, or was not given with the right
+    /// to add to it.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(51, Error::InvalidRewardPurse as u8);
+    /// ```
+    InvalidRewardPurse = 51,
+    /// The validator's stake would be below the minimum bid amount.
+    /// ```
+    /// # use casper_types::system::auction::Error;
+    /// assert_eq!(52, Error::BidAmountTooSmall as u8);
+    /// ```
+    BidAmountTooSmall = 52,
 }
 
 impl Display for Error {
@@ -382,6 +402,9 @@ impl Display for Error {
             Error::AuctionBidsDisabled => formatter.write_str("Auction bids are disabled"),
             Error::GetAccumulationPurse => formatter.write_str("Get accumulation purse error"),
             Error::TransferToAdministrator => formatter.write_str("Transfer to administrator error"),
+            Error::DelegationRateChangeLimited => formatter.write_str("The delegation rate change exceeds the allowed limit"),
+            Error::InvalidRewardPurse => formatter.write_str("The reward purse is invalid"),
+            Error::BidAmountTooSmall => formatter.write_str("The bid amount is below the minimum allowed"),
         }
     }
 }
@@ -463,6 +486,11 @@ impl TryFrom<u8> for Error {
             d if d == Error::AuctionBidsDisabled as u8 => Ok(Error::AuctionBidsDisabled),
             d if d == Error::GetAccumulationPurse as u8 => Ok(Error::GetAccumulationPurse),
             d if d == Error::TransferToAdministrator as u8 => Ok(Error::TransferToAdministrator),
+            d if d == Error::DelegationRateChangeLimited as u8 => {
+                Ok(Error::DelegationRateChangeLimited)
+            }
+            d if d == Error::InvalidRewardPurse as u8 => Ok(Error::InvalidRewardPurse),
+            d if d == Error::BidAmountTooSmall as u8 => Ok(Error::BidAmountTooSmall),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -3,6 +3,19 @@ mod constants;
 mod entry_points;
 mod error;
 
+use alloc::string::{String, ToString};
+
 pub use constants::*;
 pub use entry_points::mint_entry_points;
 pub use error::Error;
+
+use crate::EraId;
+
+/// The outcome of a single transfer of a batch made by the `transfer_batch` entry point.
+pub type TransferResult = Result<(), Error>;
+
+/// Returns the name of the item of the dictionary under [`HISTORICAL_TOTAL_SUPPLY_KEY`] holding
+/// the total supply at the start of `era_id`.
+pub fn historical_total_supply_item_key(era_id: EraId) -> String {
+    era_id.value().to_string()
+}
@@ -4,12 +4,22 @@ pub const ARG_PURSE: &str = "purse";
 pub const ARG_AMOUNT: &str = "amount";
 /// Named constant for `id`.
 pub const ARG_ID: &str = "id";
+/// Named constant for `memo`.
+pub const ARG_MEMO: &str = "memo";
 /// Named constant for `to`.
 pub const ARG_TO: &str = "to";
 /// Named constant for `source`.
 pub const ARG_SOURCE: &str = "source";
 /// Named constant for `target`.
 pub const ARG_TARGET: &str = "target";
+/// Named constant for `transfers`.
+pub const ARG_TRANSFERS: &str = "transfers";
+/// Named constant for `spender`.
+pub const ARG_SPENDER: &str = "spender";
+/// Named constant for `paused`.
+pub const ARG_PAUSED: &str = "paused";
+/// Named constant for `era_id`.
+pub const ARG_ERA_ID: &str = "era_id";
 /// Named constant for `round_seigniorage_rate` used in installer.
 pub const ARG_ROUND_SEIGNIORAGE_RATE: &str = "round_seigniorage_rate";
 
@@ -23,10 +33,24 @@ pub const METHOD_CREATE: &str = "create";
 pub const METHOD_BALANCE: &str = "balance";
 /// Named constant for method `transfer`.
 pub const METHOD_TRANSFER: &str = "transfer";
+/// Named constant for method `transfer_batch`.
+pub const METHOD_TRANSFER_BATCH: &str = "transfer_batch";
+/// Named constant for method `approve`.
+pub const METHOD_APPROVE: &str = "approve";
+/// Named constant for method `transfer_from`.
+pub const METHOD_TRANSFER_FROM: &str = "transfer_from";
+/// Named constant for method `allowance`.
+pub const METHOD_ALLOWANCE: &str = "allowance";
 /// Named constant for method `read_base_round_reward`.
 pub const METHOD_READ_BASE_ROUND_REWARD: &str = "read_base_round_reward";
 /// Named constant for method `mint_into_existing_purse`.
 pub const METHOD_MINT_INTO_EXISTING_PURSE: &str = "mint_into_existing_purse";
+/// Named constant for method `burn`.
+pub const METHOD_BURN: &str = "burn";
+/// Named constant for method `set_mint_paused`.
+pub const METHOD_SET_MINT_PAUSED: &str = "set_mint_paused";
+/// Named constant for method `record_total_supply`.
+pub const METHOD_RECORD_TOTAL_SUPPLY: &str = "record_total_supply";
 
 /// Storage for mint contract hash.
 pub const HASH_KEY: &str = "mint_hash";
@@ -38,3 +62,9 @@ pub const BASE_ROUND_REWARD_KEY: &str = "mint_base_round_reward";
 pub const TOTAL_SUPPLY_KEY: &str = "total_supply";
 /// Storage for mint round seigniorage rate.
 pub const ROUND_SEIGNIORAGE_RATE_KEY: &str = "round_seigniorage_rate";
+/// Storage for the dictionary of allowances granted to spenders of purses.
+pub const ALLOWANCES_KEY: &str = "allowances";
+/// Storage for the flag pausing mint, transfer and total supply reduction.
+pub const MINT_PAUSED_KEY: &str = "mint_paused";
+/// Storage for the dictionary of the total supply at the start of each era.
+pub const HISTORICAL_TOTAL_SUPPLY_KEY: &str = "historical_total_supply";
@@ -3,11 +3,14 @@ use alloc::boxed::Box;
 use crate::{
     contracts::Parameters,
     system::mint::{
-        ARG_AMOUNT, ARG_ID, ARG_PURSE, ARG_SOURCE, ARG_TARGET, ARG_TO, METHOD_BALANCE,
-        METHOD_CREATE, METHOD_MINT, METHOD_MINT_INTO_EXISTING_PURSE, METHOD_READ_BASE_ROUND_REWARD,
-        METHOD_REDUCE_TOTAL_SUPPLY, METHOD_TRANSFER,
+        ARG_AMOUNT, ARG_ERA_ID, ARG_ID, ARG_MEMO, ARG_PAUSED, ARG_PURSE, ARG_SOURCE, ARG_SPENDER,
+        ARG_TARGET, ARG_TO, ARG_TRANSFERS, METHOD_ALLOWANCE, METHOD_APPROVE, METHOD_BALANCE,
+        METHOD_BURN, METHOD_CREATE, METHOD_MINT, METHOD_MINT_INTO_EXISTING_PURSE,
+        METHOD_READ_BASE_ROUND_REWARD, METHOD_RECORD_TOTAL_SUPPLY, METHOD_REDUCE_TOTAL_SUPPLY,
+        METHOD_SET_MINT_PAUSED, METHOD_TRANSFER, METHOD_TRANSFER_BATCH, METHOD_TRANSFER_FROM,
     },
     CLType, EntryPoint, EntryPointAccess, EntryPointType, EntryPoints, Parameter,
+    TRANSFER_MEMO_LENGTH,
 };
 
 /// Returns entry points for a mint system contract.
@@ -64,6 +67,10 @@ pub fn mint_entry_points() -> EntryPoints {
             Parameter::new(ARG_TARGET, CLType::URef),
             Parameter::new(ARG_AMOUNT, CLType::U512),
             Parameter::new(ARG_ID, CLType::Option(Box::new(CLType::U64))),
+            Parameter::new(
+                ARG_MEMO,
+                CLType::Option(Box::new(CLType::ByteArray(TRANSFER_MEMO_LENGTH as u32))),
+            ),
         ],
         CLType::Result {
             ok: Box::new(CLType::Unit),
@@ -74,6 +81,32 @@ pub fn mint_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_TRANSFER_BATCH,
+        vec![
+            Parameter::new(ARG_SOURCE, CLType::URef),
+            Parameter::new(
+                ARG_TRANSFERS,
+                CLType::List(Box::new(CLType::Tuple3([
+                    Box::new(CLType::Option(Box::new(CLType::ByteArray(32)))),
+                    Box::new(CLType::URef),
+                    Box::new(CLType::U512),
+                ]))),
+            ),
+            Parameter::new(ARG_ID, CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::List(Box::new(CLType::Result {
+                ok: Box::new(CLType::Unit),
+                err: Box::new(CLType::U8),
+            }))),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     let entry_point = EntryPoint::new(
         METHOD_READ_BASE_ROUND_REWARD,
         Parameters::new(),
@@ -98,5 +131,89 @@ pub fn mint_entry_points() -> EntryPoints {
     );
     entry_points.add_entry_point(entry_point);
 
+    let entry_point = EntryPoint::new(
+        METHOD_BURN,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_SET_MINT_PAUSED,
+        vec![Parameter::new(ARG_PAUSED, CLType::Bool)],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_APPROVE,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::URef),
+            Parameter::new(ARG_SPENDER, CLType::Key),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_TRANSFER_FROM,
+        vec![
+            Parameter::new(ARG_SOURCE, CLType::ByteArray(32)),
+            Parameter::new(ARG_TARGET, CLType::URef),
+            Parameter::new(ARG_AMOUNT, CLType::U512),
+            Parameter::new(ARG_ID, CLType::Option(Box::new(CLType::U64))),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::Unit),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_ALLOWANCE,
+        vec![
+            Parameter::new(ARG_PURSE, CLType::ByteArray(32)),
+            Parameter::new(ARG_SPENDER, CLType::Key),
+        ],
+        CLType::Result {
+            ok: Box::new(CLType::U512),
+            err: Box::new(CLType::U8),
+        },
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
+    let entry_point = EntryPoint::new(
+        METHOD_RECORD_TOTAL_SUPPLY,
+        vec![Parameter::new(ARG_ERA_ID, CLType::U64)],
+        CLType::Unit,
+        EntryPointAccess::Public,
+        EntryPointType::Contract,
+    );
+    entry_points.add_entry_point(entry_point);
+
     entry_points
 }
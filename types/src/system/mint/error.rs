@@ -154,6 +154,27 @@ pub enum Error {
     /// assert_eq!(22, Error::DisabledUnrestrictedTransfers as u8);
     DisabledUnrestrictedTransfers = 22,
 
+    /// Attempt to mint, transfer or burn tokens while the mint is paused.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(23, Error::MintPaused as u8);
+    /// ```
+    MintPaused = 23,
+
+    /// The purse holds less than the amount to burn.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(24, Error::InsufficientBalanceForBurn as u8);
+    /// ```
+    InsufficientBalanceForBurn = 24,
+
+    /// The spender's allowance on the purse is less than the amount to transfer.
+    /// ```
+    /// # use casper_types::system::mint::Error;
+    /// assert_eq!(25, Error::InsufficientAllowance as u8);
+    /// ```
+    InsufficientAllowance = 25,
+
     #[cfg(test)]
     #[doc(hidden)]
     Sentinel,
@@ -209,6 +230,11 @@ impl TryFrom<u8> for Error {
             d if d == Error::DisabledUnrestrictedTransfers as u8 => {
                 Ok(Error::DisabledUnrestrictedTransfers)
             }
+            d if d == Error::MintPaused as u8 => Ok(Error::MintPaused),
+            d if d == Error::InsufficientBalanceForBurn as u8 => {
+                Ok(Error::InsufficientBalanceForBurn)
+            }
+            d if d == Error::InsufficientAllowance as u8 => Ok(Error::InsufficientAllowance),
             _ => Err(TryFromU8ForError(())),
         }
     }
@@ -269,6 +295,11 @@ impl Display for Error {
             Error::DisabledUnrestrictedTransfers => {
                 formatter.write_str("Disabled unrestricted transfers")
             }
+            Error::MintPaused => formatter.write_str("Mint is paused"),
+            Error::InsufficientBalanceForBurn => {
+                formatter.write_str("Insufficient balance for burn")
+            }
+            Error::InsufficientAllowance => formatter.write_str("Insufficient allowance"),
             #[cfg(test)]
             Error::Sentinel => formatter.write_str("Sentinel error"),
         }
@@ -75,6 +75,11 @@ impl TestRng {
         TestRng { seed, rng }
     }
 
+    /// Returns the seed from which `self` was constructed.
+    pub fn seed(&self) -> Seed {
+        self.seed
+    }
+
     fn set_flag_or_panic() {
         THIS_THREAD_HAS_RNG.with(|flag| {
             if *flag.borrow() {
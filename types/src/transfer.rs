@@ -28,6 +28,8 @@ use crate::{
 pub const DEPLOY_HASH_LENGTH: usize = 32;
 /// The length of a transfer address.
 pub const TRANSFER_ADDR_LENGTH: usize = 32;
+/// The length of a transfer memo.
+pub const TRANSFER_MEMO_LENGTH: usize = 32;
 pub(super) const TRANSFER_ADDR_FORMATTED_STRING_PREFIX: &str = "transfer-";
 
 /// A newtype wrapping a <code>[u8; [DEPLOY_HASH_LENGTH]]</code> which is the raw bytes of the
@@ -175,6 +177,12 @@ impl Transfer {
     }
 }
 
+impl From<TransferV2> for Transfer {
+    fn from(transfer_v2: TransferV2) -> Self {
+        transfer_v2.transfer
+    }
+}
+
 impl FromBytes for Transfer {
     fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
         let (deploy_hash, rem) = FromBytes::from_bytes(bytes)?;
@@ -239,6 +247,171 @@ impl ToBytes for Transfer {
     }
 }
 
+/// A newtype wrapping a <code>[u8; [TRANSFER_MEMO_LENGTH]]</code> which is a user-defined tag of
+/// a transfer, such as a deposit identifier too large to fit in the `u64` transfer id.
+#[derive(Default, PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+pub struct TransferMemo([u8; TRANSFER_MEMO_LENGTH]);
+
+impl TransferMemo {
+    /// Constructs a new `TransferMemo` instance from the raw bytes.
+    pub const fn new(value: [u8; TRANSFER_MEMO_LENGTH]) -> TransferMemo {
+        TransferMemo(value)
+    }
+
+    /// Returns the raw bytes of the transfer memo as an array.
+    pub fn value(&self) -> [u8; TRANSFER_MEMO_LENGTH] {
+        self.0
+    }
+
+    /// Returns the raw bytes of the transfer memo as a `slice`.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchema for TransferMemo {
+    fn schema_name() -> String {
+        String::from("TransferMemo")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let schema = gen.subschema_for::<String>();
+        let mut schema_object = schema.into_object();
+        schema_object.metadata().description = Some("Hex-encoded transfer memo.".to_string());
+        schema_object.into()
+    }
+}
+
+impl Serialize for TransferMemo {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            base16::encode_lower(&self.0).serialize(serializer)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for TransferMemo {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let bytes = if deserializer.is_human_readable() {
+            let hex_string = String::deserialize(deserializer)?;
+            let vec_bytes =
+                checksummed_hex::decode(hex_string.as_bytes()).map_err(SerdeError::custom)?;
+            <[u8; TRANSFER_MEMO_LENGTH]>::try_from(vec_bytes.as_ref())
+                .map_err(SerdeError::custom)?
+        } else {
+            <[u8; TRANSFER_MEMO_LENGTH]>::deserialize(deserializer)?
+        };
+        Ok(TransferMemo(bytes))
+    }
+}
+
+impl Display for TransferMemo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", base16::encode_lower(&self.0))
+    }
+}
+
+impl Debug for TransferMemo {
+    fn fmt(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "TransferMemo({})", base16::encode_lower(&self.0))
+    }
+}
+
+impl CLTyped for TransferMemo {
+    fn cl_type() -> CLType {
+        CLType::ByteArray(TRANSFER_MEMO_LENGTH as u32)
+    }
+}
+
+impl ToBytes for TransferMemo {
+    #[inline(always)]
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        self.0.to_bytes()
+    }
+
+    #[inline(always)]
+    fn serialized_length(&self) -> usize {
+        self.0.serialized_length()
+    }
+
+    #[inline(always)]
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.0.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
+impl FromBytes for TransferMemo {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (bytes, remainder) = FromBytes::from_bytes(bytes)?;
+        Ok((TransferMemo::new(bytes), remainder))
+    }
+}
+
+impl AsRef<[u8]> for TransferMemo {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl Distribution<TransferMemo> for Standard {
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> TransferMemo {
+        TransferMemo::new(rng.gen())
+    }
+}
+
+/// Represents a transfer from one purse to another which is tagged with a [`TransferMemo`].
+///
+/// This is a newer version of the [`Transfer`] record. It is only written for transfers carrying a
+/// memo, so that records of transfers without one keep their original encoding.
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "datasize", derive(DataSize))]
+#[cfg_attr(feature = "json-schema", derive(JsonSchema))]
+#[serde(deny_unknown_fields)]
+pub struct TransferV2 {
+    /// The transfer
+    pub transfer: Transfer,
+    /// User-defined memo
+    pub memo: TransferMemo,
+}
+
+impl TransferV2 {
+    /// Creates a [`TransferV2`].
+    pub fn new(transfer: Transfer, memo: TransferMemo) -> Self {
+        TransferV2 { transfer, memo }
+    }
+}
+
+impl FromBytes for TransferV2 {
+    fn from_bytes(bytes: &[u8]) -> Result<(Self, &[u8]), bytesrepr::Error> {
+        let (transfer, rem) = Transfer::from_bytes(bytes)?;
+        let (memo, rem) = TransferMemo::from_bytes(rem)?;
+        Ok((TransferV2 { transfer, memo }, rem))
+    }
+}
+
+impl ToBytes for TransferV2 {
+    fn to_bytes(&self) -> Result<Vec<u8>, bytesrepr::Error> {
+        let mut result = bytesrepr::allocate_buffer(self)?;
+        self.write_bytes(&mut result)?;
+        Ok(result)
+    }
+
+    fn serialized_length(&self) -> usize {
+        self.transfer.serialized_length() + self.memo.serialized_length()
+    }
+
+    fn write_bytes(&self, writer: &mut Vec<u8>) -> Result<(), bytesrepr::Error> {
+        self.transfer.write_bytes(writer)?;
+        self.memo.write_bytes(writer)?;
+        Ok(())
+    }
+}
+
 /// Error returned when decoding a `TransferAddr` from a formatted string.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -413,9 +586,10 @@ impl Distribution<TransferAddr> for Standard {
 pub mod gens {
     use proptest::prelude::{prop::option, Arbitrary, Strategy};
 
+    use super::{TransferMemo, TransferV2};
     use crate::{
         deploy_info::gens::{account_hash_arb, deploy_hash_arb},
-        gens::{u512_arb, uref_arb},
+        gens::{u512_arb, u8_slice_32, uref_arb},
         Transfer,
     };
 
@@ -444,6 +618,12 @@ pub mod gens {
                 }
             })
     }
+
+    /// Creates an arbitrary [`TransferV2`]
+    pub fn transfer_v2_arb() -> impl Strategy<Value = TransferV2> {
+        (transfer_arb(), u8_slice_32())
+            .prop_map(|(transfer, memo)| TransferV2::new(transfer, TransferMemo::new(memo)))
+    }
 }
 
 #[cfg(test)]
@@ -459,6 +639,11 @@ mod tests {
         fn test_serialization_roundtrip(transfer in gens::transfer_arb()) {
             bytesrepr::test_serialization_roundtrip(&transfer)
         }
+
+        #[test]
+        fn test_transfer_v2_serialization_roundtrip(transfer_v2 in gens::transfer_v2_arb()) {
+            bytesrepr::test_serialization_roundtrip(&transfer_v2)
+        }
     }
 
     #[test]
@@ -496,6 +681,15 @@ mod tests {
         assert_eq!(transfer_address, decoded);
     }
 
+    #[test]
+    fn transfer_memo_json_roundtrip() {
+        let transfer_memo = TransferMemo([171; 32]);
+        let json_string = serde_json::to_string_pretty(&transfer_memo).unwrap();
+        assert_eq!(json_string, format!("\"{}\"", "ab".repeat(32)));
+        let decoded = serde_json::from_str(&json_string).unwrap();
+        assert_eq!(transfer_memo, decoded);
+    }
+
     #[test]
     fn transfer_addr_json_roundtrip() {
         let transfer_address = TransferAddr([255; 32]);
@@ -11,8 +11,8 @@ use core::{
 
 use num_integer::Integer;
 use num_traits::{
-    AsPrimitive, Bounded, CheckedAdd, CheckedMul, CheckedSub, Num, One, Unsigned, WrappingAdd,
-    WrappingSub, Zero,
+    AsPrimitive, Bounded, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, Num, One, Unsigned,
+    WrappingAdd, WrappingSub, Zero,
 };
 use rand::{
     distributions::{Distribution, Standard},
@@ -273,6 +273,12 @@ macro_rules! impl_traits_for_uint {
             }
         }
 
+        impl CheckedDiv for $type {
+            fn checked_div(&self, v: &$type) -> Option<$type> {
+                $type::checked_div(*self, *v)
+            }
+        }
+
         impl Integer for $type {
             /// Unsigned integer division. Returns the same result as `div` (`/`).
             #[inline]
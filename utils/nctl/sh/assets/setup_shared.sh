@@ -443,8 +443,15 @@ function setup_asset_global_state_toml_for_node() {
     local TARGET_PROTOCOL_VERSION=${2}
     local PATH_TO_NET="$(get_path_to_net)"
     local STORAGE_PATH="$PATH_TO_NET/nodes/node-$IDX/storage"
+    local VERSIONED_STORAGE_PATH
 
-    if [ -f "$STORAGE_PATH/$(get_chain_name)/data.lmdb" ]; then
+    # Storage is kept per protocol version under the chain's directory; pick the latest one.
+    VERSIONED_STORAGE_PATH=$(find "$STORAGE_PATH/$(get_chain_name)" -mindepth 1 -maxdepth 1 -type d 2>/dev/null | sort -V | tail -n 1)
+
+    if [ -n "$VERSIONED_STORAGE_PATH" ] && [ -f "$VERSIONED_STORAGE_PATH/data.lmdb" ]; then
+        GLOBAL_STATE_OUTPUT=$("$NCTL_CASPER_HOME"/target/"$NCTL_COMPILE_TARGET"/global-state-update-gen \
+                migrate-into-system-contract-registry -d "$VERSIONED_STORAGE_PATH")
+    elif [ -f "$STORAGE_PATH/$(get_chain_name)/data.lmdb" ]; then
         GLOBAL_STATE_OUTPUT=$("$NCTL_CASPER_HOME"/target/"$NCTL_COMPILE_TARGET"/global-state-update-gen \
                 migrate-into-system-contract-registry -d "$STORAGE_PATH"/"$(get_chain_name)")
     else